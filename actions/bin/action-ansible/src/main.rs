@@ -0,0 +1,339 @@
+use ansible::{error::AnsibleError, AnsibleConfig, AnsibleExecutor, PlaybookCommand, Recap};
+use config::{collect_errors, ConfigError, MainConfig};
+use executer::Metrics;
+use processor::{MaskerEqual, MaskerRegex, MaskerRuntime, ProcessorCollection, ProcessorItem};
+
+use util::{
+    exit_code, write_step_summary, ActionResult, Failure, LogContext, LogFile, LoggerBuilder,
+    Outputs, RunSummary,
+};
+
+#[tokio::main]
+async fn main() -> std::process::ExitCode {
+    exit_code(run().await, classify)
+}
+
+async fn run() -> ActionResult {
+    let main_config = MainConfig::new();
+    let ansible_config = AnsibleConfig::new();
+
+    if std::env::args().any(|arg| arg == "--print-inputs") {
+        let inputs = main_config.inputs().extend(ansible_config.inputs());
+        print!(
+            "{}{}",
+            inputs.render_action_inputs(),
+            inputs.render_action_env()
+        );
+        return Ok(None);
+    }
+
+    let level = main_config.get_log_level().unwrap_or("info".to_string());
+    let format = main_config
+        .get_log_format()
+        .unwrap_or(config::DEFAULT_LOG_FORMAT.to_string());
+    let log_file = main_config.get_log_file().ok().flatten().map(|path| {
+        LogFile::new(path)
+            .with_level(
+                &main_config
+                    .get_log_file_level()
+                    .unwrap_or(config::DEFAULT_LOG_FILE_LEVEL.to_string()),
+            )
+            .with_max_bytes(
+                main_config
+                    .get_log_file_max_bytes()
+                    .unwrap_or(config::DEFAULT_LOG_FILE_MAX_BYTES),
+            )
+    });
+    let (logger, _) = LoggerBuilder::new()
+        .with_level(&level)
+        .with_format(&format)
+        .with_file(log_file)
+        .build();
+
+    if let Err(e) = collect_errors(vec![
+        main_config.validate_all(),
+        ansible_config.validate_all(),
+    ]) {
+        let errors = e.into_errors();
+        for error in &errors {
+            slog::error!(logger, "Invalid configuration"; "error" => error.to_string());
+        }
+        return Err(ConfigError::Multiple(errors).into());
+    }
+
+    // Playbooks connect over SSH; a cloud provider is only used for its
+    // credentials, e.g. by dynamic inventories, and masked when present.
+    let provider = provider::auto_detect().ok();
+    match &provider {
+        Some(provider) => slog::info!(
+            logger,
+            "Initialize action with provider {}",
+            provider.name()
+        ),
+        None => slog::info!(logger, "Initialize action without a cloud provider"),
+    }
+
+    let cwd = match main_config.get_working_dir() {
+        Ok(v) => {
+            slog::info!(logger, "Workdir: {:?}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Work directory not set"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let cmd = match ansible_config.get_cmd() {
+        Ok(v) => {
+            slog::info!(logger, "Action command: {}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Invalid ansible command"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let mask = match main_config.get_mask() {
+        Ok(v) => {
+            slog::debug!(logger, "mask string: {}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Mask string not set"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let bin = match ansible_config.get_bin() {
+        Ok(v) => {
+            slog::debug!(logger, "ansible-playbook binary file: {:?}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Invalid ansible-playbook bin filepath"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let masker_provider_output = match MaskerRegex::new(
+        provider
+            .as_ref()
+            .map(|p| p.get_predefined_masked_objects())
+            .unwrap_or_default(),
+        &mask,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to initialize maskers for provider"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let masker_provider_credentials = MaskerEqual::new(
+        provider.as_ref().map(|p| p.values()).unwrap_or_default(),
+        &mask,
+    );
+
+    let report = main_config.report().extend(ansible_config.report());
+    let masker_config = MaskerEqual::new(report.sensitive_values(), &mask);
+    let masker_runtime = MaskerRuntime::new(&mask);
+
+    let processor = ProcessorCollection::new(vec![
+        ProcessorItem::Regex(masker_provider_output),
+        ProcessorItem::Equal(masker_provider_credentials),
+        ProcessorItem::Equal(masker_config),
+        ProcessorItem::Runtime(masker_runtime.clone()),
+    ]);
+    let logger = LogContext::from_env("ansible").logger(&util::masked(&logger, processor.clone()));
+    for line in report.render(&processor, &mask) {
+        slog::debug!(logger, "Config: {}", line);
+    }
+    slog::info!(logger, "Action was initialized");
+
+    let playbooks = match ansible_config.get_playbooks() {
+        Ok(v) => {
+            slog::info!(
+                logger,
+                "Playbooks: {}",
+                v.iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Playbooks not set"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    if playbooks.is_empty() {
+        let e = ConfigError::RequiredValueMissing(ansible::ENV_ANSIBLE_PLAYBOOKS.to_string());
+        slog::error!(logger, "Playbooks not set"; "error" => e.to_string());
+        return Err(e.into());
+    }
+
+    let inventory = match ansible_config.get_inventory() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get inventory"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let limit = match ansible_config.get_limit() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get host limit"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let tags = match ansible_config.get_tags() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get tags"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let skip_tags = match ansible_config.get_skip_tags() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get skipped tags"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let diff = match ansible_config.get_diff() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get diff flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let verbosity = match ansible_config.get_verbosity() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get verbosity"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let mut extra_vars = match ansible_config.get_extra_vars() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get extra vars"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let extra_vars_sensitive = match ansible_config.get_extra_vars_sensitive() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get sensitive extra vars"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let vault_password = match ansible_config.get_vault_password() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get vault password file"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let private_key = match ansible_config.get_private_key() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get private key file"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    extra_vars.extend(extra_vars_sensitive);
+
+    let mut command = PlaybookCommand::new(cmd, playbooks);
+    command.inventory = inventory;
+    command.limit = limit;
+    command.tags = tags;
+    command.skip_tags = skip_tags;
+    command.diff = diff;
+    command.verbosity = verbosity;
+    command.extra_vars = extra_vars;
+    if let Some(inventory) = &command.inventory {
+        slog::info!(logger, "Inventory: {}", inventory);
+    }
+
+    let metrics = Metrics::new();
+    let executor = AnsibleExecutor::new(processor.clone(), bin)
+        .with_working_dir(cwd)
+        .with_vault_password(vault_password)
+        .with_private_key(private_key)
+        .with_secrets(masker_runtime)
+        .with_metrics(metrics.clone());
+
+    let mut outputs = Outputs::new().with_processor(processor.clone());
+    let outcome = executor.execute(&command).await;
+    if let Ok(result) = &outcome {
+        if !result.recap.is_empty() {
+            report_recap(&logger, &result.recap, &mut outputs);
+        }
+    }
+    let outcome = outcome.map(|result| result.code);
+
+    RunSummary::new(format!("ansible {}", cmd), metrics)
+        .with_outcome(&outcome)
+        .with_workflow_run()
+        .with_processor(processor)
+        .report(&logger);
+    match outputs.write() {
+        Ok(true) => slog::debug!(logger, "Step outputs were written"; "count" => outputs.len()),
+        Ok(false) => {}
+        Err(e) => slog::warn!(logger, "Failed to write step outputs"; "error" => e.to_string()),
+    }
+    let code = match outcome {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to run ansible {}", cmd; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    if code == 0 {
+        slog::info!(logger, "Action {} was finished successfully", cmd);
+        return Ok(None);
+    }
+    let failure = Failure::Tool;
+    slog::error!(logger, "Action {} failed with status: {}", cmd, code;
+        "failure" => failure.as_str(), "exit_code" => failure.code());
+    Ok(Some(failure))
+}
+
+/// Classifies the error the action stopped with.
+fn classify(error: &(dyn std::error::Error + 'static)) -> Failure {
+    match error.downcast_ref::<AnsibleError>() {
+        Some(AnsibleError::ExecuterError(e)) => Failure::of_executer(e),
+        Some(_) => Failure::Tool,
+        None => Failure::of_shared(error).unwrap_or(Failure::Tool),
+    }
+}
+
+/// Logs the recap of each host, sets the totals as step outputs and adds the recap to the job summary.
+fn report_recap(logger: &slog::Logger, recap: &Recap, outputs: &mut Outputs) {
+    for host in &recap.hosts {
+        let line = format!(
+            "Recap {}: ok={} changed={} unreachable={} failed={} skipped={}",
+            host.host, host.ok, host.changed, host.unreachable, host.failed, host.skipped
+        );
+        if host.is_failed() {
+            slog::error!(logger, "{}", line);
+        } else {
+            slog::info!(logger, "{}", line);
+        }
+    }
+    let totals = recap.totals();
+    outputs.set("ok", totals.ok);
+    outputs.set("changed", totals.changed);
+    outputs.set("unreachable", totals.unreachable);
+    outputs.set("failed", totals.failed);
+    outputs.set("failed_hosts", recap.failed_hosts().join(","));
+    outputs.set("recap", recap.to_json());
+    match write_step_summary(&recap.markdown()) {
+        Ok(true) => slog::debug!(logger, "Recap was added to the job summary"),
+        Ok(false) => {}
+        Err(e) => slog::warn!(logger, "Failed to write job summary"; "error" => e.to_string()),
+    }
+}
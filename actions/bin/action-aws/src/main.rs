@@ -1,17 +1,96 @@
-use aws::{executor::AwsExecutor, AwsConfig, AwsEnv, CommandChain};
-use config::MainConfig;
-use processor::{MaskerEqual, MaskerRegex, ProcessorCollection, ProcessorItem};
+use aws::{
+    error::AwsError,
+    executor::AwsExecutor,
+    progress::SyncSummary,
+    response::Invalidation,
+    step::{first_failure, StepPolicy, StepResult, StepStatus},
+    AwsAction, AwsCommand, AwsConfig, AwsEnv, Backend, CommandChain, GlobalOptions, LambdaTarget,
+    Route53Record, Sdk,
+};
+use config::{collect_errors, validate_groups, ConfigError, ConfigGroup, DryRunConfig, MainConfig};
+use executer::{Annotations, Metrics, RetryPolicy};
+use processor::{
+    MaskerEqual, MaskerRegex, MaskerRuntime, Processor, ProcessorCollection, ProcessorItem,
+};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use provider::auto_detect;
-use util::init_logger;
+use util::{
+    exit_code, write_step_summary, ActionResult, Failure, LogContext, LogFile, LoggerBuilder,
+    Outputs, RunSummary,
+};
+
+/// Number of synced files between two progress log lines.
+const SYNC_PROGRESS_EVERY: u64 = 100;
+
+/// Exit codes the AWS CLI documents for its own failure classes.
+const AWS_CLI_INVALID_PARAMETERS: i32 = 252;
+const AWS_CLI_INVALID_ENVIRONMENT: i32 = 253;
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() -> std::process::ExitCode {
+    exit_code(run().await, classify)
+}
+
+async fn run() -> ActionResult {
     let main_config = MainConfig::new();
+    let dry_run_config = DryRunConfig::new();
     let aws_config = AwsConfig::new();
 
+    if std::env::args().any(|arg| arg == "--print-inputs") {
+        let inputs = main_config
+            .inputs()
+            .extend(dry_run_config.inputs())
+            .extend(aws_config.inputs());
+        print!(
+            "{}{}",
+            inputs.render_action_inputs(),
+            inputs.render_action_env()
+        );
+        return Ok(None);
+    }
+
     let level = main_config.get_log_level().unwrap_or("info".to_string());
-    let logger = init_logger(&level);
+    let format = main_config
+        .get_log_format()
+        .unwrap_or(config::DEFAULT_LOG_FORMAT.to_string());
+    let log_file = main_config.get_log_file().ok().flatten().map(|path| {
+        LogFile::new(path)
+            .with_level(
+                &main_config
+                    .get_log_file_level()
+                    .unwrap_or(config::DEFAULT_LOG_FILE_LEVEL.to_string()),
+            )
+            .with_max_bytes(
+                main_config
+                    .get_log_file_max_bytes()
+                    .unwrap_or(config::DEFAULT_LOG_FILE_MAX_BYTES),
+            )
+    });
+    let (logger, _) = LoggerBuilder::new()
+        .with_level(&level)
+        .with_format(&format)
+        .with_file(log_file)
+        .build();
+
+    let groups = [ConfigGroup::mutually_exclusive(&[
+        aws::constants::ENV_AWS_LAMBDA_ZIP,
+        aws::constants::ENV_AWS_LAMBDA_IMAGE,
+    ])];
+    if let Err(e) = collect_errors(vec![
+        main_config.validate_all(),
+        dry_run_config.validate_all(),
+        aws_config.validate_all(),
+        validate_groups(&groups),
+    ]) {
+        let errors = e.into_errors();
+        for error in &errors {
+            slog::error!(logger, "Invalid configuration"; "error" => error.to_string());
+        }
+        return Err(ConfigError::Multiple(errors).into());
+    }
 
     let provider = match auto_detect() {
         Ok(v) => {
@@ -35,7 +114,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
-    let cmd = match aws_config.get_cmd() {
+    let cmds = match aws_config.get_cmd() {
         Ok(v) => {
             slog::info!(logger, "Action command: {}", v);
             v
@@ -83,117 +162,1517 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let masker_provider_credentials = MaskerEqual::new(provider.values(), &mask);
     let masker_aws_envs = MaskerEqual::new(envs.values(), &mask);
 
+    let report = main_config
+        .report()
+        .extend(dry_run_config.report())
+        .extend(aws_config.report());
+    let masker_config = MaskerEqual::new(report.sensitive_values(), &mask);
+    let masker_runtime = MaskerRuntime::new(&mask);
+
     let processor = ProcessorCollection::new(vec![
         ProcessorItem::Regex(masker_provider_output),
         ProcessorItem::Equal(masker_provider_credentials),
         ProcessorItem::Equal(masker_aws_envs),
+        ProcessorItem::Equal(masker_config),
+        ProcessorItem::Runtime(masker_runtime.clone()),
     ]);
+    let logger = LogContext::from_env("aws").logger(&util::masked(&logger, processor.clone()));
+    for line in report.render(&processor, &mask) {
+        slog::debug!(logger, "Config: {}", line);
+    }
     slog::info!(logger, "Action was initialized");
 
-    let executor = AwsExecutor::new(processor, bin);
+    let docker = match aws_config.get_docker_bin() {
+        Ok(v) => {
+            slog::debug!(logger, "docker binary file: {:?}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Invalid docker bin filepath"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
 
-    let result = match cmd.as_str() {
-        "s3_sync" => {
-            let destination = match aws_config.get_destination() {
-                Ok(v) => {
-                    //slog::info!(logger, "S3 destination: {:?}", v);
-                    v
-                }
+    let sam = match aws_config.get_sam_bin() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Invalid sam bin filepath"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let cdk = match aws_config.get_cdk_bin() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Invalid cdk bin filepath"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let retry_attempts = match aws_config.get_retry_attempts() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get retry attempts"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let retry_delay = match aws_config.get_retry_delay() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get retry delay"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let dry_run = match (
+        dry_run_config.get_dry_run(),
+        aws_config.get_global_dry_run(),
+    ) {
+        (Ok(all), Ok(aws)) => {
+            if all || aws {
+                slog::info!(logger, "Dry run: commands are printed, not executed");
+            }
+            all || aws
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            slog::error!(logger, "Failed to get dry-run option"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let mut globals = GlobalOptions::new();
+    match aws_config.get_region() {
+        Ok(Some(v)) => globals = globals.with_region(v),
+        Ok(None) => {}
+        Err(e) => {
+            slog::error!(logger, "Failed to get region"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    }
+    match aws_config.get_profile() {
+        Ok(Some(v)) => globals = globals.with_profile(v),
+        Ok(None) => {}
+        Err(e) => {
+            slog::error!(logger, "Failed to get profile"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    }
+    match aws_config.get_endpoint_url() {
+        Ok(Some(v)) => globals = globals.with_endpoint_url(v),
+        Ok(None) => {}
+        Err(e) => {
+            slog::error!(logger, "Failed to get endpoint URL"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    }
+
+    let tags = match aws_config.get_tags() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get resource tags"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let tag_resources = match aws_config.get_tag_resources() {
+        Ok(v) => v.unwrap_or_default(),
+        Err(e) => {
+            slog::error!(logger, "Failed to get tagged resources"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    if !tags.is_empty() && !tag_resources.is_empty() {
+        let rendered: Vec<String> = tags
+            .iter()
+            .map(|(k, v)| processor.process(&format!("{}={}", k, v)))
+            .collect();
+        slog::info!(logger, "Resource tags: {}", rendered.join(", ");
+            "resources" => tag_resources.len());
+    }
+
+    let progress_summary = Mutex::new(SyncSummary::new());
+    let progress_logger = logger.clone();
+
+    let backend = match aws_config.get_backend() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get backend"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let metrics = Metrics::new();
+    let annotations = Annotations::new();
+    let executor = AwsExecutor::new(processor.clone(), bin)
+        .with_metrics(metrics.clone())
+        .with_annotations(annotations.clone())
+        .with_docker_bin(docker)
+        .with_sam_bin(sam)
+        .with_cdk_bin(cdk)
+        .with_secrets(masker_runtime.clone())
+        .with_retry(RetryPolicy::new(retry_attempts, retry_delay))
+        .with_global_options(globals.clone())
+        .with_dry_run(dry_run)
+        .with_progress(move |event| {
+            let mut summary = progress_summary.lock().unwrap_or_else(|e| e.into_inner());
+            let before = summary.transferred + summary.deleted;
+            summary.record(event);
+            let after = summary.transferred + summary.deleted;
+            if after != before && after.is_multiple_of(SYNC_PROGRESS_EVERY) {
+                slog::info!(progress_logger, "Sync progress";
+                    "transferred" => summary.transferred,
+                    "deleted" => summary.deleted,
+                    "bytes" => summary.bytes);
+            }
+        });
+    let executor = match backend {
+        Backend::Cli => executor,
+        Backend::Sdk => {
+            slog::info!(
+                logger,
+                "S3 syncs, invalidations and Lambda code updates use the AWS SDK"
+            );
+            executor.with_sdk(Sdk::load(&globals).await)
+        }
+    };
+
+    let mut outputs = Outputs::new().with_processor(processor.clone());
+    let mut results: Vec<StepResult> = Vec::new();
+    // Errors of the run end up in the report as well.
+    let run: Result<Option<(i32, Failure)>, Box<dyn std::error::Error>> = async {
+        let role_arn = match aws_config.get_role_arn() {
+            Ok(v) => v,
+            Err(e) => {
+                slog::error!(logger, "Failed to get role ARN"; "error" => e.to_string());
+                return Err(e.into());
+            }
+        };
+        if let Some(role_arn) = role_arn {
+            let session_name = match aws_config.get_role_session_name() {
+                Ok(v) => v,
                 Err(e) => {
-                    slog::error!(logger, "S3 destination not set"; "error" => e.to_string());
+                    slog::error!(logger, "Failed to get role session name"; "error" => e.to_string());
                     return Err(e.into());
                 }
             };
-
-            let exclude = match aws_config.get_exclude() {
-                Ok(v) => {
-                    if let Some(patterns) = &v {
-                        slog::info!(logger, "Exclude patterns: {:?}", patterns);
-                    }
-                    v
-                }
+            let duration = match aws_config.get_role_duration() {
+                Ok(v) => v,
                 Err(e) => {
-                    slog::error!(logger, "Failed to get exclude patterns"; "error" => e.to_string());
+                    slog::error!(logger, "Failed to get role duration"; "error" => e.to_string());
                     return Err(e.into());
                 }
             };
 
-            let include = match aws_config.get_include() {
-                Ok(v) => {
-                    if let Some(patterns) = &v {
-                        slog::info!(logger, "Include patterns: {:?}", patterns);
+            slog::info!(logger, "Assuming role {}", role_arn);
+            let status = executor
+                .execute(AwsCommand::AssumeRole {
+                    role_arn,
+                    session_name,
+                    duration,
+                })
+                .await?;
+            if status != 0 {
+                slog::error!(logger, "Failed to assume role with status: {}", status);
+                return Ok(Some((status, Failure::Provider)));
+            }
+        }
+
+        let secrets = match aws_config.get_secrets() {
+            Ok(v) => v,
+            Err(e) => {
+                slog::error!(logger, "Failed to get secrets"; "error" => e.to_string());
+                return Err(e.into());
+            }
+        };
+        if !secrets.is_empty() {
+            slog::info!(logger, "Fetching secrets"; "count" => secrets.len());
+            let status = executor.execute_chain(secrets).await?;
+            if status != 0 {
+                slog::error!(logger, "Failed to fetch secrets with status: {}", status);
+                return Ok(Some((status, status_failure(status))));
+            }
+        }
+
+        for (index, cmd) in cmds.iter().enumerate() {
+            if first_failure(&results).is_some() {
+                results.push(command_result(
+                    index,
+                    cmd,
+                    StepStatus::Skipped,
+                    Duration::ZERO,
+                ));
+                continue;
+            }
+            let _group = (cmds.len() > 1).then(|| {
+                util::group(
+                    &logger,
+                    &format!("[command {}/{}] {}", index + 1, cmds.len(), cmd),
+                )
+            });
+            let started = Instant::now();
+            let result = match cmd {
+                AwsAction::S3Sync => {
+                    let source = match aws_config.get_source() {
+                        Ok(v) => {
+                            if !v.as_os_str().is_empty() {
+                                slog::info!(logger, "S3 source: {:?}", v);
+                            }
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get S3 source"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let destination = match aws_config.get_destination() {
+                        Ok(v) => {
+                            //slog::info!(logger, "S3 destination: {:?}", v);
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "S3 destination not set"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let exclude = match aws_config.get_exclude() {
+                        Ok(v) => {
+                            if let Some(patterns) = &v {
+                                slog::info!(logger, "Exclude patterns: {:?}", patterns);
+                            }
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get exclude patterns"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let include = match aws_config.get_include() {
+                        Ok(v) => {
+                            if let Some(patterns) = &v {
+                                slog::info!(logger, "Include patterns: {:?}", patterns);
+                            }
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get include patterns"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let delete = match aws_config.get_delete() {
+                        Ok(v) => {
+                            if v {
+                                slog::info!(logger, "Delete option enabled");
+                            }
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get delete option"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let dry_run = match aws_config.get_dry_run() {
+                        Ok(v) => {
+                            if v {
+                                slog::info!(logger, "Dry run mode enabled");
+                            }
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get dry run option"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let force = match aws_config.get_force() {
+                        Ok(v) => {
+                            if v {
+                                slog::info!(logger, "Force mode enabled");
+                            }
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get force option"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let cache_control = match aws_config.get_cache_control() {
+                        Ok(v) => {
+                            if let Some(cache_control) = &v {
+                                slog::info!(logger, "Cache-Control: {}", cache_control);
+                            }
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get cache control"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let content_type = match aws_config.get_content_type() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get content type"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let metadata_directive = match aws_config.get_metadata_directive() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get metadata directive"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let storage_class = match aws_config.get_storage_class() {
+                        Ok(v) => {
+                            if let Some(storage_class) = &v {
+                                slog::info!(logger, "Storage class: {}", storage_class);
+                            }
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get storage class"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let acl = match aws_config.get_acl() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get ACL"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let sse = match aws_config.get_sse() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get server-side encryption"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let overrides = match aws_config.get_cache_overrides() {
+                        Ok(v) => {
+                            for o in &v {
+                                slog::info!(logger, "Cache override: {}", o.pattern);
+                            }
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get cache overrides"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let mut chain = CommandChain::new(cwd.clone())
+                        .with_vars(envs.as_map())
+                        .with_destination(destination)
+                        .with_exclude(exclude)
+                        .with_include(include)
+                        .with_delete(delete)
+                        .with_dry_run(dry_run)
+                        .with_force(force)
+                        .with_cache_control(cache_control)
+                        .with_content_type(content_type)
+                        .with_metadata_directive(metadata_directive)
+                        .with_storage_class(storage_class)
+                        .with_acl(acl)
+                        .with_sse(sse)
+                        .with_overrides(overrides)
+                        .with_tags(tags.clone())
+                        .with_tagged_resources(tag_resources.clone());
+                    if !source.as_os_str().is_empty() {
+                        chain = chain.with_source(source);
                     }
-                    v
+
+                    slog::info!(logger, "Starting AWS S3 sync command");
+                    executor
+                        .execute_sync(chain.sync_chain())
+                        .await
+                        .map(|result| {
+                            slog::info!(logger, "Sync summary";
+                            "transferred" => result.summary.transferred,
+                            "deleted" => result.summary.deleted,
+                            "bytes" => result.summary.bytes);
+                            outputs.set("synced_files", result.summary.transferred);
+                            outputs.set("deleted_files", result.summary.deleted);
+                            outputs.set("synced_bytes", result.summary.bytes);
+                            result.code
+                        })
                 }
-                Err(e) => {
-                    slog::error!(logger, "Failed to get include patterns"; "error" => e.to_string());
-                    return Err(e.into());
+                AwsAction::StaticSite => {
+                    let source = match aws_config.get_source() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get S3 source"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let destination = match aws_config.get_destination() {
+                        Ok(v) if !v.as_os_str().is_empty() => v,
+                        Ok(_) => {
+                            slog::error!(logger, "S3 destination not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_S3_DESTINATION.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get S3 destination"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let exclude = match aws_config.get_exclude() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get exclude patterns"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let delete = match aws_config.get_delete() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get delete option"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let asset_cache_control = match aws_config.get_asset_cache_control() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get asset Cache-Control"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let html_cache_control = match aws_config.get_html_cache_control() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get HTML Cache-Control"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let distributions = match aws_config.get_cloudfront_distributions() {
+                        Ok(v) => v.unwrap_or_default(),
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get CloudFront distributions"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let paths = match aws_config.get_cloudfront_paths() {
+                        Ok(v) => v.unwrap_or_else(|| {
+                            vec![aws::constants::DEFAULT_CLOUDFRONT_PATHS.to_string()]
+                        }),
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get invalidation paths"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let mut chain = CommandChain::new(cwd.clone())
+                        .with_vars(envs.as_map())
+                        .with_destination(destination)
+                        .with_exclude(exclude)
+                        .with_delete(delete)
+                        .with_asset_cache_control(asset_cache_control)
+                        .with_html_cache_control(html_cache_control)
+                        .with_cloudfront_distributions(distributions)
+                        .with_cloudfront_paths(paths)
+                        .with_tags(tags.clone())
+                        .with_tagged_resources(tag_resources.clone());
+                    if !source.as_os_str().is_empty() {
+                        chain = chain.with_source(source);
+                    }
+
+                    let steps = chain.static_site_steps();
+                    slog::info!(logger, "Starting static site deploy";
+                        "steps" => steps.len(),
+                        "delete" => delete);
+                    let results = executor.execute_steps(steps).await;
+                    set_invalidation_ids(
+                        &mut outputs,
+                        results.iter().filter_map(|r| r.invalidation.as_ref()),
+                    );
+                    Ok(report_steps(&logger, results))
                 }
-            };
+                AwsAction::S3Presign => {
+                    let key = match aws_config.get_presign_key() {
+                        Ok(v) if !v.is_empty() => {
+                            slog::info!(logger, "Presigned object: {}", v);
+                            v
+                        }
+                        Ok(_) => {
+                            slog::error!(logger, "Presigned object not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_S3_PRESIGN_KEY.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get presigned object"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let expires_in = match aws_config.get_presign_expires() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get presigned URL lifetime"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let mask_signature = match aws_config.get_presign_mask() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get presigned URL mask option"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    executor
+                        .presign(key, expires_in, mask_signature)
+                        .await
+                        .map(|presigned| {
+                            slog::info!(logger, "Presigned URL";
+                                "url" => masker_runtime.process(&presigned.url),
+                                "expires_in" => presigned.expires_in);
+                            // Written through the maskers, so a masked signature stays masked.
+                            outputs.set("presigned_url", &presigned.url);
+                            outputs.set("presigned_expires_in", presigned.expires_in);
+                            0
+                        })
+                }
+                AwsAction::S3Cp => {
+                    let source = match aws_config.get_copy_source() {
+                        Ok(v) if !v.as_os_str().is_empty() => v,
+                        Ok(_) => {
+                            slog::error!(logger, "S3 copy source not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_S3_CP_SOURCE.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get S3 copy source"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
 
-            let delete = match aws_config.get_delete() {
-                Ok(v) => {
-                    if v {
-                        slog::info!(logger, "Delete option enabled");
+                    let destination = match aws_config.get_destination() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "S3 destination not set"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let content_type = match aws_config.get_content_type() {
+                        Ok(v) => {
+                            if let Some(content_type) = &v {
+                                slog::info!(logger, "Content type: {}", content_type);
+                            }
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get content type"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let acl = match aws_config.get_acl() {
+                        Ok(v) => {
+                            if let Some(acl) = &v {
+                                slog::info!(logger, "ACL: {}", acl);
+                            }
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get ACL"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let metadata = match aws_config.get_metadata() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get metadata"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let chain = CommandChain::new(cwd.clone())
+                        .with_vars(envs.as_map())
+                        .with_copy_source(source)
+                        .with_destination(destination)
+                        .with_content_type(content_type)
+                        .with_acl(acl)
+                        .with_metadata(metadata)
+                        .with_tags(tags.clone())
+                        .with_tagged_resources(tag_resources.clone());
+
+                    slog::info!(logger, "Starting AWS S3 copy command");
+                    executor.execute_chain(chain.copy_chain()).await
+                }
+                AwsAction::LambdaUpdate => {
+                    let mut targets = match aws_config.get_lambda_batch() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Lambda batch"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let manifest = match aws_config.get_lambda_manifest() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Lambda manifest"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+                    if !manifest.as_os_str().is_empty() {
+                        slog::info!(logger, "Lambda manifest: {:?}", manifest);
+                        let content = match std::fs::read_to_string(&manifest) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                slog::error!(logger, "Failed to read Lambda manifest"; "error" => e.to_string());
+                                return Err(e.into());
+                            }
+                        };
+                        match LambdaTarget::parse_manifest(&content) {
+                            Ok(v) => targets.extend(v),
+                            Err(e) => {
+                                slog::error!(logger, "Invalid Lambda manifest"; "error" => e.to_string());
+                                return Err(e.into());
+                            }
+                        }
+                    }
+
+                    let fail_fast = match aws_config.get_lambda_fail_fast() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Lambda fail-fast option"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let function_name = match aws_config.get_lambda_function() {
+                        Ok(v) if !targets.is_empty() => v,
+                        Ok(v) if !v.is_empty() => {
+                            slog::info!(logger, "Lambda function: {}", v);
+                            v
+                        }
+                        Ok(_) => {
+                            slog::error!(logger, "Lambda function not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_LAMBDA_FUNCTION.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Lambda function"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let zip = match aws_config.get_lambda_zip() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Lambda zip"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let image = match aws_config.get_lambda_image() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Lambda image"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let alias = match aws_config.get_lambda_alias() {
+                        Ok(v) => {
+                            if let Some(alias) = &v {
+                                slog::info!(logger, "Lambda alias: {}", alias);
+                            }
+                            v
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Lambda alias"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let publish = match aws_config.get_lambda_publish() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Lambda publish option"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    if !targets.is_empty() {
+                        let chain = CommandChain::new(cwd.clone())
+                            .with_vars(envs.as_map())
+                            .with_lambda_alias(alias)
+                            .with_lambda_publish(publish)
+                            .with_lambda_targets(targets)
+                            .with_tags(tags.clone())
+                            .with_tagged_resources(tag_resources.clone());
+                        let chains = chain.lambda_batch_chain();
+
+                        slog::info!(logger, "Starting Lambda batch update";
+                            "functions" => chains.len(),
+                            "fail_fast" => fail_fast);
+                        let mut status = 0;
+                        let mut versions = Vec::new();
+                        for (function, commands) in chains {
+                            let code = match executor.execute_lambda(commands).await {
+                                Ok(result) => {
+                                    if let Some(updated) = &result.function {
+                                        slog::info!(logger, "Lambda function updated";
+                                            "function" => &function,
+                                            "version" => &updated.version,
+                                            "sha256" => &updated.code_sha256);
+                                        versions.push(format!("{}={}", function, updated.version));
+                                    }
+                                    result.code
+                                }
+                                Err(e) => {
+                                    slog::error!(logger, "Lambda function update failed";
+                                        "function" => &function,
+                                        "error" => e.to_string());
+                                    if status == 0 {
+                                        status = 1;
+                                    }
+                                    if fail_fast {
+                                        break;
+                                    }
+                                    continue;
+                                }
+                            };
+                            if code != 0 {
+                                slog::error!(logger, "Lambda function update failed";
+                                    "function" => &function,
+                                    "status" => code);
+                                if status == 0 {
+                                    status = code;
+                                }
+                                if fail_fast {
+                                    break;
+                                }
+                            }
+                        }
+                        if !versions.is_empty() {
+                            outputs.set("lambda_versions", versions.join(","));
+                        }
+                        if status == 0 {
+                            status = executor.execute_chain(chain.tag_chain()).await?;
+                        }
+                        Ok(status)
+                    } else {
+                        let chain = CommandChain::new(cwd.clone())
+                            .with_vars(envs.as_map())
+                            .with_lambda_function(function_name)
+                            .with_lambda_alias(alias)
+                            .with_lambda_publish(publish)
+                            .with_tags(tags.clone())
+                            .with_tagged_resources(tag_resources.clone());
+
+                        let commands = if !zip.as_os_str().is_empty() {
+                            slog::info!(logger, "Lambda zip: {:?}", zip);
+                            chain.with_lambda_zip(zip).lambda_update_zip_chain()
+                        } else if !image.is_empty() {
+                            slog::info!(logger, "Lambda image: {}", image);
+                            chain.with_lambda_image(image).lambda_update_image_chain()
+                        } else {
+                            slog::error!(logger, "Lambda zip or image not set");
+                            return Err(ConfigError::RequiredValueMissing(format!(
+                                "{} or {}",
+                                aws::constants::ENV_AWS_LAMBDA_ZIP,
+                                aws::constants::ENV_AWS_LAMBDA_IMAGE
+                            ))
+                            .into());
+                        };
+
+                        slog::info!(logger, "Starting Lambda update chain"; "steps" => commands.len());
+                        executor.execute_lambda(commands).await.map(|result| {
+                            if let Some(function) = &result.function {
+                                slog::info!(logger, "Lambda function updated";
+                                    "arn" => &function.function_arn,
+                                    "version" => &function.version,
+                                    "sha256" => &function.code_sha256);
+                                outputs.set("lambda_arn", &function.function_arn);
+                                outputs.set("lambda_version", &function.version);
+                                outputs.set("lambda_sha256", &function.code_sha256);
+                            }
+                            result.code
+                        })
                     }
-                    v
                 }
-                Err(e) => {
-                    slog::error!(logger, "Failed to get delete option"; "error" => e.to_string());
-                    return Err(e.into());
+                AwsAction::Route53Upsert => {
+                    let zone_id = match aws_config.get_route53_zone_id() {
+                        Ok(v) if !v.is_empty() => v,
+                        Ok(_) => {
+                            slog::error!(logger, "Route53 zone not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_ROUTE53_ZONE_ID.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Route53 zone"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let name = match aws_config.get_route53_record_name() {
+                        Ok(v) if !v.is_empty() => v,
+                        Ok(_) => {
+                            slog::error!(logger, "Route53 record name not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_ROUTE53_RECORD_NAME.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Route53 record name"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let values = match aws_config.get_route53_record_values() {
+                        Ok(Some(v)) => v,
+                        Ok(None) => {
+                            slog::error!(logger, "Route53 record values not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_ROUTE53_RECORD_VALUES.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Route53 record values"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let record_type = match aws_config.get_route53_record_type() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Route53 record type"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let ttl = match aws_config.get_route53_record_ttl() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Route53 record TTL"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let wait = match aws_config.get_route53_wait() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get Route53 wait option"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    slog::info!(logger, "Upserting {} record {}", record_type, name; "wait" => wait);
+                    let status = executor
+                        .execute(AwsCommand::Route53Upsert {
+                            zone_id,
+                            record: Route53Record {
+                                name,
+                                record_type,
+                                ttl,
+                                values,
+                            },
+                            wait,
+                        })
+                        .await?;
+                    if status != 0 {
+                        Ok(status)
+                    } else {
+                        let tag_chain = CommandChain::new(cwd.clone())
+                            .with_tags(tags.clone())
+                            .with_tagged_resources(tag_resources.clone())
+                            .tag_chain();
+                        executor.execute_chain(tag_chain).await
+                    }
                 }
-            };
+                AwsAction::EksKubeconfig => {
+                    let cluster = match aws_config.get_eks_cluster() {
+                        Ok(v) if !v.is_empty() => v,
+                        Ok(_) => {
+                            slog::error!(logger, "EKS cluster not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_EKS_CLUSTER.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get EKS cluster"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let region = match aws_config.get_eks_region() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get EKS region"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let kubeconfig_path = match aws_config.get_eks_kubeconfig() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get kubeconfig path"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    slog::info!(logger, "Writing kubeconfig for EKS cluster {}", cluster);
+                    executor
+                        .execute(AwsCommand::EksUpdateKubeconfig {
+                            cluster,
+                            region,
+                            kubeconfig_path,
+                        })
+                        .await
+                }
+                AwsAction::EcrPush => {
+                    let registry = match aws_config.get_ecr_registry() {
+                        Ok(v) if !v.is_empty() => v,
+                        Ok(_) => {
+                            slog::error!(logger, "ECR registry not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_ECR_REGISTRY.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get ECR registry"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let image = match aws_config.get_ecr_image() {
+                        Ok(v) if !v.is_empty() => {
+                            slog::info!(logger, "ECR image: {}", v);
+                            v
+                        }
+                        Ok(_) => {
+                            slog::error!(logger, "ECR image not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_ECR_IMAGE.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get ECR image"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    outputs.set("image", format!("{}/{}", registry, image));
+                    let chain = CommandChain::new(cwd.clone())
+                        .with_vars(envs.as_map())
+                        .with_ecr_registry(registry)
+                        .with_ecr_image(image)
+                        .with_tags(tags.clone())
+                        .with_tagged_resources(tag_resources.clone());
+
+                    slog::info!(logger, "Starting AWS ECR push chain");
+                    executor.execute_chain(chain.ecr_push_chain()).await
+                }
+                AwsAction::EcsDeploy => {
+                    let cluster = match aws_config.get_ecs_cluster() {
+                        Ok(v) if !v.is_empty() => {
+                            slog::info!(logger, "ECS cluster: {}", v);
+                            v
+                        }
+                        Ok(_) => {
+                            slog::error!(logger, "ECS cluster not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_ECS_CLUSTER.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get ECS cluster"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let service = match aws_config.get_ecs_service() {
+                        Ok(v) if !v.is_empty() => {
+                            slog::info!(logger, "ECS service: {}", v);
+                            v
+                        }
+                        Ok(_) => {
+                            slog::error!(logger, "ECS service not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_ECS_SERVICE.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get ECS service"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let task_definition = match aws_config.get_ecs_task_definition() {
+                        Ok(v) if !v.as_os_str().is_empty() => {
+                            slog::info!(logger, "Task definition: {:?}", v);
+                            v
+                        }
+                        Ok(_) => {
+                            slog::error!(logger, "Task definition not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_ECS_TASK_DEFINITION.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get task definition"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let wait = match aws_config.get_ecs_wait() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get ECS wait option"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
 
-            let dry_run = match aws_config.get_dry_run() {
-                Ok(v) => {
-                    if v {
-                        slog::info!(logger, "Dry run mode enabled");
+                    slog::info!(logger, "Starting ECS deploy"; "wait" => wait);
+                    let status = executor
+                        .deploy_service(cluster, service, task_definition, wait)
+                        .await?;
+                    if status != 0 {
+                        Ok(status)
+                    } else {
+                        let tag_chain = CommandChain::new(cwd.clone())
+                            .with_tags(tags.clone())
+                            .with_tagged_resources(tag_resources.clone())
+                            .tag_chain();
+                        executor.execute_chain(tag_chain).await
                     }
-                    v
                 }
-                Err(e) => {
-                    slog::error!(logger, "Failed to get dry run option"; "error" => e.to_string());
-                    return Err(e.into());
+                AwsAction::LogsTail => {
+                    let log_group = match aws_config.get_logs_group() {
+                        Ok(v) if !v.is_empty() => {
+                            slog::info!(logger, "Log group: {}", v);
+                            v
+                        }
+                        Ok(_) => {
+                            slog::error!(logger, "Log group not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_LOGS_GROUP.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get log group"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let since = match aws_config.get_logs_since() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get log start"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let filter = match aws_config.get_logs_filter() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get log filter"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    executor
+                        .execute(AwsCommand::LogsTail {
+                            log_group,
+                            since,
+                            filter,
+                        })
+                        .await
                 }
-            };
+                AwsAction::SamDeploy => {
+                    let stack_name = match aws_config.get_sam_stack() {
+                        Ok(v) if !v.is_empty() => {
+                            slog::info!(logger, "SAM stack: {}", v);
+                            v
+                        }
+                        Ok(_) => {
+                            slog::error!(logger, "SAM stack not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_SAM_STACK.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get SAM stack"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let template = match aws_config.get_sam_template() {
+                        Ok(v) => v.map(PathBuf::from),
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get SAM template"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
 
-            let force = match aws_config.get_force() {
-                Ok(v) => {
-                    if v {
-                        slog::info!(logger, "Force mode enabled");
+                    let capabilities = match aws_config.get_sam_capabilities() {
+                        Ok(v) => v.unwrap_or_default(),
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get SAM capabilities"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let parameter_overrides = match aws_config.get_parameter_overrides() {
+                        Ok(v) => v.unwrap_or_default(),
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get parameter overrides"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    slog::info!(logger, "Starting SAM deploy"; "parameters" => parameter_overrides.len());
+                    let status = executor
+                        .execute(AwsCommand::SamDeploy {
+                            project_dir: cwd.clone(),
+                            stack_name,
+                            template,
+                            parameter_overrides,
+                            capabilities,
+                        })
+                        .await?;
+                    if status != 0 {
+                        Ok(status)
+                    } else {
+                        let tag_chain = CommandChain::new(cwd.clone())
+                            .with_tags(tags.clone())
+                            .with_tagged_resources(tag_resources.clone())
+                            .tag_chain();
+                        executor.execute_chain(tag_chain).await
                     }
-                    v
                 }
-                Err(e) => {
-                    slog::error!(logger, "Failed to get force option"; "error" => e.to_string());
-                    return Err(e.into());
+                AwsAction::CdkDeploy => {
+                    let stacks = match aws_config.get_cdk_stacks() {
+                        Ok(v) => {
+                            let stacks = v.unwrap_or_default();
+                            if stacks.is_empty() {
+                                slog::info!(logger, "CDK stacks: all");
+                            } else {
+                                slog::info!(logger, "CDK stacks: {:?}", stacks);
+                            }
+                            stacks
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get CDK stacks"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let app = match aws_config.get_cdk_app() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get CDK app"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let parameters = match aws_config.get_parameter_overrides() {
+                        Ok(v) => v.unwrap_or_default(),
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get parameter overrides"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    slog::info!(logger, "Starting CDK deploy"; "parameters" => parameters.len());
+                    let status = executor
+                        .execute(AwsCommand::CdkDeploy {
+                            project_dir: cwd.clone(),
+                            stacks,
+                            app,
+                            parameters,
+                        })
+                        .await?;
+                    if status != 0 {
+                        Ok(status)
+                    } else {
+                        let tag_chain = CommandChain::new(cwd.clone())
+                            .with_tags(tags.clone())
+                            .with_tagged_resources(tag_resources.clone())
+                            .tag_chain();
+                        executor.execute_chain(tag_chain).await
+                    }
                 }
-            };
+                AwsAction::CloudFrontInvalidate => {
+                    let distributions = match aws_config.get_cloudfront_distributions() {
+                        Ok(Some(v)) => {
+                            slog::info!(logger, "CloudFront distributions: {:?}", v);
+                            v
+                        }
+                        Ok(None) => {
+                            slog::error!(logger, "CloudFront distribution not set");
+                            return Err(ConfigError::RequiredValueMissing(
+                                aws::constants::ENV_AWS_CLOUDFRONT_DISTRIBUTION.to_string(),
+                            )
+                            .into());
+                        }
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get CloudFront distributions"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
 
-            let chain = CommandChain::new(cwd)
-                .with_vars(envs.as_map())
-                .with_destination(destination)
-                .with_exclude(exclude)
-                .with_include(include)
-                .with_delete(delete)
-                .with_dry_run(dry_run)
-                .with_force(force);
+                    let paths = match aws_config.get_cloudfront_paths() {
+                        Ok(v) => v.unwrap_or_else(|| {
+                            vec![aws::constants::DEFAULT_CLOUDFRONT_PATHS.to_string()]
+                        }),
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get invalidation paths"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
 
-            slog::info!(logger, "Starting AWS S3 sync command");
-            executor.execute_chain(chain.sync_chain()).await
-        }
-        _ => {
-            slog::error!(logger, "Unknown command: {}", cmd);
-            return Err(format!("Unknown command: {}", cmd).into());
+                    let parallel = match aws_config.get_cloudfront_parallel() {
+                        Ok(v) => v,
+                        Err(e) => {
+                            slog::error!(logger, "Failed to get parallel invalidation option"; "error" => e.to_string());
+                            return Err(e.into());
+                        }
+                    };
+
+                    let steps = CommandChain::new(cwd.clone())
+                        .with_vars(envs.as_map())
+                        .with_cloudfront_distributions(distributions)
+                        .with_cloudfront_paths(paths)
+                        .invalidate_steps();
+
+                    slog::info!(logger, "Starting CloudFront invalidation";
+                        "distributions" => steps.len(),
+                        "parallel" => parallel);
+                    if parallel {
+                        let commands = steps.into_iter().map(|step| step.command).collect();
+                        let results = executor.execute_invalidations(commands).await?;
+                        set_invalidation_ids(
+                            &mut outputs,
+                            results.iter().filter_map(|r| r.invalidation.as_ref()),
+                        );
+                        Ok(results
+                            .iter()
+                            .map(|r| r.code)
+                            .find(|code| *code != 0)
+                            .unwrap_or(0))
+                    } else {
+                        let steps = steps
+                            .into_iter()
+                            .map(|step| step.with_policy(StepPolicy::ContinueOnError))
+                            .collect();
+                        let results = executor.execute_steps(steps).await;
+                        set_invalidation_ids(
+                            &mut outputs,
+                            results.iter().filter_map(|r| r.invalidation.as_ref()),
+                        );
+                        Ok(report_steps(&logger, results))
+                    }
+                }
+            };
+
+            let status = match result {
+                Ok(0) => StepStatus::Succeeded,
+                Ok(code) => StepStatus::Failed(code),
+                Err(e) => StepStatus::Error(e),
+            };
+            results.push(command_result(index, cmd, status, started.elapsed()));
         }
-    };
+        Ok(None)
+    }
+    .await;
 
-    let status = result?;
+    let (status, error) = report_commands(&logger, &mut outputs, results);
+    let (status, failure) = match &run {
+        Ok(Some((status, failure))) => (*status, Some(*failure)),
+        _ => (status, None),
+    };
+    let outcome = match (run, error) {
+        (Err(e), _) | (Ok(_), Some(e)) => Err(e),
+        (Ok(_), None) => Ok(status),
+    };
+    for annotation in annotations.take() {
+        println!("{}", processor.process(&annotation.render()));
+    }
+    RunSummary::new(format!("aws {}", cmds), metrics)
+        .with_outcome(&outcome)
+        .with_dry_run(dry_run)
+        .with_workflow_run()
+        .with_processor(processor)
+        .report(&logger);
+    match outputs.write() {
+        Ok(true) => slog::debug!(logger, "Step outputs were written"; "count" => outputs.len()),
+        Ok(false) => {}
+        Err(e) => slog::warn!(logger, "Failed to write step outputs"; "error" => e.to_string()),
+    }
+    if let Err(e) = outcome {
+        slog::error!(logger, "Action {} failed", cmds; "error" => e.to_string());
+        return Err(e);
+    }
     if status == 0 {
-        slog::info!(logger, "Action {} was finished successfully", cmd);
-    } else {
-        slog::error!(logger, "Action {} failed with status: {}", cmd, status);
+        slog::info!(logger, "Action {} was finished successfully", cmds);
+        return Ok(None);
+    }
+    let failure = failure.unwrap_or_else(|| status_failure(status));
+    slog::error!(logger, "Action {} failed with status: {}", cmds, status;
+        "failure" => failure.as_str(), "exit_code" => failure.code());
+    Ok(Some(failure))
+}
+
+/// Classifies the error the action stopped with.
+fn classify(error: &(dyn std::error::Error + 'static)) -> Failure {
+    match error.downcast_ref::<AwsError>() {
+        Some(AwsError::ExecuterError(e)) => Failure::of_executer(e),
+        Some(_) => Failure::Tool,
+        None => Failure::of_shared(error).unwrap_or(Failure::Tool),
+    }
+}
+
+/// Classifies a failed AWS CLI command by its exit status.
+fn status_failure(status: i32) -> Failure {
+    match status {
+        AWS_CLI_INVALID_PARAMETERS => Failure::Validation,
+        AWS_CLI_INVALID_ENVIRONMENT => Failure::Provider,
+        _ => Failure::Tool,
+    }
+}
+
+/// Logs the outcome of every step and returns the status of the first failed one.
+fn report_steps(logger: &slog::Logger, results: Vec<StepResult>) -> i32 {
+    for result in &results {
+        match &result.status {
+            StepStatus::Succeeded => {
+                slog::debug!(logger, "Step succeeded"; "step" => result.label(), "operation" => &result.operation, "duration_ms" => result.duration.as_millis())
+            }
+            StepStatus::Failed(code) => {
+                slog::error!(logger, "Step failed"; "step" => result.label(), "operation" => &result.operation, "status" => code, "duration_ms" => result.duration.as_millis())
+            }
+            StepStatus::Error(e) => {
+                slog::error!(logger, "Step failed"; "step" => result.label(), "operation" => &result.operation, "error" => e.to_string(), "duration_ms" => result.duration.as_millis())
+            }
+            StepStatus::Skipped => {
+                slog::warn!(logger, "Step skipped"; "step" => result.label(), "operation" => &result.operation)
+            }
+        }
     }
+    first_failure(&results).map_or(0, StepResult::code)
+}
 
-    Ok(())
+/// Returns the result of one command of the run.
+fn command_result(
+    index: usize,
+    cmd: AwsAction,
+    status: StepStatus,
+    duration: Duration,
+) -> StepResult {
+    StepResult {
+        index,
+        operation: cmd.to_string(),
+        name: None,
+        policy: StepPolicy::FailFast,
+        status,
+        duration,
+        invalidation: None,
+    }
+}
+
+/// Reports the result of every command of a run with more than one command
+/// and returns the status of the first failed one with the first error.
+///
+/// Commands after a failed one are skipped. The error is returned, not
+/// propagated, so the run is still reported before the action stops with it.
+fn report_commands(
+    logger: &slog::Logger,
+    outputs: &mut Outputs,
+    results: Vec<StepResult>,
+) -> (i32, Option<Box<dyn std::error::Error>>) {
+    if results.len() > 1 {
+        let mut markdown =
+            "### AWS commands\n\n| Command | Result | Duration |\n| --- | --- | --- |\n"
+                .to_string();
+        for result in &results {
+            let record = format!(
+                "[command {}/{}] {}: {}",
+                result.index + 1,
+                results.len(),
+                result.operation,
+                result.outcome()
+            );
+            let duration_ms = result.duration.as_millis();
+            if result.is_failure() {
+                slog::error!(logger, "{}", record; "duration_ms" => duration_ms);
+            } else {
+                slog::info!(logger, "{}", record; "duration_ms" => duration_ms);
+            }
+            markdown.push_str(&format!(
+                "| `{}` | {} | {:.2}s |\n",
+                result.operation,
+                result.outcome(),
+                result.duration.as_secs_f64()
+            ));
+        }
+        match write_step_summary(&markdown) {
+            Ok(true) => slog::debug!(logger, "Command results were added to the job summary"),
+            Ok(false) => {}
+            Err(e) => slog::warn!(logger, "Failed to write job summary"; "error" => e.to_string()),
+        }
+    }
+    if let Some(failed) = first_failure(&results) {
+        outputs.set("failed_command", &failed.operation);
+    }
+    let code = first_failure(&results).map_or(0, StepResult::code);
+    let error = results.into_iter().find_map(|r| match r.status {
+        StepStatus::Error(e) => Some(e.into()),
+        _ => None,
+    });
+    (code, error)
+}
+
+/// Sets the `invalidation_id` output to the comma-separated IDs of the created invalidations.
+fn set_invalidation_ids<'a>(
+    outputs: &mut Outputs,
+    invalidations: impl Iterator<Item = &'a Invalidation>,
+) {
+    let ids: Vec<&str> = invalidations.map(|i| i.id.as_str()).collect();
+    if !ids.is_empty() {
+        outputs.set("invalidation_id", ids.join(","));
+    }
 }
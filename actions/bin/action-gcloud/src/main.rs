@@ -0,0 +1,320 @@
+use config::{collect_errors, ConfigError, ConfigResult, MainConfig};
+use executer::Metrics;
+use gcloud::{
+    error::GcloudError, GcloudAction, GcloudCommand, GcloudConfig, GcloudExecutor, GlobalOptions,
+};
+use processor::{MaskerEqual, MaskerRegex, ProcessorCollection, ProcessorItem};
+
+use provider::{GCPProvider, Provider};
+use util::{
+    exit_code, write_step_summary, ActionResult, Failure, LogContext, LogFile, LoggerBuilder,
+    Outputs, RunSummary,
+};
+
+#[tokio::main]
+async fn main() -> std::process::ExitCode {
+    exit_code(run().await, classify)
+}
+
+async fn run() -> ActionResult {
+    let main_config = MainConfig::new();
+    let gcloud_config = GcloudConfig::new();
+
+    if std::env::args().any(|arg| arg == "--print-inputs") {
+        let inputs = main_config.inputs().extend(gcloud_config.inputs());
+        print!(
+            "{}{}",
+            inputs.render_action_inputs(),
+            inputs.render_action_env()
+        );
+        return Ok(None);
+    }
+
+    let level = main_config.get_log_level().unwrap_or("info".to_string());
+    let format = main_config
+        .get_log_format()
+        .unwrap_or(config::DEFAULT_LOG_FORMAT.to_string());
+    let log_file = main_config.get_log_file().ok().flatten().map(|path| {
+        LogFile::new(path)
+            .with_level(
+                &main_config
+                    .get_log_file_level()
+                    .unwrap_or(config::DEFAULT_LOG_FILE_LEVEL.to_string()),
+            )
+            .with_max_bytes(
+                main_config
+                    .get_log_file_max_bytes()
+                    .unwrap_or(config::DEFAULT_LOG_FILE_MAX_BYTES),
+            )
+    });
+    let (logger, _) = LoggerBuilder::new()
+        .with_level(&level)
+        .with_format(&format)
+        .with_file(log_file)
+        .build();
+
+    if let Err(e) = collect_errors(vec![
+        main_config.validate_all(),
+        gcloud_config.validate_all(),
+    ]) {
+        let errors = e.into_errors();
+        for error in &errors {
+            slog::error!(logger, "Invalid configuration"; "error" => error.to_string());
+        }
+        return Err(ConfigError::Multiple(errors).into());
+    }
+
+    let provider = GCPProvider::from_env();
+    if let Err(e) = provider.validate() {
+        slog::error!(logger, "Failed to detect Google Cloud credentials"; "error" => e.to_string());
+        return Err(e.into());
+    }
+    slog::info!(
+        logger,
+        "Initialize action with provider {}",
+        provider.name()
+    );
+
+    let cwd = match main_config.get_working_dir() {
+        Ok(v) => {
+            slog::info!(logger, "Workdir: {:?}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Work directory not set"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let cmd = match gcloud_config.get_cmd() {
+        Ok(v) => {
+            slog::info!(logger, "Action command: {}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Invalid gcloud command"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let mask = match main_config.get_mask() {
+        Ok(v) => {
+            slog::debug!(logger, "mask string: {}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Mask string not set"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let bin = match gcloud_config.get_bin() {
+        Ok(v) => {
+            slog::debug!(logger, "gcloud binary file: {:?}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Invalid gcloud bin filepath"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let masker_provider_output = match MaskerRegex::new(
+        provider.get_predefined_masked_objects(),
+        &mask,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to initialize maskers for provider"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let masker_provider_credentials = MaskerEqual::new(provider.values(), &mask);
+
+    let report = main_config.report().extend(gcloud_config.report());
+    let masker_config = MaskerEqual::new(report.sensitive_values(), &mask);
+
+    let processor = ProcessorCollection::new(vec![
+        ProcessorItem::Regex(masker_provider_output),
+        ProcessorItem::Equal(masker_provider_credentials),
+        ProcessorItem::Equal(masker_config),
+    ]);
+    let logger = LogContext::from_env("gcloud").logger(&util::masked(&logger, processor.clone()));
+    for line in report.render(&processor, &mask) {
+        slog::debug!(logger, "Config: {}", line);
+    }
+    slog::info!(logger, "Action was initialized");
+
+    let credentials_path = match gcloud_config.get_credentials_file() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get credentials file path"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let credentials = match provider.credentials_file(&credentials_path) {
+        Ok(v) => {
+            if v.as_ref() == Some(&credentials_path) {
+                slog::debug!(logger, "Credentials were written to {:?}", credentials_path);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to write credentials"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let project = match gcloud_config.get_project() {
+        Ok(v) => v.or_else(|| provider.project().map(str::to_string)),
+        Err(e) => {
+            slog::error!(logger, "Failed to get project"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    if let Some(project) = &project {
+        slog::info!(logger, "Project: {}", project);
+    }
+
+    let command = match build_command(&gcloud_config, cmd) {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Invalid {} configuration", cmd; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let metrics = Metrics::new();
+    let executor = GcloudExecutor::new(processor.clone(), bin)
+        .with_working_dir(cwd)
+        .with_credentials(credentials)
+        .with_options(GlobalOptions::new().with_project(project))
+        .with_metrics(metrics.clone());
+
+    let mut outputs = Outputs::new().with_processor(processor.clone());
+    let outcome = executor.execute(&command).await;
+    if matches!(outcome, Ok(0)) {
+        report_deployment(&logger, &executor, &command, &mut outputs).await;
+    }
+
+    let mut summary = RunSummary::new(format!("gcloud {}", cmd), metrics)
+        .with_outcome(&outcome)
+        .with_workflow_run()
+        .with_processor(processor);
+    if let Some(url) = outputs.get("url") {
+        summary = summary.with_link("Deployment", url);
+    }
+    summary.report(&logger);
+    match outputs.write() {
+        Ok(true) => slog::debug!(logger, "Step outputs were written"; "count" => outputs.len()),
+        Ok(false) => {}
+        Err(e) => slog::warn!(logger, "Failed to write step outputs"; "error" => e.to_string()),
+    }
+    let code = match outcome {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to run gcloud {}", cmd; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    if code == 0 {
+        slog::info!(logger, "Action {} was finished successfully", cmd);
+        return Ok(None);
+    }
+    let failure = Failure::Tool;
+    slog::error!(logger, "Action {} failed with status: {}", cmd, code;
+        "failure" => failure.as_str(), "exit_code" => failure.code());
+    Ok(Some(failure))
+}
+
+/// Classifies the error the action stopped with.
+fn classify(error: &(dyn std::error::Error + 'static)) -> Failure {
+    match error.downcast_ref::<GcloudError>() {
+        Some(GcloudError::ExecuterError(e)) => Failure::of_executer(e),
+        Some(_) => Failure::Tool,
+        None => Failure::of_shared(error).unwrap_or(Failure::Tool),
+    }
+}
+
+/// Builds the command of `cmd`, checking the values it requires.
+fn build_command(config: &GcloudConfig, cmd: GcloudAction) -> ConfigResult<GcloudCommand> {
+    let required = |value: Option<String>, key: &str| {
+        value.ok_or_else(|| ConfigError::RequiredValueMissing(key.to_string()))
+    };
+    Ok(match cmd {
+        GcloudAction::StorageRsync => GcloudCommand::StorageRsync {
+            source: required(
+                config.get_storage_source()?,
+                gcloud::ENV_GCLOUD_STORAGE_SOURCE,
+            )?,
+            destination: required(
+                config.get_storage_destination()?,
+                gcloud::ENV_GCLOUD_STORAGE_DESTINATION,
+            )?,
+            exclude: config.get_storage_exclude()?,
+            delete: config.get_storage_delete()?,
+            dry_run: config.get_storage_dry_run()?,
+        },
+        GcloudAction::RunDeploy => GcloudCommand::RunDeploy {
+            service: required(config.get_run_service()?, gcloud::ENV_GCLOUD_RUN_SERVICE)?,
+            image: config.get_run_image()?,
+            source: config.get_run_source()?,
+            region: required(config.get_region()?, gcloud::ENV_GCLOUD_REGION)?,
+            env_vars: config.get_env_vars()?,
+        },
+        GcloudAction::FunctionsDeploy => GcloudCommand::FunctionsDeploy {
+            name: required(
+                config.get_functions_name()?,
+                gcloud::ENV_GCLOUD_FUNCTIONS_NAME,
+            )?,
+            runtime: required(
+                config.get_functions_runtime()?,
+                gcloud::ENV_GCLOUD_FUNCTIONS_RUNTIME,
+            )?,
+            entry_point: config.get_functions_entry_point()?,
+            source: config.get_functions_source()?,
+            region: required(config.get_region()?, gcloud::ENV_GCLOUD_REGION)?,
+            trigger: config.get_functions_trigger()?,
+            env_vars: config.get_env_vars()?,
+        },
+    })
+}
+
+/// Logs what a deploy command deployed, sets it as step outputs and adds it to the job summary.
+async fn report_deployment(
+    logger: &slog::Logger,
+    executor: &GcloudExecutor,
+    command: &GcloudCommand,
+    outputs: &mut Outputs,
+) {
+    let deployment = match executor.describe(command).await {
+        Ok(Some(v)) => v,
+        Ok(None) => return,
+        Err(e) => {
+            slog::warn!(logger, "Failed to describe deployment"; "error" => e.to_string());
+            return;
+        }
+    };
+    slog::info!(
+        logger,
+        "Deployed {} ({})",
+        deployment.name,
+        if deployment.ready {
+            "ready"
+        } else {
+            "not ready"
+        }
+    );
+    if let Some(url) = &deployment.url {
+        slog::info!(logger, "URL: {}", url);
+        outputs.set("url", url);
+    }
+    if let Some(revision) = &deployment.revision {
+        outputs.set("revision", revision);
+    }
+    match write_step_summary(&deployment.markdown()) {
+        Ok(true) => slog::debug!(logger, "Deployment was added to the job summary"),
+        Ok(false) => {}
+        Err(e) => slog::warn!(logger, "Failed to write job summary"; "error" => e.to_string()),
+    }
+}
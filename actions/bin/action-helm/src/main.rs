@@ -0,0 +1,396 @@
+use config::{collect_errors, ConfigError, MainConfig};
+use executer::Metrics;
+use helm::{
+    error::HelmError, ChartSource, GlobalOptions, HelmAction, HelmCommand, HelmConfig,
+    HelmExecutor, Wait,
+};
+use processor::{MaskerEqual, MaskerRegex, MaskerRuntime, ProcessorCollection, ProcessorItem};
+
+use provider::{KubernetesProvider, Provider};
+use util::{
+    exit_code, write_step_summary, ActionResult, Failure, LogContext, LogFile, LoggerBuilder,
+    Outputs, RunSummary,
+};
+
+#[tokio::main]
+async fn main() -> std::process::ExitCode {
+    exit_code(run().await, classify)
+}
+
+async fn run() -> ActionResult {
+    let main_config = MainConfig::new();
+    let helm_config = HelmConfig::new();
+
+    if std::env::args().any(|arg| arg == "--print-inputs") {
+        let inputs = main_config.inputs().extend(helm_config.inputs());
+        print!(
+            "{}{}",
+            inputs.render_action_inputs(),
+            inputs.render_action_env()
+        );
+        return Ok(None);
+    }
+
+    let level = main_config.get_log_level().unwrap_or("info".to_string());
+    let format = main_config
+        .get_log_format()
+        .unwrap_or(config::DEFAULT_LOG_FORMAT.to_string());
+    let log_file = main_config.get_log_file().ok().flatten().map(|path| {
+        LogFile::new(path)
+            .with_level(
+                &main_config
+                    .get_log_file_level()
+                    .unwrap_or(config::DEFAULT_LOG_FILE_LEVEL.to_string()),
+            )
+            .with_max_bytes(
+                main_config
+                    .get_log_file_max_bytes()
+                    .unwrap_or(config::DEFAULT_LOG_FILE_MAX_BYTES),
+            )
+    });
+    let (logger, _) = LoggerBuilder::new()
+        .with_level(&level)
+        .with_format(&format)
+        .with_file(log_file)
+        .build();
+
+    if let Err(e) = collect_errors(vec![main_config.validate_all(), helm_config.validate_all()]) {
+        let errors = e.into_errors();
+        for error in &errors {
+            slog::error!(logger, "Invalid configuration"; "error" => error.to_string());
+        }
+        return Err(ConfigError::Multiple(errors).into());
+    }
+
+    // The Kubernetes provider is used even when other credentials are set,
+    // e.g. AWS credentials on runners deploying to EKS.
+    let provider = KubernetesProvider::from_env();
+    if let Err(e) = provider.validate() {
+        slog::error!(logger, "Failed to detect Kubernetes credentials"; "error" => e.to_string());
+        return Err(e.into());
+    }
+    slog::info!(
+        logger,
+        "Initialize action with provider {}",
+        provider.name()
+    );
+
+    let cwd = match main_config.get_working_dir() {
+        Ok(v) => {
+            slog::info!(logger, "Workdir: {:?}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Work directory not set"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let cmd = match helm_config.get_cmd() {
+        Ok(v) => {
+            slog::info!(logger, "Action command: {}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Invalid helm command"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let mask = match main_config.get_mask() {
+        Ok(v) => {
+            slog::debug!(logger, "mask string: {}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Mask string not set"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let bin = match helm_config.get_bin() {
+        Ok(v) => {
+            slog::debug!(logger, "helm binary file: {:?}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Invalid helm bin filepath"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let masker_provider_output = match MaskerRegex::new(
+        provider.get_predefined_masked_objects(),
+        &mask,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to initialize maskers for provider"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let masker_provider_credentials = MaskerEqual::new(provider.values(), &mask);
+
+    let report = main_config.report().extend(helm_config.report());
+    let masker_config = MaskerEqual::new(report.sensitive_values(), &mask);
+    let masker_runtime = MaskerRuntime::new(&mask);
+
+    let processor = ProcessorCollection::new(vec![
+        ProcessorItem::Regex(masker_provider_output),
+        ProcessorItem::Equal(masker_provider_credentials),
+        ProcessorItem::Equal(masker_config),
+        ProcessorItem::Runtime(masker_runtime.clone()),
+    ]);
+    let logger = LogContext::from_env("helm").logger(&util::masked(&logger, processor.clone()));
+    for line in report.render(&processor, &mask) {
+        slog::debug!(logger, "Config: {}", line);
+    }
+    slog::info!(logger, "Action was initialized");
+
+    let kubeconfig_path = match helm_config.get_kubeconfig() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get kubeconfig path"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let kubeconfig = match provider.kubeconfig_file(&kubeconfig_path) {
+        Ok(v) => {
+            if provider.kubeconfig().is_some() {
+                slog::debug!(logger, "Kubeconfig was written to {:?}", kubeconfig_path);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to write kubeconfig"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let release = match helm_config.get_release() {
+        Ok(v) => {
+            slog::info!(logger, "Release: {}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Release not set"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let namespace = match helm_config.get_namespace() {
+        Ok(v) => {
+            if let Some(namespace) = &v {
+                slog::info!(logger, "Namespace: {}", namespace);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get namespace"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let context = match helm_config.get_context() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get kubeconfig context"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let enabled = match helm_config.get_wait() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get wait flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let timeout = match helm_config.get_timeout() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get timeout"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let wait = Wait { enabled, timeout };
+    let command = match cmd {
+        HelmAction::Upgrade | HelmAction::Template => {
+            let chart = match helm_config.get_chart() {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to get chart"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            if chart.is_empty() {
+                let e = ConfigError::RequiredValueMissing(helm::ENV_HELM_CHART.to_string());
+                slog::error!(logger, "Chart not set"; "error" => e.to_string());
+                return Err(e.into());
+            }
+            let mut set = match helm_config.get_set() {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to get set values"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            let set_sensitive = match helm_config.get_set_sensitive() {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to get sensitive set values"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            let version = match helm_config.get_chart_version() {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to get chart version"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            let values_files = match helm_config.get_values_files() {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to get values files"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            set.extend(set_sensitive);
+            let source = ChartSource {
+                chart,
+                version,
+                values_files,
+                set,
+            };
+            slog::info!(logger, "Chart: {}", source.chart);
+            if cmd == HelmAction::Template {
+                HelmCommand::Template { release, source }
+            } else {
+                let atomic = match helm_config.get_atomic() {
+                    Ok(v) => v,
+                    Err(e) => {
+                        slog::error!(logger, "Failed to get atomic flag"; "error" => e.to_string());
+                        return Err(e.into());
+                    }
+                };
+                let create_namespace = match helm_config.get_create_namespace() {
+                    Ok(v) => v,
+                    Err(e) => {
+                        slog::error!(logger, "Failed to get create-namespace flag"; "error" => e.to_string());
+                        return Err(e.into());
+                    }
+                };
+                HelmCommand::UpgradeInstall {
+                    release,
+                    source,
+                    wait,
+                    atomic,
+                    create_namespace,
+                }
+            }
+        }
+        HelmAction::Rollback => {
+            let revision = match helm_config.get_revision() {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to get rollback revision"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            HelmCommand::Rollback {
+                release,
+                revision,
+                wait,
+            }
+        }
+        HelmAction::Uninstall => HelmCommand::Uninstall { release, wait },
+    };
+
+    let metrics = Metrics::new();
+    let executor = HelmExecutor::new(processor.clone(), bin)
+        .with_working_dir(cwd)
+        .with_kubeconfig(kubeconfig)
+        .with_options(
+            GlobalOptions::new()
+                .with_namespace(namespace)
+                .with_kube_context(context),
+        )
+        .with_secrets(masker_runtime)
+        .with_metrics(metrics.clone());
+
+    let mut outputs = Outputs::new().with_processor(processor.clone());
+    let outcome = executor.execute(&command).await;
+    if matches!(outcome, Ok(0)) && matches!(cmd, HelmAction::Upgrade | HelmAction::Rollback) {
+        report_status(&logger, &executor, command.release(), &mut outputs).await;
+    }
+
+    RunSummary::new(format!("helm {}", cmd), metrics)
+        .with_outcome(&outcome)
+        .with_workflow_run()
+        .with_processor(processor)
+        .report(&logger);
+    match outputs.write() {
+        Ok(true) => slog::debug!(logger, "Step outputs were written"; "count" => outputs.len()),
+        Ok(false) => {}
+        Err(e) => slog::warn!(logger, "Failed to write step outputs"; "error" => e.to_string()),
+    }
+    let code = match outcome {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to run helm {}", cmd; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    if code == 0 {
+        slog::info!(logger, "Action {} was finished successfully", cmd);
+        return Ok(None);
+    }
+    let failure = Failure::Tool;
+    slog::error!(logger, "Action {} failed with status: {}", cmd, code;
+        "failure" => failure.as_str(), "exit_code" => failure.code());
+    Ok(Some(failure))
+}
+
+/// Classifies the error the action stopped with.
+fn classify(error: &(dyn std::error::Error + 'static)) -> Failure {
+    match error.downcast_ref::<HelmError>() {
+        Some(HelmError::ExecuterError(e)) => Failure::of_executer(e),
+        Some(_) => Failure::Tool,
+        None => Failure::of_shared(error).unwrap_or(Failure::Tool),
+    }
+}
+
+/// Logs the status of the release, sets it as step outputs and adds it to the job summary.
+async fn report_status(
+    logger: &slog::Logger,
+    executor: &HelmExecutor,
+    release: &str,
+    outputs: &mut Outputs,
+) {
+    let status = match executor.status(release).await {
+        Ok(v) => v,
+        Err(e) => {
+            slog::warn!(logger, "Failed to read release status"; "error" => e.to_string());
+            return;
+        }
+    };
+    slog::info!(
+        logger,
+        "Release {} is {} at revision {}",
+        status.name,
+        status.status,
+        status.revision
+    );
+    outputs.set("revision", status.revision);
+    outputs.set("status", &status.status);
+    if let Some(chart) = &status.chart {
+        outputs.set("chart", chart);
+    }
+    if let Some(app_version) = &status.app_version {
+        outputs.set("app_version", app_version);
+    }
+    match write_step_summary(&status.markdown()) {
+        Ok(true) => slog::debug!(logger, "Release status was added to the job summary"),
+        Ok(false) => {}
+        Err(e) => slog::warn!(logger, "Failed to write job summary"; "error" => e.to_string()),
+    }
+}
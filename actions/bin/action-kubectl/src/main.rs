@@ -0,0 +1,348 @@
+use config::{collect_errors, ConfigError, MainConfig};
+use executer::Metrics;
+use kubectl::{
+    error::KubectlError, executor::KubectlExecutor, manifest::read_workloads, CommandChain,
+    GlobalOptions, KubectlAction, KubectlConfig,
+};
+use processor::{MaskerEqual, MaskerRegex, MaskerRuntime, ProcessorCollection, ProcessorItem};
+
+use provider::{KubernetesProvider, Provider};
+use util::{
+    exit_code, ActionResult, Failure, LogContext, LogFile, LoggerBuilder, Outputs, RunSummary,
+};
+
+#[tokio::main]
+async fn main() -> std::process::ExitCode {
+    exit_code(run().await, classify)
+}
+
+async fn run() -> ActionResult {
+    let main_config = MainConfig::new();
+    let kubectl_config = KubectlConfig::new();
+
+    if std::env::args().any(|arg| arg == "--print-inputs") {
+        let inputs = main_config.inputs().extend(kubectl_config.inputs());
+        print!(
+            "{}{}",
+            inputs.render_action_inputs(),
+            inputs.render_action_env()
+        );
+        return Ok(None);
+    }
+
+    let level = main_config.get_log_level().unwrap_or("info".to_string());
+    let format = main_config
+        .get_log_format()
+        .unwrap_or(config::DEFAULT_LOG_FORMAT.to_string());
+    let log_file = main_config.get_log_file().ok().flatten().map(|path| {
+        LogFile::new(path)
+            .with_level(
+                &main_config
+                    .get_log_file_level()
+                    .unwrap_or(config::DEFAULT_LOG_FILE_LEVEL.to_string()),
+            )
+            .with_max_bytes(
+                main_config
+                    .get_log_file_max_bytes()
+                    .unwrap_or(config::DEFAULT_LOG_FILE_MAX_BYTES),
+            )
+    });
+    let (logger, _) = LoggerBuilder::new()
+        .with_level(&level)
+        .with_format(&format)
+        .with_file(log_file)
+        .build();
+
+    if let Err(e) = collect_errors(vec![
+        main_config.validate_all(),
+        kubectl_config.validate_all(),
+    ]) {
+        let errors = e.into_errors();
+        for error in &errors {
+            slog::error!(logger, "Invalid configuration"; "error" => error.to_string());
+        }
+        return Err(ConfigError::Multiple(errors).into());
+    }
+
+    // The Kubernetes provider is used even when other credentials are set,
+    // e.g. AWS credentials on runners deploying to EKS.
+    let provider = KubernetesProvider::from_env();
+    if let Err(e) = provider.validate() {
+        slog::error!(logger, "Failed to detect Kubernetes credentials"; "error" => e.to_string());
+        return Err(e.into());
+    }
+    slog::info!(
+        logger,
+        "Initialize action with provider {}",
+        provider.name()
+    );
+
+    let cwd = match main_config.get_working_dir() {
+        Ok(v) => {
+            slog::info!(logger, "Workdir: {:?}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Work directory not set"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let cmd = match kubectl_config.get_cmd() {
+        Ok(v) => {
+            slog::info!(logger, "Action command: {}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Invalid kubectl command"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let mask = match main_config.get_mask() {
+        Ok(v) => {
+            slog::debug!(logger, "mask string: {}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Mask string not set"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let bin = match kubectl_config.get_bin() {
+        Ok(v) => {
+            slog::debug!(logger, "kubectl binary file: {:?}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Invalid kubectl bin filepath"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let masker_provider_output = match MaskerRegex::new(
+        provider.get_predefined_masked_objects(),
+        &mask,
+    ) {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to initialize maskers for provider"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let masker_provider_credentials = MaskerEqual::new(provider.values(), &mask);
+
+    let report = main_config.report().extend(kubectl_config.report());
+    let masker_config = MaskerEqual::new(report.sensitive_values(), &mask);
+    let masker_runtime = MaskerRuntime::new(&mask);
+
+    let processor = ProcessorCollection::new(vec![
+        ProcessorItem::Regex(masker_provider_output),
+        ProcessorItem::Equal(masker_provider_credentials),
+        ProcessorItem::Equal(masker_config),
+        ProcessorItem::Runtime(masker_runtime.clone()),
+    ]);
+    let logger = LogContext::from_env("kubectl").logger(&util::masked(&logger, processor.clone()));
+    for line in report.render(&processor, &mask) {
+        slog::debug!(logger, "Config: {}", line);
+    }
+    slog::info!(logger, "Action was initialized");
+
+    let kubeconfig_path = match kubectl_config.get_kubeconfig() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get kubeconfig path"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let kubeconfig = match provider.kubeconfig_file(&kubeconfig_path) {
+        Ok(v) => {
+            if provider.kubeconfig().is_some() {
+                slog::debug!(logger, "Kubeconfig was written to {:?}", kubeconfig_path);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to write kubeconfig"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let namespace = match kubectl_config.get_namespace() {
+        Ok(v) => {
+            if let Some(namespace) = &v {
+                slog::info!(logger, "Namespace: {}", namespace);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get namespace"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let context = match kubectl_config.get_context() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get kubeconfig context"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let manifests = match kubectl_config.get_manifests() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get manifests"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    if manifests.is_empty() && matches!(cmd, KubectlAction::Apply | KubectlAction::Diff) {
+        let e = ConfigError::RequiredValueMissing(kubectl::ENV_KUBECTL_MANIFESTS.to_string());
+        slog::error!(logger, "Manifests not set"; "error" => e.to_string());
+        return Err(e.into());
+    }
+
+    let mut chain = CommandChain::new(cwd.clone()).with_manifests(manifests);
+    let mut resources = match kubectl_config.get_resources() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get resources"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    if resources.is_empty() && matches!(cmd, KubectlAction::Apply | KubectlAction::RolloutStatus) {
+        resources = match read_workloads(&chain.manifests()) {
+            Ok(v) => v,
+            Err(e) => {
+                slog::error!(logger, "Failed to read workloads of manifests"; "error" => e.to_string());
+                return Err(e.into());
+            }
+        };
+    }
+    if !resources.is_empty() {
+        slog::info!(logger, "Resources: {}", resources.join(", "));
+    }
+
+    let timeout = match kubectl_config.get_timeout() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get rollout timeout"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let diff = match kubectl_config.get_diff() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get diff flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let server_side = match kubectl_config.get_server_side() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get server-side flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let wait = match kubectl_config.get_wait() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get wait flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let ignore_not_found = match kubectl_config.get_ignore_not_found() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get ignore-not-found flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    chain = chain
+        .with_resources(resources.clone())
+        .with_timeout(timeout)
+        .with_diff(diff)
+        .with_server_side(server_side)
+        .with_wait(wait)
+        .with_ignore_not_found(ignore_not_found);
+
+    let commands = match cmd {
+        KubectlAction::Apply => chain.apply_chain(),
+        KubectlAction::Diff => chain.diff_chain(),
+        KubectlAction::RolloutStatus => chain.rollout_chain(),
+        KubectlAction::Delete => chain.delete_chain(),
+    };
+    if commands.iter().all(|c| c.manifests().is_empty()) && resources.is_empty() {
+        slog::error!(logger, "Nothing to run: set manifests or resources");
+        return Err(
+            ConfigError::RequiredValueMissing(kubectl::ENV_KUBECTL_RESOURCES.to_string()).into(),
+        );
+    }
+
+    let metrics = Metrics::new();
+    let executor = KubectlExecutor::new(processor.clone(), bin)
+        .with_kubeconfig(kubeconfig)
+        .with_options(
+            GlobalOptions::new()
+                .with_namespace(namespace)
+                .with_context(context),
+        )
+        .with_secrets(masker_runtime)
+        .with_metrics(metrics.clone());
+
+    let mut outputs = Outputs::new().with_processor(processor.clone());
+    let outcome = executor.execute_chain(&commands).await;
+    if let Some(changed) = outcome.as_ref().ok().and_then(|result| result.changed) {
+        slog::info!(
+            logger,
+            "Manifests {}",
+            if changed {
+                "have changes"
+            } else {
+                "are up to date"
+            }
+        );
+        outputs.set("changed", changed);
+    }
+    if !resources.is_empty() {
+        outputs.set("resources", resources.join(","));
+    }
+
+    let outcome = outcome.map(|result| result.code);
+    RunSummary::new(format!("kubectl {}", cmd), metrics)
+        .with_outcome(&outcome)
+        .with_workflow_run()
+        .with_processor(processor)
+        .report(&logger);
+    match outputs.write() {
+        Ok(true) => slog::debug!(logger, "Step outputs were written"; "count" => outputs.len()),
+        Ok(false) => {}
+        Err(e) => slog::warn!(logger, "Failed to write step outputs"; "error" => e.to_string()),
+    }
+    let code = match outcome {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to run kubectl {}", cmd; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    if code == 0 {
+        slog::info!(logger, "Action {} was finished successfully", cmd);
+        return Ok(None);
+    }
+    let failure = Failure::Tool;
+    slog::error!(logger, "Action {} failed with status: {}", cmd, code;
+        "failure" => failure.as_str(), "exit_code" => failure.code());
+    Ok(Some(failure))
+}
+
+/// Classifies the error the action stopped with.
+fn classify(error: &(dyn std::error::Error + 'static)) -> Failure {
+    match error.downcast_ref::<KubectlError>() {
+        Some(KubectlError::ExecuterError(e)) => Failure::of_executer(e),
+        // Manifests are inputs of the action.
+        Some(KubectlError::ManifestError(_)) => Failure::Config,
+        Some(_) => Failure::Tool,
+        None => Failure::of_shared(error).unwrap_or(Failure::Tool),
+    }
+}
@@ -1,19 +1,87 @@
-use config::MainConfig;
-use processor::{MaskerEqual, MaskerRegex, ProcessorCollection, ProcessorItem};
+use config::{collect_errors, ConfigError, DryRunConfig, MainConfig};
+use executer::{Annotations, Metrics, Output, RetryPolicy, Target};
+use processor::{
+    MaskerEqual, MaskerRegex, MaskerRuntime, Processor, ProcessorCollection, ProcessorItem,
+};
 use terraform::{
-    executor::TerraformExecutor, CommandChain, TerraformBackend, TerraformConfig, TerraformEnv,
+    cloud::{Polling, TerraformCloud},
+    command::TerraformCommand,
+    debug_log::{DebugLog, ENV_TF_LOG_PATH},
+    error::TerraformError,
+    executor::{PlanOutcome, TerraformExecutor},
+    integrity::PlanManifest,
+    modules::resolve_modules,
+    plugin_cache::{PluginCache, ENV_TF_PLUGIN_CACHE_DIR},
+    response::Severity,
+    rollback::RollbackReport,
+    step::chain_status,
+    terragrunt::Terragrunt,
+    CommandChain, TerraformAction, TerraformBackend, TerraformConfig, TerraformEnv,
 };
 
 use provider::auto_detect;
-use util::init_logger;
+use util::{
+    exit_code, step_logger, write_step_summary, ActionResult, Failure, LogContext, LogFile,
+    LoggerBuilder, Outputs, RunSummary,
+};
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() -> std::process::ExitCode {
+    exit_code(run().await, classify)
+}
+
+async fn run() -> ActionResult {
     let main_config = MainConfig::new();
+    let dry_run_config = DryRunConfig::new();
     let tf_config = TerraformConfig::new();
 
+    if std::env::args().any(|arg| arg == "--print-inputs") {
+        let inputs = main_config
+            .inputs()
+            .extend(dry_run_config.inputs())
+            .extend(tf_config.inputs());
+        print!(
+            "{}{}",
+            inputs.render_action_inputs(),
+            inputs.render_action_env()
+        );
+        return Ok(None);
+    }
+
     let level = main_config.get_log_level().unwrap_or("info".to_string());
-    let logger = init_logger(&level);
+    let format = main_config
+        .get_log_format()
+        .unwrap_or(config::DEFAULT_LOG_FORMAT.to_string());
+    let log_file = main_config.get_log_file().ok().flatten().map(|path| {
+        LogFile::new(path)
+            .with_level(
+                &main_config
+                    .get_log_file_level()
+                    .unwrap_or(config::DEFAULT_LOG_FILE_LEVEL.to_string()),
+            )
+            .with_max_bytes(
+                main_config
+                    .get_log_file_max_bytes()
+                    .unwrap_or(config::DEFAULT_LOG_FILE_MAX_BYTES),
+            )
+    });
+    let (logger, log_switch) = LoggerBuilder::new()
+        .with_level(&level)
+        .with_format(&format)
+        .with_file(log_file)
+        .build();
+
+    if let Err(e) = collect_errors(vec![
+        main_config.validate_all(),
+        dry_run_config.validate_all(),
+        tf_config.validate_all(),
+    ]) {
+        let errors = e.into_errors();
+        for error in &errors {
+            slog::error!(logger, "Invalid configuration"; "error" => error.to_string());
+        }
+        return Err(ConfigError::Multiple(errors).into());
+    }
 
     let provider = match auto_detect() {
         Ok(v) => {
@@ -48,6 +116,387 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
+    let dry_run = match dry_run_config.get_dry_run() {
+        Ok(v) => {
+            if v && matches!(cmd, TerraformAction::Apply | TerraformAction::Destroy) {
+                slog::info!(logger, "Dry run: terraform {} only plans", cmd);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get dry-run option"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    if cmd == TerraformAction::Destroy {
+        match tf_config.get_allow_destroy() {
+            Ok(true) => slog::warn!(logger, "Terraform destroy is allowed"),
+            Ok(false) => {
+                slog::error!(logger, "Terraform destroy is not allowed");
+                return Err(TerraformError::DestroyNotAllowed(
+                    terraform::ENV_TERRAFORM_ALLOW_DESTROY.to_string(),
+                )
+                .into());
+            }
+            Err(e) => {
+                slog::error!(logger, "Failed to get terraform destroy flag"; "error" => e.to_string());
+                return Err(e.into());
+            }
+        }
+    }
+
+    let targets = match tf_config.get_targets() {
+        Ok(v) => {
+            if !v.is_empty() {
+                slog::info!(logger, "Terraform targets: {}", v.join(", "));
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform targets"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let fmt_recursive = match tf_config.get_fmt_recursive() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform fmt recursive flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let detailed_exitcode = match tf_config.get_detailed_exitcode() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform detailed exitcode flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let var_files = match tf_config.get_var_files() {
+        Ok(v) => {
+            for file in &v {
+                slog::info!(logger, "Terraform var file: {:?}", file);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform var files"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let parallelism = match tf_config.get_parallelism() {
+        Ok(v) => {
+            if let Some(n) = v {
+                slog::info!(logger, "Terraform parallelism: {}", n);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform parallelism"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let backend_config_file = match tf_config.get_backend_config_file() {
+        Ok(v) => {
+            if let Some(file) = &v {
+                slog::info!(logger, "Terraform backend config file: {:?}", file);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform backend config file"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let plan_summary_file = match tf_config.get_plan_summary_file() {
+        Ok(v) => {
+            if let Some(file) = &v {
+                slog::info!(logger, "Terraform plan summary file: {:?}", file);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform plan summary file"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let log_level = match tf_config.get_log_level() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform log level"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let log_file = match tf_config.get_log_path() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Invalid terraform log filepath"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    if let Some(level) = &log_level {
+        slog::info!(
+            logger,
+            "Terraform {} log is written to {:?}",
+            level,
+            log_file
+        );
+    }
+    // Terraform would write the debug log there unmasked.
+    std::env::remove_var(ENV_TF_LOG_PATH);
+
+    let plugin_cache = match tf_config.get_plugin_cache_dir() {
+        Ok(v) => v.map(|dir| PluginCache::new(cwd.join(dir))),
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform plugin cache dir"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    if let Some(cache) = &plugin_cache {
+        if let Err(e) = cache.prepare() {
+            slog::error!(logger, "Failed to create terraform plugin cache dir";
+                "dir" => cache.dir.display().to_string(), "error" => e.to_string());
+            return Err(e.into());
+        }
+        slog::info!(logger, "Terraform plugin cache: {:?}", cache.dir);
+    }
+
+    let init_upgrade = match tf_config.get_init_upgrade() {
+        Ok(v) => {
+            slog::debug!(logger, "Terraform init upgrade: {}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform init upgrade flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let no_color = match tf_config.get_no_color() {
+        Ok(v) => {
+            slog::debug!(logger, "Terraform no color: {}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform no color flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let saved_plan = match tf_config.get_saved_plan() {
+        Ok(v) => {
+            slog::debug!(logger, "Terraform apply of a saved plan: {}", v);
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform saved plan flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let commit_sha = match tf_config.get_commit_sha() {
+        Ok(v) => {
+            if let Some(sha) = &v {
+                slog::debug!(logger, "Terraform plan commit: {}", sha);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform commit sha"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let rollback_ref = match tf_config.get_rollback_ref() {
+        Ok(v) => {
+            if let Some(git_ref) = &v {
+                slog::debug!(logger, "Terraform rollback ref: {}", git_ref);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform rollback ref"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let replace = match tf_config.get_replace() {
+        Ok(v) => {
+            if !v.is_empty() {
+                slog::info!(logger, "Terraform resources to replace: {}", v.join(", "));
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform resources to replace"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let lock_timeout = match tf_config.get_lock_timeout() {
+        Ok(v) => {
+            if let Some(timeout) = &v {
+                slog::info!(logger, "Terraform state lock timeout: {}", timeout);
+            }
+            v
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform lock timeout"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+
+    let lock_retry_attempts = match tf_config.get_lock_retry_attempts() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform lock retry attempts"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let lock_retry_delay = match tf_config.get_lock_retry_delay() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform lock retry delay"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    // Locks are held for a whole run, so waits may grow well beyond the first delay.
+    let lock_retry = RetryPolicy::new(lock_retry_attempts, lock_retry_delay)
+        .with_max_delay(lock_retry_delay.saturating_mul(8));
+
+    let backend_retry_attempts = match tf_config.get_backend_retry_attempts() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform backend retry attempts"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let backend_retry_delay = match tf_config.get_backend_retry_delay() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform backend retry delay"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let backend_retry = RetryPolicy::new(backend_retry_attempts, backend_retry_delay);
+
+    let terragrunt = match tf_config.get_terragrunt() {
+        Ok(false) => None,
+        Ok(true) => {
+            let bin = match tf_config.get_terragrunt_bin() {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Invalid terragrunt bin filepath"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            let run_all = match tf_config.get_terragrunt_run_all() {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to get terragrunt run-all flag"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            let include_dirs = match tf_config.get_terragrunt_include_dirs() {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to get terragrunt include dirs"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            slog::info!(logger, "Running through terragrunt: {:?}", bin; "run_all" => run_all);
+            Some(
+                Terragrunt::new(bin)
+                    .with_run_all(run_all)
+                    .with_include_dirs(include_dirs),
+            )
+        }
+        Err(e) => {
+            slog::error!(logger, "Failed to get terragrunt flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let modules = match tf_config.get_modules() {
+        Ok(v) => match resolve_modules(&cwd, &v) {
+            Ok(modules) => modules,
+            Err(e) => {
+                slog::error!(logger, "Failed to resolve terraform modules"; "error" => e.to_string());
+                return Err(e.into());
+            }
+        },
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform modules"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let modules_parallel = match tf_config.get_modules_parallel() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform modules parallel flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    if !modules.is_empty() {
+        slog::info!(logger, "Terraform modules: {}", modules.len(); "parallel" => modules_parallel);
+    }
+
+    // Plans and outputs of several modules cannot be read from the working directory.
+    let single_module = !terragrunt.as_ref().is_some_and(|t| t.run_all) && modules.is_empty();
+
+    let cloud_poll = match tf_config.get_cloud_poll() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform cloud poll flag"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let cloud_poll_interval = match tf_config.get_cloud_poll_interval() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform cloud poll interval"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let cloud_poll_timeout = match tf_config.get_cloud_poll_timeout() {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to get terraform cloud poll timeout"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let cloud = TerraformCloud::detect(&cwd).map(|cloud| {
+        cloud.with_polling(cloud_poll.then_some(Polling {
+            interval: cloud_poll_interval,
+            timeout: cloud_poll_timeout,
+        }))
+    });
+    if let Some(cloud) = &cloud {
+        slog::info!(logger, "Terraform runs remotely on {}", cloud.hostname; "poll" => cloud_poll);
+        if cloud_poll && cloud.token.is_none() {
+            let e = TerraformError::CloudError(format!("no API token for {}", cloud.hostname));
+            slog::error!(logger, "Cannot poll remote runs"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    }
+    // Remote runs leave no plan file in the working directory.
+    let remote_runs = cloud.is_some();
+    let local_plan = single_module && !remote_runs;
+    if rollback_ref.is_some() && cmd == TerraformAction::Apply && !local_plan {
+        let e = TerraformError::RollbackError(
+            "rollback plans are not supported with several modules, terragrunt run-all or remote runs"
+                .to_string(),
+        );
+        slog::error!(logger, "Cannot plan rollbacks"; "error" => e.to_string());
+        return Err(e.into());
+    }
+
     let workspace: Option<String> = match tf_config.get_workspace() {
         Ok(v) => {
             if v.is_empty() {
@@ -113,37 +562,482 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let makser_terraform_backend = MaskerEqual::new(backend.values(), &mask);
     let masker_terraform_envs = MaskerEqual::new(envs.values(), &mask);
 
+    let report = main_config
+        .report()
+        .extend(dry_run_config.report())
+        .extend(tf_config.report());
+    let masker_config = MaskerEqual::new(report.sensitive_values(), &mask);
+    let masker_cloud_output = match MaskerRegex::new(TerraformCloud::patterns(), &mask) {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Failed to initialize maskers for terraform cloud"; "error" => e.to_string());
+            return Err(e.into());
+        }
+    };
+    let masker_cloud_token = MaskerEqual::new(
+        cloud.as_ref().map(|c| c.values()).unwrap_or_default(),
+        &mask,
+    );
+    let masker_runtime = MaskerRuntime::new(&mask);
+
     let processors = ProcessorCollection::new(vec![
         ProcessorItem::Regex(masker_provider_output),
         ProcessorItem::Equal(masker_provider_credentials),
         ProcessorItem::Equal(makser_terraform_backend),
         ProcessorItem::Equal(masker_terraform_envs),
+        ProcessorItem::Equal(masker_config),
+        ProcessorItem::Regex(masker_cloud_output),
+        ProcessorItem::Equal(masker_cloud_token),
+        ProcessorItem::Runtime(masker_runtime.clone()),
     ]);
+    let logger =
+        LogContext::from_env("terraform").logger(&util::masked(&logger, processors.clone()));
+    for line in report.render(&processors, &mask) {
+        slog::debug!(logger, "Config: {}", line);
+    }
     slog::info!(logger, "Action was initialized");
 
-    let executor = TerraformExecutor::new(processors, bin);
-    let chain = CommandChain::new(cwd)
+    let metrics = Metrics::new();
+    let annotations = Annotations::new();
+    let mut executor = TerraformExecutor::new(processors.clone(), bin)
+        .with_metrics(metrics.clone())
+        .with_annotations(annotations.clone())
+        .with_secrets(masker_runtime)
+        .with_lock_retry(lock_retry)
+        .with_backend_retry(backend_retry)
+        .with_debug_log(log_level.map(|level| {
+            let target = Target::File(log_file.clone());
+            DebugLog::new(
+                level,
+                Output::new(processors.clone(), target.clone(), target),
+            )
+        }));
+    if let Some(terragrunt) = terragrunt {
+        executor = executor.with_terragrunt(terragrunt);
+    }
+    if let Some(cloud) = cloud {
+        executor = executor.with_cloud(cloud);
+    }
+    if let Some(cache) = &plugin_cache {
+        executor = executor.with_env(ENV_TF_PLUGIN_CACHE_DIR, cache.dir.to_string_lossy());
+    }
+    let chain = CommandChain::new(cwd.clone())
         .with_vars(envs.as_map().clone())
+        .with_var_files(var_files)
         .with_backend_config(backend.environment)
+        .with_backend_config_file(backend_config_file)
+        .with_upgrade(init_upgrade)
+        .with_no_color(no_color)
         .with_workspace(workspace)
-        .with_out(Some(output));
+        .with_out((!remote_runs).then(|| output.clone()))
+        // Without a plan file, a remote apply plans again and asks for approval.
+        .with_auto_approve(remote_runs)
+        .with_targets(targets)
+        .with_replace(replace)
+        .with_parallelism(parallelism)
+        .with_lock_timeout(lock_timeout)
+        .with_detailed_exitcode(detailed_exitcode);
 
-    let commands = match cmd.as_str().to_lowercase().as_str() {
-        "plan" => chain.plan_chain(),
-        "apply" => chain.apply_chain(),
-        _ => {
-            let err = format!("Unsupported command: {}", cmd);
-            slog::error!(logger, "{}", err);
-            return Err(err.into());
+    let mut outputs = Outputs::new().with_processor(processors.clone());
+    let summary =
+        RunSummary::new(format!("terraform {}", cmd), metrics.clone()).with_dry_run(dry_run);
+    // Errors of the run end up in the report as well.
+    let outcome: Result<i32, Box<dyn std::error::Error>> = async {
+        if !modules.is_empty() && cmd != TerraformAction::FmtCheck {
+            if saved_plan && cmd == TerraformAction::Apply {
+                let e = TerraformError::PlanIntegrityError(
+                    "saved plans cannot be verified with several modules".to_string(),
+                );
+                slog::error!(logger, "Refusing to apply saved plans"; "error" => e.to_string());
+                return Err(e.into());
+            }
+            let chains = modules
+                .iter()
+                .map(|dir| {
+                    let chain = chain.clone().with_dir(dir.clone());
+                    let commands = match cmd {
+                        TerraformAction::Plan => chain.plan_chain(),
+                        TerraformAction::Apply if dry_run => chain.plan_chain(),
+                        TerraformAction::Apply => chain.apply_chain(),
+                        TerraformAction::Validate => {
+                            let mut commands = chain.init_chain();
+                            commands.push(TerraformCommand::Validate {
+                                dir: dir.clone(),
+                                json: false,
+                            });
+                            commands
+                        }
+                        TerraformAction::FmtCheck => Vec::new(),
+                        TerraformAction::Destroy if dry_run => chain.destroy_plan_chain(),
+                        TerraformAction::Destroy => chain.with_auto_approve(true).destroy_chain(),
+                    };
+                    (dir.clone(), commands)
+                })
+                .collect();
+            slog::info!(
+                logger,
+                "Starting terraform {} chain in {} modules",
+                cmd,
+                modules.len()
+            );
+
+            let report = executor.execute_modules(chains, modules_parallel).await;
+            for line in report.lines(&cwd) {
+                slog::info!(logger, "{}", processors.process(&line));
+            }
+            let markdown = processors.process(&report.markdown(&cwd));
+            match write_step_summary(&markdown) {
+                Ok(true) => slog::debug!(logger, "Module report was added to the job summary"),
+                Ok(false) => {}
+                Err(e) => slog::warn!(logger, "Failed to write job summary"; "error" => e.to_string()),
+            }
+            outputs.set("modules_failed", report.failed());
+            return Ok(report.exit_code());
         }
-    };
-    slog::info!(logger, "Starting terraform plan chain"; "steps" => commands.len());
 
-    let result = executor.execute_chain(commands).await?;
+        let plan_file = cwd.join(&output);
+        let mut commands = match cmd {
+            TerraformAction::Plan => chain.plan_chain(),
+            TerraformAction::Apply if saved_plan => {
+                let verified = if local_plan {
+                    PlanManifest::read(&plan_file)
+                        .and_then(|manifest| manifest.verify(&plan_file, commit_sha.as_deref()))
+                } else {
+                    Err(TerraformError::PlanIntegrityError(
+                        "saved plans cannot be verified with terragrunt run-all or remote runs"
+                            .to_string(),
+                    ))
+                };
+                if let Err(e) = verified {
+                    slog::error!(logger, "Refusing to apply the saved plan"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+                slog::info!(logger, "Saved terraform plan was verified"; "file" => plan_file.display().to_string());
+                if dry_run {
+                    // The saved plan is summarized below instead of being applied.
+                    chain.init_chain()
+                } else {
+                    chain.saved_plan_apply_chain()
+                }
+            }
+            TerraformAction::Apply if dry_run => chain.plan_chain(),
+            TerraformAction::Apply => chain.apply_chain(),
+            TerraformAction::Validate => chain.init_chain(),
+            TerraformAction::FmtCheck => Vec::new(),
+            TerraformAction::Destroy if dry_run => chain.destroy_plan_chain(),
+            TerraformAction::Destroy => chain.clone().with_auto_approve(true).destroy_chain(),
+        };
+        // The plan is run on its own to learn whether it has changes.
+        let plan = match cmd {
+            TerraformAction::Plan if detailed_exitcode => commands.pop(),
+            _ => None,
+        };
+        // The apply waits for the plan to be inspected, so its sensitive values are masked.
+        let apply = match cmd {
+            TerraformAction::Apply if !dry_run => commands.pop(),
+            _ => None,
+        };
+        slog::info!(logger, "Starting terraform {} chain", cmd; "steps" => commands.len());
+
+        let cached = plugin_cache
+            .as_ref()
+            .filter(|_| single_module && !commands.is_empty())
+            .map(|cache| (cache, cache.snapshot()));
+        let steps = executor.execute_chain(commands).await;
+        for (index, step) in steps.iter().enumerate() {
+            let record = step.record(index, steps.len());
+            let logger = step_logger(&logger, index, steps.len(), &step.command.operation());
+            if step.is_failure() {
+                slog::error!(logger, "{}", record);
+                // Whatever runs after a failure, such as the rollback plan, is logged in full.
+                log_switch.raise(slog::Level::Debug);
+            } else {
+                slog::info!(logger, "{}", record);
+            }
+        }
+        let mut result = chain_status(steps)?;
+        if let Some((cache, before)) = cached {
+            match cache.usage(&before, &cwd) {
+                Ok(usage) => {
+                    slog::info!(logger, "Terraform providers from the plugin cache";
+                        "hits" => usage.hits.len(), "misses" => usage.misses.len());
+                    if let Err(e) = write_step_summary(&usage.markdown()) {
+                        slog::warn!(logger, "Failed to write job summary"; "error" => e.to_string());
+                    }
+                }
+                Err(e) => {
+                    slog::warn!(logger, "Failed to read the dependency lock file"; "error" => e.to_string())
+                }
+            }
+        }
+        if let (0, Some(plan)) = (result, plan) {
+            match executor.execute_plan(plan).await? {
+                PlanOutcome::NoChanges => {
+                    slog::info!(logger, "Terraform plan has no changes");
+                    outputs.set("has_changes", false);
+                }
+                PlanOutcome::ChangesPresent => {
+                    slog::info!(logger, "Terraform plan has changes");
+                    outputs.set("has_changes", true);
+                }
+                PlanOutcome::Unknown => {
+                    slog::info!(logger, "Terraform plan succeeded, enable detailed exit codes to report its changes");
+                }
+                PlanOutcome::Failed(code) => result = code,
+            }
+        }
+        if result == 0 && local_plan && cmd == TerraformAction::Plan {
+            let manifest = match PlanManifest::for_plan(&plan_file, commit_sha.clone()) {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to checksum terraform plan"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            if let Err(e) = manifest.write(&plan_file) {
+                slog::error!(logger, "Failed to write terraform plan manifest"; "error" => e.to_string());
+                return Err(e.into());
+            }
+            slog::info!(logger, "Terraform plan manifest was written"; "sha256" => &manifest.sha256);
+        }
+        let planned = match cmd {
+            TerraformAction::Plan | TerraformAction::Apply => true,
+            TerraformAction::Destroy => dry_run,
+            TerraformAction::Validate | TerraformAction::FmtCheck => false,
+        };
+        if result == 0 && local_plan && planned {
+            let summary = match executor.show_plan(cwd.clone(), output).await {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to summarize terraform plan"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            for resource in &summary.resources {
+                slog::info!(logger, "{} {}", resource.action.as_str(), resource.address);
+            }
+            slog::info!(logger, "{}", summary.headline());
+            outputs.set("plan_add", summary.add);
+            outputs.set("plan_change", summary.change);
+            outputs.set("plan_destroy", summary.destroy);
+            outputs.set("has_changes", summary.has_changes());
+
+            let markdown = processors.process(&summary.markdown());
+            match write_step_summary(&markdown) {
+                Ok(true) => slog::debug!(logger, "Plan summary was added to the job summary"),
+                Ok(false) => {}
+                Err(e) => slog::warn!(logger, "Failed to write job summary"; "error" => e.to_string()),
+            }
+            if let Some(file) = &plan_summary_file {
+                if let Err(e) = std::fs::write(file, &markdown) {
+                    slog::error!(logger, "Failed to write plan summary"; "file" => file.display().to_string(), "error" => e.to_string());
+                    return Err(e.into());
+                }
+            }
+        }
+        if let (0, Some(apply)) = (result, apply) {
+            // The snapshot tells what a failed apply changed before the rollback is planned.
+            let snapshot = match &rollback_ref {
+                Some(_) => match executor.snapshot_state(cwd.clone()).await {
+                    Ok(v) => Some(v),
+                    Err(e) => {
+                        slog::warn!(logger, "Failed to snapshot terraform state, no rollback will be planned"; "error" => e.to_string());
+                        None
+                    }
+                },
+                None => None,
+            };
+            result = executor.execute(apply).await?;
+            if let (true, Some(git_ref), Some(before)) = (result != 0, &rollback_ref, snapshot) {
+                slog::warn!(
+                    logger,
+                    "Terraform apply failed, planning a rollback to {}",
+                    git_ref
+                );
+                let report = match executor.snapshot_state(cwd.clone()).await {
+                    Ok(after) => {
+                        executor
+                            .rollback_plan(&chain, git_ref)
+                            .await
+                            .map(|plan| RollbackReport {
+                                git_ref: git_ref.clone(),
+                                changes: before.changes(&after),
+                                plan,
+                            })
+                    }
+                    Err(e) => Err(e),
+                };
+                match report {
+                    Ok(report) => {
+                        for resource in &report.plan.resources {
+                            slog::info!(
+                                logger,
+                                "rollback: {} {}",
+                                resource.action.as_str(),
+                                resource.address
+                            );
+                        }
+                        slog::warn!(logger, "Terraform rollback plan: {}", report.plan.headline();
+                            "state_written" => report.changes.state_written(),
+                            "changed_outputs" => report.changes.changed_outputs.join(","));
+                        outputs.set("rollback_has_changes", report.plan.has_changes());
+                        match write_step_summary(&processors.process(&report.markdown())) {
+                            Ok(true) => {
+                                slog::debug!(logger, "Rollback plan was added to the job summary")
+                            }
+                            Ok(false) => {}
+                            Err(e) => {
+                                slog::warn!(logger, "Failed to write job summary"; "error" => e.to_string())
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        slog::error!(logger, "Failed to plan terraform rollback"; "error" => e.to_string())
+                    }
+                }
+            }
+        }
+        if cmd == TerraformAction::FmtCheck {
+            let check = match executor.fmt_check(cwd.clone(), fmt_recursive).await {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to check terraform formatting"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            for (file, annotation) in check.files.iter().zip(check.annotations()) {
+                slog::error!(logger, "File is not formatted"; "file" => file);
+                println!("{}", processors.process(&annotation));
+            }
+            if !check.is_formatted() {
+                slog::info!(logger, "Formatting differences:");
+                executor
+                    .execute(TerraformCommand::Fmt {
+                        dir: cwd.clone(),
+                        check: true,
+                        recursive: fmt_recursive,
+                        diff: true,
+                    })
+                    .await?;
+                result = 1;
+            }
+            slog::info!(logger, "Terraform fmt check finished"; "unformatted" => check.files.len());
+        }
+        if result == 0 && cmd == TerraformAction::Validate {
+            let validation = match executor.validate(cwd.clone()).await {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to validate terraform configuration"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+            for diagnostic in &validation.diagnostics {
+                let location = diagnostic
+                    .range
+                    .as_ref()
+                    .map(|r| format!("{}:{}:{}", r.filename, r.start.line, r.start.column))
+                    .unwrap_or_default();
+                match diagnostic.severity {
+                    Severity::Error => slog::error!(logger, "{}", diagnostic.summary;
+                        "location" => location, "detail" => &diagnostic.detail),
+                    Severity::Warning => slog::warn!(logger, "{}", diagnostic.summary;
+                        "location" => location, "detail" => &diagnostic.detail),
+                }
+                println!("{}", processors.process(&diagnostic.annotation()));
+            }
+            slog::info!(logger, "Terraform validation finished";
+                "valid" => validation.valid,
+                "errors" => validation.error_count,
+                "warnings" => validation.warning_count);
+            if !validation.valid {
+                result = 1;
+            }
+        }
+        if result == 0 && single_module && cmd == TerraformAction::Apply {
+            let tf_outputs = match executor.outputs(cwd).await {
+                Ok(v) => v,
+                Err(e) => {
+                    slog::error!(logger, "Failed to read terraform outputs"; "error" => e.to_string());
+                    return Err(e.into());
+                }
+            };
+
+            for (name, output) in &tf_outputs {
+                if output.sensitive {
+                    slog::debug!(logger, "Skipping sensitive terraform output"; "name" => name);
+                } else {
+                    outputs.set(name, output.render());
+                }
+            }
+        }
+        Ok(result)
+    }
+    .await;
+    finish(
+        &logger,
+        &outputs,
+        summary,
+        &annotations,
+        &processors,
+        cmd,
+        outcome,
+    )
+}
+
+/// Prints the collected annotations, writes the run summary and the step outputs
+/// and logs the result of the action, whether it finished or stopped with an error.
+fn finish(
+    logger: &slog::Logger,
+    outputs: &Outputs,
+    summary: RunSummary,
+    annotations: &Annotations,
+    processors: &ProcessorCollection,
+    cmd: TerraformAction,
+    outcome: Result<i32, Box<dyn std::error::Error>>,
+) -> ActionResult {
+    for annotation in annotations.take() {
+        println!("{}", processors.process(&annotation.render()));
+    }
+    summary
+        .with_outcome(&outcome)
+        .with_workflow_run()
+        .with_processor(processors.clone())
+        .report(logger);
+    match outputs.write() {
+        Ok(true) => slog::debug!(logger, "Step outputs were written"; "count" => outputs.len()),
+        Ok(false) => {}
+        Err(e) => slog::warn!(logger, "Failed to write step outputs"; "error" => e.to_string()),
+    }
+    let result = match outcome {
+        Ok(v) => v,
+        Err(e) => {
+            slog::error!(logger, "Action {} failed", cmd; "error" => e.to_string());
+            return Err(e);
+        }
+    };
     if result == 0 {
         slog::info!(logger, "Action {} was finished successfully", cmd);
-    } else {
-        slog::error!(logger, "Action {} failed with status: {}", cmd, result);
+        return Ok(None);
+    }
+    // Validate and fmt check fail on the configuration rather than on terraform.
+    let failure = match cmd {
+        TerraformAction::Validate | TerraformAction::FmtCheck => Failure::Validation,
+        _ => Failure::Tool,
+    };
+    slog::error!(logger, "Action {} failed with status: {}", cmd, result;
+        "failure" => failure.as_str(), "exit_code" => failure.code());
+    Ok(Some(failure))
+}
+
+/// Classifies the error the action stopped with.
+fn classify(error: &(dyn std::error::Error + 'static)) -> Failure {
+    match error.downcast_ref::<TerraformError>() {
+        Some(TerraformError::ExecuterError(e)) => Failure::of_executer(e),
+        Some(TerraformError::PlanIntegrityError(_)) => Failure::Validation,
+        Some(TerraformError::DestroyNotAllowed(_) | TerraformError::ModuleError(_)) => {
+            Failure::Config
+        }
+        Some(_) => Failure::Tool,
+        None => Failure::of_shared(error).unwrap_or(Failure::Tool),
     }
-    Ok(())
 }
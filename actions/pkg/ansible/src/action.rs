@@ -0,0 +1,67 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Represents the actions supported by the Ansible binary.
+///
+/// Parsed from the `ACTION_ANSIBLE_CMD` environment variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnsibleAction {
+    /// Run the playbooks.
+    Playbook,
+
+    /// Run the playbooks in check mode, reporting what would change.
+    Check,
+
+    /// Check the syntax of the playbooks without connecting to any host.
+    SyntaxCheck,
+}
+
+impl AnsibleAction {
+    /// All supported actions, in the form accepted by `FromStr`.
+    pub const VARIANTS: &'static [&'static str] = &["playbook", "check", "syntax-check"];
+
+    /// Returns the string representation of the action.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Playbook => "playbook",
+            Self::Check => "check",
+            Self::SyntaxCheck => "syntax-check",
+        }
+    }
+}
+
+impl FromStr for AnsibleAction {
+    type Err = String;
+
+    /// Parses an action name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ansible::AnsibleAction;
+    ///
+    /// let action: AnsibleAction = "check".parse().unwrap();
+    /// assert_eq!(action, AnsibleAction::Check);
+    ///
+    /// let err = "run".parse::<AnsibleAction>().unwrap_err();
+    /// assert!(err.contains("playbook"));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "playbook" => Ok(Self::Playbook),
+            "check" => Ok(Self::Check),
+            "syntax-check" => Ok(Self::SyntaxCheck),
+            _ => Err(format!(
+                "unknown command '{}', expected one of: {}",
+                s,
+                Self::VARIANTS.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for AnsibleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,112 @@
+use crate::action::AnsibleAction;
+use std::path::PathBuf;
+
+/// An extra variable passed to the playbooks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtraVar {
+    pub key: String,
+    pub value: String,
+    /// Whether the value is masked in the output.
+    pub sensitive: bool,
+}
+
+impl ExtraVar {
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+            sensitive: false,
+        }
+    }
+
+    pub fn sensitive(mut self) -> Self {
+        self.sensitive = true;
+        self
+    }
+}
+
+/// An `ansible-playbook` run.
+///
+/// Extra variables, the vault password and the private key are passed as
+/// files by the executor, so they never appear on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaybookCommand {
+    pub action: AnsibleAction,
+    pub playbooks: Vec<PathBuf>,
+    pub inventory: Option<String>,
+    pub limit: Option<String>,
+    pub tags: Vec<String>,
+    pub skip_tags: Vec<String>,
+    pub diff: bool,
+    pub verbosity: u8,
+    pub extra_vars: Vec<ExtraVar>,
+}
+
+impl PlaybookCommand {
+    pub fn new(action: AnsibleAction, playbooks: Vec<PathBuf>) -> Self {
+        Self {
+            action,
+            playbooks,
+            inventory: None,
+            limit: None,
+            tags: Vec::new(),
+            skip_tags: Vec::new(),
+            diff: false,
+            verbosity: 0,
+            extra_vars: Vec::new(),
+        }
+    }
+
+    /// Converts the command into `ansible-playbook` arguments.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ansible::command::PlaybookCommand;
+    /// use ansible::AnsibleAction;
+    /// use std::path::PathBuf;
+    ///
+    /// let mut command = PlaybookCommand::new(AnsibleAction::Check, vec![PathBuf::from("site.yml")]);
+    /// command.inventory = Some("inventories/prod".to_string());
+    /// command.tags = vec!["deploy".to_string(), "nginx".to_string()];
+    /// command.diff = true;
+    /// command.verbosity = 2;
+    /// assert_eq!(
+    ///     command.to_args(),
+    ///     [
+    ///         "site.yml", "--inventory=inventories/prod", "--tags=deploy,nginx", "--check",
+    ///         "--diff", "-vv",
+    ///     ]
+    /// );
+    /// ```
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = self
+            .playbooks
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        if let Some(inventory) = &self.inventory {
+            args.push(format!("--inventory={}", inventory));
+        }
+        if let Some(limit) = &self.limit {
+            args.push(format!("--limit={}", limit));
+        }
+        for (flag, tags) in [("--tags", &self.tags), ("--skip-tags", &self.skip_tags)] {
+            if !tags.is_empty() {
+                args.push(format!("{}={}", flag, tags.join(",")));
+            }
+        }
+        match self.action {
+            AnsibleAction::Playbook => {}
+            AnsibleAction::Check => args.push("--check".to_string()),
+            AnsibleAction::SyntaxCheck => args.push("--syntax-check".to_string()),
+        }
+        if self.diff {
+            args.push("--diff".to_string());
+        }
+        if self.verbosity > 0 {
+            args.push(format!("-{}", "v".repeat(self.verbosity.into())));
+        }
+        args
+    }
+}
@@ -0,0 +1,100 @@
+use config::{ConfigValue, FileExists, Number, Required};
+use lazy_static::lazy_static;
+use std::path::PathBuf;
+
+/// ENV keys
+pub const ENV_ANSIBLE_CMD: &str = "ACTION_ANSIBLE_CMD";
+pub const ENV_ANSIBLE_BIN: &str = "ACTION_ANSIBLE_BIN";
+pub const ENV_ANSIBLE_PLAYBOOKS: &str = "ACTION_ANSIBLE_PLAYBOOKS";
+pub const ENV_ANSIBLE_INVENTORY: &str = "ACTION_ANSIBLE_INVENTORY";
+pub const ENV_ANSIBLE_LIMIT: &str = "ACTION_ANSIBLE_LIMIT";
+pub const ENV_ANSIBLE_TAGS: &str = "ACTION_ANSIBLE_TAGS";
+pub const ENV_ANSIBLE_SKIP_TAGS: &str = "ACTION_ANSIBLE_SKIP_TAGS";
+pub const ENV_ANSIBLE_EXTRA_VARS: &str = "ACTION_ANSIBLE_EXTRA_VARS";
+pub const ENV_ANSIBLE_EXTRA_VARS_SENSITIVE: &str = "ACTION_ANSIBLE_EXTRA_VARS_SENSITIVE";
+pub const ENV_ANSIBLE_VAULT_PASSWORD: &str = "ACTION_ANSIBLE_VAULT_PASSWORD";
+pub const ENV_ANSIBLE_PRIVATE_KEY: &str = "ACTION_ANSIBLE_PRIVATE_KEY";
+pub const ENV_ANSIBLE_DIFF: &str = "ACTION_ANSIBLE_DIFF";
+pub const ENV_ANSIBLE_VERBOSITY: &str = "ACTION_ANSIBLE_VERBOSITY";
+
+/// Default values
+pub const DEFAULT_ANSIBLE_BIN: &str = "/usr/local/bin/ansible-playbook";
+pub const DEFAULT_EMPTY: &str = "";
+pub const DEFAULT_VERBOSITY: u8 = 0;
+/// Highest verbosity, `-vvvv`.
+pub const MAX_VERBOSITY: u8 = 4;
+
+lazy_static! {
+    /// Configuration value for the Ansible command.
+    pub static ref CMD: ConfigValue<Required> =
+        ConfigValue::<Required>::required(ENV_ANSIBLE_CMD)
+            .with_description("Ansible command to run: playbook, check or syntax-check.");
+
+    /// Configuration value for the ansible-playbook executable path.
+    pub static ref ANSIBLE_BIN: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_ANSIBLE_BIN), ENV_ANSIBLE_BIN)
+            .expand()
+            .with_validator(FileExists)
+            .with_description("Path to the ansible-playbook executable.");
+
+    /// Configuration value for the playbooks.
+    pub static ref PLAYBOOKS: ConfigValue<Required> =
+        ConfigValue::<Required>::required(ENV_ANSIBLE_PLAYBOOKS)
+            .with_description("Comma-separated playbooks, relative to the working directory, run in order.");
+
+    /// Configuration value for the inventory.
+    pub static ref INVENTORY: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_ANSIBLE_INVENTORY)
+            .with_description("Inventory file, directory or comma-separated host list; defaults to the inventory of ansible.cfg.");
+
+    /// Configuration value for the host limit.
+    pub static ref LIMIT: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_ANSIBLE_LIMIT)
+            .with_description("Host pattern the playbooks are limited to.");
+
+    /// Configuration value for the tags.
+    pub static ref TAGS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_ANSIBLE_TAGS)
+            .with_description("Comma-separated tags of the tasks to run.");
+
+    /// Configuration value for the skipped tags.
+    pub static ref SKIP_TAGS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_ANSIBLE_SKIP_TAGS)
+            .with_description("Comma-separated tags of the tasks to skip.");
+
+    /// Configuration value for the extra variables.
+    pub static ref EXTRA_VARS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_ANSIBLE_EXTRA_VARS)
+            .with_validator(crate::validate_extra_vars)
+            .with_description("Extra variables, one key=value per line.");
+
+    /// Configuration value for the sensitive extra variables.
+    pub static ref EXTRA_VARS_SENSITIVE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_ANSIBLE_EXTRA_VARS_SENSITIVE)
+            .sensitive()
+            .with_validator(crate::validate_extra_vars)
+            .with_description("Extra variables masked in the output, one key=value per line.");
+
+    /// Configuration value for the vault password.
+    pub static ref VAULT_PASSWORD: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_ANSIBLE_VAULT_PASSWORD)
+            .sensitive()
+            .with_description("Password of the vault-encrypted files and variables.");
+
+    /// Configuration value for the SSH private key.
+    pub static ref PRIVATE_KEY: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_ANSIBLE_PRIVATE_KEY)
+            .sensitive()
+            .with_description("SSH private key used to connect to the hosts.");
+
+    /// Configuration value for showing diffs.
+    pub static ref DIFF: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_ANSIBLE_DIFF)
+            .with_description("Show the changes made to files and templates.");
+
+    /// Configuration value for the verbosity.
+    pub static ref VERBOSITY: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_VERBOSITY.to_string(), ENV_ANSIBLE_VERBOSITY)
+            .with_validator(Number::<u8>::new("a verbosity from 0 to 4").at_most(MAX_VERBOSITY))
+            .with_description("Verbosity of ansible-playbook, from 0 to 4.");
+}
@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+/// Represents the different errors that can occur when running playbooks.
+#[derive(Error, Debug)]
+pub enum AnsibleError {
+    /// Error when a file passed to ansible-playbook cannot be written.
+    #[error("Failed to write file for ansible-playbook: {0}")]
+    FileError(#[from] std::io::Error),
+
+    /// Error from the underlying executor.
+    #[error(transparent)]
+    ExecuterError(#[from] executer::ExecuterError),
+}
+
+/// A type alias for results returned by Ansible operations.
+pub type AnsibleResult<T> = Result<T, AnsibleError>;
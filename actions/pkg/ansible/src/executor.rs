@@ -0,0 +1,266 @@
+use crate::command::PlaybookCommand;
+use crate::error::AnsibleResult;
+use crate::recap::{Recap, RecapParser};
+use executer::{Context, Metrics, Output, Subprocess, Target, Validator};
+use processor::{MaskerRuntime, ProcessorCollection};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Environment variables that keep colors out of the output, so it can be
+/// masked and parsed.
+const ENV_NOCOLOR: &str = "ANSIBLE_NOCOLOR";
+const ENV_FORCE_COLOR: &str = "ANSIBLE_FORCE_COLOR";
+
+/// The result of a playbook run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaybookResult {
+    /// Exit status of `ansible-playbook`.
+    pub code: i32,
+    /// Recap of the run; empty when the run ended before it.
+    pub recap: Recap,
+}
+
+/// Executor responsible for running playbooks.
+pub struct AnsibleExecutor {
+    subprocess: Subprocess,
+    ansible_path: PathBuf,
+    dir: Option<PathBuf>,
+    files_dir: PathBuf,
+    vault_password: Option<String>,
+    private_key: Option<String>,
+    secrets: Option<MaskerRuntime>,
+}
+
+impl AnsibleExecutor {
+    /// Creates a new instance of `AnsibleExecutor`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ansible::executor::AnsibleExecutor;
+    /// use processor::ProcessorCollection;
+    /// use std::path::PathBuf;
+    ///
+    /// let executor = AnsibleExecutor::new(
+    ///     ProcessorCollection::new(vec![]),
+    ///     PathBuf::from("/usr/local/bin/ansible-playbook"),
+    /// )
+    /// .with_vault_password(Some("s3cr3t".to_string()));
+    /// ```
+    pub fn new(processor: ProcessorCollection, ansible_path: PathBuf) -> Self {
+        let output = Output::new(processor, Target::Stdout, Target::Stderr);
+        let subprocess = Subprocess::new(output, Validator::default());
+
+        Self {
+            subprocess,
+            ansible_path,
+            dir: None,
+            files_dir: std::env::temp_dir().join(format!("action-ansible-{}", std::process::id())),
+            vault_password: None,
+            private_key: None,
+            secrets: None,
+        }
+    }
+
+    /// Sets the directory playbooks and the inventory are resolved against.
+    pub fn with_working_dir(mut self, dir: PathBuf) -> Self {
+        self.dir = Some(dir);
+        self
+    }
+
+    /// Sets the directory the extra variables, vault password and private
+    /// key are written to for a run; it is removed afterwards.
+    pub fn with_files_dir(mut self, dir: PathBuf) -> Self {
+        self.files_dir = dir;
+        self
+    }
+
+    pub fn with_vault_password(mut self, password: Option<String>) -> Self {
+        self.vault_password = password;
+        self
+    }
+
+    /// Sets the SSH private key used to connect to the hosts.
+    pub fn with_private_key(mut self, key: Option<String>) -> Self {
+        self.private_key = key;
+        self
+    }
+
+    /// Registers the sensitive extra variables of each run and the lines of
+    /// the private key on `secrets`.
+    ///
+    /// `secrets` should be a clone of a `MaskerRuntime` that is part of the
+    /// processor collection, so the values are masked in the output.
+    pub fn with_secrets(mut self, secrets: MaskerRuntime) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
+    /// Records command timings and masked output lines on `metrics`.
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.subprocess = self.subprocess.with_metrics(metrics);
+        self
+    }
+
+    /// Writes the files of a run and returns the arguments passing them.
+    fn write_files(&self, command: &PlaybookCommand) -> io::Result<Vec<String>> {
+        let mut args = Vec::new();
+        if command.extra_vars.is_empty()
+            && self.vault_password.is_none()
+            && self.private_key.is_none()
+        {
+            return Ok(args);
+        }
+        fs::create_dir_all(&self.files_dir)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&self.files_dir, fs::Permissions::from_mode(0o700))?;
+        }
+        if !command.extra_vars.is_empty() {
+            let vars: serde_json::Map<String, serde_json::Value> = command
+                .extra_vars
+                .iter()
+                .map(|v| (v.key.clone(), serde_json::Value::String(v.value.clone())))
+                .collect();
+            let path = self.files_dir.join("extra-vars.json");
+            write_private(&path, &serde_json::Value::Object(vars).to_string())?;
+            args.push(format!("--extra-vars=@{}", path.display()));
+        }
+        if let Some(password) = &self.vault_password {
+            let path = self.files_dir.join("vault-password");
+            write_private(&path, password)?;
+            args.push(format!("--vault-password-file={}", path.display()));
+        }
+        if let Some(key) = &self.private_key {
+            // OpenSSH rejects keys without a final newline.
+            let path = self.files_dir.join("private-key");
+            write_private(&path, &format!("{}\n", key.trim_end()))?;
+            args.push(format!("--private-key={}", path.display()));
+        }
+        Ok(args)
+    }
+
+    /// Runs a playbook command and collects its recap.
+    ///
+    /// The secrets of the run are registered before it runs.
+    pub async fn execute(&self, command: &PlaybookCommand) -> AnsibleResult<PlaybookResult> {
+        if let Some(secrets) = &self.secrets {
+            for var in command.extra_vars.iter().filter(|v| v.sensitive) {
+                secrets.add(&var.value);
+            }
+            // Output is masked line by line, so each line of the key is registered.
+            for line in self.private_key.iter().flat_map(|key| key.lines()) {
+                let line = line.trim();
+                if !line.is_empty() && !line.starts_with("-----") {
+                    secrets.add(line);
+                }
+            }
+        }
+        let mut cmd = vec![self.ansible_path.to_string_lossy().to_string()];
+        cmd.extend(command.to_args());
+        let files = self.write_files(command);
+        let result = match files {
+            Ok(args) => {
+                cmd.extend(args);
+                self.run(cmd).await
+            }
+            Err(e) => Err(e.into()),
+        };
+        if self.files_dir.exists() {
+            fs::remove_dir_all(&self.files_dir)?;
+        }
+        result
+    }
+
+    async fn run(&self, cmd: Vec<String>) -> AnsibleResult<PlaybookResult> {
+        let env = HashMap::from([
+            (ENV_NOCOLOR.to_string(), "1".to_string()),
+            (ENV_FORCE_COLOR.to_string(), "0".to_string()),
+        ]);
+        let context = Context::new(cmd, env, self.dir.clone());
+        let parser = Arc::new(Mutex::new(RecapParser::default()));
+        let hook = Arc::clone(&parser);
+        let code = self
+            .subprocess
+            .execute_filtered(context, move |line| {
+                if let Ok(mut parser) = hook.lock() {
+                    parser.line(line);
+                }
+                true
+            })
+            .await?;
+        let recap = parser
+            .lock()
+            .map(|mut parser| std::mem::take(&mut *parser).finish())
+            .unwrap_or_default();
+        Ok(PlaybookResult { code, recap })
+    }
+}
+
+/// Writes a file readable only by the current user.
+fn write_private(path: &Path, contents: &str) -> io::Result<()> {
+    fs::write(path, contents)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::AnsibleAction;
+    use crate::command::ExtraVar;
+    use testing::script;
+
+    #[tokio::test]
+    async fn test_playbook_files_and_recap() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = dir.path().join("files");
+        let calls = dir.path().join("calls");
+        let ansible = script(
+            &dir.path().join("ansible-playbook"),
+            &format!(
+                "echo \"$*\" >> {calls}\n\
+                 cat {files}/extra-vars.json {files}/vault-password >> {calls}\n\
+                 echo 'PLAY RECAP ****'\n\
+                 echo 'web1 : ok=2 changed=1 unreachable=0 failed=0 skipped=0 rescued=0 ignored=0'\n\
+                 exit 0",
+                calls = calls.display(),
+                files = files.display()
+            ),
+        );
+
+        let secrets = MaskerRuntime::new("****");
+        let executor = AnsibleExecutor::new(ProcessorCollection::new(vec![]), ansible)
+            .with_files_dir(files.clone())
+            .with_vault_password(Some("v4ult".to_string()))
+            .with_secrets(secrets.clone());
+        let mut command =
+            PlaybookCommand::new(AnsibleAction::Playbook, vec![PathBuf::from("site.yml")]);
+        command.extra_vars = vec![
+            ExtraVar::new("env", "prod"),
+            ExtraVar::new("db_password", "s3cr3t").sensitive(),
+        ];
+        let result = executor.execute(&command).await.unwrap();
+
+        assert_eq!(result.code, 0);
+        assert_eq!(result.recap.totals().changed, 1);
+        assert_eq!(secrets.len(), 1);
+        assert!(!files.exists());
+        let f = files.display();
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            format!(
+                "site.yml --extra-vars=@{f}/extra-vars.json --vault-password-file={f}/vault-password\n\
+                 {{\"db_password\":\"s3cr3t\",\"env\":\"prod\"}}v4ult"
+            )
+        );
+    }
+}
@@ -0,0 +1,97 @@
+pub mod action;
+pub mod command;
+pub mod constants;
+pub mod error;
+pub mod executor;
+pub mod recap;
+
+use std::path::PathBuf;
+
+pub use action::AnsibleAction;
+pub use command::{ExtraVar, PlaybookCommand};
+use config::{config_struct, non_empty, split_list, split_paths, ConfigError, ConfigResult};
+pub use constants::*;
+pub use executor::{AnsibleExecutor, PlaybookResult};
+pub use recap::{HostRecap, Recap};
+
+config_struct! {
+    /// Represents the configuration for Ansible operations.
+    pub struct AnsibleConfig {
+        /// Gets the Ansible command to execute.
+        get_cmd: AnsibleAction = CMD,
+        /// Gets the ansible-playbook executable path.
+        get_bin: PathBuf = ANSIBLE_BIN,
+        /// Gets the playbooks.
+        get_playbooks: Vec<PathBuf> = PLAYBOOKS => split_paths,
+        /// Gets the inventory.
+        get_inventory: Option<String> = INVENTORY => non_empty,
+        /// Gets the host limit.
+        get_limit: Option<String> = LIMIT => non_empty,
+        /// Gets the tags to run.
+        get_tags: Vec<String> = TAGS => split_list,
+        /// Gets the tags to skip.
+        get_skip_tags: Vec<String> = SKIP_TAGS => split_list,
+        /// Gets the extra variables.
+        get_extra_vars: Vec<ExtraVar> = EXTRA_VARS => split_extra_vars,
+        /// Gets the extra variables masked in the output.
+        get_extra_vars_sensitive: Vec<ExtraVar> = EXTRA_VARS_SENSITIVE => |s| split_extra_vars(s).into_iter().map(ExtraVar::sensitive).collect(),
+        /// Gets the vault password.
+        get_vault_password: Option<String> = VAULT_PASSWORD => non_empty,
+        /// Gets the SSH private key.
+        get_private_key: Option<String> = PRIVATE_KEY => non_empty,
+        /// Gets the diff flag.
+        get_diff: bool = DIFF,
+        /// Gets the verbosity.
+        get_verbosity: u8 = VERBOSITY as u8,
+    }
+}
+
+/// Splits `key=value` lines, skipping empty lines.
+fn split_extra_vars(s: String) -> Vec<ExtraVar> {
+    s.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| ExtraVar::new(key.trim(), value))
+        .collect()
+}
+
+/// Checks that each non-empty line is `key=value`.
+///
+/// The error names the line instead of its content, which may be sensitive.
+#[allow(clippy::ptr_arg)]
+pub(crate) fn validate_extra_vars(s: &String) -> ConfigResult<()> {
+    for (index, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match line.split_once('=') {
+            Some((key, _)) if !key.trim().is_empty() => {}
+            _ => {
+                return Err(ConfigError::invalid(
+                    format!("line {}", index + 1),
+                    "key=value",
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_extra_vars() {
+        assert_eq!(
+            split_extra_vars("env=prod\n\n release = 1.4.0\n".to_string()),
+            vec![
+                ExtraVar::new("env", "prod"),
+                ExtraVar::new("release", " 1.4.0"),
+            ]
+        );
+        assert!(validate_extra_vars(&"a=1\n\nb=".to_string()).is_ok());
+        let err = validate_extra_vars(&"a=1\ns3cr3t".to_string()).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        assert!(!err.to_string().contains("s3cr3t"));
+    }
+}
@@ -0,0 +1,179 @@
+use serde::Serialize;
+
+/// Header line the recap of a run starts with.
+const RECAP_HEADER: &str = "PLAY RECAP";
+
+/// Task counts of one host, from the `PLAY RECAP` of a run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HostRecap {
+    pub host: String,
+    pub ok: u32,
+    pub changed: u32,
+    pub unreachable: u32,
+    pub failed: u32,
+    pub skipped: u32,
+    pub rescued: u32,
+    pub ignored: u32,
+}
+
+impl HostRecap {
+    /// Parses one host line of a recap.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ansible::recap::HostRecap;
+    ///
+    /// let recap = HostRecap::parse(
+    ///     "web1  : ok=3    changed=1    unreachable=0    failed=0    skipped=2    rescued=0    ignored=0",
+    /// )
+    /// .unwrap();
+    /// assert_eq!((recap.host.as_str(), recap.ok, recap.changed, recap.skipped), ("web1", 3, 1, 2));
+    /// assert!(HostRecap::parse("TASK [Gathering Facts] ****").is_none());
+    /// ```
+    pub fn parse(line: &str) -> Option<Self> {
+        let (host, counts) = line.rsplit_once(" : ")?;
+        let mut recap = Self {
+            host: host.trim().to_string(),
+            ..Default::default()
+        };
+        for pair in counts.split_whitespace() {
+            let (key, value) = pair.split_once('=')?;
+            let value = value.parse().ok()?;
+            match key {
+                "ok" => recap.ok = value,
+                "changed" => recap.changed = value,
+                "unreachable" => recap.unreachable = value,
+                "failed" => recap.failed = value,
+                "skipped" => recap.skipped = value,
+                "rescued" => recap.rescued = value,
+                "ignored" => recap.ignored = value,
+                _ => {}
+            }
+        }
+        (!recap.host.is_empty()).then_some(recap)
+    }
+
+    /// Returns whether a task failed on the host or the host was unreachable.
+    pub fn is_failed(&self) -> bool {
+        self.failed > 0 || self.unreachable > 0
+    }
+}
+
+/// The `PLAY RECAP` of a run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recap {
+    pub hosts: Vec<HostRecap>,
+}
+
+impl Recap {
+    pub fn is_empty(&self) -> bool {
+        self.hosts.is_empty()
+    }
+
+    /// Sums the counts of all hosts.
+    pub fn totals(&self) -> HostRecap {
+        self.hosts
+            .iter()
+            .fold(HostRecap::default(), |mut total, host| {
+                total.ok += host.ok;
+                total.changed += host.changed;
+                total.unreachable += host.unreachable;
+                total.failed += host.failed;
+                total.skipped += host.skipped;
+                total.rescued += host.rescued;
+                total.ignored += host.ignored;
+                total
+            })
+    }
+
+    /// Returns the hosts a task failed on or that were unreachable.
+    pub fn failed_hosts(&self) -> Vec<&str> {
+        self.hosts
+            .iter()
+            .filter(|h| h.is_failed())
+            .map(|h| h.host.as_str())
+            .collect()
+    }
+
+    /// Renders the counts of each host as a JSON array.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.hosts).unwrap_or_default()
+    }
+
+    /// Renders the recap as markdown for the job summary.
+    pub fn markdown(&self) -> String {
+        let mut out = String::from(
+            "### Ansible recap\n\n\
+             | Host | Ok | Changed | Unreachable | Failed | Skipped | Rescued | Ignored |\n\
+             | --- | --- | --- | --- | --- | --- | --- | --- |\n",
+        );
+        for h in &self.hosts {
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {} | {} | {} | {} | {} |\n",
+                h.host, h.ok, h.changed, h.unreachable, h.failed, h.skipped, h.rescued, h.ignored
+            ));
+        }
+        out
+    }
+}
+
+/// Collects the recap from the output lines of a run.
+#[derive(Default)]
+pub(crate) struct RecapParser {
+    in_recap: bool,
+    recap: Recap,
+}
+
+impl RecapParser {
+    pub(crate) fn line(&mut self, line: &str) {
+        if line.starts_with(RECAP_HEADER) {
+            self.in_recap = true;
+            self.recap.hosts.clear();
+        } else if self.in_recap {
+            match HostRecap::parse(line) {
+                Some(host) => self.recap.hosts.push(host),
+                None if line.trim().is_empty() => {}
+                None => self.in_recap = false,
+            }
+        }
+    }
+
+    pub(crate) fn finish(self) -> Recap {
+        self.recap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser_collects_recap() {
+        let mut parser = RecapParser::default();
+        for line in [
+            "TASK [deploy : restart api] ****",
+            "changed: [web1]",
+            "fatal: [db1]: UNREACHABLE! => {\"changed\": false}",
+            "",
+            "PLAY RECAP *********************************************************************",
+            "db1                        : ok=0    changed=0    unreachable=1    failed=0    skipped=0    rescued=0    ignored=0",
+            "web1                       : ok=5    changed=2    unreachable=0    failed=1    skipped=1    rescued=0    ignored=0",
+            "",
+            "Playbook run took 0 days, 0 hours, 0 minutes, 12 seconds",
+        ] {
+            parser.line(line);
+        }
+        let recap = parser.finish();
+        assert_eq!(recap.hosts.len(), 2);
+        assert_eq!(recap.failed_hosts(), vec!["db1", "web1"]);
+        let totals = recap.totals();
+        assert_eq!((totals.ok, totals.changed, totals.failed), (5, 2, 1));
+        assert!(recap
+            .to_json()
+            .starts_with(r#"[{"host":"db1","ok":0,"changed":0,"unreachable":1,"#));
+        assert!(recap
+            .markdown()
+            .ends_with("| `web1` | 5 | 2 | 0 | 1 | 1 | 0 | 0 |\n"));
+    }
+}
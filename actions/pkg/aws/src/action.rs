@@ -0,0 +1,202 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Represents the actions supported by the AWS binary.
+///
+/// Parsed from the `ACTION_AWS_CMD` environment variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AwsAction {
+    /// Synchronize a local directory with an S3 bucket.
+    S3Sync,
+
+    /// Copy a single file to or from S3.
+    S3Cp,
+
+    /// Generate a presigned URL for an S3 object.
+    S3Presign,
+
+    /// Deploy a static site: assets, pages, cleanup and CDN invalidation.
+    StaticSite,
+
+    /// Update the code of a Lambda function and wait for the update.
+    LambdaUpdate,
+
+    /// Create or update a Route53 record set.
+    Route53Upsert,
+
+    /// Write a kubeconfig for an EKS cluster.
+    EksKubeconfig,
+
+    /// Log in to ECR and push a Docker image.
+    EcrPush,
+
+    /// Deploy a new task definition revision to an ECS service.
+    EcsDeploy,
+
+    /// Invalidate the cache of one or more CloudFront distributions.
+    CloudFrontInvalidate,
+
+    /// Print recent events of a CloudWatch Logs group.
+    LogsTail,
+
+    /// Deploy a stack with the SAM CLI.
+    SamDeploy,
+
+    /// Deploy stacks with the CDK CLI.
+    CdkDeploy,
+}
+
+impl AwsAction {
+    /// All supported actions, in the form accepted by `FromStr`.
+    pub const VARIANTS: &'static [&'static str] = &[
+        "s3_sync",
+        "s3_cp",
+        "s3_presign",
+        "static_site",
+        "lambda_update",
+        "route53_upsert",
+        "eks_kubeconfig",
+        "ecr_push",
+        "ecs_deploy",
+        "cloudfront_invalidate",
+        "logs_tail",
+        "sam_deploy",
+        "cdk_deploy",
+    ];
+
+    /// Returns the string representation of the action.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::S3Sync => "s3_sync",
+            Self::S3Cp => "s3_cp",
+            Self::S3Presign => "s3_presign",
+            Self::StaticSite => "static_site",
+            Self::LambdaUpdate => "lambda_update",
+            Self::Route53Upsert => "route53_upsert",
+            Self::EksKubeconfig => "eks_kubeconfig",
+            Self::EcrPush => "ecr_push",
+            Self::EcsDeploy => "ecs_deploy",
+            Self::CloudFrontInvalidate => "cloudfront_invalidate",
+            Self::LogsTail => "logs_tail",
+            Self::SamDeploy => "sam_deploy",
+            Self::CdkDeploy => "cdk_deploy",
+        }
+    }
+}
+
+impl FromStr for AwsAction {
+    type Err = String;
+
+    /// Parses an action name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::AwsAction;
+    ///
+    /// let action: AwsAction = "s3_sync".parse().unwrap();
+    /// assert_eq!(action, AwsAction::S3Sync);
+    ///
+    /// let err = "s3_copy".parse::<AwsAction>().unwrap_err();
+    /// assert!(err.contains("s3_sync"));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "s3_sync" => Ok(Self::S3Sync),
+            "s3_cp" => Ok(Self::S3Cp),
+            "s3_presign" => Ok(Self::S3Presign),
+            "static_site" => Ok(Self::StaticSite),
+            "lambda_update" => Ok(Self::LambdaUpdate),
+            "route53_upsert" => Ok(Self::Route53Upsert),
+            "eks_kubeconfig" => Ok(Self::EksKubeconfig),
+            "ecr_push" => Ok(Self::EcrPush),
+            "ecs_deploy" => Ok(Self::EcsDeploy),
+            "cloudfront_invalidate" => Ok(Self::CloudFrontInvalidate),
+            "logs_tail" => Ok(Self::LogsTail),
+            "sam_deploy" => Ok(Self::SamDeploy),
+            "cdk_deploy" => Ok(Self::CdkDeploy),
+            _ => Err(format!(
+                "unknown command '{}', expected one of: {}",
+                s,
+                Self::VARIANTS.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for AwsAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The actions of one run, in the order they run.
+///
+/// Parsed from a comma- or newline-separated `ACTION_AWS_CMD`, e.g.
+/// `s3_sync,cloudfront_invalidate`. All actions read the same inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AwsActions(Vec<AwsAction>);
+
+impl AwsActions {
+    pub fn iter(&self) -> impl Iterator<Item = AwsAction> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Always `false`: parsing rejects an empty list.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for AwsActions {
+    type Err = String;
+
+    /// Parses a list of action names.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::{AwsAction, AwsActions};
+    ///
+    /// let actions: AwsActions = "s3_sync, cloudfront_invalidate".parse().unwrap();
+    /// assert_eq!(
+    ///     actions.iter().collect::<Vec<_>>(),
+    ///     vec![AwsAction::S3Sync, AwsAction::CloudFrontInvalidate]
+    /// );
+    ///
+    /// let err = "s3_sync,s3_sync".parse::<AwsActions>().unwrap_err();
+    /// assert!(err.contains("more than once"));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut actions = Vec::new();
+        for name in s
+            .split([',', '\n'])
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+        {
+            let action: AwsAction = name.parse()?;
+            if actions.contains(&action) {
+                return Err(format!("command '{}' is listed more than once", action));
+            }
+            actions.push(action);
+        }
+        if actions.is_empty() {
+            return Err(format!(
+                "no command given, expected one or more of: {}",
+                AwsAction::VARIANTS.join(", ")
+            ));
+        }
+        Ok(Self(actions))
+    }
+}
+
+impl fmt::Display for AwsActions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.0.iter().map(AwsAction::as_str).collect();
+        write!(f, "{}", names.join(","))
+    }
+}
@@ -1,17 +1,46 @@
-use crate::command::AwsCommand;
-use std::collections::HashMap;
+use crate::command::{AwsCommand, LambdaCode, LambdaTarget, SyncOverride};
+use crate::constants::{
+    DEFAULT_ASSET_CACHE_CONTROL, DEFAULT_CLOUDFRONT_PATHS, DEFAULT_HTML_CACHE_CONTROL,
+};
+use crate::step::Step;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Clone)]
 pub struct CommandChain {
     dir: PathBuf,
     vars: HashMap<String, String>,
+    source: Option<PathBuf>,
     destination: Option<PathBuf>,
     exclude: Option<Vec<String>>,
     include: Option<Vec<String>>,
     delete: bool,
     dry_run: bool,
     force: bool,
+    cache_control: Option<String>,
+    metadata_directive: Option<String>,
+    storage_class: Option<String>,
+    sse: Option<String>,
+    overrides: Vec<SyncOverride>,
+    copy_source: Option<PathBuf>,
+    content_type: Option<String>,
+    acl: Option<String>,
+    metadata: Option<BTreeMap<String, String>>,
+    lambda_function: Option<String>,
+    lambda_zip: Option<PathBuf>,
+    lambda_image: Option<String>,
+    lambda_alias: Option<String>,
+    lambda_publish: bool,
+    lambda_targets: Vec<LambdaTarget>,
+    ecr_registry: Option<String>,
+    ecr_image: Option<String>,
+    cloudfront_distributions: Vec<String>,
+    cloudfront_paths: Vec<String>,
+    asset_cache_control: String,
+    html_cache_control: String,
+    tags: BTreeMap<String, String>,
+    tagged_resources: Vec<String>,
 }
 
 impl CommandChain {
@@ -19,12 +48,36 @@ impl CommandChain {
         Self {
             dir,
             vars: HashMap::new(),
+            source: None,
             destination: None,
             exclude: None,
             include: None,
             delete: false,
             dry_run: false,
             force: false,
+            cache_control: None,
+            metadata_directive: None,
+            storage_class: None,
+            sse: None,
+            overrides: Vec::new(),
+            copy_source: None,
+            content_type: None,
+            acl: None,
+            metadata: None,
+            lambda_function: None,
+            lambda_zip: None,
+            lambda_image: None,
+            lambda_alias: None,
+            lambda_publish: false,
+            lambda_targets: Vec::new(),
+            ecr_registry: None,
+            ecr_image: None,
+            cloudfront_distributions: Vec::new(),
+            cloudfront_paths: vec![DEFAULT_CLOUDFRONT_PATHS.to_string()],
+            asset_cache_control: DEFAULT_ASSET_CACHE_CONTROL.to_string(),
+            html_cache_control: DEFAULT_HTML_CACHE_CONTROL.to_string(),
+            tags: BTreeMap::new(),
+            tagged_resources: Vec::new(),
         }
     }
 
@@ -33,6 +86,12 @@ impl CommandChain {
         self
     }
 
+    /// Sets the synced directory, relative to the chain directory unless absolute.
+    pub fn with_source(mut self, source: PathBuf) -> Self {
+        self.source = Some(source);
+        self
+    }
+
     pub fn with_destination(mut self, destination: PathBuf) -> Self {
         self.destination = Some(destination);
         self
@@ -63,21 +122,387 @@ impl CommandChain {
         self
     }
 
+    pub fn with_cache_control(mut self, cache_control: Option<String>) -> Self {
+        self.cache_control = cache_control;
+        self
+    }
+
+    pub fn with_metadata_directive(mut self, directive: Option<String>) -> Self {
+        self.metadata_directive = directive;
+        self
+    }
+
+    pub fn with_storage_class(mut self, storage_class: Option<String>) -> Self {
+        self.storage_class = storage_class;
+        self
+    }
+
+    pub fn with_sse(mut self, sse: Option<String>) -> Self {
+        self.sse = sse;
+        self
+    }
+
+    /// Adds per-pattern header overrides, see [`AwsCommand::with_overrides`].
+    pub fn with_overrides(mut self, overrides: Vec<SyncOverride>) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Sets the file copied by `copy_chain`; relative local paths are resolved
+    /// against the chain directory.
+    pub fn with_copy_source(mut self, source: PathBuf) -> Self {
+        self.copy_source = Some(source);
+        self
+    }
+
+    pub fn with_content_type(mut self, content_type: Option<String>) -> Self {
+        self.content_type = content_type;
+        self
+    }
+
+    pub fn with_acl(mut self, acl: Option<String>) -> Self {
+        self.acl = acl;
+        self
+    }
+
+    pub fn with_metadata(mut self, metadata: Option<BTreeMap<String, String>>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn with_lambda_function(mut self, function_name: String) -> Self {
+        self.lambda_function = Some(function_name);
+        self
+    }
+
+    /// Sets the deployment package; relative paths are resolved against the
+    /// chain directory.
+    pub fn with_lambda_zip(mut self, zip_file: PathBuf) -> Self {
+        self.lambda_zip = Some(zip_file);
+        self
+    }
+
+    pub fn with_lambda_image(mut self, image_uri: String) -> Self {
+        self.lambda_image = Some(image_uri);
+        self
+    }
+
+    /// Sets the alias moved to the new version; implies publishing.
+    pub fn with_lambda_alias(mut self, alias: Option<String>) -> Self {
+        self.lambda_alias = alias;
+        self
+    }
+
+    pub fn with_lambda_publish(mut self, publish: bool) -> Self {
+        self.lambda_publish = publish;
+        self
+    }
+
+    pub fn with_lambda_targets(mut self, targets: Vec<LambdaTarget>) -> Self {
+        self.lambda_targets = targets;
+        self
+    }
+
+    pub fn with_ecr_registry(mut self, registry: String) -> Self {
+        self.ecr_registry = Some(registry);
+        self
+    }
+
+    pub fn with_ecr_image(mut self, image: String) -> Self {
+        self.ecr_image = Some(image);
+        self
+    }
+
+    pub fn with_cloudfront_distributions(mut self, distributions: Vec<String>) -> Self {
+        self.cloudfront_distributions = distributions;
+        self
+    }
+
+    pub fn with_cloudfront_paths(mut self, paths: Vec<String>) -> Self {
+        self.cloudfront_paths = paths;
+        self
+    }
+
+    /// Sets the `Cache-Control` of the non-HTML files of a static site.
+    pub fn with_asset_cache_control(mut self, cache_control: String) -> Self {
+        self.asset_cache_control = cache_control;
+        self
+    }
+
+    /// Sets the `Cache-Control` of the HTML files of a static site.
+    pub fn with_html_cache_control(mut self, cache_control: String) -> Self {
+        self.html_cache_control = cache_control;
+        self
+    }
+
+    /// Sets the tags stamped on the tagged resources after a deploy.
+    pub fn with_tags(mut self, tags: BTreeMap<String, String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn with_tagged_resources(mut self, arns: Vec<String>) -> Self {
+        self.tagged_resources = arns;
+        self
+    }
+
     fn build_sync(&self) -> AwsCommand {
+        let source = match &self.source {
+            Some(source) => self.dir.join(source),
+            None => self.dir.clone(),
+        };
+
         AwsCommand::S3Sync {
-            source: self.dir.clone(),
+            source,
             destination: self.destination.clone().expect("Destination must be set"),
             exclude: self.exclude.clone(),
             include: self.include.clone(),
             delete: self.delete,
             dry_run: self.dry_run,
             force: self.force,
+            cache_control: self.cache_control.clone(),
+            content_type: self.content_type.clone(),
+            metadata_directive: self.metadata_directive.clone(),
+            storage_class: self.storage_class.clone(),
+            acl: self.acl.clone(),
+            sse: self.sse.clone(),
+        }
+    }
+
+    fn build_copy(&self) -> AwsCommand {
+        let source = self.copy_source.clone().expect("Copy source must be set");
+        let source = if source.starts_with("s3:") || source.is_absolute() {
+            source
+        } else {
+            self.dir.join(source)
+        };
+
+        AwsCommand::S3Cp {
+            source,
+            destination: self.destination.clone().expect("Destination must be set"),
+            content_type: self.content_type.clone(),
+            acl: self.acl.clone(),
+            metadata: self.metadata.clone(),
         }
     }
 
     pub fn sync_chain(&self) -> Vec<AwsCommand> {
-        vec![self.build_sync()]
+        self.tagged(self.build_sync().with_overrides(&self.overrides))
     }
+
+    pub fn copy_chain(&self) -> Vec<AwsCommand> {
+        self.tagged(vec![self.build_copy()])
+    }
+
+    /// Builds one tag step per tagged resource.
+    ///
+    /// Empty unless both tags and resources are set. Deploy chains end with
+    /// these steps; chains that are split up, e.g. a Lambda batch, leave
+    /// running them to the caller.
+    pub fn tag_chain(&self) -> Vec<AwsCommand> {
+        if self.tags.is_empty() {
+            return Vec::new();
+        }
+        self.tagged_resources
+            .iter()
+            .map(|arn| AwsCommand::TagResource {
+                arn: arn.clone(),
+                tags: self.tags.clone(),
+            })
+            .collect()
+    }
+
+    /// Appends the tag steps to a deploy chain.
+    fn tagged(&self, mut commands: Vec<AwsCommand>) -> Vec<AwsCommand> {
+        commands.extend(self.tag_chain());
+        commands
+    }
+
+    fn lambda_function(&self) -> String {
+        self.lambda_function
+            .clone()
+            .expect("Lambda function must be set")
+    }
+
+    /// Appends the wait and optional alias steps to a code update.
+    fn lambda_chain(&self, function_name: String, update: AwsCommand) -> Vec<AwsCommand> {
+        let mut commands = vec![
+            update,
+            AwsCommand::LambdaWait {
+                function_name: function_name.clone(),
+            },
+        ];
+        if let Some(alias) = &self.lambda_alias {
+            commands.push(AwsCommand::LambdaUpdateAlias {
+                function_name,
+                alias: alias.clone(),
+                version: None,
+            });
+        }
+        commands
+    }
+
+    /// Builds the update (zip) -> wait -> alias chain.
+    pub fn lambda_update_zip_chain(&self) -> Vec<AwsCommand> {
+        let zip_file = self.lambda_zip.clone().expect("Lambda zip must be set");
+        self.tagged(self.lambda_target_chain(&LambdaTarget {
+            function: self.lambda_function(),
+            code: LambdaCode::Zip(zip_file),
+        }))
+    }
+
+    /// Builds the update (image) -> wait -> alias chain.
+    pub fn lambda_update_image_chain(&self) -> Vec<AwsCommand> {
+        let image_uri = self.lambda_image.clone().expect("Lambda image must be set");
+        self.tagged(self.lambda_target_chain(&LambdaTarget {
+            function: self.lambda_function(),
+            code: LambdaCode::Image(image_uri),
+        }))
+    }
+
+    /// Builds the update -> wait -> alias chain of a single target.
+    fn lambda_target_chain(&self, target: &LambdaTarget) -> Vec<AwsCommand> {
+        let function_name = target.function.clone();
+        let publish = self.lambda_publish || self.lambda_alias.is_some();
+        let update = match &target.code {
+            LambdaCode::Zip(zip_file) => AwsCommand::LambdaUpdateZip {
+                function_name: function_name.clone(),
+                zip_file: self.dir.join(zip_file),
+                publish,
+            },
+            LambdaCode::Image(image_uri) => AwsCommand::LambdaUpdateImage {
+                function_name: function_name.clone(),
+                image_uri: image_uri.clone(),
+                publish,
+            },
+        };
+        self.lambda_chain(function_name, update)
+    }
+
+    /// Builds one update chain per batch target, keyed by function name.
+    ///
+    /// The chains do not include the tag steps, see [`CommandChain::tag_chain`].
+    pub fn lambda_batch_chain(&self) -> Vec<(String, Vec<AwsCommand>)> {
+        self.lambda_targets
+            .iter()
+            .map(|target| (target.function.clone(), self.lambda_target_chain(target)))
+            .collect()
+    }
+
+    /// Builds one invalidation per distribution.
+    ///
+    /// The commands are independent and may run with
+    /// [`AwsExecutor::execute_parallel`](crate::AwsExecutor::execute_parallel).
+    pub fn invalidate_chain(&self) -> Vec<AwsCommand> {
+        assert!(
+            !self.cloudfront_distributions.is_empty(),
+            "CloudFront distribution must be set"
+        );
+        self.cloudfront_distributions
+            .iter()
+            .map(|distribution_id| AwsCommand::CloudFrontInvalidate {
+                distribution_id: distribution_id.clone(),
+                paths: self.cloudfront_paths.clone(),
+                caller_reference: caller_reference(distribution_id),
+            })
+            .collect()
+    }
+
+    /// Builds the invalidations as steps named after their distribution.
+    pub fn invalidate_steps(&self) -> Vec<Step> {
+        self.invalidate_chain().into_iter().map(named).collect()
+    }
+
+    /// Builds the static site deploy chain.
+    ///
+    /// Hashed assets are uploaded first with a long cache, then the HTML
+    /// pages referencing them with a short one. If deletion was requested,
+    /// stale objects are only removed once the new pages are live. The
+    /// configured CloudFront distributions are invalidated, then the
+    /// resources are tagged.
+    pub fn static_site_chain(&self) -> Vec<AwsCommand> {
+        self.static_site_steps()
+            .into_iter()
+            .map(|step| step.command)
+            .collect()
+    }
+
+    /// Builds the static site deploy chain as named steps, see
+    /// [`CommandChain::static_site_chain`].
+    pub fn static_site_steps(&self) -> Vec<Step> {
+        let user_exclude = self.exclude.clone().unwrap_or_default();
+        let excluding = |pattern: &str| {
+            let mut exclude = user_exclude.clone();
+            exclude.push(pattern.to_string());
+            Some(exclude)
+        };
+
+        let assets = Self {
+            exclude: excluding("*.html"),
+            delete: false,
+            cache_control: Some(self.asset_cache_control.clone()),
+            ..self.clone()
+        };
+        let html = Self {
+            exclude: excluding("*"),
+            include: Some(vec!["*.html".to_string()]),
+            delete: false,
+            cache_control: Some(self.html_cache_control.clone()),
+            ..self.clone()
+        };
+
+        let mut steps = vec![
+            Step::new(assets.build_sync()).with_name("upload assets"),
+            Step::new(html.build_sync()).with_name("upload pages"),
+        ];
+        if self.delete {
+            steps.push(Step::new(self.build_sync()).with_name("delete stale objects"));
+        }
+        if !self.cloudfront_distributions.is_empty() {
+            steps.extend(self.invalidate_steps());
+        }
+        steps.extend(self.tag_chain().into_iter().map(named));
+        steps
+    }
+
+    /// Builds the ECR login -> push chain.
+    pub fn ecr_push_chain(&self) -> Vec<AwsCommand> {
+        self.tagged(vec![
+            AwsCommand::EcrLogin {
+                registry: self.ecr_registry.clone().expect("ECR registry must be set"),
+                region: None,
+            },
+            AwsCommand::EcrPush {
+                image: self.ecr_image.clone().expect("ECR image must be set"),
+            },
+        ])
+    }
+}
+
+/// Names a step after the resource its command targets.
+fn named(command: AwsCommand) -> Step {
+    let name = match &command {
+        AwsCommand::CloudFrontInvalidate {
+            distribution_id, ..
+        } => Some(format!("invalidate {}", distribution_id)),
+        AwsCommand::TagResource { arn, .. } => Some(format!("tag {}", arn)),
+        _ => None,
+    };
+    match name {
+        Some(name) => Step::new(command).with_name(name),
+        None => Step::new(command),
+    }
+}
+
+/// Returns a reference unique to this invalidation request.
+///
+/// CloudFront treats a repeated reference as a retry of the earlier request.
+fn caller_reference(distribution_id: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("ci-actions-{}-{}", distribution_id, nanos)
 }
 
 #[cfg(test)]
@@ -117,10 +542,274 @@ mod tests {
         assert!(chain.force);
     }
 
+    #[test]
+    fn test_sync_chain_with_overrides() {
+        let chain = CommandChain::new(PathBuf::from("/test/path"))
+            .with_destination(PathBuf::from("s3://test-bucket"))
+            .with_overrides(vec![SyncOverride::cache_control("*.html", "no-cache")]);
+
+        let commands = chain.sync_chain();
+        assert_eq!(commands.len(), 2);
+        assert!(commands[0]
+            .to_args()
+            .contains(&"--cache-control=no-cache".to_string()));
+    }
+
+    #[test]
+    fn test_sync_chain_with_source() {
+        let chain = CommandChain::new(PathBuf::from("/work"))
+            .with_source(PathBuf::from("dist"))
+            .with_destination(PathBuf::from("s3://test-bucket"));
+
+        match &chain.sync_chain()[0] {
+            AwsCommand::S3Sync { source, .. } => {
+                assert_eq!(source, &PathBuf::from("/work/dist"))
+            }
+            other => panic!("Expected S3Sync, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_copy_chain_resolves_local_source() {
+        let chain = CommandChain::new(PathBuf::from("/work"))
+            .with_copy_source(PathBuf::from("dist/version.json"))
+            .with_destination(PathBuf::from("s3://bucket/version.json"))
+            .with_content_type(Some("application/json".to_string()));
+
+        match &chain.copy_chain()[0] {
+            AwsCommand::S3Cp {
+                source,
+                content_type,
+                ..
+            } => {
+                assert_eq!(source, &PathBuf::from("/work/dist/version.json"));
+                assert_eq!(content_type.as_deref(), Some("application/json"));
+            }
+            other => panic!("Expected S3Cp, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_copy_chain_keeps_s3_source() {
+        let chain = CommandChain::new(PathBuf::from("/work"))
+            .with_copy_source(PathBuf::from("s3://bucket/version.json"))
+            .with_destination(PathBuf::from("version.json"));
+
+        match &chain.copy_chain()[0] {
+            AwsCommand::S3Cp { source, .. } => {
+                assert_eq!(source, &PathBuf::from("s3://bucket/version.json"))
+            }
+            other => panic!("Expected S3Cp, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_lambda_update_zip_chain() {
+        let chain = CommandChain::new(PathBuf::from("/work"))
+            .with_lambda_function("api".to_string())
+            .with_lambda_zip(PathBuf::from("dist/app.zip"))
+            .with_lambda_alias(Some("live".to_string()));
+
+        let commands = chain.lambda_update_zip_chain();
+        assert_eq!(commands.len(), 3);
+        assert!(matches!(
+            &commands[0],
+            AwsCommand::LambdaUpdateZip { zip_file, publish: true, .. }
+                if zip_file == &PathBuf::from("/work/dist/app.zip")
+        ));
+        assert!(matches!(&commands[1], AwsCommand::LambdaWait { .. }));
+        assert!(matches!(
+            &commands[2],
+            AwsCommand::LambdaUpdateAlias { alias, version: None, .. } if alias == "live"
+        ));
+    }
+
+    #[test]
+    fn test_lambda_update_image_chain_without_alias() {
+        let chain = CommandChain::new(PathBuf::from("/work"))
+            .with_lambda_function("api".to_string())
+            .with_lambda_image("registry/api:1".to_string());
+
+        let commands = chain.lambda_update_image_chain();
+        assert_eq!(commands.len(), 2);
+        assert!(matches!(
+            &commands[0],
+            AwsCommand::LambdaUpdateImage { publish: false, .. }
+        ));
+    }
+
+    #[test]
+    fn test_ecr_push_chain() {
+        let chain = CommandChain::new(PathBuf::from("/test/path"))
+            .with_ecr_registry("registry.example".to_string())
+            .with_ecr_image("registry.example/app:1".to_string());
+
+        let commands = chain.ecr_push_chain();
+        assert_eq!(commands.len(), 2);
+        assert!(
+            matches!(&commands[0], AwsCommand::EcrLogin { registry, .. } if registry == "registry.example")
+        );
+        assert!(
+            matches!(&commands[1], AwsCommand::EcrPush { image } if image == "registry.example/app:1")
+        );
+    }
+
     #[test]
     #[should_panic(expected = "Destination must be set")]
     fn test_build_sync_without_destination() {
         let chain = CommandChain::new(PathBuf::from("/test/path"));
         chain.build_sync();
     }
+
+    #[test]
+    fn test_static_site_chain() {
+        let chain = CommandChain::new(PathBuf::from("/work"))
+            .with_source(PathBuf::from("dist"))
+            .with_destination(PathBuf::from("s3://site"))
+            .with_exclude(Some(vec!["*.map".to_string()]))
+            .with_delete(true)
+            .with_cloudfront_distributions(vec!["E1".to_string()]);
+
+        let commands = chain.static_site_chain();
+        assert_eq!(commands.len(), 4);
+
+        let assets = commands[0].to_args();
+        assert!(assets.contains(&"--exclude=*.map".to_string()));
+        assert!(assets.contains(&"--exclude=*.html".to_string()));
+        assert!(assets.contains(&"--cache-control=public, max-age=31536000, immutable".to_string()));
+        assert!(!assets.contains(&"--delete".to_string()));
+
+        let html = commands[1].to_args();
+        assert!(html.contains(&"--exclude=*".to_string()));
+        assert!(html.contains(&"--include=*.html".to_string()));
+        assert!(html.contains(&"--cache-control=no-cache".to_string()));
+
+        assert!(commands[2].to_args().contains(&"--delete".to_string()));
+        assert!(matches!(
+            commands[3],
+            AwsCommand::CloudFrontInvalidate { .. }
+        ));
+    }
+
+    #[test]
+    fn test_static_site_chain_without_delete_or_cdn() {
+        let chain = CommandChain::new(PathBuf::from("/work"))
+            .with_destination(PathBuf::from("s3://site"))
+            .with_html_cache_control("max-age=60".to_string());
+
+        let commands = chain.static_site_chain();
+        assert_eq!(commands.len(), 2);
+        assert!(commands[1]
+            .to_args()
+            .contains(&"--cache-control=max-age=60".to_string()));
+    }
+
+    #[test]
+    fn test_lambda_batch_chain() {
+        let chain = CommandChain::new(PathBuf::from("/work"))
+            .with_lambda_alias(Some("live".to_string()))
+            .with_lambda_targets(vec![
+                LambdaTarget {
+                    function: "api".to_string(),
+                    code: LambdaCode::Zip(PathBuf::from("dist/api.zip")),
+                },
+                LambdaTarget {
+                    function: "worker".to_string(),
+                    code: LambdaCode::Image("registry.example/worker:1".to_string()),
+                },
+            ]);
+
+        let chains = chain.lambda_batch_chain();
+        assert_eq!(chains.len(), 2);
+        assert_eq!(chains[0].0, "api");
+        assert_eq!(chains[0].1.len(), 3);
+        assert!(chains[0].1[0]
+            .to_args()
+            .contains(&"--zip-file=fileb:///work/dist/api.zip".to_string()));
+        assert!(matches!(
+            chains[1].1[0],
+            AwsCommand::LambdaUpdateImage { publish: true, .. }
+        ));
+    }
+
+    #[test]
+    fn test_invalidate_chain_fans_out() {
+        let chain = CommandChain::new(PathBuf::from("/test/path"))
+            .with_cloudfront_distributions(vec!["E1".to_string(), "E2".to_string()]);
+
+        let commands = chain.invalidate_chain();
+        assert_eq!(commands.len(), 2);
+        match &commands[1] {
+            AwsCommand::CloudFrontInvalidate {
+                distribution_id,
+                paths,
+                caller_reference,
+            } => {
+                assert_eq!(distribution_id, "E2");
+                assert_eq!(paths, &vec!["/*".to_string()]);
+                assert!(caller_reference.starts_with("ci-actions-E2-"));
+            }
+            other => panic!("Expected CloudFrontInvalidate, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_deploy_chains_end_with_tags() {
+        let chain = CommandChain::new(PathBuf::from("/test/path"))
+            .with_destination(PathBuf::from("s3://test-bucket"))
+            .with_lambda_function("api".to_string())
+            .with_lambda_zip(PathBuf::from("api.zip"))
+            .with_tags(BTreeMap::from([("commit".to_string(), "abc".to_string())]))
+            .with_tagged_resources(vec!["arn:aws:s3:::test-bucket".to_string()]);
+
+        for commands in [chain.sync_chain(), chain.lambda_update_zip_chain()] {
+            match commands.last().unwrap() {
+                AwsCommand::TagResource { arn, tags } => {
+                    assert_eq!(arn, "arn:aws:s3:::test-bucket");
+                    assert_eq!(tags.get("commit").unwrap(), "abc");
+                }
+                other => panic!("Expected TagResource, got {:?}", other),
+            }
+        }
+        assert!(chain
+            .lambda_batch_chain()
+            .iter()
+            .all(|(_, commands)| commands.len() == 2));
+    }
+
+    #[test]
+    fn test_tag_chain_requires_tags() {
+        let chain = CommandChain::new(PathBuf::from("/test/path"))
+            .with_destination(PathBuf::from("s3://test-bucket"))
+            .with_tagged_resources(vec!["arn:aws:s3:::test-bucket".to_string()]);
+
+        assert!(chain.tag_chain().is_empty());
+        assert_eq!(chain.sync_chain().len(), 1);
+    }
+
+    #[test]
+    fn test_static_site_steps_are_named() {
+        let chain = CommandChain::new(PathBuf::from("/test/path"))
+            .with_destination(PathBuf::from("s3://site"))
+            .with_delete(true)
+            .with_cloudfront_distributions(vec!["E1".to_string()])
+            .with_tags(BTreeMap::from([("commit".to_string(), "abc".to_string())]))
+            .with_tagged_resources(vec!["arn:aws:s3:::site".to_string()]);
+
+        let names: Vec<_> = chain
+            .static_site_steps()
+            .into_iter()
+            .map(|step| step.name.unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "upload assets",
+                "upload pages",
+                "delete stale objects",
+                "invalidate E1",
+                "tag arn:aws:s3:::site"
+            ]
+        );
+    }
 }
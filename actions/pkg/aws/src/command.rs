@@ -1,5 +1,229 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+/// Number of items requested per page by list commands.
+const PAGE_SIZE: u32 = 100;
+
+/// The command-line tool that runs a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cli {
+    Aws,
+    Docker,
+    Sam,
+    Cdk,
+}
+
+impl Cli {
+    /// Returns the default executable name of the tool.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Aws => "aws",
+            Self::Docker => "docker",
+            Self::Sam => "sam",
+            Self::Cdk => "cdk",
+        }
+    }
+}
+
+/// Object headers applied to the files matching a pattern during a sync.
+///
+/// See [`AwsCommand::with_overrides`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncOverride {
+    /// Pattern of the files the override applies to, e.g. `assets/*`.
+    pub pattern: String,
+    /// `Cache-Control` of the matching files.
+    pub cache_control: Option<String>,
+    /// `Content-Type` of the matching files.
+    pub content_type: Option<String>,
+}
+
+impl SyncOverride {
+    /// Creates an override with a `Cache-Control` value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::command::SyncOverride;
+    ///
+    /// let immutable = SyncOverride::cache_control("assets/*", "public, max-age=31536000, immutable");
+    /// let html = SyncOverride::cache_control("*.html", "no-cache");
+    /// ```
+    pub fn cache_control(pattern: impl Into<String>, cache_control: impl Into<String>) -> Self {
+        Self {
+            pattern: pattern.into(),
+            cache_control: Some(cache_control.into()),
+            content_type: None,
+        }
+    }
+
+    /// Sets the `Content-Type` of the matching files.
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+}
+
+/// Global AWS CLI options appended to every command.
+///
+/// An option already passed by the command itself, such as the `--region`
+/// of `EcrLogin`, takes precedence.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlobalOptions {
+    /// Region passed as `--region`.
+    pub region: Option<String>,
+    /// Named profile passed as `--profile`.
+    pub profile: Option<String>,
+    /// Endpoint passed as `--endpoint-url`.
+    pub endpoint_url: Option<String>,
+}
+
+impl GlobalOptions {
+    /// Creates empty options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    pub fn with_endpoint_url(mut self, endpoint_url: impl Into<String>) -> Self {
+        self.endpoint_url = Some(endpoint_url.into());
+        self
+    }
+
+    /// Appends the options missing from `args`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::command::GlobalOptions;
+    ///
+    /// let options = GlobalOptions::new().with_region("eu-west-1").with_profile("prod");
+    /// let mut args = vec!["ecr".to_string(), "get-login-password".to_string(), "--region=us-east-1".to_string()];
+    /// options.apply(&mut args);
+    /// assert_eq!(args[2..], ["--region=us-east-1", "--profile=prod"]);
+    /// ```
+    pub fn apply(&self, args: &mut Vec<String>) {
+        for (flag, value) in [
+            ("--region", &self.region),
+            ("--profile", &self.profile),
+            ("--endpoint-url", &self.endpoint_url),
+        ] {
+            let Some(value) = value else { continue };
+            let present = args
+                .iter()
+                .any(|arg| arg == flag || arg.starts_with(&format!("{}=", flag)));
+            if !present {
+                args.push(format!("{}={}", flag, value));
+            }
+        }
+    }
+}
+
+/// The code a Lambda function is updated to.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LambdaCode {
+    /// A zip package, relative to the working directory.
+    Zip(PathBuf),
+    /// A container image URI.
+    Image(String),
+}
+
+/// A Lambda function updated as part of a batch.
+///
+/// Manifests list targets as JSON objects such as
+/// `{"function": "api", "zip": "dist/api.zip"}` or
+/// `{"function": "worker", "image": "registry.example/worker:1"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LambdaTarget {
+    /// Name or ARN of the function.
+    pub function: String,
+    #[serde(flatten)]
+    pub code: LambdaCode,
+}
+
+impl LambdaTarget {
+    /// Parses a JSON manifest holding an array of targets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::command::{LambdaCode, LambdaTarget};
+    ///
+    /// let targets = LambdaTarget::parse_manifest(r#"[
+    ///     {"function": "api", "zip": "dist/api.zip"},
+    ///     {"function": "worker", "image": "registry.example/worker:1"}
+    /// ]"#).unwrap();
+    /// assert_eq!(targets[1].code, LambdaCode::Image("registry.example/worker:1".to_string()));
+    /// ```
+    pub fn parse_manifest(manifest: &str) -> serde_json::Result<Vec<Self>> {
+        serde_json::from_str(manifest)
+    }
+}
+
+/// A DNS record set written by `Route53Upsert`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route53Record {
+    /// Fully qualified record name, e.g. `api.example.com`.
+    pub name: String,
+    /// Record type, e.g. `CNAME` or `A`.
+    pub record_type: String,
+    /// Time to live in seconds.
+    pub ttl: u32,
+    /// Record values.
+    pub values: Vec<String>,
+}
+
+impl Route53Record {
+    /// Builds the `UPSERT` change batch accepted by `change-resource-record-sets`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::command::Route53Record;
+    ///
+    /// let record = Route53Record {
+    ///     name: "api.example.com".to_string(),
+    ///     record_type: "CNAME".to_string(),
+    ///     ttl: 60,
+    ///     values: vec!["green.example.com".to_string()],
+    /// };
+    ///
+    /// let batch = record.change_batch();
+    /// assert_eq!(batch["Changes"][0]["Action"], "UPSERT");
+    /// assert_eq!(batch["Changes"][0]["ResourceRecordSet"]["TTL"], 60);
+    /// ```
+    pub fn change_batch(&self) -> serde_json::Value {
+        let records: Vec<serde_json::Value> = self
+            .values
+            .iter()
+            .map(|v| serde_json::json!({ "Value": v }))
+            .collect();
+
+        serde_json::json!({
+            "Changes": [{
+                "Action": "UPSERT",
+                "ResourceRecordSet": {
+                    "Name": self.name,
+                    "Type": self.record_type,
+                    "TTL": self.ttl,
+                    "ResourceRecords": records,
+                }
+            }]
+        })
+    }
+}
+
 /// Represents the various AWS commands that can be executed.
 #[derive(Debug, Clone)]
 pub enum AwsCommand {
@@ -14,6 +238,12 @@ pub enum AwsCommand {
     /// - `delete`: Whether to delete files in the destination not present in the source.
     /// - `dry_run`: Whether to perform a dry run.
     /// - `force`: Whether to force synchronization.
+    /// - `cache_control`: Optional `Cache-Control` of uploaded files.
+    /// - `content_type`: Optional `Content-Type` of uploaded files.
+    /// - `metadata_directive`: Optional `COPY` or `REPLACE` for S3-to-S3 syncs.
+    /// - `storage_class`: Optional storage class, e.g. `INTELLIGENT_TIERING`.
+    /// - `acl`: Optional canned ACL, e.g. `bucket-owner-full-control`.
+    /// - `sse`: Optional server-side encryption, `AES256` or `aws:kms`.
     S3Sync {
         source: PathBuf,
         destination: PathBuf,
@@ -22,12 +252,578 @@ pub enum AwsCommand {
         delete: bool,
         dry_run: bool,
         force: bool,
+        cache_control: Option<String>,
+        content_type: Option<String>,
+        metadata_directive: Option<String>,
+        storage_class: Option<String>,
+        acl: Option<String>,
+        sse: Option<String>,
+    },
+
+    /// Copies a single file to or from S3.
+    ///
+    /// # Fields
+    ///
+    /// - `source`: Local file or S3 object.
+    /// - `destination`: Local file or S3 object.
+    /// - `content_type`: Optional `Content-Type` of the uploaded object.
+    /// - `acl`: Optional canned ACL, e.g. `bucket-owner-full-control`.
+    /// - `metadata`: Optional user metadata of the uploaded object.
+    S3Cp {
+        source: PathBuf,
+        destination: PathBuf,
+        content_type: Option<String>,
+        acl: Option<String>,
+        metadata: Option<BTreeMap<String, String>>,
+    },
+
+    /// Updates the code of a Lambda function from a zip archive.
+    ///
+    /// # Fields
+    ///
+    /// - `function_name`: Name or ARN of the function.
+    /// - `zip_file`: Path to the deployment package.
+    /// - `publish`: Whether to publish a new version.
+    LambdaUpdateZip {
+        function_name: String,
+        zip_file: PathBuf,
+        publish: bool,
+    },
+
+    /// Updates the code of a Lambda function from a container image.
+    ///
+    /// # Fields
+    ///
+    /// - `function_name`: Name or ARN of the function.
+    /// - `image_uri`: URI of the image in ECR.
+    /// - `publish`: Whether to publish a new version.
+    LambdaUpdateImage {
+        function_name: String,
+        image_uri: String,
+        publish: bool,
+    },
+
+    /// Waits until the last update of a Lambda function has completed.
+    ///
+    /// Lambda rejects further updates with `ResourceConflictException` while
+    /// an update is in progress, so this runs after every code update.
+    LambdaWait { function_name: String },
+
+    /// Points a Lambda alias at a function version.
+    ///
+    /// # Fields
+    ///
+    /// - `function_name`: Name or ARN of the function.
+    /// - `alias`: Name of the alias.
+    /// - `version`: Version to point at; `None` selects the latest published version.
+    LambdaUpdateAlias {
+        function_name: String,
+        alias: String,
+        version: Option<String>,
+    },
+
+    /// Fetches a secret from Secrets Manager.
+    ///
+    /// The executor registers the secret for masking and exports it as
+    /// `env_name` to every following command.
+    ///
+    /// # Fields
+    ///
+    /// - `secret_id`: Name or ARN of the secret.
+    /// - `json_key`: Optional key to extract from a JSON secret.
+    /// - `env_name`: Environment variable receiving the value.
+    GetSecretValue {
+        secret_id: String,
+        json_key: Option<String>,
+        env_name: String,
+    },
+
+    /// Assumes an IAM role with STS.
+    ///
+    /// The executor masks the temporary credentials and uses them as the
+    /// environment of every following command.
+    ///
+    /// # Fields
+    ///
+    /// - `role_arn`: ARN of the role to assume.
+    /// - `session_name`: Role session name.
+    /// - `duration`: Optional session duration in seconds.
+    AssumeRole {
+        role_arn: String,
+        session_name: String,
+        duration: Option<u32>,
+    },
+
+    /// Creates or updates a Route53 record set.
+    ///
+    /// # Fields
+    ///
+    /// - `zone_id`: ID of the hosted zone.
+    /// - `record`: The record set to write.
+    /// - `wait`: Whether to wait until the change is `INSYNC`.
+    Route53Upsert {
+        zone_id: String,
+        record: Route53Record,
+        wait: bool,
+    },
+
+    /// Writes a kubeconfig for an EKS cluster.
+    ///
+    /// The executor registers the kubeconfig path and any embedded token as
+    /// sensitive and exports `KUBECONFIG` to the following commands.
+    ///
+    /// # Fields
+    ///
+    /// - `cluster`: Name of the EKS cluster.
+    /// - `region`: Optional region of the cluster.
+    /// - `kubeconfig_path`: File the kubeconfig is written to.
+    EksUpdateKubeconfig {
+        cluster: String,
+        region: Option<String>,
+        kubeconfig_path: PathBuf,
+    },
+
+    /// Logs Docker in to an ECR registry.
+    ///
+    /// The password from `aws ecr get-login-password` is captured, registered for
+    /// masking and passed to `docker login --password-stdin`; it never appears on
+    /// a command line or in the output.
+    ///
+    /// # Fields
+    ///
+    /// - `registry`: Registry host, e.g. `123456789012.dkr.ecr.eu-west-1.amazonaws.com`.
+    /// - `region`: Optional region of the registry.
+    EcrLogin {
+        registry: String,
+        region: Option<String>,
+    },
+
+    /// Pushes a Docker image; run after `EcrLogin`.
+    ///
+    /// # Fields
+    ///
+    /// - `image`: Full image reference including the registry and tag.
+    EcrPush { image: String },
+
+    /// Deploys a new task definition revision to an ECS service.
+    ///
+    /// The task definition is registered first; the service is then updated to
+    /// the new revision and, if `wait` is set, polled until it is stable.
+    ///
+    /// # Fields
+    ///
+    /// - `cluster`: Name or ARN of the ECS cluster.
+    /// - `service`: Name of the service to update.
+    /// - `task_definition`: Path to the task definition JSON file.
+    /// - `wait`: Whether to wait for the service to become stable.
+    EcsDeploy {
+        cluster: String,
+        service: String,
+        task_definition: PathBuf,
+        wait: bool,
+    },
+
+    /// Generates a presigned URL for an S3 object.
+    ///
+    /// # Fields
+    ///
+    /// - `key`: S3 URI of the object, e.g. `s3://artifacts/build/app.zip`.
+    /// - `expires_in`: Lifetime of the URL in seconds.
+    Presign { key: String, expires_in: u32 },
+
+    /// Prints the events of a CloudWatch Logs group.
+    ///
+    /// Events are written through the masked output like any command output.
+    ///
+    /// # Fields
+    ///
+    /// - `log_group`: Name of the log group, e.g. `/aws/lambda/api`.
+    /// - `since`: Optional start of the events, e.g. `10m` or an ISO 8601 timestamp.
+    /// - `filter`: Optional CloudWatch Logs filter pattern.
+    LogsTail {
+        log_group: String,
+        since: Option<String>,
+        filter: Option<String>,
+    },
+
+    /// Invalidates paths in the cache of a CloudFront distribution.
+    ///
+    /// # Fields
+    ///
+    /// - `distribution_id`: ID of the distribution.
+    /// - `paths`: Invalidated paths, e.g. `/*` or `/index.html`.
+    /// - `caller_reference`: Unique reference of the invalidation request.
+    CloudFrontInvalidate {
+        distribution_id: String,
+        paths: Vec<String>,
+        caller_reference: String,
+    },
+
+    /// Tags a resource, e.g. with the commit and run of the deploy.
+    ///
+    /// # Fields
+    ///
+    /// - `arn`: ARN of the tagged resource.
+    /// - `tags`: Tag keys and their rendered values.
+    TagResource {
+        arn: String,
+        tags: BTreeMap<String, String>,
+    },
+
+    /// Lists one page of the invalidations of a CloudFront distribution.
+    ///
+    /// Use [`AwsExecutor::list`](crate::AwsExecutor::list) to follow the
+    /// pagination tokens.
+    ///
+    /// # Fields
+    ///
+    /// - `distribution_id`: ID of the distribution.
+    /// - `starting_token`: `NextToken` of the previous page.
+    ListInvalidations {
+        distribution_id: String,
+        starting_token: Option<String>,
+    },
+
+    /// Lists one page of the versions of a Lambda function, oldest first.
+    ///
+    /// # Fields
+    ///
+    /// - `function_name`: Name or ARN of the function.
+    /// - `starting_token`: `NextToken` of the previous page.
+    ListFunctionVersions {
+        function_name: String,
+        starting_token: Option<String>,
+    },
+
+    /// Deploys a stack with the SAM CLI, without confirming the change set.
+    ///
+    /// # Fields
+    ///
+    /// - `project_dir`: Directory the SAM CLI runs in.
+    /// - `stack_name`: Name of the CloudFormation stack.
+    /// - `template`: Optional template, relative to `project_dir`; SAM looks
+    ///   for the built template by default.
+    /// - `parameter_overrides`: Template parameters.
+    /// - `capabilities`: Acknowledged capabilities, e.g. `CAPABILITY_IAM`.
+    SamDeploy {
+        project_dir: PathBuf,
+        stack_name: String,
+        template: Option<PathBuf>,
+        parameter_overrides: BTreeMap<String, String>,
+        capabilities: Vec<String>,
+    },
+
+    /// Deploys stacks with the CDK CLI, without approval prompts.
+    ///
+    /// # Fields
+    ///
+    /// - `project_dir`: Directory the CDK CLI runs in, usually holding `cdk.json`.
+    /// - `stacks`: Deployed stacks; all stacks if empty.
+    /// - `app`: Optional app command overriding `cdk.json`.
+    /// - `parameters`: CloudFormation parameters, e.g. `Stack:Name` keys.
+    CdkDeploy {
+        project_dir: PathBuf,
+        stacks: Vec<String>,
+        app: Option<String>,
+        parameters: BTreeMap<String, String>,
     },
 }
 
 impl AwsCommand {
+    /// Builds the `--invalidation-batch` payload of a `CloudFrontInvalidate` command.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::AwsCommand;
+    ///
+    /// let command = AwsCommand::CloudFrontInvalidate {
+    ///     distribution_id: "E123".to_string(),
+    ///     paths: vec!["/say \"hi\".html".to_string()],
+    ///     caller_reference: "release-1".to_string(),
+    /// };
+    /// let batch = command.invalidation_batch().unwrap();
+    /// assert_eq!(batch["Paths"]["Quantity"], 1);
+    /// assert_eq!(batch["Paths"]["Items"][0], "/say \"hi\".html");
+    /// ```
+    pub fn invalidation_batch(&self) -> Option<serde_json::Value> {
+        match self {
+            Self::CloudFrontInvalidate {
+                paths,
+                caller_reference,
+                ..
+            } => Some(serde_json::json!({
+                "Paths": {
+                    "Quantity": paths.len(),
+                    "Items": paths,
+                },
+                "CallerReference": caller_reference,
+            })),
+            _ => None,
+        }
+    }
+
+    /// Returns the operation of the command, e.g. `s3 sync` or `docker push`.
+    pub fn operation(&self) -> String {
+        let args = self.to_args();
+        match self.cli() {
+            Cli::Aws => args.iter().take(2).cloned().collect::<Vec<_>>().join(" "),
+            cli => format!(
+                "{} {}",
+                cli.name(),
+                args.first().map(String::as_str).unwrap_or_default()
+            ),
+        }
+    }
+
+    /// Returns `true` if the command only reads from AWS.
+    ///
+    /// Read-only commands still run in a dry run, since later commands need
+    /// the credentials and secrets they produce.
+    pub fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Self::AssumeRole { .. }
+                | Self::GetSecretValue { .. }
+                | Self::Presign { .. }
+                | Self::LogsTail { .. }
+                | Self::ListInvalidations { .. }
+                | Self::ListFunctionVersions { .. }
+        )
+    }
+
+    /// Returns the JSON pointer to the items of a page of a list command.
+    pub fn page_items(&self) -> Option<&'static str> {
+        match self {
+            Self::ListInvalidations { .. } => Some("/InvalidationList/Items"),
+            Self::ListFunctionVersions { .. } => Some("/Versions"),
+            _ => None,
+        }
+    }
+
+    /// Sets the pagination token of a list command; other commands are returned unchanged.
+    pub fn with_starting_token(mut self, token: String) -> Self {
+        match &mut self {
+            Self::ListInvalidations { starting_token, .. }
+            | Self::ListFunctionVersions { starting_token, .. } => *starting_token = Some(token),
+            _ => {}
+        }
+        self
+    }
+
+    /// Turns an `S3Sync` into a `--dryrun` sync; other commands are returned unchanged.
+    pub fn into_dry_run(mut self) -> Self {
+        if let Self::S3Sync { dry_run, .. } = &mut self {
+            *dry_run = true;
+        }
+        self
+    }
+
+    /// Returns the tool that runs the command.
+    pub fn cli(&self) -> Cli {
+        match self {
+            Self::EcrPush { .. } => Cli::Docker,
+            Self::SamDeploy { .. } => Cli::Sam,
+            Self::CdkDeploy { .. } => Cli::Cdk,
+            _ => Cli::Aws,
+        }
+    }
+
+    /// Returns `true` if the command runs the Docker CLI rather than the AWS CLI.
+    pub fn is_docker(&self) -> bool {
+        self.cli() == Cli::Docker
+    }
+
+    /// Returns the arguments of `docker login` for an `EcrLogin` command.
+    pub fn docker_login_args(&self) -> Option<Vec<String>> {
+        match self {
+            Self::EcrLogin { registry, .. } => Some(vec![
+                "login".to_string(),
+                "--username".to_string(),
+                "AWS".to_string(),
+                "--password-stdin".to_string(),
+                registry.clone(),
+            ]),
+            _ => None,
+        }
+    }
+
+    /// Returns the arguments of `aws route53 wait resource-record-sets-changed` for a
+    /// `Route53Upsert` command, or `None` if waiting is disabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `change_id` - ID of the change returned by the upsert.
+    pub fn route53_wait_args(&self, change_id: &str) -> Option<Vec<String>> {
+        match self {
+            Self::Route53Upsert { wait: true, .. } => Some(vec![
+                "route53".to_string(),
+                "wait".to_string(),
+                "resource-record-sets-changed".to_string(),
+                format!("--id={}", change_id),
+            ]),
+            _ => None,
+        }
+    }
+
+    /// Returns the arguments of `aws ecs update-service` for an `EcsDeploy` command.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_definition_arn` - ARN of the revision registered by the first step.
+    pub fn ecs_update_service_args(&self, task_definition_arn: &str) -> Option<Vec<String>> {
+        match self {
+            Self::EcsDeploy {
+                cluster, service, ..
+            } => Some(vec![
+                "ecs".to_string(),
+                "update-service".to_string(),
+                format!("--cluster={}", cluster),
+                format!("--service={}", service),
+                format!("--task-definition={}", task_definition_arn),
+            ]),
+            _ => None,
+        }
+    }
+
+    /// Returns the arguments of `aws ecs wait services-stable` for an `EcsDeploy`
+    /// command, or `None` if waiting is disabled.
+    pub fn ecs_wait_args(&self) -> Option<Vec<String>> {
+        match self {
+            Self::EcsDeploy {
+                cluster,
+                service,
+                wait: true,
+                ..
+            } => Some(vec![
+                "ecs".to_string(),
+                "wait".to_string(),
+                "services-stable".to_string(),
+                format!("--cluster={}", cluster),
+                format!("--services={}", service),
+            ]),
+            _ => None,
+        }
+    }
+
+    /// Splits an `S3Sync` command into one sync per override plus a final sync
+    /// of the remaining files.
+    ///
+    /// Each override pass syncs only the files matching its pattern
+    /// (`--exclude=* --include=<pattern>`) with the override headers; the final
+    /// pass excludes all override patterns and keeps the original headers.
+    /// Override passes ignore the command's own include and exclude patterns.
+    /// Other commands, or an empty override list, are returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::PathBuf;
+    /// use aws::command::{AwsCommand, SyncOverride};
+    ///
+    /// let sync = AwsCommand::S3Sync {
+    ///     source: PathBuf::from("./dist"),
+    ///     destination: PathBuf::from("s3://site"),
+    ///     exclude: None,
+    ///     include: None,
+    ///     delete: false,
+    ///     dry_run: false,
+    ///     force: false,
+    ///     cache_control: None,
+    ///     content_type: None,
+    ///     metadata_directive: None,
+    ///     storage_class: None,
+    ///     acl: None,
+    ///     sse: None,
+    /// };
+    ///
+    /// let commands = sync.with_overrides(&[SyncOverride::cache_control("*.html", "no-cache")]);
+    /// assert_eq!(commands.len(), 2);
+    /// assert!(commands[0].to_args().contains(&"--cache-control=no-cache".to_string()));
+    /// assert!(commands[1].to_args().contains(&"--exclude=*.html".to_string()));
+    /// ```
+    pub fn with_overrides(self, overrides: &[SyncOverride]) -> Vec<AwsCommand> {
+        let Self::S3Sync {
+            source,
+            destination,
+            exclude,
+            include,
+            delete,
+            dry_run,
+            force,
+            cache_control,
+            content_type,
+            metadata_directive,
+            storage_class,
+            acl,
+            sse,
+        } = self
+        else {
+            return vec![self];
+        };
+        if overrides.is_empty() {
+            return vec![Self::S3Sync {
+                source,
+                destination,
+                exclude,
+                include,
+                delete,
+                dry_run,
+                force,
+                cache_control,
+                content_type,
+                metadata_directive,
+                storage_class,
+                acl,
+                sse,
+            }];
+        }
+
+        let mut commands: Vec<AwsCommand> = overrides
+            .iter()
+            .map(|o| Self::S3Sync {
+                source: source.clone(),
+                destination: destination.clone(),
+                exclude: Some(vec!["*".to_string()]),
+                include: Some(vec![o.pattern.clone()]),
+                delete,
+                dry_run,
+                force,
+                cache_control: o.cache_control.clone().or_else(|| cache_control.clone()),
+                content_type: o.content_type.clone().or_else(|| content_type.clone()),
+                metadata_directive: metadata_directive.clone(),
+                storage_class: storage_class.clone(),
+                acl: acl.clone(),
+                sse: sse.clone(),
+            })
+            .collect();
+
+        let mut rest = exclude.unwrap_or_default();
+        rest.extend(overrides.iter().map(|o| o.pattern.clone()));
+        commands.push(Self::S3Sync {
+            source,
+            destination,
+            exclude: Some(rest),
+            include,
+            delete,
+            dry_run,
+            force,
+            cache_control,
+            content_type,
+            metadata_directive,
+            storage_class,
+            acl,
+            sse,
+        });
+        commands
+    }
+
     /// Converts the `AwsCommand` into a list of command-line arguments.
     ///
+    /// For Docker, SAM and CDK commands (see [`AwsCommand::cli`]) these are
+    /// arguments of that tool. For `EcsDeploy` these register the task definition and
+    /// print the ARN of the new revision.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -42,6 +838,12 @@ impl AwsCommand {
     ///     delete: true,
     ///     dry_run: false,
     ///     force: false,
+    ///     cache_control: None,
+    ///     content_type: None,
+    ///     metadata_directive: None,
+    ///     storage_class: None,
+    ///     acl: None,
+    ///     sse: None,
     /// };
     ///
     /// let args = sync_command.to_args();
@@ -59,6 +861,274 @@ impl AwsCommand {
     /// ```
     pub fn to_args(&self) -> Vec<String> {
         match self {
+            Self::EcrLogin { region, .. } => {
+                let mut args = vec!["ecr".to_string(), "get-login-password".to_string()];
+                if let Some(region) = region {
+                    args.push(format!("--region={}", region));
+                }
+                args
+            }
+            Self::EcrPush { image } => vec!["push".to_string(), image.clone()],
+            Self::S3Cp {
+                source,
+                destination,
+                content_type,
+                acl,
+                metadata,
+            } => {
+                let mut args = vec![
+                    "s3".to_string(),
+                    "cp".to_string(),
+                    source.to_string_lossy().to_string(),
+                    destination.to_string_lossy().to_string(),
+                ];
+
+                if let Some(content_type) = content_type {
+                    args.push(format!("--content-type={}", content_type));
+                }
+
+                if let Some(acl) = acl {
+                    args.push(format!("--acl={}", acl));
+                }
+
+                if let Some(metadata) = metadata {
+                    let pairs: Vec<String> = metadata
+                        .iter()
+                        .map(|(k, v)| format!("{}={}", k, v))
+                        .collect();
+                    args.push(format!("--metadata={}", pairs.join(",")));
+                }
+
+                args
+            }
+            Self::LambdaUpdateZip {
+                function_name,
+                zip_file,
+                publish,
+            } => {
+                let mut args = vec![
+                    "lambda".to_string(),
+                    "update-function-code".to_string(),
+                    format!("--function-name={}", function_name),
+                    format!("--zip-file=fileb://{}", zip_file.display()),
+                ];
+                if *publish {
+                    args.push("--publish".to_string());
+                }
+                args
+            }
+            Self::LambdaUpdateImage {
+                function_name,
+                image_uri,
+                publish,
+            } => {
+                let mut args = vec![
+                    "lambda".to_string(),
+                    "update-function-code".to_string(),
+                    format!("--function-name={}", function_name),
+                    format!("--image-uri={}", image_uri),
+                ];
+                if *publish {
+                    args.push("--publish".to_string());
+                }
+                args
+            }
+            Self::LambdaWait { function_name } => vec![
+                "lambda".to_string(),
+                "wait".to_string(),
+                "function-updated".to_string(),
+                format!("--function-name={}", function_name),
+            ],
+            Self::LambdaUpdateAlias {
+                function_name,
+                alias,
+                version,
+            } => vec![
+                "lambda".to_string(),
+                "update-alias".to_string(),
+                format!("--function-name={}", function_name),
+                format!("--name={}", alias),
+                format!(
+                    "--function-version={}",
+                    version.as_deref().unwrap_or("$LATEST")
+                ),
+            ],
+            Self::AssumeRole {
+                role_arn,
+                session_name,
+                duration,
+            } => {
+                let mut args = vec![
+                    "sts".to_string(),
+                    "assume-role".to_string(),
+                    format!("--role-arn={}", role_arn),
+                    format!("--role-session-name={}", session_name),
+                ];
+                if let Some(duration) = duration {
+                    args.push(format!("--duration-seconds={}", duration));
+                }
+                args.push(
+                    "--query=Credentials.[AccessKeyId,SecretAccessKey,SessionToken]".to_string(),
+                );
+                args.push("--output=text".to_string());
+                args
+            }
+            Self::Route53Upsert {
+                zone_id, record, ..
+            } => vec![
+                "route53".to_string(),
+                "change-resource-record-sets".to_string(),
+                format!("--hosted-zone-id={}", zone_id),
+                format!("--change-batch={}", record.change_batch()),
+                "--query=ChangeInfo.Id".to_string(),
+                "--output=text".to_string(),
+            ],
+            Self::EksUpdateKubeconfig {
+                cluster,
+                region,
+                kubeconfig_path,
+            } => {
+                let mut args = vec![
+                    "eks".to_string(),
+                    "update-kubeconfig".to_string(),
+                    format!("--name={}", cluster),
+                    format!("--kubeconfig={}", kubeconfig_path.display()),
+                ];
+                if let Some(region) = region {
+                    args.push(format!("--region={}", region));
+                }
+                args
+            }
+            Self::GetSecretValue { secret_id, .. } => vec![
+                "secretsmanager".to_string(),
+                "get-secret-value".to_string(),
+                format!("--secret-id={}", secret_id),
+                "--query=SecretString".to_string(),
+                "--output=text".to_string(),
+            ],
+            Self::LogsTail {
+                log_group,
+                since,
+                filter,
+            } => {
+                let mut args = vec![
+                    "logs".to_string(),
+                    "tail".to_string(),
+                    log_group.clone(),
+                    "--format=short".to_string(),
+                ];
+                if let Some(since) = since {
+                    args.push(format!("--since={}", since));
+                }
+                if let Some(filter) = filter {
+                    args.push(format!("--filter-pattern={}", filter));
+                }
+                args
+            }
+            Self::Presign { key, expires_in } => vec![
+                "s3".to_string(),
+                "presign".to_string(),
+                key.clone(),
+                format!("--expires-in={}", expires_in),
+            ],
+            Self::CloudFrontInvalidate {
+                distribution_id, ..
+            } => vec![
+                "cloudfront".to_string(),
+                "create-invalidation".to_string(),
+                format!("--distribution-id={}", distribution_id),
+                format!(
+                    "--invalidation-batch={}",
+                    self.invalidation_batch().unwrap_or_default()
+                ),
+            ],
+            Self::ListInvalidations {
+                distribution_id,
+                starting_token,
+            } => page_args(
+                vec![
+                    "cloudfront".to_string(),
+                    "list-invalidations".to_string(),
+                    format!("--distribution-id={}", distribution_id),
+                ],
+                starting_token,
+            ),
+            Self::ListFunctionVersions {
+                function_name,
+                starting_token,
+            } => page_args(
+                vec![
+                    "lambda".to_string(),
+                    "list-versions-by-function".to_string(),
+                    format!("--function-name={}", function_name),
+                ],
+                starting_token,
+            ),
+            Self::SamDeploy {
+                stack_name,
+                template,
+                parameter_overrides,
+                capabilities,
+                ..
+            } => {
+                let mut args = vec!["deploy".to_string(), format!("--stack-name={}", stack_name)];
+                if let Some(template) = template {
+                    args.push(format!("--template-file={}", template.display()));
+                }
+                args.push("--no-confirm-changeset".to_string());
+                args.push("--no-fail-on-empty-changeset".to_string());
+                if !capabilities.is_empty() {
+                    args.push("--capabilities".to_string());
+                    args.extend(capabilities.iter().cloned());
+                }
+                if !parameter_overrides.is_empty() {
+                    args.push("--parameter-overrides".to_string());
+                    args.extend(
+                        parameter_overrides
+                            .iter()
+                            .map(|(k, v)| format!("{}={}", k, v)),
+                    );
+                }
+                args
+            }
+            Self::CdkDeploy {
+                stacks,
+                app,
+                parameters,
+                ..
+            } => {
+                let mut args = vec!["deploy".to_string()];
+                if stacks.is_empty() {
+                    args.push("--all".to_string());
+                } else {
+                    args.extend(stacks.iter().cloned());
+                }
+                args.push("--require-approval=never".to_string());
+                if let Some(app) = app {
+                    args.push(format!("--app={}", app));
+                }
+                args.extend(
+                    parameters
+                        .iter()
+                        .map(|(k, v)| format!("--parameters={}={}", k, v)),
+                );
+                args
+            }
+            Self::TagResource { arn, tags } => vec![
+                "resourcegroupstaggingapi".to_string(),
+                "tag-resources".to_string(),
+                format!("--resource-arn-list={}", arn),
+                format!("--tags={}", serde_json::json!(tags)),
+            ],
+            Self::EcsDeploy {
+                task_definition, ..
+            } => vec![
+                "ecs".to_string(),
+                "register-task-definition".to_string(),
+                format!("--cli-input-json=file://{}", task_definition.display()),
+                "--query=taskDefinition.taskDefinitionArn".to_string(),
+                "--output=text".to_string(),
+            ],
             Self::S3Sync {
                 source,
                 destination,
@@ -67,6 +1137,12 @@ impl AwsCommand {
                 delete,
                 dry_run,
                 force,
+                cache_control,
+                content_type,
+                metadata_directive,
+                storage_class,
+                acl,
+                sse,
             } => {
                 let mut args = vec![
                     "s3".to_string(),
@@ -99,8 +1175,565 @@ impl AwsCommand {
                     args.push("--force".to_string());
                 }
 
+                if let Some(cache_control) = cache_control {
+                    args.push(format!("--cache-control={}", cache_control));
+                }
+
+                if let Some(content_type) = content_type {
+                    args.push(format!("--content-type={}", content_type));
+                }
+
+                if let Some(directive) = metadata_directive {
+                    args.push(format!("--metadata-directive={}", directive));
+                }
+
+                if let Some(storage_class) = storage_class {
+                    args.push(format!("--storage-class={}", storage_class));
+                }
+
+                if let Some(acl) = acl {
+                    args.push(format!("--acl={}", acl));
+                }
+
+                if let Some(sse) = sse {
+                    args.push(format!("--sse={}", sse));
+                }
+
                 args
             }
         }
     }
 }
+
+/// Appends the page size, JSON output and pagination token of a list command.
+fn page_args(mut args: Vec<String>, starting_token: &Option<String>) -> Vec<String> {
+    args.push(format!("--max-items={}", PAGE_SIZE));
+    args.push("--output=json".to_string());
+    if let Some(token) = starting_token {
+        args.push(format!("--starting-token={}", token));
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_ecr_login_args() {
+        let command = AwsCommand::EcrLogin {
+            registry: "123.dkr.ecr.eu-west-1.amazonaws.com".to_string(),
+            region: Some("eu-west-1".to_string()),
+        };
+        assert_eq!(
+            command.to_args(),
+            vec!["ecr", "get-login-password", "--region=eu-west-1"]
+        );
+        assert_eq!(
+            command.docker_login_args().unwrap(),
+            vec![
+                "login",
+                "--username",
+                "AWS",
+                "--password-stdin",
+                "123.dkr.ecr.eu-west-1.amazonaws.com"
+            ]
+        );
+        assert!(!command.is_docker());
+    }
+
+    #[test]
+    fn test_ecr_push_args() {
+        let command = AwsCommand::EcrPush {
+            image: "registry/app:1.0".to_string(),
+        };
+        assert_eq!(command.to_args(), vec!["push", "registry/app:1.0"]);
+        assert!(command.is_docker());
+        assert!(command.docker_login_args().is_none());
+    }
+
+    fn sync() -> AwsCommand {
+        AwsCommand::S3Sync {
+            source: PathBuf::from("./dist"),
+            destination: PathBuf::from("s3://site"),
+            exclude: Some(vec!["*.map".to_string()]),
+            include: None,
+            delete: true,
+            dry_run: false,
+            force: false,
+            cache_control: Some("max-age=300".to_string()),
+            content_type: None,
+            metadata_directive: Some("REPLACE".to_string()),
+            storage_class: None,
+            acl: None,
+            sse: None,
+        }
+    }
+
+    #[test]
+    fn test_s3_sync_header_args() {
+        let args = sync().to_args();
+        assert!(args.contains(&"--cache-control=max-age=300".to_string()));
+        assert!(args.contains(&"--metadata-directive=REPLACE".to_string()));
+        assert!(!args.iter().any(|a| a.starts_with("--content-type")));
+    }
+
+    #[test]
+    fn test_s3_sync_storage_args() {
+        let mut command = sync();
+        if let AwsCommand::S3Sync {
+            storage_class,
+            acl,
+            sse,
+            ..
+        } = &mut command
+        {
+            *storage_class = Some("INTELLIGENT_TIERING".to_string());
+            *acl = Some("bucket-owner-full-control".to_string());
+            *sse = Some("aws:kms".to_string());
+        }
+
+        let args = command.to_args();
+        assert!(args.ends_with(&[
+            "--storage-class=INTELLIGENT_TIERING".to_string(),
+            "--acl=bucket-owner-full-control".to_string(),
+            "--sse=aws:kms".to_string(),
+        ]));
+    }
+
+    #[test]
+    fn test_s3_sync_overrides() {
+        let commands = sync().with_overrides(&[
+            SyncOverride::cache_control("assets/*", "public, max-age=31536000, immutable"),
+            SyncOverride::cache_control("index.html", "no-cache").with_content_type("text/html"),
+        ]);
+        assert_eq!(commands.len(), 3);
+
+        let first = commands[0].to_args();
+        assert_eq!(
+            &first[4..],
+            &[
+                "--exclude=*",
+                "--include=assets/*",
+                "--delete",
+                "--cache-control=public, max-age=31536000, immutable",
+                "--metadata-directive=REPLACE"
+            ]
+        );
+
+        let second = commands[1].to_args();
+        assert!(second.contains(&"--content-type=text/html".to_string()));
+        assert!(second.contains(&"--cache-control=no-cache".to_string()));
+
+        let rest = commands[2].to_args();
+        assert_eq!(
+            &rest[4..],
+            &[
+                "--exclude=*.map",
+                "--exclude=assets/*",
+                "--exclude=index.html",
+                "--delete",
+                "--cache-control=max-age=300",
+                "--metadata-directive=REPLACE"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overrides_ignored_for_other_commands() {
+        let command = AwsCommand::EcrPush {
+            image: "app".to_string(),
+        };
+        let commands = command.with_overrides(&[SyncOverride::cache_control("*", "no-cache")]);
+        assert_eq!(commands.len(), 1);
+        assert_eq!(sync().with_overrides(&[]).len(), 1);
+    }
+
+    #[test]
+    fn test_s3_cp_args() {
+        let metadata = BTreeMap::from([
+            ("version".to_string(), "1.2.0".to_string()),
+            ("commit".to_string(), "abc123".to_string()),
+        ]);
+        let command = AwsCommand::S3Cp {
+            source: PathBuf::from("dist/manifest.json"),
+            destination: PathBuf::from("s3://bucket/manifest.json"),
+            content_type: Some("application/json".to_string()),
+            acl: Some("bucket-owner-full-control".to_string()),
+            metadata: Some(metadata),
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "s3",
+                "cp",
+                "dist/manifest.json",
+                "s3://bucket/manifest.json",
+                "--content-type=application/json",
+                "--acl=bucket-owner-full-control",
+                "--metadata=commit=abc123,version=1.2.0"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lambda_args() {
+        let update = AwsCommand::LambdaUpdateZip {
+            function_name: "api".to_string(),
+            zip_file: PathBuf::from("/work/app.zip"),
+            publish: true,
+        };
+        assert_eq!(
+            update.to_args(),
+            vec![
+                "lambda",
+                "update-function-code",
+                "--function-name=api",
+                "--zip-file=fileb:///work/app.zip",
+                "--publish"
+            ]
+        );
+
+        let wait = AwsCommand::LambdaWait {
+            function_name: "api".to_string(),
+        };
+        assert_eq!(
+            wait.to_args(),
+            vec!["lambda", "wait", "function-updated", "--function-name=api"]
+        );
+
+        let alias = AwsCommand::LambdaUpdateAlias {
+            function_name: "api".to_string(),
+            alias: "live".to_string(),
+            version: Some("7".to_string()),
+        };
+        assert_eq!(
+            alias.to_args(),
+            vec![
+                "lambda",
+                "update-alias",
+                "--function-name=api",
+                "--name=live",
+                "--function-version=7"
+            ]
+        );
+        assert!(alias.page_items().is_none());
+        assert!(wait.page_items().is_none());
+    }
+
+    #[test]
+    fn test_list_args_follow_token() {
+        let command = AwsCommand::ListFunctionVersions {
+            function_name: "api".to_string(),
+            starting_token: None,
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "lambda",
+                "list-versions-by-function",
+                "--function-name=api",
+                "--max-items=100",
+                "--output=json"
+            ]
+        );
+        assert_eq!(command.page_items(), Some("/Versions"));
+
+        let next = AwsCommand::ListInvalidations {
+            distribution_id: "E123".to_string(),
+            starting_token: None,
+        }
+        .with_starting_token("tok".to_string());
+        assert_eq!(
+            next.to_args(),
+            vec![
+                "cloudfront",
+                "list-invalidations",
+                "--distribution-id=E123",
+                "--max-items=100",
+                "--output=json",
+                "--starting-token=tok"
+            ]
+        );
+        assert!(next.is_read_only());
+    }
+
+    #[test]
+    fn test_assume_role_args() {
+        let command = AwsCommand::AssumeRole {
+            role_arn: "arn:aws:iam::123:role/deploy".to_string(),
+            session_name: "ci".to_string(),
+            duration: Some(900),
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "sts",
+                "assume-role",
+                "--role-arn=arn:aws:iam::123:role/deploy",
+                "--role-session-name=ci",
+                "--duration-seconds=900",
+                "--query=Credentials.[AccessKeyId,SecretAccessKey,SessionToken]",
+                "--output=text"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_route53_upsert_args() {
+        let command = AwsCommand::Route53Upsert {
+            zone_id: "Z123".to_string(),
+            record: Route53Record {
+                name: "api.example.com".to_string(),
+                record_type: "A".to_string(),
+                ttl: 60,
+                values: vec!["10.0.0.1".to_string(), "10.0.0.2".to_string()],
+            },
+            wait: true,
+        };
+
+        let args = command.to_args();
+        assert_eq!(args[2], "--hosted-zone-id=Z123");
+        let batch: serde_json::Value =
+            serde_json::from_str(args[3].strip_prefix("--change-batch=").unwrap()).unwrap();
+        let set = &batch["Changes"][0]["ResourceRecordSet"];
+        assert_eq!(set["Name"], "api.example.com");
+        assert_eq!(set["Type"], "A");
+        assert_eq!(set["ResourceRecords"][1]["Value"], "10.0.0.2");
+
+        assert_eq!(
+            command.route53_wait_args("/change/C1").unwrap(),
+            vec![
+                "route53",
+                "wait",
+                "resource-record-sets-changed",
+                "--id=/change/C1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eks_update_kubeconfig_args() {
+        let command = AwsCommand::EksUpdateKubeconfig {
+            cluster: "prod".to_string(),
+            region: Some("eu-west-1".to_string()),
+            kubeconfig_path: PathBuf::from("/tmp/kubeconfig"),
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "eks",
+                "update-kubeconfig",
+                "--name=prod",
+                "--kubeconfig=/tmp/kubeconfig",
+                "--region=eu-west-1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ecs_deploy_args() {
+        let command = AwsCommand::EcsDeploy {
+            cluster: "prod".to_string(),
+            service: "api".to_string(),
+            task_definition: PathBuf::from("/deploy/task.json"),
+            wait: true,
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "ecs",
+                "register-task-definition",
+                "--cli-input-json=file:///deploy/task.json",
+                "--query=taskDefinition.taskDefinitionArn",
+                "--output=text"
+            ]
+        );
+        assert_eq!(
+            command.ecs_update_service_args("arn:td:3").unwrap(),
+            vec![
+                "ecs",
+                "update-service",
+                "--cluster=prod",
+                "--service=api",
+                "--task-definition=arn:td:3"
+            ]
+        );
+        assert_eq!(
+            command.ecs_wait_args().unwrap(),
+            vec![
+                "ecs",
+                "wait",
+                "services-stable",
+                "--cluster=prod",
+                "--services=api"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_logs_tail_args() {
+        let command = AwsCommand::LogsTail {
+            log_group: "/aws/lambda/api".to_string(),
+            since: Some("5m".to_string()),
+            filter: Some("ERROR".to_string()),
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "logs",
+                "tail",
+                "/aws/lambda/api",
+                "--format=short",
+                "--since=5m",
+                "--filter-pattern=ERROR"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_presign_args() {
+        let command = AwsCommand::Presign {
+            key: "s3://artifacts/app.zip".to_string(),
+            expires_in: 600,
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "s3",
+                "presign",
+                "s3://artifacts/app.zip",
+                "--expires-in=600"
+            ]
+        );
+        assert!(command.is_read_only());
+    }
+
+    #[test]
+    fn test_cloudfront_invalidate_args() {
+        let command = AwsCommand::CloudFrontInvalidate {
+            distribution_id: "E123".to_string(),
+            paths: vec!["/index.html".to_string(), "/assets/*".to_string()],
+            caller_reference: "ref-1".to_string(),
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "cloudfront",
+                "create-invalidation",
+                "--distribution-id=E123",
+                r#"--invalidation-batch={"CallerReference":"ref-1","Paths":{"Items":["/index.html","/assets/*"],"Quantity":2}}"#
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tag_resource_args() {
+        let command = AwsCommand::TagResource {
+            arn: "arn:aws:lambda:eu-west-1:123:function:api".to_string(),
+            tags: BTreeMap::from([
+                ("commit".to_string(), "abc123".to_string()),
+                ("run".to_string(), "say \"42\"".to_string()),
+            ]),
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "resourcegroupstaggingapi",
+                "tag-resources",
+                "--resource-arn-list=arn:aws:lambda:eu-west-1:123:function:api",
+                r#"--tags={"commit":"abc123","run":"say \"42\""}"#
+            ]
+        );
+        assert!(!command.is_read_only());
+    }
+
+    #[test]
+    fn test_sam_deploy_args() {
+        let command = AwsCommand::SamDeploy {
+            project_dir: PathBuf::from("/work"),
+            stack_name: "api".to_string(),
+            template: Some(PathBuf::from("template.yaml")),
+            parameter_overrides: BTreeMap::from([
+                ("Stage".to_string(), "prod".to_string()),
+                ("Commit".to_string(), "abc".to_string()),
+            ]),
+            capabilities: vec!["CAPABILITY_IAM".to_string()],
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "deploy",
+                "--stack-name=api",
+                "--template-file=template.yaml",
+                "--no-confirm-changeset",
+                "--no-fail-on-empty-changeset",
+                "--capabilities",
+                "CAPABILITY_IAM",
+                "--parameter-overrides",
+                "Commit=abc",
+                "Stage=prod"
+            ]
+        );
+        assert_eq!(command.cli(), Cli::Sam);
+        assert_eq!(command.operation(), "sam deploy");
+    }
+
+    #[test]
+    fn test_cdk_deploy_args() {
+        let command = AwsCommand::CdkDeploy {
+            project_dir: PathBuf::from("/work"),
+            stacks: Vec::new(),
+            app: Some("npx ts-node bin/app.ts".to_string()),
+            parameters: BTreeMap::from([("Api:Stage".to_string(), "prod".to_string())]),
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "deploy",
+                "--all",
+                "--require-approval=never",
+                "--app=npx ts-node bin/app.ts",
+                "--parameters=Api:Stage=prod"
+            ]
+        );
+        assert_eq!(command.cli(), Cli::Cdk);
+        assert!(!command.is_read_only());
+    }
+
+    proptest! {
+        #[test]
+        fn test_invalidation_batch_round_trips_any_path(
+            paths in vec(any::<String>().prop_map(|path| format!("/{}", path)), 1..5),
+            caller_reference in any::<String>(),
+        ) {
+            let command = AwsCommand::CloudFrontInvalidate {
+                distribution_id: "E123".to_string(),
+                paths: paths.clone(),
+                caller_reference: caller_reference.clone(),
+            };
+
+            let args = command.to_args();
+            let payload = args[3].strip_prefix("--invalidation-batch=").unwrap();
+            let batch: serde_json::Value = serde_json::from_str(payload)?;
+
+            let items: Vec<String> = serde_json::from_value(batch["Paths"]["Items"].clone())?;
+            prop_assert_eq!(items, paths.clone());
+            prop_assert_eq!(&batch["Paths"]["Quantity"], paths.len());
+            prop_assert_eq!(&batch["CallerReference"], caller_reference.as_str());
+        }
+    }
+
+    #[test]
+    fn test_ecs_deploy_without_wait() {
+        let command = AwsCommand::EcsDeploy {
+            cluster: "prod".to_string(),
+            service: "api".to_string(),
+            task_definition: PathBuf::from("task.json"),
+            wait: false,
+        };
+        assert!(command.ecs_wait_args().is_none());
+    }
+}
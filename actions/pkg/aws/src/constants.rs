@@ -1,52 +1,565 @@
-use config::{ConfigValue, FileExists, Required};
+use config::{ConfigError, ConfigValue, FileExists, Number, Required, Validator};
 use lazy_static::lazy_static;
 use std::path::PathBuf;
 
 /// ENV keys
 pub const ENV_AWS_CMD: &str = "ACTION_AWS_CMD";
 pub const ENV_AWS_BIN: &str = "ACTION_AWS_BIN";
+pub const ENV_AWS_S3_SOURCE: &str = "ACTION_AWS_S3_SOURCE";
 pub const ENV_AWS_S3_DESTINATION: &str = "ACTION_AWS_S3_DESTINATION";
 pub const ENV_AWS_S3_EXCLUDE: &str = "ACTION_AWS_S3_EXCLUDE";
 pub const ENV_AWS_S3_INCLUDE: &str = "ACTION_AWS_S3_INCLUDE";
 pub const ENV_AWS_S3_DELETE: &str = "ACTION_AWS_S3_DELETE";
 pub const ENV_AWS_S3_DRY_RUN: &str = "ACTION_AWS_S3_DRY_RUN";
 pub const ENV_AWS_S3_FORCE: &str = "ACTION_AWS_S3_FORCE";
+pub const ENV_AWS_S3_CACHE_CONTROL: &str = "ACTION_AWS_S3_CACHE_CONTROL";
+pub const ENV_AWS_S3_METADATA_DIRECTIVE: &str = "ACTION_AWS_S3_METADATA_DIRECTIVE";
+pub const ENV_AWS_S3_CACHE_OVERRIDES: &str = "ACTION_AWS_S3_CACHE_OVERRIDES";
+pub const ENV_AWS_S3_ASSET_CACHE_CONTROL: &str = "ACTION_AWS_S3_ASSET_CACHE_CONTROL";
+pub const ENV_AWS_S3_HTML_CACHE_CONTROL: &str = "ACTION_AWS_S3_HTML_CACHE_CONTROL";
+pub const ENV_AWS_S3_STORAGE_CLASS: &str = "ACTION_AWS_S3_STORAGE_CLASS";
+pub const ENV_AWS_S3_SSE: &str = "ACTION_AWS_S3_SSE";
+pub const ENV_AWS_S3_CP_SOURCE: &str = "ACTION_AWS_S3_CP_SOURCE";
+pub const ENV_AWS_S3_PRESIGN_KEY: &str = "ACTION_AWS_S3_PRESIGN_KEY";
+pub const ENV_AWS_S3_PRESIGN_EXPIRES: &str = "ACTION_AWS_S3_PRESIGN_EXPIRES";
+pub const ENV_AWS_S3_PRESIGN_MASK: &str = "ACTION_AWS_S3_PRESIGN_MASK";
+pub const ENV_AWS_S3_CONTENT_TYPE: &str = "ACTION_AWS_S3_CONTENT_TYPE";
+pub const ENV_AWS_S3_ACL: &str = "ACTION_AWS_S3_ACL";
+pub const ENV_AWS_S3_METADATA: &str = "ACTION_AWS_S3_METADATA";
+pub const ENV_AWS_LAMBDA_FUNCTION: &str = "ACTION_AWS_LAMBDA_FUNCTION";
+pub const ENV_AWS_LAMBDA_ZIP: &str = "ACTION_AWS_LAMBDA_ZIP";
+pub const ENV_AWS_LAMBDA_IMAGE: &str = "ACTION_AWS_LAMBDA_IMAGE";
+pub const ENV_AWS_LAMBDA_ALIAS: &str = "ACTION_AWS_LAMBDA_ALIAS";
+pub const ENV_AWS_LAMBDA_PUBLISH: &str = "ACTION_AWS_LAMBDA_PUBLISH";
+pub const ENV_AWS_LAMBDA_BATCH: &str = "ACTION_AWS_LAMBDA_BATCH";
+pub const ENV_AWS_LAMBDA_MANIFEST: &str = "ACTION_AWS_LAMBDA_MANIFEST";
+pub const ENV_AWS_LAMBDA_FAIL_FAST: &str = "ACTION_AWS_LAMBDA_FAIL_FAST";
+pub const ENV_AWS_ROLE_ARN: &str = "ACTION_AWS_ROLE_ARN";
+pub const ENV_AWS_ROLE_SESSION_NAME: &str = "ACTION_AWS_ROLE_SESSION_NAME";
+pub const ENV_AWS_ROLE_DURATION: &str = "ACTION_AWS_ROLE_DURATION";
+pub const ENV_AWS_SECRETS: &str = "ACTION_AWS_SECRETS";
+pub const ENV_AWS_ROUTE53_ZONE_ID: &str = "ACTION_AWS_ROUTE53_ZONE_ID";
+pub const ENV_AWS_ROUTE53_RECORD_NAME: &str = "ACTION_AWS_ROUTE53_RECORD_NAME";
+pub const ENV_AWS_ROUTE53_RECORD_TYPE: &str = "ACTION_AWS_ROUTE53_RECORD_TYPE";
+pub const ENV_AWS_ROUTE53_RECORD_TTL: &str = "ACTION_AWS_ROUTE53_RECORD_TTL";
+pub const ENV_AWS_ROUTE53_RECORD_VALUES: &str = "ACTION_AWS_ROUTE53_RECORD_VALUES";
+pub const ENV_AWS_ROUTE53_WAIT: &str = "ACTION_AWS_ROUTE53_WAIT";
+pub const ENV_AWS_EKS_CLUSTER: &str = "ACTION_AWS_EKS_CLUSTER";
+pub const ENV_AWS_EKS_REGION: &str = "ACTION_AWS_EKS_REGION";
+pub const ENV_AWS_EKS_KUBECONFIG: &str = "ACTION_AWS_EKS_KUBECONFIG";
+pub const ENV_AWS_ECR_REGISTRY: &str = "ACTION_AWS_ECR_REGISTRY";
+pub const ENV_AWS_ECR_IMAGE: &str = "ACTION_AWS_ECR_IMAGE";
+pub const ENV_AWS_DOCKER_BIN: &str = "ACTION_AWS_DOCKER_BIN";
+pub const ENV_AWS_ECS_CLUSTER: &str = "ACTION_AWS_ECS_CLUSTER";
+pub const ENV_AWS_ECS_SERVICE: &str = "ACTION_AWS_ECS_SERVICE";
+pub const ENV_AWS_ECS_TASK_DEFINITION: &str = "ACTION_AWS_ECS_TASK_DEFINITION";
+pub const ENV_AWS_ECS_WAIT: &str = "ACTION_AWS_ECS_WAIT";
+pub const ENV_AWS_RETRY_ATTEMPTS: &str = "ACTION_AWS_RETRY_ATTEMPTS";
+pub const ENV_AWS_RETRY_DELAY_MS: &str = "ACTION_AWS_RETRY_DELAY_MS";
+pub const ENV_AWS_LOGS_GROUP: &str = "ACTION_AWS_LOGS_GROUP";
+pub const ENV_AWS_LOGS_SINCE: &str = "ACTION_AWS_LOGS_SINCE";
+pub const ENV_AWS_LOGS_FILTER: &str = "ACTION_AWS_LOGS_FILTER";
+pub const ENV_AWS_CLOUDFRONT_DISTRIBUTION: &str = "ACTION_AWS_CLOUDFRONT_DISTRIBUTION";
+pub const ENV_AWS_CLOUDFRONT_PATHS: &str = "ACTION_AWS_CLOUDFRONT_PATHS";
+pub const ENV_AWS_CLOUDFRONT_PARALLEL: &str = "ACTION_AWS_CLOUDFRONT_PARALLEL";
+pub const ENV_AWS_TAGS: &str = "ACTION_AWS_TAGS";
+pub const ENV_AWS_TAG_RESOURCES: &str = "ACTION_AWS_TAG_RESOURCES";
+pub const ENV_AWS_DRY_RUN: &str = "ACTION_AWS_DRY_RUN";
+pub const ENV_AWS_REGION: &str = "ACTION_AWS_REGION";
+pub const ENV_AWS_PROFILE: &str = "ACTION_AWS_PROFILE";
+pub const ENV_AWS_ENDPOINT_URL: &str = "ACTION_AWS_ENDPOINT_URL";
+pub const ENV_AWS_BACKEND: &str = "ACTION_AWS_BACKEND";
+pub const ENV_AWS_SAM_BIN: &str = "ACTION_AWS_SAM_BIN";
+pub const ENV_AWS_SAM_STACK: &str = "ACTION_AWS_SAM_STACK";
+pub const ENV_AWS_SAM_TEMPLATE: &str = "ACTION_AWS_SAM_TEMPLATE";
+pub const ENV_AWS_SAM_CAPABILITIES: &str = "ACTION_AWS_SAM_CAPABILITIES";
+pub const ENV_AWS_CDK_BIN: &str = "ACTION_AWS_CDK_BIN";
+pub const ENV_AWS_CDK_STACKS: &str = "ACTION_AWS_CDK_STACKS";
+pub const ENV_AWS_CDK_APP: &str = "ACTION_AWS_CDK_APP";
+pub const ENV_AWS_PARAMETER_OVERRIDES: &str = "ACTION_AWS_PARAMETER_OVERRIDES";
 
 /// Default values
 pub const DEFAULT_AWS_BIN: &str = "/usr/local/bin/aws";
+pub const DEFAULT_DOCKER_BIN: &str = "docker";
+pub const DEFAULT_SAM_BIN: &str = "sam";
+pub const DEFAULT_CDK_BIN: &str = "cdk";
 pub const DEFAULT_EMPTY: &str = "";
+pub const DEFAULT_ROLE_SESSION_NAME: &str = "ci-actions";
+pub const DEFAULT_ROUTE53_RECORD_TYPE: &str = "CNAME";
+pub const DEFAULT_ROUTE53_TTL: u32 = 300;
+pub const DEFAULT_KUBECONFIG: &str = "~/.kube/config";
+pub const DEFAULT_PRESIGN_EXPIRES: u32 = 3600;
+/// Longest lifetime S3 accepts for a presigned URL (7 days).
+pub const MAX_PRESIGN_EXPIRES: u32 = 604_800;
+pub const DEFAULT_ASSET_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+pub const DEFAULT_HTML_CACHE_CONTROL: &str = "no-cache";
+pub const DEFAULT_LOGS_SINCE: &str = "10m";
+pub const DEFAULT_CLOUDFRONT_PATHS: &str = "/*";
+pub const DEFAULT_RETRY_ATTEMPTS: u32 = 3;
+pub const DEFAULT_RETRY_DELAY_MS: u64 = 1000;
+pub const DEFAULT_BACKEND: &str = "cli";
+
+/// Storage classes accepted by `--storage-class`.
+pub const S3_STORAGE_CLASSES: &[&str] = &[
+    "STANDARD",
+    "REDUCED_REDUNDANCY",
+    "STANDARD_IA",
+    "ONEZONE_IA",
+    "INTELLIGENT_TIERING",
+    "GLACIER",
+    "DEEP_ARCHIVE",
+    "GLACIER_IR",
+];
+
+/// Server-side encryption modes accepted by `--sse`.
+pub const S3_SSE_MODES: &[&str] = &["AES256", "aws:kms"];
 
 lazy_static! {
     /// Configuration value for the AWS command.
     pub static ref CMD: ConfigValue<Required> =
-        ConfigValue::<Required>::required(ENV_AWS_CMD);
+        ConfigValue::<Required>::required(ENV_AWS_CMD)
+            .with_description("AWS commands to run in order, comma-separated, e.g. s3_sync,cloudfront_invalidate.");
 
     /// Configuration value for the AWS CLI executable path.
     pub static ref AWS_BIN: ConfigValue<PathBuf> =
         ConfigValue::new(PathBuf::from(DEFAULT_AWS_BIN), ENV_AWS_BIN)
-            .with_validator(FileExists);
+            .expand()
+            .with_validator(|v: &PathBuf| match BACKEND.parse() {
+                // The SDK backend runs the CLI only for the commands it does not cover.
+                Ok(crate::sdk::Backend::Sdk) => Ok(()),
+                _ => FileExists.validate(v),
+            })
+            .with_description("Path to the AWS CLI executable.");
+
+    /// Configuration value for the synced directory.
+    pub static ref S3_SOURCE: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_EMPTY), ENV_AWS_S3_SOURCE)
+            .with_description("Directory synced by s3_sync, relative to the working directory; defaults to the working directory itself.");
 
     /// Configuration value for S3 destination bucket/path.
     pub static ref S3_DESTINATION: ConfigValue<PathBuf> =
-        ConfigValue::new(PathBuf::from(DEFAULT_EMPTY), ENV_AWS_S3_DESTINATION);
+        ConfigValue::new(PathBuf::from(DEFAULT_EMPTY), ENV_AWS_S3_DESTINATION)
+            .with_description("S3 destination, e.g. s3://bucket/prefix.");
 
     /// Configuration value for exclude patterns.
     pub static ref S3_EXCLUDE: ConfigValue<String> =
-        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_EXCLUDE);
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_EXCLUDE)
+            .with_description("Comma-separated exclude patterns.");
 
     /// Configuration value for include patterns.
     pub static ref S3_INCLUDE: ConfigValue<String> =
-        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_INCLUDE);
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_INCLUDE)
+            .with_description("Comma-separated include patterns.");
 
     /// Configuration value for delete flag.
     pub static ref S3_DELETE: ConfigValue<bool> =
-        ConfigValue::new(false, ENV_AWS_S3_DELETE);
+        ConfigValue::new(false, ENV_AWS_S3_DELETE)
+            .with_description("Delete destination files missing from the source.");
 
     /// Configuration value for dry run flag.
     pub static ref S3_DRY_RUN: ConfigValue<bool> =
-        ConfigValue::new(false, ENV_AWS_S3_DRY_RUN);
+        ConfigValue::new(false, ENV_AWS_S3_DRY_RUN)
+            .with_description("Show what would be synced without syncing.");
 
     /// Configuration value for force flag.
     pub static ref S3_FORCE: ConfigValue<bool> =
-        ConfigValue::new(false, ENV_AWS_S3_FORCE);
+        ConfigValue::new(false, ENV_AWS_S3_FORCE)
+            .with_description("Pass --force to the sync command.");
+
+    /// Configuration value for the Cache-Control of synced files.
+    pub static ref S3_CACHE_CONTROL: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_CACHE_CONTROL)
+            .with_description("Cache-Control of synced files.");
+
+    /// Configuration value for the Cache-Control of static site assets.
+    pub static ref S3_ASSET_CACHE_CONTROL: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_ASSET_CACHE_CONTROL.to_string(), ENV_AWS_S3_ASSET_CACHE_CONTROL)
+            .with_description("Cache-Control of the non-HTML files deployed by static_site.");
+
+    /// Configuration value for the Cache-Control of static site pages.
+    pub static ref S3_HTML_CACHE_CONTROL: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_HTML_CACHE_CONTROL.to_string(), ENV_AWS_S3_HTML_CACHE_CONTROL)
+            .with_description("Cache-Control of the HTML files deployed by static_site.");
+
+    /// Configuration value for the metadata directive of the sync.
+    pub static ref S3_METADATA_DIRECTIVE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_METADATA_DIRECTIVE)
+            .with_validator(|v: &String| match v.as_str() {
+                "" | "COPY" | "REPLACE" => Ok(()),
+                _ => Err(ConfigError::invalid(v, "one of: COPY, REPLACE")),
+            })
+            .with_description("Metadata directive for S3-to-S3 syncs: COPY or REPLACE.");
+
+    /// Configuration value for the per-pattern Cache-Control overrides.
+    pub static ref S3_CACHE_OVERRIDES: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_CACHE_OVERRIDES)
+            .with_validator(crate::validate_overrides)
+            .with_description("Semicolon-separated pattern=cache-control overrides, e.g. 'assets/*=max-age=31536000;index.html=no-cache'.");
+
+    /// Configuration value for the storage class of synced files.
+    pub static ref S3_STORAGE_CLASS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_STORAGE_CLASS)
+            .with_validator(|v: &String| {
+                if v.is_empty() || S3_STORAGE_CLASSES.contains(&v.as_str()) {
+                    Ok(())
+                } else {
+                    Err(ConfigError::invalid(
+                        v,
+                        format!("one of: {}", S3_STORAGE_CLASSES.join(", ")),
+                    ))
+                }
+            })
+            .with_description("Storage class of synced files, e.g. INTELLIGENT_TIERING.");
+
+    /// Configuration value for the server-side encryption of synced files.
+    pub static ref S3_SSE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_SSE)
+            .with_validator(|v: &String| {
+                if v.is_empty() || S3_SSE_MODES.contains(&v.as_str()) {
+                    Ok(())
+                } else {
+                    Err(ConfigError::invalid(
+                        v,
+                        format!("one of: {}", S3_SSE_MODES.join(", ")),
+                    ))
+                }
+            })
+            .with_description("Server-side encryption of synced files: AES256 or aws:kms.");
+
+    /// Configuration value for the file copied by s3_cp.
+    pub static ref S3_CP_SOURCE: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_EMPTY), ENV_AWS_S3_CP_SOURCE)
+            .with_description("File copied by s3_cp: a local path relative to the working directory or an s3:// URL.");
+
+    /// Configuration value for the presigned object.
+    pub static ref S3_PRESIGN_KEY: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_PRESIGN_KEY)
+            .with_validator(|v: &String| {
+                if v.is_empty() || v.starts_with("s3://") {
+                    Ok(())
+                } else {
+                    Err(ConfigError::invalid(v, "an s3:// URI"))
+                }
+            })
+            .with_description("S3 URI of the object s3_presign generates a URL for.");
+
+    /// Configuration value for the lifetime of presigned URLs.
+    pub static ref S3_PRESIGN_EXPIRES: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_PRESIGN_EXPIRES.to_string(), ENV_AWS_S3_PRESIGN_EXPIRES)
+            .with_validator(
+                Number::<u32>::new("a lifetime between 1 and 604800 seconds")
+                    .at_least(1)
+                    .at_most(MAX_PRESIGN_EXPIRES),
+            )
+            .with_description("Lifetime of the presigned URL in seconds.");
+
+    /// Configuration value for masking presigned URLs.
+    pub static ref S3_PRESIGN_MASK: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_AWS_S3_PRESIGN_MASK)
+            .with_description("Mask the signature of the presigned URL in the logs.");
+
+    /// Configuration value for the uploaded object content type.
+    pub static ref S3_CONTENT_TYPE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_CONTENT_TYPE)
+            .with_description("Content-Type of uploaded or synced files.");
+
+    /// Configuration value for the canned ACL.
+    pub static ref S3_ACL: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_ACL)
+            .with_description("Canned ACL of uploaded or synced files, e.g. bucket-owner-full-control.");
+
+    /// Configuration value for the uploaded object metadata.
+    pub static ref S3_METADATA: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_S3_METADATA)
+            .with_validator(crate::validate_metadata)
+            .with_description("Comma-separated key=value metadata of uploaded objects.");
+
+    /// Configuration value for the Lambda function.
+    pub static ref LAMBDA_FUNCTION: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_LAMBDA_FUNCTION)
+            .with_description("Name or ARN of the Lambda function.");
+
+    /// Configuration value for the Lambda deployment package.
+    pub static ref LAMBDA_ZIP: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_EMPTY), ENV_AWS_LAMBDA_ZIP)
+            .relative_to_working_dir()
+            .with_description("Lambda zip package, relative to the working directory.");
+
+    /// Configuration value for the Lambda container image.
+    pub static ref LAMBDA_IMAGE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_LAMBDA_IMAGE)
+            .with_description("Lambda container image URI; mutually exclusive with the zip package.");
+
+    /// Configuration value for the Lambda alias.
+    pub static ref LAMBDA_ALIAS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_LAMBDA_ALIAS)
+            .with_description("Alias moved to the newly published version.");
+
+    /// Configuration value for the Lambda publish flag.
+    pub static ref LAMBDA_PUBLISH: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_AWS_LAMBDA_PUBLISH)
+            .with_description("Publish a new version; implied by the alias.");
+
+    /// Configuration value for the functions updated in one run.
+    pub static ref LAMBDA_BATCH: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_LAMBDA_BATCH)
+            .with_validator(crate::validate_lambda_targets)
+            .with_description("Comma-separated function=code entries; code ending in .zip is a package relative to the working directory, anything else an image URI.");
+
+    /// Configuration value for the Lambda batch manifest.
+    pub static ref LAMBDA_MANIFEST: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_EMPTY), ENV_AWS_LAMBDA_MANIFEST)
+            .relative_to_working_dir()
+            .with_description("JSON file listing {\"function\", \"zip\" | \"image\"} objects to update, relative to the working directory.");
+
+    /// Configuration value for the batch failure policy.
+    pub static ref LAMBDA_FAIL_FAST: ConfigValue<bool> =
+        ConfigValue::new(true, ENV_AWS_LAMBDA_FAIL_FAST)
+            .with_description("Stop a Lambda batch at the first failed function instead of updating the remaining ones.");
+
+    /// Configuration value for the role assumed before the command.
+    pub static ref ROLE_ARN: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_ROLE_ARN)
+            .with_description("ARN of an IAM role assumed before running the command.");
+
+    /// Configuration value for the role session name.
+    pub static ref ROLE_SESSION_NAME: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_ROLE_SESSION_NAME.to_string(), ENV_AWS_ROLE_SESSION_NAME)
+            .with_description("Session name used when assuming the role.");
+
+    /// Configuration value for the role session duration.
+    pub static ref ROLE_DURATION: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_ROLE_DURATION)
+            .with_validator(Number::<u32>::new("a duration in seconds").or_empty())
+            .with_description("Session duration of the assumed role in seconds.");
+
+    /// Configuration value for the secrets fetched before the command.
+    pub static ref SECRETS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_SECRETS)
+            .with_validator(crate::validate_secrets)
+            .with_description("Comma-separated NAME=secret-id[#json-key] entries fetched from Secrets Manager into the environment.");
+
+    /// Configuration value for the Route53 hosted zone.
+    pub static ref ROUTE53_ZONE_ID: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_ROUTE53_ZONE_ID)
+            .with_description("ID of the Route53 hosted zone.");
+
+    /// Configuration value for the Route53 record name.
+    pub static ref ROUTE53_RECORD_NAME: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_ROUTE53_RECORD_NAME)
+            .with_description("Fully qualified name of the record to upsert.");
+
+    /// Configuration value for the Route53 record type.
+    pub static ref ROUTE53_RECORD_TYPE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_ROUTE53_RECORD_TYPE.to_string(), ENV_AWS_ROUTE53_RECORD_TYPE)
+            .with_description("Type of the record to upsert, e.g. CNAME or A.");
+
+    /// Configuration value for the Route53 record TTL.
+    pub static ref ROUTE53_RECORD_TTL: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_ROUTE53_TTL.to_string(), ENV_AWS_ROUTE53_RECORD_TTL)
+            .with_validator(Number::<u32>::new("a TTL in seconds"))
+            .with_description("TTL of the record in seconds.");
+
+    /// Configuration value for the Route53 record values.
+    pub static ref ROUTE53_RECORD_VALUES: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_ROUTE53_RECORD_VALUES)
+            .with_description("Comma-separated record values.");
+
+    /// Configuration value for the Route53 wait flag.
+    pub static ref ROUTE53_WAIT: ConfigValue<bool> =
+        ConfigValue::new(true, ENV_AWS_ROUTE53_WAIT)
+            .with_description("Wait until the record change is INSYNC.");
+
+    /// Configuration value for the EKS cluster.
+    pub static ref EKS_CLUSTER: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_EKS_CLUSTER)
+            .with_description("Name of the EKS cluster.");
+
+    /// Configuration value for the EKS cluster region.
+    pub static ref EKS_REGION: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_EKS_REGION)
+            .with_description("Region of the EKS cluster.");
+
+    /// Configuration value for the written kubeconfig.
+    pub static ref EKS_KUBECONFIG: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_KUBECONFIG), ENV_AWS_EKS_KUBECONFIG)
+            .expand()
+            .sensitive()
+            .with_description("File the EKS kubeconfig is written to.");
+
+    /// Configuration value for the ECR registry host.
+    pub static ref ECR_REGISTRY: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_ECR_REGISTRY)
+            .with_description("ECR registry host, e.g. 123456789012.dkr.ecr.eu-west-1.amazonaws.com.");
+
+    /// Configuration value for the image pushed to ECR.
+    pub static ref ECR_IMAGE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_ECR_IMAGE)
+            .with_description("Full image reference to push, including registry and tag.");
+
+    /// Configuration value for the Docker CLI executable path.
+    pub static ref DOCKER_BIN: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_DOCKER_BIN), ENV_AWS_DOCKER_BIN)
+            .expand()
+            .with_description("Path to the Docker CLI used by ECR commands.");
+
+    /// Configuration value for the ECS cluster.
+    pub static ref ECS_CLUSTER: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_ECS_CLUSTER)
+            .with_description("Name or ARN of the ECS cluster.");
+
+    /// Configuration value for the ECS service.
+    pub static ref ECS_SERVICE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_ECS_SERVICE)
+            .with_description("Name of the ECS service to update.");
+
+    /// Configuration value for the task definition file.
+    pub static ref ECS_TASK_DEFINITION: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_EMPTY), ENV_AWS_ECS_TASK_DEFINITION)
+            .relative_to_working_dir()
+            .with_description("Task definition JSON file, relative to the working directory.");
+
+    /// Configuration value for the wait-for-stability flag.
+    pub static ref ECS_WAIT: ConfigValue<bool> =
+        ConfigValue::new(true, ENV_AWS_ECS_WAIT)
+            .with_description("Wait until the ECS service is stable after the update.");
+
+    /// Configuration value for the tailed log group.
+    pub static ref LOGS_GROUP: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_LOGS_GROUP)
+            .with_description("CloudWatch Logs group printed by logs_tail, e.g. /aws/lambda/api.");
+
+    /// Configuration value for the start of the tailed events.
+    pub static ref LOGS_SINCE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_LOGS_SINCE.to_string(), ENV_AWS_LOGS_SINCE)
+            .with_description("Start of the printed events, e.g. 10m, 1h or an ISO 8601 timestamp.");
+
+    /// Configuration value for the log event filter.
+    pub static ref LOGS_FILTER: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_LOGS_FILTER)
+            .with_description("CloudWatch Logs filter pattern of the printed events.");
+
+    /// Configuration value for the CloudFront distributions.
+    pub static ref CLOUDFRONT_DISTRIBUTION: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_CLOUDFRONT_DISTRIBUTION)
+            .with_description("Comma-separated IDs of the CloudFront distributions to invalidate.");
+
+    /// Configuration value for the invalidated paths.
+    pub static ref CLOUDFRONT_PATHS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_CLOUDFRONT_PATHS.to_string(), ENV_AWS_CLOUDFRONT_PATHS)
+            .with_description("Comma-separated paths invalidated in every distribution.");
+
+    /// Configuration value for the parallel invalidation flag.
+    pub static ref CLOUDFRONT_PARALLEL: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_AWS_CLOUDFRONT_PARALLEL)
+            .with_description("Invalidate all distributions concurrently instead of one after another.");
+
+    /// Configuration value for the tags stamped on deployed resources.
+    pub static ref TAGS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_TAGS)
+            .with_validator(crate::validate_tags)
+            .with_description("Comma-separated key=value tags of deployed resources; ${VAR} references are rendered from the environment, e.g. commit=${GITHUB_SHA}.");
+
+    /// Configuration value for the tagged resources.
+    pub static ref TAG_RESOURCES: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_TAG_RESOURCES)
+            .with_description("Comma-separated ARNs of the resources tagged after a successful deploy.");
+}
+
+// Options shared by all commands.
+lazy_static! {
+    /// Configuration value for the number of retries of transient failures.
+    pub static ref RETRY_ATTEMPTS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_RETRY_ATTEMPTS.to_string(), ENV_AWS_RETRY_ATTEMPTS)
+            .with_validator(Number::<u32>::new("a number of retries"))
+            .with_description("Number of retries of throttled or otherwise transient AWS CLI failures; 0 disables retrying.");
+
+    /// Configuration value for the base delay between retries.
+    pub static ref RETRY_DELAY_MS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_RETRY_DELAY_MS.to_string(), ENV_AWS_RETRY_DELAY_MS)
+            .with_validator(Number::<u64>::new("a delay in milliseconds"))
+            .with_description("Delay before the first retry in milliseconds, doubled on every further retry.");
+
+    /// Configuration value for the dry-run flag of all commands.
+    pub static ref DRY_RUN: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_AWS_DRY_RUN)
+            .with_description("Preview the whole action: print the masked command lines without running them; S3 syncs run with --dryrun.");
+
+    /// Configuration value for the region of all commands.
+    pub static ref REGION: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_REGION)
+            .with_description("Region passed to every AWS CLI command.");
+
+    /// Configuration value for the named profile of all commands.
+    pub static ref PROFILE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_PROFILE)
+            .with_description("Named profile passed to every AWS CLI command.");
+
+    /// Configuration value for the endpoint of all commands.
+    pub static ref ENDPOINT_URL: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_ENDPOINT_URL)
+            .with_validator(|v: &String| {
+                if v.is_empty() || v.starts_with("http://") || v.starts_with("https://") {
+                    Ok(())
+                } else {
+                    Err(ConfigError::invalid(v, "an http(s) URL"))
+                }
+            })
+            .with_description("Endpoint URL passed to every AWS CLI command, e.g. for VPC endpoints.");
+
+    /// Configuration value for the backend of S3 syncs, invalidations and Lambda updates.
+    pub static ref BACKEND: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_BACKEND.to_string(), ENV_AWS_BACKEND)
+            .with_validator(|v: &String| {
+                v.parse::<crate::sdk::Backend>()
+                    .map(drop)
+                    .map_err(|_| ConfigError::invalid(v, "cli or sdk"))
+            })
+            .with_description("How S3 syncs, CloudFront invalidations and Lambda code updates reach AWS: cli runs the AWS CLI, sdk calls the APIs directly so the CLI is only needed by the other commands.");
+}
+
+// SAM and CDK deploys.
+lazy_static! {
+    /// Configuration value for the SAM CLI path.
+    pub static ref SAM_BIN: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_SAM_BIN), ENV_AWS_SAM_BIN)
+            .expand()
+            .with_description("Path to the SAM CLI used by sam_deploy.");
+
+    /// Configuration value for the SAM stack.
+    pub static ref SAM_STACK: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_SAM_STACK)
+            .with_description("Name of the CloudFormation stack deployed by sam_deploy.");
+
+    /// Configuration value for the SAM template.
+    pub static ref SAM_TEMPLATE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_SAM_TEMPLATE)
+            .with_description("Template deployed by sam_deploy; defaults to the template built by sam build.");
+
+    /// Configuration value for the SAM capabilities.
+    pub static ref SAM_CAPABILITIES: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_SAM_CAPABILITIES)
+            .with_description("Comma-separated capabilities acknowledged by sam_deploy, e.g. CAPABILITY_IAM.");
+
+    /// Configuration value for the CDK CLI path.
+    pub static ref CDK_BIN: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_CDK_BIN), ENV_AWS_CDK_BIN)
+            .expand()
+            .with_description("Path to the CDK CLI used by cdk_deploy.");
+
+    /// Configuration value for the CDK stacks.
+    pub static ref CDK_STACKS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_CDK_STACKS)
+            .with_description("Comma-separated stacks deployed by cdk_deploy; all stacks if empty.");
+
+    /// Configuration value for the CDK app.
+    pub static ref CDK_APP: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_CDK_APP)
+            .with_description("App command of cdk_deploy, overriding cdk.json.");
+
+    /// Configuration value for the stack parameters.
+    pub static ref PARAMETER_OVERRIDES: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_AWS_PARAMETER_OVERRIDES)
+            .with_validator(crate::validate_metadata)
+            .with_description("Comma-separated key=value stack parameters of sam_deploy and cdk_deploy.");
 }
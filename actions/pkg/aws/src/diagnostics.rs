@@ -0,0 +1,110 @@
+use executer::Annotation;
+
+/// Header the CLI prints before the parameter errors found by client-side validation.
+const PARAMETER_VALIDATION: &str = "Parameter validation failed:";
+
+/// Turns the errors the AWS CLI prints to standard error into annotations.
+///
+/// Recognizes parameter validation errors, usage errors of the CLI itself,
+/// unparsable parameters and the errors returned by the service. None of
+/// them name a file, so the annotations are shown on the workflow run.
+///
+/// # Example
+///
+/// ```rust
+/// use aws::diagnostics::ErrorMatcher;
+///
+/// let mut matcher = ErrorMatcher::default();
+/// matcher.line("Parameter validation failed:");
+/// matcher.line("Missing required parameter in input: \"Bucket\"");
+/// assert_eq!(
+///     matcher.finish()[0].render(),
+///     "::error title=Parameter validation failed::Missing required parameter in input: \"Bucket\""
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct ErrorMatcher {
+    in_validation: bool,
+    found: Vec<Annotation>,
+}
+
+impl ErrorMatcher {
+    /// Reads one line of standard error.
+    pub fn line(&mut self, line: &str) {
+        let line = line.trim();
+        if line == PARAMETER_VALIDATION {
+            self.in_validation = true;
+            return;
+        }
+        if self.in_validation && is_parameter_error(line) {
+            self.found
+                .push(Annotation::error(line).with_title("Parameter validation failed"));
+            return;
+        }
+        self.in_validation = false;
+
+        if let Some(message) = line.strip_prefix("aws: error: ") {
+            self.found
+                .push(Annotation::error(message).with_title("AWS CLI usage"));
+        } else if line.starts_with("Error parsing parameter ") {
+            let title = line.split_once(':').map_or(line, |(title, _)| title);
+            self.found.push(Annotation::error(line).with_title(title));
+        } else if let Some(code) = service_error(line) {
+            self.found.push(Annotation::error(line).with_title(code));
+        }
+    }
+
+    /// Returns the annotations of all errors read.
+    pub fn finish(self) -> Vec<Annotation> {
+        self.found
+    }
+}
+
+/// Returns whether a line is one of the errors listed after [`PARAMETER_VALIDATION`].
+fn is_parameter_error(line: &str) -> bool {
+    [
+        "Invalid ",
+        "Missing required parameter",
+        "Unknown parameter",
+    ]
+    .iter()
+    .any(|prefix| line.starts_with(prefix))
+}
+
+/// Returns the error code of `An error occurred (CODE) when calling the OP operation: ...`.
+fn service_error(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("An error occurred (")?;
+    let (code, rest) = rest.split_once(')')?;
+    rest.starts_with(" when calling").then_some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_and_service_errors() {
+        let mut matcher = ErrorMatcher::default();
+        for line in [
+            "usage: aws [options] <command> <subcommand> [<subcommand> ...] [parameters]",
+            "aws: error: the following arguments are required: --bucket",
+            "upload failed: ./index.html to s3://site/index.html",
+            "An error occurred (AccessDenied) when calling the PutObject operation: Access Denied",
+            "Invalid value for the --region option",
+        ] {
+            matcher.line(line);
+        }
+
+        let rendered: Vec<_> = matcher.finish().iter().map(Annotation::render).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "::error title=AWS CLI usage::the following arguments are required: --bucket"
+                    .to_string(),
+                "::error title=AccessDenied::An error occurred (AccessDenied) when calling the \
+                 PutObject operation: Access Denied"
+                    .to_string(),
+            ]
+        );
+    }
+}
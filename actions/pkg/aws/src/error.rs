@@ -11,6 +11,16 @@ pub enum AwsError {
     #[error("AWS S3 operation error: {0}")]
     S3Error(String),
 
+    /// Error returned by an AWS API called through the SDK backend.
+    #[error("AWS {operation} failed ({}): {message}", code.as_deref().unwrap_or("no error code"))]
+    SdkError {
+        /// The called operation, e.g. `PutObject`.
+        operation: String,
+        /// The error code of the service, e.g. `AccessDenied`.
+        code: Option<String>,
+        message: String,
+    },
+
     /// Error from the underlying executor.
     #[error(transparent)]
     ExecuterError(#[from] executer::ExecuterError),
@@ -1,9 +1,28 @@
-use crate::command::AwsCommand;
+use crate::command::{AwsCommand, Cli, GlobalOptions, SyncOverride};
+use crate::constants::{DEFAULT_CDK_BIN, DEFAULT_DOCKER_BIN, DEFAULT_SAM_BIN};
+use crate::diagnostics::ErrorMatcher;
 use crate::error::{AwsError, AwsResult};
+use crate::progress::{parse_line, SyncEvent, SyncSummary};
+use crate::response::{
+    FunctionVersion, Invalidation, InvalidationResult, InvalidationSummary, LambdaFunction,
+    LambdaResult, Page, PresignedUrl, SyncResult,
+};
+use crate::retry::is_transient;
+use crate::sdk::Sdk;
+use crate::step::{step_title, Step, StepPolicy, StepResult, StepStatus};
 
-use executer::{Context, Output, Subprocess, Target, Validator};
-use processor::ProcessorCollection;
+use executer::{
+    Annotations, Captured, Context, LineHook, Metrics, Output, RetryPolicy, Subprocess, Target,
+    Validator,
+};
+use futures::future::join_all;
+use processor::{MaskerRuntime, ProcessorCollection};
+use serde::de::DeserializeOwned;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 /// Options for synchronizing files between source and destination.
 #[derive(Debug, Clone)]
@@ -13,6 +32,13 @@ pub struct SyncOptions {
     pub delete: bool,
     pub dry_run: bool,
     pub force: bool,
+    pub cache_control: Option<String>,
+    pub content_type: Option<String>,
+    pub metadata_directive: Option<String>,
+    pub storage_class: Option<String>,
+    pub acl: Option<String>,
+    pub sse: Option<String>,
+    pub overrides: Vec<SyncOverride>,
 }
 
 impl SyncOptions {
@@ -23,6 +49,13 @@ impl SyncOptions {
             delete: false,
             dry_run: false,
             force: false,
+            cache_control: None,
+            content_type: None,
+            metadata_directive: None,
+            storage_class: None,
+            acl: None,
+            sse: None,
+            overrides: Vec::new(),
         }
     }
 
@@ -50,6 +83,42 @@ impl SyncOptions {
         self.force = force;
         self
     }
+
+    pub fn with_cache_control(mut self, cache_control: Option<String>) -> Self {
+        self.cache_control = cache_control;
+        self
+    }
+
+    pub fn with_content_type(mut self, content_type: Option<String>) -> Self {
+        self.content_type = content_type;
+        self
+    }
+
+    pub fn with_metadata_directive(mut self, directive: Option<String>) -> Self {
+        self.metadata_directive = directive;
+        self
+    }
+
+    pub fn with_storage_class(mut self, storage_class: Option<String>) -> Self {
+        self.storage_class = storage_class;
+        self
+    }
+
+    pub fn with_acl(mut self, acl: Option<String>) -> Self {
+        self.acl = acl;
+        self
+    }
+
+    pub fn with_sse(mut self, sse: Option<String>) -> Self {
+        self.sse = sse;
+        self
+    }
+
+    /// Adds per-pattern header overrides, see [`AwsCommand::with_overrides`].
+    pub fn with_overrides(mut self, overrides: Vec<SyncOverride>) -> Self {
+        self.overrides = overrides;
+        self
+    }
 }
 
 impl Default for SyncOptions {
@@ -58,12 +127,60 @@ impl Default for SyncOptions {
     }
 }
 
+/// Options for copying a single object.
+#[derive(Debug, Clone, Default)]
+pub struct CopyOptions {
+    pub content_type: Option<String>,
+    pub acl: Option<String>,
+    pub metadata: Option<BTreeMap<String, String>>,
+}
+
+impl CopyOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_content_type(mut self, content_type: Option<String>) -> Self {
+        self.content_type = content_type;
+        self
+    }
+
+    pub fn with_acl(mut self, acl: Option<String>) -> Self {
+        self.acl = acl;
+        self
+    }
+
+    pub fn with_metadata(mut self, metadata: Option<BTreeMap<String, String>>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+}
+
+/// Callback receiving the progress events of S3 syncs.
+pub type ProgressHandler = Arc<dyn Fn(&SyncEvent) + Send + Sync>;
+
 /// Executor responsible for running AWS commands.
 pub struct AwsExecutor {
     subprocess: Subprocess,
     aws_path: PathBuf,
+    docker_path: PathBuf,
+    sam_path: PathBuf,
+    cdk_path: PathBuf,
+    secrets: Option<MaskerRuntime>,
+    env: Arc<Mutex<HashMap<String, String>>>,
+    progress: Option<ProgressHandler>,
+    retry: RetryPolicy,
+    globals: GlobalOptions,
+    output: Output,
+    dry_run: bool,
+    metrics: Option<Metrics>,
+    annotations: Option<Annotations>,
+    sdk: Option<Sdk>,
 }
 
+/// Counter of commands retried after a transient failure.
+pub const RETRIES: &str = "retries";
+
 impl AwsExecutor {
     /// Creates a new instance of `AwsExecutor`.
     ///
@@ -94,14 +211,182 @@ impl AwsExecutor {
     pub fn new(processor: ProcessorCollection, aws_path: PathBuf) -> Self {
         let output = Output::new(processor, Target::Stdout, Target::Stderr);
         let validator = Validator::default();
-        let subprocess = Subprocess::new(output, validator);
+        let subprocess = Subprocess::new(output.clone(), validator);
 
         Self {
             subprocess,
             aws_path,
+            docker_path: PathBuf::from(DEFAULT_DOCKER_BIN),
+            sam_path: PathBuf::from(DEFAULT_SAM_BIN),
+            cdk_path: PathBuf::from(DEFAULT_CDK_BIN),
+            secrets: None,
+            env: Arc::new(Mutex::new(HashMap::new())),
+            progress: None,
+            retry: RetryPolicy::default(),
+            globals: GlobalOptions::default(),
+            output,
+            dry_run: false,
+            metrics: None,
+            annotations: None,
+            sdk: None,
+        }
+    }
+
+    /// Records command timings, retries and masked output lines on `metrics`.
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.subprocess = self.subprocess.with_metrics(metrics.clone());
+        self.output = self.output.with_metrics(metrics.clone());
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Collects the errors commands print to standard error on `annotations`.
+    ///
+    /// Errors of attempts that are retried are dropped.
+    pub fn with_annotations(mut self, annotations: Annotations) -> Self {
+        self.annotations = Some(annotations);
+        self
+    }
+
+    /// Adds the errors in the standard error of a captured command to the annotations.
+    fn annotate(&self, stderr: &str) {
+        if let Some(annotations) = &self.annotations {
+            let mut matcher = ErrorMatcher::default();
+            stderr.lines().for_each(|line| matcher.line(line));
+            annotations.extend(matcher.finish());
+        }
+    }
+
+    fn count_retry(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.increment(RETRIES);
         }
     }
 
+    /// Sets the path of the Docker CLI used by ECR commands.
+    pub fn with_docker_bin(mut self, docker_path: PathBuf) -> Self {
+        self.docker_path = docker_path;
+        self
+    }
+
+    /// Sets the path of the SAM CLI used by `SamDeploy`.
+    pub fn with_sam_bin(mut self, sam_path: PathBuf) -> Self {
+        self.sam_path = sam_path;
+        self
+    }
+
+    /// Sets the path of the CDK CLI used by `CdkDeploy`.
+    pub fn with_cdk_bin(mut self, cdk_path: PathBuf) -> Self {
+        self.cdk_path = cdk_path;
+        self
+    }
+
+    /// Sets the runtime masker that receives credentials obtained by commands.
+    ///
+    /// The same masker (or a clone of it) must be part of the `ProcessorCollection`
+    /// passed to [`AwsExecutor::new`], otherwise registered secrets are not masked.
+    /// Commands producing credentials, such as `EcrLogin`, refuse to run without it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::executor::AwsExecutor;
+    /// use processor::{MaskerRuntime, ProcessorCollection, ProcessorItem};
+    /// use std::path::PathBuf;
+    ///
+    /// let secrets = MaskerRuntime::new("****");
+    /// let processor = ProcessorCollection::new(vec![ProcessorItem::Runtime(secrets.clone())]);
+    /// let executor = AwsExecutor::new(processor, PathBuf::from("/usr/local/bin/aws"))
+    ///     .with_secrets(secrets);
+    /// ```
+    pub fn with_secrets(mut self, secrets: MaskerRuntime) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
+    /// Sets the policy for retrying transient CLI failures such as throttling.
+    ///
+    /// Defaults to [`RetryPolicy::default`]; use [`RetryPolicy::none`] to disable.
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Sets the global options, e.g. the region, appended to every AWS CLI command.
+    pub fn with_global_options(mut self, globals: GlobalOptions) -> Self {
+        self.globals = globals;
+        self
+    }
+
+    /// Previews every command instead of running it.
+    ///
+    /// S3 syncs run with `--dryrun`, and read-only commands such as
+    /// `AssumeRole` still run so that later commands resolve. All other
+    /// commands are printed, masked, and report success without running.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Calls the AWS APIs through `sdk` instead of running the CLI for
+    /// S3 syncs to a bucket, CloudFront invalidations and Lambda code updates.
+    ///
+    /// All other commands, e.g. Lambda aliases, still run the CLI. The clients
+    /// use the credentials of [`AwsExecutor::env`] once a command such as
+    /// `AssumeRole` set them. SDK calls are retried by the SDK itself.
+    pub fn with_sdk(mut self, sdk: Sdk) -> Self {
+        self.sdk = Some(sdk);
+        self
+    }
+
+    /// Returns the SDK clients, using the credentials of the environment.
+    fn sdk(&self) -> Option<Sdk> {
+        self.sdk.as_ref().map(|sdk| sdk.with_env(&self.env()))
+    }
+
+    /// Sets a handler for the progress of S3 syncs.
+    ///
+    /// With a handler set, transfer and progress lines of `aws s3 sync` are
+    /// passed to it as [`SyncEvent`]s instead of being written to the output;
+    /// any other line is still written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::executor::AwsExecutor;
+    /// use aws::progress::SyncSummary;
+    /// use processor::ProcessorCollection;
+    /// use std::path::PathBuf;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let summary = Arc::new(Mutex::new(SyncSummary::new()));
+    /// let sink = Arc::clone(&summary);
+    /// let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), PathBuf::from("aws"))
+    ///     .with_progress(move |event| sink.lock().unwrap().record(event));
+    /// ```
+    pub fn with_progress<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&SyncEvent) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(handler));
+        self
+    }
+
+    /// Returns the environment passed to every command.
+    ///
+    /// Starts empty; commands such as `GetSecretValue` add to it, so later
+    /// commands of a chain see their results.
+    pub fn env(&self) -> HashMap<String, String> {
+        self.env.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn set_env(&self, key: String, value: String) {
+        self.env
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, value);
+    }
+
     /// Executes a given AWS command asynchronously.
     ///
     /// # Arguments
@@ -145,24 +430,611 @@ impl AwsExecutor {
     /// }
     /// ```
     pub async fn execute(&self, command: AwsCommand) -> AwsResult<i32> {
-        let args = command.to_args();
-        let working_dir = match &command {
+        let command = if self.dry_run {
+            match command {
+                AwsCommand::S3Sync { .. } => command.into_dry_run(),
+                command if command.is_read_only() => command,
+                command => return Ok(self.print_dry_run(&command)),
+            }
+        } else {
+            command
+        };
+
+        if let Some(sdk) = self.sdk() {
+            match &command {
+                AwsCommand::CloudFrontInvalidate { .. } => {
+                    return sdk.invalidate(&command).await.map(|_| 0)
+                }
+                AwsCommand::LambdaUpdateZip { .. } | AwsCommand::LambdaUpdateImage { .. } => {
+                    return sdk.update_function_code(&command).await.map(|_| 0)
+                }
+                AwsCommand::LambdaWait { function_name } => {
+                    return sdk.wait_function_updated(function_name).await
+                }
+                _ => {}
+            }
+        }
+
+        match &command {
+            AwsCommand::S3Sync { .. } => {
+                return self.run_sync(&command, self.progress.clone()).await
+            }
+            AwsCommand::EcrLogin { .. } => return self.ecr_login(&command).await,
+            AwsCommand::EcsDeploy { .. } => return self.ecs_deploy(&command).await,
+            AwsCommand::GetSecretValue { .. } => return self.get_secret_value(&command).await,
+            AwsCommand::AssumeRole { .. } => return self.assume_role(&command).await,
+            AwsCommand::Route53Upsert { .. } => return self.route53_upsert(&command).await,
+            AwsCommand::EksUpdateKubeconfig { .. } => {
+                return self.eks_update_kubeconfig(&command).await
+            }
+            AwsCommand::LambdaUpdateAlias { version: None, .. } => {
+                return self.lambda_alias_latest(&command).await
+            }
+            _ => {}
+        }
+
+        self.run(self.context(&command), None).await
+    }
+
+    /// Prints the command line of a skipped command, masked like command output.
+    fn print_dry_run(&self, command: &AwsCommand) -> i32 {
+        self.output.write(&format!(
+            "[dry-run] {}",
+            self.command_line(command).join(" ")
+        ));
+        0
+    }
+
+    /// Builds the execution context of a plain CLI command.
+    fn context(&self, command: &AwsCommand) -> Context {
+        let working_dir = match command {
             AwsCommand::S3Sync { source, .. } => {
                 let default_path = PathBuf::from(".");
                 let parent = source.parent().unwrap_or(&default_path);
-                PathBuf::from(parent)
+                Some(PathBuf::from(parent))
             }
+            AwsCommand::SamDeploy { project_dir, .. }
+            | AwsCommand::CdkDeploy { project_dir, .. } => Some(project_dir.clone()),
+            AwsCommand::S3Cp { .. }
+            | AwsCommand::LambdaUpdateZip { .. }
+            | AwsCommand::LambdaUpdateImage { .. }
+            | AwsCommand::LambdaWait { .. }
+            | AwsCommand::LambdaUpdateAlias { .. }
+            | AwsCommand::GetSecretValue { .. }
+            | AwsCommand::AssumeRole { .. }
+            | AwsCommand::Route53Upsert { .. }
+            | AwsCommand::EksUpdateKubeconfig { .. }
+            | AwsCommand::EcrLogin { .. }
+            | AwsCommand::EcrPush { .. }
+            | AwsCommand::EcsDeploy { .. }
+            | AwsCommand::CloudFrontInvalidate { .. }
+            | AwsCommand::TagResource { .. }
+            | AwsCommand::ListInvalidations { .. }
+            | AwsCommand::ListFunctionVersions { .. }
+            | AwsCommand::Presign { .. }
+            | AwsCommand::LogsTail { .. } => None,
+        };
+
+        Context::new(self.command_line(command), self.env(), working_dir)
+    }
+
+    /// Runs a context, retrying transient failures according to the retry policy.
+    ///
+    /// A failure is transient if the command exits non-zero and its error
+    /// output matches [`is_transient`]. Sync output lines recognized by
+    /// [`parse_line`] are passed to `progress`, and only written if no
+    /// handler was set with [`AwsExecutor::with_progress`].
+    async fn run(&self, context: Context, progress: Option<ProgressHandler>) -> AwsResult<i32> {
+        let mut attempt = 0;
+        loop {
+            let transient = Arc::new(AtomicBool::new(false));
+            let flag = Arc::clone(&transient);
+            let matcher = Arc::new(Mutex::new(ErrorMatcher::default()));
+            let line_matcher = self.annotations.as_ref().map(|_| Arc::clone(&matcher));
+            let stderr: LineHook = Box::new(move |line| {
+                if is_transient(line) {
+                    flag.store(true, Ordering::Relaxed);
+                }
+                if let Some(matcher) = &line_matcher {
+                    matcher.lock().unwrap().line(line);
+                }
+                true
+            });
+            let echo = self.progress.is_none();
+            let stdout = progress.clone().map(|handler| -> LineHook {
+                Box::new(move |line| match parse_line(line) {
+                    Some(events) => {
+                        events.iter().for_each(|event| handler(event));
+                        echo
+                    }
+                    None => true,
+                })
+            });
+
+            let code = self
+                .subprocess
+                .execute_hooked(context.clone(), stdout, Some(stderr))
+                .await?;
+            if code == 0 || !transient.load(Ordering::Relaxed) || !self.retry.should_retry(attempt)
+            {
+                if let Some(annotations) = &self.annotations {
+                    annotations.extend(std::mem::take(&mut *matcher.lock().unwrap()).finish());
+                }
+                return Ok(code);
+            }
+            tokio::time::sleep(self.retry.delay(attempt)).await;
+            attempt += 1;
+            self.count_retry();
+        }
+    }
+
+    /// Runs an S3 sync through the SDK backend, if set, or the CLI.
+    ///
+    /// Lines of the SDK backend are handled like the output of the CLI.
+    async fn run_sync(
+        &self,
+        command: &AwsCommand,
+        progress: Option<ProgressHandler>,
+    ) -> AwsResult<i32> {
+        let Some(sdk) = self.sdk() else {
+            return self.run(self.context(command), progress).await;
         };
+        let echo = self.progress.is_none();
+        let report = |line: &str| match (&progress, parse_line(line)) {
+            (Some(handler), Some(events)) => {
+                events.iter().for_each(|event| handler(event));
+                if echo {
+                    self.output.write(line);
+                }
+            }
+            _ => self.output.write(line),
+        };
+        sdk.sync(command, &report).await
+    }
+
+    /// Like [`AwsExecutor::run`], capturing standard output.
+    async fn run_captured(&self, context: Context) -> AwsResult<Captured> {
+        let mut attempt = 0;
+        loop {
+            let captured = self.subprocess.execute_captured(context.clone()).await?;
+            if captured.code == 0
+                || !captured.stderr.lines().any(is_transient)
+                || !self.retry.should_retry(attempt)
+            {
+                self.annotate(&captured.stderr);
+                return Ok(captured);
+            }
+            tokio::time::sleep(self.retry.delay(attempt)).await;
+            attempt += 1;
+            self.count_retry();
+        }
+    }
+
+    /// Pushes a Docker image to ECR, logging in to the registry first.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry` - The ECR registry host.
+    /// * `image` - The full image reference to push.
+    /// * `region` - Optional region of the registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AwsError::CommandError` if no runtime masker was set with
+    /// [`AwsExecutor::with_secrets`].
+    pub async fn push_image(
+        &self,
+        registry: String,
+        image: String,
+        region: Option<String>,
+    ) -> AwsResult<i32> {
+        self.execute_chain(vec![
+            AwsCommand::EcrLogin { registry, region },
+            AwsCommand::EcrPush { image },
+        ])
+        .await
+    }
+
+    /// Generates a presigned URL for an S3 object.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - S3 URI of the object.
+    /// * `expires_in` - Lifetime of the URL in seconds.
+    /// * `mask_signature` - Whether to register the signature query parameters
+    ///   for masking, so the URL is not usable from the logs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AwsError::CommandError` if the CLI fails, or if `mask_signature`
+    /// is set without a runtime masker (see [`AwsExecutor::with_secrets`]).
+    pub async fn presign(
+        &self,
+        key: String,
+        expires_in: u32,
+        mask_signature: bool,
+    ) -> AwsResult<PresignedUrl> {
+        let secrets = match (mask_signature, &self.secrets) {
+            (false, _) => None,
+            (true, Some(secrets)) => Some(secrets),
+            (true, None) => return Err(AwsError::CommandError(
+                "Masking presigned URLs requires a runtime masker, see AwsExecutor::with_secrets"
+                    .to_string(),
+            )),
+        };
+
+        let command = AwsCommand::Presign { key, expires_in };
+        let captured = self.run_captured(self.context(&command)).await?;
+        if captured.code != 0 {
+            return Err(AwsError::CommandError(format!(
+                "presign failed with status {}",
+                captured.code
+            )));
+        }
 
+        let presigned = PresignedUrl {
+            url: captured.stdout.trim().to_string(),
+            expires_in,
+        };
+        if let Some(secrets) = secrets {
+            for value in presigned.signature_values() {
+                secrets.add(value);
+            }
+        }
+        Ok(presigned)
+    }
+
+    /// Runs a list command, following the pagination tokens until the last page.
+    ///
+    /// Returns the items of all pages in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AwsError::CommandError` if the command is not a list command,
+    /// a page fails or cannot be parsed, or a page repeats its token.
+    pub async fn list<T: DeserializeOwned>(&self, command: AwsCommand) -> AwsResult<Vec<T>> {
+        let Some(pointer) = command.page_items() else {
+            return Err(AwsError::CommandError(format!(
+                "{} is not a list command",
+                command.operation()
+            )));
+        };
+
+        let mut items = Vec::new();
+        let mut page_command = command.clone();
+        loop {
+            let captured = self.run_captured(self.context(&page_command)).await?;
+            if captured.code != 0 {
+                return Err(AwsError::CommandError(format!(
+                    "{} failed with status {}",
+                    command.operation(),
+                    captured.code
+                )));
+            }
+            let page: Page<T> = Page::parse(&captured.stdout, pointer).map_err(|e| {
+                AwsError::CommandError(format!("unexpected {} output: {}", command.operation(), e))
+            })?;
+            items.extend(page.items);
+
+            let Some(token) = page.next_token else {
+                return Ok(items);
+            };
+            if page_command
+                .to_args()
+                .contains(&format!("--starting-token={}", token))
+            {
+                return Err(AwsError::CommandError(format!(
+                    "{} returned the same page token twice",
+                    command.operation()
+                )));
+            }
+            page_command = command.clone().with_starting_token(token);
+        }
+    }
+
+    /// Lists all invalidations of a CloudFront distribution, newest first.
+    pub async fn list_invalidations(
+        &self,
+        distribution_id: String,
+    ) -> AwsResult<Vec<InvalidationSummary>> {
+        self.list(AwsCommand::ListInvalidations {
+            distribution_id,
+            starting_token: None,
+        })
+        .await
+    }
+
+    /// Lists all versions of a Lambda function, including `$LATEST`.
+    pub async fn list_function_versions(
+        &self,
+        function_name: String,
+    ) -> AwsResult<Vec<FunctionVersion>> {
+        self.list(AwsCommand::ListFunctionVersions {
+            function_name,
+            starting_token: None,
+        })
+        .await
+    }
+
+    /// Deploys a task definition to an ECS service.
+    ///
+    /// # Arguments
+    ///
+    /// * `cluster` - Name or ARN of the ECS cluster.
+    /// * `service` - Name of the service to update.
+    /// * `task_definition` - Path to the task definition JSON file.
+    /// * `wait` - Whether to wait for the service to become stable.
+    pub async fn deploy_service(
+        &self,
+        cluster: String,
+        service: String,
+        task_definition: PathBuf,
+        wait: bool,
+    ) -> AwsResult<i32> {
+        self.execute(AwsCommand::EcsDeploy {
+            cluster,
+            service,
+            task_definition,
+            wait,
+        })
+        .await
+    }
+
+    /// Builds the full command line, choosing the CLI of the command.
+    ///
+    /// Global options only apply to the AWS CLI.
+    fn command_line(&self, command: &AwsCommand) -> Vec<String> {
+        let cli = command.cli();
+        let bin = match cli {
+            Cli::Aws => &self.aws_path,
+            Cli::Docker => &self.docker_path,
+            Cli::Sam => &self.sam_path,
+            Cli::Cdk => &self.cdk_path,
+        };
+        let mut cmd = vec![bin.to_string_lossy().to_string()];
+        cmd.extend(command.to_args());
+        if cli == Cli::Aws {
+            self.globals.apply(&mut cmd);
+        }
+        cmd
+    }
+
+    /// Builds an AWS CLI command line from raw arguments.
+    fn aws_line(&self, args: Vec<String>) -> Vec<String> {
         let mut cmd = vec![self.aws_path.to_string_lossy().to_string()];
         cmd.extend(args);
+        self.globals.apply(&mut cmd);
+        cmd
+    }
 
-        let context = Context::new(cmd, std::collections::HashMap::new(), Some(working_dir));
+    /// Fetches the ECR password, registers it for masking and passes it to `docker login`.
+    async fn ecr_login(&self, command: &AwsCommand) -> AwsResult<i32> {
+        let secrets = self.secrets.as_ref().ok_or_else(|| {
+            AwsError::CommandError(
+                "ECR login requires a runtime masker, see AwsExecutor::with_secrets".to_string(),
+            )
+        })?;
 
-        self.subprocess
-            .execute(context)
-            .await
-            .map_err(AwsError::from)
+        let context = Context::new(self.command_line(command), self.env(), None);
+        let captured = self.run_captured(context).await?;
+        if captured.code != 0 {
+            return Ok(captured.code);
+        }
+        let password = captured.stdout.trim();
+        if password.is_empty() {
+            return Err(AwsError::CommandError(
+                "ECR returned an empty login password".to_string(),
+            ));
+        }
+        secrets.add(password);
+
+        let mut cmd = vec![self.docker_path.to_string_lossy().to_string()];
+        cmd.extend(command.docker_login_args().unwrap_or_default());
+        let context = Context::new(cmd, self.env(), None).with_stdin(password);
+
+        self.run(context, None).await
+    }
+
+    /// Writes the kubeconfig, masks its path and tokens and exports `KUBECONFIG`.
+    async fn eks_update_kubeconfig(&self, command: &AwsCommand) -> AwsResult<i32> {
+        let AwsCommand::EksUpdateKubeconfig {
+            kubeconfig_path, ..
+        } = command
+        else {
+            return Err(AwsError::CommandError(
+                "expected an EksUpdateKubeconfig command".to_string(),
+            ));
+        };
+        let secrets = self.secrets.as_ref().ok_or_else(|| {
+            AwsError::CommandError(
+                "EKS kubeconfig requires a runtime masker, see AwsExecutor::with_secrets"
+                    .to_string(),
+            )
+        })?;
+
+        let path = kubeconfig_path.to_string_lossy().to_string();
+        secrets.add(&path);
+
+        let context = Context::new(self.command_line(command), self.env(), None);
+        let code = self.run(context, None).await?;
+        if code != 0 {
+            return Ok(code);
+        }
+
+        let kubeconfig = std::fs::read_to_string(kubeconfig_path)
+            .map_err(|e| AwsError::CommandError(format!("failed to read kubeconfig: {}", e)))?;
+        for token in kubeconfig_tokens(&kubeconfig) {
+            secrets.add(token);
+        }
+
+        self.set_env("KUBECONFIG".to_string(), path);
+        Ok(0)
+    }
+
+    /// Submits the record change and optionally waits until it is `INSYNC`.
+    async fn route53_upsert(&self, command: &AwsCommand) -> AwsResult<i32> {
+        let context = Context::new(self.command_line(command), self.env(), None);
+        let captured = self.run_captured(context).await?;
+        if captured.code != 0 {
+            return Ok(captured.code);
+        }
+
+        let change_id = captured.stdout.trim();
+        let Some(args) = command.route53_wait_args(change_id) else {
+            return Ok(0);
+        };
+        if change_id.is_empty() {
+            return Err(AwsError::CommandError(
+                "Route53 returned no change ID".to_string(),
+            ));
+        }
+
+        let cmd = self.aws_line(args);
+        self.run(Context::new(cmd, self.env(), None), None).await
+    }
+
+    /// Assumes a role and switches the environment to its temporary credentials.
+    async fn assume_role(&self, command: &AwsCommand) -> AwsResult<i32> {
+        let secrets = self.secrets.as_ref().ok_or_else(|| {
+            AwsError::CommandError(
+                "Assume role requires a runtime masker, see AwsExecutor::with_secrets".to_string(),
+            )
+        })?;
+
+        let context = Context::new(self.command_line(command), self.env(), None);
+        let captured = self.run_captured(context).await?;
+        if captured.code != 0 {
+            return Ok(captured.code);
+        }
+
+        let credentials: Vec<&str> = captured.stdout.split_whitespace().collect();
+        let [access_key, secret_key, session_token] = credentials[..] else {
+            return Err(AwsError::CommandError(
+                "STS returned malformed credentials".to_string(),
+            ));
+        };
+        for value in [access_key, secret_key, session_token] {
+            secrets.add(value);
+        }
+
+        self.set_env("AWS_ACCESS_KEY_ID".to_string(), access_key.to_string());
+        self.set_env("AWS_SECRET_ACCESS_KEY".to_string(), secret_key.to_string());
+        self.set_env("AWS_SESSION_TOKEN".to_string(), session_token.to_string());
+        Ok(0)
+    }
+
+    /// Fetches a secret, registers it for masking and exports it to the environment.
+    async fn get_secret_value(&self, command: &AwsCommand) -> AwsResult<i32> {
+        let AwsCommand::GetSecretValue {
+            secret_id,
+            json_key,
+            env_name,
+        } = command
+        else {
+            return Err(AwsError::CommandError(
+                "expected a GetSecretValue command".to_string(),
+            ));
+        };
+        let secrets = self.secrets.as_ref().ok_or_else(|| {
+            AwsError::CommandError(
+                "Secret retrieval requires a runtime masker, see AwsExecutor::with_secrets"
+                    .to_string(),
+            )
+        })?;
+
+        let context = Context::new(self.command_line(command), self.env(), None);
+        let captured = self.run_captured(context).await?;
+        if captured.code != 0 {
+            return Ok(captured.code);
+        }
+        let secret = captured.stdout.trim_end_matches(['\r', '\n']);
+        secrets.add(secret);
+
+        let value = match json_key {
+            None => secret.to_string(),
+            Some(key) => {
+                let parsed: serde_json::Value = serde_json::from_str(secret).map_err(|_| {
+                    AwsError::CommandError(format!("secret {} is not a JSON object", secret_id))
+                })?;
+                let value = match parsed.get(key) {
+                    Some(serde_json::Value::String(v)) => v.clone(),
+                    Some(v) => v.to_string(),
+                    None => {
+                        return Err(AwsError::CommandError(format!(
+                            "secret {} has no key '{}'",
+                            secret_id, key
+                        )))
+                    }
+                };
+                secrets.add(&value);
+                value
+            }
+        };
+
+        self.set_env(env_name.clone(), value);
+        Ok(0)
+    }
+
+    /// Resolves the latest published version and points the alias at it.
+    async fn lambda_alias_latest(&self, command: &AwsCommand) -> AwsResult<i32> {
+        let AwsCommand::LambdaUpdateAlias {
+            function_name,
+            alias,
+            ..
+        } = command
+        else {
+            return Err(AwsError::CommandError(
+                "expected a LambdaUpdateAlias command".to_string(),
+            ));
+        };
+
+        let versions = self.list_function_versions(function_name.clone()).await?;
+        let Some(version) = versions.iter().filter_map(FunctionVersion::number).max() else {
+            return Err(AwsError::CommandError(format!(
+                "function {} has no published version",
+                function_name
+            )));
+        };
+
+        let resolved = AwsCommand::LambdaUpdateAlias {
+            function_name: function_name.clone(),
+            alias: alias.clone(),
+            version: Some(version.to_string()),
+        };
+        self.run(
+            Context::new(self.command_line(&resolved), self.env(), None),
+            None,
+        )
+        .await
+    }
+
+    /// Registers the task definition, points the service at the new revision
+    /// and optionally waits for it to become stable.
+    async fn ecs_deploy(&self, command: &AwsCommand) -> AwsResult<i32> {
+        let context = Context::new(self.command_line(command), self.env(), None);
+        let captured = self.run_captured(context).await?;
+        if captured.code != 0 {
+            return Ok(captured.code);
+        }
+        let arn = captured.stdout.trim();
+        if arn.is_empty() {
+            return Err(AwsError::CommandError(
+                "ECS returned no task definition ARN".to_string(),
+            ));
+        }
+
+        let mut steps = vec![command.ecs_update_service_args(arn).unwrap_or_default()];
+        steps.extend(command.ecs_wait_args());
+
+        for args in steps {
+            let cmd = self.aws_line(args);
+
+            let code = self.run(Context::new(cmd, self.env(), None), None).await?;
+            if code != 0 {
+                return Ok(code);
+            }
+        }
+        Ok(0)
     }
 
     /// Synchronizes files between a local directory and an S3 bucket or between two S3 buckets.
@@ -210,8 +1082,8 @@ impl AwsExecutor {
         source: PathBuf,
         destination: PathBuf,
         options: SyncOptions,
-    ) -> AwsResult<i32> {
-        self.execute(AwsCommand::S3Sync {
+    ) -> AwsResult<SyncResult> {
+        let command = AwsCommand::S3Sync {
             source,
             destination,
             exclude: options.exclude,
@@ -219,24 +1091,1041 @@ impl AwsExecutor {
             delete: options.delete,
             dry_run: options.dry_run,
             force: options.force,
+            cache_control: options.cache_control,
+            content_type: options.content_type,
+            metadata_directive: options.metadata_directive,
+            storage_class: options.storage_class,
+            acl: options.acl,
+            sse: options.sse,
+        };
+        self.execute_sync(command.with_overrides(&options.overrides))
+            .await
+    }
+
+    /// Runs S3 sync commands in order, summarizing their transfers.
+    ///
+    /// Stops at the first failed command. Progress events are still passed to
+    /// the handler set with [`AwsExecutor::with_progress`].
+    pub async fn execute_sync(&self, commands: Vec<AwsCommand>) -> AwsResult<SyncResult> {
+        let summary = Arc::new(Mutex::new(SyncSummary::new()));
+        let sink = Arc::clone(&summary);
+        let forward = self.progress.clone();
+        let handler: ProgressHandler = Arc::new(move |event| {
+            sink.lock().unwrap_or_else(|e| e.into_inner()).record(event);
+            if let Some(forward) = &forward {
+                forward(event);
+            }
+        });
+
+        let mut code = 0;
+        for command in commands {
+            let command = if self.dry_run {
+                command.into_dry_run()
+            } else {
+                command
+            };
+            code = self.run_sync(&command, Some(Arc::clone(&handler))).await?;
+            if code != 0 {
+                break;
+            }
+        }
+
+        let summary = summary.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        Ok(SyncResult { code, summary })
+    }
+
+    /// Runs a Lambda update chain, returning the updated function.
+    ///
+    /// The output of `update-function-code` is parsed into a
+    /// [`LambdaFunction`]; the other commands run as with
+    /// [`AwsExecutor::execute`]. Stops at the first failed command.
+    pub async fn execute_lambda(&self, commands: Vec<AwsCommand>) -> AwsResult<LambdaResult> {
+        if self.dry_run {
+            let code = self.execute_chain(commands).await?;
+            return Ok(LambdaResult {
+                code,
+                function: None,
+            });
+        }
+
+        let mut function = None;
+        for command in commands {
+            let code = match &command {
+                AwsCommand::LambdaUpdateZip { .. } | AwsCommand::LambdaUpdateImage { .. } => {
+                    if let Some(sdk) = self.sdk() {
+                        function = Some(sdk.update_function_code(&command).await?);
+                        continue;
+                    }
+                    let mut cmd = self.command_line(&command);
+                    cmd.push("--output=json".to_string());
+                    let captured = self
+                        .run_captured(Context::new(cmd, self.env(), None))
+                        .await?;
+                    if captured.code == 0 {
+                        let updated = LambdaFunction::parse(&captured.stdout).map_err(|e| {
+                            AwsError::CommandError(format!(
+                                "unexpected update-function-code output: {}",
+                                e
+                            ))
+                        })?;
+                        function = Some(updated);
+                    }
+                    captured.code
+                }
+                _ => self.execute(command).await?,
+            };
+            if code != 0 {
+                return Ok(LambdaResult { code, function });
+            }
+        }
+        Ok(LambdaResult { code: 0, function })
+    }
+
+    /// Runs a `CloudFrontInvalidate` command, returning the created invalidation.
+    ///
+    /// Other commands run as with [`AwsExecutor::execute`] and return no
+    /// invalidation, as do dry runs.
+    pub async fn execute_invalidation(&self, command: AwsCommand) -> AwsResult<InvalidationResult> {
+        if self.dry_run || !matches!(command, AwsCommand::CloudFrontInvalidate { .. }) {
+            let code = self.execute(command).await?;
+            return Ok(InvalidationResult {
+                code,
+                invalidation: None,
+            });
+        }
+
+        if let Some(sdk) = self.sdk() {
+            return Ok(InvalidationResult {
+                code: 0,
+                invalidation: Some(sdk.invalidate(&command).await?),
+            });
+        }
+
+        let mut cmd = self.command_line(&command);
+        cmd.push("--output=json".to_string());
+        let captured = self
+            .run_captured(Context::new(cmd, self.env(), None))
+            .await?;
+        let invalidation = if captured.code == 0 {
+            let invalidation = Invalidation::parse(&captured.stdout).map_err(|e| {
+                AwsError::CommandError(format!("unexpected create-invalidation output: {}", e))
+            })?;
+            Some(invalidation)
+        } else {
+            None
+        };
+        Ok(InvalidationResult {
+            code: captured.code,
+            invalidation,
+        })
+    }
+
+    /// Runs invalidations concurrently, see [`AwsExecutor::execute_parallel`].
+    ///
+    /// Returns one result per command, in command order.
+    pub async fn execute_invalidations(
+        &self,
+        commands: Vec<AwsCommand>,
+    ) -> AwsResult<Vec<InvalidationResult>> {
+        join_all(
+            commands
+                .into_iter()
+                .map(|cmd| self.execute_invalidation(cmd)),
+        )
+        .await
+        .into_iter()
+        .collect()
+    }
+
+    /// Copies a single file to or from S3.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Local file or S3 object.
+    /// * `destination` - Local file or S3 object.
+    /// * `options` - Object options such as content type, ACL and metadata.
+    pub async fn copy(
+        &self,
+        source: PathBuf,
+        destination: PathBuf,
+        options: CopyOptions,
+    ) -> AwsResult<i32> {
+        self.execute(AwsCommand::S3Cp {
+            source,
+            destination,
+            content_type: options.content_type,
+            acl: options.acl,
+            metadata: options.metadata,
         })
         .await
     }
 
+    /// Runs independent commands concurrently.
+    ///
+    /// All commands run to completion; the first error, or else the first
+    /// non-zero exit code in command order, is returned.
+    pub async fn execute_parallel(&self, commands: Vec<AwsCommand>) -> AwsResult<i32> {
+        let results = join_all(commands.into_iter().map(|cmd| self.execute(cmd))).await;
+        let mut status = 0;
+        for result in results {
+            let code = result?;
+            if status == 0 {
+                status = code;
+            }
+        }
+        Ok(status)
+    }
+
     pub async fn execute_chain(&self, commands: Vec<AwsCommand>) -> AwsResult<i32> {
-        let mut last_result = 0;
-        for cmd in &commands {
-            let result = self.execute(cmd.clone()).await;
-            match result {
-                Ok(code) => {
-                    last_result = code;
-                    if code != 0 {
-                        return Ok(code);
-                    }
+        let results = self
+            .execute_steps(commands.into_iter().map(Step::from).collect())
+            .await;
+        for result in results {
+            match result.status {
+                StepStatus::Error(e) => return Err(e),
+                StepStatus::Failed(code) => return Ok(code),
+                StepStatus::Succeeded | StepStatus::Skipped => {}
+            }
+        }
+        Ok(0)
+    }
+
+    /// Runs steps in order according to their failure policies.
+    ///
+    /// A failed `FailFast` step stops the chain: the remaining steps are
+    /// skipped, except `AlwaysRun` steps. Failed `ContinueOnError` steps are
+    /// recorded without stopping. Returns one result per step.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "chain", skip_all, fields(steps = steps.len()))
+    )]
+    pub async fn execute_steps(&self, steps: Vec<Step>) -> Vec<StepResult> {
+        let mut stopped = false;
+        let mut results = Vec::with_capacity(steps.len());
+        let total = steps.len();
+        for (index, step) in steps.into_iter().enumerate() {
+            let operation = step.command.operation();
+            let mut invalidation = None;
+            let started = Instant::now();
+            let status = if stopped && step.policy != StepPolicy::AlwaysRun {
+                StepStatus::Skipped
+            } else {
+                self.output.write(&format!(
+                    "[step {}/{}] {}: started",
+                    index + 1,
+                    total,
+                    step_title(step.name.as_deref(), &operation)
+                ));
+                let run = self.execute_invalidation(step.command);
+                #[cfg(feature = "tracing")]
+                let run = tracing::Instrument::instrument(
+                    run,
+                    tracing::info_span!("step", step = index + 1, total, command = %operation),
+                );
+                let code = run.await.map(|result| {
+                    invalidation = result.invalidation;
+                    result.code
+                });
+                match code {
+                    Ok(0) => StepStatus::Succeeded,
+                    Ok(code) => StepStatus::Failed(code),
+                    Err(e) => StepStatus::Error(e),
                 }
-                Err(e) => return Err(e),
+            };
+            if step.policy == StepPolicy::FailFast
+                && matches!(status, StepStatus::Failed(_) | StepStatus::Error(_))
+            {
+                stopped = true;
             }
+            let duration = match status {
+                StepStatus::Skipped => Duration::ZERO,
+                _ => started.elapsed(),
+            };
+            let result = StepResult {
+                index,
+                operation,
+                name: step.name,
+                policy: step.policy,
+                status,
+                duration,
+                invalidation,
+            };
+            self.output.write(&result.record(total));
+            results.push(result);
         }
-        Ok(last_result)
+        results
+    }
+}
+
+/// Returns the values of `token:` entries of a kubeconfig.
+fn kubeconfig_tokens(kubeconfig: &str) -> Vec<&str> {
+    kubeconfig
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("token:"))
+        .map(|token| token.trim().trim_matches('"'))
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use processor::{Processor, ProcessorItem};
+    use std::fs;
+    use std::time::Duration;
+    use tempfile::tempdir;
+    use testing::script;
+
+    #[tokio::test]
+    async fn test_ecr_login_masks_password() {
+        let dir = tempdir().unwrap();
+        let received = dir.path().join("received");
+        let aws = script(&dir.path().join("aws"), "echo ecr-password-123");
+        let docker = script(
+            &dir.path().join("docker"),
+            &format!("cat > {}", received.display()),
+        );
+
+        let secrets = MaskerRuntime::new("****");
+        let processor = ProcessorCollection::new(vec![ProcessorItem::Runtime(secrets.clone())]);
+        let executor = AwsExecutor::new(processor, aws)
+            .with_docker_bin(docker)
+            .with_secrets(secrets.clone());
+
+        let code = executor
+            .execute(AwsCommand::EcrLogin {
+                registry: "registry.example".to_string(),
+                region: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(code, 0);
+        assert_eq!(fs::read_to_string(&received).unwrap(), "ecr-password-123");
+        assert_eq!(secrets.process("pw=ecr-password-123"), "pw=****");
+    }
+
+    #[tokio::test]
+    async fn test_ecr_login_requires_secrets() {
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), PathBuf::from("aws"));
+        let result = executor
+            .execute(AwsCommand::EcrLogin {
+                registry: "registry.example".to_string(),
+                region: None,
+            })
+            .await;
+        assert!(matches!(result, Err(AwsError::CommandError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_ecr_login_failure_skips_docker() {
+        let dir = tempdir().unwrap();
+        let marker = dir.path().join("docker-called");
+        let aws = script(&dir.path().join("aws"), "exit 3");
+        let docker = script(
+            &dir.path().join("docker"),
+            &format!("touch {}", marker.display()),
+        );
+
+        let secrets = MaskerRuntime::new("****");
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws)
+            .with_docker_bin(docker)
+            .with_secrets(secrets);
+
+        let code = executor
+            .push_image(
+                "registry.example".to_string(),
+                "registry.example/app:1".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(code, 3);
+        assert!(!marker.exists());
+    }
+
+    #[tokio::test]
+    async fn test_lambda_alias_uses_latest_version() {
+        let dir = tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!(
+                "if [ \"$2\" = list-versions-by-function ]; then echo '{{\"Versions\": [{{\"Version\": \"$LATEST\", \"FunctionArn\": \"a\", \"CodeSha256\": \"s\"}}, {{\"Version\": \"12\", \"FunctionArn\": \"a:12\", \"CodeSha256\": \"s\"}}, {{\"Version\": \"9\", \"FunctionArn\": \"a:9\", \"CodeSha256\": \"s\"}}]}}'; else echo \"$@\" >> {}; fi",
+                calls.display()
+            ),
+        );
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws);
+        let code = executor
+            .execute(AwsCommand::LambdaUpdateAlias {
+                function_name: "api".to_string(),
+                alias: "live".to_string(),
+                version: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(code, 0);
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            "lambda update-alias --function-name=api --name=live --function-version=12\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_secret_is_exported_and_masked() {
+        let dir = tempdir().unwrap();
+        let seen = dir.path().join("seen");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!(
+                "if [ \"$1\" = secretsmanager ]; then echo '{{\"user\":\"app\",\"password\":\"s3cr3t-pw\"}}'; else echo \"$DB_PASSWORD\" > {}; fi",
+                seen.display()
+            ),
+        );
+
+        let secrets = MaskerRuntime::new("****");
+        let executor =
+            AwsExecutor::new(ProcessorCollection::new(vec![]), aws).with_secrets(secrets.clone());
+        let code = executor
+            .execute_chain(vec![
+                AwsCommand::GetSecretValue {
+                    secret_id: "prod/db".to_string(),
+                    json_key: Some("password".to_string()),
+                    env_name: "DB_PASSWORD".to_string(),
+                },
+                AwsCommand::LambdaWait {
+                    function_name: "api".to_string(),
+                },
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(code, 0);
+        assert_eq!(fs::read_to_string(&seen).unwrap(), "s3cr3t-pw\n");
+        assert_eq!(secrets.process("pw=s3cr3t-pw"), "pw=****");
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_switches_credentials() {
+        let dir = tempdir().unwrap();
+        let seen = dir.path().join("seen");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!(
+                "if [ \"$1\" = sts ]; then printf 'ASIAKEY\\tsecretkey\\ttoken123\\n'; else echo \"$AWS_ACCESS_KEY_ID $AWS_SESSION_TOKEN\" > {}; fi",
+                seen.display()
+            ),
+        );
+
+        let secrets = MaskerRuntime::new("****");
+        let executor =
+            AwsExecutor::new(ProcessorCollection::new(vec![]), aws).with_secrets(secrets.clone());
+        let code = executor
+            .execute_chain(vec![
+                AwsCommand::AssumeRole {
+                    role_arn: "arn:aws:iam::123:role/deploy".to_string(),
+                    session_name: "ci".to_string(),
+                    duration: None,
+                },
+                AwsCommand::LambdaWait {
+                    function_name: "api".to_string(),
+                },
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(code, 0);
+        assert_eq!(fs::read_to_string(&seen).unwrap(), "ASIAKEY token123\n");
+        assert_eq!(secrets.process("secretkey"), "****");
+    }
+
+    #[tokio::test]
+    async fn test_assume_role_malformed_output() {
+        let dir = tempdir().unwrap();
+        let aws = script(&dir.path().join("aws"), "echo None");
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws)
+            .with_secrets(MaskerRuntime::new("****"));
+        let result = executor
+            .execute(AwsCommand::AssumeRole {
+                role_arn: "arn:aws:iam::123:role/deploy".to_string(),
+                session_name: "ci".to_string(),
+                duration: None,
+            })
+            .await;
+        assert!(matches!(result, Err(AwsError::CommandError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_route53_upsert_waits_for_change() {
+        let dir = tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!(
+                "if [ \"$2\" = change-resource-record-sets ]; then echo /change/C42; else echo \"$@\" >> {}; fi",
+                calls.display()
+            ),
+        );
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws);
+        let code = executor
+            .execute(AwsCommand::Route53Upsert {
+                zone_id: "Z123".to_string(),
+                record: crate::command::Route53Record {
+                    name: "api.example.com".to_string(),
+                    record_type: "CNAME".to_string(),
+                    ttl: 60,
+                    values: vec!["green.example.com".to_string()],
+                },
+                wait: true,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(code, 0);
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            "route53 wait resource-record-sets-changed --id=/change/C42\n"
+        );
+    }
+
+    #[test]
+    fn test_kubeconfig_tokens() {
+        let kubeconfig =
+            "users:\n- name: ci\n  user:\n    token: \"k8s-aws-v1.abc\"\n    exec: null\n";
+        assert_eq!(kubeconfig_tokens(kubeconfig), vec!["k8s-aws-v1.abc"]);
+    }
+
+    #[tokio::test]
+    async fn test_eks_update_kubeconfig_masks_and_exports() {
+        let dir = tempdir().unwrap();
+        let kubeconfig = dir.path().join("kubeconfig");
+        let seen = dir.path().join("seen");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!(
+                "if [ \"$1\" = eks ]; then printf 'users:\\n- user:\\n    token: tok-123\\n' > {}; else echo \"$KUBECONFIG\" > {}; fi",
+                kubeconfig.display(),
+                seen.display()
+            ),
+        );
+
+        let secrets = MaskerRuntime::new("****");
+        let executor =
+            AwsExecutor::new(ProcessorCollection::new(vec![]), aws).with_secrets(secrets.clone());
+        let code = executor
+            .execute_chain(vec![
+                AwsCommand::EksUpdateKubeconfig {
+                    cluster: "prod".to_string(),
+                    region: None,
+                    kubeconfig_path: kubeconfig.clone(),
+                },
+                AwsCommand::LambdaWait {
+                    function_name: "api".to_string(),
+                },
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(code, 0);
+        assert_eq!(
+            fs::read_to_string(&seen).unwrap().trim(),
+            kubeconfig.display().to_string()
+        );
+        assert_eq!(secrets.process("token tok-123"), "token ****");
+        assert_eq!(secrets.process(&kubeconfig.display().to_string()), "****");
+    }
+
+    #[tokio::test]
+    async fn test_sync_progress_events() {
+        let dir = tempdir().unwrap();
+        let aws = script(
+            &dir.path().join("aws"),
+            "echo 'upload: a.js to s3://site/a.js'\necho 'delete: s3://site/old.js'",
+        );
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws)
+            .with_progress(move |event| sink.lock().unwrap().push(event.clone()));
+        let result = executor
+            .sync(
+                dir.path().join("dist"),
+                PathBuf::from("s3://site"),
+                SyncOptions::new(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.code, 0);
+        assert_eq!(result.summary.transferred, 1);
+        assert_eq!(result.summary.deleted, 1);
+        assert_eq!(events.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_secret_missing_json_key() {
+        let dir = tempdir().unwrap();
+        let aws = script(&dir.path().join("aws"), "echo '{\"user\":\"app\"}'");
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws)
+            .with_secrets(MaskerRuntime::new("****"));
+        let result = executor
+            .execute(AwsCommand::GetSecretValue {
+                secret_id: "prod/db".to_string(),
+                json_key: Some("password".to_string()),
+                env_name: "DB_PASSWORD".to_string(),
+            })
+            .await;
+        assert!(matches!(result, Err(AwsError::CommandError(_))));
+        assert!(executor.env().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ecs_deploy_steps() {
+        let dir = tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!(
+                "echo \"$2\" >> {}\nif [ \"$2\" = register-task-definition ]; then echo arn:td:7; fi\nif [ \"$2\" = update-service ]; then echo \"$5\" >> {}; fi",
+                calls.display(),
+                calls.display()
+            ),
+        );
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws);
+        let code = executor
+            .deploy_service(
+                "prod".to_string(),
+                "api".to_string(),
+                PathBuf::from("task.json"),
+                true,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(code, 0);
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            "register-task-definition\nupdate-service\n--task-definition=arn:td:7\nwait\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ecs_deploy_stops_on_failure() {
+        let dir = tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!(
+                "echo \"$2\" >> {}\nif [ \"$2\" = register-task-definition ]; then echo arn:td:7; else exit 2; fi",
+                calls.display()
+            ),
+        );
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws);
+        let code = executor
+            .deploy_service(
+                "prod".to_string(),
+                "api".to_string(),
+                PathBuf::from("task.json"),
+                true,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(code, 2);
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            "register-task-definition\nupdate-service\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transient_failure_is_retried() {
+        let dir = tempdir().unwrap();
+        let marker = dir.path().join("attempted");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!(
+                "if [ -e {m} ]; then exit 0; fi\ntouch {m}\necho 'An error occurred (SlowDown)' >&2\nexit 1",
+                m = marker.display()
+            ),
+        );
+
+        let metrics = Metrics::new();
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws)
+            .with_retry(RetryPolicy::new(2, Duration::ZERO))
+            .with_metrics(metrics.clone());
+        let result = executor
+            .sync(
+                dir.path().join("dist"),
+                PathBuf::from("s3://site"),
+                SyncOptions::new(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.code, 0);
+        assert!(marker.exists());
+        assert_eq!(metrics.counter(RETRIES), 1);
+        assert_eq!(metrics.timing("aws s3").map(|t| t.count), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_permanent_failure_is_not_retried() {
+        let dir = tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!(
+                "echo call >> {}\n\
+                 echo 'An error occurred (AccessDenied) when calling the GetFunction operation' >&2\n\
+                 exit 1",
+                calls.display()
+            ),
+        );
+
+        let annotations = Annotations::new();
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws)
+            .with_retry(RetryPolicy::new(2, Duration::ZERO))
+            .with_annotations(annotations.clone());
+        let code = executor
+            .execute(AwsCommand::LambdaWait {
+                function_name: "api".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(code, 1);
+        assert_eq!(fs::read_to_string(&calls).unwrap(), "call\n");
+        let annotations = annotations.take();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].title.as_deref(), Some("AccessDenied"));
+    }
+
+    #[tokio::test]
+    async fn test_global_options_are_appended() {
+        let dir = tempdir().unwrap();
+        let received = dir.path().join("received");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!("echo \"$@\" > {}", received.display()),
+        );
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws).with_global_options(
+            GlobalOptions::new()
+                .with_region("eu-west-1")
+                .with_profile("prod"),
+        );
+        executor
+            .execute(AwsCommand::LambdaWait {
+                function_name: "api".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let args = fs::read_to_string(&received).unwrap();
+        assert!(args
+            .trim_end()
+            .ends_with("--region=eu-west-1 --profile=prod"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_lambda_returns_function() {
+        let dir = tempdir().unwrap();
+        let aws = script(
+            &dir.path().join("aws"),
+            r#"if [ "$2" = update-function-code ]; then echo '{"FunctionName":"api","FunctionArn":"arn:fn:api","Version":"4","CodeSha256":"sha="}'; fi"#,
+        );
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws);
+        let result = executor
+            .execute_lambda(vec![
+                AwsCommand::LambdaUpdateImage {
+                    function_name: "api".to_string(),
+                    image_uri: "registry.example/api:1".to_string(),
+                    publish: true,
+                },
+                AwsCommand::LambdaWait {
+                    function_name: "api".to_string(),
+                },
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(result.code, 0);
+        let function = result.function.unwrap();
+        assert_eq!(function.version, "4");
+        assert_eq!(function.code_sha256, "sha=");
+    }
+
+    #[tokio::test]
+    async fn test_execute_parallel_runs_all() {
+        let dir = tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!(
+                "echo \"$3\" >> {}\n[ \"$3\" != --distribution-id=E2 ]",
+                calls.display()
+            ),
+        );
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws);
+        let code = executor
+            .execute_parallel(
+                ["E1", "E2", "E3"]
+                    .iter()
+                    .map(|id| AwsCommand::CloudFrontInvalidate {
+                        distribution_id: id.to_string(),
+                        paths: vec!["/*".to_string()],
+                        caller_reference: format!("ref-{}", id),
+                    })
+                    .collect(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(code, 1);
+        assert_eq!(fs::read_to_string(&calls).unwrap().lines().count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_skips_mutating_commands() {
+        let dir = tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!("echo \"$1 $2 $5\" >> {}", calls.display()),
+        );
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws).with_dry_run(true);
+        let code = executor
+            .execute_chain(vec![
+                AwsCommand::LambdaUpdateImage {
+                    function_name: "api".to_string(),
+                    image_uri: "registry.example/api:1".to_string(),
+                    publish: false,
+                },
+                AwsCommand::S3Sync {
+                    source: dir.path().join("dist"),
+                    destination: PathBuf::from("s3://site"),
+                    exclude: None,
+                    include: None,
+                    delete: false,
+                    dry_run: false,
+                    force: false,
+                    cache_control: None,
+                    content_type: None,
+                    metadata_directive: None,
+                    storage_class: None,
+                    acl: None,
+                    sse: None,
+                },
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(code, 0);
+        assert_eq!(fs::read_to_string(&calls).unwrap(), "s3 sync --dryrun\n");
+    }
+
+    #[tokio::test]
+    async fn test_presign_masks_signature() {
+        let dir = tempdir().unwrap();
+        let aws = script(
+            &dir.path().join("aws"),
+            "echo 'https://artifacts.s3.amazonaws.com/app.zip?X-Amz-Expires=600&X-Amz-Signature=deadbeef'",
+        );
+
+        let secrets = MaskerRuntime::new("****");
+        let executor = AwsExecutor::new(
+            ProcessorCollection::new(vec![ProcessorItem::Runtime(secrets.clone())]),
+            aws,
+        )
+        .with_secrets(secrets.clone());
+        let presigned = executor
+            .presign("s3://artifacts/app.zip".to_string(), 600, true)
+            .await
+            .unwrap();
+
+        assert!(presigned.url.ends_with("X-Amz-Signature=deadbeef"));
+        assert_eq!(presigned.expires_in, 600);
+        assert_eq!(
+            secrets.process(&presigned.url),
+            "https://artifacts.s3.amazonaws.com/app.zip?X-Amz-Expires=600&X-Amz-Signature=****"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_steps_policies() {
+        let dir = tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!(
+                "echo \"$4\" >> {}\ncase \"$4\" in --function-name=fail*) exit 4;; esac",
+                calls.display()
+            ),
+        );
+        let wait = |name: &str| AwsCommand::LambdaWait {
+            function_name: name.to_string(),
+        };
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws);
+        let results = executor
+            .execute_steps(vec![
+                Step::new(wait("fail-soft")).with_policy(StepPolicy::ContinueOnError),
+                Step::new(wait("fail-hard")),
+                Step::new(wait("skipped")),
+                Step::new(wait("cleanup")).with_policy(StepPolicy::AlwaysRun),
+            ])
+            .await;
+
+        assert!(matches!(results[0].status, StepStatus::Failed(4)));
+        assert!(matches!(results[1].status, StepStatus::Failed(4)));
+        assert!(matches!(results[2].status, StepStatus::Skipped));
+        assert!(matches!(results[3].status, StepStatus::Succeeded));
+        assert_eq!(results[1].operation, "lambda wait");
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            "--function-name=fail-soft\n--function-name=fail-hard\n--function-name=cleanup\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_steps_records_invalidation() {
+        let dir = tempdir().unwrap();
+        let aws = script(
+            &dir.path().join("aws"),
+            "echo '{\"Location\": \"l\", \"Invalidation\": {\"Id\": \"I1\", \"Status\": \"InProgress\"}}'",
+        );
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws);
+        let results = executor
+            .execute_steps(vec![Step::new(AwsCommand::CloudFrontInvalidate {
+                distribution_id: "E1".to_string(),
+                paths: vec!["/*".to_string()],
+                caller_reference: "ref".to_string(),
+            })])
+            .await;
+
+        assert!(matches!(results[0].status, StepStatus::Succeeded));
+        assert_eq!(results[0].invalidation.as_ref().unwrap().id, "I1");
+    }
+
+    #[tokio::test]
+    async fn test_sdk_backend_replaces_the_cli() {
+        use aws_config::{retry::RetryConfig, BehaviorVersion, Region, SdkConfig};
+        use aws_sdk_s3::config::{Credentials, SharedCredentialsProvider};
+
+        let dir = tempdir().unwrap();
+        let marker = dir.path().join("ran");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!("touch {}", marker.display()),
+        );
+        let conf = SdkConfig::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .endpoint_url("http://127.0.0.1:1")
+            .credentials_provider(SharedCredentialsProvider::new(Credentials::new(
+                "key", "secret", None, None, "test",
+            )))
+            .retry_config(RetryConfig::disabled())
+            .build();
+
+        let executor =
+            AwsExecutor::new(ProcessorCollection::new(vec![]), aws).with_sdk(Sdk::from_conf(&conf));
+        let result = executor
+            .execute(AwsCommand::CloudFrontInvalidate {
+                distribution_id: "E1".to_string(),
+                paths: vec!["/*".to_string()],
+                caller_reference: "ref".to_string(),
+            })
+            .await;
+
+        assert!(
+            matches!(&result, Err(AwsError::SdkError { operation, .. }) if operation == "CreateInvalidation"),
+            "{:?}",
+            result
+        );
+        assert!(!marker.exists());
+    }
+
+    #[tokio::test]
+    async fn test_list_follows_pagination_tokens() {
+        let dir = tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let aws = script(
+            &dir.path().join("aws"),
+            &format!(
+                "echo \"$6\" >> {}\ncase \"$6\" in\n--starting-token=p2) echo '{{\"Versions\": [{{\"Version\": \"2\", \"FunctionArn\": \"a:2\", \"CodeSha256\": \"s\"}}]}}';;\n*) echo '{{\"Versions\": [{{\"Version\": \"1\", \"FunctionArn\": \"a:1\", \"CodeSha256\": \"s\"}}], \"NextToken\": \"p2\"}}';;\nesac",
+                calls.display()
+            ),
+        );
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws);
+        let versions = executor
+            .list_function_versions("api".to_string())
+            .await
+            .unwrap();
+
+        let numbers: Vec<_> = versions
+            .iter()
+            .filter_map(FunctionVersion::number)
+            .collect();
+        assert_eq!(numbers, vec![1, 2]);
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            "\n--starting-token=p2\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_rejects_repeated_token() {
+        let dir = tempdir().unwrap();
+        let aws = script(
+            &dir.path().join("aws"),
+            "echo '{\"InvalidationList\": {\"Quantity\": 0}, \"NextToken\": \"same\"}'",
+        );
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), aws);
+        assert!(executor.list_invalidations("E1".to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sam_deploy_runs_in_project_dir() {
+        let dir = tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let sam = script(
+            &dir.path().join("sam"),
+            &format!("echo \"$(pwd) $1 $2\" >> {}", calls.display()),
+        );
+
+        let executor = AwsExecutor::new(ProcessorCollection::new(vec![]), PathBuf::from("aws"))
+            .with_sam_bin(sam)
+            .with_global_options(GlobalOptions::new().with_region("eu-west-1".to_string()));
+        let code = executor
+            .execute(AwsCommand::SamDeploy {
+                project_dir: dir.path().to_path_buf(),
+                stack_name: "api".to_string(),
+                template: None,
+                parameter_overrides: BTreeMap::new(),
+                capabilities: Vec::new(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(code, 0);
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            format!("{} deploy --stack-name=api\n", dir.path().display())
+        );
     }
 }
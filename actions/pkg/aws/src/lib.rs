@@ -1,83 +1,457 @@
+pub mod action;
 pub mod chain;
 pub mod command;
 pub mod constants;
+pub mod diagnostics;
 pub mod environments;
 pub mod error;
 pub mod executor;
+pub mod progress;
+pub mod response;
+pub mod retry;
+pub mod sdk;
+pub mod step;
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
+pub use action::{AwsAction, AwsActions};
 pub use chain::CommandChain;
-pub use command::AwsCommand;
-use config::ConfigResult;
+pub use command::{
+    AwsCommand, Cli, GlobalOptions, LambdaCode, LambdaTarget, Route53Record, SyncOverride,
+};
+use config::{config_struct, non_empty, ConfigError, ConfigResult, Optional};
 pub use constants::*;
 pub use environments::AwsEnv;
 pub use executor::AwsExecutor;
+pub use sdk::{Backend, Sdk};
 
-/// Represents the configuration for AWS operations.
-pub struct AwsConfig {}
+config_struct! {
+    /// Represents the configuration for AWS operations.
+    pub struct AwsConfig {
+        /// Gets the AWS commands to execute, in order.
+        get_cmd: AwsActions = CMD,
+        /// Gets the AWS CLI executable path.
+        get_bin: PathBuf = AWS_BIN,
+        /// Gets the synced directory.
+        get_source: PathBuf = S3_SOURCE,
+        /// Gets the S3 destination bucket/path.
+        get_destination: PathBuf = S3_DESTINATION,
+        /// Gets the exclude patterns as a vector of strings.
+        get_exclude: Option<Vec<String>> = S3_EXCLUDE => split_patterns,
+        /// Gets the include patterns as a vector of strings.
+        get_include: Option<Vec<String>> = S3_INCLUDE => split_patterns,
+        /// Gets the delete flag status.
+        get_delete: bool = S3_DELETE,
+        /// Gets the dry run flag status.
+        get_dry_run: bool = S3_DRY_RUN,
+        /// Gets the force flag status.
+        get_force: bool = S3_FORCE,
+        /// Gets the Cache-Control of synced files.
+        get_cache_control: Option<String> = S3_CACHE_CONTROL => non_empty,
+        /// Gets the Cache-Control of static site assets.
+        get_asset_cache_control: String = S3_ASSET_CACHE_CONTROL,
+        /// Gets the Cache-Control of static site pages.
+        get_html_cache_control: String = S3_HTML_CACHE_CONTROL,
+        /// Gets the metadata directive of the sync.
+        get_metadata_directive: Option<String> = S3_METADATA_DIRECTIVE => non_empty,
+        /// Gets the per-pattern Cache-Control overrides of the sync.
+        get_cache_overrides: Vec<SyncOverride> = S3_CACHE_OVERRIDES => split_overrides,
+        /// Gets the storage class of synced files.
+        get_storage_class: Option<String> = S3_STORAGE_CLASS => non_empty,
+        /// Gets the server-side encryption of synced files.
+        get_sse: Option<String> = S3_SSE => non_empty,
+        /// Gets the file copied by s3_cp.
+        get_copy_source: PathBuf = S3_CP_SOURCE,
+        /// Gets the presigned object.
+        get_presign_key: String = S3_PRESIGN_KEY,
+        /// Gets the lifetime of presigned URLs.
+        get_presign_expires: u32 = S3_PRESIGN_EXPIRES as u32,
+        /// Gets the presigned URL masking flag.
+        get_presign_mask: bool = S3_PRESIGN_MASK,
+        /// Gets the content type of uploaded objects.
+        get_content_type: Option<String> = S3_CONTENT_TYPE => non_empty,
+        /// Gets the canned ACL of uploaded or synced files.
+        get_acl: Option<String> = S3_ACL => non_empty,
+        /// Gets the metadata of uploaded objects.
+        get_metadata: Option<BTreeMap<String, String>> = S3_METADATA => split_metadata,
+        /// Gets the Lambda function.
+        get_lambda_function: String = LAMBDA_FUNCTION,
+        /// Gets the Lambda zip package.
+        get_lambda_zip: PathBuf = LAMBDA_ZIP,
+        /// Gets the Lambda container image.
+        get_lambda_image: String = LAMBDA_IMAGE,
+        /// Gets the Lambda alias.
+        get_lambda_alias: Option<String> = LAMBDA_ALIAS => non_empty,
+        /// Gets the Lambda publish flag.
+        get_lambda_publish: bool = LAMBDA_PUBLISH,
+        /// Gets the functions updated in one run.
+        get_lambda_batch: Vec<LambdaTarget> = LAMBDA_BATCH => split_lambda_targets,
+        /// Gets the Lambda batch manifest.
+        get_lambda_manifest: PathBuf = LAMBDA_MANIFEST,
+        /// Gets the batch failure policy.
+        get_lambda_fail_fast: bool = LAMBDA_FAIL_FAST,
+        /// Gets the role assumed before the command.
+        get_role_arn: Option<String> = ROLE_ARN => non_empty,
+        /// Gets the role session name.
+        get_role_session_name: String = ROLE_SESSION_NAME,
+        /// Gets the role session duration in seconds.
+        get_role_duration: Option<u32> = ROLE_DURATION as Optional<u32> => Optional::into_inner,
+        /// Gets the secrets fetched before the command.
+        get_secrets: Vec<AwsCommand> = SECRETS => split_secrets,
+        /// Gets the Route53 hosted zone ID.
+        get_route53_zone_id: String = ROUTE53_ZONE_ID,
+        /// Gets the Route53 record name.
+        get_route53_record_name: String = ROUTE53_RECORD_NAME,
+        /// Gets the Route53 record type.
+        get_route53_record_type: String = ROUTE53_RECORD_TYPE,
+        /// Gets the Route53 record TTL.
+        get_route53_record_ttl: u32 = ROUTE53_RECORD_TTL as u32,
+        /// Gets the Route53 record values.
+        get_route53_record_values: Option<Vec<String>> = ROUTE53_RECORD_VALUES => split_patterns,
+        /// Gets the Route53 wait flag.
+        get_route53_wait: bool = ROUTE53_WAIT,
+        /// Gets the EKS cluster.
+        get_eks_cluster: String = EKS_CLUSTER,
+        /// Gets the EKS cluster region.
+        get_eks_region: Option<String> = EKS_REGION => non_empty,
+        /// Gets the written kubeconfig path.
+        get_eks_kubeconfig: PathBuf = EKS_KUBECONFIG,
+        /// Gets the ECR registry host.
+        get_ecr_registry: String = ECR_REGISTRY,
+        /// Gets the image pushed to ECR.
+        get_ecr_image: String = ECR_IMAGE,
+        /// Gets the Docker CLI executable path.
+        get_docker_bin: PathBuf = DOCKER_BIN,
+        /// Gets the ECS cluster.
+        get_ecs_cluster: String = ECS_CLUSTER,
+        /// Gets the ECS service.
+        get_ecs_service: String = ECS_SERVICE,
+        /// Gets the task definition file.
+        get_ecs_task_definition: PathBuf = ECS_TASK_DEFINITION,
+        /// Gets the wait-for-stability flag.
+        get_ecs_wait: bool = ECS_WAIT,
+        /// Gets the tailed log group.
+        get_logs_group: String = LOGS_GROUP,
+        /// Gets the start of the tailed events.
+        get_logs_since: Option<String> = LOGS_SINCE => non_empty,
+        /// Gets the log event filter.
+        get_logs_filter: Option<String> = LOGS_FILTER => non_empty,
+        /// Gets the CloudFront distributions.
+        get_cloudfront_distributions: Option<Vec<String>> = CLOUDFRONT_DISTRIBUTION => split_patterns,
+        /// Gets the invalidated paths.
+        get_cloudfront_paths: Option<Vec<String>> = CLOUDFRONT_PATHS => split_patterns,
+        /// Gets the parallel invalidation flag.
+        get_cloudfront_parallel: bool = CLOUDFRONT_PARALLEL,
+        /// Gets the rendered tags of deployed resources.
+        get_tags: BTreeMap<String, String> = TAGS => split_tags,
+        /// Gets the ARNs of the tagged resources.
+        get_tag_resources: Option<Vec<String>> = TAG_RESOURCES => split_patterns,
+        /// Gets the SAM CLI executable path.
+        get_sam_bin: PathBuf = SAM_BIN,
+        /// Gets the SAM stack.
+        get_sam_stack: String = SAM_STACK,
+        /// Gets the SAM template.
+        get_sam_template: Option<String> = SAM_TEMPLATE => non_empty,
+        /// Gets the SAM capabilities.
+        get_sam_capabilities: Option<Vec<String>> = SAM_CAPABILITIES => split_patterns,
+        /// Gets the CDK CLI executable path.
+        get_cdk_bin: PathBuf = CDK_BIN,
+        /// Gets the CDK stacks.
+        get_cdk_stacks: Option<Vec<String>> = CDK_STACKS => split_patterns,
+        /// Gets the CDK app.
+        get_cdk_app: Option<String> = CDK_APP => non_empty,
+        /// Gets the stack parameters.
+        get_parameter_overrides: Option<BTreeMap<String, String>> = PARAMETER_OVERRIDES => split_metadata,
+        /// Gets the number of retries of transient failures.
+        get_retry_attempts: u32 = RETRY_ATTEMPTS as u32,
+        /// Gets the base delay between retries.
+        get_retry_delay: Duration = RETRY_DELAY_MS as u64 => Duration::from_millis,
+        /// Gets the dry-run flag of all commands.
+        get_global_dry_run: bool = DRY_RUN,
+        /// Gets the region of all commands.
+        get_region: Option<String> = REGION => non_empty,
+        /// Gets the named profile of all commands.
+        get_profile: Option<String> = PROFILE => non_empty,
+        /// Gets the endpoint of all commands.
+        get_endpoint_url: Option<String> = ENDPOINT_URL => non_empty,
+        /// Gets the backend of S3 syncs, invalidations and Lambda updates.
+        get_backend: Backend = BACKEND as Backend,
+    }
+}
 
-impl AwsConfig {
-    /// Creates a new `AwsConfig` instance.
-    pub fn new() -> Self {
-        Self {}
+/// Splits a comma-separated pattern list, returning `None` when it is empty.
+fn split_patterns(s: String) -> Option<Vec<String>> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s.split(',').map(|s| s.trim().to_string()).collect())
     }
+}
 
-    /// Gets the AWS CLI executable path.
-    pub fn get_bin(&self) -> ConfigResult<PathBuf> {
-        AWS_BIN.get()
+/// Parses comma-separated `key=value` pairs, returning `None` when empty.
+///
+/// Entries without `=` are skipped; [`validate_metadata`] rejects them at
+/// validation time.
+fn split_metadata(s: String) -> Option<BTreeMap<String, String>> {
+    let pairs: BTreeMap<String, String> = s
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .collect();
+    if pairs.is_empty() {
+        None
+    } else {
+        Some(pairs)
     }
+}
 
-    /// Gets the AWS command to execute.
-    pub fn get_cmd(&self) -> ConfigResult<String> {
-        CMD.get()
+/// Validates that every non-empty entry of a metadata list is a `key=value` pair.
+#[allow(clippy::ptr_arg)] // must match `Validator<String>`
+pub(crate) fn validate_metadata(value: &String) -> ConfigResult<()> {
+    for pair in value.split(',').filter(|p| !p.trim().is_empty()) {
+        match pair.split_once('=') {
+            Some((k, _)) if !k.trim().is_empty() => {}
+            _ => {
+                return Err(ConfigError::invalid(pair, "a key=value pair"));
+            }
+        }
     }
+    Ok(())
+}
+
+/// Parses comma-separated `key=value` tags and renders their values.
+fn split_tags(s: String) -> BTreeMap<String, String> {
+    split_metadata(s)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(k, v)| (k, render_template(&v)))
+        .collect()
+}
 
-    /// Gets the S3 destination bucket/path.
-    pub fn get_destination(&self) -> ConfigResult<PathBuf> {
-        S3_DESTINATION.get()
+/// Replaces `${VAR}` references with the value of the environment variable.
+///
+/// Unset variables render as empty strings; [`validate_tags`] rejects
+/// unclosed references.
+fn render_template(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        match rest[start + 2..].find('}') {
+            Some(end) => {
+                let name = &rest[start + 2..start + 2 + end];
+                out.push_str(&std::env::var(name).unwrap_or_default());
+                rest = &rest[start + 3 + end..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
     }
+    out.push_str(rest);
+    out
+}
 
-    /// Gets the exclude patterns as a vector of strings.
-    pub fn get_exclude(&self) -> ConfigResult<Option<Vec<String>>> {
-        S3_EXCLUDE.get().map(|s| {
-            if s.is_empty() {
-                None
-            } else {
-                Some(s.split(',').map(|s| s.trim().to_string()).collect())
+/// Validates a tag list like [`validate_metadata`] and checks that every
+/// `${` reference is closed.
+#[allow(clippy::ptr_arg)] // must match `Validator<String>`
+pub(crate) fn validate_tags(value: &String) -> ConfigResult<()> {
+    validate_metadata(value)?;
+    for pair in value.split(',') {
+        let mut rest = pair;
+        while let Some(start) = rest.find("${") {
+            match rest[start..].find('}') {
+                Some(end) => rest = &rest[start + end + 1..],
+                None => {
+                    return Err(ConfigError::invalid(
+                        pair,
+                        "a tag with closed ${...} references",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses `pattern=cache-control` entries separated by `;`.
+///
+/// Only the first `=` separates the pattern, so values like `max-age=60`
+/// are kept intact.
+fn split_overrides(s: String) -> Vec<SyncOverride> {
+    s.split(';')
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(pattern, value)| SyncOverride::cache_control(pattern.trim(), value.trim()))
+        .collect()
+}
+
+/// Validates that every non-empty entry of an override list is a `pattern=value` pair.
+#[allow(clippy::ptr_arg)] // must match `Validator<String>`
+pub(crate) fn validate_overrides(value: &String) -> ConfigResult<()> {
+    for entry in value.split(';').filter(|e| !e.trim().is_empty()) {
+        match entry.split_once('=') {
+            Some((p, v)) if !p.trim().is_empty() && !v.trim().is_empty() => {}
+            _ => {
+                return Err(ConfigError::invalid(entry, "a pattern=cache-control entry"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses `NAME=secret-id[#json-key]` entries into `GetSecretValue` commands.
+fn split_secrets(s: String) -> Vec<AwsCommand> {
+    s.split(',')
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(name, secret)| {
+            let (secret_id, json_key) = match secret.trim().split_once('#') {
+                Some((id, key)) => (id.to_string(), Some(key.to_string())),
+                None => (secret.trim().to_string(), None),
+            };
+            AwsCommand::GetSecretValue {
+                secret_id,
+                json_key,
+                env_name: name.trim().to_string(),
             }
         })
+        .collect()
+}
+
+/// Validates that every non-empty entry of a secret list is a `NAME=secret-id` pair.
+#[allow(clippy::ptr_arg)] // must match `Validator<String>`
+pub(crate) fn validate_secrets(value: &String) -> ConfigResult<()> {
+    for entry in value.split(',').filter(|e| !e.trim().is_empty()) {
+        match entry.split_once('=') {
+            Some((n, id)) if !n.trim().is_empty() && !id.trim().is_empty() => {}
+            _ => {
+                return Err(ConfigError::invalid(entry, "a NAME=secret-id entry"));
+            }
+        }
     }
+    Ok(())
+}
 
-    /// Gets the include patterns as a vector of strings.
-    pub fn get_include(&self) -> ConfigResult<Option<Vec<String>>> {
-        S3_INCLUDE.get().map(|s| {
-            if s.is_empty() {
-                None
-            } else {
-                Some(s.split(',').map(|s| s.trim().to_string()).collect())
+/// Parses `function=code` entries into Lambda batch targets.
+///
+/// Code ending in `.zip` is a package, anything else an image URI.
+fn split_lambda_targets(s: String) -> Vec<LambdaTarget> {
+    s.split(',')
+        .filter_map(|entry| entry.split_once('='))
+        .map(|(function, code)| {
+            let code = code.trim();
+            LambdaTarget {
+                function: function.trim().to_string(),
+                code: if code.ends_with(".zip") {
+                    LambdaCode::Zip(PathBuf::from(code))
+                } else {
+                    LambdaCode::Image(code.to_string())
+                },
             }
         })
+        .collect()
+}
+
+/// Validates that every non-empty entry of a Lambda batch is a `function=code` pair.
+#[allow(clippy::ptr_arg)] // must match `Validator<String>`
+pub(crate) fn validate_lambda_targets(value: &String) -> ConfigResult<()> {
+    for entry in value.split(',').filter(|e| !e.trim().is_empty()) {
+        match entry.split_once('=') {
+            Some((f, c)) if !f.trim().is_empty() && !c.trim().is_empty() => {}
+            _ => {
+                return Err(ConfigError::invalid(entry, "a function=code entry"));
+            }
+        }
     }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    /// Gets the delete flag status.
-    pub fn get_delete(&self) -> ConfigResult<bool> {
-        S3_DELETE.get()
+    #[test]
+    fn test_split_metadata() {
+        let metadata = split_metadata("version=1.2, commit = abc".to_string()).unwrap();
+        assert_eq!(metadata.get("version").unwrap(), "1.2");
+        assert_eq!(metadata.get("commit").unwrap(), "abc");
+        assert!(split_metadata(String::new()).is_none());
     }
 
-    /// Gets the dry run flag status.
-    pub fn get_dry_run(&self) -> ConfigResult<bool> {
-        S3_DRY_RUN.get()
+    #[test]
+    fn test_split_overrides() {
+        let overrides =
+            split_overrides("assets/*=public, max-age=31536000; index.html = no-cache".to_string());
+        assert_eq!(
+            overrides,
+            vec![
+                SyncOverride::cache_control("assets/*", "public, max-age=31536000"),
+                SyncOverride::cache_control("index.html", "no-cache"),
+            ]
+        );
+        assert!(split_overrides(String::new()).is_empty());
+        assert!(validate_overrides(&"*.html=".to_string()).is_err());
     }
 
-    /// Gets the force flag status.
-    pub fn get_force(&self) -> ConfigResult<bool> {
-        S3_FORCE.get()
+    #[test]
+    fn test_split_secrets() {
+        let commands = split_secrets("DB_PASSWORD=prod/db#password, TOKEN=api-token".to_string());
+        assert_eq!(commands.len(), 2);
+        assert!(matches!(
+            &commands[0],
+            AwsCommand::GetSecretValue { secret_id, json_key: Some(key), env_name }
+                if secret_id == "prod/db" && key == "password" && env_name == "DB_PASSWORD"
+        ));
+        assert!(matches!(
+            &commands[1],
+            AwsCommand::GetSecretValue { json_key: None, env_name, .. } if env_name == "TOKEN"
+        ));
+        assert!(validate_secrets(&"TOKEN".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_split_lambda_targets() {
+        let targets =
+            split_lambda_targets("api=dist/api.zip, worker=registry.example/worker:1".to_string());
+        assert_eq!(
+            targets[0].code,
+            LambdaCode::Zip(PathBuf::from("dist/api.zip"))
+        );
+        assert_eq!(targets[1].function, "worker");
+        assert_eq!(
+            targets[1].code,
+            LambdaCode::Image("registry.example/worker:1".to_string())
+        );
+        assert!(validate_lambda_targets(&"api".to_string()).is_err());
+        assert!(validate_lambda_targets(&String::new()).is_ok());
+    }
+
+    #[test]
+    fn test_split_tags_renders_references() {
+        std::env::set_var("TAGS_TEST_SHA", "abc123");
+        let tags = split_tags("commit=${TAGS_TEST_SHA}, run=r-${TAGS_TEST_UNSET}".to_string());
+        assert_eq!(tags.get("commit").unwrap(), "abc123");
+        assert_eq!(tags.get("run").unwrap(), "r-");
+        assert!(split_tags(String::new()).is_empty());
+    }
+
+    #[test]
+    fn test_validate_tags() {
+        assert!(validate_tags(&String::new()).is_ok());
+        assert!(validate_tags(&"commit=${GITHUB_SHA},team=web".to_string()).is_ok());
+        assert!(validate_tags(&"commit=${GITHUB_SHA".to_string()).is_err());
+        assert!(validate_tags(&"broken".to_string()).is_err());
     }
-}
 
-impl Default for AwsConfig {
-    fn default() -> Self {
-        Self::new()
+    #[test]
+    fn test_validate_metadata() {
+        assert!(validate_metadata(&String::new()).is_ok());
+        assert!(validate_metadata(&"a=1,b=2".to_string()).is_ok());
+        assert!(matches!(
+            validate_metadata(&"a=1,broken".to_string()),
+            Err(ConfigError::InvalidValue(_))
+        ));
     }
 }
@@ -0,0 +1,239 @@
+use std::fmt;
+
+/// The kind of transfer reported by `aws s3 sync`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncOperation {
+    Upload,
+    Download,
+    Copy,
+    Delete,
+}
+
+impl fmt::Display for SyncOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Upload => "upload",
+            Self::Download => "download",
+            Self::Copy => "copy",
+            Self::Delete => "delete",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A structured event parsed from the output of `aws s3 sync`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncEvent {
+    /// A file was transferred or deleted.
+    ///
+    /// `key` is the destination of the transfer, or the deleted object.
+    Transferred {
+        operation: SyncOperation,
+        key: String,
+    },
+
+    /// Overall progress of the running sync.
+    Progress {
+        completed_bytes: u64,
+        total_bytes: Option<u64>,
+        files_remaining: Option<u64>,
+    },
+}
+
+/// Parses one line of `aws s3 sync` output.
+///
+/// The CLI separates progress updates with `\r`, so a single line may hold
+/// several events. Returns `None` if the line contains no recognized event,
+/// i.e. it should be shown as is.
+///
+/// # Example
+///
+/// ```rust
+/// use aws::progress::{parse_line, SyncEvent, SyncOperation};
+///
+/// let events = parse_line("upload: dist/app.js to s3://site/app.js").unwrap();
+/// assert_eq!(
+///     events,
+///     vec![SyncEvent::Transferred {
+///         operation: SyncOperation::Upload,
+///         key: "s3://site/app.js".to_string(),
+///     }]
+/// );
+/// assert!(parse_line("warning: Skipping file").is_none());
+/// ```
+pub fn parse_line(line: &str) -> Option<Vec<SyncEvent>> {
+    let mut events = Vec::new();
+    for segment in line.split('\r').map(str::trim).filter(|s| !s.is_empty()) {
+        events.push(parse_segment(segment)?);
+    }
+    if events.is_empty() {
+        None
+    } else {
+        Some(events)
+    }
+}
+
+fn parse_segment(segment: &str) -> Option<SyncEvent> {
+    if let Some(rest) = segment.strip_prefix("Completed ") {
+        return parse_progress(rest);
+    }
+
+    let (operation, rest) = segment.split_once(": ")?;
+    let operation = match operation {
+        "upload" => SyncOperation::Upload,
+        "download" => SyncOperation::Download,
+        "copy" => SyncOperation::Copy,
+        "delete" => SyncOperation::Delete,
+        _ => return None,
+    };
+    let key = match rest.rsplit_once(" to ") {
+        Some((_, destination)) if operation != SyncOperation::Delete => destination,
+        _ => rest,
+    };
+    Some(SyncEvent::Transferred {
+        operation,
+        key: key.to_string(),
+    })
+}
+
+/// Parses `1.0 MiB/5.0 MiB (2.1 MiB/s) with 3 file(s) remaining`.
+fn parse_progress(rest: &str) -> Option<SyncEvent> {
+    let (sizes, tail) = match rest.split_once(" (") {
+        Some((sizes, tail)) => (sizes, Some(tail)),
+        None => (rest, None),
+    };
+    let (completed, total) = match sizes.split_once('/') {
+        Some((completed, total)) => (completed, parse_size(total.trim_end_matches('~'))),
+        None => (sizes, None),
+    };
+
+    let files_remaining = tail
+        .and_then(|t| t.split_once(" with "))
+        .and_then(|(_, files)| files.split_whitespace().next())
+        .and_then(|n| n.parse().ok());
+
+    Some(SyncEvent::Progress {
+        completed_bytes: parse_size(completed)?,
+        total_bytes: total,
+        files_remaining,
+    })
+}
+
+/// Parses a size such as `256 Bytes` or `1.5 MiB` into bytes.
+fn parse_size(s: &str) -> Option<u64> {
+    let mut parts = s.split_whitespace();
+    let value: f64 = parts.next()?.parse().ok()?;
+    let unit = match parts.next().unwrap_or("Bytes") {
+        "Byte" | "Bytes" => 1u64,
+        "KiB" => 1 << 10,
+        "MiB" => 1 << 20,
+        "GiB" => 1 << 30,
+        "TiB" => 1 << 40,
+        _ => return None,
+    };
+    Some((value * unit as f64) as u64)
+}
+
+/// Running totals of a sync, built from its events.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncSummary {
+    /// Number of uploaded, downloaded or copied files.
+    pub transferred: u64,
+    /// Number of deleted objects.
+    pub deleted: u64,
+    /// Bytes completed according to the last progress update.
+    pub bytes: u64,
+    /// The most recently transferred key.
+    pub last_key: Option<String>,
+}
+
+impl SyncSummary {
+    /// Creates an empty summary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the totals with an event.
+    pub fn record(&mut self, event: &SyncEvent) {
+        match event {
+            SyncEvent::Transferred { operation, key } => {
+                if *operation == SyncOperation::Delete {
+                    self.deleted += 1;
+                } else {
+                    self.transferred += 1;
+                }
+                self.last_key = Some(key.clone());
+            }
+            SyncEvent::Progress {
+                completed_bytes, ..
+            } => {
+                self.bytes = self.bytes.max(*completed_bytes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_transfers() {
+        assert_eq!(
+            parse_line("delete: s3://site/old.js").unwrap(),
+            vec![SyncEvent::Transferred {
+                operation: SyncOperation::Delete,
+                key: "s3://site/old.js".to_string(),
+            }]
+        );
+        assert_eq!(
+            parse_line("copy: s3://a/x to s3://b/x").unwrap(),
+            vec![SyncEvent::Transferred {
+                operation: SyncOperation::Copy,
+                key: "s3://b/x".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_progress_with_carriage_returns() {
+        let events = parse_line(
+            "Completed 1.0 MiB/4.0 MiB (2.0 MiB/s) with 3 file(s) remaining\rupload: a.js to s3://site/a.js",
+        )
+        .unwrap();
+        assert_eq!(
+            events[0],
+            SyncEvent::Progress {
+                completed_bytes: 1 << 20,
+                total_bytes: Some(4 << 20),
+                files_remaining: Some(3),
+            }
+        );
+        assert!(matches!(events[1], SyncEvent::Transferred { .. }));
+    }
+
+    #[test]
+    fn test_parse_unknown_line() {
+        assert!(parse_line("").is_none());
+        assert!(parse_line("fatal error: NoSuchBucket").is_none());
+        assert!(parse_line("Completed 1 MiB\rsomething else").is_none());
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut summary = SyncSummary::new();
+        for line in [
+            "upload: a to s3://site/a",
+            "Completed 512 Bytes/1.0 KiB (1 KiB/s) with 1 file(s) remaining",
+            "delete: s3://site/b",
+        ] {
+            for event in parse_line(line).unwrap() {
+                summary.record(&event);
+            }
+        }
+        assert_eq!(summary.transferred, 1);
+        assert_eq!(summary.deleted, 1);
+        assert_eq!(summary.bytes, 512);
+        assert_eq!(summary.last_key.as_deref(), Some("s3://site/b"));
+    }
+}
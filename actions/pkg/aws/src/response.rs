@@ -0,0 +1,298 @@
+use crate::progress::SyncSummary;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// The function configuration returned by `lambda update-function-code`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct LambdaFunction {
+    pub function_name: String,
+    pub function_arn: String,
+    /// `$LATEST`, or the published version with `--publish`.
+    pub version: String,
+    /// Base64-encoded SHA-256 of the deployment package.
+    pub code_sha256: String,
+    #[serde(default)]
+    pub last_update_status: Option<String>,
+}
+
+impl LambdaFunction {
+    /// Parses the JSON output of `update-function-code`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::response::LambdaFunction;
+    ///
+    /// let function = LambdaFunction::parse(r#"{
+    ///     "FunctionName": "api",
+    ///     "FunctionArn": "arn:aws:lambda:eu-west-1:123:function:api",
+    ///     "Version": "7",
+    ///     "CodeSha256": "abc="
+    /// }"#).unwrap();
+    /// assert_eq!(function.version, "7");
+    /// ```
+    pub fn parse(output: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(output)
+    }
+}
+
+/// An invalidation created by `cloudfront create-invalidation`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Invalidation {
+    pub id: String,
+    /// `InProgress` or `Completed`.
+    pub status: String,
+}
+
+impl Invalidation {
+    /// Parses the JSON output of `create-invalidation`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::response::Invalidation;
+    ///
+    /// let invalidation = Invalidation::parse(r#"{
+    ///     "Location": "https://cloudfront.amazonaws.com/2020-05-31/distribution/E123/invalidation/I2J0",
+    ///     "Invalidation": { "Id": "I2J0", "Status": "InProgress" }
+    /// }"#).unwrap();
+    /// assert_eq!(invalidation.id, "I2J0");
+    /// ```
+    pub fn parse(output: &str) -> serde_json::Result<Self> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct Response {
+            invalidation: Invalidation,
+        }
+        serde_json::from_str::<Response>(output).map(|r| r.invalidation)
+    }
+}
+
+/// A summary of an invalidation returned by `cloudfront list-invalidations`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InvalidationSummary {
+    pub id: String,
+    pub status: String,
+    pub create_time: String,
+}
+
+/// A version returned by `lambda list-versions-by-function`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct FunctionVersion {
+    /// `$LATEST` or a published version number.
+    pub version: String,
+    pub function_arn: String,
+    pub code_sha256: String,
+}
+
+impl FunctionVersion {
+    /// Returns the number of a published version, `None` for `$LATEST`.
+    pub fn number(&self) -> Option<u64> {
+        self.version.parse().ok()
+    }
+}
+
+/// One page of the output of a list command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Token of the next page; `None` on the last page.
+    pub next_token: Option<String>,
+}
+
+impl<T: DeserializeOwned> Page<T> {
+    /// Parses a page of JSON output, reading the items at the JSON pointer `items`.
+    ///
+    /// Missing items, e.g. of an empty CloudFront list, parse as an empty page.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::response::{FunctionVersion, Page};
+    ///
+    /// let page: Page<FunctionVersion> = Page::parse(r#"{
+    ///     "Versions": [{"Version": "1", "FunctionArn": "arn:aws:lambda:eu-west-1:123:function:api:1", "CodeSha256": "a="}],
+    ///     "NextToken": "eyJNYXJrZXIiOiAiMSJ9"
+    /// }"#, "/Versions").unwrap();
+    /// assert_eq!(page.items[0].number(), Some(1));
+    /// assert_eq!(page.next_token.as_deref(), Some("eyJNYXJrZXIiOiAiMSJ9"));
+    /// ```
+    pub fn parse(output: &str, items: &str) -> serde_json::Result<Self> {
+        let mut value: serde_json::Value = serde_json::from_str(output)?;
+        let next_token = value
+            .get("NextToken")
+            .and_then(|t| t.as_str())
+            .map(str::to_string);
+        let items = match value.pointer_mut(items) {
+            Some(items) => serde_json::from_value(items.take())?,
+            None => Vec::new(),
+        };
+        Ok(Self { items, next_token })
+    }
+}
+
+/// Query parameters of a presigned URL that grant access to the object.
+const SIGNATURE_PARAMS: &[&str] = &[
+    "X-Amz-Signature",
+    "X-Amz-Credential",
+    "X-Amz-Security-Token",
+];
+
+/// A presigned S3 URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresignedUrl {
+    pub url: String,
+    /// Lifetime of the URL in seconds.
+    pub expires_in: u32,
+}
+
+impl PresignedUrl {
+    /// Returns the values of the signature query parameters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::response::PresignedUrl;
+    ///
+    /// let url = PresignedUrl {
+    ///     url: "https://b.s3.amazonaws.com/k?X-Amz-Expires=60&X-Amz-Signature=abc123".to_string(),
+    ///     expires_in: 60,
+    /// };
+    /// assert_eq!(url.signature_values(), vec!["abc123"]);
+    /// ```
+    pub fn signature_values(&self) -> Vec<&str> {
+        let Some((_, query)) = self.url.split_once('?') else {
+            return Vec::new();
+        };
+        query
+            .split('&')
+            .filter_map(|param| param.split_once('='))
+            .filter(|(name, value)| SIGNATURE_PARAMS.contains(name) && !value.is_empty())
+            .map(|(_, value)| value)
+            .collect()
+    }
+}
+
+/// The result of an S3 sync.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncResult {
+    /// Exit code of the first failed command, or `0`.
+    pub code: i32,
+    /// Totals over all commands of the sync.
+    pub summary: SyncSummary,
+}
+
+/// The result of a Lambda update chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LambdaResult {
+    /// Exit code of the first failed command, or `0`.
+    pub code: i32,
+    /// The updated function, if the code update succeeded.
+    pub function: Option<LambdaFunction>,
+}
+
+/// The result of a CloudFront invalidation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidationResult {
+    /// Exit code of the command.
+    pub code: i32,
+    /// The created invalidation; `None` if the command failed or was not run.
+    pub invalidation: Option<Invalidation>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lambda_function() {
+        let function = LambdaFunction::parse(
+            r#"{
+                "FunctionName": "api",
+                "FunctionArn": "arn:aws:lambda:eu-west-1:123:function:api:3",
+                "Runtime": "provided.al2",
+                "Version": "3",
+                "CodeSha256": "n0o/abc=",
+                "LastUpdateStatus": "InProgress"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(function.function_name, "api");
+        assert_eq!(function.code_sha256, "n0o/abc=");
+        assert_eq!(function.last_update_status.as_deref(), Some("InProgress"));
+    }
+
+    #[test]
+    fn test_signature_values() {
+        let url = PresignedUrl {
+            url: "https://b.s3.amazonaws.com/k?X-Amz-Credential=AKIA%2F20240101&X-Amz-Signature=f00&X-Amz-Security-Token=tok".to_string(),
+            expires_in: 60,
+        };
+        assert_eq!(
+            url.signature_values(),
+            vec!["AKIA%2F20240101", "f00", "tok"]
+        );
+        assert!(PresignedUrl {
+            url: "https://b.s3.amazonaws.com/k".to_string(),
+            expires_in: 60,
+        }
+        .signature_values()
+        .is_empty());
+    }
+
+    #[test]
+    fn test_parse_invalidation() {
+        let invalidation = Invalidation::parse(
+            r#"{
+                "Location": "https://cloudfront.amazonaws.com/2020-05-31/distribution/E123/invalidation/I9",
+                "Invalidation": {
+                    "Id": "I9",
+                    "Status": "InProgress",
+                    "CreateTime": "2024-01-01T00:00:00Z",
+                    "InvalidationBatch": { "Paths": { "Quantity": 1, "Items": ["/*"] }, "CallerReference": "r" }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(invalidation.id, "I9");
+        assert_eq!(invalidation.status, "InProgress");
+        assert!(Invalidation::parse(r#"{"Id": "I9"}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_invalidation_page() {
+        let page: Page<InvalidationSummary> = Page::parse(
+            r#"{
+                "InvalidationList": {
+                    "Items": [
+                        { "Id": "I2", "CreateTime": "2024-01-02T00:00:00Z", "Status": "InProgress" },
+                        { "Id": "I1", "CreateTime": "2024-01-01T00:00:00Z", "Status": "Completed" }
+                    ],
+                    "Quantity": 2
+                }
+            }"#,
+            "/InvalidationList/Items",
+        )
+        .unwrap();
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.items[1].status, "Completed");
+        assert!(page.next_token.is_none());
+
+        let empty: Page<InvalidationSummary> = Page::parse(
+            r#"{"InvalidationList": {"Quantity": 0}}"#,
+            "/InvalidationList/Items",
+        )
+        .unwrap();
+        assert!(empty.items.is_empty());
+        assert!(Page::<InvalidationSummary>::parse("I1\tCompleted", "/Items").is_err());
+    }
+
+    #[test]
+    fn test_parse_lambda_function_rejects_text() {
+        assert!(LambdaFunction::parse("api\t3").is_err());
+    }
+}
@@ -0,0 +1,47 @@
+/// Error messages of the AWS CLI that indicate a transient failure.
+const TRANSIENT_PATTERNS: &[&str] = &[
+    "Throttling",
+    "SlowDown",
+    "RequestTimeout",
+    "RequestLimitExceeded",
+    "TooManyRequestsException",
+    "ServiceUnavailable",
+    "InternalError",
+    "(500)",
+    "(502)",
+    "(503)",
+    "(504)",
+    "Could not connect to the endpoint URL",
+    "Connection was closed",
+    "Read timeout on endpoint URL",
+];
+
+/// Returns `true` if a line of CLI error output indicates a transient failure.
+///
+/// # Example
+///
+/// ```rust
+/// use aws::retry::is_transient;
+///
+/// assert!(is_transient("An error occurred (SlowDown) when calling the PutObject operation"));
+/// assert!(!is_transient("An error occurred (AccessDenied) when calling the PutObject operation"));
+/// ```
+pub fn is_transient(line: &str) -> bool {
+    TRANSIENT_PATTERNS.iter().any(|p| line.contains(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient(
+            "upload failed: a to s3://b/a An error occurred (ThrottlingException)"
+        ));
+        assert!(is_transient(
+            "An error occurred (503) when calling the HeadObject operation"
+        ));
+        assert!(!is_transient("fatal error: NoSuchBucket"));
+    }
+}
@@ -0,0 +1,754 @@
+use crate::command::{AwsCommand, GlobalOptions};
+use crate::error::{AwsError, AwsResult};
+use crate::response::{Invalidation, LambdaFunction};
+
+use aws_config::{BehaviorVersion, Region, SdkConfig};
+use aws_sdk_s3::config::Credentials;
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::primitives::ByteStream;
+use futures::stream::{self, TryStreamExt};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Number of objects uploaded or deleted at the same time, as with the CLI.
+const CONCURRENCY: usize = 10;
+
+/// Delay between two polls of a Lambda function that is still updating.
+const LAMBDA_POLL_DELAY: Duration = Duration::from_secs(5);
+
+/// Number of polls before `LambdaWait` gives up, as with `aws lambda wait`.
+const LAMBDA_POLL_ATTEMPTS: u32 = 60;
+
+/// How S3 syncs, CloudFront invalidations and Lambda code updates reach AWS.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Runs the AWS CLI.
+    #[default]
+    Cli,
+    /// Calls the AWS APIs through the SDK, see [`AwsExecutor::with_sdk`](crate::AwsExecutor::with_sdk).
+    Sdk,
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    /// Parses `cli` or `sdk`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use aws::Backend;
+    ///
+    /// assert_eq!("sdk".parse(), Ok(Backend::Sdk));
+    /// assert!("boto".parse::<Backend>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cli" => Ok(Self::Cli),
+            "sdk" => Ok(Self::Sdk),
+            _ => Err(format!("unknown backend '{}', expected cli or sdk", s)),
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cli => write!(f, "cli"),
+            Self::Sdk => write!(f, "sdk"),
+        }
+    }
+}
+
+/// Clients of the AWS APIs called by the SDK backend.
+#[derive(Debug, Clone)]
+pub struct Sdk {
+    s3: aws_sdk_s3::Client,
+    cloudfront: aws_sdk_cloudfront::Client,
+    lambda: aws_sdk_lambda::Client,
+}
+
+impl Sdk {
+    /// Loads the clients from the default credential chain.
+    ///
+    /// The region, profile and endpoint of `globals` take precedence, as they
+    /// do for the CLI.
+    pub async fn load(globals: &GlobalOptions) -> Self {
+        let mut loader = aws_config::defaults(BehaviorVersion::latest());
+        if let Some(region) = &globals.region {
+            loader = loader.region(Region::new(region.clone()));
+        }
+        if let Some(profile) = &globals.profile {
+            loader = loader.profile_name(profile);
+        }
+        if let Some(endpoint_url) = &globals.endpoint_url {
+            loader = loader.endpoint_url(endpoint_url);
+        }
+        Self::from_conf(&loader.load().await)
+    }
+
+    /// Creates the clients from a shared SDK configuration.
+    ///
+    /// With a custom endpoint, such as LocalStack, buckets are addressed by path.
+    pub fn from_conf(conf: &SdkConfig) -> Self {
+        let s3 = aws_sdk_s3::config::Builder::from(conf)
+            .force_path_style(conf.endpoint_url().is_some())
+            .build();
+        Self {
+            s3: aws_sdk_s3::Client::from_conf(s3),
+            cloudfront: aws_sdk_cloudfront::Client::new(conf),
+            lambda: aws_sdk_lambda::Client::new(conf),
+        }
+    }
+
+    /// Returns clients using the credentials of `env`, if it has any.
+    ///
+    /// Commands such as `AssumeRole` switch the credentials of the executor's
+    /// environment, which the clients would not see otherwise.
+    pub(crate) fn with_env(&self, env: &HashMap<String, String>) -> Self {
+        let (Some(access_key), Some(secret_key)) = (
+            env.get("AWS_ACCESS_KEY_ID"),
+            env.get("AWS_SECRET_ACCESS_KEY"),
+        ) else {
+            return self.clone();
+        };
+        let credentials = Credentials::new(
+            access_key,
+            secret_key,
+            env.get("AWS_SESSION_TOKEN").cloned(),
+            None,
+            "executor-env",
+        );
+        Self {
+            s3: aws_sdk_s3::Client::from_conf(
+                self.s3
+                    .config()
+                    .to_builder()
+                    .credentials_provider(credentials.clone())
+                    .build(),
+            ),
+            cloudfront: aws_sdk_cloudfront::Client::from_conf(
+                self.cloudfront
+                    .config()
+                    .to_builder()
+                    .credentials_provider(credentials.clone())
+                    .build(),
+            ),
+            lambda: aws_sdk_lambda::Client::from_conf(
+                self.lambda
+                    .config()
+                    .to_builder()
+                    .credentials_provider(credentials)
+                    .build(),
+            ),
+        }
+    }
+
+    /// Syncs a local directory to S3 like `aws s3 sync`.
+    ///
+    /// A file is uploaded if its object is missing, has another size or is
+    /// older than the file, and every file is with `force`. `report` receives
+    /// a line in the format of the CLI for every transfer. Syncs from S3, and
+    /// so `metadata_directive`, are not supported.
+    pub(crate) async fn sync(
+        &self,
+        command: &AwsCommand,
+        report: &(dyn Fn(&str) + Sync),
+    ) -> AwsResult<i32> {
+        let AwsCommand::S3Sync {
+            source,
+            destination,
+            exclude,
+            include,
+            delete,
+            dry_run,
+            force,
+            cache_control,
+            content_type,
+            storage_class,
+            acl,
+            sse,
+            metadata_directive,
+        } = command
+        else {
+            return Err(AwsError::CommandError(
+                "expected an S3Sync command".to_string(),
+            ));
+        };
+        if s3_location(source).is_some() {
+            return Err(AwsError::S3Error(
+                "the SDK backend only syncs local directories to S3".to_string(),
+            ));
+        }
+        if metadata_directive.is_some() {
+            return Err(AwsError::S3Error(
+                "metadata_directive is not supported by the SDK backend".to_string(),
+            ));
+        }
+        let Some((bucket, prefix)) = s3_location(destination) else {
+            return Err(AwsError::S3Error(format!(
+                "{} is not an s3:// location",
+                destination.display()
+            )));
+        };
+
+        let filters = Filters {
+            exclude: exclude.as_deref().unwrap_or_default(),
+            include: include.as_deref().unwrap_or_default(),
+        };
+        let local = local_files(source)?;
+        let remote = self.remote_objects(&bucket, &prefix).await?;
+        let transfers = plan(&local, &remote, &filters, *delete, *force);
+
+        let uri = |key: &str| format!("s3://{}/{}{}", bucket, prefix, key);
+        let dry = if *dry_run { "(dryrun) " } else { "" };
+        let files: HashMap<&str, &LocalFile> =
+            local.iter().map(|file| (file.key.as_str(), file)).collect();
+        let uploaded = stream::iter(transfers.iter().map(Ok))
+            .map_ok(|transfer| async {
+                let (line, size) = match transfer {
+                    Transfer::Upload(key) => {
+                        let file = files[key.as_str()];
+                        if !*dry_run {
+                            let mime = content_type.clone().unwrap_or_else(|| {
+                                mime_guess::from_path(&file.path)
+                                    .first_or_octet_stream()
+                                    .to_string()
+                            });
+                            let body = ByteStream::from_path(&file.path)
+                                .await
+                                .map_err(|e| AwsError::S3Error(e.to_string()))?;
+                            self.s3
+                                .put_object()
+                                .bucket(&bucket)
+                                .key(format!("{}{}", prefix, key))
+                                .body(body)
+                                .content_type(mime)
+                                .set_cache_control(cache_control.clone())
+                                .set_storage_class(storage_class.as_deref().map(Into::into))
+                                .set_acl(acl.as_deref().map(Into::into))
+                                .set_server_side_encryption(sse.as_deref().map(Into::into))
+                                .send()
+                                .await
+                                .map_err(|e| sdk_error("PutObject", e))?;
+                        }
+                        let line =
+                            format!("{}upload: {} to {}", dry, file.path.display(), uri(key));
+                        (line, file.size)
+                    }
+                    Transfer::Delete(key) => {
+                        if !*dry_run {
+                            self.s3
+                                .delete_object()
+                                .bucket(&bucket)
+                                .key(format!("{}{}", prefix, key))
+                                .send()
+                                .await
+                                .map_err(|e| sdk_error("DeleteObject", e))?;
+                        }
+                        (format!("{}delete: {}", dry, uri(key)), 0)
+                    }
+                };
+                report(&line);
+                Ok::<u64, AwsError>(size)
+            })
+            .try_buffer_unordered(CONCURRENCY)
+            .try_fold(0, |total, size| async move { Ok(total + size) })
+            .await?;
+
+        if uploaded > 0 && !*dry_run {
+            report(&format!("Completed {} Bytes/{} Bytes", uploaded, uploaded));
+        }
+        Ok(0)
+    }
+
+    /// Lists the objects below `prefix`, keyed by their path relative to it.
+    async fn remote_objects(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> AwsResult<HashMap<String, RemoteObject>> {
+        let mut objects = HashMap::new();
+        let mut pages = self
+            .s3
+            .list_objects_v2()
+            .bucket(bucket)
+            .prefix(prefix)
+            .into_paginator()
+            .send();
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| sdk_error("ListObjectsV2", e))?;
+            for object in page.contents() {
+                let Some(key) = object.key().and_then(|key| key.strip_prefix(prefix)) else {
+                    continue;
+                };
+                objects.insert(
+                    key.to_string(),
+                    RemoteObject {
+                        size: object.size().unwrap_or_default() as u64,
+                        modified: object.last_modified().map_or(0, |time| time.secs()),
+                    },
+                );
+            }
+        }
+        Ok(objects)
+    }
+
+    /// Creates the invalidation of a `CloudFrontInvalidate` command.
+    pub(crate) async fn invalidate(&self, command: &AwsCommand) -> AwsResult<Invalidation> {
+        let AwsCommand::CloudFrontInvalidate {
+            distribution_id,
+            paths,
+            caller_reference,
+        } = command
+        else {
+            return Err(AwsError::CommandError(
+                "expected a CloudFrontInvalidate command".to_string(),
+            ));
+        };
+        let batch = aws_sdk_cloudfront::types::Paths::builder()
+            .quantity(paths.len() as i32)
+            .set_items(Some(paths.clone()))
+            .build()
+            .and_then(|paths| {
+                aws_sdk_cloudfront::types::InvalidationBatch::builder()
+                    .paths(paths)
+                    .caller_reference(caller_reference)
+                    .build()
+            })
+            .map_err(|e| AwsError::CommandError(e.to_string()))?;
+        let output = self
+            .cloudfront
+            .create_invalidation()
+            .distribution_id(distribution_id)
+            .invalidation_batch(batch)
+            .send()
+            .await
+            .map_err(|e| sdk_error("CreateInvalidation", e))?;
+        let invalidation = output.invalidation().ok_or_else(|| {
+            AwsError::CommandError("CreateInvalidation returned no invalidation".to_string())
+        })?;
+        Ok(Invalidation {
+            id: invalidation.id().to_string(),
+            status: invalidation.status().to_string(),
+        })
+    }
+
+    /// Updates the code of a function from a `LambdaUpdateZip` or
+    /// `LambdaUpdateImage` command.
+    pub(crate) async fn update_function_code(
+        &self,
+        command: &AwsCommand,
+    ) -> AwsResult<LambdaFunction> {
+        let request = match command {
+            AwsCommand::LambdaUpdateZip {
+                function_name,
+                zip_file,
+                publish,
+            } => {
+                let code = tokio::fs::read(zip_file).await.map_err(|e| {
+                    AwsError::CommandError(format!("failed to read {}: {}", zip_file.display(), e))
+                })?;
+                self.lambda
+                    .update_function_code()
+                    .function_name(function_name)
+                    .zip_file(code.into())
+                    .publish(*publish)
+            }
+            AwsCommand::LambdaUpdateImage {
+                function_name,
+                image_uri,
+                publish,
+            } => self
+                .lambda
+                .update_function_code()
+                .function_name(function_name)
+                .image_uri(image_uri)
+                .publish(*publish),
+            _ => {
+                return Err(AwsError::CommandError(
+                    "expected a Lambda code update command".to_string(),
+                ))
+            }
+        };
+        let output = request
+            .send()
+            .await
+            .map_err(|e| sdk_error("UpdateFunctionCode", e))?;
+        Ok(LambdaFunction {
+            function_name: output.function_name().unwrap_or_default().to_string(),
+            function_arn: output.function_arn().unwrap_or_default().to_string(),
+            version: output.version().unwrap_or_default().to_string(),
+            code_sha256: output.code_sha256().unwrap_or_default().to_string(),
+            last_update_status: output
+                .last_update_status()
+                .map(|status| status.as_str().to_string()),
+        })
+    }
+
+    /// Waits until the last update of a function has completed, like
+    /// `aws lambda wait function-updated`.
+    pub(crate) async fn wait_function_updated(&self, function_name: &str) -> AwsResult<i32> {
+        use aws_sdk_lambda::types::LastUpdateStatus;
+
+        for _ in 0..LAMBDA_POLL_ATTEMPTS {
+            let output = self
+                .lambda
+                .get_function_configuration()
+                .function_name(function_name)
+                .send()
+                .await
+                .map_err(|e| sdk_error("GetFunctionConfiguration", e))?;
+            match output.last_update_status() {
+                Some(LastUpdateStatus::InProgress) => tokio::time::sleep(LAMBDA_POLL_DELAY).await,
+                Some(LastUpdateStatus::Failed) => {
+                    return Err(AwsError::CommandError(format!(
+                        "update of function {} failed: {}",
+                        function_name,
+                        output
+                            .last_update_status_reason()
+                            .unwrap_or("no reason given")
+                    )))
+                }
+                _ => return Ok(0),
+            }
+        }
+        Err(AwsError::CommandError(format!(
+            "function {} was still updating after {} checks",
+            function_name, LAMBDA_POLL_ATTEMPTS
+        )))
+    }
+}
+
+/// Converts an error of an SDK call into an [`AwsError::SdkError`].
+///
+/// Errors without a message of the service, e.g. failed connections, are
+/// described by their chain of sources.
+fn sdk_error<E, R>(operation: &str, error: SdkError<E, R>) -> AwsError
+where
+    E: ProvideErrorMetadata + std::error::Error + 'static,
+    R: fmt::Debug,
+{
+    AwsError::SdkError {
+        operation: operation.to_string(),
+        code: error.code().map(str::to_string),
+        message: match error.message() {
+            Some(message) => message.to_string(),
+            None => {
+                let mut message = error.to_string();
+                let mut source = std::error::Error::source(&error);
+                while let Some(cause) = source {
+                    message.push_str(&format!(": {}", cause));
+                    source = cause.source();
+                }
+                message
+            }
+        },
+    }
+}
+
+/// Splits `s3://bucket/prefix` into the bucket and the prefix of the keys,
+/// which is empty or ends with `/`.
+fn s3_location(path: &Path) -> Option<(String, String)> {
+    let location = path.to_str()?.strip_prefix("s3://")?;
+    let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+    if bucket.is_empty() {
+        return None;
+    }
+    let prefix = match prefix.trim_end_matches('/') {
+        "" => String::new(),
+        prefix => format!("{}/", prefix),
+    };
+    Some((bucket.to_string(), prefix))
+}
+
+/// A file of the synced directory.
+#[derive(Debug, Clone, PartialEq)]
+struct LocalFile {
+    /// Path relative to the directory, separated by `/`.
+    key: String,
+    path: PathBuf,
+    size: u64,
+    /// Modification time in seconds since the epoch.
+    modified: i64,
+}
+
+/// An object below the destination prefix.
+#[derive(Debug, Clone, PartialEq)]
+struct RemoteObject {
+    size: u64,
+    /// Modification time in seconds since the epoch.
+    modified: i64,
+}
+
+/// Lists the files below `dir`, following symbolic links like the CLI.
+fn local_files(dir: &Path) -> AwsResult<Vec<LocalFile>> {
+    let io_error = |path: &Path, e: std::io::Error| {
+        AwsError::S3Error(format!("failed to read {}: {}", path.display(), e))
+    };
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(current) = dirs.pop() {
+        for entry in std::fs::read_dir(&current).map_err(|e| io_error(&current, e))? {
+            let path = entry.map_err(|e| io_error(&current, e))?.path();
+            let metadata = std::fs::metadata(&path).map_err(|e| io_error(&path, e))?;
+            if metadata.is_dir() {
+                dirs.push(path);
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(dir) else {
+                continue;
+            };
+            let key = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |duration| duration.as_secs() as i64);
+            files.push(LocalFile {
+                key,
+                path,
+                size: metadata.len(),
+                modified,
+            });
+        }
+    }
+    files.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(files)
+}
+
+/// The `--exclude` and `--include` patterns of a sync.
+///
+/// Every key is synced unless a pattern says otherwise; later patterns take
+/// precedence, with includes following excludes as on the command line.
+struct Filters<'a> {
+    exclude: &'a [String],
+    include: &'a [String],
+}
+
+impl Filters<'_> {
+    fn allows(&self, key: &str) -> bool {
+        let mut allowed = true;
+        for pattern in self.exclude {
+            if wildcard(pattern, key) {
+                allowed = false;
+            }
+        }
+        for pattern in self.include {
+            if wildcard(pattern, key) {
+                allowed = true;
+            }
+        }
+        allowed
+    }
+}
+
+/// Matches `text` against a pattern where `*` matches any characters,
+/// including `/`, and `?` matches one character.
+fn wildcard(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// A change made by a sync.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Transfer {
+    Upload(String),
+    Delete(String),
+}
+
+/// Decides the transfers of a sync, uploads first.
+fn plan(
+    local: &[LocalFile],
+    remote: &HashMap<String, RemoteObject>,
+    filters: &Filters,
+    delete: bool,
+    force: bool,
+) -> Vec<Transfer> {
+    let mut transfers: Vec<Transfer> = local
+        .iter()
+        .filter(|file| filters.allows(&file.key))
+        .filter(|file| match remote.get(&file.key) {
+            Some(object) => force || object.size != file.size || object.modified < file.modified,
+            None => true,
+        })
+        .map(|file| Transfer::Upload(file.key.clone()))
+        .collect();
+    if delete {
+        let keys: HashSet<&str> = local.iter().map(|file| file.key.as_str()).collect();
+        let mut deleted: Vec<&String> = remote
+            .keys()
+            .filter(|key| !keys.contains(key.as_str()) && filters.allows(key))
+            .collect();
+        deleted.sort();
+        transfers.extend(deleted.into_iter().map(|key| Transfer::Delete(key.clone())));
+    }
+    transfers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn file(key: &str, size: u64, modified: i64) -> LocalFile {
+        LocalFile {
+            key: key.to_string(),
+            path: PathBuf::from(key),
+            size,
+            modified,
+        }
+    }
+
+    #[test]
+    fn test_wildcard() {
+        assert!(wildcard("*.html", "index.html"));
+        assert!(wildcard("*.html", "docs/index.html"));
+        assert!(wildcard("assets/*", "assets/js/app.js"));
+        assert!(wildcard("app.?s", "app.js"));
+        assert!(!wildcard("*.html", "index.htm"));
+        assert!(!wildcard("assets/*", "img/assets/logo.png"));
+    }
+
+    #[test]
+    fn test_later_filters_take_precedence() {
+        let exclude = vec!["*".to_string()];
+        let include = vec!["*.html".to_string()];
+        let filters = Filters {
+            exclude: &exclude,
+            include: &include,
+        };
+        assert!(filters.allows("index.html"));
+        assert!(!filters.allows("app.js"));
+    }
+
+    #[test]
+    fn test_s3_location() {
+        assert_eq!(
+            s3_location(Path::new("s3://site")),
+            Some(("site".to_string(), String::new()))
+        );
+        assert_eq!(
+            s3_location(Path::new("s3://site/releases/v1/")),
+            Some(("site".to_string(), "releases/v1/".to_string()))
+        );
+        assert_eq!(s3_location(Path::new("./dist")), None);
+        assert_eq!(s3_location(Path::new("s3:///key")), None);
+    }
+
+    #[test]
+    fn test_plan_uploads_changed_files() {
+        let local = vec![
+            file("index.html", 10, 200),
+            file("app.js", 20, 100),
+            file("new.css", 5, 100),
+            file("old.png", 7, 100),
+        ];
+        let remote = HashMap::from([
+            (
+                "index.html".to_string(),
+                RemoteObject {
+                    size: 10,
+                    modified: 150,
+                },
+            ),
+            (
+                "app.js".to_string(),
+                RemoteObject {
+                    size: 20,
+                    modified: 150,
+                },
+            ),
+            (
+                "old.png".to_string(),
+                RemoteObject {
+                    size: 8,
+                    modified: 150,
+                },
+            ),
+        ]);
+        let filters = Filters {
+            exclude: &[],
+            include: &[],
+        };
+        assert_eq!(
+            plan(&local, &remote, &filters, false, false),
+            vec![
+                Transfer::Upload("index.html".to_string()),
+                Transfer::Upload("new.css".to_string()),
+                Transfer::Upload("old.png".to_string()),
+            ]
+        );
+        assert_eq!(plan(&local, &remote, &filters, false, true).len(), 4);
+    }
+
+    #[test]
+    fn test_plan_deletes_only_filtered_objects() {
+        let local = vec![file("index.html", 10, 100)];
+        let object = RemoteObject {
+            size: 1,
+            modified: 100,
+        };
+        let remote = HashMap::from([
+            ("index.html".to_string(), object.clone()),
+            ("gone.html".to_string(), object.clone()),
+            ("logs/access.log".to_string(), object),
+        ]);
+        let exclude = vec!["logs/*".to_string()];
+        let filters = Filters {
+            exclude: &exclude,
+            include: &[],
+        };
+        assert_eq!(
+            plan(&local, &remote, &filters, true, false),
+            vec![
+                Transfer::Upload("index.html".to_string()),
+                Transfer::Delete("gone.html".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_local_files_are_keyed_by_relative_path() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("assets/js")).unwrap();
+        fs::write(dir.path().join("index.html"), "<html></html>").unwrap();
+        fs::write(dir.path().join("assets/js/app.js"), "1").unwrap();
+
+        let files = local_files(dir.path()).unwrap();
+        let keys: Vec<&str> = files.iter().map(|f| f.key.as_str()).collect();
+        assert_eq!(keys, ["assets/js/app.js", "index.html"]);
+        assert_eq!(files[1].size, 13);
+    }
+}
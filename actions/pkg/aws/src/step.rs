@@ -0,0 +1,228 @@
+use crate::command::AwsCommand;
+use crate::error::AwsError;
+use crate::response::Invalidation;
+use std::time::Duration;
+
+/// How a step reacts to failures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StepPolicy {
+    /// Stop the chain if the step fails.
+    #[default]
+    FailFast,
+
+    /// Record the failure and continue with the next step.
+    ContinueOnError,
+
+    /// Run even after an earlier step stopped the chain, e.g. for cleanup.
+    AlwaysRun,
+}
+
+/// A command of a chain together with its failure policy.
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub command: AwsCommand,
+    pub policy: StepPolicy,
+    /// Human-readable name shown in the step records, e.g. `upload assets`.
+    pub name: Option<String>,
+}
+
+impl Step {
+    /// Creates an unnamed fail-fast step.
+    pub fn new(command: AwsCommand) -> Self {
+        Self {
+            command,
+            policy: StepPolicy::default(),
+            name: None,
+        }
+    }
+
+    pub fn with_policy(mut self, policy: StepPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+impl From<AwsCommand> for Step {
+    fn from(command: AwsCommand) -> Self {
+        Self::new(command)
+    }
+}
+
+/// The outcome of a single step.
+#[derive(Debug)]
+pub enum StepStatus {
+    /// The command exited with status `0`.
+    Succeeded,
+
+    /// The command exited with a non-zero status.
+    Failed(i32),
+
+    /// The command could not be run.
+    Error(AwsError),
+
+    /// The command was skipped because an earlier step stopped the chain.
+    Skipped,
+}
+
+/// The result of a step, in chain order.
+#[derive(Debug)]
+pub struct StepResult {
+    /// Position of the step in the chain.
+    pub index: usize,
+    /// The operation of the step, e.g. `lambda update-function-code`.
+    pub operation: String,
+    pub name: Option<String>,
+    pub policy: StepPolicy,
+    pub status: StepStatus,
+    /// Time the command ran, zero for skipped steps.
+    pub duration: Duration,
+    /// The invalidation created by a `CloudFrontInvalidate` step.
+    pub invalidation: Option<Invalidation>,
+}
+
+impl StepResult {
+    /// Returns the name of the step, or its operation if it has none.
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.operation)
+    }
+
+    /// Formats the finish record of the step, e.g.
+    /// `[step 1/3] upload assets (s3 sync): succeeded in 2.41s`.
+    pub fn record(&self, total: usize) -> String {
+        let title = step_title(self.name.as_deref(), &self.operation);
+        if matches!(self.status, StepStatus::Skipped) {
+            return format!("[step {}/{}] {}: skipped", self.index + 1, total, title);
+        }
+        format!(
+            "[step {}/{}] {}: {} in {:.2}s",
+            self.index + 1,
+            total,
+            title,
+            self.outcome(),
+            self.duration.as_secs_f64()
+        )
+    }
+
+    /// Describes the status of the step, e.g. `failed with status 254`.
+    pub fn outcome(&self) -> String {
+        match &self.status {
+            StepStatus::Succeeded => "succeeded".to_string(),
+            StepStatus::Failed(code) => format!("failed with status {}", code),
+            StepStatus::Error(e) => format!("errored ({})", e),
+            StepStatus::Skipped => "skipped".to_string(),
+        }
+    }
+
+    /// Returns `true` if the step failed or errored.
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status, StepStatus::Failed(_) | StepStatus::Error(_))
+    }
+
+    /// Returns the exit code of the step; errors count as `1`, skipped steps as `0`.
+    pub fn code(&self) -> i32 {
+        match self.status {
+            StepStatus::Failed(code) => code,
+            StepStatus::Error(_) => 1,
+            StepStatus::Succeeded | StepStatus::Skipped => 0,
+        }
+    }
+}
+
+/// Returns `name (operation)`, or the operation of an unnamed step.
+pub fn step_title(name: Option<&str>, operation: &str) -> String {
+    match name {
+        Some(name) => format!("{} ({})", name, operation),
+        None => operation.to_string(),
+    }
+}
+
+/// Returns the first failed step of a chain.
+///
+/// # Example
+///
+/// ```rust
+/// use aws::step::{first_failure, StepPolicy, StepResult, StepStatus};
+/// use std::time::Duration;
+///
+/// let results = vec![
+///     StepResult {
+///         index: 0,
+///         operation: "s3 sync".to_string(),
+///         name: Some("upload assets".to_string()),
+///         policy: StepPolicy::FailFast,
+///         status: StepStatus::Succeeded,
+///         duration: Duration::from_secs(3),
+///         invalidation: None,
+///     },
+///     StepResult {
+///         index: 1,
+///         operation: "cloudfront create-invalidation".to_string(),
+///         name: None,
+///         policy: StepPolicy::FailFast,
+///         status: StepStatus::Failed(254),
+///         duration: Duration::from_secs(1),
+///         invalidation: None,
+///     },
+/// ];
+/// assert_eq!(first_failure(&results).unwrap().index, 1);
+/// ```
+pub fn first_failure(results: &[StepResult]) -> Option<&StepResult> {
+    results.iter().find(|r| r.is_failure())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(status: StepStatus) -> StepResult {
+        StepResult {
+            index: 0,
+            operation: "s3 sync".to_string(),
+            name: None,
+            policy: StepPolicy::FailFast,
+            status,
+            duration: Duration::ZERO,
+            invalidation: None,
+        }
+    }
+
+    #[test]
+    fn test_step_result_code() {
+        assert_eq!(result(StepStatus::Succeeded).code(), 0);
+        assert_eq!(result(StepStatus::Skipped).code(), 0);
+        assert_eq!(result(StepStatus::Failed(3)).code(), 3);
+        assert_eq!(
+            result(StepStatus::Error(AwsError::CommandError("x".to_string()))).code(),
+            1
+        );
+        assert!(!result(StepStatus::Skipped).is_failure());
+    }
+
+    #[test]
+    fn test_step_result_label() {
+        let mut result = result(StepStatus::Succeeded);
+        assert_eq!(result.label(), "s3 sync");
+        result.name = Some("upload assets".to_string());
+        assert_eq!(result.label(), "upload assets");
+    }
+
+    #[test]
+    fn test_step_result_record() {
+        let mut result = result(StepStatus::Failed(2));
+        result.index = 1;
+        result.name = Some("pages".to_string());
+        result.duration = Duration::from_millis(1500);
+        assert_eq!(
+            result.record(3),
+            "[step 2/3] pages (s3 sync): failed with status 2 in 1.50s"
+        );
+
+        result.status = StepStatus::Skipped;
+        assert_eq!(result.record(3), "[step 2/3] pages (s3 sync): skipped");
+    }
+}
@@ -0,0 +1,200 @@
+//! End-to-end tests of the command chains against LocalStack.
+//!
+//! Run with a LocalStack instance listening on `LOCALSTACK_ENDPOINT`
+//! (default `http://localhost:4566`) and the AWS CLI on `PATH` or at
+//! `ACTION_AWS_BIN`:
+//!
+//! ```sh
+//! cargo test -p aws --features localstack
+//! ```
+#![cfg(feature = "localstack")]
+
+use aws::executor::AwsExecutor;
+use aws::{CommandChain, GlobalOptions, Sdk};
+use processor::ProcessorCollection;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const DEFAULT_ENDPOINT: &str = "http://localhost:4566";
+const REGION: &str = "us-east-1";
+
+fn endpoint() -> String {
+    env::var("LOCALSTACK_ENDPOINT").unwrap_or_else(|_| DEFAULT_ENDPOINT.to_string())
+}
+
+fn aws_bin() -> PathBuf {
+    env::var("ACTION_AWS_BIN")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("aws"))
+}
+
+fn globals() -> GlobalOptions {
+    GlobalOptions::new()
+        .with_region(REGION)
+        .with_endpoint_url(endpoint())
+}
+
+fn executor() -> AwsExecutor {
+    AwsExecutor::new(ProcessorCollection::new(vec![]), aws_bin()).with_global_options(globals())
+}
+
+/// Runs the AWS CLI directly, for fixtures and assertions.
+fn aws(args: &[&str]) -> String {
+    let output = Command::new(aws_bin())
+        .args(args)
+        .arg(format!("--region={}", REGION))
+        .arg(format!("--endpoint-url={}", endpoint()))
+        .output()
+        .expect("failed to run the AWS CLI");
+    assert!(
+        output.status.success(),
+        "aws {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn zip(dir: &Path, name: &str, source: &str) -> PathBuf {
+    fs::write(dir.join("index.py"), source).unwrap();
+    let status = Command::new("zip")
+        .arg("-q")
+        .arg(name)
+        .arg("index.py")
+        .current_dir(dir)
+        .status()
+        .expect("failed to run zip");
+    assert!(status.success());
+    dir.join(name)
+}
+
+#[tokio::test]
+async fn test_sync_chain() {
+    let bucket = "ci-actions-sync";
+    aws(&["s3", "mb", &format!("s3://{}", bucket)]);
+
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("dist/assets")).unwrap();
+    fs::write(dir.path().join("dist/index.html"), "<html></html>").unwrap();
+    fs::write(dir.path().join("dist/assets/app.js"), "console.log(1)").unwrap();
+
+    let chain = CommandChain::new(dir.path().to_path_buf())
+        .with_source(PathBuf::from("dist"))
+        .with_destination(PathBuf::from(format!("s3://{}", bucket)))
+        .with_overrides(vec![aws::SyncOverride::cache_control(
+            "assets/*",
+            "max-age=31536000",
+        )]);
+
+    let result = executor().execute_sync(chain.sync_chain()).await.unwrap();
+    assert_eq!(result.code, 0);
+    assert_eq!(result.summary.transferred, 2);
+
+    let head = aws(&[
+        "s3api",
+        "head-object",
+        &format!("--bucket={}", bucket),
+        "--key=assets/app.js",
+        "--query=CacheControl",
+        "--output=text",
+    ]);
+    assert_eq!(head.trim(), "max-age=31536000");
+}
+
+#[tokio::test]
+async fn test_sdk_sync_chain() {
+    let bucket = "ci-actions-sdk-sync";
+    aws(&["s3", "mb", &format!("s3://{}", bucket)]);
+    aws(&["s3", "cp", "-", &format!("s3://{}/stale.html", bucket)]);
+
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("dist/assets")).unwrap();
+    fs::write(dir.path().join("dist/index.html"), "<html></html>").unwrap();
+    fs::write(dir.path().join("dist/assets/app.js"), "console.log(1)").unwrap();
+    let chain = CommandChain::new(dir.path().to_path_buf())
+        .with_source(PathBuf::from("dist"))
+        .with_destination(PathBuf::from(format!("s3://{}", bucket)))
+        .with_delete(true);
+
+    let executor = executor().with_sdk(Sdk::load(&globals()).await);
+    let result = executor.execute_sync(chain.sync_chain()).await.unwrap();
+    assert_eq!(result.code, 0);
+    assert_eq!(result.summary.transferred, 2);
+    assert_eq!(result.summary.deleted, 1);
+
+    let head = aws(&[
+        "s3api",
+        "head-object",
+        &format!("--bucket={}", bucket),
+        "--key=index.html",
+        "--query=ContentType",
+        "--output=text",
+    ]);
+    assert_eq!(head.trim(), "text/html");
+
+    let again = executor.execute_sync(chain.sync_chain()).await.unwrap();
+    assert_eq!(again.summary.transferred, 0);
+}
+
+#[tokio::test]
+async fn test_lambda_update_zip_chain() {
+    let function = "ci-actions-lambda";
+    let dir = tempfile::tempdir().unwrap();
+    let initial = zip(
+        dir.path(),
+        "initial.zip",
+        "def handler(event, context):\n    return 1\n",
+    );
+    aws(&[
+        "lambda",
+        "create-function",
+        &format!("--function-name={}", function),
+        "--runtime=python3.12",
+        "--handler=index.handler",
+        "--role=arn:aws:iam::000000000000:role/lambda",
+        &format!("--zip-file=fileb://{}", initial.display()),
+    ]);
+    aws(&[
+        "lambda",
+        "wait",
+        "function-active-v2",
+        &format!("--function-name={}", function),
+    ]);
+
+    zip(
+        dir.path(),
+        "update.zip",
+        "def handler(event, context):\n    return 2\n",
+    );
+    let chain = CommandChain::new(dir.path().to_path_buf())
+        .with_lambda_function(function.to_string())
+        .with_lambda_zip(PathBuf::from("update.zip"))
+        .with_lambda_alias(Some("live".to_string()));
+    aws(&[
+        "lambda",
+        "create-alias",
+        &format!("--function-name={}", function),
+        "--name=live",
+        "--function-version=$LATEST",
+    ]);
+
+    let result = executor()
+        .execute_lambda(chain.lambda_update_zip_chain())
+        .await
+        .unwrap();
+    assert_eq!(result.code, 0);
+
+    let updated = result.function.unwrap();
+    assert_ne!(updated.version, "$LATEST");
+    let alias = aws(&[
+        "lambda",
+        "get-alias",
+        &format!("--function-name={}", function),
+        "--name=live",
+        "--query=FunctionVersion",
+        "--output=text",
+    ]);
+    assert_eq!(alias.trim(), updated.version);
+}
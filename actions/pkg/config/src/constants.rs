@@ -1,4 +1,4 @@
-use crate::validator::DirExists;
+use crate::validator::{DirExists, Number, OneOf};
 use crate::value::ConfigValue;
 
 use lazy_static::lazy_static;
@@ -7,18 +7,53 @@ use std::path::PathBuf;
 // ENV keys.
 pub const ENV_MASK: &str = "ACTION_MASK";
 pub const ENV_LOG_LEVEL: &str = "ACTION_LOG_LEVEL";
+pub const ENV_LOG_FORMAT: &str = "ACTION_LOG_FORMAT";
+pub const ENV_LOG_FILE: &str = "ACTION_LOG_FILE";
+pub const ENV_LOG_FILE_LEVEL: &str = "ACTION_LOG_FILE_LEVEL";
+pub const ENV_LOG_FILE_MAX_BYTES: &str = "ACTION_LOG_FILE_MAX_BYTES";
 pub const ENV_WORKING_DIR: &str = "ACTION_WORKING_DIR";
+pub const ENV_DRY_RUN: &str = "ACTION_DRY_RUN";
 
 // Default values.
 pub const DEFAULT_LOG_LEVEL: &str = "info";
+pub const DEFAULT_LOG_FORMAT: &str = "auto";
+pub const DEFAULT_LOG_FILE_LEVEL: &str = "debug";
+pub const DEFAULT_LOG_FILE_MAX_BYTES: u64 = 10 * 1024 * 1024;
 pub const DEFAULT_WORKING_DIR: &str = ".";
 pub const DEFAULT_MASK: &str = "*****";
 
+// Allowed values.
+pub const LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error", "critical"];
+pub const LOG_FORMATS: &[&str] = &["auto", "plain", "compact", "json", "github"];
+
 lazy_static! {
     pub static ref WORKING_DIR: ConfigValue<PathBuf> =
         ConfigValue::new(PathBuf::from(DEFAULT_WORKING_DIR), ENV_WORKING_DIR)
-            .with_validator(DirExists);
+            .expand()
+            .with_validator(DirExists)
+            .with_description("Directory the action runs in.");
     pub static ref LOG_LEVEL: ConfigValue<String> =
-        ConfigValue::new(DEFAULT_LOG_LEVEL.to_string(), ENV_LOG_LEVEL);
-    pub static ref MASK: ConfigValue<String> = ConfigValue::new(DEFAULT_MASK.to_string(), ENV_MASK);
+        ConfigValue::new(DEFAULT_LOG_LEVEL.to_string(), ENV_LOG_LEVEL)
+            .with_validator(OneOf::new(LOG_LEVELS).ignore_case())
+            .with_description("Log level: trace, debug, info, warn, error or critical.");
+    pub static ref LOG_FORMAT: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_LOG_FORMAT.to_string(), ENV_LOG_FORMAT)
+            .with_validator(OneOf::new(LOG_FORMATS).ignore_case())
+            .with_description(
+                "Log format: plain, compact, json or github; auto uses github in GitHub Actions and compact elsewhere.",
+            );
+    pub static ref LOG_FILE: ConfigValue<String> = ConfigValue::new(String::new(), ENV_LOG_FILE)
+        .with_description("File the log is also written to, e.g. to upload it as an artifact.");
+    pub static ref LOG_FILE_LEVEL: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_LOG_FILE_LEVEL.to_string(), ENV_LOG_FILE_LEVEL)
+            .with_validator(OneOf::new(LOG_LEVELS).ignore_case())
+            .with_description("Log level of the log file.");
+    pub static ref LOG_FILE_MAX_BYTES: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_LOG_FILE_MAX_BYTES.to_string(), ENV_LOG_FILE_MAX_BYTES)
+            .with_validator(Number::<u64>::new("a size in bytes"))
+            .with_description("Size in bytes at which the log file is rotated.");
+    pub static ref MASK: ConfigValue<String> = ConfigValue::new(DEFAULT_MASK.to_string(), ENV_MASK)
+        .with_description("Replacement for masked values in the output.");
+    pub static ref DRY_RUN: ConfigValue<bool> = ConfigValue::new(false, ENV_DRY_RUN)
+        .with_description("Preview the run without changing anything: terraform only plans, AWS commands are printed or run with --dryrun.");
 }
@@ -4,11 +4,103 @@ use std::fmt;
 #[derive(Debug, Clone)]
 pub struct Required;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ConfigError {
     RequiredValueMissing(String),
     EnvVarMissing(String),
-    InvalidValue(String),
+    InvalidValue(InvalidValue),
+    ReadFailed { key: String, message: String },
+    GroupConstraint(String),
+    Multiple(Vec<ConfigError>),
+}
+
+/// Describes a value that failed parsing or validation.
+///
+/// Validators create it without a key; `ConfigValue` fills in its key and
+/// masks the offending value when the setting is sensitive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidValue {
+    /// The key of the offending setting, if known.
+    pub key: Option<String>,
+    /// The offending value, masked if the setting is sensitive.
+    pub value: String,
+    /// A short description of the accepted format.
+    pub expected: String,
+    /// An optional suggestion on how to fix the value.
+    pub hint: Option<String>,
+}
+
+impl InvalidValue {
+    /// Creates a description of an invalid `value`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::InvalidValue;
+    ///
+    /// let err = InvalidValue::new("verbose", "one of: debug, info")
+    ///     .with_key("ACTION_LOG_LEVEL")
+    ///     .with_hint("levels are case-insensitive");
+    ///
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "Invalid value for ACTION_LOG_LEVEL: 'verbose', expected one of: debug, info \
+    ///      (hint: levels are case-insensitive)"
+    /// );
+    /// ```
+    pub fn new(value: impl fmt::Display, expected: impl Into<String>) -> Self {
+        Self {
+            key: None,
+            value: value.to_string(),
+            expected: expected.into(),
+            hint: None,
+        }
+    }
+
+    /// Sets the key of the offending setting.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Adds a suggestion on how to fix the value.
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    /// Replaces the offending value with `mask`.
+    pub fn masked(mut self, mask: &str) -> Self {
+        self.value = mask.to_string();
+        self
+    }
+}
+
+impl fmt::Display for InvalidValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.key {
+            Some(key) => write!(
+                f,
+                "Invalid value for {}: '{}', expected {}",
+                key, self.value, self.expected
+            )?,
+            None => write!(
+                f,
+                "Invalid value '{}', expected {}",
+                self.value, self.expected
+            )?,
+        }
+        if let Some(hint) = &self.hint {
+            write!(f, " (hint: {})", hint)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<InvalidValue> for ConfigError {
+    fn from(value: InvalidValue) -> Self {
+        ConfigError::InvalidValue(value)
+    }
 }
 
 impl fmt::Display for ConfigError {
@@ -20,7 +112,21 @@ impl fmt::Display for ConfigError {
             ConfigError::EnvVarMissing(var) => {
                 write!(f, "Required environment variable missing: {}", var)
             }
-            ConfigError::InvalidValue(msg) => write!(f, "Invalid value: {}", msg),
+            ConfigError::InvalidValue(invalid) => write!(f, "{}", invalid),
+            ConfigError::ReadFailed { key, message } => {
+                write!(f, "Cannot read value for {}: {}", key, message)
+            }
+            ConfigError::GroupConstraint(msg) => {
+                write!(f, "Invalid configuration combination: {}", msg)
+            }
+            ConfigError::Multiple(errors) => {
+                let noun = if errors.len() == 1 { "error" } else { "errors" };
+                write!(f, "{} configuration {}:", errors.len(), noun)?;
+                for error in errors {
+                    write!(f, "\n  - {}", error)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -28,3 +134,86 @@ impl fmt::Display for ConfigError {
 impl Error for ConfigError {}
 
 pub type ConfigResult<T> = Result<T, ConfigError>;
+
+impl ConfigError {
+    /// Shorthand for `ConfigError::InvalidValue(InvalidValue::new(value, expected))`.
+    pub fn invalid(value: impl fmt::Display, expected: impl Into<String>) -> Self {
+        InvalidValue::new(value, expected).into()
+    }
+
+    /// Attaches `key` to an `InvalidValue` error that has none yet, masking
+    /// the offending value with `mask` if given. Other errors are returned unchanged.
+    pub fn for_key(self, key: &str, mask: Option<&str>) -> Self {
+        match self {
+            ConfigError::InvalidValue(mut invalid) => {
+                if invalid.key.is_none() {
+                    invalid.key = Some(key.to_string());
+                }
+                if let Some(mask) = mask {
+                    invalid = invalid.masked(mask);
+                }
+                ConfigError::InvalidValue(invalid)
+            }
+            other => other,
+        }
+    }
+
+    /// Returns the individual errors, flattening `ConfigError::Multiple`.
+    pub fn into_errors(self) -> Vec<ConfigError> {
+        match self {
+            ConfigError::Multiple(errors) => {
+                errors.into_iter().flat_map(|e| e.into_errors()).collect()
+            }
+            other => vec![other],
+        }
+    }
+}
+
+/// Combines several results into one, reporting every failure at once.
+///
+/// # Example
+///
+/// ```rust
+/// use config::{collect_errors, ConfigError};
+///
+/// let result = collect_errors(vec![
+///     Ok(()),
+///     Err(ConfigError::RequiredValueMissing("A".to_string())),
+///     Err(ConfigError::RequiredValueMissing("B".to_string())),
+/// ]);
+///
+/// match result {
+///     Err(ConfigError::Multiple(errors)) => assert_eq!(errors.len(), 2),
+///     _ => panic!("Expected Multiple error"),
+/// }
+/// ```
+pub fn collect_errors(results: Vec<ConfigResult<()>>) -> ConfigResult<()> {
+    let errors: Vec<ConfigError> = results
+        .into_iter()
+        .filter_map(|r| r.err())
+        .flat_map(|e| e.into_errors())
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(ConfigError::Multiple(errors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multiple_is_pluralised_by_count() {
+        let missing = |key: &str| ConfigError::RequiredValueMissing(key.to_string());
+        assert_eq!(
+            ConfigError::Multiple(vec![missing("A")]).to_string(),
+            "1 configuration error:\n  - Required value missing for: A"
+        );
+        assert_eq!(
+            ConfigError::Multiple(vec![missing("A"), missing("B")]).to_string(),
+            "2 configuration errors:\n  - Required value missing for: A\n  - Required value missing for: B"
+        );
+    }
+}
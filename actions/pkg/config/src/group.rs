@@ -0,0 +1,191 @@
+use crate::{source, ConfigError, ConfigResult};
+
+/// Describes a constraint between several configuration keys.
+///
+/// A key counts as set when the source chain provides a non-empty value for it,
+/// which matches how optional values with an empty default behave.
+#[derive(Debug, Clone)]
+pub enum ConfigGroup {
+    /// At most one of the keys may be set.
+    MutuallyExclusive(Vec<&'static str>),
+
+    /// Either all of the keys are set or none of them are.
+    RequiredTogether(Vec<&'static str>),
+
+    /// If `key` is set, all of `requires` must be set as well.
+    Requires {
+        key: &'static str,
+        requires: Vec<&'static str>,
+    },
+}
+
+impl ConfigGroup {
+    /// Creates a constraint allowing at most one of the keys to be set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::ConfigGroup;
+    ///
+    /// let group = ConfigGroup::mutually_exclusive(&["LAMBDA_ZIP", "LAMBDA_IMAGE"]);
+    /// ```
+    pub fn mutually_exclusive(keys: &[&'static str]) -> Self {
+        Self::MutuallyExclusive(keys.to_vec())
+    }
+
+    /// Creates a constraint requiring the keys to be set all together or not at all.
+    pub fn required_together(keys: &[&'static str]) -> Self {
+        Self::RequiredTogether(keys.to_vec())
+    }
+
+    /// Creates a constraint requiring `requires` whenever `key` is set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::ConfigGroup;
+    ///
+    /// let group = ConfigGroup::requires("CLOUDFRONT_DISTRIBUTION", &["CLOUDFRONT_PATHS"]);
+    /// ```
+    pub fn requires(key: &'static str, requires: &[&'static str]) -> Self {
+        Self::Requires {
+            key,
+            requires: requires.to_vec(),
+        }
+    }
+
+    /// Checks the constraint against the current environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::GroupConstraint` describing the offending keys.
+    pub fn validate(&self) -> ConfigResult<()> {
+        match self {
+            Self::MutuallyExclusive(keys) => {
+                let set = set_keys(keys);
+                if set.len() > 1 {
+                    return Err(ConfigError::GroupConstraint(format!(
+                        "{} are mutually exclusive",
+                        set.join(", ")
+                    )));
+                }
+            }
+            Self::RequiredTogether(keys) => {
+                let set = set_keys(keys);
+                if !set.is_empty() && set.len() != keys.len() {
+                    let missing: Vec<&str> =
+                        keys.iter().filter(|k| !set.contains(k)).copied().collect();
+                    return Err(ConfigError::GroupConstraint(format!(
+                        "{} must be set together, missing: {}",
+                        keys.join(", "),
+                        missing.join(", ")
+                    )));
+                }
+            }
+            Self::Requires { key, requires } => {
+                if is_set(key) {
+                    let missing: Vec<&str> =
+                        requires.iter().filter(|k| !is_set(k)).copied().collect();
+                    if !missing.is_empty() {
+                        return Err(ConfigError::GroupConstraint(format!(
+                            "{} requires: {}",
+                            key,
+                            missing.join(", ")
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks every constraint, stopping at the first violation.
+pub fn validate_groups(groups: &[ConfigGroup]) -> ConfigResult<()> {
+    for group in groups {
+        group.validate()?;
+    }
+    Ok(())
+}
+
+fn is_set(key: &str) -> bool {
+    matches!(source::lookup(key), Ok(Some((_, v))) if !v.is_empty())
+}
+
+fn set_keys<'a>(keys: &'a [&'static str]) -> Vec<&'a str> {
+    keys.iter().filter(|k| is_set(k)).copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_mutually_exclusive() {
+        env::set_var("GROUP_EXCL_A", "a");
+        env::remove_var("GROUP_EXCL_B");
+        let group = ConfigGroup::mutually_exclusive(&["GROUP_EXCL_A", "GROUP_EXCL_B"]);
+        assert!(group.validate().is_ok());
+
+        env::set_var("GROUP_EXCL_B", "b");
+        assert!(matches!(
+            group.validate(),
+            Err(ConfigError::GroupConstraint(_))
+        ));
+    }
+
+    #[test]
+    fn test_mutually_exclusive_ignores_empty() {
+        env::set_var("GROUP_EMPTY_A", "a");
+        env::set_var("GROUP_EMPTY_B", "");
+        let group = ConfigGroup::mutually_exclusive(&["GROUP_EMPTY_A", "GROUP_EMPTY_B"]);
+        assert!(group.validate().is_ok());
+    }
+
+    #[test]
+    fn test_required_together() {
+        env::remove_var("GROUP_TOGETHER_A");
+        env::remove_var("GROUP_TOGETHER_B");
+        let group = ConfigGroup::required_together(&["GROUP_TOGETHER_A", "GROUP_TOGETHER_B"]);
+        assert!(group.validate().is_ok());
+
+        env::set_var("GROUP_TOGETHER_A", "a");
+        match group.validate() {
+            Err(ConfigError::GroupConstraint(msg)) => assert!(msg.contains("GROUP_TOGETHER_B")),
+            _ => panic!("Expected GroupConstraint error"),
+        }
+
+        env::set_var("GROUP_TOGETHER_B", "b");
+        assert!(group.validate().is_ok());
+    }
+
+    #[test]
+    fn test_requires() {
+        env::remove_var("GROUP_REQ_KEY");
+        env::remove_var("GROUP_REQ_DEP");
+        let group = ConfigGroup::requires("GROUP_REQ_KEY", &["GROUP_REQ_DEP"]);
+        assert!(group.validate().is_ok());
+
+        env::set_var("GROUP_REQ_DEP", "dep");
+        assert!(group.validate().is_ok());
+
+        env::remove_var("GROUP_REQ_DEP");
+        env::set_var("GROUP_REQ_KEY", "key");
+        assert!(matches!(
+            group.validate(),
+            Err(ConfigError::GroupConstraint(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_groups() {
+        env::set_var("GROUP_ALL_A", "a");
+        env::set_var("GROUP_ALL_B", "b");
+        let groups = vec![
+            ConfigGroup::required_together(&["GROUP_ALL_A", "GROUP_ALL_B"]),
+            ConfigGroup::mutually_exclusive(&["GROUP_ALL_A", "GROUP_ALL_B"]),
+        ];
+        assert!(validate_groups(&groups).is_err());
+    }
+}
@@ -1,36 +1,56 @@
 mod constants;
 mod error;
+mod group;
+mod macros;
+mod mask;
+mod parse;
+mod path;
+mod report;
+mod schema;
+mod source;
 mod validator;
 mod value;
+mod watch;
 
 pub use constants::*;
-pub use error::{ConfigError, ConfigResult, Required};
-pub use validator::{DirExists, FileExists};
-pub use value::ConfigValue;
+pub use error::{collect_errors, ConfigError, ConfigResult, InvalidValue, Required};
+pub use group::{validate_groups, ConfigGroup};
+pub use mask::sensitive_maskers;
+pub use parse::{non_empty, split_list, split_paths, Optional};
+pub use path::expand_path;
+pub use report::{ConfigReport, ReportEntry, ValueSource};
+pub use schema::{InputSchema, InputSpec};
+pub use source::{declare_key, set_source_chain, source_chain_maskers, KeyFileSource, SourceChain};
+pub use validator::{Canonicalize, DirExists, FileExists, Number, OneOf, Validator};
+pub use value::{ConfigValue, FILE_SUFFIX};
+pub use watch::{ConfigChange, ConfigWatcher, WatchHandle, Watchable};
 
 use std::path::PathBuf;
-pub struct MainConfig {}
 
-impl MainConfig {
-    pub fn new() -> Self {
-        Self {}
-    }
-
-    pub fn get_working_dir(&self) -> ConfigResult<PathBuf> {
-        WORKING_DIR.get()
-    }
-
-    pub fn get_log_level(&self) -> ConfigResult<String> {
-        LOG_LEVEL.get()
-    }
-
-    pub fn get_mask(&self) -> ConfigResult<String> {
-        MASK.get()
+config_struct! {
+    /// Represents the configuration shared by all actions.
+    pub struct MainConfig {
+        /// Gets the directory the action runs in.
+        get_working_dir: PathBuf = WORKING_DIR,
+        /// Gets the log level.
+        get_log_level: String = LOG_LEVEL,
+        /// Gets the log format.
+        get_log_format: String = LOG_FORMAT,
+        /// Gets the file the log is also written to.
+        get_log_file: Option<PathBuf> = LOG_FILE => |s| (!s.is_empty()).then(|| PathBuf::from(s)),
+        /// Gets the log level of the log file.
+        get_log_file_level: String = LOG_FILE_LEVEL,
+        /// Gets the size at which the log file is rotated.
+        get_log_file_max_bytes: u64 = LOG_FILE_MAX_BYTES as u64,
+        /// Gets the replacement used for masked values.
+        get_mask: String = MASK,
     }
 }
 
-impl Default for MainConfig {
-    fn default() -> Self {
-        Self::new()
+config_struct! {
+    /// Represents the dry-run setting of the actions that can preview a run.
+    pub struct DryRunConfig {
+        /// Gets whether the run only previews its changes.
+        get_dry_run: bool = DRY_RUN,
     }
 }
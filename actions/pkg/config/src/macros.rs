@@ -0,0 +1,118 @@
+/// Generates a typed configuration struct from `ConfigValue` declarations.
+///
+/// Each field names a getter, its return type and the `ConfigValue` it reads
+/// from, optionally followed by `as Type` to parse a string value with
+/// [`ConfigValue::parse`](crate::ConfigValue::parse), and by `=> mapper` to
+/// transform the resolved value. Parse errors are returned by the getter.
+/// The key, default, validators and sensitivity stay on the `ConfigValue`
+/// declaration itself.
+///
+/// Besides one getter per field, the struct gets `new()`, `Default`,
+/// `validate_all()` (every getter evaluated, all failures reported at once),
+/// `report()` and `inputs()` (one entry per field, in declaration order).
+///
+/// # Example
+///
+/// ```rust
+/// use config::{config_struct, ConfigValue};
+/// use lazy_static::lazy_static;
+/// use std::env;
+/// use std::time::Duration;
+///
+/// lazy_static! {
+///     static ref NAME: ConfigValue<String> = ConfigValue::new("app".to_string(), "EXAMPLE_NAME");
+///     static ref TAGS: ConfigValue<String> = ConfigValue::new(String::new(), "EXAMPLE_TAGS");
+///     static ref DELAY: ConfigValue<String> = ConfigValue::new("500".to_string(), "EXAMPLE_DELAY_MS");
+/// }
+///
+/// config_struct! {
+///     /// Example configuration.
+///     pub struct ExampleConfig {
+///         /// Gets the name.
+///         get_name: String = NAME,
+///         /// Gets the tags.
+///         get_tags: Vec<String> = TAGS => |s| s.split(',').map(str::to_string).collect(),
+///         /// Gets the delay.
+///         get_delay: Duration = DELAY as u64 => Duration::from_millis,
+///     }
+/// }
+///
+/// env::set_var("EXAMPLE_TAGS", "a,b");
+/// let config = ExampleConfig::new();
+/// assert_eq!(config.get_name().unwrap(), "app");
+/// assert_eq!(config.get_tags().unwrap(), vec!["a", "b"]);
+/// assert_eq!(config.get_delay().unwrap(), Duration::from_millis(500));
+/// assert!(config.validate_all().is_ok());
+/// assert_eq!(config.report().entries().len(), 3);
+/// assert_eq!(config.inputs().inputs().len(), 3);
+///
+/// env::set_var("EXAMPLE_DELAY_MS", "soon");
+/// DELAY.refresh();
+/// assert!(config.get_delay().is_err());
+/// ```
+#[macro_export]
+macro_rules! config_struct {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $getter:ident: $ty:ty = $value:path $(as $parse:ty)? $(=> $map:expr)?
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {}
+
+        impl $name {
+            /// Creates a new configuration instance.
+            pub fn new() -> Self {
+                Self {}
+            }
+
+            $(
+                $(#[$field_meta])*
+                pub fn $getter(&self) -> $crate::ConfigResult<$ty> {
+                    $crate::config_struct!(@get $value $(as $parse)? $(, $map)?)
+                }
+            )*
+
+            /// Evaluates every configuration value and reports all failures at once.
+            pub fn validate_all(&self) -> $crate::ConfigResult<()> {
+                $crate::collect_errors(vec![$(self.$getter().map(drop)),*])
+            }
+
+            /// Describes all resolved configuration values.
+            pub fn report(&self) -> $crate::ConfigReport {
+                $crate::ConfigReport::new()$(.with_entry($value.report_entry()))*
+            }
+
+            /// Describes all configuration values as action inputs.
+            pub fn inputs(&self) -> $crate::InputSchema {
+                $crate::InputSchema::new()$(.with_input($value.input_spec()))*
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+
+    (@get $value:path) => {
+        $value.get()
+    };
+
+    (@get $value:path, $map:expr) => {
+        $value.get().map($map)
+    };
+
+    (@get $value:path as $parse:ty) => {
+        $value.parse::<$parse>()
+    };
+
+    (@get $value:path as $parse:ty, $map:expr) => {
+        $value.parse::<$parse>().map($map)
+    };
+}
@@ -0,0 +1,90 @@
+use processor::{MaskerEqual, ProcessorItem};
+use shared::source::{RawValue, Source, SourceResult};
+use std::collections::BTreeSet;
+
+/// Returns a masker over the values of the sensitive keys a source provides.
+///
+/// List values are masked as a whole and item by item. Returns no masker
+/// when the source provides no sensitive value.
+///
+/// # Errors
+///
+/// Returns a `SourceError` if the source cannot be loaded.
+///
+/// # Example
+///
+/// ```rust
+/// use config::sensitive_maskers;
+/// use processor::{Processor, ProcessorCollection};
+/// use shared::source::EnvSource;
+///
+/// std::env::set_var("MASK_EXAMPLE_TOKEN", "s3cr3t");
+/// let source = EnvSource::new()
+///     .with_prefix("MASK_EXAMPLE_")
+///     .with_sensitive_keys(&["TOKEN"]);
+///
+/// let processor = ProcessorCollection::new(sensitive_maskers(&source, "****").unwrap());
+/// assert_eq!(processor.process("token s3cr3t"), "token ****");
+/// ```
+pub fn sensitive_maskers(
+    source: &(impl Source + ?Sized),
+    mask: &str,
+) -> SourceResult<Vec<ProcessorItem>> {
+    let mut values = BTreeSet::new();
+    for (key, value) in source.load()? {
+        if source.is_sensitive(&key) {
+            collect(&value, &mut values);
+        }
+    }
+    if values.is_empty() {
+        return Ok(Vec::new());
+    }
+    // Longer values first, so a value containing another is masked whole.
+    let mut values: Vec<String> = values.into_iter().collect();
+    values.sort_by_key(|v| std::cmp::Reverse(v.len()));
+    Ok(vec![ProcessorItem::Equal(MaskerEqual::new(
+        values.iter().map(String::as_str).collect(),
+        mask,
+    ))])
+}
+
+fn collect(value: &RawValue, out: &mut BTreeSet<String>) {
+    if let RawValue::List(items) = value {
+        for item in items {
+            collect(item, out);
+        }
+    }
+    let value = value.to_string();
+    if !value.is_empty() {
+        out.insert(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use processor::{Processor, ProcessorCollection};
+    use shared::source::{FileFormat, FileSource};
+
+    #[test]
+    fn test_sensitive_maskers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(
+            &path,
+            r#"{"token": "abc", "keys": ["k1", "k2"], "region": "eu-west-1", "empty": ""}"#,
+        )
+        .unwrap();
+        let source = FileSource::new(&path, FileFormat::Json)
+            .with_sensitive_keys(&["token", "keys", "empty"]);
+
+        let processor = ProcessorCollection::new(sensitive_maskers(&source, "***").unwrap());
+        assert_eq!(
+            processor.process("abc k1,k2 k2 eu-west-1"),
+            "*** *** *** eu-west-1"
+        );
+
+        let plain = FileSource::new(&path, FileFormat::Json);
+        assert!(sensitive_maskers(&plain, "***").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+//! Mappers for the getters of [`crate::config_struct!`].
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Splits a comma-separated list, skipping empty entries.
+///
+/// # Example
+///
+/// ```rust
+/// use config::split_list;
+///
+/// assert_eq!(
+///     split_list("deployment/api, ,statefulset/db".to_string()),
+///     vec!["deployment/api", "statefulset/db"]
+/// );
+/// assert!(split_list(String::new()).is_empty());
+/// ```
+pub fn split_list(s: String) -> Vec<String> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Splits a comma-separated list of paths, skipping empty entries.
+pub fn split_paths(s: String) -> Vec<PathBuf> {
+    split_list(s).into_iter().map(PathBuf::from).collect()
+}
+
+/// Returns `None` for an empty string.
+pub fn non_empty(s: String) -> Option<String> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+/// A value that may be left empty, parsed with
+/// [`ConfigValue::parse`](crate::ConfigValue::parse); an empty string is `None`.
+///
+/// # Example
+///
+/// ```rust
+/// use config::{ConfigValue, Optional};
+///
+/// let revision = ConfigValue::new(String::new(), "OPTIONAL_REVISION");
+/// assert_eq!(revision.parse::<Optional<u32>>().unwrap().into_inner(), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Optional<T>(pub Option<T>);
+
+impl<T> Optional<T> {
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T: FromStr> FromStr for Optional<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Self(None));
+        }
+        s.parse().map(|value| Self(Some(value)))
+    }
+}
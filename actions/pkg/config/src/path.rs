@@ -0,0 +1,164 @@
+use crate::{ConfigResult, InvalidValue};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Describes how a `PathBuf` value is normalized after it is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Normalize {
+    /// The value is used as is.
+    None,
+
+    /// `~` and environment references are expanded.
+    Expand,
+
+    /// Expanded, then resolved against the configured working directory.
+    WorkingDir,
+}
+
+/// Expands a leading `~` and `$VAR` / `${VAR}` references in a path.
+///
+/// # Errors
+///
+/// Returns `ConfigError::InvalidValue` if a referenced variable (or `HOME`
+/// for `~`) is not set, or if a `${` reference is not closed.
+///
+/// # Example
+///
+/// ```rust
+/// use config::expand_path;
+/// use std::env;
+/// use std::path::PathBuf;
+///
+/// env::set_var("EXPAND_EXAMPLE_DIR", "/builds");
+/// let path = expand_path("${EXPAND_EXAMPLE_DIR}/lambda.zip").unwrap();
+/// assert_eq!(path, PathBuf::from("/builds/lambda.zip"));
+/// ```
+pub fn expand_path(raw: &str) -> ConfigResult<PathBuf> {
+    let original = raw;
+    let raw = match raw.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            format!("{}{}", var(raw, "HOME")?, rest)
+        }
+        _ => raw.to_string(),
+    };
+
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+        let name = if chars.peek() == Some(&'{') {
+            chars.next();
+            let mut name = String::new();
+            let mut closed = false;
+            for c in chars.by_ref() {
+                if c == '}' {
+                    closed = true;
+                    break;
+                }
+                name.push(c);
+            }
+            if !closed {
+                return Err(
+                    InvalidValue::new(original, "a path with closed ${...} references")
+                        .with_hint(format!("add '}}' after '${{{}'", name))
+                        .into(),
+                );
+            }
+            name
+        } else {
+            let mut name = String::new();
+            while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                name.push(c);
+            }
+            name
+        };
+        if name.is_empty() {
+            out.push('$');
+        } else {
+            out.push_str(&var(original, &name)?);
+        }
+    }
+    Ok(PathBuf::from(out))
+}
+
+/// Joins a relative `path` onto `base`; absolute paths are returned unchanged.
+pub(crate) fn resolve_against(path: PathBuf, base: &Path) -> PathBuf {
+    if path.is_relative() {
+        base.join(path)
+    } else {
+        path
+    }
+}
+
+fn var(raw: &str, name: &str) -> ConfigResult<String> {
+    env::var(name).map_err(|_| {
+        InvalidValue::new(raw, "a path referencing only set variables")
+            .with_hint(format!("{} is not set", name))
+            .into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConfigError;
+
+    #[test]
+    fn test_expand_home() {
+        let home = PathBuf::from(env::var("HOME").unwrap());
+        assert_eq!(
+            expand_path("~/lambda.zip").unwrap(),
+            home.join("lambda.zip")
+        );
+        assert_eq!(expand_path("~").unwrap(), home);
+        assert_eq!(expand_path("~user/x").unwrap(), PathBuf::from("~user/x"));
+    }
+
+    #[test]
+    fn test_expand_vars() {
+        env::set_var("PATH_EXPAND_ROOT", "/srv");
+        env::set_var("PATH_EXPAND_NAME", "app");
+        assert_eq!(
+            expand_path("$PATH_EXPAND_ROOT/${PATH_EXPAND_NAME}.zip").unwrap(),
+            PathBuf::from("/srv/app.zip")
+        );
+    }
+
+    #[test]
+    fn test_expand_keeps_lone_dollar() {
+        assert_eq!(expand_path("a$/b").unwrap(), PathBuf::from("a$/b"));
+    }
+
+    #[test]
+    fn test_expand_unset_var() {
+        env::remove_var("PATH_EXPAND_UNSET");
+        match expand_path("$PATH_EXPAND_UNSET/file") {
+            Err(ConfigError::InvalidValue(invalid)) => {
+                assert_eq!(invalid.value, "$PATH_EXPAND_UNSET/file");
+                assert!(invalid.hint.unwrap().contains("PATH_EXPAND_UNSET"));
+            }
+            _ => panic!("Expected InvalidValue error"),
+        }
+    }
+
+    #[test]
+    fn test_expand_unclosed() {
+        assert!(expand_path("${PATH_EXPAND_OPEN/file").is_err());
+    }
+
+    #[test]
+    fn test_resolve_against() {
+        let base = Path::new("/work");
+        assert_eq!(
+            resolve_against(PathBuf::from("dist/app.zip"), base),
+            PathBuf::from("/work/dist/app.zip")
+        );
+        assert_eq!(
+            resolve_against(PathBuf::from("/abs/app.zip"), base),
+            PathBuf::from("/abs/app.zip")
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use serde_json::{json, Map, Value};
+
+/// Prefix stripped from keys to form action input names.
+const KEY_PREFIX: &str = "ACTION_";
+
+/// Describes a single configuration value as an action input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputSpec {
+    /// The environment variable key.
+    pub key: &'static str,
+    /// The value type: `string`, `boolean` or `path`.
+    pub value_type: &'static str,
+    /// The rendered default value, if any.
+    pub default: Option<String>,
+    /// Whether the value must be provided.
+    pub required: bool,
+    /// A human readable description.
+    pub description: &'static str,
+    /// Whether the value is a secret.
+    pub sensitive: bool,
+}
+
+impl InputSpec {
+    /// Returns the action input name, e.g. `aws_cmd` for `ACTION_AWS_CMD`.
+    pub fn input_name(&self) -> String {
+        self.key
+            .strip_prefix(KEY_PREFIX)
+            .unwrap_or(self.key)
+            .to_lowercase()
+    }
+
+    fn description(&self) -> String {
+        let mut description = self.description.to_string();
+        if self.sensitive {
+            if !description.is_empty() {
+                description.push(' ');
+            }
+            description.push_str("(sensitive, masked in logs)");
+        }
+        description
+    }
+}
+
+/// A collection of input specifications, rendered into `action.yml`
+/// fragments, Markdown documentation or a JSON schema.
+#[derive(Debug, Clone, Default)]
+pub struct InputSchema {
+    inputs: Vec<InputSpec>,
+}
+
+impl InputSchema {
+    /// Creates an empty schema.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an input to the schema.
+    pub fn with_input(mut self, input: InputSpec) -> Self {
+        self.inputs.push(input);
+        self
+    }
+
+    /// Appends all inputs of another schema.
+    pub fn extend(mut self, other: InputSchema) -> Self {
+        self.inputs.extend(other.inputs);
+        self
+    }
+
+    /// Returns the inputs.
+    pub fn inputs(&self) -> &[InputSpec] {
+        &self.inputs
+    }
+
+    /// Renders the `inputs:` section of an `action.yml`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::{InputSchema, InputSpec};
+    ///
+    /// let schema = InputSchema::new().with_input(InputSpec {
+    ///     key: "ACTION_LOG_LEVEL",
+    ///     value_type: "string",
+    ///     default: Some("info".to_string()),
+    ///     required: false,
+    ///     description: "Log level.",
+    ///     sensitive: false,
+    /// });
+    ///
+    /// assert_eq!(
+    ///     schema.render_action_inputs(),
+    ///     "inputs:\n  log_level:\n    description: \"Log level.\"\n    required: false\n    default: \"info\"\n"
+    /// );
+    /// ```
+    pub fn render_action_inputs(&self) -> String {
+        let mut out = String::from("inputs:\n");
+        for input in &self.inputs {
+            out.push_str(&format!("  {}:\n", input.input_name()));
+            out.push_str(&format!(
+                "    description: {}\n",
+                quote(&input.description())
+            ));
+            out.push_str(&format!("    required: {}\n", input.required));
+            if let Some(default) = input.default.as_deref().filter(|d| !d.is_empty()) {
+                out.push_str(&format!("    default: {}\n", quote(default)));
+            }
+        }
+        out
+    }
+
+    /// Renders the `env:` mapping passing every input to the action container.
+    pub fn render_action_env(&self) -> String {
+        let mut out = String::from("env:\n");
+        for input in &self.inputs {
+            out.push_str(&format!(
+                "  {}: ${{{{ inputs.{} }}}}\n",
+                input.key,
+                input.input_name()
+            ));
+        }
+        out
+    }
+
+    /// Renders a Markdown table documenting every input.
+    pub fn render_markdown(&self) -> String {
+        let mut out = String::from(
+            "| Input | Env | Type | Required | Default | Description |\n\
+             |-------|-----|------|----------|---------|-------------|\n",
+        );
+        for input in &self.inputs {
+            let default = match input.default.as_deref() {
+                Some(d) if !d.is_empty() => format!("`{}`", d),
+                _ => String::new(),
+            };
+            out.push_str(&format!(
+                "| `{}` | `{}` | {} | {} | {} | {} |\n",
+                input.input_name(),
+                input.key,
+                input.value_type,
+                if input.required { "yes" } else { "no" },
+                default,
+                input.description().replace('|', "\\|"),
+            ));
+        }
+        out
+    }
+
+    /// Renders a JSON schema (draft 07) describing the configuration keys.
+    pub fn render_json_schema(&self) -> String {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for input in &self.inputs {
+            let mut property = Map::new();
+            let json_type = if input.value_type == "boolean" {
+                "boolean"
+            } else {
+                "string"
+            };
+            property.insert("type".to_string(), json!(json_type));
+            property.insert("description".to_string(), json!(input.description()));
+            if let Some(default) = &input.default {
+                let default = match json_type {
+                    "boolean" => json!(default == "true"),
+                    _ => json!(default),
+                };
+                property.insert("default".to_string(), default);
+            }
+            if input.sensitive {
+                property.insert("writeOnly".to_string(), json!(true));
+            }
+            properties.insert(input.key.to_string(), Value::Object(property));
+            if input.required {
+                required.push(input.key);
+            }
+        }
+
+        let schema = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        });
+        serde_json::to_string_pretty(&schema).unwrap_or_default()
+    }
+}
+
+/// Quotes a value as a YAML double-quoted scalar.
+fn quote(value: &str) -> String {
+    Value::String(value.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(key: &'static str, value_type: &'static str, default: Option<&str>) -> InputSpec {
+        InputSpec {
+            key,
+            value_type,
+            default: default.map(|d| d.to_string()),
+            required: default.is_none(),
+            description: "Description.",
+            sensitive: false,
+        }
+    }
+
+    #[test]
+    fn test_input_name() {
+        assert_eq!(
+            input("ACTION_AWS_CMD", "string", None).input_name(),
+            "aws_cmd"
+        );
+        assert_eq!(input("OTHER_KEY", "string", None).input_name(), "other_key");
+    }
+
+    #[test]
+    fn test_render_action_inputs_required() {
+        let schema = InputSchema::new().with_input(input("ACTION_AWS_CMD", "string", None));
+        let rendered = schema.render_action_inputs();
+        assert!(rendered.contains("  aws_cmd:\n"));
+        assert!(rendered.contains("    required: true\n"));
+        assert!(!rendered.contains("default"));
+    }
+
+    #[test]
+    fn test_render_action_inputs_sensitive() {
+        let mut secret = input("ACTION_TOKEN", "string", Some(""));
+        secret.sensitive = true;
+        let rendered = InputSchema::new().with_input(secret).render_action_inputs();
+        assert!(rendered.contains("\"Description. (sensitive, masked in logs)\""));
+        assert!(!rendered.contains("default"));
+    }
+
+    #[test]
+    fn test_render_action_env() {
+        let schema = InputSchema::new().with_input(input("ACTION_AWS_CMD", "string", None));
+        assert_eq!(
+            schema.render_action_env(),
+            "env:\n  ACTION_AWS_CMD: ${{ inputs.aws_cmd }}\n"
+        );
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let schema =
+            InputSchema::new().with_input(input("ACTION_DRY_RUN", "boolean", Some("false")));
+        let rendered = schema.render_markdown();
+        assert!(rendered
+            .contains("| `dry_run` | `ACTION_DRY_RUN` | boolean | no | `false` | Description. |"));
+    }
+
+    #[test]
+    fn test_render_json_schema() {
+        let schema = InputSchema::new()
+            .with_input(input("ACTION_AWS_CMD", "string", None))
+            .with_input(input("ACTION_DRY_RUN", "boolean", Some("false")));
+        let value: Value = serde_json::from_str(&schema.render_json_schema()).unwrap();
+        assert_eq!(value["required"], json!(["ACTION_AWS_CMD"]));
+        assert_eq!(
+            value["properties"]["ACTION_DRY_RUN"]["default"],
+            json!(false)
+        );
+        assert_eq!(
+            value["properties"]["ACTION_DRY_RUN"]["type"],
+            json!("boolean")
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use crate::report::ValueSource;
+use crate::value::FILE_SUFFIX;
+use crate::{sensitive_maskers, ConfigError, ConfigResult};
+use lazy_static::lazy_static;
+use processor::ProcessorItem;
+use shared::source::{EnvSource, LayeredSource, RawValue, Source, SourceError, SourceResult};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::sync::RwLock;
+
+lazy_static! {
+    static ref SOURCES: RwLock<SourceChain> = RwLock::new(SourceChain::default());
+    static ref DECLARED: RwLock<BTreeSet<String>> = RwLock::new(BTreeSet::new());
+}
+
+/// An ordered list of sources consulted by every `ConfigValue`.
+///
+/// The first source providing a key wins; when none does, the value's default
+/// is used. The default chain reads the environment, then `<KEY>_FILE`.
+pub struct SourceChain {
+    layers: LayeredSource,
+    origins: HashMap<String, ValueSource>,
+}
+
+impl SourceChain {
+    /// Creates an empty chain.
+    pub fn new() -> Self {
+        Self {
+            layers: LayeredSource::new(),
+            origins: HashMap::new(),
+        }
+    }
+
+    /// Appends a source; values it provides are reported with `origin`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::{KeyFileSource, SourceChain, ValueSource};
+    /// use shared::source::EnvSource;
+    ///
+    /// let chain = SourceChain::new()
+    ///     .with_source(ValueSource::Env, EnvSource::new())
+    ///     .with_source(ValueSource::File, KeyFileSource);
+    /// ```
+    pub fn with_source(mut self, origin: ValueSource, source: impl Source + 'static) -> Self {
+        // Layers are named after their origin, so the origin is known from the layer.
+        let name = origin.to_string();
+        self.layers = self.layers.with_layer(name.clone(), source);
+        self.origins.insert(name, origin);
+        self
+    }
+
+    /// Returns the first value provided for `key` and where it came from.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::ReadFailed` if a source fails to read the key.
+    pub fn lookup(&self, key: &str) -> ConfigResult<Option<(ValueSource, String)>> {
+        let resolved = self
+            .layers
+            .resolve(key)
+            .map_err(|e| ConfigError::ReadFailed {
+                key: key.to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(resolved.map(|resolved| (self.origins[&resolved.layer], resolved.value.to_string())))
+    }
+
+    /// Returns maskers over the sensitive values of the chain.
+    ///
+    /// A key is sensitive if the source supplying it marks it; see
+    /// [`sensitive_maskers`](crate::sensitive_maskers).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SourceError` if a source cannot be loaded.
+    pub fn maskers(&self, mask: &str) -> SourceResult<Vec<ProcessorItem>> {
+        sensitive_maskers(&self.layers, mask)
+    }
+}
+
+impl Default for SourceChain {
+    fn default() -> Self {
+        Self::new()
+            .with_source(ValueSource::Env, EnvSource::new())
+            .with_source(ValueSource::File, KeyFileSource)
+    }
+}
+
+/// Replaces the chain used by all configuration values.
+///
+/// Values that were already resolved keep their cached result until
+/// `ConfigValue::refresh` is called.
+pub fn set_source_chain(chain: SourceChain) {
+    *SOURCES.write().unwrap_or_else(|e| e.into_inner()) = chain;
+}
+
+/// Returns maskers over the sensitive values of the configured chain.
+///
+/// # Errors
+///
+/// Returns a `SourceError` if a source cannot be loaded.
+pub fn source_chain_maskers(mask: &str) -> SourceResult<Vec<ProcessorItem>> {
+    SOURCES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .maskers(mask)
+}
+
+/// Declares a configuration key, so [`KeyFileSource`] resolves its `<KEY>_FILE`.
+///
+/// Every `ConfigValue` declares its key and aliases when it is first read.
+pub fn declare_key(key: &str) {
+    let mut declared = DECLARED.write().unwrap_or_else(|e| e.into_inner());
+    if !declared.contains(key) {
+        declared.insert(key.to_string());
+    }
+}
+
+fn is_declared(key: &str) -> bool {
+    DECLARED
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains(key)
+}
+
+/// Looks a key up in the configured chain.
+pub(crate) fn lookup(key: &str) -> ConfigResult<Option<(ValueSource, String)>> {
+    SOURCES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .lookup(key)
+}
+
+/// Reads the value of `KEY` from the file named by the `<KEY>_FILE` environment variable.
+///
+/// Only declared keys are resolved, see [`declare_key`]; other variables
+/// ending in `_FILE`, such as log paths, are left alone.
+///
+/// A single trailing newline is stripped, as most secret files end with one.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeyFileSource;
+
+impl KeyFileSource {
+    fn read(path: &str) -> SourceResult<String> {
+        let content = fs::read_to_string(path).map_err(|e| SourceError::Io {
+            path: path.into(),
+            message: e.to_string(),
+        })?;
+        Ok(content
+            .strip_suffix('\n')
+            .map(|c| c.strip_suffix('\r').unwrap_or(c))
+            .unwrap_or(&content)
+            .to_string())
+    }
+
+    /// Reads the values of `keys` that have a `<KEY>_FILE` set.
+    fn load_keys<'a>(
+        &self,
+        keys: impl IntoIterator<Item = &'a String>,
+    ) -> SourceResult<HashMap<String, RawValue>> {
+        let mut values = HashMap::new();
+        for key in keys {
+            if let Some(value) = self.read_key(key)? {
+                values.insert(key.clone(), value);
+            }
+        }
+        Ok(values)
+    }
+
+    fn read_key(&self, key: &str) -> SourceResult<Option<RawValue>> {
+        match EnvSource::new().get(&format!("{}{}", key, FILE_SUFFIX))? {
+            Some(path) => Ok(Some(Self::read(&path.to_string())?.into())),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Source for KeyFileSource {
+    fn name(&self) -> &str {
+        "key file"
+    }
+
+    fn load(&self) -> SourceResult<HashMap<String, RawValue>> {
+        let declared = DECLARED.read().unwrap_or_else(|e| e.into_inner()).clone();
+        self.load_keys(&declared)
+    }
+
+    fn get(&self, key: &str) -> SourceResult<Option<RawValue>> {
+        if !is_declared(key) {
+            return Ok(None);
+        }
+        self.read_key(key)
+    }
+
+    /// Every value read from a file is treated as a secret.
+    fn sensitive_keys(&self) -> &[String] {
+        &[]
+    }
+
+    fn is_sensitive(&self, _key: &str) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_chain_order() {
+        env::set_var("CHAIN_ORDER_KEY", "env");
+        let chain = SourceChain::new()
+            .with_source(ValueSource::Default, EnvSource::new().with_prefix("CHAIN_"))
+            .with_source(ValueSource::Env, EnvSource::new());
+
+        assert_eq!(
+            chain.lookup("ORDER_KEY").unwrap(),
+            Some((ValueSource::Default, "env".to_string()))
+        );
+        assert_eq!(
+            chain.lookup("CHAIN_ORDER_KEY").unwrap(),
+            Some((ValueSource::Env, "env".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_chain_empty() {
+        assert_eq!(SourceChain::new().lookup("CHAIN_EMPTY").unwrap(), None);
+    }
+
+    #[test]
+    fn test_chain_maskers() {
+        env::set_var("CHAIN_MASK_TOKEN", "t0ken");
+        env::set_var("CHAIN_MASK_REGION", "eu-west-1");
+        let chain = SourceChain::new().with_source(
+            ValueSource::Env,
+            EnvSource::new()
+                .with_prefix("CHAIN_MASK_")
+                .with_sensitive_keys(&["TOKEN"]),
+        );
+
+        let processor = processor::ProcessorCollection::new(chain.maskers("***").unwrap());
+        assert_eq!(
+            processor::Processor::process(&processor, "t0ken eu-west-1"),
+            "*** eu-west-1"
+        );
+    }
+
+    #[test]
+    fn test_key_file_source() {
+        let dir = tempdir().unwrap();
+        let secret = dir.path().join("secret");
+        fs::write(&secret, "value\r\n").unwrap();
+        env::set_var("KEY_FILE_SOURCE_FILE", &secret);
+        declare_key("KEY_FILE_SOURCE");
+
+        assert_eq!(
+            KeyFileSource.get("KEY_FILE_SOURCE").unwrap(),
+            Some(RawValue::from("value"))
+        );
+        assert!(KeyFileSource.is_sensitive("KEY_FILE_SOURCE"));
+    }
+
+    #[test]
+    fn test_key_file_source_unreadable() {
+        env::set_var("KEY_FILE_BROKEN_FILE", "/non/existent/secret");
+        declare_key("KEY_FILE_BROKEN");
+        assert!(matches!(
+            KeyFileSource.get("KEY_FILE_BROKEN"),
+            Err(SourceError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_key_file_source_skips_undeclared_keys() {
+        env::set_var("KEY_FILE_UNDECLARED_FILE", "/non/existent/secret");
+        assert_eq!(KeyFileSource.get("KEY_FILE_UNDECLARED").unwrap(), None);
+    }
+
+    #[test]
+    fn test_key_file_source_declared_key_ending_in_file() {
+        env::set_var("KEY_FILE_LOG_FILE", "/non/existent/debug.log");
+        let log_file = crate::ConfigValue::new(String::new(), "KEY_FILE_LOG_FILE");
+
+        assert_eq!(log_file.get().unwrap(), "/non/existent/debug.log");
+        assert_eq!(KeyFileSource.get("KEY_FILE_LOG").unwrap(), None);
+        let loaded = KeyFileSource
+            .load_keys(&["KEY_FILE_LOG_FILE".to_string()])
+            .unwrap();
+        assert!(loaded.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
-use crate::{ConfigError, ConfigResult};
+use crate::{ConfigError, ConfigResult, InvalidValue};
 use std::path::PathBuf;
+use std::str::FromStr;
 
 /// The `Validator` trait defines a common interface for validating configuration values.
 ///
@@ -48,10 +49,7 @@ pub struct FileExists;
 impl Validator<PathBuf> for FileExists {
     fn validate(&self, value: &PathBuf) -> ConfigResult<()> {
         if !value.is_file() {
-            return Err(ConfigError::InvalidValue(format!(
-                "File does not exist: {:?}",
-                value
-            )));
+            return Err(ConfigError::invalid(value.display(), "an existing file"));
         }
         Ok(())
     }
@@ -67,10 +65,10 @@ pub struct DirExists;
 impl Validator<PathBuf> for DirExists {
     fn validate(&self, value: &PathBuf) -> ConfigResult<()> {
         if !value.is_dir() {
-            return Err(ConfigError::InvalidValue(format!(
-                "Directory does not exist: {:?}",
-                value
-            )));
+            return Err(ConfigError::invalid(
+                value.display(),
+                "an existing directory",
+            ));
         }
         Ok(())
     }
@@ -80,6 +78,161 @@ impl Validator<PathBuf> for DirExists {
     }
 }
 
+/// Validates that a path can be canonicalized, i.e. it exists and every
+/// component (including symlinks) can be resolved.
+#[derive(Clone)]
+pub struct Canonicalize;
+
+impl Validator<PathBuf> for Canonicalize {
+    fn validate(&self, value: &PathBuf) -> ConfigResult<()> {
+        value.canonicalize().map(drop).map_err(|e| {
+            InvalidValue::new(value.display(), "a resolvable path")
+                .with_hint(e.to_string())
+                .into()
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn Validator<PathBuf>> {
+        Box::new(self.clone())
+    }
+}
+
+/// Validates that a string value is one of a fixed set of options.
+///
+/// The error message lists all valid options, so a misconfigured input
+/// tells the user exactly what is accepted.
+#[derive(Clone)]
+pub struct OneOf {
+    options: Vec<String>,
+    ignore_case: bool,
+}
+
+impl OneOf {
+    /// Creates a new `OneOf` validator with the given options.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::{ConfigValue, OneOf};
+    ///
+    /// let config_value = ConfigValue::new("info".to_string(), "LOG_LEVEL")
+    ///     .with_validator(OneOf::new(&["debug", "info", "error"]));
+    /// ```
+    pub fn new(options: &[&str]) -> Self {
+        Self {
+            options: options.iter().map(|o| o.to_string()).collect(),
+            ignore_case: false,
+        }
+    }
+
+    /// Makes the comparison case-insensitive.
+    pub fn ignore_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+}
+
+impl Validator<String> for OneOf {
+    fn validate(&self, value: &String) -> ConfigResult<()> {
+        let found = self.options.iter().any(|o| {
+            if self.ignore_case {
+                o.eq_ignore_ascii_case(value)
+            } else {
+                o == value
+            }
+        });
+        if !found {
+            return Err(ConfigError::invalid(
+                value,
+                format!("one of: {}", self.options.join(", ")),
+            ));
+        }
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Validator<String>> {
+        Box::new(self.clone())
+    }
+}
+
+/// Validates that a string value is an integer of type `T`, optionally
+/// within bounds.
+///
+/// `expected` describes the accepted values in the error message, e.g.
+/// "a number of retries".
+#[derive(Clone)]
+pub struct Number<T> {
+    expected: &'static str,
+    min: Option<T>,
+    max: Option<T>,
+    or_empty: bool,
+}
+
+impl<T> Number<T> {
+    /// Creates a validator accepting any value of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::{ConfigValue, Number};
+    ///
+    /// let config_value = ConfigValue::new("3".to_string(), "RETRY_ATTEMPTS")
+    ///     .with_validator(Number::<u32>::new("a number of retries"));
+    /// let value: u32 = config_value.parse().unwrap();
+    /// assert_eq!(value, 3);
+    /// ```
+    pub fn new(expected: &'static str) -> Self {
+        Self {
+            expected,
+            min: None,
+            max: None,
+            or_empty: false,
+        }
+    }
+
+    /// Rejects values below `min`.
+    pub fn at_least(mut self, min: T) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Rejects values above `max`.
+    pub fn at_most(mut self, max: T) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Also accepts an empty value, for settings that are off unless set.
+    pub fn or_empty(mut self) -> Self {
+        self.or_empty = true;
+        self
+    }
+}
+
+impl<T> Validator<String> for Number<T>
+where
+    T: FromStr + PartialOrd + Clone + Send + Sync + 'static,
+{
+    fn validate(&self, value: &String) -> ConfigResult<()> {
+        if self.or_empty && value.is_empty() {
+            return Ok(());
+        }
+        match value.parse::<T>() {
+            Ok(n)
+                if self.min.as_ref().is_none_or(|min| n >= *min)
+                    && self.max.as_ref().is_none_or(|max| n <= *max) =>
+            {
+                Ok(())
+            }
+            _ => Err(ConfigError::invalid(value, self.expected)),
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn Validator<String>> {
+        Box::new(self.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,13 +279,13 @@ mod tests {
     fn test_custom_validator_success() {
         let non_empty = |s: &String| -> ConfigResult<()> {
             if s.is_empty() {
-                Err(ConfigError::InvalidValue("String is empty".to_string()))
+                Err(ConfigError::invalid(s, "a non-empty string"))
             } else {
                 Ok(())
             }
         };
 
-        let validator: Box<dyn Validator<String>> = Box::new(non_empty.clone());
+        let validator: Box<dyn Validator<String>> = Box::new(non_empty);
         let result = validator.validate(&"valid".to_string());
         assert!(result.is_ok());
     }
@@ -141,17 +294,67 @@ mod tests {
     fn test_custom_validator_failure() {
         let non_empty = |s: &String| -> ConfigResult<()> {
             if s.is_empty() {
-                Err(ConfigError::InvalidValue("String is empty".to_string()))
+                Err(ConfigError::invalid(s, "a non-empty string"))
             } else {
                 Ok(())
             }
         };
 
-        let validator: Box<dyn Validator<String>> = Box::new(non_empty.clone());
+        let validator: Box<dyn Validator<String>> = Box::new(non_empty);
         let result = validator.validate(&"".to_string());
         assert!(matches!(result, Err(ConfigError::InvalidValue(_))));
     }
 
+    #[test]
+    fn test_one_of_success() {
+        let validator = OneOf::new(&["plan", "apply"]);
+        assert!(validator.validate(&"plan".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_one_of_failure_lists_options() {
+        let validator = OneOf::new(&["plan", "apply"]);
+        match validator.validate(&"destroy".to_string()) {
+            Err(ConfigError::InvalidValue(invalid)) => {
+                assert_eq!(invalid.value, "destroy");
+                assert_eq!(invalid.expected, "one of: plan, apply");
+            }
+            _ => panic!("Expected InvalidValue error"),
+        }
+    }
+
+    #[test]
+    fn test_one_of_ignore_case() {
+        let validator = OneOf::new(&["plan", "apply"]);
+        assert!(validator.validate(&"PLAN".to_string()).is_err());
+
+        let validator = validator.ignore_case();
+        assert!(validator.validate(&"PLAN".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_canonicalize_success() {
+        let dir = tempdir().unwrap();
+        let path = dir
+            .path()
+            .join(".")
+            .join("..")
+            .join(dir.path().file_name().unwrap());
+        assert!(Canonicalize.validate(&path).is_ok());
+    }
+
+    #[test]
+    fn test_canonicalize_failure() {
+        let path = PathBuf::from("/non/existent/../path");
+        match Canonicalize.validate(&path) {
+            Err(ConfigError::InvalidValue(invalid)) => {
+                assert_eq!(invalid.value, "/non/existent/../path");
+                assert!(invalid.hint.is_some());
+            }
+            _ => panic!("Expected InvalidValue error"),
+        }
+    }
+
     #[test]
     fn test_validator_clone() {
         let validator = DirExists.clone();
@@ -160,4 +363,27 @@ mod tests {
         let result = validator.validate(&path);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_number() {
+        let validator = Number::<u32>::new("a lifetime in seconds")
+            .at_least(1)
+            .at_most(3600);
+        assert!(validator.validate(&"60".to_string()).is_ok());
+        assert!(validator.validate(&"0".to_string()).is_err());
+        assert!(validator.validate(&"3601".to_string()).is_err());
+        assert!(validator.validate(&"-1".to_string()).is_err());
+        assert!(validator.validate(&String::new()).is_err());
+        assert!(matches!(
+            validator.validate(&"1m".to_string()),
+            Err(ConfigError::InvalidValue(e)) if e.to_string().contains("a lifetime in seconds")
+        ));
+    }
+
+    #[test]
+    fn test_number_or_empty() {
+        let validator = Number::<u8>::new("a revision").or_empty();
+        assert!(validator.validate(&String::new()).is_ok());
+        assert!(validator.validate(&"256".to_string()).is_err());
+    }
 }
@@ -1,10 +1,22 @@
-use crate::{validator::Validator, ConfigError, ConfigResult, Required};
-use std::{env, path::PathBuf};
+use crate::path::{expand_path, resolve_against, Normalize};
+use crate::report::{ReportEntry, ValueSource};
+use crate::schema::InputSpec;
+use crate::{source, validator::Validator, ConfigError, ConfigResult, InvalidValue, Required};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::{fmt, path::PathBuf, str::FromStr};
+
+/// Suffix of the companion key pointing to a file that holds the value.
+pub const FILE_SUFFIX: &str = "_FILE";
+
+/// The raw value found in the source chain and where it came from.
+type Lookup = Option<(ValueSource, String)>;
 
 /// Represents a configuration value that can be retrieved from an environment variable.
 /// It may have a default value and a set of validators to ensure the value meets
 /// certain criteria.
-#[derive(Clone)]
+///
+/// The value is resolved and validated once, on first access; call
+/// [`ConfigValue::refresh`] to force it to be read again.
 pub struct ConfigValue<T> {
     /// The default value if the environment variable is not set.
     default: Option<T>,
@@ -12,8 +24,210 @@ pub struct ConfigValue<T> {
     /// The key of the environment variable.
     env_key: &'static str,
 
+    /// Deprecated keys still honored when the primary key is not set.
+    aliases: Vec<&'static str>,
+
     /// A list of validators to validate the retrieved value.
     validators: Vec<Box<dyn Validator<T>>>,
+
+    /// Whether the value must be masked in reports and logs.
+    sensitive: bool,
+
+    /// A human readable description, used for generated documentation.
+    description: &'static str,
+
+    /// How `PathBuf` values are normalized before validation.
+    normalize: Normalize,
+
+    /// Cached result of reading the source chain.
+    raw: RwLock<Option<ConfigResult<Lookup>>>,
+
+    /// Cached result of the typed, validated value.
+    resolved: RwLock<Option<ConfigResult<T>>>,
+}
+
+impl<T: Clone> Clone for ConfigValue<T> {
+    /// Clones the declaration; the clone starts with an empty cache.
+    fn clone(&self) -> Self {
+        Self {
+            default: self.default.clone(),
+            env_key: self.env_key,
+            aliases: self.aliases.clone(),
+            validators: self.validators.clone(),
+            sensitive: self.sensitive,
+            description: self.description,
+            normalize: self.normalize,
+            raw: RwLock::new(None),
+            resolved: RwLock::new(None),
+        }
+    }
+}
+
+impl<T> ConfigValue<T> {
+    /// Returns the key of the environment variable.
+    pub fn env_key(&self) -> &'static str {
+        self.env_key
+    }
+
+    /// Returns `true` if the value was marked as sensitive.
+    pub fn is_sensitive(&self) -> bool {
+        self.sensitive
+    }
+
+    /// Describes the value for generated documentation and `action.yml` inputs.
+    pub fn with_description(mut self, description: &'static str) -> Self {
+        self.description = description;
+        self
+    }
+
+    /// Returns the description of the value.
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// Declares a legacy environment key that is still honored.
+    ///
+    /// Aliases are checked in declaration order after the primary key, and a
+    /// deprecation warning is printed whenever one of them supplies the value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::ConfigValue;
+    /// use std::env;
+    ///
+    /// let config_value = ConfigValue::new(".".to_string(), "NEW_WORKING_DIR")
+    ///     .with_alias("OLD_WORKING_DIR");
+    ///
+    /// env::set_var("OLD_WORKING_DIR", "/legacy");
+    /// assert_eq!(config_value.get().unwrap(), "/legacy");
+    /// ```
+    pub fn with_alias(mut self, alias: &'static str) -> Self {
+        self.aliases.push(alias);
+        self
+    }
+
+    /// Returns the declared legacy keys.
+    pub fn aliases(&self) -> &[&'static str] {
+        &self.aliases
+    }
+
+    /// Reads the raw value from the source chain.
+    ///
+    /// The primary key is checked first, then every alias. If a key is not set
+    /// but `<KEY>_FILE` is, the value is read from that file (Docker secrets
+    /// convention).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::ReadFailed` if a source cannot read the key.
+    fn lookup(&self) -> ConfigResult<Lookup> {
+        if let Some(cached) = read(&self.raw).as_ref() {
+            return cached.clone();
+        }
+        let result = self.read_sources();
+        *write(&self.raw) = Some(result.clone());
+        result
+    }
+
+    fn read_sources(&self) -> ConfigResult<Lookup> {
+        source::declare_key(self.env_key);
+        for alias in &self.aliases {
+            source::declare_key(alias);
+        }
+        if let Some(found) = source::lookup(self.env_key)? {
+            return Ok(Some(found));
+        }
+        for alias in &self.aliases {
+            if let Some(found) = source::lookup(alias)? {
+                eprintln!(
+                    "Warning: '{}' is deprecated and will be removed, use '{}' instead",
+                    alias, self.env_key
+                );
+                return Ok(Some(found));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Drops the cached value, so the next access reads the sources again.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::ConfigValue;
+    /// use std::env;
+    ///
+    /// let config_value = ConfigValue::new("default".to_string(), "REFRESHED_VAR");
+    /// env::remove_var("REFRESHED_VAR");
+    /// assert_eq!(config_value.get().unwrap(), "default");
+    ///
+    /// env::set_var("REFRESHED_VAR", "updated");
+    /// assert_eq!(config_value.get().unwrap(), "default");
+    ///
+    /// config_value.refresh();
+    /// assert_eq!(config_value.get().unwrap(), "updated");
+    /// ```
+    pub fn refresh(&self) {
+        *write(&self.raw) = None;
+        *write(&self.resolved) = None;
+    }
+
+    /// Returns the cached typed value, resolving it with `resolve` on first use.
+    fn cached<F>(&self, resolve: F) -> ConfigResult<T>
+    where
+        T: Clone,
+        F: FnOnce() -> ConfigResult<T>,
+    {
+        if let Some(cached) = read(&self.resolved).as_ref() {
+            return cached.clone();
+        }
+        let result = resolve().map_err(|e| self.with_context(e));
+        *write(&self.resolved) = Some(result.clone());
+        result
+    }
+
+    /// Attaches the key to an invalid value error and masks the offending
+    /// value if the setting is sensitive or was read from a file.
+    fn with_context(&self, err: ConfigError) -> ConfigError {
+        let from_file = matches!(
+            read(&self.raw).as_ref(),
+            Some(Ok(Some((ValueSource::File, _))))
+        );
+        let mask = (self.sensitive || from_file).then_some(crate::DEFAULT_MASK);
+        err.for_key(self.env_key, mask)
+    }
+
+    /// Builds a report entry from the environment value or the rendered default.
+    ///
+    /// Values read from a `<KEY>_FILE` are always reported as sensitive.
+    fn entry(&self, default: Option<String>) -> ReportEntry {
+        let (source, value) = match self.lookup() {
+            Ok(Some((source, val))) => (source, Some(val)),
+            Ok(None) | Err(_) => match default {
+                Some(default) => (ValueSource::Default, Some(default)),
+                None => (ValueSource::Missing, None),
+            },
+        };
+        ReportEntry {
+            key: self.env_key,
+            sensitive: self.sensitive || source == ValueSource::File,
+            source,
+            value,
+        }
+    }
+
+    /// Builds an input specification; values without a default are required.
+    fn spec(&self, value_type: &'static str, default: Option<String>) -> InputSpec {
+        InputSpec {
+            key: self.env_key,
+            value_type,
+            required: default.is_none(),
+            default,
+            description: self.description,
+            sensitive: self.sensitive,
+        }
+    }
 }
 
 impl<T: Clone> ConfigValue<T> {
@@ -36,10 +250,31 @@ impl<T: Clone> ConfigValue<T> {
         Self {
             default: Some(default),
             env_key,
+            aliases: Vec::new(),
             validators: Vec::new(),
+            sensitive: false,
+            description: "",
+            normalize: Normalize::None,
+            raw: RwLock::new(None),
+            resolved: RwLock::new(None),
         }
     }
 
+    /// Marks the value as sensitive, so it is masked in configuration reports.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::ConfigValue;
+    ///
+    /// let token = ConfigValue::new(String::new(), "API_TOKEN").sensitive();
+    /// assert!(token.is_sensitive());
+    /// ```
+    pub fn sensitive(mut self) -> Self {
+        self.sensitive = true;
+        self
+    }
+
     /// Attaches a validator to the `ConfigValue`.
     ///
     /// # Arguments
@@ -82,9 +317,25 @@ impl ConfigValue<Required> {
         ConfigValue {
             default: None,
             env_key,
+            aliases: Vec::new(),
             validators: Vec::new(),
+            sensitive: false,
+            description: "",
+            normalize: Normalize::None,
+            raw: RwLock::new(None),
+            resolved: RwLock::new(None),
         }
     }
+
+    /// Describes the resolved value for a configuration report.
+    pub fn report_entry(&self) -> ReportEntry {
+        self.entry(None)
+    }
+
+    /// Describes the value as an action input.
+    pub fn input_spec(&self) -> InputSpec {
+        self.spec("string", None)
+    }
 }
 
 impl ConfigValue<bool> {
@@ -109,14 +360,15 @@ impl ConfigValue<bool> {
     /// assert!(value);
     /// ```
     pub fn get(&self) -> ConfigResult<bool> {
-        let val = match env::var(self.env_key) {
-            Ok(val) => val.parse::<bool>().map_err(|_| {
-                ConfigError::InvalidValue(format!(
-                    "Cannot parse boolean value for: {}",
-                    self.env_key
-                ))
-            })?,
-            Err(_) => {
+        self.cached(|| self.resolve())
+    }
+
+    fn resolve(&self) -> ConfigResult<bool> {
+        let val = match self.lookup()? {
+            Some((_, val)) => val
+                .parse::<bool>()
+                .map_err(|_| ConfigError::invalid(&val, "a boolean (true or false)"))?,
+            None => {
                 if let Some(default) = &self.default {
                     *default
                 } else {
@@ -130,6 +382,16 @@ impl ConfigValue<bool> {
         }
         Ok(val)
     }
+
+    /// Describes the resolved value for a configuration report.
+    pub fn report_entry(&self) -> ReportEntry {
+        self.entry(self.default.map(|d| d.to_string()))
+    }
+
+    /// Describes the value as an action input.
+    pub fn input_spec(&self) -> InputSpec {
+        self.spec("boolean", self.default.map(|d| d.to_string()))
+    }
 }
 
 impl ConfigValue<String> {
@@ -156,9 +418,13 @@ impl ConfigValue<String> {
     /// assert_eq!(value, "default");
     /// ```
     pub fn get(&self) -> ConfigResult<String> {
-        let val = match env::var(self.env_key) {
-            Ok(val) => val,
-            Err(_) => {
+        self.cached(|| self.resolve())
+    }
+
+    fn resolve(&self) -> ConfigResult<String> {
+        let val = match self.lookup()? {
+            Some((_, val)) => val,
+            None => {
                 if let Some(default) = &self.default {
                     default.clone()
                 } else {
@@ -171,9 +437,88 @@ impl ConfigValue<String> {
         }
         Ok(val)
     }
+
+    /// Retrieves the `String` configuration value and parses it into `T`.
+    ///
+    /// Validators run against the raw string before parsing. This is
+    /// intended for enums implementing `FromStr`, so callers get exhaustive
+    /// typed matching instead of comparing strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::InvalidValue` if any validator fails or parsing fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::ConfigValue;
+    ///
+    /// let config_value = ConfigValue::new("42".to_string(), "MY_NUMBER");
+    /// let value: u32 = config_value.parse().unwrap();
+    /// assert_eq!(value, 42);
+    /// ```
+    pub fn parse<T>(&self) -> ConfigResult<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let val = self.get()?;
+        parse_value(&val).map_err(|e| self.with_context(e))
+    }
+
+    /// Describes the resolved value for a configuration report.
+    pub fn report_entry(&self) -> ReportEntry {
+        self.entry(self.default.clone())
+    }
+
+    /// Describes the value as an action input.
+    pub fn input_spec(&self) -> InputSpec {
+        self.spec("string", self.default.clone())
+    }
 }
 
 impl ConfigValue<PathBuf> {
+    /// Expands a leading `~` and `$VAR` / `${VAR}` references in the value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::ConfigValue;
+    /// use std::env;
+    /// use std::path::PathBuf;
+    ///
+    /// env::set_var("EXPAND_ROOT", "/builds");
+    /// env::set_var("EXPANDED_PATH", "$EXPAND_ROOT/app");
+    ///
+    /// let config_value = ConfigValue::new(PathBuf::new(), "EXPANDED_PATH").expand();
+    /// assert_eq!(config_value.get().unwrap(), PathBuf::from("/builds/app"));
+    /// ```
+    pub fn expand(mut self) -> Self {
+        self.normalize = Normalize::Expand;
+        self
+    }
+
+    /// Expands the value like [`ConfigValue::expand`] and resolves relative
+    /// paths against the configured working directory (`ACTION_WORKING_DIR`),
+    /// so the result does not depend on the runner's current directory.
+    ///
+    /// Must not be used on the working directory value itself.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::ConfigValue;
+    /// use std::path::PathBuf;
+    ///
+    /// let config_value =
+    ///     ConfigValue::new(PathBuf::from("dist/app.zip"), "RELATIVE_PATH").relative_to_working_dir();
+    /// assert!(config_value.get().unwrap().ends_with("dist/app.zip"));
+    /// ```
+    pub fn relative_to_working_dir(mut self) -> Self {
+        self.normalize = Normalize::WorkingDir;
+        self
+    }
+
     /// Retrieves the `PathBuf` configuration value.
     ///
     /// # Errors
@@ -204,9 +549,13 @@ impl ConfigValue<PathBuf> {
     /// assert_eq!(path, temp_path);
     /// ```
     pub fn get(&self) -> ConfigResult<PathBuf> {
-        let val = match env::var(self.env_key) {
-            Ok(path) => PathBuf::from(path),
-            Err(_) => {
+        self.cached(|| self.resolve())
+    }
+
+    fn resolve(&self) -> ConfigResult<PathBuf> {
+        let val = match self.lookup()? {
+            Some((_, path)) => PathBuf::from(path),
+            None => {
                 if let Some(default) = &self.default {
                     default.clone()
                 } else {
@@ -214,11 +563,39 @@ impl ConfigValue<PathBuf> {
                 }
             }
         };
+        let val = self.normalize_path(val)?;
         for validator in &self.validators {
             validator.validate(&val)?;
         }
         Ok(val)
     }
+
+    fn normalize_path(&self, path: PathBuf) -> ConfigResult<PathBuf> {
+        if self.normalize == Normalize::None || path.as_os_str().is_empty() {
+            return Ok(path);
+        }
+        let path = expand_path(&path.to_string_lossy())?;
+        if self.normalize == Normalize::WorkingDir {
+            return Ok(resolve_against(path, &crate::WORKING_DIR.get()?));
+        }
+        Ok(path)
+    }
+
+    /// Describes the resolved value for a configuration report.
+    pub fn report_entry(&self) -> ReportEntry {
+        self.entry(self.default_path())
+    }
+
+    /// Describes the value as an action input.
+    pub fn input_spec(&self) -> InputSpec {
+        self.spec("path", self.default_path())
+    }
+
+    fn default_path(&self) -> Option<String> {
+        self.default
+            .as_ref()
+            .map(|d| d.to_string_lossy().to_string())
+    }
 }
 
 impl ConfigValue<Required> {
@@ -246,20 +623,47 @@ impl ConfigValue<Required> {
     /// let parsed: i32 = required_value.get().unwrap();
     /// assert_eq!(parsed, 42);
     /// ```
-    pub fn get<T: std::str::FromStr>(&self) -> ConfigResult<T> {
-        let val_str = match env::var(self.env_key) {
-            Ok(val_str) => val_str,
-            Err(_) => {
+    pub fn get<T>(&self) -> ConfigResult<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let val_str = match self.lookup()? {
+            Some((_, val_str)) => val_str,
+            None => {
                 return Err(ConfigError::RequiredValueMissing(self.env_key.to_string()));
             }
         };
-        let parsed = val_str.parse::<T>().map_err(|_| {
-            ConfigError::InvalidValue(format!("Cannot parse value for: {}", self.env_key))
-        })?;
-        Ok(parsed)
+        parse_value(&val_str).map_err(|e| self.with_context(e))
     }
 }
 
+/// Locks a cache for reading, ignoring poisoning since cached values are always complete.
+fn read<V>(lock: &RwLock<V>) -> RwLockReadGuard<'_, V> {
+    lock.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Locks a cache for writing, ignoring poisoning since cached values are always complete.
+fn write<V>(lock: &RwLock<V>) -> RwLockWriteGuard<'_, V> {
+    lock.write().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Parses a raw configuration string into `T`, keeping the parser's own
+/// error message (e.g. the list of valid enum options) in the resulting error.
+fn parse_value<T>(raw: &str) -> ConfigResult<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    raw.parse::<T>().map_err(|e| {
+        let type_name = std::any::type_name::<T>();
+        let type_name = type_name.rsplit("::").next().unwrap_or(type_name);
+        InvalidValue::new(raw, format!("a valid {}", type_name))
+            .with_hint(e.to_string())
+            .into()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -334,6 +738,52 @@ mod tests {
         assert!(matches!(result, Err(ConfigError::InvalidValue(_))));
     }
 
+    #[derive(Debug, PartialEq)]
+    enum TestAction {
+        Plan,
+        Apply,
+    }
+
+    impl FromStr for TestAction {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "plan" => Ok(TestAction::Plan),
+                "apply" => Ok(TestAction::Apply),
+                _ => Err(format!("expected one of: plan, apply, got '{}'", s)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_required_get_enum() {
+        let config: ConfigValue<Required> = ConfigValue::required("REQUIRED_ENUM");
+        env::set_var("REQUIRED_ENUM", "apply");
+        let value: TestAction = config.get().unwrap();
+        assert_eq!(value, TestAction::Apply);
+    }
+
+    #[test]
+    fn test_required_get_enum_error_lists_options() {
+        let config: ConfigValue<Required> = ConfigValue::required("REQUIRED_ENUM_INVALID");
+        env::set_var("REQUIRED_ENUM_INVALID", "destroy");
+        match config.get::<TestAction>() {
+            Err(ConfigError::InvalidValue(invalid)) => {
+                assert!(invalid.hint.unwrap().contains("plan, apply"))
+            }
+            _ => panic!("Expected InvalidValue error"),
+        }
+    }
+
+    #[test]
+    fn test_string_parse_with_default() {
+        let config = ConfigValue::new("plan".to_string(), "TEST_STRING_PARSE");
+        env::remove_var("TEST_STRING_PARSE");
+        let value: TestAction = config.parse().unwrap();
+        assert_eq!(value, TestAction::Plan);
+    }
+
     #[test]
     fn test_with_validator_success() {
         let temp_dir = tempdir().unwrap();
@@ -358,7 +808,7 @@ mod tests {
         let config =
             ConfigValue::new("value".to_string(), "CLONE_TEST").with_validator(|v: &String| {
                 if v.is_empty() {
-                    Err(ConfigError::InvalidValue("Empty string".to_string()))
+                    Err(ConfigError::invalid(v, "a non-empty string"))
                 } else {
                     Ok(())
                 }
@@ -371,6 +821,217 @@ mod tests {
         assert_eq!(clone_val, "cloned_value");
     }
 
+    #[test]
+    fn test_pathbuf_expand() {
+        env::set_var("TEST_EXPAND_BASE", "/opt/builds");
+        env::set_var("TEST_EXPAND_PATH", "${TEST_EXPAND_BASE}/lambda.zip");
+        let config = ConfigValue::new(PathBuf::new(), "TEST_EXPAND_PATH");
+        assert_eq!(
+            config.get().unwrap(),
+            PathBuf::from("${TEST_EXPAND_BASE}/lambda.zip")
+        );
+
+        let config = ConfigValue::new(PathBuf::new(), "TEST_EXPAND_PATH").expand();
+        assert_eq!(
+            config.get().unwrap(),
+            PathBuf::from("/opt/builds/lambda.zip")
+        );
+    }
+
+    #[test]
+    fn test_pathbuf_relative_to_working_dir() {
+        let working_dir = crate::WORKING_DIR.get().unwrap();
+        env::set_var("TEST_RELATIVE_PATH", "dist/lambda.zip");
+        let config =
+            ConfigValue::new(PathBuf::new(), "TEST_RELATIVE_PATH").relative_to_working_dir();
+        assert_eq!(config.get().unwrap(), working_dir.join("dist/lambda.zip"));
+
+        env::set_var("TEST_RELATIVE_PATH", "/abs/lambda.zip");
+        config.refresh();
+        assert_eq!(config.get().unwrap(), PathBuf::from("/abs/lambda.zip"));
+    }
+
+    #[test]
+    fn test_pathbuf_normalize_skips_empty() {
+        env::remove_var("TEST_RELATIVE_EMPTY");
+        let config =
+            ConfigValue::new(PathBuf::new(), "TEST_RELATIVE_EMPTY").relative_to_working_dir();
+        assert_eq!(config.get().unwrap(), PathBuf::new());
+    }
+
+    #[test]
+    fn test_invalid_value_has_key() {
+        env::set_var("TEST_INVALID_KEY", "maybe");
+        let config = ConfigValue::new(false, "TEST_INVALID_KEY");
+        match config.get() {
+            Err(ConfigError::InvalidValue(invalid)) => {
+                assert_eq!(invalid.key.as_deref(), Some("TEST_INVALID_KEY"));
+                assert_eq!(invalid.value, "maybe");
+                assert_eq!(invalid.expected, "a boolean (true or false)");
+            }
+            _ => panic!("Expected InvalidValue error"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_value_masked_when_sensitive() {
+        env::set_var("TEST_INVALID_SECRET", "hunter2");
+        let config: ConfigValue<Required> =
+            ConfigValue::required("TEST_INVALID_SECRET").sensitive();
+        let err = config.get::<u16>().unwrap_err();
+        assert!(!err.to_string().contains("hunter2"));
+        match err {
+            ConfigError::InvalidValue(invalid) => {
+                assert_eq!(invalid.value, crate::DEFAULT_MASK);
+                assert_eq!(invalid.expected, "a valid u16");
+            }
+            _ => panic!("Expected InvalidValue error"),
+        }
+    }
+
+    #[test]
+    fn test_input_spec() {
+        let config = ConfigValue::new(true, "TEST_INPUT_SPEC").with_description("Enables it.");
+        let spec = config.input_spec();
+        assert_eq!(spec.value_type, "boolean");
+        assert_eq!(spec.default.as_deref(), Some("true"));
+        assert!(!spec.required);
+        assert_eq!(spec.description, "Enables it.");
+
+        let required: ConfigValue<Required> =
+            ConfigValue::required("TEST_INPUT_SPEC_REQUIRED").sensitive();
+        let spec = required.input_spec();
+        assert!(spec.required);
+        assert!(spec.sensitive);
+    }
+
+    #[test]
+    fn test_value_is_cached_until_refresh() {
+        let config = ConfigValue::new("default".to_string(), "TEST_CACHED_VALUE");
+        env::set_var("TEST_CACHED_VALUE", "first");
+        assert_eq!(config.get().unwrap(), "first");
+
+        env::set_var("TEST_CACHED_VALUE", "second");
+        assert_eq!(config.get().unwrap(), "first");
+
+        config.refresh();
+        assert_eq!(config.get().unwrap(), "second");
+    }
+
+    #[test]
+    fn test_cached_error_until_refresh() {
+        let config: ConfigValue<Required> = ConfigValue::required("TEST_CACHED_REQUIRED");
+        env::remove_var("TEST_CACHED_REQUIRED");
+        assert!(config.get::<String>().is_err());
+
+        env::set_var("TEST_CACHED_REQUIRED", "set");
+        assert!(config.get::<String>().is_err());
+
+        config.refresh();
+        assert_eq!(config.get::<String>().unwrap(), "set");
+    }
+
+    #[test]
+    fn test_clone_starts_with_empty_cache() {
+        let config = ConfigValue::new(false, "TEST_CACHED_CLONE");
+        env::set_var("TEST_CACHED_CLONE", "false");
+        assert!(!config.get().unwrap());
+
+        env::set_var("TEST_CACHED_CLONE", "true");
+        assert!(config.clone().get().unwrap());
+        assert!(!config.get().unwrap());
+    }
+
+    #[test]
+    fn test_report_entry_source() {
+        let config = ConfigValue::new("default".to_string(), "TEST_REPORT_SOURCE");
+        env::remove_var("TEST_REPORT_SOURCE");
+        let entry = config.report_entry();
+        assert_eq!(entry.source, ValueSource::Default);
+        assert_eq!(entry.value.as_deref(), Some("default"));
+
+        env::set_var("TEST_REPORT_SOURCE", "from_env");
+        config.refresh();
+        let entry = config.report_entry();
+        assert_eq!(entry.source, ValueSource::Env);
+        assert_eq!(entry.value.as_deref(), Some("from_env"));
+    }
+
+    #[test]
+    fn test_report_entry_required_missing() {
+        let config = ConfigValue::required("TEST_REPORT_REQUIRED").sensitive();
+        env::remove_var("TEST_REPORT_REQUIRED");
+        let entry = config.report_entry();
+        assert_eq!(entry.source, ValueSource::Missing);
+        assert!(entry.value.is_none());
+        assert!(entry.sensitive);
+    }
+
+    #[test]
+    fn test_string_from_file() {
+        let dir = tempdir().unwrap();
+        let secret = dir.path().join("secret");
+        std::fs::write(&secret, "from_file\n").unwrap();
+
+        let config = ConfigValue::new(String::new(), "TEST_SECRET_FILE_VALUE");
+        env::remove_var("TEST_SECRET_FILE_VALUE");
+        env::set_var("TEST_SECRET_FILE_VALUE_FILE", &secret);
+        assert_eq!(config.get().unwrap(), "from_file");
+
+        let entry = config.report_entry();
+        assert_eq!(entry.source, ValueSource::File);
+        assert!(entry.sensitive);
+    }
+
+    #[test]
+    fn test_env_takes_precedence_over_file() {
+        let dir = tempdir().unwrap();
+        let secret = dir.path().join("secret");
+        std::fs::write(&secret, "from_file").unwrap();
+
+        let config: ConfigValue<Required> = ConfigValue::required("TEST_SECRET_PRECEDENCE");
+        env::set_var("TEST_SECRET_PRECEDENCE", "from_env");
+        env::set_var("TEST_SECRET_PRECEDENCE_FILE", &secret);
+        assert_eq!(config.get::<String>().unwrap(), "from_env");
+    }
+
+    #[test]
+    fn test_file_missing() {
+        let config: ConfigValue<Required> = ConfigValue::required("TEST_SECRET_MISSING");
+        env::remove_var("TEST_SECRET_MISSING");
+        env::set_var("TEST_SECRET_MISSING_FILE", "/non/existent/secret");
+        let result = config.get::<String>();
+        assert!(matches!(result, Err(ConfigError::ReadFailed { .. })));
+    }
+
+    #[test]
+    fn test_alias_used_when_primary_missing() {
+        let config = ConfigValue::new("default".to_string(), "TEST_ALIAS_PRIMARY")
+            .with_alias("TEST_ALIAS_LEGACY");
+        env::remove_var("TEST_ALIAS_PRIMARY");
+        env::set_var("TEST_ALIAS_LEGACY", "legacy");
+        assert_eq!(config.get().unwrap(), "legacy");
+
+        env::set_var("TEST_ALIAS_PRIMARY", "primary");
+        config.refresh();
+        assert_eq!(config.get().unwrap(), "primary");
+    }
+
+    #[test]
+    fn test_alias_order() {
+        let config: ConfigValue<Required> = ConfigValue::required("TEST_ALIAS_ORDER")
+            .with_alias("TEST_ALIAS_ORDER_FIRST")
+            .with_alias("TEST_ALIAS_ORDER_SECOND");
+        env::remove_var("TEST_ALIAS_ORDER");
+        env::set_var("TEST_ALIAS_ORDER_FIRST", "first");
+        env::set_var("TEST_ALIAS_ORDER_SECOND", "second");
+        assert_eq!(config.get::<String>().unwrap(), "first");
+        assert_eq!(
+            config.aliases(),
+            &["TEST_ALIAS_ORDER_FIRST", "TEST_ALIAS_ORDER_SECOND"]
+        );
+    }
+
     #[test]
     fn test_bool_with_default() {
         let config = ConfigValue::new(false, "TEST_BOOL");
@@ -403,7 +1064,7 @@ mod tests {
         impl Validator<String> for NonEmptyValidator {
             fn validate(&self, value: &String) -> ConfigResult<()> {
                 if value.is_empty() {
-                    Err(ConfigError::InvalidValue("String is empty".to_string()))
+                    Err(ConfigError::invalid(value, "a non-empty string"))
                 } else {
                     Ok(())
                 }
@@ -0,0 +1,245 @@
+use crate::value::{ConfigValue, FILE_SUFFIX};
+use shared::source::{EnvSource, Source};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// A configuration value that can be re-read on change.
+pub trait Watchable: Send + Sync {
+    /// Returns the key of the value.
+    fn env_key(&self) -> &'static str;
+
+    /// Drops the cached value so it is resolved again on next access.
+    fn refresh(&self);
+}
+
+impl<T: Send + Sync> Watchable for ConfigValue<T> {
+    fn env_key(&self) -> &'static str {
+        ConfigValue::env_key(self)
+    }
+
+    fn refresh(&self) {
+        ConfigValue::refresh(self)
+    }
+}
+
+/// Describes a changed file-backed value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    /// The key of the changed value.
+    pub key: &'static str,
+    /// The file referenced by `<KEY>_FILE`.
+    pub path: PathBuf,
+}
+
+type Subscriber = Box<dyn Fn(&ConfigChange) + Send + Sync>;
+
+struct Watched {
+    value: &'static dyn Watchable,
+    last: Option<(PathBuf, Option<String>)>,
+}
+
+/// Re-reads file-backed (`<KEY>_FILE`) values when their files change and
+/// notifies subscribers.
+///
+/// Only values read from a file are watched; a value set directly in the
+/// environment cannot change during the run.
+///
+/// # Example
+///
+/// ```rust
+/// use config::{ConfigWatcher, ConfigValue};
+/// use lazy_static::lazy_static;
+///
+/// lazy_static! {
+///     static ref TOKEN: ConfigValue<String> = ConfigValue::new(String::new(), "WATCHED_TOKEN");
+/// }
+///
+/// let mut watcher = ConfigWatcher::new()
+///     .watch(&*TOKEN)
+///     .subscribe(|change| println!("{} changed", change.key));
+///
+/// // Nothing changed since the first poll.
+/// watcher.poll();
+/// assert!(watcher.poll().is_empty());
+/// ```
+pub struct ConfigWatcher {
+    watched: Vec<Watched>,
+    subscribers: Vec<Subscriber>,
+}
+
+impl ConfigWatcher {
+    /// Creates a watcher with no values and no subscribers.
+    pub fn new() -> Self {
+        Self {
+            watched: Vec::new(),
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Adds a value to watch.
+    pub fn watch(mut self, value: &'static dyn Watchable) -> Self {
+        let last = Self::snapshot(value.env_key());
+        self.watched.push(Watched { value, last });
+        self
+    }
+
+    /// Registers a callback invoked for every change.
+    pub fn subscribe<F>(mut self, subscriber: F) -> Self
+    where
+        F: Fn(&ConfigChange) + Send + Sync + 'static,
+    {
+        self.subscribers.push(Box::new(subscriber));
+        self
+    }
+
+    /// Checks every watched file once.
+    ///
+    /// Changed values are refreshed before subscribers are notified, so
+    /// callbacks already see the new value.
+    pub fn poll(&mut self) -> Vec<ConfigChange> {
+        let mut changes = Vec::new();
+        for watched in &mut self.watched {
+            let current = Self::snapshot(watched.value.env_key());
+            if current == watched.last {
+                continue;
+            }
+            watched.last = current;
+            watched.value.refresh();
+            if let Some((path, _)) = &watched.last {
+                changes.push(ConfigChange {
+                    key: watched.value.env_key(),
+                    path: path.clone(),
+                });
+            }
+        }
+        for change in &changes {
+            for subscriber in &self.subscribers {
+                subscriber(change);
+            }
+        }
+        changes
+    }
+
+    /// Polls in a background thread every `interval` until the handle is stopped.
+    pub fn spawn(mut self, interval: Duration) -> WatchHandle {
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = stop.clone();
+        let thread = thread::spawn(move || {
+            while !flag.load(Ordering::Relaxed) {
+                self.poll();
+                thread::sleep(interval);
+            }
+        });
+        WatchHandle { stop, thread }
+    }
+
+    /// Reads the file currently referenced by `<KEY>_FILE`, if any.
+    fn snapshot(key: &str) -> Option<(PathBuf, Option<String>)> {
+        let path = EnvSource::new()
+            .get(&format!("{}{}", key, FILE_SUFFIX))
+            .ok()
+            .flatten()?;
+        let path = PathBuf::from(path.to_string());
+        let content = fs::read_to_string(&path).ok();
+        Some((path, content))
+    }
+}
+
+impl Default for ConfigWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Controls a watcher started with [`ConfigWatcher::spawn`].
+pub struct WatchHandle {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl WatchHandle {
+    /// Stops the watcher and waits for its thread to finish.
+    pub fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.thread.join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lazy_static::lazy_static;
+    use std::env;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    lazy_static! {
+        static ref WATCHED: ConfigValue<String> =
+            ConfigValue::new(String::new(), "TEST_WATCH_VALUE");
+        static ref WATCHED_ENV: ConfigValue<String> =
+            ConfigValue::new(String::new(), "TEST_WATCH_ENV_ONLY");
+        static ref WATCHED_SPAWN: ConfigValue<String> =
+            ConfigValue::new(String::new(), "TEST_WATCH_SPAWN");
+    }
+
+    #[test]
+    fn test_poll_detects_change() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("value");
+        fs::write(&path, "first").unwrap();
+        env::remove_var("TEST_WATCH_VALUE");
+        env::set_var("TEST_WATCH_VALUE_FILE", &path);
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let mut watcher = ConfigWatcher::new()
+            .watch(&*WATCHED)
+            .subscribe(move |_| sink.lock().unwrap().push(WATCHED.get().unwrap()));
+        assert_eq!(WATCHED.get().unwrap(), "first");
+        assert!(watcher.poll().is_empty());
+
+        fs::write(&path, "second").unwrap();
+        let changes = watcher.poll();
+        assert_eq!(
+            changes,
+            vec![ConfigChange {
+                key: "TEST_WATCH_VALUE",
+                path: path.clone()
+            }]
+        );
+        assert_eq!(*seen.lock().unwrap(), vec!["second".to_string()]);
+        assert!(watcher.poll().is_empty());
+    }
+
+    #[test]
+    fn test_poll_ignores_env_values() {
+        env::set_var("TEST_WATCH_ENV_ONLY", "value");
+        env::remove_var("TEST_WATCH_ENV_ONLY_FILE");
+        let mut watcher = ConfigWatcher::new().watch(&*WATCHED_ENV);
+        assert!(watcher.poll().is_empty());
+    }
+
+    #[test]
+    fn test_spawn_and_stop() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("value");
+        fs::write(&path, "first").unwrap();
+        env::set_var("TEST_WATCH_SPAWN_FILE", &path);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let tx = Mutex::new(tx);
+        let handle = ConfigWatcher::new()
+            .watch(&*WATCHED_SPAWN)
+            .subscribe(move |change| tx.lock().unwrap().send(change.key).unwrap())
+            .spawn(Duration::from_millis(10));
+
+        fs::write(&path, "second").unwrap();
+        let key = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(key, "TEST_WATCH_SPAWN");
+        handle.stop();
+    }
+}
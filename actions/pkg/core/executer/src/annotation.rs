@@ -0,0 +1,206 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Directory the repository is checked out to, set by the GitHub Actions runner.
+pub const ENV_GITHUB_WORKSPACE: &str = "GITHUB_WORKSPACE";
+
+/// The workflow command an annotation is rendered as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationLevel {
+    Error,
+    Warning,
+    Notice,
+}
+
+impl AnnotationLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Notice => "notice",
+        }
+    }
+}
+
+/// A problem found in command output, shown inline on the pull request diff
+/// when it has a file and line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    pub level: AnnotationLevel,
+    pub message: String,
+    pub title: Option<String>,
+    /// Path relative to the repository root.
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub col: Option<u32>,
+}
+
+impl Annotation {
+    pub fn new(level: AnnotationLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+            title: None,
+            file: None,
+            line: None,
+            col: None,
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(AnnotationLevel::Error, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(AnnotationLevel::Warning, message)
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the file of the problem.
+    ///
+    /// Absolute paths inside `GITHUB_WORKSPACE` are made relative to it, as
+    /// the runner only matches annotations to files by their repository path.
+    pub fn with_file(mut self, file: impl AsRef<Path>) -> Self {
+        self.file = Some(workspace_path(file.as_ref()));
+        self
+    }
+
+    pub fn with_line(mut self, line: u32) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub fn with_col(mut self, col: u32) -> Self {
+        self.col = Some(col);
+        self
+    }
+
+    /// Renders the annotation as a workflow command.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use executer::Annotation;
+    ///
+    /// let annotation = Annotation::error("An argument named \"acl\" is not expected here.")
+    ///     .with_title("Unsupported argument")
+    ///     .with_file("infra/main.tf")
+    ///     .with_line(4);
+    /// assert_eq!(
+    ///     annotation.render(),
+    ///     "::error file=infra/main.tf,line=4,title=Unsupported argument::\
+    ///      An argument named \"acl\" is not expected here."
+    /// );
+    /// ```
+    pub fn render(&self) -> String {
+        let mut properties = Vec::new();
+        if let Some(file) = &self.file {
+            properties.push(format!("file={}", escape_property(file)));
+        }
+        if let Some(line) = self.line {
+            properties.push(format!("line={}", line));
+        }
+        if let Some(col) = self.col {
+            properties.push(format!("col={}", col));
+        }
+        if let Some(title) = &self.title {
+            properties.push(format!("title={}", escape_property(title)));
+        }
+        let properties = if properties.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", properties.join(","))
+        };
+        format!(
+            "::{}{}::{}",
+            self.level.as_str(),
+            properties,
+            escape_data(&self.message)
+        )
+    }
+}
+
+/// Annotations collected from the output of a run; clones share the same list.
+#[derive(Debug, Clone, Default)]
+pub struct Annotations {
+    items: Arc<Mutex<Vec<Annotation>>>,
+}
+
+impl Annotations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn items(&self) -> std::sync::MutexGuard<'_, Vec<Annotation>> {
+        self.items.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn extend(&self, annotations: impl IntoIterator<Item = Annotation>) {
+        self.items().extend(annotations);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items().is_empty()
+    }
+
+    /// Removes and returns the annotations collected so far.
+    pub fn take(&self) -> Vec<Annotation> {
+        std::mem::take(&mut *self.items())
+    }
+}
+
+/// Returns `path` relative to `GITHUB_WORKSPACE` when it is inside it.
+fn workspace_path(path: &Path) -> String {
+    let relative = std::env::var_os(ENV_GITHUB_WORKSPACE)
+        .filter(|_| path.is_absolute())
+        .and_then(|workspace| path.strip_prefix(workspace).ok().map(Path::to_path_buf));
+    let path = relative.as_deref().unwrap_or(path);
+    path.strip_prefix("./")
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Escapes the data of a workflow command, which must fit on one line.
+pub fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property value of a workflow command, e.g. a file or title.
+pub fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_escapes_and_shares() {
+        let annotations = Annotations::new();
+        annotations.clone().extend([
+            Annotation::warning("50% done\nstill running").with_title("a: b, c"),
+            Annotation::error("Missing required parameter").with_file("./main.tf"),
+        ]);
+
+        let rendered: Vec<_> = annotations.take().iter().map(Annotation::render).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "::warning title=a%3A b%2C c::50%25 done%0Astill running".to_string(),
+                "::error file=main.tf::Missing required parameter".to_string(),
+            ]
+        );
+        assert!(annotations.is_empty());
+    }
+}
@@ -21,6 +21,10 @@ pub struct Context {
     /// An optional timeout (in seconds) for the command execution.
     /// If set, the command will be killed if it does not complete within the specified duration.
     pub timeout: Option<u64>,
+
+    /// Optional data written to the command's standard input.
+    /// If `None`, standard input is closed.
+    pub stdin: Option<String>,
 }
 
 impl Context {
@@ -51,6 +55,7 @@ impl Context {
             env,
             cwd,
             timeout: None,
+            stdin: None,
         }
     }
 
@@ -78,4 +83,24 @@ impl Context {
         self.timeout = Some(timeout);
         self
     }
+
+    /// Sets data to write to the command's standard input.
+    ///
+    /// This allows passing secrets (e.g. `--password-stdin`) without putting them
+    /// on the command line, where shell pipes are rejected by the validator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use executer::Context;
+    /// use std::collections::HashMap;
+    ///
+    /// let command = vec!["cat".to_string()];
+    /// let context = Context::new(command, HashMap::new(), None).with_stdin("input");
+    /// assert_eq!(context.stdin.as_deref(), Some("input"));
+    /// ```
+    pub fn with_stdin(mut self, stdin: impl Into<String>) -> Self {
+        self.stdin = Some(stdin.into());
+        self
+    }
 }
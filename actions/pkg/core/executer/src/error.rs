@@ -11,6 +11,10 @@ pub enum ExecuterError {
     #[error("Command execution error: {0}")]
     ExecutionError(String),
 
+    /// Error when a command runs longer than its timeout and is killed.
+    #[error("Command timed out after {0} seconds")]
+    Timeout(u64),
+
     /// Error related to stream processing (stdout/stderr).
     #[error("Stream error: {0}")]
     StreamError(String),
@@ -11,6 +11,9 @@
 //! - [`error`]: Defines error types and result aliases used across the crate.
 //! - [`output`]: Handles output processing, including logging and writing to various targets.
 //! - [`validate`]: Contains validation rules to ensure commands are safe to execute.
+//! - [`metrics`]: Collects command timings and counters of a run.
+//! - [`annotation`]: Workflow annotations for problems found in command output.
+//! - [`retry`]: Backoff policy for retrying failed commands.
 //! - [`subprocess`]: Manages the execution of subprocesses with proper validation and output handling.
 //!
 //! ## Usage
@@ -58,9 +61,12 @@
 //! }
 //! ```
 
+mod annotation;
 mod context;
 mod error;
+mod metrics;
 mod output;
+mod retry;
 mod subprocess;
 mod validate;
 
@@ -72,9 +78,14 @@ pub use validate::Validator;
 pub use error::ExecuterError;
 pub use error::ExecuterResult;
 
+pub use annotation::{
+    escape_data, escape_property, Annotation, AnnotationLevel, Annotations, ENV_GITHUB_WORKSPACE,
+};
+pub use metrics::{Metrics, Run, Timing, MASKED_LINES};
 pub use output::Output;
 pub use output::Target;
+pub use retry::RetryPolicy;
 
 pub use context::Context;
 
-pub use subprocess::Subprocess;
+pub use subprocess::{Captured, LineHook, Subprocess};
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Counter of output lines in which at least one value was masked.
+pub const MASKED_LINES: &str = "masked output lines";
+
+/// Timings of one command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    pub count: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+/// One finished command, in the order the commands ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub command: String,
+    pub duration: Duration,
+    pub code: i32,
+}
+
+#[derive(Debug, Default)]
+struct Data {
+    counters: BTreeMap<String, u64>,
+    timings: BTreeMap<String, Timing>,
+    runs: Vec<Run>,
+}
+
+/// Counters and command timings of a run; clones share the same values.
+///
+/// # Example
+///
+/// ```rust
+/// use executer::Metrics;
+/// use std::time::Duration;
+///
+/// let metrics = Metrics::new();
+/// metrics.record("terraform plan", Duration::from_millis(1500));
+/// metrics.increment("lock retries");
+/// assert_eq!(metrics.counter("lock retries"), 1);
+/// assert_eq!(
+///     metrics.markdown(),
+///     "### Run summary\n\n\
+///      | Command | Runs | Total | Longest |\n\
+///      | --- | --- | --- | --- |\n\
+///      | `terraform plan` | 1 | 1.50s | 1.50s |\n\n\
+///      | Counter | Value |\n\
+///      | --- | --- |\n\
+///      | lock retries | 1 |\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    data: Arc<Mutex<Data>>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn data(&self) -> std::sync::MutexGuard<'_, Data> {
+        self.data.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn increment(&self, name: &str) {
+        self.add(name, 1);
+    }
+
+    pub fn add(&self, name: &str, value: u64) {
+        *self.data().counters.entry(name.to_string()).or_default() += value;
+    }
+
+    /// Records one run of `name` that took `duration`.
+    pub fn record(&self, name: &str, duration: Duration) {
+        let mut data = self.data();
+        let timing = data.timings.entry(name.to_string()).or_default();
+        timing.count += 1;
+        timing.total += duration;
+        timing.max = timing.max.max(duration);
+    }
+
+    /// Records one run of `name` that exited with `code`, besides its timing.
+    pub fn record_run(&self, name: &str, duration: Duration, code: i32) {
+        self.record(name, duration);
+        self.data().runs.push(Run {
+            command: name.to_string(),
+            duration,
+            code,
+        });
+    }
+
+    pub fn counter(&self, name: &str) -> u64 {
+        self.data().counters.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn timing(&self, name: &str) -> Option<Timing> {
+        self.data().timings.get(name).copied()
+    }
+
+    pub fn counters(&self) -> BTreeMap<String, u64> {
+        self.data().counters.clone()
+    }
+
+    /// Returns the runs recorded with [`Metrics::record_run`].
+    pub fn runs(&self) -> Vec<Run> {
+        self.data().runs.clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let data = self.data();
+        data.counters.is_empty() && data.timings.is_empty()
+    }
+
+    /// Returns one log line per command and counter.
+    pub fn lines(&self) -> Vec<String> {
+        let data = self.data();
+        let timings = data.timings.iter().map(|(name, t)| {
+            format!(
+                "{}: {} runs in {:.2}s, longest {:.2}s",
+                name,
+                t.count,
+                t.total.as_secs_f64(),
+                t.max.as_secs_f64()
+            )
+        });
+        let counters = data
+            .counters
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value));
+        timings.chain(counters).collect()
+    }
+
+    /// Renders the commands and counters as markdown tables for the job summary.
+    pub fn markdown(&self) -> String {
+        let data = self.data();
+        let mut out = "### Run summary\n".to_string();
+        if !data.timings.is_empty() {
+            out.push_str("\n| Command | Runs | Total | Longest |\n| --- | --- | --- | --- |\n");
+            for (name, t) in &data.timings {
+                out.push_str(&format!(
+                    "| `{}` | {} | {:.2}s | {:.2}s |\n",
+                    name,
+                    t.count,
+                    t.total.as_secs_f64(),
+                    t.max.as_secs_f64()
+                ));
+            }
+        }
+        if !data.counters.is_empty() {
+            out.push_str("\n| Counter | Value |\n| --- | --- |\n");
+            for (name, value) in &data.counters {
+                out.push_str(&format!("| {} | {} |\n", name, value));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timings_are_shared() {
+        let metrics = Metrics::new();
+        let clone = metrics.clone();
+        clone.record("aws s3", Duration::from_secs(2));
+        metrics.record("aws s3", Duration::from_secs(5));
+        metrics.add(MASKED_LINES, 3);
+        metrics.record_run("aws s3", Duration::from_secs(1), 1);
+
+        assert_eq!(
+            metrics.timing("aws s3"),
+            Some(Timing {
+                count: 3,
+                total: Duration::from_secs(8),
+                max: Duration::from_secs(5),
+            })
+        );
+        assert_eq!(
+            clone.lines(),
+            vec![
+                "aws s3: 3 runs in 8.00s, longest 5.00s".to_string(),
+                "masked output lines: 3".to_string(),
+            ]
+        );
+        assert_eq!(
+            clone.runs(),
+            vec![Run {
+                command: "aws s3".to_string(),
+                duration: Duration::from_secs(1),
+                code: 1,
+            }]
+        );
+        assert!(Metrics::new().is_empty());
+    }
+}
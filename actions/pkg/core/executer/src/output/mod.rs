@@ -4,6 +4,7 @@ mod writer;
 
 pub use types::Target;
 
+use crate::metrics::{Metrics, MASKED_LINES};
 use formatter::PlainFormatter;
 use processor::{Processor, ProcessorCollection};
 use slog::{o, Drain, Logger};
@@ -21,6 +22,7 @@ pub struct Output {
     error_target: Target,
     logger: Logger,
     writer: Writer,
+    metrics: Option<Metrics>,
 }
 
 impl Output {
@@ -59,9 +61,26 @@ impl Output {
             output_target,
             error_target,
             processor,
+            metrics: None,
         }
     }
 
+    /// Counts the lines in which a value was masked on `metrics`.
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    fn process(&self, line: &str) -> String {
+        let processed = self.processor.process(line);
+        if let Some(metrics) = &self.metrics {
+            if processed != line {
+                metrics.increment(MASKED_LINES);
+            }
+        }
+        processed
+    }
+
     /// Writes a standard log message to the designated output target.
     ///
     /// # Arguments
@@ -84,7 +103,7 @@ impl Output {
     /// output.write("This is an log message");
     /// ```
     pub fn write(&self, line: &str) {
-        let processed = self.processor.process(line);
+        let processed = self.process(line);
         //slog::info!(self.logger, "{}", processed);
         self.writer.write(&processed, &self.output_target);
     }
@@ -111,7 +130,7 @@ impl Output {
     /// output.write_error("This is an error message");
     /// ```
     pub fn write_error(&self, line: &str) {
-        let processed = self.processor.process(line);
+        let processed = self.process(line);
         slog::error!(self.logger, "{}", processed);
         self.writer.write(&processed, &self.error_target);
     }
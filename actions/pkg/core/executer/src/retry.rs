@@ -0,0 +1,92 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Controls how failed commands are retried.
+///
+/// Delays grow exponentially from `base_delay` up to `max_delay`; up to half
+/// of each delay is added as jitter so parallel jobs do not retry in lockstep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub base_delay: Duration,
+    /// Upper bound of the delay before jitter.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a policy with the given number of retries and base delay.
+    pub fn new(max_retries: u32, base_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            base_delay,
+            max_delay: Duration::from_secs(20),
+        }
+    }
+
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Returns `true` if another attempt may follow the failed attempt `attempt` (0-based).
+    pub fn should_retry(&self, attempt: u32) -> bool {
+        attempt < self.max_retries
+    }
+
+    /// Returns the delay before retrying after the failed attempt `attempt` (0-based).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        backoff + jitter(backoff / 2)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_secs(1))
+    }
+}
+
+/// Returns a pseudo-random duration in `[0, max]`.
+fn jitter(max: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    max.mul_f64(f64::from(nanos % 1000) / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_grows_and_is_capped() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(300));
+
+        let first = policy.delay(0);
+        assert!(first >= Duration::from_millis(100) && first <= Duration::from_millis(150));
+        let second = policy.delay(1);
+        assert!(second >= Duration::from_millis(200) && second <= Duration::from_millis(300));
+        let capped = policy.delay(10);
+        assert!(capped >= Duration::from_millis(300) && capped <= Duration::from_millis(450));
+    }
+
+    #[test]
+    fn test_should_retry() {
+        let policy = RetryPolicy::new(2, Duration::ZERO);
+        assert!(policy.should_retry(0));
+        assert!(policy.should_retry(1));
+        assert!(!policy.should_retry(2));
+        assert!(!RetryPolicy::none().should_retry(0));
+    }
+}
@@ -1,11 +1,42 @@
-use crate::{Context, ExecuterError, ExecuterResult, Output, Validator};
+use crate::{Context, ExecuterError, ExecuterResult, Metrics, Output, Validator};
 
-use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command;
 use tokio::time::{timeout, Duration};
 
+use std::path::Path;
 use std::process::Stdio;
 use std::sync::Arc;
+use std::time::Instant;
+
+/// The result of a command whose standard output was captured.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captured {
+    /// The exit code of the command.
+    pub code: i32,
+
+    /// The raw (unmasked) standard output, one line per entry joined with `\n`.
+    pub stdout: String,
+
+    /// The raw (unmasked) standard error, joined with `\n`.
+    ///
+    /// Standard error is still written to the error target as well; this copy
+    /// lets callers inspect failures.
+    pub stderr: String,
+}
+
+/// A callback receiving raw output lines; a line is written only if it returns `true`.
+pub type LineHook = Box<dyn FnMut(&str) -> bool + Send>;
+
+/// How standard output lines of a command are handled.
+enum StdoutMode {
+    /// Process and write every line.
+    Write,
+    /// Collect every line unprocessed.
+    Capture,
+    /// Pass every line to the filter; only lines it returns `true` for are written.
+    Filter(LineHook),
+}
 
 /// Manages the execution of subprocesses with proper validation and output handling.
 ///
@@ -17,6 +48,7 @@ pub struct Subprocess {
     stdout: Arc<Output>,
     stderr: Arc<Output>,
     validator: Validator,
+    metrics: Option<Metrics>,
 }
 
 impl Subprocess {
@@ -48,9 +80,20 @@ impl Subprocess {
             stdout,
             stderr,
             validator,
+            metrics: None,
         }
     }
 
+    /// Records the duration of every command and the masked output lines on `metrics`.
+    ///
+    /// Commands are recorded by program name and first argument, e.g. `terraform plan`.
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.stdout = Arc::new((*self.stdout).clone().with_metrics(metrics.clone()));
+        self.stderr = Arc::new((*self.stderr).clone().with_metrics(metrics.clone()));
+        self.metrics = Some(metrics);
+        self
+    }
+
     /// Executes a command based on the provided context.
     ///
     /// This method validates the command using the `Validator`, spawns a subprocess,
@@ -106,6 +149,144 @@ impl Subprocess {
     /// }
     /// ```
     pub async fn execute(&self, context: Context) -> ExecuterResult<i32> {
+        self.run(context, StdoutMode::Write, None)
+            .await
+            .map(|(code, ..)| code)
+    }
+
+    /// Executes a command, passing each raw standard output line to `filter` first.
+    ///
+    /// Lines for which `filter` returns `true` are processed and written as
+    /// usual; the others are dropped. This lets callers turn verbose output
+    /// into their own events while still printing anything unexpected.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use executer::{Context, Output, Subprocess, Target, Validator};
+    /// use processor::ProcessorCollection;
+    /// use std::collections::HashMap;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let subprocess = Subprocess::new(
+    ///         Output::new(ProcessorCollection::new(vec![]), Target::Stdout, Target::Stderr),
+    ///         Validator::default(),
+    ///     );
+    ///     let context = Context::new(vec!["ls".to_string()], HashMap::new(), None);
+    ///
+    ///     // Hide hidden files from the output.
+    ///     subprocess
+    ///         .execute_filtered(context, |line| !line.starts_with('.'))
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub async fn execute_filtered<F>(&self, context: Context, filter: F) -> ExecuterResult<i32>
+    where
+        F: FnMut(&str) -> bool + Send + 'static,
+    {
+        self.execute_hooked(context, Some(Box::new(filter)), None)
+            .await
+    }
+
+    /// Executes a command, passing raw standard output and error lines to hooks.
+    ///
+    /// Each hook sees every line of its stream before it is processed; lines
+    /// it returns `true` for are written as usual. A stream without a hook is
+    /// written unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use executer::{Context, LineHook, Output, Subprocess, Target, Validator};
+    /// use processor::ProcessorCollection;
+    /// use std::collections::HashMap;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let subprocess = Subprocess::new(
+    ///         Output::new(ProcessorCollection::new(vec![]), Target::Stdout, Target::Stderr),
+    ///         Validator::default(),
+    ///     );
+    ///     let context = Context::new(vec!["ls".to_string(), "missing".to_string()], HashMap::new(), None);
+    ///
+    ///     let not_found = Arc::new(AtomicBool::new(false));
+    ///     let flag = Arc::clone(&not_found);
+    ///     let stderr: LineHook = Box::new(move |line| {
+    ///         if line.contains("No such file") {
+    ///             flag.store(true, Ordering::SeqCst);
+    ///         }
+    ///         true
+    ///     });
+    ///
+    ///     subprocess.execute_hooked(context, None, Some(stderr)).await.unwrap();
+    ///     assert!(not_found.load(Ordering::SeqCst));
+    /// }
+    /// ```
+    pub async fn execute_hooked(
+        &self,
+        context: Context,
+        stdout: Option<LineHook>,
+        stderr: Option<LineHook>,
+    ) -> ExecuterResult<i32> {
+        let mode = match stdout {
+            Some(hook) => StdoutMode::Filter(hook),
+            None => StdoutMode::Write,
+        };
+        self.run(context, mode, stderr).await.map(|(code, ..)| code)
+    }
+
+    /// Executes a command and captures its standard output instead of writing it.
+    ///
+    /// Standard error is still processed and written to the error target. The
+    /// captured output is returned unmasked, so it must not be logged as is;
+    /// this is meant for commands that print credentials or machine-readable data.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use executer::{Context, Output, Subprocess, Target, Validator};
+    /// use processor::ProcessorCollection;
+    /// use std::collections::HashMap;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let subprocess = Subprocess::new(
+    ///         Output::new(ProcessorCollection::new(vec![]), Target::Stdout, Target::Stderr),
+    ///         Validator::default(),
+    ///     );
+    ///     let context = Context::new(vec!["echo".to_string(), "hello".to_string()], HashMap::new(), None);
+    ///
+    ///     let captured = subprocess.execute_captured(context).await.unwrap();
+    ///     assert_eq!(captured.stdout, "hello");
+    /// }
+    /// ```
+    pub async fn execute_captured(&self, context: Context) -> ExecuterResult<Captured> {
+        let (code, stdout, stderr) = self.run(context, StdoutMode::Capture, None).await?;
+        Ok(Captured {
+            code,
+            stdout: stdout.unwrap_or_default(),
+            stderr,
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "subprocess",
+            skip_all,
+            fields(command = %command_name(&context.command), exit_code = tracing::field::Empty)
+        )
+    )]
+    async fn run(
+        &self,
+        context: Context,
+        mode: StdoutMode,
+        stderr_hook: Option<LineHook>,
+    ) -> ExecuterResult<(i32, Option<String>, String)> {
         self.validator.validate(&context)?;
 
         let mut command = Command::new(&context.command[0]);
@@ -113,15 +294,29 @@ impl Subprocess {
             .args(&context.command[1..])
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .stdin(Stdio::null());
+            .stdin(if context.stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            });
         if let Some(path) = &context.cwd {
             command.current_dir(path);
         }
         if !context.env.is_empty() {
             command.envs(&context.env);
         }
+        let started = Instant::now();
         let mut child = command.spawn()?;
 
+        let stdin_handle = match (child.stdin.take(), context.stdin.clone()) {
+            (Some(mut pipe), Some(data)) => Some(tokio::spawn(async move {
+                // A command may exit without reading its input; that is not an error here.
+                let _ = pipe.write_all(data.as_bytes()).await;
+                let _ = pipe.shutdown().await;
+            })),
+            _ => None,
+        };
+
         let stdout = child
             .stdout
             .take()
@@ -131,20 +326,41 @@ impl Subprocess {
             .take()
             .ok_or_else(|| ExecuterError::ExecutionError("Failed to capture stderr".to_string()))?;
 
+        let capture_stderr = matches!(mode, StdoutMode::Capture);
         let stdout_output = Arc::clone(&self.stdout);
         let stderr_output = Arc::clone(&self.stderr);
 
         let stdout_handle = tokio::spawn(async move {
+            let mut mode = mode;
             let mut reader = BufReader::new(stdout).lines();
+            let mut captured = Vec::new();
             while let Ok(Some(line)) = reader.next_line().await {
-                stdout_output.write(&line);
+                match &mut mode {
+                    StdoutMode::Write => stdout_output.write(&line),
+                    StdoutMode::Capture => captured.push(line),
+                    StdoutMode::Filter(filter) => {
+                        if filter(&line) {
+                            stdout_output.write(&line);
+                        }
+                    }
+                }
             }
+            matches!(mode, StdoutMode::Capture).then(|| captured.join("\n"))
         });
         let stderr_handle = tokio::spawn(async move {
+            let mut hook = stderr_hook;
             let mut reader = BufReader::new(stderr).lines();
+            let mut captured = Vec::new();
             while let Ok(Some(line)) = reader.next_line().await {
-                stderr_output.write_error(&line);
+                let write = hook.as_mut().is_none_or(|hook| hook(&line));
+                if write {
+                    stderr_output.write_error(&line);
+                }
+                if capture_stderr {
+                    captured.push(line);
+                }
             }
+            captured.join("\n")
         });
 
         let status = if let Some(t) = context.timeout {
@@ -152,22 +368,46 @@ impl Subprocess {
                 Ok(status) => status?,
                 Err(_) => {
                     child.kill().await?;
-                    return Err(ExecuterError::ExecutionError(format!(
-                        "Command timed out after {} seconds",
-                        t
-                    )));
+                    if let Some(metrics) = &self.metrics {
+                        // Reported with the exit code of timeout(1).
+                        metrics.record_run(&command_name(&context.command), started.elapsed(), 124);
+                    }
+                    return Err(ExecuterError::Timeout(t));
                 }
             }
         } else {
             child.wait().await?
         };
-        stdout_handle.await.map_err(|e| {
+        let code = status.code().unwrap_or(2);
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("exit_code", code);
+        if let Some(metrics) = &self.metrics {
+            metrics.record_run(&command_name(&context.command), started.elapsed(), code);
+        }
+        if let Some(handle) = stdin_handle {
+            handle.await.map_err(|e| {
+                ExecuterError::ExecutionError(format!("Failed to write stdin: {}", e))
+            })?;
+        }
+        let captured = stdout_handle.await.map_err(|e| {
             ExecuterError::ExecutionError(format!("Failed to process stdout: {}", e))
         })?;
-        stderr_handle.await.map_err(|e| {
+        let captured_stderr = stderr_handle.await.map_err(|e| {
             ExecuterError::ExecutionError(format!("Failed to process stderr: {}", e))
         })?;
-        Ok(status.code().unwrap_or(2))
+        Ok((code, captured, captured_stderr))
+    }
+}
+
+/// Returns the program name and first argument of a command, e.g. `aws s3`.
+fn command_name(command: &[String]) -> String {
+    let program = Path::new(&command[0])
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| command[0].clone());
+    match command.get(1).filter(|arg| !arg.starts_with('-')) {
+        Some(arg) => format!("{} {}", program, arg),
+        None => program,
     }
 }
 
@@ -526,9 +766,7 @@ mod tests {
         assert!(result.is_err());
         if let Err(e) = result {
             match e {
-                ExecuterError::ExecutionError(msg) => {
-                    assert!(msg.contains("timed out"));
-                }
+                ExecuterError::Timeout(seconds) => assert_eq!(seconds, 1),
                 _ => panic!("Unexpected error type"),
             }
         }
@@ -635,4 +873,105 @@ mod tests {
             "Process that attempts to read from stdin should not exit with code 0"
         );
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stdin_data() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("output.log");
+
+        let output = Output::new(
+            create_processor(),
+            Target::File(output_path.clone()),
+            Target::File(output_path.clone()),
+        );
+        let subprocess = Subprocess::new(output, Validator::default());
+
+        let context = Context::new(
+            build_command("read var && echo got-$var"),
+            HashMap::new(),
+            None,
+        )
+        .with_stdin("value\n")
+        .with_timeout(5);
+        let status = subprocess
+            .execute(context)
+            .await
+            .expect("Failed to run command reading stdin");
+
+        assert_eq!(status, 0);
+        let content = fs::read_to_string(&output_path).expect("Failed to read output file");
+        assert!(content.contains("got-value"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_execute_captured() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("output.log");
+        let error_path = temp_dir.path().join("error.log");
+
+        let output = Output::new(
+            create_processor(),
+            Target::File(output_path.clone()),
+            Target::File(error_path.clone()),
+        );
+        let subprocess = Subprocess::new(output, Validator::default());
+
+        let context = Context::new(
+            build_command("echo password=secret && echo second && echo token=abc >&2"),
+            HashMap::new(),
+            None,
+        );
+        let captured = subprocess
+            .execute_captured(context)
+            .await
+            .expect("Failed to capture command output");
+
+        assert_eq!(captured.code, 0);
+        assert_eq!(captured.stdout, "password=secret\nsecond");
+        assert_eq!(captured.stderr, "token=abc");
+        assert!(!output_path.exists() || fs::read_to_string(&output_path).unwrap().is_empty());
+        let error_content = fs::read_to_string(&error_path).expect("Failed to read error file");
+        assert!(!error_content.contains("abc"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_execute_filtered() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let output_path = temp_dir.path().join("output.log");
+        let error_path = temp_dir.path().join("error.log");
+
+        let output = Output::new(
+            create_processor(),
+            Target::File(output_path.clone()),
+            Target::File(error_path.clone()),
+        );
+        let subprocess = Subprocess::new(output, Validator::default());
+
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let context = Context::new(
+            build_command("echo progress 1 && echo keep password=secret"),
+            HashMap::new(),
+            None,
+        );
+        let status = subprocess
+            .execute_filtered(context, move |line| {
+                sink.lock().unwrap().push(line.to_string());
+                !line.starts_with("progress")
+            })
+            .await
+            .expect("Failed to run filtered command");
+
+        assert_eq!(status, 0);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec!["progress 1", "keep password=secret"]
+        );
+        let content = fs::read_to_string(&output_path).expect("Failed to read output file");
+        assert!(!content.contains("progress"));
+        assert!(content.contains("keep ****"));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::maskers::{MaskerEqual, MaskerRegex};
+use crate::maskers::{MaskerEqual, MaskerRegex, MaskerRuntime};
 use crate::Processor;
 
 /// Represents different types of masking processors.
@@ -8,6 +8,8 @@ pub enum ProcessorItem {
     Regex(MaskerRegex),
     /// Exact string match processor.
     Equal(MaskerEqual),
+    /// Exact string match processor for secrets added at runtime.
+    Runtime(MaskerRuntime),
 }
 
 impl Processor for ProcessorItem {
@@ -33,6 +35,7 @@ impl Processor for ProcessorItem {
         match self {
             ProcessorItem::Regex(processor) => processor.process(input),
             ProcessorItem::Equal(processor) => processor.process(input),
+            ProcessorItem::Runtime(processor) => processor.process(input),
         }
     }
 }
@@ -48,7 +48,7 @@ mod traits;
 pub use collection::ProcessorCollection;
 pub use error::ProcessorError;
 pub use item::ProcessorItem;
-pub use maskers::{MaskerEqual, MaskerRegex};
+pub use maskers::{MaskerEqual, MaskerRegex, MaskerRuntime};
 pub use traits::Processor;
 
 #[cfg(test)]
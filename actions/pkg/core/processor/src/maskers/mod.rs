@@ -1,5 +1,7 @@
 mod equal;
 mod regex;
+mod runtime;
 
 pub use equal::MaskerEqual;
 pub use regex::MaskerRegex;
+pub use runtime::MaskerRuntime;
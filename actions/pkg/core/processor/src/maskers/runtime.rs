@@ -0,0 +1,112 @@
+use crate::Processor;
+use std::sync::{Arc, RwLock};
+
+/// Processor that masks secrets discovered while the action is running.
+///
+/// Unlike `MaskerEqual`, values can be added after the processor has been handed
+/// to an `Output`: clones share the same list, so a secret registered through one
+/// handle is masked by every copy. This covers credentials that only exist once
+/// a command has run, e.g. an ECR login password or assumed-role credentials.
+#[derive(Clone)]
+pub struct MaskerRuntime {
+    /// Registered secrets, longest first so overlapping values are fully masked.
+    values: Arc<RwLock<Vec<String>>>,
+
+    /// The string to replace matched values with.
+    mask: String,
+}
+
+impl MaskerRuntime {
+    /// Creates a new runtime masker without any values.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use processor::{MaskerRuntime, Processor};
+    ///
+    /// let masker = MaskerRuntime::new("***");
+    /// let handle = masker.clone();
+    ///
+    /// handle.add("s3cr3t");
+    /// assert_eq!(masker.process("token=s3cr3t"), "token=***");
+    /// ```
+    pub fn new(mask: &str) -> Self {
+        Self {
+            values: Arc::new(RwLock::new(Vec::new())),
+            mask: mask.to_string(),
+        }
+    }
+
+    /// Registers a secret; empty and already registered values are ignored.
+    pub fn add(&self, value: &str) {
+        if value.is_empty() {
+            return;
+        }
+        let mut values = self.values.write().unwrap_or_else(|e| e.into_inner());
+        if !values.iter().any(|v| v == value) {
+            values.push(value.to_string());
+            values.sort_by_key(|v| std::cmp::Reverse(v.len()));
+        }
+    }
+
+    /// Returns the number of registered secrets.
+    pub fn len(&self) -> usize {
+        self.values.read().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Returns `true` if no secret has been registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the mask used for registered secrets.
+    pub fn mask(&self) -> &str {
+        &self.mask
+    }
+}
+
+impl Processor for MaskerRuntime {
+    /// Replaces every registered secret with the mask.
+    fn process(&self, input: &str) -> String {
+        let values = self.values.read().unwrap_or_else(|e| e.into_inner());
+        let mut output = input.to_string();
+        for value in values.iter() {
+            output = output.replace(value, &self.mask);
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_between_clones() {
+        let masker = MaskerRuntime::new("***");
+        let clone = masker.clone();
+        assert_eq!(masker.process("value"), "value");
+
+        clone.add("value");
+        assert_eq!(masker.process("value"), "***");
+        assert_eq!(masker.len(), 1);
+    }
+
+    #[test]
+    fn test_ignores_empty_and_duplicates() {
+        let masker = MaskerRuntime::new("***");
+        masker.add("");
+        masker.add("secret");
+        masker.add("secret");
+        assert_eq!(masker.len(), 1);
+        assert_eq!(masker.process("a secret"), "a ***");
+    }
+
+    #[test]
+    fn test_longest_value_first() {
+        let masker = MaskerRuntime::new("***");
+        masker.add("abc");
+        masker.add("abcdef");
+        assert_eq!(masker.process("abcdef"), "***");
+    }
+}
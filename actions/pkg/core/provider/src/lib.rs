@@ -56,10 +56,14 @@ mod providers;
 mod traits;
 
 use crate::providers::aws::constants::REQUIRED_ENV_VARS;
+use crate::providers::gcp::constants as gcp;
+use crate::providers::kubernetes::constants as kubernetes;
 use std::{collections::HashMap, env};
 
 pub use error::{ProviderError, ProviderResult};
 pub use providers::aws::AWSProvider;
+pub use providers::gcp::GCPProvider;
+pub use providers::kubernetes::{decode_base64, encode_base64, KubernetesProvider};
 pub use traits::Provider;
 
 /// Attempts to automatically detect and create a provider based on environment variables.
@@ -100,11 +104,25 @@ pub fn auto_detect() -> ProviderResult<Box<dyn Provider>> {
         return Ok(Box::new(AWSProvider::new(filtered_vars)));
     }
 
-    // Add checks for other providers here when they are added
-    // Example:
-    // if has_gcp {
-    //     return Ok(Box::new(GCPProvider::new(env_vars)));
-    // }
+    // A bare KUBECONFIG is not detected, since runners often set one
+    // without meaning to use Kubernetes.
+    let has_kubernetes = env_vars.contains_key(kubernetes::ENV_KUBE_CONFIG_DATA)
+        || (env_vars.contains_key(kubernetes::ENV_KUBE_SERVER)
+            && env_vars.contains_key(kubernetes::ENV_KUBE_TOKEN));
+    if has_kubernetes {
+        return Ok(Box::new(KubernetesProvider::from_env()));
+    }
+
+    let has_gcp = [
+        gcp::ENV_GOOGLE_CREDENTIALS,
+        gcp::ENV_GOOGLE_APPLICATION_CREDENTIALS,
+        gcp::ENV_CLOUDSDK_AUTH_ACCESS_TOKEN,
+    ]
+    .iter()
+    .any(|var| env_vars.contains_key(*var));
+    if has_gcp {
+        return Ok(Box::new(GCPProvider::from_env()));
+    }
 
     Err(ProviderError::ProviderNotFound)
 }
@@ -118,6 +136,9 @@ mod tests {
     fn cleanup_env() {
         env::remove_var("AWS_ACCESS_KEY_ID");
         env::remove_var("AWS_SECRET_ACCESS_KEY");
+        for var in gcp::ENV_VARS {
+            env::remove_var(var);
+        }
         // Add other provider vars when implemented
     }
 
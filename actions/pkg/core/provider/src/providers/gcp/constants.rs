@@ -0,0 +1,28 @@
+/// Contents of a service account key or external account configuration,
+/// as JSON or base64-encoded JSON.
+pub const ENV_GOOGLE_CREDENTIALS: &str = "GOOGLE_CREDENTIALS";
+/// Path of an existing credentials file, e.g. written by `google-github-actions/auth`.
+pub const ENV_GOOGLE_APPLICATION_CREDENTIALS: &str = "GOOGLE_APPLICATION_CREDENTIALS";
+/// OAuth access token used by gcloud instead of a credentials file.
+pub const ENV_CLOUDSDK_AUTH_ACCESS_TOKEN: &str = "CLOUDSDK_AUTH_ACCESS_TOKEN";
+/// Project of all commands.
+pub const ENV_GOOGLE_CLOUD_PROJECT: &str = "GOOGLE_CLOUD_PROJECT";
+/// Project of all commands, as read by gcloud.
+pub const ENV_CLOUDSDK_CORE_PROJECT: &str = "CLOUDSDK_CORE_PROJECT";
+
+/// Environment variables read by the provider.
+pub const ENV_VARS: &[&str] = &[
+    ENV_GOOGLE_CREDENTIALS,
+    ENV_GOOGLE_APPLICATION_CREDENTIALS,
+    ENV_CLOUDSDK_AUTH_ACCESS_TOKEN,
+    ENV_GOOGLE_CLOUD_PROJECT,
+    ENV_CLOUDSDK_CORE_PROJECT,
+];
+
+/// Credentials file fields whose values are secrets.
+pub(crate) const SECRET_FIELDS: &[&str] = &[
+    "private_key",
+    "private_key_id",
+    "client_secret",
+    "refresh_token",
+];
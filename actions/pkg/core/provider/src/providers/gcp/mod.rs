@@ -0,0 +1,6 @@
+pub(crate) mod constants;
+mod credentials;
+mod patterns;
+mod provider;
+
+pub use provider::GCPProvider;
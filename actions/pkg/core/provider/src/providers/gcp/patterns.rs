@@ -0,0 +1,16 @@
+use lazy_static::lazy_static;
+
+lazy_static! {
+    pub static ref GCP_PATTERNS: Vec<String> = vec![
+        // OAuth access tokens and API keys
+        r"ya29\.[0-9A-Za-z_-]{20,}".to_string(),
+        r"AIza[0-9A-Za-z_-]{35}".to_string(),
+        // Service account keys
+        r#""private_key_id":\s*"[a-f0-9]{40}""#.to_string(),
+        r"-----BEGIN [A-Z ]*PRIVATE KEY-----".to_string(),
+        // Service accounts
+        r"[a-z][a-z0-9-]{4,28}[a-z0-9]@[a-z][a-z0-9-]{4,28}[a-z0-9]\.iam\.gserviceaccount\.com".to_string(),
+        // Authorization headers of verbose gcloud output
+        r"Authorization: Bearer \S+".to_string(),
+    ];
+}
@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::{ProviderError, ProviderResult};
+use crate::Provider;
+
+use super::constants::{
+    ENV_CLOUDSDK_AUTH_ACCESS_TOKEN, ENV_CLOUDSDK_CORE_PROJECT, ENV_GOOGLE_APPLICATION_CREDENTIALS,
+    ENV_GOOGLE_CLOUD_PROJECT, ENV_GOOGLE_CREDENTIALS, ENV_VARS,
+};
+use super::credentials::{parse_credentials, Credentials};
+use super::patterns::GCP_PATTERNS;
+
+/// Google Cloud provider.
+///
+/// Credentials come from one of, in order of precedence:
+///
+/// - `GOOGLE_CREDENTIALS`: a credentials JSON, plain or base64-encoded;
+/// - `GOOGLE_APPLICATION_CREDENTIALS`: the path of an existing credentials file;
+/// - `CLOUDSDK_AUTH_ACCESS_TOKEN`: an OAuth access token.
+///
+/// The project is read from `GOOGLE_CLOUD_PROJECT` or `CLOUDSDK_CORE_PROJECT`,
+/// falling back to the project of a service account key.
+#[derive(Clone)]
+pub struct GCPProvider {
+    environment: HashMap<String, String>,
+    credentials: Option<Credentials>,
+}
+
+impl GCPProvider {
+    /// Creates a new GCPProvider instance with the given environment variables.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use provider::{GCPProvider, Provider};
+    /// use std::collections::HashMap;
+    ///
+    /// let key = r#"{"type": "service_account", "project_id": "acme", "private_key_id": "k1"}"#;
+    /// let env = HashMap::from([("GOOGLE_CREDENTIALS".to_string(), key.to_string())]);
+    /// let provider = GCPProvider::new(env);
+    /// assert!(provider.validate().is_ok());
+    /// assert_eq!(provider.project(), Some("acme"));
+    /// assert_eq!(provider.values(), vec![key, "k1"]);
+    /// ```
+    pub fn new(environment: HashMap<String, String>) -> Self {
+        let credentials = environment
+            .get(ENV_GOOGLE_CREDENTIALS)
+            .filter(|v| !v.is_empty())
+            .and_then(|data| parse_credentials(data));
+        Self {
+            environment,
+            credentials,
+        }
+    }
+
+    /// Creates a provider from the Google Cloud variables of the process environment.
+    pub fn from_env() -> Self {
+        Self::new(
+            ENV_VARS
+                .iter()
+                .filter_map(|key| env::var(key).ok().map(|value| (key.to_string(), value)))
+                .collect(),
+        )
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.environment
+            .get(key)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Returns the project of all commands, if known.
+    pub fn project(&self) -> Option<&str> {
+        self.get(ENV_GOOGLE_CLOUD_PROJECT)
+            .or_else(|| self.get(ENV_CLOUDSDK_CORE_PROJECT))
+            .or_else(|| self.credentials.as_ref()?.project_id.as_deref())
+    }
+
+    /// Returns the access token, if credentials are not given as a file.
+    pub fn access_token(&self) -> Option<&str> {
+        self.get(ENV_CLOUDSDK_AUTH_ACCESS_TOKEN)
+    }
+
+    /// Returns the path of the credentials file to use.
+    ///
+    /// Credentials from `GOOGLE_CREDENTIALS` are written to `path`, readable
+    /// only by the current user; otherwise the `GOOGLE_APPLICATION_CREDENTIALS`
+    /// path is returned.
+    pub fn credentials_file(&self, path: &Path) -> io::Result<Option<PathBuf>> {
+        let Some(credentials) = &self.credentials else {
+            return Ok(self
+                .get(ENV_GOOGLE_APPLICATION_CREDENTIALS)
+                .map(PathBuf::from));
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, &credentials.json)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(Some(path.to_path_buf()))
+    }
+
+    fn validate(&self) -> ProviderResult<()> {
+        if self.credentials.is_some()
+            || self.get(ENV_GOOGLE_APPLICATION_CREDENTIALS).is_some()
+            || self.access_token().is_some()
+        {
+            return Ok(());
+        }
+        if self.get(ENV_GOOGLE_CREDENTIALS).is_some() {
+            return Err(ProviderError::InvalidConfiguration(format!(
+                "{} is not a credentials JSON",
+                ENV_GOOGLE_CREDENTIALS
+            )));
+        }
+        Err(ProviderError::MissingEnvironmentVariable(
+            ENV_GOOGLE_CREDENTIALS.to_string(),
+        ))
+    }
+}
+
+impl Provider for GCPProvider {
+    fn get_environment(&self) -> HashMap<String, String> {
+        self.environment.clone()
+    }
+
+    fn get_predefined_masked_objects(&self) -> Vec<String> {
+        GCP_PATTERNS.to_vec()
+    }
+
+    fn validate(&self) -> ProviderResult<()> {
+        self.validate()
+    }
+
+    /// Removes the credential variables from the process environment.
+    fn clean(&self) {
+        for var in [ENV_GOOGLE_CREDENTIALS, ENV_CLOUDSDK_AUTH_ACCESS_TOKEN] {
+            env::remove_var(var);
+        }
+    }
+
+    /// Returns the credentials: the credentials data, the access token and
+    /// the secret fields of the credentials JSON.
+    fn values(&self) -> Vec<&str> {
+        let mut values: Vec<&str> = [ENV_GOOGLE_CREDENTIALS, ENV_CLOUDSDK_AUTH_ACCESS_TOKEN]
+            .iter()
+            .filter_map(|key| self.get(key))
+            .collect();
+        for secret in self.credentials.iter().flat_map(|c| &c.secrets) {
+            if !values.contains(&secret.as_str()) {
+                values.push(secret);
+            }
+        }
+        values
+    }
+
+    fn name(&self) -> String {
+        "GCP".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credentials_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gcp/credentials.json");
+
+        let provider = GCPProvider::new(HashMap::from([
+            (
+                ENV_GOOGLE_CREDENTIALS.to_string(),
+                r#"{"type": "service_account", "project_id": "acme"}"#.to_string(),
+            ),
+            (
+                ENV_CLOUDSDK_CORE_PROJECT.to_string(),
+                "acme-prod".to_string(),
+            ),
+        ]));
+        assert_eq!(provider.project(), Some("acme-prod"));
+        assert_eq!(
+            provider.credentials_file(&path).unwrap(),
+            Some(path.clone())
+        );
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("service_account"));
+
+        let provider = GCPProvider::new(HashMap::from([(
+            ENV_GOOGLE_APPLICATION_CREDENTIALS.to_string(),
+            "/home/runner/gha-creds.json".to_string(),
+        )]));
+        assert!(provider.validate().is_ok());
+        assert!(provider.values().is_empty());
+        assert_eq!(provider.project(), None);
+        assert_eq!(
+            provider.credentials_file(&path).unwrap(),
+            Some(PathBuf::from("/home/runner/gha-creds.json"))
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid_credentials() {
+        let provider = GCPProvider::new(HashMap::from([(
+            ENV_GOOGLE_CREDENTIALS.to_string(),
+            "not json".to_string(),
+        )]));
+        assert!(matches!(
+            provider.validate(),
+            Err(ProviderError::InvalidConfiguration(_))
+        ));
+        assert_eq!(provider.values(), vec!["not json"]);
+        assert!(GCPProvider::new(HashMap::new()).validate().is_err());
+
+        let provider = GCPProvider::new(HashMap::from([(
+            ENV_CLOUDSDK_AUTH_ACCESS_TOKEN.to_string(),
+            "ya29.token".to_string(),
+        )]));
+        assert!(provider.validate().is_ok());
+        assert_eq!(provider.access_token(), Some("ya29.token"));
+    }
+}
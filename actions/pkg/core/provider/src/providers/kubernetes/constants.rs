@@ -0,0 +1,30 @@
+/// Path of an existing kubeconfig file.
+pub const ENV_KUBECONFIG: &str = "KUBECONFIG";
+/// Contents of a kubeconfig, as YAML or base64-encoded YAML.
+pub const ENV_KUBE_CONFIG_DATA: &str = "KUBE_CONFIG_DATA";
+/// API server URL used together with [`ENV_KUBE_TOKEN`].
+pub const ENV_KUBE_SERVER: &str = "KUBE_SERVER";
+/// Bearer token of a service account.
+pub const ENV_KUBE_TOKEN: &str = "KUBE_TOKEN";
+/// Base64-encoded certificate authority of the API server.
+pub const ENV_KUBE_CA_DATA: &str = "KUBE_CA_DATA";
+
+/// Environment variables read by the provider.
+pub const ENV_VARS: &[&str] = &[
+    ENV_KUBECONFIG,
+    ENV_KUBE_CONFIG_DATA,
+    ENV_KUBE_SERVER,
+    ENV_KUBE_TOKEN,
+    ENV_KUBE_CA_DATA,
+];
+
+/// Kubeconfig fields whose values are credentials.
+pub(crate) const SECRET_FIELDS: &[&str] = &[
+    "token",
+    "password",
+    "client-key-data",
+    "client-certificate-data",
+    "id-token",
+    "refresh-token",
+    "access-token",
+];
@@ -0,0 +1,139 @@
+use super::constants::SECRET_FIELDS;
+
+/// Decodes standard or URL-safe base64, ignoring whitespace and padding.
+///
+/// Returns `None` for input that is not base64.
+///
+/// # Example
+///
+/// ```rust
+/// use provider::decode_base64;
+///
+/// assert_eq!(decode_base64("c2VjcmV0"), Some(b"secret".to_vec()));
+/// assert_eq!(decode_base64("c2VjcmV0IQ=="), Some(b"secret!".to_vec()));
+/// assert_eq!(decode_base64("not base64!"), None);
+/// ```
+pub fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in input.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => break,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// Encodes bytes as standard base64 with padding.
+///
+/// # Example
+///
+/// ```rust
+/// use provider::encode_base64;
+///
+/// assert_eq!(encode_base64(b"secret!"), "c2VjcmV0IQ==");
+/// ```
+pub fn encode_base64(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (u32::from(bytes[0]) << 16) | (u32::from(bytes[1]) << 8) | u32::from(bytes[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Returns the kubeconfig YAML of `data`, decoding it first if it is base64.
+pub(crate) fn decode_kubeconfig(data: &str) -> String {
+    let data = data.trim();
+    if data.contains("apiVersion") || data.contains('\n') {
+        return data.to_string();
+    }
+    decode_base64(data)
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .unwrap_or_else(|| data.to_string())
+}
+
+/// Returns the values of the credential fields of a kubeconfig.
+pub(crate) fn secret_values(kubeconfig: &str) -> Vec<String> {
+    kubeconfig
+        .lines()
+        .filter_map(|line| line.trim().trim_start_matches("- ").split_once(':'))
+        .filter(|(key, _)| SECRET_FIELDS.contains(&key.trim()))
+        .map(|(_, value)| {
+            value
+                .trim()
+                .trim_matches('"')
+                .trim_matches('\'')
+                .to_string()
+        })
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// Renders a kubeconfig of one cluster authenticated with a bearer token.
+pub(crate) fn token_kubeconfig(server: &str, token: &str, ca_data: Option<&str>) -> String {
+    let cluster = match ca_data {
+        Some(ca) => format!(
+            "    server: {}\n    certificate-authority-data: {}\n",
+            server, ca
+        ),
+        None => format!("    server: {}\n", server),
+    };
+    format!(
+        "apiVersion: v1\n\
+         kind: Config\n\
+         clusters:\n\
+         - name: default\n  cluster:\n{}\
+         users:\n\
+         - name: default\n  user:\n    token: {}\n\
+         contexts:\n\
+         - name: default\n  context:\n    cluster: default\n    user: default\n\
+         current-context: default\n",
+        cluster, token
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_values() {
+        let kubeconfig = token_kubeconfig("https://k8s.example:6443", "s3cr3t", Some("Q0E="));
+        assert_eq!(secret_values(&kubeconfig), vec!["s3cr3t".to_string()]);
+
+        let encoded = "YXBpVmVyc2lvbjogdjEKdXNlcnM6Ci0gbmFtZTogY2kKICB1c2VyOgogICAgY2xpZW50LWtleS1kYXRhOiBMUzB0Q2cK";
+        let decoded = decode_kubeconfig(encoded);
+        assert!(decoded.starts_with("apiVersion: v1\n"));
+        assert_eq!(secret_values(&decoded), vec!["LS0tCg".to_string()]);
+
+        for input in [&b""[..], b"a", b"ab", b"abc", b"\xff\x00"] {
+            assert_eq!(decode_base64(&encode_base64(input)), Some(input.to_vec()));
+        }
+    }
+}
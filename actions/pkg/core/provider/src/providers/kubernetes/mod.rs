@@ -0,0 +1,7 @@
+pub(crate) mod constants;
+mod kubeconfig;
+mod patterns;
+mod provider;
+
+pub use kubeconfig::{decode_base64, encode_base64};
+pub use provider::KubernetesProvider;
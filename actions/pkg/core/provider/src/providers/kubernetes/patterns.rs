@@ -0,0 +1,15 @@
+use lazy_static::lazy_static;
+
+lazy_static! {
+    pub static ref KUBERNETES_PATTERNS: Vec<String> = vec![
+        // Service account and OIDC tokens
+        r"eyJ[A-Za-z0-9_-]{10,}\.eyJ[A-Za-z0-9_-]{10,}\.[A-Za-z0-9_-]{10,}".to_string(),
+        // Bootstrap tokens
+        r"\b[a-z0-9]{6}\.[a-z0-9]{16}\b".to_string(),
+        // Kubeconfig credentials
+        r"(client-key-data|client-certificate-data|token|password):\s*\S+".to_string(),
+        r"-----BEGIN [A-Z ]*PRIVATE KEY-----".to_string(),
+        // Authorization headers of verbose kubectl output
+        r"Authorization: Bearer \S+".to_string(),
+    ];
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::{ProviderError, ProviderResult};
+use crate::Provider;
+
+use super::constants::{
+    ENV_KUBECONFIG, ENV_KUBE_CA_DATA, ENV_KUBE_CONFIG_DATA, ENV_KUBE_SERVER, ENV_KUBE_TOKEN,
+    ENV_VARS,
+};
+use super::kubeconfig::{decode_kubeconfig, secret_values, token_kubeconfig};
+use super::patterns::KUBERNETES_PATTERNS;
+
+/// Kubernetes cluster provider.
+///
+/// Credentials come from one of, in order of precedence:
+///
+/// - `KUBE_CONFIG_DATA`: kubeconfig contents, plain or base64-encoded;
+/// - `KUBE_SERVER` and `KUBE_TOKEN`, with an optional `KUBE_CA_DATA`;
+/// - `KUBECONFIG`: the path of an existing kubeconfig.
+///
+/// The credentials found in a kubeconfig are masked along with the
+/// variables themselves.
+#[derive(Clone)]
+pub struct KubernetesProvider {
+    environment: HashMap<String, String>,
+    kubeconfig: Option<String>,
+    secrets: Vec<String>,
+}
+
+impl KubernetesProvider {
+    /// Creates a new KubernetesProvider instance with the given environment variables.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use provider::{KubernetesProvider, Provider};
+    /// use std::collections::HashMap;
+    ///
+    /// let env = HashMap::from([
+    ///     ("KUBE_SERVER".to_string(), "https://k8s.example:6443".to_string()),
+    ///     ("KUBE_TOKEN".to_string(), "s3cr3t".to_string()),
+    /// ]);
+    /// let provider = KubernetesProvider::new(env);
+    /// assert!(provider.validate().is_ok());
+    /// assert!(provider.kubeconfig().unwrap().contains("token: s3cr3t"));
+    /// assert_eq!(provider.values(), vec!["s3cr3t"]);
+    /// ```
+    pub fn new(environment: HashMap<String, String>) -> Self {
+        let get = |key: &str| environment.get(key).filter(|v| !v.is_empty());
+        let kubeconfig = match (
+            get(ENV_KUBE_CONFIG_DATA),
+            get(ENV_KUBE_SERVER),
+            get(ENV_KUBE_TOKEN),
+        ) {
+            (Some(data), _, _) => Some(decode_kubeconfig(data)),
+            (None, Some(server), Some(token)) => Some(token_kubeconfig(
+                server,
+                token,
+                get(ENV_KUBE_CA_DATA).map(String::as_str),
+            )),
+            _ => None,
+        };
+        let secrets = kubeconfig.as_deref().map(secret_values).unwrap_or_default();
+        Self {
+            environment,
+            kubeconfig,
+            secrets,
+        }
+    }
+
+    /// Creates a provider from the Kubernetes variables of the process environment.
+    pub fn from_env() -> Self {
+        Self::new(
+            ENV_VARS
+                .iter()
+                .filter_map(|key| env::var(key).ok().map(|value| (key.to_string(), value)))
+                .collect(),
+        )
+    }
+
+    /// Returns the kubeconfig built from the environment, if it does not point to a file.
+    pub fn kubeconfig(&self) -> Option<&str> {
+        self.kubeconfig.as_deref()
+    }
+
+    /// Returns the path of the kubeconfig to use.
+    ///
+    /// A kubeconfig built from the environment is written to `path`, readable
+    /// only by the current user; otherwise the `KUBECONFIG` path is returned.
+    pub fn kubeconfig_file(&self, path: &Path) -> io::Result<Option<PathBuf>> {
+        let Some(kubeconfig) = &self.kubeconfig else {
+            return Ok(self
+                .environment
+                .get(ENV_KUBECONFIG)
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from));
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, kubeconfig)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(Some(path.to_path_buf()))
+    }
+
+    fn validate(&self) -> ProviderResult<()> {
+        if self.kubeconfig.is_some() || self.environment.contains_key(ENV_KUBECONFIG) {
+            return Ok(());
+        }
+        if self.environment.contains_key(ENV_KUBE_TOKEN) {
+            return Err(ProviderError::MissingEnvironmentVariable(
+                ENV_KUBE_SERVER.to_string(),
+            ));
+        }
+        Err(ProviderError::MissingEnvironmentVariable(
+            ENV_KUBE_CONFIG_DATA.to_string(),
+        ))
+    }
+}
+
+impl Provider for KubernetesProvider {
+    fn get_environment(&self) -> HashMap<String, String> {
+        self.environment.clone()
+    }
+
+    fn get_predefined_masked_objects(&self) -> Vec<String> {
+        KUBERNETES_PATTERNS.to_vec()
+    }
+
+    fn validate(&self) -> ProviderResult<()> {
+        self.validate()
+    }
+
+    /// Removes the credential variables from the process environment.
+    fn clean(&self) {
+        for var in [ENV_KUBE_CONFIG_DATA, ENV_KUBE_TOKEN, ENV_KUBE_CA_DATA] {
+            env::remove_var(var);
+        }
+    }
+
+    /// Returns the credentials: the kubeconfig data, the token and the
+    /// credential fields of the kubeconfig.
+    fn values(&self) -> Vec<&str> {
+        let mut values: Vec<&str> = [ENV_KUBE_CONFIG_DATA, ENV_KUBE_TOKEN]
+            .iter()
+            .filter_map(|key| self.environment.get(*key))
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+            .collect();
+        for secret in &self.secrets {
+            if !values.contains(&secret.as_str()) {
+                values.push(secret);
+            }
+        }
+        values
+    }
+
+    fn name(&self) -> String {
+        "Kubernetes".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kubeconfig_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("kube/config");
+
+        let provider = KubernetesProvider::new(HashMap::from([(
+            ENV_KUBE_CONFIG_DATA.to_string(),
+            "apiVersion: v1\nusers:\n- name: ci\n  user:\n    token: abc\n".to_string(),
+        )]));
+        assert_eq!(provider.kubeconfig_file(&path).unwrap(), Some(path.clone()));
+        assert!(fs::read_to_string(&path).unwrap().contains("token: abc"));
+        assert!(provider.values().contains(&"abc"));
+
+        let provider = KubernetesProvider::new(HashMap::from([(
+            ENV_KUBECONFIG.to_string(),
+            "/home/runner/.kube/config".to_string(),
+        )]));
+        assert!(provider.validate().is_ok());
+        assert!(provider.values().is_empty());
+        assert_eq!(
+            provider.kubeconfig_file(&path).unwrap(),
+            Some(PathBuf::from("/home/runner/.kube/config"))
+        );
+    }
+
+    #[test]
+    fn test_validate_token_without_server() {
+        let provider = KubernetesProvider::new(HashMap::from([(
+            ENV_KUBE_TOKEN.to_string(),
+            "t".to_string(),
+        )]));
+        match provider.validate() {
+            Err(ProviderError::MissingEnvironmentVariable(var)) => assert_eq!(var, "KUBE_SERVER"),
+            _ => panic!("Expected MissingEnvironmentVariable error"),
+        }
+        assert!(KubernetesProvider::new(HashMap::new()).validate().is_err());
+    }
+}
@@ -1 +1,3 @@
 pub mod aws;
+pub mod gcp;
+pub mod kubernetes;
@@ -0,0 +1,68 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Represents the actions supported by the gcloud binary.
+///
+/// Parsed from the `ACTION_GCLOUD_CMD` environment variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcloudAction {
+    /// Synchronize a local directory with a Cloud Storage bucket.
+    StorageRsync,
+
+    /// Deploy a Cloud Run service from an image or from source.
+    RunDeploy,
+
+    /// Deploy a Cloud Function.
+    FunctionsDeploy,
+}
+
+impl GcloudAction {
+    /// All supported actions, in the form accepted by `FromStr`.
+    pub const VARIANTS: &'static [&'static str] =
+        &["storage-rsync", "run-deploy", "functions-deploy"];
+
+    /// Returns the string representation of the action.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::StorageRsync => "storage-rsync",
+            Self::RunDeploy => "run-deploy",
+            Self::FunctionsDeploy => "functions-deploy",
+        }
+    }
+}
+
+impl FromStr for GcloudAction {
+    type Err = String;
+
+    /// Parses an action name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use gcloud::GcloudAction;
+    ///
+    /// let action: GcloudAction = "run-deploy".parse().unwrap();
+    /// assert_eq!(action, GcloudAction::RunDeploy);
+    ///
+    /// let err = "deploy".parse::<GcloudAction>().unwrap_err();
+    /// assert!(err.contains("storage-rsync"));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "storage-rsync" => Ok(Self::StorageRsync),
+            "run-deploy" => Ok(Self::RunDeploy),
+            "functions-deploy" => Ok(Self::FunctionsDeploy),
+            _ => Err(format!(
+                "unknown command '{}', expected one of: {}",
+                s,
+                Self::VARIANTS.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for GcloudAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,343 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Options applied to every gcloud command.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlobalOptions {
+    pub project: Option<String>,
+}
+
+impl GlobalOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_project(mut self, project: Option<String>) -> Self {
+        self.project = project;
+        self
+    }
+
+    /// Appends the options to `args`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use gcloud::command::GlobalOptions;
+    ///
+    /// let options = GlobalOptions::new().with_project(Some("acme".to_string()));
+    /// let mut args = vec!["run".to_string()];
+    /// options.apply(&mut args);
+    /// assert_eq!(args, ["run", "--project=acme"]);
+    /// ```
+    pub fn apply(&self, args: &mut Vec<String>) {
+        if let Some(project) = &self.project {
+            args.push(format!("--project={}", project));
+        }
+    }
+}
+
+/// Event that triggers a Cloud Function.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FunctionTrigger {
+    /// HTTP requests to the function URL.
+    #[default]
+    Http,
+    /// Messages published to a Pub/Sub topic.
+    Topic(String),
+    /// Changes to the objects of a Cloud Storage bucket.
+    Bucket(String),
+}
+
+impl FromStr for FunctionTrigger {
+    type Err = String;
+
+    /// Parses `http`, `topic:NAME` or `bucket:NAME`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use gcloud::command::FunctionTrigger;
+    ///
+    /// assert_eq!("http".parse(), Ok(FunctionTrigger::Http));
+    /// assert_eq!("topic:builds".parse(), Ok(FunctionTrigger::Topic("builds".to_string())));
+    /// assert!("topic:".parse::<FunctionTrigger>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "http" => Ok(Self::Http),
+            Some(("topic", name)) if !name.is_empty() => Ok(Self::Topic(name.to_string())),
+            Some(("bucket", name)) if !name.is_empty() => Ok(Self::Bucket(name.to_string())),
+            _ => Err(format!(
+                "unknown trigger '{}', expected http, topic:NAME or bucket:NAME",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for FunctionTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Http => write!(f, "http"),
+            Self::Topic(name) => write!(f, "topic:{}", name),
+            Self::Bucket(name) => write!(f, "bucket:{}", name),
+        }
+    }
+}
+
+/// Renders the `--update-env-vars` argument of a deployment.
+///
+/// Values containing commas are joined with gcloud's alternate delimiter
+/// syntax, `^DELIM^KEY=value`.
+///
+/// # Example
+///
+/// ```rust
+/// use gcloud::command::env_vars_arg;
+///
+/// let vars = vec![("MODE".to_string(), "prod".to_string())];
+/// assert_eq!(env_vars_arg(&vars), "--update-env-vars=MODE=prod");
+///
+/// let vars = vec![
+///     ("HOSTS".to_string(), "a.dev,b.dev".to_string()),
+///     ("MODE".to_string(), "prod".to_string()),
+/// ];
+/// assert_eq!(env_vars_arg(&vars), "--update-env-vars=^@^HOSTS=a.dev,b.dev@MODE=prod");
+/// ```
+pub fn env_vars_arg(vars: &[(String, String)]) -> String {
+    let pairs: Vec<String> = vars.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    if !pairs.iter().any(|p| p.contains(',')) {
+        return format!("--update-env-vars={}", pairs.join(","));
+    }
+    let delimiter = ['@', '|', ';', '#', '~']
+        .into_iter()
+        .find(|d| !pairs.iter().any(|p| p.contains(*d)))
+        .unwrap_or('@');
+    format!(
+        "--update-env-vars=^{d}^{}",
+        pairs.join(&delimiter.to_string()),
+        d = delimiter
+    )
+}
+
+/// Represents the gcloud commands run by the action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GcloudCommand {
+    /// Synchronizes a directory with a bucket.
+    StorageRsync {
+        source: String,
+        destination: String,
+        exclude: Option<String>,
+        delete: bool,
+        dry_run: bool,
+    },
+    /// Deploys a Cloud Run service from an image, or builds it from source.
+    RunDeploy {
+        service: String,
+        image: Option<String>,
+        source: PathBuf,
+        region: String,
+        env_vars: Vec<(String, String)>,
+    },
+    /// Deploys a 2nd gen Cloud Function.
+    FunctionsDeploy {
+        name: String,
+        runtime: String,
+        entry_point: Option<String>,
+        source: PathBuf,
+        region: String,
+        trigger: FunctionTrigger,
+        env_vars: Vec<(String, String)>,
+    },
+    /// Describes a Cloud Run service as JSON.
+    RunDescribe { service: String, region: String },
+    /// Describes a Cloud Function as JSON.
+    FunctionsDescribe { name: String, region: String },
+}
+
+impl GcloudCommand {
+    /// Returns the command describing what a deploy command deployed.
+    pub fn describe(&self) -> Option<GcloudCommand> {
+        match self {
+            Self::RunDeploy {
+                service, region, ..
+            } => Some(Self::RunDescribe {
+                service: service.clone(),
+                region: region.clone(),
+            }),
+            Self::FunctionsDeploy { name, region, .. } => Some(Self::FunctionsDescribe {
+                name: name.clone(),
+                region: region.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Converts the command into gcloud arguments.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use gcloud::command::GcloudCommand;
+    /// use std::path::PathBuf;
+    ///
+    /// let command = GcloudCommand::RunDeploy {
+    ///     service: "api".to_string(),
+    ///     image: Some("europe-docker.pkg.dev/acme/web/api:1.4.2".to_string()),
+    ///     source: PathBuf::from("."),
+    ///     region: "europe-west1".to_string(),
+    ///     env_vars: vec![],
+    /// };
+    /// assert_eq!(
+    ///     command.to_args(),
+    ///     [
+    ///         "run", "deploy", "api", "--image=europe-docker.pkg.dev/acme/web/api:1.4.2",
+    ///         "--region=europe-west1",
+    ///     ]
+    /// );
+    /// ```
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        match self {
+            Self::StorageRsync {
+                source,
+                destination,
+                exclude,
+                delete,
+                dry_run,
+            } => {
+                args.extend(
+                    ["storage", "rsync", source, destination, "--recursive"].map(str::to_string),
+                );
+                if *delete {
+                    args.push("--delete-unmatched-destination-objects".to_string());
+                }
+                if let Some(exclude) = exclude {
+                    args.push(format!("--exclude={}", exclude));
+                }
+                if *dry_run {
+                    args.push("--dry-run".to_string());
+                }
+            }
+            Self::RunDeploy {
+                service,
+                image,
+                source,
+                region,
+                env_vars,
+            } => {
+                args.extend(["run", "deploy", service].map(str::to_string));
+                match image {
+                    Some(image) => args.push(format!("--image={}", image)),
+                    None => args.push(format!("--source={}", source.display())),
+                }
+                args.push(format!("--region={}", region));
+                if !env_vars.is_empty() {
+                    args.push(env_vars_arg(env_vars));
+                }
+            }
+            Self::FunctionsDeploy {
+                name,
+                runtime,
+                entry_point,
+                source,
+                region,
+                trigger,
+                env_vars,
+            } => {
+                args.extend(["functions", "deploy", name, "--gen2"].map(str::to_string));
+                args.push(format!("--runtime={}", runtime));
+                args.push(format!("--source={}", source.display()));
+                args.push(format!("--region={}", region));
+                if let Some(entry_point) = entry_point {
+                    args.push(format!("--entry-point={}", entry_point));
+                }
+                args.push(match trigger {
+                    FunctionTrigger::Http => "--trigger-http".to_string(),
+                    FunctionTrigger::Topic(topic) => format!("--trigger-topic={}", topic),
+                    FunctionTrigger::Bucket(bucket) => format!("--trigger-bucket={}", bucket),
+                });
+                if !env_vars.is_empty() {
+                    args.push(env_vars_arg(env_vars));
+                }
+            }
+            Self::RunDescribe { service, region } => {
+                args.extend(["run", "services", "describe", service].map(str::to_string));
+                args.push(format!("--region={}", region));
+                args.push("--format=json".to_string());
+            }
+            Self::FunctionsDescribe { name, region } => {
+                args.extend(["functions", "describe", name, "--gen2"].map(str::to_string));
+                args.push(format!("--region={}", region));
+                args.push("--format=json".to_string());
+            }
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_args() {
+        let rsync = GcloudCommand::StorageRsync {
+            source: "dist".to_string(),
+            destination: "gs://acme-web".to_string(),
+            exclude: Some(r".*\.map$".to_string()),
+            delete: true,
+            dry_run: true,
+        };
+        assert_eq!(
+            rsync.to_args(),
+            [
+                "storage",
+                "rsync",
+                "dist",
+                "gs://acme-web",
+                "--recursive",
+                "--delete-unmatched-destination-objects",
+                r"--exclude=.*\.map$",
+                "--dry-run",
+            ]
+        );
+        assert_eq!(rsync.describe(), None);
+
+        let function = GcloudCommand::FunctionsDeploy {
+            name: "resize".to_string(),
+            runtime: "python312".to_string(),
+            entry_point: None,
+            source: PathBuf::from("functions/resize"),
+            region: "europe-west1".to_string(),
+            trigger: FunctionTrigger::Bucket("acme-uploads".to_string()),
+            env_vars: vec![("MODE".to_string(), "prod".to_string())],
+        };
+        assert_eq!(
+            function.to_args(),
+            [
+                "functions",
+                "deploy",
+                "resize",
+                "--gen2",
+                "--runtime=python312",
+                "--source=functions/resize",
+                "--region=europe-west1",
+                "--trigger-bucket=acme-uploads",
+                "--update-env-vars=MODE=prod",
+            ]
+        );
+        assert_eq!(
+            function.describe().unwrap().to_args(),
+            [
+                "functions",
+                "describe",
+                "resize",
+                "--gen2",
+                "--region=europe-west1",
+                "--format=json"
+            ]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use config::{ConfigError, ConfigValue, FileExists, Required};
+use lazy_static::lazy_static;
+use std::path::PathBuf;
+
+/// ENV keys
+pub const ENV_GCLOUD_CMD: &str = "ACTION_GCLOUD_CMD";
+pub const ENV_GCLOUD_BIN: &str = "ACTION_GCLOUD_BIN";
+pub const ENV_GCLOUD_PROJECT: &str = "ACTION_GCLOUD_PROJECT";
+pub const ENV_GCLOUD_REGION: &str = "ACTION_GCLOUD_REGION";
+pub const ENV_GCLOUD_CREDENTIALS_FILE: &str = "ACTION_GCLOUD_CREDENTIALS_FILE";
+pub const ENV_GCLOUD_STORAGE_SOURCE: &str = "ACTION_GCLOUD_STORAGE_SOURCE";
+pub const ENV_GCLOUD_STORAGE_DESTINATION: &str = "ACTION_GCLOUD_STORAGE_DESTINATION";
+pub const ENV_GCLOUD_STORAGE_EXCLUDE: &str = "ACTION_GCLOUD_STORAGE_EXCLUDE";
+pub const ENV_GCLOUD_STORAGE_DELETE: &str = "ACTION_GCLOUD_STORAGE_DELETE";
+pub const ENV_GCLOUD_STORAGE_DRY_RUN: &str = "ACTION_GCLOUD_STORAGE_DRY_RUN";
+pub const ENV_GCLOUD_RUN_SERVICE: &str = "ACTION_GCLOUD_RUN_SERVICE";
+pub const ENV_GCLOUD_RUN_IMAGE: &str = "ACTION_GCLOUD_RUN_IMAGE";
+pub const ENV_GCLOUD_RUN_SOURCE: &str = "ACTION_GCLOUD_RUN_SOURCE";
+pub const ENV_GCLOUD_FUNCTIONS_NAME: &str = "ACTION_GCLOUD_FUNCTIONS_NAME";
+pub const ENV_GCLOUD_FUNCTIONS_RUNTIME: &str = "ACTION_GCLOUD_FUNCTIONS_RUNTIME";
+pub const ENV_GCLOUD_FUNCTIONS_ENTRY_POINT: &str = "ACTION_GCLOUD_FUNCTIONS_ENTRY_POINT";
+pub const ENV_GCLOUD_FUNCTIONS_SOURCE: &str = "ACTION_GCLOUD_FUNCTIONS_SOURCE";
+pub const ENV_GCLOUD_FUNCTIONS_TRIGGER: &str = "ACTION_GCLOUD_FUNCTIONS_TRIGGER";
+pub const ENV_GCLOUD_ENV_VARS: &str = "ACTION_GCLOUD_ENV_VARS";
+
+/// Default values
+pub const DEFAULT_GCLOUD_BIN: &str = "/usr/local/bin/gcloud";
+pub const DEFAULT_EMPTY: &str = "";
+pub const DEFAULT_SOURCE: &str = ".";
+pub const DEFAULT_TRIGGER: &str = "http";
+/// Name of the credentials file written to the temporary directory by default.
+pub const DEFAULT_CREDENTIALS_NAME: &str = "action-gcloud.credentials.json";
+
+lazy_static! {
+    /// Configuration value for the gcloud command.
+    pub static ref CMD: ConfigValue<Required> =
+        ConfigValue::<Required>::required(ENV_GCLOUD_CMD)
+            .with_description("gcloud command to run: storage-rsync, run-deploy or functions-deploy.");
+
+    /// Configuration value for the gcloud executable path.
+    pub static ref GCLOUD_BIN: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_GCLOUD_BIN), ENV_GCLOUD_BIN)
+            .expand()
+            .with_validator(FileExists)
+            .with_description("Path to the gcloud executable.");
+
+    /// Configuration value for the project.
+    pub static ref PROJECT: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_GCLOUD_PROJECT)
+            .with_description("Project of all commands; defaults to GOOGLE_CLOUD_PROJECT or the project of the service account key.");
+
+    /// Configuration value for the region.
+    pub static ref REGION: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_GCLOUD_REGION)
+            .with_description("Region of run-deploy and functions-deploy, e.g. europe-west1.");
+
+    /// Configuration value for the written credentials file.
+    pub static ref CREDENTIALS_FILE: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_EMPTY), ENV_GCLOUD_CREDENTIALS_FILE)
+            .expand()
+            .with_description("File the credentials from GOOGLE_CREDENTIALS are written to; defaults to a file in the temporary directory.");
+
+    /// Configuration value for the rsync source.
+    pub static ref STORAGE_SOURCE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_GCLOUD_STORAGE_SOURCE)
+            .with_description("Source of storage-rsync: a directory relative to the working directory or a gs:// URL.");
+
+    /// Configuration value for the rsync destination.
+    pub static ref STORAGE_DESTINATION: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_GCLOUD_STORAGE_DESTINATION)
+            .with_validator(|v: &String| {
+                if v.is_empty() || v.starts_with("gs://") {
+                    Ok(())
+                } else {
+                    Err(ConfigError::invalid(v, "a gs:// URL"))
+                }
+            })
+            .with_description("Destination of storage-rsync, a gs:// URL.");
+
+    /// Configuration value for the rsync exclude pattern.
+    pub static ref STORAGE_EXCLUDE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_GCLOUD_STORAGE_EXCLUDE)
+            .with_description("Regular expression of the files storage-rsync skips, relative to the source.");
+
+    /// Configuration value for deleting unmatched objects.
+    pub static ref STORAGE_DELETE: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_GCLOUD_STORAGE_DELETE)
+            .with_description("Delete destination objects that are not in the source.");
+
+    /// Configuration value for the rsync dry run.
+    pub static ref STORAGE_DRY_RUN: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_GCLOUD_STORAGE_DRY_RUN)
+            .with_description("List what storage-rsync would copy and delete without changing anything.");
+
+    /// Configuration value for the Cloud Run service.
+    pub static ref RUN_SERVICE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_GCLOUD_RUN_SERVICE)
+            .with_description("Name of the Cloud Run service of run-deploy.");
+
+    /// Configuration value for the Cloud Run image.
+    pub static ref RUN_IMAGE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_GCLOUD_RUN_IMAGE)
+            .with_description("Container image run-deploy deploys; the service is built from its source when empty.");
+
+    /// Configuration value for the Cloud Run source.
+    pub static ref RUN_SOURCE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_SOURCE.to_string(), ENV_GCLOUD_RUN_SOURCE)
+            .with_description("Source directory run-deploy builds when no image is set, relative to the working directory.");
+
+    /// Configuration value for the Cloud Function.
+    pub static ref FUNCTIONS_NAME: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_GCLOUD_FUNCTIONS_NAME)
+            .with_description("Name of the Cloud Function of functions-deploy.");
+
+    /// Configuration value for the function runtime.
+    pub static ref FUNCTIONS_RUNTIME: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_GCLOUD_FUNCTIONS_RUNTIME)
+            .with_description("Runtime of the function, e.g. python312 or nodejs20.");
+
+    /// Configuration value for the function entry point.
+    pub static ref FUNCTIONS_ENTRY_POINT: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_GCLOUD_FUNCTIONS_ENTRY_POINT)
+            .with_description("Function in the source that is executed; defaults to the function name.");
+
+    /// Configuration value for the function source.
+    pub static ref FUNCTIONS_SOURCE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_SOURCE.to_string(), ENV_GCLOUD_FUNCTIONS_SOURCE)
+            .with_description("Source directory of the function, relative to the working directory.");
+
+    /// Configuration value for the function trigger.
+    pub static ref FUNCTIONS_TRIGGER: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_TRIGGER.to_string(), ENV_GCLOUD_FUNCTIONS_TRIGGER)
+            .with_validator(|v: &String| {
+                v.parse::<crate::command::FunctionTrigger>()
+                    .map(drop)
+                    .map_err(|_| ConfigError::invalid(v, "http, topic:NAME or bucket:NAME"))
+            })
+            .with_description("Trigger of the function: http, topic:NAME or bucket:NAME.");
+
+    /// Configuration value for the environment variables of deployments.
+    pub static ref ENV_VARS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_GCLOUD_ENV_VARS)
+            .with_validator(crate::validate_env_vars)
+            .with_description("Environment variables set on the deployed service or function, one KEY=value per line.");
+}
@@ -0,0 +1,20 @@
+use thiserror::Error;
+
+/// Represents the different errors that can occur when executing gcloud commands.
+#[derive(Error, Debug)]
+pub enum GcloudError {
+    /// Error when a gcloud command fails.
+    #[error("gcloud command failed: {0}")]
+    CommandError(String),
+
+    /// Error when the output of a gcloud command cannot be parsed.
+    #[error("Failed to parse gcloud output: {0}")]
+    ParseError(String),
+
+    /// Error from the underlying executor.
+    #[error(transparent)]
+    ExecuterError(#[from] executer::ExecuterError),
+}
+
+/// A type alias for results returned by gcloud operations.
+pub type GcloudResult<T> = Result<T, GcloudError>;
@@ -0,0 +1,167 @@
+use crate::command::{GcloudCommand, GlobalOptions};
+use crate::error::{GcloudError, GcloudResult};
+use crate::response::Deployment;
+use executer::{Context, Metrics, Output, Subprocess, Target, Validator};
+use processor::ProcessorCollection;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Environment variable gcloud reads the credentials file from.
+const ENV_CREDENTIAL_FILE_OVERRIDE: &str = "CLOUDSDK_AUTH_CREDENTIAL_FILE_OVERRIDE";
+/// Environment variable that makes gcloud fail instead of prompting.
+const ENV_DISABLE_PROMPTS: &str = "CLOUDSDK_CORE_DISABLE_PROMPTS";
+
+/// Executor responsible for running gcloud commands.
+pub struct GcloudExecutor {
+    subprocess: Subprocess,
+    gcloud_path: PathBuf,
+    dir: Option<PathBuf>,
+    options: GlobalOptions,
+    env: HashMap<String, String>,
+}
+
+impl GcloudExecutor {
+    /// Creates a new instance of `GcloudExecutor`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use gcloud::executor::GcloudExecutor;
+    /// use processor::ProcessorCollection;
+    /// use std::path::PathBuf;
+    ///
+    /// let executor = GcloudExecutor::new(
+    ///     ProcessorCollection::new(vec![]),
+    ///     PathBuf::from("/usr/local/bin/gcloud"),
+    /// )
+    /// .with_credentials(Some(PathBuf::from("/tmp/credentials.json")));
+    /// ```
+    pub fn new(processor: ProcessorCollection, gcloud_path: PathBuf) -> Self {
+        let output = Output::new(processor, Target::Stdout, Target::Stderr);
+        let subprocess = Subprocess::new(output, Validator::default());
+
+        Self {
+            subprocess,
+            gcloud_path,
+            dir: None,
+            options: GlobalOptions::default(),
+            env: HashMap::from([(ENV_DISABLE_PROMPTS.to_string(), "1".to_string())]),
+        }
+    }
+
+    /// Sets the directory sources are resolved against.
+    pub fn with_working_dir(mut self, dir: PathBuf) -> Self {
+        self.dir = Some(dir);
+        self
+    }
+
+    /// Sets the credentials file of all commands; gcloud's active account is
+    /// used when `None`.
+    pub fn with_credentials(mut self, credentials: Option<PathBuf>) -> Self {
+        match credentials {
+            Some(path) => self.env.insert(
+                ENV_CREDENTIAL_FILE_OVERRIDE.to_string(),
+                path.to_string_lossy().to_string(),
+            ),
+            None => self.env.remove(ENV_CREDENTIAL_FILE_OVERRIDE),
+        };
+        self
+    }
+
+    pub fn with_options(mut self, options: GlobalOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Records command timings and masked output lines on `metrics`.
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.subprocess = self.subprocess.with_metrics(metrics);
+        self
+    }
+
+    fn context(&self, command: &GcloudCommand) -> Context {
+        let mut cmd = vec![self.gcloud_path.to_string_lossy().to_string()];
+        cmd.extend(command.to_args());
+        self.options.apply(&mut cmd);
+        Context::new(cmd, self.env.clone(), self.dir.clone())
+    }
+
+    /// Executes a gcloud command and returns its exit status.
+    pub async fn execute(&self, command: &GcloudCommand) -> GcloudResult<i32> {
+        Ok(self.subprocess.execute(self.context(command)).await?)
+    }
+
+    /// Describes what a deploy command deployed.
+    ///
+    /// The JSON is captured instead of printed, since it contains the
+    /// environment variables of the deployment. Returns `None` for commands
+    /// that do not deploy anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GcloudError::CommandError` if the command fails and
+    /// `GcloudError::ParseError` for unexpected output.
+    pub async fn describe(&self, command: &GcloudCommand) -> GcloudResult<Option<Deployment>> {
+        let Some(describe) = command.describe() else {
+            return Ok(None);
+        };
+        let captured = self
+            .subprocess
+            .execute_captured(self.context(&describe))
+            .await?;
+        if captured.code != 0 {
+            return Err(GcloudError::CommandError(format!(
+                "gcloud {} exited with status {}",
+                describe.to_args()[..3].join(" "),
+                captured.code
+            )));
+        }
+        match describe {
+            GcloudCommand::RunDescribe { .. } => Deployment::parse_service(&captured.stdout),
+            _ => Deployment::parse_function(&captured.stdout),
+        }
+        .map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use testing::script;
+
+    #[tokio::test]
+    async fn test_deploy_and_describe() {
+        let dir = tempfile::tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let gcloud = script(
+            &dir.path().join("gcloud"),
+            &format!(
+                "echo \"$* $CLOUDSDK_AUTH_CREDENTIAL_FILE_OVERRIDE $CLOUDSDK_CORE_DISABLE_PROMPTS\" >> {}\n\
+                 if [ \"$2\" = services ]; then echo '{{\"metadata\":{{\"name\":\"api\"}},\"status\":{{\"url\":\"https://api.run.app\"}}}}'; fi",
+                calls.display()
+            ),
+        );
+
+        let executor = GcloudExecutor::new(ProcessorCollection::new(vec![]), gcloud)
+            .with_credentials(Some(PathBuf::from("/tmp/creds.json")))
+            .with_options(GlobalOptions::new().with_project(Some("acme".to_string())));
+        let command = GcloudCommand::RunDeploy {
+            service: "api".to_string(),
+            image: None,
+            source: PathBuf::from("."),
+            region: "europe-west1".to_string(),
+            env_vars: vec![],
+        };
+        assert_eq!(executor.execute(&command).await.unwrap(), 0);
+        let deployment = executor.describe(&command).await.unwrap().unwrap();
+        assert_eq!(deployment.url.as_deref(), Some("https://api.run.app"));
+        assert!(!deployment.ready);
+
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            "run deploy api --source=. --region=europe-west1 --project=acme /tmp/creds.json 1\n\
+             run services describe api --region=europe-west1 --format=json --project=acme /tmp/creds.json 1\n"
+        );
+    }
+}
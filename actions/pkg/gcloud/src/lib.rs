@@ -0,0 +1,103 @@
+pub mod action;
+pub mod command;
+pub mod constants;
+pub mod error;
+pub mod executor;
+pub mod response;
+
+use std::path::PathBuf;
+
+pub use action::GcloudAction;
+pub use command::{FunctionTrigger, GcloudCommand, GlobalOptions};
+use config::{config_struct, non_empty, ConfigError, ConfigResult};
+pub use constants::*;
+pub use executor::GcloudExecutor;
+pub use response::Deployment;
+
+config_struct! {
+    /// Represents the configuration for gcloud operations.
+    pub struct GcloudConfig {
+        /// Gets the gcloud command to execute.
+        get_cmd: GcloudAction = CMD,
+        /// Gets the gcloud executable path.
+        get_bin: PathBuf = GCLOUD_BIN,
+        /// Gets the project of all commands.
+        get_project: Option<String> = PROJECT => non_empty,
+        /// Gets the region of deployments.
+        get_region: Option<String> = REGION => non_empty,
+        /// Gets the file the provider credentials are written to.
+        get_credentials_file: PathBuf = CREDENTIALS_FILE => |p| {
+            if p.as_os_str().is_empty() {
+                std::env::temp_dir().join(DEFAULT_CREDENTIALS_NAME)
+            } else {
+                p
+            }
+        },
+        /// Gets the rsync source.
+        get_storage_source: Option<String> = STORAGE_SOURCE => non_empty,
+        /// Gets the rsync destination.
+        get_storage_destination: Option<String> = STORAGE_DESTINATION => non_empty,
+        /// Gets the rsync exclude pattern.
+        get_storage_exclude: Option<String> = STORAGE_EXCLUDE => non_empty,
+        /// Gets the delete flag of rsync.
+        get_storage_delete: bool = STORAGE_DELETE,
+        /// Gets the dry-run flag of rsync.
+        get_storage_dry_run: bool = STORAGE_DRY_RUN,
+        /// Gets the Cloud Run service.
+        get_run_service: Option<String> = RUN_SERVICE => non_empty,
+        /// Gets the Cloud Run image.
+        get_run_image: Option<String> = RUN_IMAGE => non_empty,
+        /// Gets the Cloud Run source directory.
+        get_run_source: PathBuf = RUN_SOURCE => PathBuf::from,
+        /// Gets the Cloud Function name.
+        get_functions_name: Option<String> = FUNCTIONS_NAME => non_empty,
+        /// Gets the function runtime.
+        get_functions_runtime: Option<String> = FUNCTIONS_RUNTIME => non_empty,
+        /// Gets the function entry point.
+        get_functions_entry_point: Option<String> = FUNCTIONS_ENTRY_POINT => non_empty,
+        /// Gets the function source directory.
+        get_functions_source: PathBuf = FUNCTIONS_SOURCE => PathBuf::from,
+        /// Gets the function trigger.
+        get_functions_trigger: FunctionTrigger = FUNCTIONS_TRIGGER as FunctionTrigger,
+        /// Gets the environment variables of deployments.
+        get_env_vars: Vec<(String, String)> = ENV_VARS => split_env_vars,
+    }
+}
+
+/// Splits `KEY=value` lines, skipping empty lines.
+fn split_env_vars(s: String) -> Vec<(String, String)> {
+    s.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .collect()
+}
+
+/// Checks that each non-empty line is `KEY=value`.
+#[allow(clippy::ptr_arg)]
+pub(crate) fn validate_env_vars(s: &String) -> ConfigResult<()> {
+    for line in s.lines().filter(|line| !line.trim().is_empty()) {
+        match line.split_once('=') {
+            Some((key, _)) if !key.trim().is_empty() => {}
+            _ => return Err(ConfigError::invalid(line, "KEY=value")),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_env_vars() {
+        assert_eq!(
+            split_env_vars("MODE=prod\n\nDSN = postgres://db?a=b\n".to_string()),
+            vec![
+                ("MODE".to_string(), "prod".to_string()),
+                ("DSN".to_string(), " postgres://db?a=b".to_string()),
+            ]
+        );
+        assert!(validate_env_vars(&"A=1\n\nB=".to_string()).is_ok());
+        assert!(validate_env_vars(&"A=1\nB".to_string()).is_err());
+    }
+}
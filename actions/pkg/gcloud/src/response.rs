@@ -0,0 +1,126 @@
+use crate::error::{GcloudError, GcloudResult};
+use serde_json::Value;
+
+/// What a deploy command deployed, parsed from the describe JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deployment {
+    pub name: String,
+    /// URL the service or function is reachable at.
+    pub url: Option<String>,
+    /// Revision serving the traffic, e.g. `api-00004-abc`.
+    pub revision: Option<String>,
+    /// Whether the service or function is ready to serve.
+    pub ready: bool,
+}
+
+impl Deployment {
+    /// Parses the output of `gcloud run services describe --format=json`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use gcloud::response::Deployment;
+    ///
+    /// let deployment = Deployment::parse_service(r#"{
+    ///     "metadata": {"name": "api"},
+    ///     "status": {
+    ///         "url": "https://api-abc123-ew.a.run.app",
+    ///         "latestReadyRevisionName": "api-00004-abc",
+    ///         "conditions": [{"type": "Ready", "status": "True"}]
+    ///     }
+    /// }"#).unwrap();
+    /// assert_eq!(deployment.url.as_deref(), Some("https://api-abc123-ew.a.run.app"));
+    /// assert_eq!(deployment.revision.as_deref(), Some("api-00004-abc"));
+    /// assert!(deployment.ready);
+    /// ```
+    pub fn parse_service(json: &str) -> GcloudResult<Self> {
+        let value = parse(json)?;
+        let status = &value["status"];
+        Ok(Self {
+            name: string(&value["metadata"]["name"]).unwrap_or_default(),
+            url: string(&status["url"]),
+            revision: string(&status["latestReadyRevisionName"]),
+            ready: status["conditions"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .any(|c| c["type"] == "Ready" && c["status"] == "True"),
+        })
+    }
+
+    /// Parses the output of `gcloud functions describe --gen2 --format=json`.
+    ///
+    /// The name is the last segment of the function's resource name.
+    pub fn parse_function(json: &str) -> GcloudResult<Self> {
+        let value = parse(json)?;
+        let service = &value["serviceConfig"];
+        Ok(Self {
+            name: string(&value["name"])
+                .map(|name| name.rsplit('/').next().unwrap_or_default().to_string())
+                .unwrap_or_default(),
+            url: string(&service["uri"]).or_else(|| string(&value["url"])),
+            revision: string(&service["revision"]),
+            ready: value["state"] == "ACTIVE",
+        })
+    }
+
+    /// Renders the deployment as markdown for the job summary.
+    pub fn markdown(&self) -> String {
+        let mut out = format!(
+            "### Deployed `{}`\n\n| Field | Value |\n| --- | --- |\n| Ready | {} |\n",
+            self.name,
+            if self.ready { "yes" } else { "no" }
+        );
+        for (field, value) in [("URL", &self.url), ("Revision", &self.revision)] {
+            if let Some(value) = value {
+                out.push_str(&format!("| {} | {} |\n", field, value));
+            }
+        }
+        out
+    }
+}
+
+fn parse(json: &str) -> GcloudResult<Value> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|e| GcloudError::ParseError(e.to_string()))?;
+    if !value.is_object() {
+        return Err(GcloudError::ParseError(
+            "expected a JSON object".to_string(),
+        ));
+    }
+    Ok(value)
+}
+
+fn string(value: &Value) -> Option<String> {
+    value.as_str().filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_function() {
+        let deployment = Deployment::parse_function(
+            r#"{
+                "name": "projects/acme/locations/europe-west1/functions/resize",
+                "state": "DEPLOYING",
+                "serviceConfig": {"revision": "resize-00002-xyz"},
+                "url": "https://europe-west1-acme.cloudfunctions.net/resize"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(deployment.name, "resize");
+        assert!(!deployment.ready);
+        assert_eq!(
+            deployment.markdown(),
+            "### Deployed `resize`\n\n| Field | Value |\n| --- | --- |\n| Ready | no |\n\
+             | URL | https://europe-west1-acme.cloudfunctions.net/resize |\n\
+             | Revision | resize-00002-xyz |\n"
+        );
+        assert!(matches!(
+            Deployment::parse_function("[]"),
+            Err(GcloudError::ParseError(_))
+        ));
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Represents the actions supported by the Helm binary.
+///
+/// Parsed from the `ACTION_HELM_CMD` environment variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelmAction {
+    /// Install a release, or upgrade it if it exists.
+    Upgrade,
+
+    /// Roll a release back to an earlier revision.
+    Rollback,
+
+    /// Render the manifests of a chart without installing it.
+    Template,
+
+    /// Uninstall a release.
+    Uninstall,
+}
+
+impl HelmAction {
+    /// All supported actions, in the form accepted by `FromStr`.
+    pub const VARIANTS: &'static [&'static str] = &["upgrade", "rollback", "template", "uninstall"];
+
+    /// Returns the string representation of the action.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Upgrade => "upgrade",
+            Self::Rollback => "rollback",
+            Self::Template => "template",
+            Self::Uninstall => "uninstall",
+        }
+    }
+}
+
+impl FromStr for HelmAction {
+    type Err = String;
+
+    /// Parses an action name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use helm::HelmAction;
+    ///
+    /// let action: HelmAction = "upgrade".parse().unwrap();
+    /// assert_eq!(action, HelmAction::Upgrade);
+    ///
+    /// let err = "install".parse::<HelmAction>().unwrap_err();
+    /// assert!(err.contains("upgrade"));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "upgrade" => Ok(Self::Upgrade),
+            "rollback" => Ok(Self::Rollback),
+            "template" => Ok(Self::Template),
+            "uninstall" => Ok(Self::Uninstall),
+            _ => Err(format!(
+                "unknown command '{}', expected one of: {}",
+                s,
+                Self::VARIANTS.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for HelmAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,296 @@
+use std::path::PathBuf;
+
+/// Options applied to every Helm command.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlobalOptions {
+    pub namespace: Option<String>,
+    pub kube_context: Option<String>,
+}
+
+impl GlobalOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_namespace(mut self, namespace: Option<String>) -> Self {
+        self.namespace = namespace;
+        self
+    }
+
+    pub fn with_kube_context(mut self, kube_context: Option<String>) -> Self {
+        self.kube_context = kube_context;
+        self
+    }
+
+    /// Appends the options to `args`.
+    pub fn apply(&self, args: &mut Vec<String>) {
+        for (flag, value) in [
+            ("--namespace", &self.namespace),
+            ("--kube-context", &self.kube_context),
+        ] {
+            if let Some(value) = value {
+                args.push(format!("{}={}", flag, value));
+            }
+        }
+    }
+}
+
+/// A value passed to a chart with `--set`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetValue {
+    pub key: String,
+    pub value: String,
+    /// Whether the value is masked in the output.
+    pub sensitive: bool,
+}
+
+impl SetValue {
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+            sensitive: false,
+        }
+    }
+
+    pub fn sensitive(mut self) -> Self {
+        self.sensitive = true;
+        self
+    }
+
+    /// Renders the `--set` argument, escaping the separators Helm would split on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use helm::command::SetValue;
+    ///
+    /// assert_eq!(SetValue::new("hosts", "a.dev,b.dev").to_arg(), r"hosts=a.dev\,b.dev");
+    /// ```
+    pub fn to_arg(&self) -> String {
+        format!(
+            "{}={}",
+            self.key,
+            self.value.replace('\\', r"\\").replace(',', r"\,")
+        )
+    }
+}
+
+/// Where a chart is installed from and with which values.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChartSource {
+    /// Chart path, `repo/name` or `oci://` reference.
+    pub chart: String,
+    pub version: Option<String>,
+    pub values_files: Vec<PathBuf>,
+    pub set: Vec<SetValue>,
+}
+
+impl ChartSource {
+    fn apply(&self, args: &mut Vec<String>) {
+        args.push(self.chart.clone());
+        if let Some(version) = &self.version {
+            args.push(format!("--version={}", version));
+        }
+        for file in &self.values_files {
+            args.push(format!("--values={}", file.display()));
+        }
+        for value in &self.set {
+            args.push("--set".to_string());
+            args.push(value.to_arg());
+        }
+    }
+}
+
+/// How long and whether a command waits for the release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wait {
+    pub enabled: bool,
+    pub timeout: String,
+}
+
+impl Wait {
+    fn apply(&self, args: &mut Vec<String>) {
+        if self.enabled {
+            args.push("--wait".to_string());
+        }
+        args.push(format!("--timeout={}", self.timeout));
+    }
+}
+
+/// Represents the Helm commands run by the action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HelmCommand {
+    /// Installs a release or upgrades it.
+    UpgradeInstall {
+        release: String,
+        source: ChartSource,
+        wait: Wait,
+        atomic: bool,
+        create_namespace: bool,
+    },
+
+    /// Rolls a release back; to the previous revision without `revision`.
+    Rollback {
+        release: String,
+        revision: Option<u32>,
+        wait: Wait,
+    },
+
+    /// Renders the manifests of a chart.
+    Template {
+        release: String,
+        source: ChartSource,
+    },
+
+    /// Uninstalls a release.
+    Uninstall { release: String, wait: Wait },
+
+    /// Prints the status of a release as JSON.
+    Status { release: String },
+}
+
+impl HelmCommand {
+    /// Returns the release the command operates on.
+    pub fn release(&self) -> &str {
+        match self {
+            Self::UpgradeInstall { release, .. }
+            | Self::Rollback { release, .. }
+            | Self::Template { release, .. }
+            | Self::Uninstall { release, .. }
+            | Self::Status { release } => release,
+        }
+    }
+
+    /// Returns the values passed with `--set`.
+    pub fn set_values(&self) -> &[SetValue] {
+        match self {
+            Self::UpgradeInstall { source, .. } | Self::Template { source, .. } => &source.set,
+            _ => &[],
+        }
+    }
+
+    /// Converts the command into Helm arguments.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use helm::command::{ChartSource, HelmCommand, SetValue, Wait};
+    /// use std::path::PathBuf;
+    ///
+    /// let command = HelmCommand::UpgradeInstall {
+    ///     release: "api".to_string(),
+    ///     source: ChartSource {
+    ///         chart: "charts/api".to_string(),
+    ///         version: None,
+    ///         values_files: vec![PathBuf::from("values/prod.yaml")],
+    ///         set: vec![SetValue::new("image.tag", "1.4.2")],
+    ///     },
+    ///     wait: Wait { enabled: true, timeout: "5m".to_string() },
+    ///     atomic: true,
+    ///     create_namespace: false,
+    /// };
+    /// assert_eq!(
+    ///     command.to_args(),
+    ///     [
+    ///         "upgrade", "--install", "api", "charts/api", "--values=values/prod.yaml",
+    ///         "--set", "image.tag=1.4.2", "--wait", "--timeout=5m", "--atomic",
+    ///     ]
+    /// );
+    /// ```
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        match self {
+            Self::UpgradeInstall {
+                release,
+                source,
+                wait,
+                atomic,
+                create_namespace,
+            } => {
+                args.extend(["upgrade", "--install", release].map(str::to_string));
+                source.apply(&mut args);
+                wait.apply(&mut args);
+                if *atomic {
+                    args.push("--atomic".to_string());
+                }
+                if *create_namespace {
+                    args.push("--create-namespace".to_string());
+                }
+            }
+            Self::Rollback {
+                release,
+                revision,
+                wait,
+            } => {
+                args.extend(["rollback".to_string(), release.clone()]);
+                if let Some(revision) = revision {
+                    args.push(revision.to_string());
+                }
+                wait.apply(&mut args);
+            }
+            Self::Template { release, source } => {
+                args.extend(["template".to_string(), release.clone()]);
+                source.apply(&mut args);
+            }
+            Self::Uninstall { release, wait } => {
+                args.extend(["uninstall".to_string(), release.clone()]);
+                wait.apply(&mut args);
+            }
+            Self::Status { release } => {
+                args.extend(["status", release, "--output=json"].map(str::to_string));
+            }
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_args() {
+        let wait = Wait {
+            enabled: false,
+            timeout: "90s".to_string(),
+        };
+        let rollback = HelmCommand::Rollback {
+            release: "api".to_string(),
+            revision: Some(3),
+            wait: wait.clone(),
+        };
+        assert_eq!(
+            rollback.to_args(),
+            ["rollback", "api", "3", "--timeout=90s"]
+        );
+
+        let template = HelmCommand::Template {
+            release: "api".to_string(),
+            source: ChartSource {
+                chart: "oci://registry.example/charts/api".to_string(),
+                version: Some("~1.2".to_string()),
+                set: vec![SetValue::new("db.password", r"a\b").sensitive()],
+                ..Default::default()
+            },
+        };
+        assert_eq!(
+            template.to_args(),
+            [
+                "template",
+                "api",
+                "oci://registry.example/charts/api",
+                "--version=~1.2",
+                "--set",
+                r"db.password=a\\b",
+            ]
+        );
+        assert!(template.set_values()[0].sensitive);
+        assert!(HelmCommand::Uninstall {
+            release: "api".to_string(),
+            wait
+        }
+        .set_values()
+        .is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+use config::{ConfigError, ConfigValue, FileExists, Number, Required};
+use lazy_static::lazy_static;
+use std::path::PathBuf;
+
+/// ENV keys
+pub const ENV_HELM_CMD: &str = "ACTION_HELM_CMD";
+pub const ENV_HELM_BIN: &str = "ACTION_HELM_BIN";
+pub const ENV_HELM_RELEASE: &str = "ACTION_HELM_RELEASE";
+pub const ENV_HELM_CHART: &str = "ACTION_HELM_CHART";
+pub const ENV_HELM_CHART_VERSION: &str = "ACTION_HELM_CHART_VERSION";
+pub const ENV_HELM_VALUES_FILES: &str = "ACTION_HELM_VALUES_FILES";
+pub const ENV_HELM_SET: &str = "ACTION_HELM_SET";
+pub const ENV_HELM_SET_SENSITIVE: &str = "ACTION_HELM_SET_SENSITIVE";
+pub const ENV_HELM_NAMESPACE: &str = "ACTION_HELM_NAMESPACE";
+pub const ENV_HELM_CREATE_NAMESPACE: &str = "ACTION_HELM_CREATE_NAMESPACE";
+pub const ENV_HELM_CONTEXT: &str = "ACTION_HELM_CONTEXT";
+pub const ENV_HELM_KUBECONFIG: &str = "ACTION_HELM_KUBECONFIG";
+pub const ENV_HELM_REVISION: &str = "ACTION_HELM_REVISION";
+pub const ENV_HELM_WAIT: &str = "ACTION_HELM_WAIT";
+pub const ENV_HELM_ATOMIC: &str = "ACTION_HELM_ATOMIC";
+pub const ENV_HELM_TIMEOUT: &str = "ACTION_HELM_TIMEOUT";
+
+/// Default values
+pub const DEFAULT_HELM_BIN: &str = "/usr/local/bin/helm";
+pub const DEFAULT_EMPTY: &str = "";
+pub const DEFAULT_TIMEOUT: &str = "5m";
+/// Name of the kubeconfig written to the temporary directory by default.
+pub const DEFAULT_KUBECONFIG_NAME: &str = "action-helm.kubeconfig";
+
+lazy_static! {
+    /// Configuration value for the Helm command.
+    pub static ref CMD: ConfigValue<Required> =
+        ConfigValue::<Required>::required(ENV_HELM_CMD)
+            .with_description("Helm command to run: upgrade, rollback, template or uninstall.");
+
+    /// Configuration value for the Helm executable path.
+    pub static ref HELM_BIN: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_HELM_BIN), ENV_HELM_BIN)
+            .expand()
+            .with_validator(FileExists)
+            .with_description("Path to the Helm executable.");
+
+    /// Configuration value for the release name.
+    pub static ref RELEASE: ConfigValue<Required> =
+        ConfigValue::<Required>::required(ENV_HELM_RELEASE)
+            .with_description("Name of the release.");
+
+    /// Configuration value for the chart.
+    pub static ref CHART: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_HELM_CHART)
+            .with_description("Chart of upgrade and template: a path relative to the working directory, repo/name or an oci:// reference.");
+
+    /// Configuration value for the chart version.
+    pub static ref CHART_VERSION: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_HELM_CHART_VERSION)
+            .with_description("Version constraint of the chart; defaults to the latest version.");
+
+    /// Configuration value for the values files.
+    pub static ref VALUES_FILES: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_HELM_VALUES_FILES)
+            .with_description("Comma-separated values files, relative to the working directory; later files take precedence.");
+
+    /// Configuration value for the set values.
+    pub static ref SET: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_HELM_SET)
+            .with_validator(crate::validate_set_values)
+            .with_description("Values passed with --set, one key=value per line.");
+
+    /// Configuration value for the sensitive set values.
+    pub static ref SET_SENSITIVE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_HELM_SET_SENSITIVE)
+            .sensitive()
+            .with_validator(crate::validate_set_values)
+            .with_description("Values passed with --set and masked in the output, one key=value per line.");
+
+    /// Configuration value for the namespace.
+    pub static ref NAMESPACE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_HELM_NAMESPACE)
+            .with_description("Namespace of the release; defaults to the namespace of the kubeconfig context.");
+
+    /// Configuration value for creating the namespace.
+    pub static ref CREATE_NAMESPACE: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_HELM_CREATE_NAMESPACE)
+            .with_description("Create the namespace of the release if it does not exist.");
+
+    /// Configuration value for the kubeconfig context.
+    pub static ref CONTEXT: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_HELM_CONTEXT)
+            .with_description("Kubeconfig context of all commands; defaults to the current context.");
+
+    /// Configuration value for the written kubeconfig.
+    pub static ref KUBECONFIG: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_EMPTY), ENV_HELM_KUBECONFIG)
+            .expand()
+            .with_description("File the kubeconfig from KUBE_CONFIG_DATA or KUBE_TOKEN is written to; defaults to a file in the temporary directory.");
+
+    /// Configuration value for the rollback revision.
+    pub static ref REVISION: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_HELM_REVISION)
+            .with_validator(Number::<u32>::new("a revision number").or_empty())
+            .with_description("Revision rollback returns to; defaults to the previous revision.");
+
+    /// Configuration value for waiting on the release.
+    pub static ref WAIT: ConfigValue<bool> =
+        ConfigValue::new(true, ENV_HELM_WAIT)
+            .with_description("Wait until the resources of the release are ready.");
+
+    /// Configuration value for atomic upgrades.
+    pub static ref ATOMIC: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_HELM_ATOMIC)
+            .with_description("Roll a failed upgrade back automatically.");
+
+    /// Configuration value for the timeout.
+    pub static ref TIMEOUT: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_TIMEOUT.to_string(), ENV_HELM_TIMEOUT)
+            .with_validator(|v: &String| {
+                let digits = v.trim_end_matches(['s', 'm', 'h']);
+                if !digits.is_empty() && digits.len() < v.len() && digits.bytes().all(|b| b.is_ascii_digit()) {
+                    Ok(())
+                } else {
+                    Err(ConfigError::invalid(v, "a duration such as 90s or 5m"))
+                }
+            })
+            .with_description("How long to wait for the release.");
+}
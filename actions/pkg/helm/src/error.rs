@@ -0,0 +1,20 @@
+use thiserror::Error;
+
+/// Represents the different errors that can occur when executing Helm commands.
+#[derive(Error, Debug)]
+pub enum HelmError {
+    /// Error when a Helm command fails.
+    #[error("Helm command failed: {0}")]
+    CommandError(String),
+
+    /// Error when the output of a Helm command cannot be parsed.
+    #[error("Failed to parse Helm output: {0}")]
+    ParseError(String),
+
+    /// Error from the underlying executor.
+    #[error(transparent)]
+    ExecuterError(#[from] executer::ExecuterError),
+}
+
+/// A type alias for results returned by Helm operations.
+pub type HelmResult<T> = Result<T, HelmError>;
@@ -0,0 +1,201 @@
+use crate::command::{GlobalOptions, HelmCommand};
+use crate::error::{HelmError, HelmResult};
+use crate::response::ReleaseStatus;
+use executer::{Context, Metrics, Output, Subprocess, Target, Validator};
+use processor::{MaskerRuntime, ProcessorCollection};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Environment variable Helm reads the kubeconfig path from.
+const ENV_KUBECONFIG: &str = "KUBECONFIG";
+
+/// Executor responsible for running Helm commands.
+pub struct HelmExecutor {
+    subprocess: Subprocess,
+    helm_path: PathBuf,
+    dir: Option<PathBuf>,
+    options: GlobalOptions,
+    env: HashMap<String, String>,
+    secrets: Option<MaskerRuntime>,
+}
+
+impl HelmExecutor {
+    /// Creates a new instance of `HelmExecutor`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use helm::executor::HelmExecutor;
+    /// use processor::ProcessorCollection;
+    /// use std::path::PathBuf;
+    ///
+    /// let executor = HelmExecutor::new(
+    ///     ProcessorCollection::new(vec![]),
+    ///     PathBuf::from("/usr/local/bin/helm"),
+    /// )
+    /// .with_kubeconfig(Some(PathBuf::from("/tmp/kubeconfig")));
+    /// ```
+    pub fn new(processor: ProcessorCollection, helm_path: PathBuf) -> Self {
+        let output = Output::new(processor, Target::Stdout, Target::Stderr);
+        let subprocess = Subprocess::new(output, Validator::default());
+
+        Self {
+            subprocess,
+            helm_path,
+            dir: None,
+            options: GlobalOptions::default(),
+            env: HashMap::new(),
+            secrets: None,
+        }
+    }
+
+    /// Sets the directory charts and values files are resolved against.
+    pub fn with_working_dir(mut self, dir: PathBuf) -> Self {
+        self.dir = Some(dir);
+        self
+    }
+
+    /// Sets the kubeconfig of all commands; Helm's default is used when `None`.
+    pub fn with_kubeconfig(mut self, kubeconfig: Option<PathBuf>) -> Self {
+        match kubeconfig {
+            Some(path) => self.env.insert(
+                ENV_KUBECONFIG.to_string(),
+                path.to_string_lossy().to_string(),
+            ),
+            None => self.env.remove(ENV_KUBECONFIG),
+        };
+        self
+    }
+
+    pub fn with_options(mut self, options: GlobalOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Registers the sensitive `--set` values of each command on `secrets`.
+    ///
+    /// `secrets` should be a clone of a `MaskerRuntime` that is part of the
+    /// processor collection. The base64 form of each value is registered too,
+    /// since charts commonly render values into `Secret` data.
+    pub fn with_secrets(mut self, secrets: MaskerRuntime) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
+    /// Records command timings and masked output lines on `metrics`.
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.subprocess = self.subprocess.with_metrics(metrics);
+        self
+    }
+
+    fn context(&self, command: &HelmCommand) -> Context {
+        let mut cmd = vec![self.helm_path.to_string_lossy().to_string()];
+        cmd.extend(command.to_args());
+        self.options.apply(&mut cmd);
+        Context::new(cmd, self.env.clone(), self.dir.clone())
+    }
+
+    /// Executes a Helm command and returns its exit status.
+    ///
+    /// The sensitive values of the command are registered before it runs.
+    pub async fn execute(&self, command: &HelmCommand) -> HelmResult<i32> {
+        if let Some(secrets) = &self.secrets {
+            for value in command.set_values().iter().filter(|v| v.sensitive) {
+                secrets.add(&value.value);
+                secrets.add(&provider::encode_base64(value.value.as_bytes()));
+            }
+        }
+        Ok(self.subprocess.execute(self.context(command)).await?)
+    }
+
+    /// Reads the status of a release with `helm status --output=json`.
+    ///
+    /// The JSON is captured instead of printed, since it contains the values
+    /// and notes of the release.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HelmError::CommandError` if the command fails, e.g. for an
+    /// unknown release, and `HelmError::ParseError` for unexpected output.
+    pub async fn status(&self, release: &str) -> HelmResult<ReleaseStatus> {
+        let command = HelmCommand::Status {
+            release: release.to_string(),
+        };
+        let captured = self
+            .subprocess
+            .execute_captured(self.context(&command))
+            .await?;
+        if captured.code != 0 {
+            return Err(HelmError::CommandError(format!(
+                "helm status {} exited with status {}",
+                release, captured.code
+            )));
+        }
+        ReleaseStatus::parse(&captured.stdout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::{ChartSource, SetValue, Wait};
+    use std::fs;
+    use testing::script;
+
+    #[tokio::test]
+    async fn test_upgrade_and_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let helm = script(
+            &dir.path().join("helm"),
+            &format!(
+                "echo \"$* $KUBECONFIG $(pwd)\" >> {}\n\
+                 if [ \"$1\" = status ]; then echo '{{\"name\":\"api\",\"namespace\":\"web\",\"version\":3,\"info\":{{\"status\":\"deployed\"}}}}'; fi",
+                calls.display()
+            ),
+        );
+
+        let secrets = MaskerRuntime::new("****");
+        let executor = HelmExecutor::new(ProcessorCollection::new(vec![]), helm)
+            .with_working_dir(dir.path().to_path_buf())
+            .with_kubeconfig(Some(PathBuf::from("/tmp/kc")))
+            .with_options(GlobalOptions::new().with_namespace(Some("web".to_string())))
+            .with_secrets(secrets.clone());
+        let code = executor
+            .execute(&HelmCommand::UpgradeInstall {
+                release: "api".to_string(),
+                source: ChartSource {
+                    chart: "./chart".to_string(),
+                    set: vec![
+                        SetValue::new("image.tag", "1.4.0"),
+                        SetValue::new("db.password", "s3cr3t").sensitive(),
+                    ],
+                    ..Default::default()
+                },
+                wait: Wait {
+                    enabled: false,
+                    timeout: "5m".to_string(),
+                },
+                atomic: false,
+                create_namespace: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!(code, 0);
+        assert_eq!(secrets.len(), 2);
+
+        let status = executor.status("api").await.unwrap();
+        assert_eq!(status.revision, 3);
+        assert!(status.is_deployed());
+
+        let d = dir.path().canonicalize().unwrap();
+        let d = d.display();
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            format!(
+                "upgrade --install api ./chart --set image.tag=1.4.0 --set db.password=s3cr3t --timeout=5m --namespace=web /tmp/kc {d}\n\
+                 status api --output=json --namespace=web /tmp/kc {d}\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,110 @@
+pub mod action;
+pub mod command;
+pub mod constants;
+pub mod error;
+pub mod executor;
+pub mod response;
+
+use std::path::PathBuf;
+
+pub use action::HelmAction;
+pub use command::{ChartSource, GlobalOptions, HelmCommand, SetValue, Wait};
+use config::{config_struct, non_empty, split_paths, ConfigError, ConfigResult, Optional};
+pub use constants::*;
+pub use executor::HelmExecutor;
+pub use response::ReleaseStatus;
+
+config_struct! {
+    /// Represents the configuration for Helm operations.
+    pub struct HelmConfig {
+        /// Gets the Helm command to execute.
+        get_cmd: HelmAction = CMD,
+        /// Gets the Helm executable path.
+        get_bin: PathBuf = HELM_BIN,
+        /// Gets the release name.
+        get_release: String = RELEASE,
+        /// Gets the chart of upgrade and template.
+        get_chart: String = CHART,
+        /// Gets the chart version constraint.
+        get_chart_version: Option<String> = CHART_VERSION => non_empty,
+        /// Gets the values files.
+        get_values_files: Vec<PathBuf> = VALUES_FILES => split_paths,
+        /// Gets the set values.
+        get_set: Vec<SetValue> = SET => split_set_values,
+        /// Gets the set values masked in the output.
+        get_set_sensitive: Vec<SetValue> = SET_SENSITIVE => |s| split_set_values(s).into_iter().map(SetValue::sensitive).collect(),
+        /// Gets the namespace of all commands.
+        get_namespace: Option<String> = NAMESPACE => non_empty,
+        /// Gets the create-namespace flag of upgrade.
+        get_create_namespace: bool = CREATE_NAMESPACE,
+        /// Gets the kubeconfig context of all commands.
+        get_context: Option<String> = CONTEXT => non_empty,
+        /// Gets the file the provider kubeconfig is written to.
+        get_kubeconfig: PathBuf = KUBECONFIG => |p| {
+            if p.as_os_str().is_empty() {
+                std::env::temp_dir().join(DEFAULT_KUBECONFIG_NAME)
+            } else {
+                p
+            }
+        },
+        /// Gets the revision of rollback.
+        get_revision: Option<u32> = REVISION as Optional<u32> => Optional::into_inner,
+        /// Gets the wait flag.
+        get_wait: bool = WAIT,
+        /// Gets the atomic flag of upgrade.
+        get_atomic: bool = ATOMIC,
+        /// Gets the wait timeout.
+        get_timeout: String = TIMEOUT,
+    }
+}
+
+/// Splits `key=value` lines, skipping empty lines.
+fn split_set_values(s: String) -> Vec<SetValue> {
+    s.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| SetValue::new(key.trim(), value))
+        .collect()
+}
+
+/// Checks that each non-empty line is `key=value`.
+///
+/// The error names the line instead of its content, which may be sensitive.
+#[allow(clippy::ptr_arg)]
+pub(crate) fn validate_set_values(s: &String) -> ConfigResult<()> {
+    for (index, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match line.split_once('=') {
+            Some((key, _)) if !key.trim().is_empty() => {}
+            _ => {
+                return Err(ConfigError::invalid(
+                    format!("line {}", index + 1),
+                    "key=value",
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_set_values() {
+        assert_eq!(
+            split_set_values("image.tag=1.4.0\n\n hosts = a.dev,b.dev\n".to_string()),
+            vec![
+                SetValue::new("image.tag", "1.4.0"),
+                SetValue::new("hosts", " a.dev,b.dev"),
+            ]
+        );
+        assert!(validate_set_values(&"a=1\n\nb=".to_string()).is_ok());
+        let err = validate_set_values(&"a=1\ns3cr3t".to_string()).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        assert!(!err.to_string().contains("s3cr3t"));
+        assert!(validate_set_values(&"=value".to_string()).is_err());
+    }
+}
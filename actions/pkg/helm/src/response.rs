@@ -0,0 +1,143 @@
+use crate::error::{HelmError, HelmResult};
+use serde::Deserialize;
+
+/// Status of a release, parsed from `helm status --output=json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseStatus {
+    pub name: String,
+    pub namespace: String,
+    pub revision: u32,
+    /// Status such as `deployed`, `failed` or `pending-upgrade`.
+    pub status: String,
+    pub description: String,
+    pub last_deployed: Option<String>,
+    /// Chart name and version, e.g. `api-1.4.0`.
+    pub chart: Option<String>,
+    pub app_version: Option<String>,
+}
+
+impl ReleaseStatus {
+    /// Parses the output of `helm status --output=json`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use helm::response::ReleaseStatus;
+    ///
+    /// let status = ReleaseStatus::parse(r#"{
+    ///     "name": "api",
+    ///     "namespace": "web",
+    ///     "version": 4,
+    ///     "info": {"status": "deployed", "description": "Upgrade complete"},
+    ///     "chart": {"metadata": {"name": "api", "version": "1.4.0", "appVersion": "2.1.0"}}
+    /// }"#).unwrap();
+    /// assert_eq!(status.revision, 4);
+    /// assert!(status.is_deployed());
+    /// assert_eq!(status.chart.as_deref(), Some("api-1.4.0"));
+    /// ```
+    pub fn parse(json: &str) -> HelmResult<Self> {
+        let release: ReleaseJson =
+            serde_json::from_str(json).map_err(|e| HelmError::ParseError(e.to_string()))?;
+        let metadata = release.chart.map(|chart| chart.metadata);
+        Ok(Self {
+            name: release.name,
+            namespace: release.namespace,
+            revision: release.version,
+            status: release.info.status,
+            description: release.info.description,
+            last_deployed: release.info.last_deployed.filter(|d| !d.is_empty()),
+            chart: metadata
+                .as_ref()
+                .map(|m| format!("{}-{}", m.name, m.version)),
+            app_version: metadata
+                .and_then(|m| m.app_version)
+                .filter(|v| !v.is_empty()),
+        })
+    }
+
+    pub fn is_deployed(&self) -> bool {
+        self.status == "deployed"
+    }
+
+    /// Renders the status as markdown for the job summary.
+    pub fn markdown(&self) -> String {
+        let mut out = format!(
+            "### Helm release `{}`\n\n| Field | Value |\n| --- | --- |\n\
+             | Namespace | {} |\n| Revision | {} |\n| Status | {} |\n",
+            self.name, self.namespace, self.revision, self.status
+        );
+        for (field, value) in [
+            ("Chart", &self.chart),
+            ("App version", &self.app_version),
+            ("Last deployed", &self.last_deployed),
+        ] {
+            if let Some(value) = value {
+                out.push_str(&format!("| {} | {} |\n", field, value));
+            }
+        }
+        if !self.description.is_empty() {
+            out.push_str(&format!(
+                "| Description | {} |\n",
+                self.description.replace('|', "\\|")
+            ));
+        }
+        out
+    }
+}
+
+#[derive(Deserialize)]
+struct ReleaseJson {
+    name: String,
+    #[serde(default)]
+    namespace: String,
+    version: u32,
+    info: InfoJson,
+    chart: Option<ChartJson>,
+}
+
+#[derive(Deserialize)]
+struct InfoJson {
+    status: String,
+    #[serde(default)]
+    description: String,
+    last_deployed: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChartJson {
+    metadata: MetadataJson,
+}
+
+#[derive(Deserialize)]
+struct MetadataJson {
+    name: String,
+    version: String,
+    #[serde(rename = "appVersion")]
+    app_version: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_failed_release() {
+        let status = ReleaseStatus::parse(
+            r#"{"name": "api", "version": 2, "info": {"status": "failed", "description": "Upgrade \"api\" failed: timed out | context deadline exceeded", "last_deployed": "2024-05-06T10:11:12Z"}}"#,
+        )
+        .unwrap();
+        assert!(!status.is_deployed());
+        assert_eq!(status.chart, None);
+        assert_eq!(
+            status.markdown(),
+            "### Helm release `api`\n\n| Field | Value |\n| --- | --- |\n\
+             | Namespace |  |\n| Revision | 2 |\n| Status | failed |\n\
+             | Last deployed | 2024-05-06T10:11:12Z |\n\
+             | Description | Upgrade \"api\" failed: timed out \\| context deadline exceeded |\n"
+        );
+        assert!(matches!(
+            ReleaseStatus::parse("Error: release: not found"),
+            Err(HelmError::ParseError(_))
+        ));
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Represents the actions supported by the kubectl binary.
+///
+/// Parsed from the `ACTION_KUBECTL_CMD` environment variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KubectlAction {
+    /// Apply manifests, after showing their diff, and wait for the rollouts.
+    Apply,
+
+    /// Show the changes applying the manifests would make.
+    Diff,
+
+    /// Wait for the rollouts of workloads to finish.
+    RolloutStatus,
+
+    /// Delete the resources of manifests or named resources.
+    Delete,
+}
+
+impl KubectlAction {
+    /// All supported actions, in the form accepted by `FromStr`.
+    pub const VARIANTS: &'static [&'static str] = &["apply", "diff", "rollout_status", "delete"];
+
+    /// Returns the string representation of the action.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Apply => "apply",
+            Self::Diff => "diff",
+            Self::RolloutStatus => "rollout_status",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+impl FromStr for KubectlAction {
+    type Err = String;
+
+    /// Parses an action name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kubectl::KubectlAction;
+    ///
+    /// let action: KubectlAction = "rollout_status".parse().unwrap();
+    /// assert_eq!(action, KubectlAction::RolloutStatus);
+    ///
+    /// let err = "rollout".parse::<KubectlAction>().unwrap_err();
+    /// assert!(err.contains("rollout_status"));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "apply" => Ok(Self::Apply),
+            "diff" => Ok(Self::Diff),
+            "rollout_status" => Ok(Self::RolloutStatus),
+            "delete" => Ok(Self::Delete),
+            _ => Err(format!(
+                "unknown command '{}', expected one of: {}",
+                s,
+                Self::VARIANTS.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for KubectlAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
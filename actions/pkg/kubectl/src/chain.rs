@@ -0,0 +1,150 @@
+use crate::command::KubectlCommand;
+use crate::constants::DEFAULT_TIMEOUT;
+use std::path::PathBuf;
+
+/// Builds the commands of each action from the action configuration.
+#[derive(Debug, Clone)]
+pub struct CommandChain {
+    dir: PathBuf,
+    manifests: Vec<PathBuf>,
+    resources: Vec<String>,
+    server_side: bool,
+    diff: bool,
+    wait: bool,
+    timeout: String,
+    ignore_not_found: bool,
+}
+
+impl CommandChain {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            manifests: Vec::new(),
+            resources: Vec::new(),
+            server_side: false,
+            diff: true,
+            wait: true,
+            timeout: DEFAULT_TIMEOUT.to_string(),
+            ignore_not_found: true,
+        }
+    }
+
+    /// Sets the manifests, relative to the chain directory unless absolute.
+    pub fn with_manifests(mut self, manifests: Vec<PathBuf>) -> Self {
+        self.manifests = manifests;
+        self
+    }
+
+    /// Sets the resources waited for or deleted, e.g. `deployment/api`.
+    pub fn with_resources(mut self, resources: Vec<String>) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    pub fn with_server_side(mut self, server_side: bool) -> Self {
+        self.server_side = server_side;
+        self
+    }
+
+    /// Sets whether apply shows the diff of the manifests first.
+    pub fn with_diff(mut self, diff: bool) -> Self {
+        self.diff = diff;
+        self
+    }
+
+    /// Sets whether apply waits for the rollouts of the resources.
+    pub fn with_wait(mut self, wait: bool) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: impl Into<String>) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    pub fn with_ignore_not_found(mut self, ignore_not_found: bool) -> Self {
+        self.ignore_not_found = ignore_not_found;
+        self
+    }
+
+    /// Returns the manifest paths resolved against the chain directory.
+    pub fn manifests(&self) -> Vec<PathBuf> {
+        self.manifests.iter().map(|m| self.dir.join(m)).collect()
+    }
+
+    /// Diff, apply, then wait for the rollout of every resource.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kubectl::CommandChain;
+    /// use std::path::PathBuf;
+    ///
+    /// let chain = CommandChain::new(PathBuf::from("/repo"))
+    ///     .with_manifests(vec![PathBuf::from("k8s")])
+    ///     .with_resources(vec!["deployment/api".to_string()]);
+    /// let names: Vec<_> = chain.apply_chain().iter().map(|c| c.name()).collect();
+    /// assert_eq!(names, ["diff", "apply", "rollout status"]);
+    /// ```
+    pub fn apply_chain(&self) -> Vec<KubectlCommand> {
+        let mut commands = Vec::new();
+        if self.diff {
+            commands.extend(self.diff_chain());
+        }
+        commands.push(KubectlCommand::Apply {
+            manifests: self.manifests(),
+            server_side: self.server_side,
+        });
+        if self.wait {
+            commands.extend(self.rollout_chain());
+        }
+        commands
+    }
+
+    pub fn diff_chain(&self) -> Vec<KubectlCommand> {
+        vec![KubectlCommand::Diff {
+            manifests: self.manifests(),
+            server_side: self.server_side,
+        }]
+    }
+
+    pub fn rollout_chain(&self) -> Vec<KubectlCommand> {
+        self.resources
+            .iter()
+            .map(|resource| KubectlCommand::RolloutStatus {
+                resource: resource.clone(),
+                timeout: self.timeout.clone(),
+            })
+            .collect()
+    }
+
+    pub fn delete_chain(&self) -> Vec<KubectlCommand> {
+        vec![KubectlCommand::Delete {
+            manifests: self.manifests(),
+            resources: self.resources.clone(),
+            ignore_not_found: self.ignore_not_found,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_chain_without_diff_and_wait() {
+        let chain = CommandChain::new(PathBuf::from("/repo"))
+            .with_manifests(vec![PathBuf::from("k8s/app.yaml")])
+            .with_resources(vec!["deployment/api".to_string()])
+            .with_diff(false)
+            .with_wait(false);
+        assert_eq!(
+            chain.apply_chain(),
+            vec![KubectlCommand::Apply {
+                manifests: vec![PathBuf::from("/repo/k8s/app.yaml")],
+                server_side: false,
+            }]
+        );
+    }
+}
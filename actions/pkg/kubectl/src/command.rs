@@ -0,0 +1,178 @@
+use std::path::PathBuf;
+
+/// Options applied to every kubectl command.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlobalOptions {
+    pub namespace: Option<String>,
+    pub context: Option<String>,
+}
+
+impl GlobalOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_namespace(mut self, namespace: Option<String>) -> Self {
+        self.namespace = namespace;
+        self
+    }
+
+    pub fn with_context(mut self, context: Option<String>) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Appends the options to `args`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kubectl::command::GlobalOptions;
+    ///
+    /// let options = GlobalOptions::new().with_namespace(Some("web".to_string()));
+    /// let mut args = vec!["apply".to_string()];
+    /// options.apply(&mut args);
+    /// assert_eq!(args, ["apply", "--namespace=web"]);
+    /// ```
+    pub fn apply(&self, args: &mut Vec<String>) {
+        for (flag, value) in [
+            ("--namespace", &self.namespace),
+            ("--context", &self.context),
+        ] {
+            if let Some(value) = value {
+                args.push(format!("{}={}", flag, value));
+            }
+        }
+    }
+}
+
+/// Represents the kubectl commands run by the action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KubectlCommand {
+    /// Applies manifest files or directories.
+    Apply {
+        manifests: Vec<PathBuf>,
+        server_side: bool,
+    },
+
+    /// Shows the changes applying manifests would make.
+    ///
+    /// Exits with status 1 when there are changes.
+    Diff {
+        manifests: Vec<PathBuf>,
+        server_side: bool,
+    },
+
+    /// Waits for the rollout of a workload, e.g. `deployment/api`.
+    RolloutStatus { resource: String, timeout: String },
+
+    /// Deletes the resources of manifests and named resources.
+    Delete {
+        manifests: Vec<PathBuf>,
+        resources: Vec<String>,
+        ignore_not_found: bool,
+    },
+}
+
+impl KubectlCommand {
+    /// Returns the kubectl subcommand.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Apply { .. } => "apply",
+            Self::Diff { .. } => "diff",
+            Self::RolloutStatus { .. } => "rollout status",
+            Self::Delete { .. } => "delete",
+        }
+    }
+
+    /// Returns whether the command leaves the cluster unchanged.
+    pub fn is_read_only(&self) -> bool {
+        matches!(self, Self::Diff { .. } | Self::RolloutStatus { .. })
+    }
+
+    /// Returns the manifests the command reads.
+    pub fn manifests(&self) -> &[PathBuf] {
+        match self {
+            Self::Apply { manifests, .. }
+            | Self::Diff { manifests, .. }
+            | Self::Delete { manifests, .. } => manifests,
+            Self::RolloutStatus { .. } => &[],
+        }
+    }
+
+    /// Converts the command into kubectl arguments.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kubectl::KubectlCommand;
+    /// use std::path::PathBuf;
+    ///
+    /// let command = KubectlCommand::Apply {
+    ///     manifests: vec![PathBuf::from("k8s/app.yaml"), PathBuf::from("k8s/db")],
+    ///     server_side: true,
+    /// };
+    /// assert_eq!(
+    ///     command.to_args(),
+    ///     ["apply", "-f", "k8s/app.yaml", "-f", "k8s/db", "--server-side"]
+    /// );
+    /// ```
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = self.name().split(' ').map(str::to_string).collect();
+        for manifest in self.manifests() {
+            args.push("-f".to_string());
+            args.push(manifest.to_string_lossy().to_string());
+        }
+        match self {
+            Self::Apply { server_side, .. } | Self::Diff { server_side, .. } => {
+                if *server_side {
+                    args.push("--server-side".to_string());
+                }
+            }
+            Self::RolloutStatus { resource, timeout } => {
+                args.push(resource.clone());
+                args.push(format!("--timeout={}", timeout));
+            }
+            Self::Delete {
+                resources,
+                ignore_not_found,
+                ..
+            } => {
+                args.extend(resources.iter().cloned());
+                if *ignore_not_found {
+                    args.push("--ignore-not-found".to_string());
+                }
+            }
+        }
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_args() {
+        let rollout = KubectlCommand::RolloutStatus {
+            resource: "deployment/api".to_string(),
+            timeout: "5m".to_string(),
+        };
+        assert_eq!(
+            rollout.to_args(),
+            ["rollout", "status", "deployment/api", "--timeout=5m"]
+        );
+        assert!(rollout.is_read_only());
+
+        let delete = KubectlCommand::Delete {
+            manifests: vec![],
+            resources: vec!["job/migrate".to_string()],
+            ignore_not_found: true,
+        };
+        assert_eq!(
+            delete.to_args(),
+            ["delete", "job/migrate", "--ignore-not-found"]
+        );
+        assert!(!delete.is_read_only());
+    }
+}
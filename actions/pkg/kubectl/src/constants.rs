@@ -0,0 +1,100 @@
+use config::{ConfigError, ConfigValue, FileExists, Required};
+use lazy_static::lazy_static;
+use std::path::PathBuf;
+
+/// ENV keys
+pub const ENV_KUBECTL_CMD: &str = "ACTION_KUBECTL_CMD";
+pub const ENV_KUBECTL_BIN: &str = "ACTION_KUBECTL_BIN";
+pub const ENV_KUBECTL_MANIFESTS: &str = "ACTION_KUBECTL_MANIFESTS";
+pub const ENV_KUBECTL_NAMESPACE: &str = "ACTION_KUBECTL_NAMESPACE";
+pub const ENV_KUBECTL_CONTEXT: &str = "ACTION_KUBECTL_CONTEXT";
+pub const ENV_KUBECTL_KUBECONFIG: &str = "ACTION_KUBECTL_KUBECONFIG";
+pub const ENV_KUBECTL_DIFF: &str = "ACTION_KUBECTL_DIFF";
+pub const ENV_KUBECTL_SERVER_SIDE: &str = "ACTION_KUBECTL_SERVER_SIDE";
+pub const ENV_KUBECTL_RESOURCES: &str = "ACTION_KUBECTL_RESOURCES";
+pub const ENV_KUBECTL_WAIT: &str = "ACTION_KUBECTL_WAIT";
+pub const ENV_KUBECTL_TIMEOUT: &str = "ACTION_KUBECTL_TIMEOUT";
+pub const ENV_KUBECTL_IGNORE_NOT_FOUND: &str = "ACTION_KUBECTL_IGNORE_NOT_FOUND";
+
+/// Default values
+pub const DEFAULT_KUBECTL_BIN: &str = "/usr/local/bin/kubectl";
+pub const DEFAULT_EMPTY: &str = "";
+pub const DEFAULT_TIMEOUT: &str = "5m";
+/// Name of the kubeconfig written to the temporary directory by default.
+pub const DEFAULT_KUBECONFIG_NAME: &str = "action-kubectl.kubeconfig";
+
+/// Kinds whose rollout `kubectl rollout status` can wait for.
+pub const ROLLOUT_KINDS: &[&str] = &["Deployment", "StatefulSet", "DaemonSet"];
+
+lazy_static! {
+    /// Configuration value for the kubectl command.
+    pub static ref CMD: ConfigValue<Required> =
+        ConfigValue::<Required>::required(ENV_KUBECTL_CMD)
+            .with_description("kubectl command to run: apply, diff, rollout_status or delete.");
+
+    /// Configuration value for the kubectl executable path.
+    pub static ref KUBECTL_BIN: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_KUBECTL_BIN), ENV_KUBECTL_BIN)
+            .expand()
+            .with_validator(FileExists)
+            .with_description("Path to the kubectl executable.");
+
+    /// Configuration value for the manifests.
+    pub static ref MANIFESTS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_KUBECTL_MANIFESTS)
+            .with_description("Comma-separated manifest files or directories, relative to the working directory.");
+
+    /// Configuration value for the namespace.
+    pub static ref NAMESPACE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_KUBECTL_NAMESPACE)
+            .with_description("Namespace of all commands; defaults to the namespace of the kubeconfig context.");
+
+    /// Configuration value for the kubeconfig context.
+    pub static ref CONTEXT: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_KUBECTL_CONTEXT)
+            .with_description("Kubeconfig context of all commands; defaults to the current context.");
+
+    /// Configuration value for the written kubeconfig.
+    pub static ref KUBECONFIG: ConfigValue<PathBuf> =
+        ConfigValue::new(PathBuf::from(DEFAULT_EMPTY), ENV_KUBECTL_KUBECONFIG)
+            .expand()
+            .with_description("File the kubeconfig from KUBE_CONFIG_DATA or KUBE_TOKEN is written to; defaults to a file in the temporary directory.");
+
+    /// Configuration value for the diff before apply.
+    pub static ref DIFF: ConfigValue<bool> =
+        ConfigValue::new(true, ENV_KUBECTL_DIFF)
+            .with_description("Show the diff of the manifests before applying them.");
+
+    /// Configuration value for server-side apply.
+    pub static ref SERVER_SIDE: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_KUBECTL_SERVER_SIDE)
+            .with_description("Apply and diff on the server instead of the client.");
+
+    /// Configuration value for the named resources.
+    pub static ref RESOURCES: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_KUBECTL_RESOURCES)
+            .with_description("Comma-separated resources, e.g. deployment/api; rollout_status defaults to the workloads of the manifests.");
+
+    /// Configuration value for waiting on rollouts after apply.
+    pub static ref WAIT: ConfigValue<bool> =
+        ConfigValue::new(true, ENV_KUBECTL_WAIT)
+            .with_description("Wait for the rollouts of the applied workloads.");
+
+    /// Configuration value for the rollout timeout.
+    pub static ref TIMEOUT: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_TIMEOUT.to_string(), ENV_KUBECTL_TIMEOUT)
+            .with_validator(|v: &String| {
+                let digits = v.trim_end_matches(['s', 'm', 'h']);
+                if !digits.is_empty() && digits.len() < v.len() && digits.bytes().all(|b| b.is_ascii_digit()) {
+                    Ok(())
+                } else {
+                    Err(ConfigError::invalid(v, "a duration such as 90s or 5m"))
+                }
+            })
+            .with_description("How long to wait for each rollout.");
+
+    /// Configuration value for ignoring missing resources on delete.
+    pub static ref IGNORE_NOT_FOUND: ConfigValue<bool> =
+        ConfigValue::new(true, ENV_KUBECTL_IGNORE_NOT_FOUND)
+            .with_description("Do not fail delete for resources that do not exist.");
+}
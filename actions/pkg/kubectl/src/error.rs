@@ -0,0 +1,20 @@
+use thiserror::Error;
+
+/// Represents the different errors that can occur when executing kubectl commands.
+#[derive(Error, Debug)]
+pub enum KubectlError {
+    /// Error when a kubectl command fails.
+    #[error("kubectl command failed: {0}")]
+    CommandError(String),
+
+    /// Error when a manifest cannot be read.
+    #[error("Manifest error: {0}")]
+    ManifestError(String),
+
+    /// Error from the underlying executor.
+    #[error(transparent)]
+    ExecuterError(#[from] executer::ExecuterError),
+}
+
+/// A type alias for results returned by kubectl operations.
+pub type KubectlResult<T> = Result<T, KubectlError>;
@@ -0,0 +1,201 @@
+use crate::command::{GlobalOptions, KubectlCommand};
+use crate::error::KubectlResult;
+use crate::manifest::read_secret_values;
+use executer::{Context, Metrics, Output, Subprocess, Target, Validator};
+use processor::{MaskerRuntime, ProcessorCollection};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Environment variable kubectl reads the kubeconfig path from.
+const ENV_KUBECONFIG: &str = "KUBECONFIG";
+
+/// The result of a chain of kubectl commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainResult {
+    /// Exit status of the first failed command, or `0`.
+    pub code: i32,
+    /// Whether the diff found changes; `None` without a diff.
+    pub changed: Option<bool>,
+}
+
+/// Executor responsible for running kubectl commands.
+pub struct KubectlExecutor {
+    subprocess: Subprocess,
+    kubectl_path: PathBuf,
+    options: GlobalOptions,
+    env: HashMap<String, String>,
+    secrets: Option<MaskerRuntime>,
+}
+
+impl KubectlExecutor {
+    /// Creates a new instance of `KubectlExecutor`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kubectl::executor::KubectlExecutor;
+    /// use processor::ProcessorCollection;
+    /// use std::path::PathBuf;
+    ///
+    /// let executor = KubectlExecutor::new(
+    ///     ProcessorCollection::new(vec![]),
+    ///     PathBuf::from("/usr/local/bin/kubectl"),
+    /// )
+    /// .with_kubeconfig(Some(PathBuf::from("/tmp/kubeconfig")));
+    /// ```
+    pub fn new(processor: ProcessorCollection, kubectl_path: PathBuf) -> Self {
+        let output = Output::new(processor, Target::Stdout, Target::Stderr);
+        let subprocess = Subprocess::new(output, Validator::default());
+
+        Self {
+            subprocess,
+            kubectl_path,
+            options: GlobalOptions::default(),
+            env: HashMap::new(),
+            secrets: None,
+        }
+    }
+
+    /// Sets the kubeconfig of all commands; kubectl's default is used when `None`.
+    pub fn with_kubeconfig(mut self, kubeconfig: Option<PathBuf>) -> Self {
+        match kubeconfig {
+            Some(path) => self.env.insert(
+                ENV_KUBECONFIG.to_string(),
+                path.to_string_lossy().to_string(),
+            ),
+            None => self.env.remove(ENV_KUBECONFIG),
+        };
+        self
+    }
+
+    pub fn with_options(mut self, options: GlobalOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Registers the values of the `Secret` manifests of each command on `secrets`.
+    ///
+    /// `secrets` should be a clone of a `MaskerRuntime` that is part of the
+    /// processor collection, so the values are masked in the command output.
+    pub fn with_secrets(mut self, secrets: MaskerRuntime) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
+    /// Records command timings and masked output lines on `metrics`.
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.subprocess = self.subprocess.with_metrics(metrics);
+        self
+    }
+
+    fn command_line(&self, command: &KubectlCommand) -> Vec<String> {
+        let mut cmd = vec![self.kubectl_path.to_string_lossy().to_string()];
+        cmd.extend(command.to_args());
+        self.options.apply(&mut cmd);
+        cmd
+    }
+
+    /// Executes a kubectl command and returns its exit status.
+    ///
+    /// The secrets of the command's manifests are registered before it runs.
+    pub async fn execute(&self, command: &KubectlCommand) -> KubectlResult<i32> {
+        if let Some(secrets) = &self.secrets {
+            if !command.manifests().is_empty() {
+                for value in read_secret_values(command.manifests())? {
+                    secrets.add(&value);
+                }
+            }
+        }
+        let context = Context::new(self.command_line(command), self.env.clone(), None);
+        Ok(self.subprocess.execute(context).await?)
+    }
+
+    /// Executes commands in order until one fails.
+    ///
+    /// A diff exiting with status 1 found changes and does not stop the chain.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "chain", skip_all, fields(steps = commands.len()))
+    )]
+    pub async fn execute_chain(&self, commands: &[KubectlCommand]) -> KubectlResult<ChainResult> {
+        let mut changed = None;
+        for command in commands {
+            let code = self.execute(command).await?;
+            let code = match (command, code) {
+                (KubectlCommand::Diff { .. }, 0 | 1) => {
+                    changed = Some(code == 1);
+                    0
+                }
+                _ => code,
+            };
+            if code != 0 {
+                return Ok(ChainResult { code, changed });
+            }
+        }
+        Ok(ChainResult { code: 0, changed })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use testing::script;
+
+    #[tokio::test]
+    async fn test_chain_continues_after_diff_with_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let manifest = dir.path().join("secret.yaml");
+        fs::write(&manifest, "kind: Secret\nstringData:\n  token: t0ken\n").unwrap();
+        let kubectl = script(
+            &dir.path().join("kubectl"),
+            &format!(
+                "echo \"$* $KUBECONFIG\" >> {}\nif [ \"$1\" = diff ]; then exit 1; fi\nif [ \"$1\" = rollout ]; then exit 3; fi",
+                calls.display()
+            ),
+        );
+
+        let secrets = MaskerRuntime::new("****");
+        let executor = KubectlExecutor::new(ProcessorCollection::new(vec![]), kubectl)
+            .with_kubeconfig(Some(PathBuf::from("/tmp/kc")))
+            .with_options(GlobalOptions::new().with_namespace(Some("web".to_string())))
+            .with_secrets(secrets.clone());
+        let manifests = vec![manifest.clone()];
+        let result = executor
+            .execute_chain(&[
+                KubectlCommand::Diff {
+                    manifests: manifests.clone(),
+                    server_side: false,
+                },
+                KubectlCommand::Apply {
+                    manifests,
+                    server_side: false,
+                },
+                KubectlCommand::RolloutStatus {
+                    resource: "deployment/api".to_string(),
+                    timeout: "1m".to_string(),
+                },
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            ChainResult {
+                code: 3,
+                changed: Some(true)
+            }
+        );
+        let m = manifest.display();
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            format!(
+                "diff -f {m} --namespace=web /tmp/kc\n\
+                 apply -f {m} --namespace=web /tmp/kc\n\
+                 rollout status deployment/api --timeout=1m --namespace=web /tmp/kc\n"
+            )
+        );
+        assert_eq!(secrets.len(), 1);
+    }
+}
@@ -0,0 +1,52 @@
+pub mod action;
+pub mod chain;
+pub mod command;
+pub mod constants;
+pub mod error;
+pub mod executor;
+pub mod manifest;
+
+use std::path::PathBuf;
+
+pub use action::KubectlAction;
+pub use chain::CommandChain;
+pub use command::{GlobalOptions, KubectlCommand};
+use config::{config_struct, non_empty, split_list, split_paths};
+pub use constants::*;
+pub use executor::KubectlExecutor;
+
+config_struct! {
+    /// Represents the configuration for kubectl operations.
+    pub struct KubectlConfig {
+        /// Gets the kubectl command to execute.
+        get_cmd: KubectlAction = CMD,
+        /// Gets the kubectl executable path.
+        get_bin: PathBuf = KUBECTL_BIN,
+        /// Gets the manifest files and directories.
+        get_manifests: Vec<PathBuf> = MANIFESTS => split_paths,
+        /// Gets the namespace of all commands.
+        get_namespace: Option<String> = NAMESPACE => non_empty,
+        /// Gets the kubeconfig context of all commands.
+        get_context: Option<String> = CONTEXT => non_empty,
+        /// Gets the file the provider kubeconfig is written to.
+        get_kubeconfig: PathBuf = KUBECONFIG => |p| {
+            if p.as_os_str().is_empty() {
+                std::env::temp_dir().join(DEFAULT_KUBECONFIG_NAME)
+            } else {
+                p
+            }
+        },
+        /// Gets the diff flag of apply.
+        get_diff: bool = DIFF,
+        /// Gets the server-side apply flag.
+        get_server_side: bool = SERVER_SIDE,
+        /// Gets the named resources.
+        get_resources: Vec<String> = RESOURCES => split_list,
+        /// Gets the rollout wait flag of apply.
+        get_wait: bool = WAIT,
+        /// Gets the rollout timeout.
+        get_timeout: String = TIMEOUT,
+        /// Gets the ignore-not-found flag of delete.
+        get_ignore_not_found: bool = IGNORE_NOT_FOUND,
+    }
+}
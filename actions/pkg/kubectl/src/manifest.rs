@@ -0,0 +1,232 @@
+use crate::constants::ROLLOUT_KINDS;
+use crate::error::{KubectlError, KubectlResult};
+use provider::decode_base64;
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extensions of the manifest files read from a directory.
+const MANIFEST_EXTENSIONS: &[&str] = &["yaml", "yml", "json"];
+
+/// Lists the manifest files of files and directories, like `kubectl apply -f`.
+///
+/// Directories are not searched recursively; their files are sorted.
+pub fn manifest_files(paths: &[PathBuf]) -> KubectlResult<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let entries = fs::read_dir(path)
+                .map_err(|e| KubectlError::ManifestError(format!("{}: {}", path.display(), e)))?;
+            let mut dir_files: Vec<_> = entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| {
+                    path.extension()
+                        .is_some_and(|ext| MANIFEST_EXTENSIONS.iter().any(|e| ext == *e))
+                })
+                .collect();
+            dir_files.sort();
+            files.extend(dir_files);
+        } else if path.is_file() {
+            files.push(path.clone());
+        } else {
+            return Err(KubectlError::ManifestError(format!(
+                "{} does not exist",
+                path.display()
+            )));
+        }
+    }
+    Ok(files)
+}
+
+/// Parses the documents of a manifest, flattening `List` items.
+fn documents(manifest: &str) -> KubectlResult<Vec<Value>> {
+    let mut documents = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(manifest) {
+        let value =
+            Value::deserialize(document).map_err(|e| KubectlError::ManifestError(e.to_string()))?;
+        flatten(value, &mut documents);
+    }
+    Ok(documents)
+}
+
+fn flatten(value: Value, out: &mut Vec<Value>) {
+    match value.get("items").and_then(Value::as_sequence) {
+        Some(items) if value.get("kind").and_then(Value::as_str) == Some("List") => {
+            for item in items.clone() {
+                flatten(item, out);
+            }
+        }
+        _ if !value.is_null() => out.push(value),
+        _ => {}
+    }
+}
+
+fn kind(document: &Value) -> Option<&str> {
+    document.get("kind").and_then(Value::as_str)
+}
+
+/// Returns the values of the `Secret` documents of a manifest.
+///
+/// Both the base64 `data` values and their decoded form are returned, along
+/// with each line of multiline values, so they can be masked in the diff.
+///
+/// # Example
+///
+/// ```rust
+/// use kubectl::manifest::secret_values;
+///
+/// let manifest = "\
+/// apiVersion: v1
+/// kind: Secret
+/// metadata:
+///   name: db
+/// data:
+///   password: aHVudGVyMg==
+/// stringData:
+///   user: admin
+/// ";
+/// assert_eq!(
+///     secret_values(manifest).unwrap(),
+///     vec!["aHVudGVyMg==", "hunter2", "admin"]
+/// );
+/// ```
+pub fn secret_values(manifest: &str) -> KubectlResult<Vec<String>> {
+    let mut values = Vec::new();
+    let mut push = |value: String| {
+        if value.contains('\n') {
+            for line in value.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                values.push(line.to_string());
+            }
+        }
+        if !value.trim().is_empty() {
+            values.push(value);
+        }
+    };
+    for document in documents(manifest)?
+        .iter()
+        .filter(|d| kind(d) == Some("Secret"))
+    {
+        for (field, encoded) in [("data", true), ("stringData", false)] {
+            let Some(map) = document.get(field).and_then(Value::as_mapping) else {
+                continue;
+            };
+            for value in map.values().filter_map(Value::as_str) {
+                push(value.to_string());
+                if encoded {
+                    if let Some(decoded) =
+                        decode_base64(value).and_then(|b| String::from_utf8(b).ok())
+                    {
+                        push(decoded);
+                    }
+                }
+            }
+        }
+    }
+    Ok(values)
+}
+
+/// Returns the workloads of a manifest whose rollout can be waited for,
+/// e.g. `deployment/api`.
+///
+/// # Example
+///
+/// ```rust
+/// use kubectl::manifest::workloads;
+///
+/// let manifest = "\
+/// kind: Deployment
+/// metadata:
+///   name: api
+/// ---
+/// kind: Service
+/// metadata:
+///   name: api
+/// ";
+/// assert_eq!(workloads(manifest).unwrap(), vec!["deployment/api"]);
+/// ```
+pub fn workloads(manifest: &str) -> KubectlResult<Vec<String>> {
+    Ok(documents(manifest)?
+        .iter()
+        .filter_map(|document| {
+            let kind = kind(document).filter(|k| ROLLOUT_KINDS.contains(k))?;
+            let name = document.get("metadata")?.get("name")?.as_str()?;
+            Some(format!("{}/{}", kind.to_lowercase(), name))
+        })
+        .collect())
+}
+
+/// Reads the workloads of manifest files and directories.
+pub fn read_workloads(paths: &[PathBuf]) -> KubectlResult<Vec<String>> {
+    let mut all = Vec::new();
+    for file in manifest_files(paths)? {
+        for workload in workloads(&read(&file)?)? {
+            if !all.contains(&workload) {
+                all.push(workload);
+            }
+        }
+    }
+    Ok(all)
+}
+
+/// Reads the secret values of manifest files and directories.
+pub fn read_secret_values(paths: &[PathBuf]) -> KubectlResult<Vec<String>> {
+    let mut all = Vec::new();
+    for file in manifest_files(paths)? {
+        all.extend(secret_values(&read(&file)?)?);
+    }
+    Ok(all)
+}
+
+fn read(path: &Path) -> KubectlResult<String> {
+    fs::read_to_string(path)
+        .map_err(|e| KubectlError::ManifestError(format!("{}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_and_multiline_secrets() {
+        let manifest = r#"{
+  "apiVersion": "v1",
+  "kind": "List",
+  "items": [
+    {"kind": "StatefulSet", "metadata": {"name": "db"}},
+    {"kind": "Secret", "metadata": {"name": "tls"}, "stringData": {"tls.key": "line-one\nline-two\n"}}
+  ]
+}"#;
+        assert_eq!(workloads(manifest).unwrap(), vec!["statefulset/db"]);
+        assert_eq!(
+            secret_values(manifest).unwrap(),
+            vec!["line-one", "line-two", "line-one\nline-two\n"]
+        );
+        assert!(matches!(
+            secret_values("kind: [unclosed"),
+            Err(KubectlError::ManifestError(_))
+        ));
+    }
+
+    #[test]
+    fn test_manifest_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["b.yaml", "a.yml", "README.md"] {
+            fs::write(
+                dir.path().join(name),
+                "kind: Deployment\nmetadata:\n  name: web\n",
+            )
+            .unwrap();
+        }
+        let files = manifest_files(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(
+            files,
+            vec![dir.path().join("a.yml"), dir.path().join("b.yaml")]
+        );
+        assert_eq!(
+            read_workloads(&[dir.path().to_path_buf()]).unwrap(),
+            vec!["deployment/web"]
+        );
+        assert!(manifest_files(&[dir.path().join("missing.yaml")]).is_err());
+    }
+}
@@ -0,0 +1,249 @@
+//! A blocking HTTP client for the remote sources and the APIs the actions read.
+//!
+//! Redirects are followed, and error statuses are returned as responses so
+//! callers can handle them, e.g. a `304 Not Modified`.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Default limit on the duration of one request.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Error when a request fails before a response is received.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{0}")]
+pub struct HttpError(String);
+
+/// A type alias for results of HTTP requests.
+pub type HttpResult<T> = Result<T, HttpError>;
+
+/// A request; it is a `POST` if it has a body and a `GET` otherwise.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use shared::http::Request;
+///
+/// let response = Request::get("https://app.terraform.io/api/v2/runs/run-1")
+///     .with_header("Authorization", "Bearer t0ken")
+///     .send()
+///     .unwrap();
+/// assert_eq!(response.status, 200);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Request {
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+    timeout: Duration,
+}
+
+/// A response with its status, headers (lowercased names) and body.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl Request {
+    /// Creates a request for `url` with the default timeout of 10 seconds.
+    pub fn get(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sends `body`, which makes the request a `POST`.
+    pub fn with_body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Limits the duration of the whole request, including redirects.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the URL of the request.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sends the request, following redirects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `HttpError` if the request fails before a response is
+    /// received; error statuses are returned as responses.
+    pub fn send(&self) -> HttpResult<Response> {
+        let agent = ureq::Agent::new_with_config(
+            ureq::Agent::config_builder()
+                .http_status_as_error(false)
+                .timeout_global(Some(self.timeout))
+                .build(),
+        );
+        let mut response = match &self.body {
+            Some(body) => {
+                let mut request = agent.post(&self.url);
+                for (name, value) in &self.headers {
+                    request = request.header(name, value);
+                }
+                request.send(body.as_str())
+            }
+            None => {
+                let mut request = agent.get(&self.url);
+                for (name, value) in &self.headers {
+                    request = request.header(name, value);
+                }
+                request.call()
+            }
+        }
+        .map_err(|e| HttpError(e.to_string()))?;
+
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                let value = value.to_str().ok()?;
+                Some((name.as_str().to_lowercase(), value.to_string()))
+            })
+            .collect();
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| HttpError(e.to_string()))?;
+        Ok(Response {
+            status: response.status().as_u16(),
+            headers,
+            body,
+        })
+    }
+}
+
+/// A local HTTP server for the tests of the HTTP clients.
+#[cfg(test)]
+pub(crate) mod server {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Builds a response with the given status line, extra headers and body.
+    pub fn response(status: &str, headers: &[&str], body: &str) -> String {
+        let mut out = format!("HTTP/1.1 {}\r\n", status);
+        for header in headers {
+            out.push_str(&format!("{}\r\n", header));
+        }
+        out.push_str(&format!(
+            "Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        ));
+        out
+    }
+
+    /// Serves the responses in order and sends back each request: the request
+    /// line, the headers with lowercased names and, if there is one, the body
+    /// as the last line.
+    pub fn serve(responses: Vec<String>) -> (String, mpsc::Receiver<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end().to_string();
+                    if line.is_empty() {
+                        break;
+                    }
+                    request.push(match line.split_once(':') {
+                        Some((name, value)) if !request.is_empty() => {
+                            format!("{}:{}", name.to_lowercase(), value)
+                        }
+                        _ => line,
+                    });
+                }
+                let length = request
+                    .iter()
+                    .find_map(|h| {
+                        h.strip_prefix("content-length:")
+                            .map(|l| l.trim().parse().unwrap())
+                    })
+                    .unwrap_or(0);
+                if length > 0 {
+                    let mut body = vec![0; length];
+                    reader.read_exact(&mut body).unwrap();
+                    request.push(String::from_utf8(body).unwrap());
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+                tx.send(request).unwrap();
+            }
+        });
+        (url, rx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::server::{response, serve};
+    use super::*;
+
+    #[test]
+    fn test_send_follows_redirects() {
+        let (url, requests) = serve(vec![
+            response("302 Found", &["Location: /v2"], ""),
+            response("200 OK", &["ETag: W/\"2\""], "a: 1\n"),
+        ]);
+        let response = Request::get(format!("{}/v1", url))
+            .with_header("X-Vault-Token", "s.\"x\"")
+            .send()
+            .unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            response.headers.get("etag").map(String::as_str),
+            Some("W/\"2\"")
+        );
+        assert_eq!(response.body, "a: 1\n");
+        let first = requests.recv().unwrap();
+        assert!(first[0].starts_with("GET /v1 "));
+        assert!(first.contains(&"x-vault-token: s.\"x\"".to_string()));
+        assert!(requests.recv().unwrap()[0].starts_with("GET /v2 "));
+    }
+
+    #[test]
+    fn test_send_posts_body() {
+        let (url, requests) = serve(vec![response("403 Forbidden", &[], "denied")]);
+        let response = Request::get(url).with_body("{\"a\":\n1}").send().unwrap();
+
+        assert_eq!(response.status, 403);
+        assert_eq!(response.body, "denied");
+        let request = requests.recv().unwrap();
+        assert!(request[0].starts_with("POST / "));
+        assert_eq!(request.last().unwrap(), "{\"a\":\n1}");
+    }
+
+    #[test]
+    fn test_send_fails_without_server() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        assert!(Request::get(url).send().is_err());
+    }
+}
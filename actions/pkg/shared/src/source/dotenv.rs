@@ -0,0 +1,136 @@
+//! The `.env` file format.
+//!
+//! Every line is `KEY=value`, optionally prefixed with `export`. Blank lines
+//! and lines starting with `#` are skipped. Values may be unquoted, where a
+//! `#` after whitespace starts a comment; single-quoted, taken literally; or
+//! double-quoted, where `\n`, `\t`, `\"` and `\\` are unescaped.
+
+use super::RawValue;
+use std::collections::HashMap;
+
+/// Parses `.env` content; every value is loaded as a string.
+pub(super) fn parse(content: &str) -> Result<HashMap<String, RawValue>, String> {
+    let mut values = HashMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line
+            .strip_prefix("export")
+            .filter(|rest| rest.starts_with([' ', '\t']))
+            .unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {}: expected KEY=value", index + 1));
+        };
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(format!("line {}: invalid key '{}'", index + 1, key));
+        }
+        let value = parse_value(value.trim())
+            .ok_or_else(|| format!("line {}: unterminated quote", index + 1))?;
+        values.insert(key.to_string(), RawValue::String(value));
+    }
+    Ok(values)
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(rest) = value.strip_prefix('\'') {
+        let end = rest.find('\'')?;
+        return Some(rest[..end].to_string());
+    }
+    if let Some(rest) = value.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return Some(out),
+                '\\' => match chars.next()? {
+                    'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    other => out.push(other),
+                },
+                c => out.push(c),
+            }
+        }
+        return None;
+    }
+    let end = value.find(" #").or_else(|| value.find("\t#"));
+    Some(
+        end.map_or(value, |end| &value[..end])
+            .trim_end()
+            .to_string(),
+    )
+}
+
+/// Serializes values as sorted `KEY=value` lines, quoting values where needed.
+pub(super) fn serialize(values: &HashMap<String, RawValue>) -> String {
+    let mut keys: Vec<&String> = values.keys().collect();
+    keys.sort();
+    keys.into_iter()
+        .map(|key| format!("{}={}\n", key, quote(&values[key].to_string())))
+        .collect()
+}
+
+fn quote(value: &str) -> String {
+    let plain = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "_-.,:/@+".contains(c));
+    if plain {
+        return value.to_string();
+    }
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let values = parse(
+            "# deploy settings\n\
+             export AWS_REGION=eu-west-1\n\
+             BUCKET = assets # the site bucket\n\
+             PASSWORD='p#ss w\\rd'\n\
+             GREETING=\"hello\\n\\\"world\\\"\"\n\
+             EMPTY=\n\
+             exporter=yes\n",
+        )
+        .unwrap();
+        let get = |key: &str| values.get(key).map(|v| v.to_string());
+        assert_eq!(get("AWS_REGION").as_deref(), Some("eu-west-1"));
+        assert_eq!(get("BUCKET").as_deref(), Some("assets"));
+        assert_eq!(get("PASSWORD").as_deref(), Some("p#ss w\\rd"));
+        assert_eq!(get("GREETING").as_deref(), Some("hello\n\"world\""));
+        assert_eq!(get("EMPTY").as_deref(), Some(""));
+        assert_eq!(get("exporter").as_deref(), Some("yes"));
+        assert_eq!(values.len(), 6);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse("A=1\nB").unwrap_err(), "line 2: expected KEY=value");
+        assert_eq!(parse("A=\"open").unwrap_err(), "line 1: unterminated quote");
+        assert!(parse("MY KEY=1").is_err());
+    }
+
+    #[test]
+    fn test_serialize_quotes() {
+        let values = HashMap::from([
+            ("URL".to_string(), RawValue::from("https://example.com/a")),
+            ("MESSAGE".to_string(), RawValue::from("say \"hi\"\n")),
+        ]);
+        let content = serialize(&values);
+        assert_eq!(
+            content,
+            "MESSAGE=\"say \\\"hi\\\"\\n\"\nURL=https://example.com/a\n"
+        );
+        assert_eq!(parse(&content).unwrap(), values);
+    }
+}
@@ -0,0 +1,339 @@
+use super::watch::diff;
+use super::{RawValue, Source, SourceError, SourceEvent, SourceResult, Watch};
+use std::collections::HashMap;
+use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// File the runner reads environment variables for later steps from.
+const ENV_GITHUB_ENV: &str = "GITHUB_ENV";
+
+/// Reads configuration values from the process environment.
+///
+/// Every value is loaded as a `RawValue::String`. [`Source::set`] changes the
+/// environment of the process and [`Source::save`] exports the changed
+/// variables to later workflow steps.
+#[derive(Debug, Clone, Default)]
+pub struct EnvSource {
+    prefix: String,
+    sensitive_keys: Vec<String>,
+    export_file: Option<PathBuf>,
+    changes: Vec<EnvChange>,
+}
+
+/// A variable changed with [`Source::set`]; values are not recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvChange {
+    /// Name of the variable, including the prefix of the source.
+    pub name: String,
+    /// Whether the variable was set before.
+    pub replaced: bool,
+    /// Whether the value must be masked.
+    pub sensitive: bool,
+}
+
+impl EnvSource {
+    /// Creates a source over all environment variables.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the source to variables starting with `prefix`.
+    ///
+    /// Keys are given without the prefix, so with the prefix `ACTION_` the key
+    /// `LOG_LEVEL` reads `ACTION_LOG_LEVEL`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use shared::source::{EnvSource, RawValue, Source};
+    /// use std::env;
+    ///
+    /// env::set_var("EXAMPLE_PREFIX_LEVEL", "debug");
+    /// let source = EnvSource::new().with_prefix("EXAMPLE_PREFIX_");
+    /// assert_eq!(source.get("LEVEL").unwrap(), Some(RawValue::from("debug")));
+    /// ```
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Marks keys whose values must be masked.
+    pub fn with_sensitive_keys(mut self, keys: &[&str]) -> Self {
+        self.sensitive_keys
+            .extend(keys.iter().map(|k| k.to_string()));
+        self
+    }
+
+    /// Sets the file [`Source::save`] appends to, instead of the `GITHUB_ENV` file.
+    pub fn with_export_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.export_file = Some(path.into());
+        self
+    }
+
+    /// Returns the variables changed with [`Source::set`], in order.
+    pub fn changes(&self) -> &[EnvChange] {
+        &self.changes
+    }
+
+    /// Renders the changed variables in the `GITHUB_ENV` file format, with
+    /// their current values.
+    ///
+    /// Multiline values use the `name<<DELIMITER` syntax.
+    fn render(&self) -> String {
+        let mut names: Vec<&str> = Vec::new();
+        for change in &self.changes {
+            if !names.contains(&change.name.as_str()) {
+                names.push(&change.name);
+            }
+        }
+        let mut out = String::new();
+        for name in names {
+            let Ok(value) = env::var(name) else {
+                continue;
+            };
+            if value.contains('\n') {
+                let mut delimiter = "ACTION_ENV_EOF".to_string();
+                while value.lines().any(|line| line == delimiter) {
+                    delimiter.push('_');
+                }
+                out.push_str(&format!(
+                    "{}<<{}\n{}\n{}\n",
+                    name, delimiter, value, delimiter
+                ));
+            } else {
+                out.push_str(&format!("{}={}\n", name, value));
+            }
+        }
+        out
+    }
+
+    fn export_file(&self) -> Option<PathBuf> {
+        self.export_file.clone().or_else(|| {
+            env::var_os(ENV_GITHUB_ENV)
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from)
+        })
+    }
+}
+
+fn invalid(key: &str, expected: &'static str, value: &str) -> SourceError {
+    SourceError::Conversion {
+        key: key.to_string(),
+        expected,
+        value: value.to_string(),
+    }
+}
+
+fn append(path: &Path, content: &str) -> SourceResult<()> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|e| SourceError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })
+}
+
+impl Source for EnvSource {
+    fn name(&self) -> &str {
+        "env"
+    }
+
+    fn load(&self) -> SourceResult<HashMap<String, RawValue>> {
+        Ok(env::vars()
+            .filter_map(|(k, v)| {
+                k.strip_prefix(self.prefix.as_str())
+                    .map(|k| (k.to_string(), RawValue::String(v)))
+            })
+            .collect())
+    }
+
+    fn get(&self, key: &str) -> SourceResult<Option<RawValue>> {
+        Ok(env::var(format!("{}{}", self.prefix, key))
+            .ok()
+            .map(RawValue::String))
+    }
+
+    fn sensitive_keys(&self) -> &[String] {
+        &self.sensitive_keys
+    }
+
+    /// Watches the environment by comparing snapshots of it.
+    fn watch(&self) -> Option<Box<dyn Watch>> {
+        Some(Box::new(EnvWatch {
+            last: self.load().unwrap_or_default(),
+            source: self.clone(),
+        }))
+    }
+
+    /// Sets the variable `key`, with the prefix of the source, in the process environment.
+    ///
+    /// Lists are joined with commas and null sets an empty value.
+    fn set(&mut self, key: &str, value: RawValue) -> SourceResult<()> {
+        let name = format!("{}{}", self.prefix, key);
+        if name.is_empty() || name.contains(['=', '\0']) {
+            return Err(invalid(key, "an environment variable name", &name));
+        }
+        let value = value.to_string();
+        if value.contains('\0') {
+            return Err(invalid(key, "a value without NUL bytes", ""));
+        }
+        self.changes.push(EnvChange {
+            replaced: env::var_os(&name).is_some(),
+            sensitive: self.is_sensitive(key),
+            name: name.clone(),
+        });
+        env::set_var(name, value);
+        Ok(())
+    }
+
+    /// Appends the changed variables to the export file or to the `GITHUB_ENV` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SourceError::Unsupported` when there is no file to export to.
+    fn save(&self) -> SourceResult<()> {
+        let Some(path) = self.export_file() else {
+            return Err(SourceError::Unsupported {
+                source_name: self.name().to_string(),
+                operation: "save",
+            });
+        };
+        if self.changes.is_empty() {
+            return Ok(());
+        }
+        append(&path, &self.render())
+    }
+}
+
+/// Compares snapshots of the environment.
+struct EnvWatch {
+    source: EnvSource,
+    last: HashMap<String, RawValue>,
+}
+
+impl Watch for EnvWatch {
+    fn poll(&mut self) -> SourceResult<Vec<SourceEvent>> {
+        let current = self.source.load()?;
+        let events = diff(&self.last, &current);
+        self.last = current;
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        env::set_var("ENV_SOURCE_GET", "value");
+        env::remove_var("ENV_SOURCE_MISSING");
+        let source = EnvSource::new();
+        assert_eq!(
+            source.get("ENV_SOURCE_GET").unwrap(),
+            Some(RawValue::from("value"))
+        );
+        assert_eq!(source.get("ENV_SOURCE_MISSING").unwrap(), None);
+    }
+
+    #[test]
+    fn test_load_with_prefix() {
+        env::set_var("ENV_SOURCE_LOAD_A", "a");
+        env::set_var("ENV_SOURCE_LOAD_B", "b");
+        let values = EnvSource::new()
+            .with_prefix("ENV_SOURCE_LOAD_")
+            .load()
+            .unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values.get("A"), Some(&RawValue::from("a")));
+    }
+
+    #[test]
+    fn test_sensitive_keys() {
+        let source = EnvSource::new().with_sensitive_keys(&["TOKEN"]);
+        assert!(source.is_sensitive("TOKEN"));
+        assert!(!source.is_sensitive("LEVEL"));
+    }
+
+    #[test]
+    fn test_watch() {
+        env::set_var("ENV_SOURCE_WATCH_A", "1");
+        let mut watch = EnvSource::new()
+            .with_prefix("ENV_SOURCE_WATCH_")
+            .watch()
+            .unwrap();
+        assert!(watch.poll().unwrap().is_empty());
+
+        env::set_var("ENV_SOURCE_WATCH_A", "2");
+        env::set_var("ENV_SOURCE_WATCH_B", "new");
+        let events = watch.poll().unwrap();
+        assert_eq!(
+            events
+                .iter()
+                .map(|e| (e.key.as_str(), e.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                ("A", crate::source::ChangeKind::Changed),
+                ("B", crate::source::ChangeKind::Added)
+            ]
+        );
+        assert!(watch.poll().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_set_and_save() {
+        env::set_var("ENV_SOURCE_SET_REGION", "eu-west-1");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("github_env");
+        let mut source = EnvSource::new()
+            .with_prefix("ENV_SOURCE_SET_")
+            .with_sensitive_keys(&["TOKEN"])
+            .with_export_file(&path);
+
+        source.set("REGION", RawValue::from("us-east-1")).unwrap();
+        source.set("TOKEN", RawValue::from("s3cr3t")).unwrap();
+        source
+            .set("NOTES", RawValue::from("a\nACTION_ENV_EOF\nb"))
+            .unwrap();
+        source.set("REGION", RawValue::Integer(2)).unwrap();
+        assert_eq!(env::var("ENV_SOURCE_SET_REGION").unwrap(), "2");
+        assert_eq!(
+            source.changes()[..2],
+            [
+                EnvChange {
+                    name: "ENV_SOURCE_SET_REGION".to_string(),
+                    replaced: true,
+                    sensitive: false,
+                },
+                EnvChange {
+                    name: "ENV_SOURCE_SET_TOKEN".to_string(),
+                    replaced: false,
+                    sensitive: true,
+                },
+            ]
+        );
+
+        source.save().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "ENV_SOURCE_SET_REGION=2\n\
+             ENV_SOURCE_SET_TOKEN=s3cr3t\n\
+             ENV_SOURCE_SET_NOTES<<ACTION_ENV_EOF_\na\nACTION_ENV_EOF\nb\nACTION_ENV_EOF_\n"
+        );
+    }
+
+    #[test]
+    fn test_set_invalid_name() {
+        let mut source = EnvSource::new();
+        assert!(matches!(
+            source.set("A=B", RawValue::from("v")),
+            Err(SourceError::Conversion { .. })
+        ));
+        assert!(source.changes().is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Represents the different errors that can occur when reading a configuration source.
+#[derive(Error, Debug, Clone)]
+pub enum SourceError {
+    /// Error when a source file cannot be read.
+    #[error("Cannot read {path:?}: {message}")]
+    Io { path: PathBuf, message: String },
+
+    /// Error when a source file has invalid content.
+    #[error("Cannot parse {path:?}: {message}")]
+    Parse { path: PathBuf, message: String },
+
+    /// Error when a remote source cannot be fetched or its content is invalid.
+    #[error("Cannot fetch {url}: {message}")]
+    Http { url: String, message: String },
+
+    /// Error when a remote store cannot be read or returns invalid content.
+    #[error("Cannot read {source_name}: {message}")]
+    Remote {
+        source_name: String,
+        message: String,
+    },
+
+    /// Error when a value cannot be converted to the requested type.
+    #[error("Invalid value for {key}: expected {expected}, got '{value}'")]
+    Conversion {
+        key: String,
+        expected: &'static str,
+        value: String,
+    },
+
+    /// Error when a required key is not present.
+    #[error("Missing required key: {0}")]
+    Missing(String),
+
+    /// Error when the source does not support an operation.
+    #[error("{source_name} does not support {operation}")]
+    Unsupported {
+        source_name: String,
+        operation: &'static str,
+    },
+}
+
+/// A type alias for results returned by configuration sources.
+pub type SourceResult<T> = Result<T, SourceError>;
@@ -0,0 +1,612 @@
+use super::watch::diff;
+use super::{dotenv, ini, RawValue, Source, SourceError, SourceEvent, SourceResult, Watch};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Supported configuration file formats.
+///
+/// Nested maps are flattened into dotted keys, so `{"s3": {"bucket": "b"}}`
+/// provides the key `s3.bucket`. `.env` files are flat and only hold strings,
+/// as do INI files, whose sections are flattened the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    /// JSON (`.json`).
+    Json,
+    /// YAML (`.yaml`, `.yml`).
+    Yaml,
+    /// TOML (`.toml`); dates and times are loaded as strings.
+    Toml,
+    /// `KEY=value` lines (`.env`, `*.env`), shared with local development tools.
+    Dotenv,
+    /// INI and Java properties (`.ini`, `.properties`); `[section]` keys load as `section.key`.
+    Ini,
+}
+
+impl FileFormat {
+    /// Detects the format from the file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        if path.file_name()? == ".env" {
+            return Some(Self::Dotenv);
+        }
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            "env" => Some(Self::Dotenv),
+            "ini" | "properties" => Some(Self::Ini),
+            _ => None,
+        }
+    }
+
+    /// Parses file content into flat `key -> value` pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns the parser error message if the content is invalid or its root is not a map.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use shared::source::{FileFormat, RawValue};
+    ///
+    /// let values = FileFormat::Json.parse(r#"{"s3": {"bucket": "b"}}"#).unwrap();
+    /// assert_eq!(values.get("s3.bucket"), Some(&RawValue::from("b")));
+    /// ```
+    pub fn parse(&self, content: &str) -> Result<HashMap<String, RawValue>, String> {
+        let root = match self {
+            Self::Json => {
+                serde_json::from_str::<serde_json::Value>(content).map_err(|e| e.to_string())?
+            }
+            Self::Yaml => {
+                serde_yaml::from_str::<serde_json::Value>(content).map_err(|e| e.to_string())?
+            }
+            Self::Toml => {
+                toml_to_json(toml::from_str::<toml::Value>(content).map_err(|e| e.to_string())?)
+            }
+            Self::Dotenv => return dotenv::parse(content),
+            Self::Ini => return ini::parse(content),
+        };
+        let serde_json::Value::Object(map) = root else {
+            return Err("root element must be a map".to_string());
+        };
+
+        let mut values = HashMap::new();
+        flatten("", map, &mut values);
+        Ok(values)
+    }
+
+    /// Serializes flat `key -> value` pairs.
+    ///
+    /// TOML has no null, so null values are left out of TOML files.
+    ///
+    /// # Errors
+    ///
+    /// Returns the serializer error message on failure.
+    pub fn serialize(&self, values: &HashMap<String, RawValue>) -> Result<String, String> {
+        let mut keys: Vec<&String> = values.keys().collect();
+        keys.sort();
+        let map: serde_json::Map<String, serde_json::Value> = keys
+            .into_iter()
+            .map(|k| (k.clone(), to_json(&values[k])))
+            .collect();
+        let root = serde_json::Value::Object(map);
+
+        match self {
+            Self::Json => serde_json::to_string_pretty(&root).map_err(|e| e.to_string()),
+            Self::Yaml => serde_yaml::to_string(&root).map_err(|e| e.to_string()),
+            Self::Toml => match json_to_toml(root) {
+                Some(table) => toml::to_string(&table).map_err(|e| e.to_string()),
+                None => Ok(String::new()),
+            },
+            Self::Dotenv => Ok(dotenv::serialize(values)),
+            Self::Ini => Ok(ini::serialize(values)),
+        }
+    }
+}
+
+/// Reads configuration values from a JSON, YAML, TOML, `.env` or INI file.
+///
+/// The parsed values are cached. Every access checks the modification time
+/// and size of the file, unless the last check is more recent than the cache
+/// TTL, and the file is only parsed again when its content changed. Clones
+/// share the cache.
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    path: PathBuf,
+    format: FileFormat,
+    optional: bool,
+    sensitive_keys: Vec<String>,
+    ttl: Duration,
+    cache: Arc<Mutex<Option<Cached>>>,
+}
+
+/// Values parsed from the file and what they were parsed from.
+#[derive(Debug)]
+struct Cached {
+    stamp: Stamp,
+    hash: u64,
+    checked: Instant,
+    values: HashMap<String, RawValue>,
+}
+
+impl FileSource {
+    /// Creates a source over the file at `path` in the given format.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use shared::source::{FileFormat, FileSource, RawValue, Source};
+    /// use tempfile::tempdir;
+    ///
+    /// let dir = tempdir().unwrap();
+    /// let path = dir.path().join("config.json");
+    /// std::fs::write(&path, r#"{"workspace": "prod", "retries": 3}"#).unwrap();
+    ///
+    /// let source = FileSource::new(&path, FileFormat::Json);
+    /// assert_eq!(source.get("retries").unwrap(), Some(RawValue::Integer(3)));
+    /// ```
+    pub fn new(path: impl Into<PathBuf>, format: FileFormat) -> Self {
+        Self {
+            path: path.into(),
+            format,
+            optional: false,
+            sensitive_keys: Vec::new(),
+            ttl: Duration::ZERO,
+            cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Creates a source detecting the format from the file extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SourceError::Parse` if the extension is not supported.
+    pub fn from_path(path: impl Into<PathBuf>) -> SourceResult<Self> {
+        let path = path.into();
+        let format = FileFormat::from_path(&path).ok_or_else(|| SourceError::Parse {
+            path: path.clone(),
+            message: "unsupported file extension".to_string(),
+        })?;
+        Ok(Self::new(path, format))
+    }
+
+    /// Treats a missing file as an empty source instead of an error.
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Marks keys whose values must be masked.
+    pub fn with_sensitive_keys(mut self, keys: &[&str]) -> Self {
+        self.sensitive_keys
+            .extend(keys.iter().map(|k| k.to_string()));
+        self
+    }
+
+    /// Trusts the cached values for `ttl` without checking the file.
+    ///
+    /// Defaults to zero, so changes of the file are picked up on the next access.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Drops the cached values and reads the file again.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SourceError` if the file cannot be read or parsed.
+    pub fn reload(&self) -> SourceResult<HashMap<String, RawValue>> {
+        self.lock().take();
+        self.load()
+    }
+
+    /// Returns the path of the source file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the format of the source file.
+    pub fn format(&self) -> FileFormat {
+        self.format
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<Cached>> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Runs `f` on the current values, reading the file only when it changed.
+    fn with_values<T>(&self, f: impl FnOnce(&HashMap<String, RawValue>) -> T) -> SourceResult<T> {
+        let mut cache = self.lock();
+        if let Some(cached) = cache.as_mut() {
+            if cached.checked.elapsed() < self.ttl {
+                return Ok(f(&cached.values));
+            }
+            let current = stamp(&self.path);
+            if current.is_some() && current == cached.stamp {
+                cached.checked = Instant::now();
+                return Ok(f(&cached.values));
+            }
+        }
+
+        let stamp = stamp(&self.path);
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if self.optional && e.kind() == std::io::ErrorKind::NotFound => {
+                *cache = None;
+                return Ok(f(&HashMap::new()));
+            }
+            Err(e) => {
+                *cache = None;
+                return Err(SourceError::Io {
+                    path: self.path.clone(),
+                    message: e.to_string(),
+                });
+            }
+        };
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        if let Some(cached) = cache.as_mut().filter(|cached| cached.hash == hash) {
+            cached.stamp = stamp;
+            cached.checked = Instant::now();
+            return Ok(f(&cached.values));
+        }
+        let values = self
+            .format
+            .parse(&content)
+            .map_err(|message| SourceError::Parse {
+                path: self.path.clone(),
+                message,
+            })?;
+        let cached = cache.insert(Cached {
+            stamp,
+            hash,
+            checked: Instant::now(),
+            values,
+        });
+        Ok(f(&cached.values))
+    }
+}
+
+impl Source for FileSource {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    fn load(&self) -> SourceResult<HashMap<String, RawValue>> {
+        self.with_values(HashMap::clone)
+    }
+
+    fn get(&self, key: &str) -> SourceResult<Option<RawValue>> {
+        self.with_values(|values| values.get(key).cloned())
+    }
+
+    fn sensitive_keys(&self) -> &[String] {
+        &self.sensitive_keys
+    }
+
+    /// Watches the file by polling its modification time and size; the file
+    /// is only parsed again when either changed.
+    fn watch(&self) -> Option<Box<dyn Watch>> {
+        Some(Box::new(FileWatch {
+            stamp: stamp(&self.path),
+            last: self.load().unwrap_or_default(),
+            source: self.clone(),
+        }))
+    }
+}
+
+/// Modification time and size of a file, `None` if it does not exist.
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Reloads a file when its stamp changes.
+struct FileWatch {
+    source: FileSource,
+    stamp: Stamp,
+    last: HashMap<String, RawValue>,
+}
+
+impl Watch for FileWatch {
+    fn poll(&mut self) -> SourceResult<Vec<SourceEvent>> {
+        let current = stamp(&self.source.path);
+        if current == self.stamp {
+            return Ok(Vec::new());
+        }
+        let values = self.source.load()?;
+        self.stamp = current;
+        let events = diff(&self.last, &values);
+        self.last = values;
+        Ok(events)
+    }
+}
+
+fn flatten(
+    prefix: &str,
+    map: serde_json::Map<String, serde_json::Value>,
+    out: &mut HashMap<String, RawValue>,
+) {
+    for (key, value) in map {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            serde_json::Value::Object(nested) => flatten(&key, nested, out),
+            other => {
+                out.insert(key, from_json(other));
+            }
+        }
+    }
+}
+
+fn from_json(value: serde_json::Value) -> RawValue {
+    match value {
+        serde_json::Value::Null => RawValue::Null,
+        serde_json::Value::Bool(v) => RawValue::Bool(v),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(v) => RawValue::Integer(v),
+            None => RawValue::Float(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(v) => RawValue::String(v),
+        serde_json::Value::Array(items) => {
+            RawValue::List(items.into_iter().map(from_json).collect())
+        }
+        object @ serde_json::Value::Object(_) => RawValue::String(object.to_string()),
+    }
+}
+
+fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    match value {
+        toml::Value::String(v) => serde_json::Value::String(v),
+        toml::Value::Integer(v) => serde_json::Value::from(v),
+        toml::Value::Float(v) => serde_json::Value::from(v),
+        toml::Value::Boolean(v) => serde_json::Value::Bool(v),
+        toml::Value::Datetime(v) => serde_json::Value::String(v.to_string()),
+        toml::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(toml_to_json).collect())
+        }
+        toml::Value::Table(table) => serde_json::Value::Object(
+            table
+                .into_iter()
+                .map(|(k, v)| (k, toml_to_json(v)))
+                .collect(),
+        ),
+    }
+}
+
+/// Converts a JSON value to TOML, returning `None` for null.
+fn json_to_toml(value: serde_json::Value) -> Option<toml::Value> {
+    Some(match value {
+        serde_json::Value::Null => return None,
+        serde_json::Value::Bool(v) => toml::Value::Boolean(v),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(v) => toml::Value::Integer(v),
+            None => toml::Value::Float(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(v) => toml::Value::String(v),
+        serde_json::Value::Array(items) => {
+            toml::Value::Array(items.into_iter().filter_map(json_to_toml).collect())
+        }
+        serde_json::Value::Object(map) => toml::Value::Table(
+            map.into_iter()
+                .filter_map(|(k, v)| json_to_toml(v).map(|v| (k, v)))
+                .collect(),
+        ),
+    })
+}
+
+fn to_json(value: &RawValue) -> serde_json::Value {
+    match value {
+        RawValue::Null => serde_json::Value::Null,
+        RawValue::Bool(v) => serde_json::Value::Bool(*v),
+        RawValue::Integer(v) => serde_json::Value::from(*v),
+        RawValue::Float(v) => serde_json::Value::from(*v),
+        RawValue::String(v) => serde_json::Value::String(v.clone()),
+        RawValue::List(items) => serde_json::Value::Array(items.iter().map(to_json).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            FileFormat::from_path(Path::new("a.JSON")),
+            Some(FileFormat::Json)
+        );
+        assert_eq!(
+            FileFormat::from_path(Path::new("a.yml")),
+            Some(FileFormat::Yaml)
+        );
+        assert_eq!(
+            FileFormat::from_path(Path::new("Cargo.toml")),
+            Some(FileFormat::Toml)
+        );
+        assert_eq!(
+            FileFormat::from_path(Path::new("ci/.env")),
+            Some(FileFormat::Dotenv)
+        );
+        assert_eq!(
+            FileFormat::from_path(Path::new("prod.env")),
+            Some(FileFormat::Dotenv)
+        );
+        assert_eq!(
+            FileFormat::from_path(Path::new("deploy.properties")),
+            Some(FileFormat::Ini)
+        );
+        assert_eq!(FileFormat::from_path(Path::new("a.txt")), None);
+    }
+
+    #[test]
+    fn test_parse_json_flattens() {
+        let values = FileFormat::Json
+            .parse(r#"{"a": {"b": {"c": true}}, "list": [1, "x"], "none": null}"#)
+            .unwrap();
+        assert_eq!(values.get("a.b.c"), Some(&RawValue::Bool(true)));
+        assert_eq!(
+            values.get("list"),
+            Some(&RawValue::List(vec![
+                RawValue::Integer(1),
+                RawValue::from("x")
+            ]))
+        );
+        assert_eq!(values.get("none"), Some(&RawValue::Null));
+    }
+
+    #[test]
+    fn test_parse_yaml() {
+        let values = FileFormat::Yaml
+            .parse("s3:\n  bucket: assets\n  delete: false\nratio: 0.5\n")
+            .unwrap();
+        assert_eq!(values.get("s3.bucket"), Some(&RawValue::from("assets")));
+        assert_eq!(values.get("s3.delete"), Some(&RawValue::Bool(false)));
+        assert_eq!(values.get("ratio"), Some(&RawValue::Float(0.5)));
+    }
+
+    #[test]
+    fn test_parse_toml() {
+        let values = FileFormat::Toml
+            .parse(
+                "ratio = 0.5\nreleased = 2024-05-06\n\n[s3]\nbucket = \"assets\"\nregions = [\"eu-west-1\", \"us-east-1\"]\n",
+            )
+            .unwrap();
+        assert_eq!(values.get("s3.bucket"), Some(&RawValue::from("assets")));
+        assert_eq!(
+            values.get("s3.regions"),
+            Some(&RawValue::List(vec![
+                RawValue::from("eu-west-1"),
+                RawValue::from("us-east-1")
+            ]))
+        );
+        assert_eq!(values.get("ratio"), Some(&RawValue::Float(0.5)));
+        assert_eq!(values.get("released"), Some(&RawValue::from("2024-05-06")));
+        assert!(FileFormat::Toml.parse("bucket = ").is_err());
+    }
+
+    #[test]
+    fn test_serialize_toml_skips_null() {
+        let mut values = HashMap::new();
+        values.insert("s3.bucket".to_string(), RawValue::from("assets"));
+        values.insert("unset".to_string(), RawValue::Null);
+
+        let content = FileFormat::Toml.serialize(&values).unwrap();
+        assert_eq!(content, "\"s3.bucket\" = \"assets\"\n");
+        values.remove("unset");
+        assert_eq!(FileFormat::Toml.parse(&content).unwrap(), values);
+    }
+
+    #[test]
+    fn test_parse_rejects_non_map() {
+        assert!(FileFormat::Json.parse("[1, 2]").is_err());
+        assert!(FileFormat::Json.parse("{").is_err());
+    }
+
+    #[test]
+    fn test_serialize_roundtrip() {
+        let mut values = HashMap::new();
+        values.insert("name".to_string(), RawValue::from("app"));
+        values.insert("count".to_string(), RawValue::Integer(2));
+
+        for format in [FileFormat::Json, FileFormat::Yaml, FileFormat::Toml] {
+            let content = format.serialize(&values).unwrap();
+            assert_eq!(format.parse(&content).unwrap(), values);
+        }
+    }
+
+    #[test]
+    fn test_file_source_load() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        fs::write(&path, "workspace: prod\n").unwrap();
+
+        let source = FileSource::from_path(&path).unwrap();
+        assert_eq!(source.format(), FileFormat::Yaml);
+        assert_eq!(
+            source.get("workspace").unwrap(),
+            Some(RawValue::from("prod"))
+        );
+        assert_eq!(source.get("missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_file_source_missing() {
+        let source = FileSource::new("/non/existent.json", FileFormat::Json);
+        assert!(matches!(source.load(), Err(SourceError::Io { .. })));
+
+        let source = source.optional();
+        assert!(source.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_file_source_watch() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(&path, r#"{"workspace": "prod"}"#).unwrap();
+        let mut watch = FileSource::new(&path, FileFormat::Json).watch().unwrap();
+        assert!(watch.poll().unwrap().is_empty());
+
+        fs::write(&path, r#"{"workspace": "staging"}"#).unwrap();
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(5))
+            .unwrap();
+        let events = watch.poll().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].key, "workspace");
+
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(watch.poll(), Err(SourceError::Io { .. })));
+    }
+
+    #[test]
+    fn test_file_source_cache() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        fs::write(&path, "workspace: prod\n").unwrap();
+        let source =
+            FileSource::new(&path, FileFormat::Yaml).with_cache_ttl(Duration::from_secs(60));
+        assert_eq!(
+            source.get("workspace").unwrap(),
+            Some(RawValue::from("prod"))
+        );
+
+        fs::write(&path, "workspace: staging\n").unwrap();
+        assert_eq!(
+            source.get("workspace").unwrap(),
+            Some(RawValue::from("prod"))
+        );
+        assert_eq!(
+            source.reload().unwrap().get("workspace"),
+            Some(&RawValue::from("staging"))
+        );
+
+        let uncached = FileSource::new(&path, FileFormat::Yaml);
+        assert_eq!(uncached.load().unwrap().len(), 1);
+        fs::write(&path, "workspace: dev\nregion: eu\n").unwrap();
+        assert_eq!(uncached.get("region").unwrap(), Some(RawValue::from("eu")));
+    }
+
+    #[test]
+    fn test_file_source_invalid() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(&path, "not json").unwrap();
+        let source = FileSource::new(&path, FileFormat::Json);
+        assert!(matches!(source.load(), Err(SourceError::Parse { .. })));
+    }
+}
@@ -0,0 +1,152 @@
+use super::{FileFormat, RawValue, Source, SourceError, SourceResult};
+use crate::http::{Request, DEFAULT_TIMEOUT};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Reads configuration values from a document served over HTTP.
+///
+/// The document is fetched on every access; once a response carried an `ETag`,
+/// later requests are conditional and an unchanged document is not
+/// downloaded again.
+#[derive(Debug)]
+pub struct HttpSource {
+    url: String,
+    format: FileFormat,
+    token: Option<String>,
+    timeout: Duration,
+    sensitive_keys: Vec<String>,
+    cache: Mutex<Option<Cached>>,
+}
+
+/// The last document fetched and the `ETag` it was served with.
+#[derive(Debug, Clone)]
+struct Cached {
+    etag: String,
+    body: String,
+}
+
+impl HttpSource {
+    /// Creates a source over the document at `url` in the given format.
+    pub fn new(url: impl Into<String>, format: FileFormat) -> Self {
+        Self {
+            url: url.into(),
+            format,
+            token: None,
+            timeout: DEFAULT_TIMEOUT,
+            sensitive_keys: Vec::new(),
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Sends a bearer token with every request.
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Limits the duration of one request, 10 seconds by default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Marks keys whose values must be masked.
+    pub fn with_sensitive_keys(mut self, keys: &[&str]) -> Self {
+        self.sensitive_keys
+            .extend(keys.iter().map(|k| k.to_string()));
+        self
+    }
+
+    /// Returns the URL of the document.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn error(&self, message: impl Into<String>) -> SourceError {
+        SourceError::Http {
+            url: self.url.clone(),
+            message: message.into(),
+        }
+    }
+
+    /// Fetches the document, or returns the cached one if it did not change.
+    fn fetch(&self) -> SourceResult<String> {
+        let cached = self.cache.lock().unwrap().clone();
+        let mut request = Request::get(&self.url).with_timeout(self.timeout);
+        if let Some(token) = &self.token {
+            request = request.with_header("Authorization", format!("Bearer {}", token));
+        }
+        if let Some(cached) = &cached {
+            request = request.with_header("If-None-Match", &cached.etag);
+        }
+
+        let response = request.send().map_err(|e| self.error(e.to_string()))?;
+        match (response.status, cached) {
+            (304, Some(cached)) => Ok(cached.body),
+            (200, _) => {
+                *self.cache.lock().unwrap() = response.headers.get("etag").map(|etag| Cached {
+                    etag: etag.clone(),
+                    body: response.body.clone(),
+                });
+                Ok(response.body)
+            }
+            (status, _) => Err(self.error(format!("unexpected status {}", status))),
+        }
+    }
+}
+
+impl Source for HttpSource {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    fn load(&self) -> SourceResult<HashMap<String, RawValue>> {
+        let body = self.fetch()?;
+        self.format
+            .parse(&body)
+            .map_err(|message| self.error(format!("invalid content: {}", message)))
+    }
+
+    fn sensitive_keys(&self) -> &[String] {
+        &self.sensitive_keys
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::server::{response, serve};
+
+    #[test]
+    fn test_load_revalidates_with_etag() {
+        let (url, requests) = serve(vec![
+            response("200 OK", &["ETag: \"v1\""], r#"{"workspace": "prod"}"#),
+            response("304 Not Modified", &["ETag: \"v1\""], ""),
+        ]);
+        let source = HttpSource::new(format!("{}/deploy.json", url), FileFormat::Json)
+            .with_bearer_token("s3cr3t");
+
+        for _ in 0..2 {
+            assert_eq!(
+                source.get("workspace").unwrap(),
+                Some(RawValue::from("prod"))
+            );
+        }
+        let first = requests.recv().unwrap();
+        assert!(first.contains(&"authorization: Bearer s3cr3t".to_string()));
+        assert!(!first.iter().any(|h| h.starts_with("if-none-match")));
+        let second = requests.recv().unwrap();
+        assert!(second.contains(&"if-none-match: \"v1\"".to_string()));
+    }
+
+    #[test]
+    fn test_load_rejects_error_status() {
+        let (url, _requests) = serve(vec![response("403 Forbidden", &[], "")]);
+        let source = HttpSource::new(url, FileFormat::Json);
+        assert!(matches!(
+            source.load(),
+            Err(SourceError::Http { message, .. }) if message == "unexpected status 403"
+        ));
+    }
+}
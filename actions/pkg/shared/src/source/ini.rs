@@ -0,0 +1,155 @@
+//! The INI and Java properties file formats.
+//!
+//! Every line is `key = value` or `key: value`; keys under a `[section]`
+//! header are loaded as `section.key`. Blank lines and lines starting with
+//! `;`, `#` or `!` are skipped, and a trailing `\` continues the value on the
+//! next line. Values surrounded by double quotes are loaded without them.
+
+use super::RawValue;
+use std::collections::{BTreeMap, HashMap};
+
+/// Parses INI or properties content; every value is loaded as a string.
+pub(super) fn parse(content: &str) -> Result<HashMap<String, RawValue>, String> {
+    let mut values = HashMap::new();
+    let mut section = String::new();
+    let mut lines = content.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with([';', '#', '!']) {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix('[') {
+            let name = rest
+                .strip_suffix(']')
+                .ok_or_else(|| format!("line {}: unterminated section header", index + 1))?;
+            section = name.trim().to_string();
+            continue;
+        }
+        let Some(split) = line.find(['=', ':']) else {
+            return Err(format!("line {}: expected key = value", index + 1));
+        };
+        let key = line[..split].trim();
+        if key.is_empty() {
+            return Err(format!("line {}: empty key", index + 1));
+        }
+        let mut value = line[split + 1..].trim().to_string();
+        while let Some(head) = value.strip_suffix('\\') {
+            value = head.to_string();
+            match lines.next() {
+                Some((_, next)) => value.push_str(next.trim()),
+                None => break,
+            }
+        }
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .map(str::to_string)
+            .unwrap_or(value);
+        let key = if section.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", section, key)
+        };
+        values.insert(key, RawValue::String(value));
+    }
+    Ok(values)
+}
+
+/// Serializes values as sorted `key = value` lines; keys with a dot go to the
+/// section named by their first part.
+pub(super) fn serialize(values: &HashMap<String, RawValue>) -> String {
+    let mut sections: BTreeMap<&str, BTreeMap<&str, String>> = BTreeMap::new();
+    for (key, value) in values {
+        let (section, key) = key.split_once('.').unwrap_or(("", key));
+        sections
+            .entry(section)
+            .or_default()
+            .insert(key, quote(&value.to_string()));
+    }
+    let mut out = String::new();
+    for (section, entries) in sections {
+        if !section.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("[{}]\n", section));
+        }
+        for (key, value) in entries {
+            out.push_str(&format!("{} = {}\n", key, value));
+        }
+    }
+    out
+}
+
+/// Quotes values that would otherwise lose whitespace around them.
+fn quote(value: &str) -> String {
+    if value != value.trim() || (value.starts_with('"') && value.ends_with('"')) {
+        format!("\"{}\"", value)
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let values = parse(
+            "; deployment descriptor\n\
+             app.name=shop\n\
+             \n\
+             [database]\n\
+             host = db.internal\n\
+             url: jdbc:postgresql://db.internal/shop\n\
+             # multiline\n\
+             hosts = a, \\\n\
+             \x20   b\n\
+             [ cache ]\n\
+             prefix = \" shop \"\n",
+        )
+        .unwrap();
+        let get = |key: &str| values.get(key).map(|v| v.to_string());
+        assert_eq!(get("app.name").as_deref(), Some("shop"));
+        assert_eq!(get("database.host").as_deref(), Some("db.internal"));
+        assert_eq!(
+            get("database.url").as_deref(),
+            Some("jdbc:postgresql://db.internal/shop")
+        );
+        assert_eq!(get("database.hosts").as_deref(), Some("a, b"));
+        assert_eq!(get("cache.prefix").as_deref(), Some(" shop "));
+        assert_eq!(values.len(), 5);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("[database\nhost=a").unwrap_err(),
+            "line 1: unterminated section header"
+        );
+        assert_eq!(parse("a=1\nb").unwrap_err(), "line 2: expected key = value");
+        assert!(parse("= 1").is_err());
+    }
+
+    #[test]
+    fn test_serialize_roundtrip() {
+        let values = HashMap::from([
+            ("name".to_string(), RawValue::from("shop")),
+            ("database.host".to_string(), RawValue::from("db")),
+            ("database.pool.size".to_string(), RawValue::from("10")),
+            ("cache.prefix".to_string(), RawValue::from(" shop ")),
+        ]);
+        let content = serialize(&values);
+        assert_eq!(
+            content,
+            "name = shop\n\n\
+             [cache]\n\
+             prefix = \" shop \"\n\n\
+             [database]\n\
+             host = db\n\
+             pool.size = 10\n"
+        );
+        assert_eq!(parse(&content).unwrap(), values);
+    }
+}
@@ -0,0 +1,196 @@
+use super::{RawValue, Source, SourceResult};
+use std::collections::HashMap;
+
+/// Name of the layer of [`LayeredSource::with_defaults`].
+pub const DEFAULTS_LAYER: &str = "defaults";
+
+/// Composes sources into named layers, consulted in order of precedence.
+///
+/// The first layer providing a key wins, so layers are added from the
+/// highest precedence to the lowest, e.g. env, then file, then remote, with
+/// defaults below all of them.
+///
+/// # Example
+///
+/// ```rust
+/// use shared::source::{EnvSource, LayeredSource, RawValue, Source};
+/// use std::collections::HashMap;
+///
+/// std::env::set_var("LAYERED_EXAMPLE_REGION", "us-east-1");
+/// let source = LayeredSource::new()
+///     .with_layer("env", EnvSource::new().with_prefix("LAYERED_EXAMPLE_"))
+///     .with_defaults(HashMap::from([
+///         ("REGION".to_string(), RawValue::from("eu-west-1")),
+///         ("RETRIES".to_string(), RawValue::Integer(3)),
+///     ]));
+///
+/// let region = source.resolve("REGION").unwrap().unwrap();
+/// assert_eq!((region.layer.as_str(), region.value), ("env", RawValue::from("us-east-1")));
+/// assert_eq!(source.resolve("RETRIES").unwrap().unwrap().layer, "defaults");
+/// ```
+#[derive(Default)]
+pub struct LayeredSource {
+    layers: Vec<(String, Box<dyn Source>)>,
+    defaults: HashMap<String, RawValue>,
+}
+
+/// A value and the layer that supplied it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolved {
+    /// Name of the layer.
+    pub layer: String,
+    pub value: RawValue,
+    /// Whether the supplying layer marks the value as sensitive.
+    pub sensitive: bool,
+}
+
+impl LayeredSource {
+    /// Creates a source without layers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a layer with a lower precedence than the existing ones.
+    pub fn with_layer(mut self, name: impl Into<String>, source: impl Source + 'static) -> Self {
+        self.layers.push((name.into(), Box::new(source)));
+        self
+    }
+
+    /// Sets the values used when no layer provides a key.
+    pub fn with_defaults(mut self, defaults: HashMap<String, RawValue>) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// Returns the names of the layers in order of precedence, without the defaults.
+    pub fn layers(&self) -> Vec<&str> {
+        self.layers.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Returns the value of `key` and the layer that supplied it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first layer that fails to read the key.
+    pub fn resolve(&self, key: &str) -> SourceResult<Option<Resolved>> {
+        for (name, source) in &self.layers {
+            if let Some(value) = source.get(key)? {
+                return Ok(Some(Resolved {
+                    layer: name.clone(),
+                    value,
+                    sensitive: source.is_sensitive(key),
+                }));
+            }
+        }
+        Ok(self.defaults.get(key).map(|value| Resolved {
+            layer: DEFAULTS_LAYER.to_string(),
+            value: value.clone(),
+            sensitive: false,
+        }))
+    }
+
+    /// Returns the name of the layer that supplies each key.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first layer that fails to load.
+    pub fn provenance(&self) -> SourceResult<HashMap<String, String>> {
+        let mut provenance: HashMap<String, String> = self
+            .defaults
+            .keys()
+            .map(|key| (key.clone(), DEFAULTS_LAYER.to_string()))
+            .collect();
+        for (name, source) in self.layers.iter().rev() {
+            for key in source.load()?.into_keys() {
+                provenance.insert(key, name.clone());
+            }
+        }
+        Ok(provenance)
+    }
+}
+
+impl Source for LayeredSource {
+    fn name(&self) -> &str {
+        "layered"
+    }
+
+    /// Merges the values of all layers; higher layers override lower ones.
+    fn load(&self) -> SourceResult<HashMap<String, RawValue>> {
+        let mut values = self.defaults.clone();
+        for (_, source) in self.layers.iter().rev() {
+            values.extend(source.load()?);
+        }
+        Ok(values)
+    }
+
+    fn get(&self, key: &str) -> SourceResult<Option<RawValue>> {
+        Ok(self.resolve(key)?.map(|resolved| resolved.value))
+    }
+
+    /// Sensitivity depends on the layer supplying a key; see [`Source::is_sensitive`].
+    fn sensitive_keys(&self) -> &[String] {
+        &[]
+    }
+
+    /// Returns whether the layer supplying `key` marks it as sensitive.
+    ///
+    /// A key that cannot be read is treated as sensitive.
+    fn is_sensitive(&self, key: &str) -> bool {
+        match self.resolve(key) {
+            Ok(resolved) => resolved.is_some_and(|r| r.sensitive),
+            Err(_) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::{FileFormat, FileSource};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_precedence_and_provenance() {
+        let dir = tempdir().unwrap();
+        let high = dir.path().join("high.json");
+        let low = dir.path().join("low.yaml");
+        std::fs::write(&high, r#"{"token": "t0ken", "region": "us-east-1"}"#).unwrap();
+        std::fs::write(&low, "region: eu-west-1\nbucket: assets\n").unwrap();
+
+        let source = LayeredSource::new()
+            .with_layer(
+                "file",
+                FileSource::new(&high, FileFormat::Json).with_sensitive_keys(&["token"]),
+            )
+            .with_layer("remote", FileSource::new(&low, FileFormat::Yaml))
+            .with_defaults(HashMap::from([
+                ("bucket".to_string(), RawValue::from("default")),
+                ("retries".to_string(), RawValue::Integer(3)),
+            ]));
+        assert_eq!(source.layers(), vec!["file", "remote"]);
+
+        let values = source.load().unwrap();
+        assert_eq!(values.get("region"), Some(&RawValue::from("us-east-1")));
+        assert_eq!(values.get("bucket"), Some(&RawValue::from("assets")));
+        assert_eq!(values.get("retries"), Some(&RawValue::Integer(3)));
+
+        let provenance = source.provenance().unwrap();
+        assert_eq!(provenance["region"], "file");
+        assert_eq!(provenance["bucket"], "remote");
+        assert_eq!(provenance["retries"], DEFAULTS_LAYER);
+
+        assert!(source.is_sensitive("token"));
+        assert!(!source.is_sensitive("region"));
+        assert_eq!(source.resolve("missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_failing_layer() {
+        let source = LayeredSource::new().with_layer(
+            "file",
+            FileSource::new("/non/existent.json", FileFormat::Json),
+        );
+        assert!(source.get("key").is_err());
+        assert!(source.is_sensitive("key"));
+    }
+}
@@ -0,0 +1,183 @@
+//! Configuration sources.
+//!
+//! A [`Source`] provides flat `key -> value` pairs, e.g. from the process
+//! environment ([`EnvSource`]), a JSON, YAML, TOML, `.env` or INI file
+//! ([`FileSource`]), a document served over HTTP ([`HttpSource`]), the
+//! SSM Parameter Store ([`SsmSource`]) or a Vault KV secret ([`VaultSource`]).
+//! [`LayeredSource`] combines several sources by precedence, and a
+//! [`SourceSchema`] validates the values a source loads.
+
+mod dotenv;
+mod env;
+mod error;
+mod file;
+mod http;
+mod ini;
+mod layered;
+mod schema;
+mod ssm;
+mod value;
+mod vault;
+mod watch;
+
+pub use env::{EnvChange, EnvSource};
+pub use error::{SourceError, SourceResult};
+pub use file::{FileFormat, FileSource};
+pub use http::HttpSource;
+pub use layered::{LayeredSource, Resolved, DEFAULTS_LAYER};
+pub use schema::{KeySpec, SourceSchema, ValueType, Violation};
+pub use ssm::SsmSource;
+pub use value::{FromValue, RawValue};
+pub use vault::{VaultAuth, VaultSource};
+pub use watch::{ChangeKind, SourceEvent, Watch};
+
+use std::collections::HashMap;
+
+/// The `Source` trait defines a common interface for reading configuration values.
+pub trait Source: Send + Sync {
+    /// Returns a short, human readable name of the source.
+    fn name(&self) -> &str;
+
+    /// Loads every value provided by the source.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SourceError` if the underlying storage cannot be read or parsed.
+    fn load(&self) -> SourceResult<HashMap<String, RawValue>>;
+
+    /// Returns the value for a single key, or `None` if the source does not provide it.
+    fn get(&self, key: &str) -> SourceResult<Option<RawValue>> {
+        Ok(self.load()?.remove(key))
+    }
+
+    /// Returns the value for `key` converted to `T`, or `None` if the source does not provide it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SourceError::Conversion` if the value cannot be converted.
+    fn get_as<T: FromValue>(&self, key: &str) -> SourceResult<Option<T>>
+    where
+        Self: Sized,
+    {
+        self.get(key)?
+            .map(|value| T::from_value(key, &value))
+            .transpose()
+    }
+
+    /// Returns the value for `key` converted to `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SourceError::Missing` if the source does not provide the key and
+    /// `SourceError::Conversion` if the value cannot be converted.
+    fn get_required_as<T: FromValue>(&self, key: &str) -> SourceResult<T>
+    where
+        Self: Sized,
+    {
+        self.get_as(key)?
+            .ok_or_else(|| SourceError::Missing(key.to_string()))
+    }
+
+    /// Returns the keys whose values must be masked.
+    fn sensitive_keys(&self) -> &[String];
+
+    /// Returns `true` if the value of `key` must be masked.
+    fn is_sensitive(&self, key: &str) -> bool {
+        self.sensitive_keys().iter().any(|k| k == key)
+    }
+
+    /// Starts watching the source for changes, or returns `None` if it cannot be watched.
+    ///
+    /// The watch compares later polls with the values loaded when it starts;
+    /// it does not borrow the source.
+    fn watch(&self) -> Option<Box<dyn Watch>> {
+        None
+    }
+
+    /// Stores a value in the source.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SourceError::Unsupported` unless the source is writable.
+    fn set(&mut self, _key: &str, _value: RawValue) -> SourceResult<()> {
+        Err(SourceError::Unsupported {
+            source_name: self.name().to_string(),
+            operation: "set",
+        })
+    }
+
+    /// Persists values stored with [`Source::set`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `SourceError::Unsupported` unless the source is writable.
+    fn save(&self) -> SourceResult<()> {
+        Err(SourceError::Unsupported {
+            source_name: self.name().to_string(),
+            operation: "save",
+        })
+    }
+}
+
+/// Forwards to the boxed source, so that boxed trait objects get the typed getters.
+impl<S: Source + ?Sized> Source for Box<S> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn load(&self) -> SourceResult<HashMap<String, RawValue>> {
+        (**self).load()
+    }
+
+    fn get(&self, key: &str) -> SourceResult<Option<RawValue>> {
+        (**self).get(key)
+    }
+
+    fn sensitive_keys(&self) -> &[String] {
+        (**self).sensitive_keys()
+    }
+
+    fn is_sensitive(&self, key: &str) -> bool {
+        (**self).is_sensitive(key)
+    }
+
+    fn watch(&self) -> Option<Box<dyn Watch>> {
+        (**self).watch()
+    }
+
+    fn set(&mut self, key: &str, value: RawValue) -> SourceResult<()> {
+        (**self).set(key, value)
+    }
+
+    fn save(&self) -> SourceResult<()> {
+        (**self).save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_typed_getters() {
+        env::set_var("SOURCE_TYPED_PARALLELISM", "4");
+        env::set_var("SOURCE_TYPED_LOCK", "yes");
+        let source = EnvSource::new().with_prefix("SOURCE_TYPED_");
+
+        assert_eq!(source.get_as::<u64>("PARALLELISM").unwrap(), Some(4));
+        assert!(source.get_required_as::<bool>("LOCK").unwrap());
+        assert_eq!(source.get_as::<bool>("UNSET").unwrap(), None);
+        assert!(matches!(
+            source.get_required_as::<bool>("UNSET"),
+            Err(SourceError::Missing(key)) if key == "UNSET"
+        ));
+        assert!(matches!(
+            source.get_as::<bool>("PARALLELISM"),
+            Err(SourceError::Conversion { .. })
+        ));
+
+        let boxed: Box<dyn Source> = Box::new(source);
+        assert_eq!(boxed.get_required_as::<i64>("PARALLELISM").unwrap(), 4);
+    }
+}
@@ -0,0 +1,276 @@
+use super::{FromValue, RawValue, Source, SourceResult};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The type a value must convert to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    /// Any value except a list.
+    String,
+    Bool,
+    Integer,
+    Float,
+    /// A list, or a comma separated string.
+    List,
+}
+
+impl ValueType {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Bool => "bool",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::List => "list",
+        }
+    }
+
+    /// Returns whether `value` converts to the type, the way [`FromValue`] converts it.
+    fn accepts(&self, key: &str, value: &RawValue) -> bool {
+        match self {
+            Self::String => !matches!(value, RawValue::List(_)),
+            Self::Bool => bool::from_value(key, value).is_ok(),
+            Self::Integer => i64::from_value(key, value).is_ok(),
+            Self::Float => f64::from_value(key, value).is_ok(),
+            Self::List => matches!(value, RawValue::List(_) | RawValue::String(_)),
+        }
+    }
+}
+
+/// The rules for one key of a [`SourceSchema`].
+#[derive(Debug, Clone)]
+pub struct KeySpec {
+    key: String,
+    required: bool,
+    value_type: Option<ValueType>,
+    allowed: Vec<String>,
+}
+
+impl KeySpec {
+    /// Creates an optional key of any type.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            required: false,
+            value_type: None,
+            allowed: Vec::new(),
+        }
+    }
+
+    /// Requires the source to provide the key.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    pub fn with_type(mut self, value_type: ValueType) -> Self {
+        self.value_type = Some(value_type);
+        self
+    }
+
+    /// Restricts the value, or every item of a list, to `values`.
+    pub fn with_allowed(mut self, values: &[&str]) -> Self {
+        self.allowed = values.iter().map(|v| v.to_string()).collect();
+        self
+    }
+}
+
+/// A rule broken by the values of a source.
+///
+/// Values are not included, as they may be secrets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// A required key is not provided.
+    Missing(String),
+    /// A value does not convert to the type of its key.
+    InvalidType {
+        key: String,
+        expected: &'static str,
+        actual: &'static str,
+    },
+    /// A value is not one of the allowed values of its key.
+    NotAllowed { key: String, allowed: Vec<String> },
+    /// A key is not part of a schema that denies unknown keys.
+    Unknown(String),
+}
+
+impl Violation {
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Missing(key) | Self::Unknown(key) => key,
+            Self::InvalidType { key, .. } | Self::NotAllowed { key, .. } => key,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(key) => write!(f, "Missing required key: {}", key),
+            Self::InvalidType {
+                key,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Invalid type for {}: expected {}, got {}",
+                key, expected, actual
+            ),
+            Self::NotAllowed { key, allowed } => {
+                write!(
+                    f,
+                    "Invalid value for {}: must be one of {}",
+                    key,
+                    allowed.join(", ")
+                )
+            }
+            Self::Unknown(key) => write!(f, "Unknown key: {}", key),
+        }
+    }
+}
+
+/// Required keys, types and allowed values of the values loaded from a source.
+///
+/// # Example
+///
+/// ```rust
+/// use shared::source::{KeySpec, RawValue, SourceSchema, ValueType, Violation};
+/// use std::collections::HashMap;
+///
+/// let schema = SourceSchema::new()
+///     .with_key(KeySpec::new("workspace").required().with_allowed(&["prod", "staging"]))
+///     .with_key(KeySpec::new("parallelism").with_type(ValueType::Integer));
+///
+/// let values = HashMap::from([("parallelism".to_string(), RawValue::from("ten"))]);
+/// assert_eq!(
+///     schema.validate(&values),
+///     vec![
+///         Violation::Missing("workspace".to_string()),
+///         Violation::InvalidType {
+///             key: "parallelism".to_string(),
+///             expected: "integer",
+///             actual: "string",
+///         },
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SourceSchema {
+    keys: Vec<KeySpec>,
+    deny_unknown: bool,
+}
+
+impl SourceSchema {
+    /// Creates an empty schema, which accepts any values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_key(mut self, spec: KeySpec) -> Self {
+        self.keys.push(spec);
+        self
+    }
+
+    /// Reports keys that the schema does not list, e.g. misspelled ones.
+    pub fn deny_unknown(mut self) -> Self {
+        self.deny_unknown = true;
+        self
+    }
+
+    /// Returns every violation of `values`, in the order of the schema keys;
+    /// unknown keys follow, sorted.
+    pub fn validate(&self, values: &HashMap<String, RawValue>) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for spec in &self.keys {
+            let Some(value) = values.get(&spec.key) else {
+                if spec.required {
+                    violations.push(Violation::Missing(spec.key.clone()));
+                }
+                continue;
+            };
+            if let Some(value_type) = spec.value_type {
+                if !value_type.accepts(&spec.key, value) {
+                    violations.push(Violation::InvalidType {
+                        key: spec.key.clone(),
+                        expected: value_type.name(),
+                        actual: value.type_name(),
+                    });
+                    continue;
+                }
+            }
+            if !spec.allowed.is_empty() && !is_allowed(value, &spec.allowed) {
+                violations.push(Violation::NotAllowed {
+                    key: spec.key.clone(),
+                    allowed: spec.allowed.clone(),
+                });
+            }
+        }
+
+        if self.deny_unknown {
+            let mut unknown: Vec<&String> = values
+                .keys()
+                .filter(|key| !self.keys.iter().any(|spec| &spec.key == *key))
+                .collect();
+            unknown.sort();
+            violations.extend(unknown.into_iter().cloned().map(Violation::Unknown));
+        }
+        violations
+    }
+
+    /// Loads `source` and returns every violation of its values.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SourceError` if the source cannot be loaded.
+    pub fn check(&self, source: &(impl Source + ?Sized)) -> SourceResult<Vec<Violation>> {
+        Ok(self.validate(&source.load()?))
+    }
+}
+
+fn is_allowed(value: &RawValue, allowed: &[String]) -> bool {
+    match value {
+        RawValue::List(items) => items.iter().all(|item| is_allowed(item, allowed)),
+        other => allowed.contains(&other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::{FileFormat, FileSource};
+
+    #[test]
+    fn test_check_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deploy.yaml");
+        std::fs::write(
+            &path,
+            "regions: [eu-west-1, us-east-9]\nlock: \"yes\"\nretries: 2.5\nworkspaec: prod\n",
+        )
+        .unwrap();
+        let schema = SourceSchema::new()
+            .with_key(
+                KeySpec::new("regions")
+                    .with_type(ValueType::List)
+                    .with_allowed(&["eu-west-1", "us-east-1"]),
+            )
+            .with_key(KeySpec::new("lock").with_type(ValueType::Bool))
+            .with_key(KeySpec::new("retries").with_type(ValueType::Integer))
+            .with_key(KeySpec::new("workspace").required())
+            .deny_unknown();
+
+        let violations = schema
+            .check(&FileSource::new(&path, FileFormat::Yaml))
+            .unwrap();
+        assert_eq!(
+            violations.iter().map(|v| v.to_string()).collect::<Vec<_>>(),
+            vec![
+                "Invalid value for regions: must be one of eu-west-1, us-east-1",
+                "Invalid type for retries: expected integer, got float",
+                "Missing required key: workspace",
+                "Unknown key: workspaec",
+            ]
+        );
+        assert_eq!(violations[3].key(), "workspaec");
+    }
+}
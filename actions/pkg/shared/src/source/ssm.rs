@@ -0,0 +1,175 @@
+use super::{RawValue, Source, SourceError, SourceResult};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Reads configuration values from the AWS SSM Parameter Store.
+///
+/// Every parameter below a path prefix is loaded with `aws ssm
+/// get-parameters-by-path`, decrypting `SecureString` parameters. Keys are
+/// the parameter names relative to the prefix with `/` replaced by `.`, so
+/// with the prefix `/deploy/prod` the parameter `/deploy/prod/db/password`
+/// provides the key `db.password`. `StringList` parameters are loaded as lists.
+///
+/// Every value is treated as a secret.
+#[derive(Debug, Clone)]
+pub struct SsmSource {
+    prefix: String,
+    region: Option<String>,
+    cli: PathBuf,
+}
+
+impl SsmSource {
+    /// Creates a source over the parameters below `prefix`, e.g. `/deploy/prod`.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            region: None,
+            cli: PathBuf::from("aws"),
+        }
+    }
+
+    /// Reads the parameters from `region` instead of the default region.
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Runs the AWS CLI at `cli` instead of `aws` from `PATH`.
+    pub fn with_cli(mut self, cli: impl Into<PathBuf>) -> Self {
+        self.cli = cli.into();
+        self
+    }
+
+    /// Returns the path prefix of the parameters.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn error(&self, message: impl Into<String>) -> SourceError {
+        SourceError::Remote {
+            source_name: format!("ssm:{}", self.prefix),
+            message: message.into(),
+        }
+    }
+}
+
+impl Source for SsmSource {
+    fn name(&self) -> &str {
+        "ssm"
+    }
+
+    fn load(&self) -> SourceResult<HashMap<String, RawValue>> {
+        let mut command = Command::new(&self.cli);
+        command.args([
+            "ssm",
+            "get-parameters-by-path",
+            "--path",
+            &self.prefix,
+            "--recursive",
+            "--with-decryption",
+            "--output",
+            "json",
+        ]);
+        if let Some(region) = &self.region {
+            command.args(["--region", region]);
+        }
+        let output = command
+            .output()
+            .map_err(|e| self.error(format!("cannot run {}: {}", self.cli.display(), e)))?;
+        if !output.status.success() {
+            return Err(self.error(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+        }
+        parse_parameters(&String::from_utf8_lossy(&output.stdout), &self.prefix)
+            .map_err(|message| self.error(message))
+    }
+
+    /// Every parameter is treated as a secret.
+    fn sensitive_keys(&self) -> &[String] {
+        &[]
+    }
+
+    fn is_sensitive(&self, _key: &str) -> bool {
+        true
+    }
+}
+
+/// Parses the output of `aws ssm get-parameters-by-path` into flat pairs.
+fn parse_parameters(json: &str, prefix: &str) -> Result<HashMap<String, RawValue>, String> {
+    let root: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let parameters = root
+        .get("Parameters")
+        .and_then(Value::as_array)
+        .ok_or("missing Parameters")?;
+
+    let prefix = prefix.trim_end_matches('/');
+    let mut values = HashMap::new();
+    for parameter in parameters {
+        let field = |name| parameter.get(name).and_then(Value::as_str);
+        let (Some(name), Some(value)) = (field("Name"), field("Value")) else {
+            return Err("parameter without Name or Value".to_string());
+        };
+        let key = name
+            .strip_prefix(prefix)
+            .unwrap_or(name)
+            .trim_start_matches('/')
+            .replace('/', ".");
+        let value = match field("Type") {
+            Some("StringList") => RawValue::List(value.split(',').map(RawValue::from).collect()),
+            _ => RawValue::from(value),
+        };
+        values.insert(key, value);
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use testing::script;
+
+    const PARAMETERS: &str = r#"{"Parameters": [
+        {"Name": "/deploy/prod/db/password", "Type": "SecureString", "Value": "hunter2"},
+        {"Name": "/deploy/prod/regions", "Type": "StringList", "Value": "eu-west-1,us-east-1"}
+    ]}"#;
+
+    #[test]
+    fn test_parse_parameters() {
+        let values = parse_parameters(PARAMETERS, "/deploy/prod/").unwrap();
+        assert_eq!(values.get("db.password"), Some(&RawValue::from("hunter2")));
+        assert_eq!(
+            values.get("regions"),
+            Some(&RawValue::List(vec![
+                RawValue::from("eu-west-1"),
+                RawValue::from("us-east-1")
+            ]))
+        );
+        assert!(parse_parameters("{}", "/deploy").is_err());
+    }
+
+    #[test]
+    fn test_load_through_cli() {
+        let dir = tempdir().unwrap();
+        let cli = script(
+            &dir.path().join("aws"),
+            &format!(
+                "[ \"$*\" = \"ssm get-parameters-by-path --path /deploy/prod --recursive --with-decryption --output json --region eu-west-1\" ] || exit 1\ncat <<'EOF'\n{}\nEOF",
+                PARAMETERS
+            ),
+        );
+
+        let source = SsmSource::new("/deploy/prod")
+            .with_region("eu-west-1")
+            .with_cli(&cli);
+        assert_eq!(
+            source.get("db.password").unwrap(),
+            Some(RawValue::from("hunter2"))
+        );
+        assert!(source.is_sensitive("db.password"));
+
+        let failing = SsmSource::new("/deploy/prod").with_cli(&cli);
+        assert!(matches!(failing.load(), Err(SourceError::Remote { .. })));
+    }
+}
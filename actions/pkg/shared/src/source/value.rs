@@ -0,0 +1,245 @@
+use super::{SourceError, SourceResult};
+use std::fmt;
+use std::path::PathBuf;
+
+/// A configuration value as loaded from a source, before conversion.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawValue {
+    /// An explicit null / empty value.
+    Null,
+    /// A boolean value.
+    Bool(bool),
+    /// An integer value.
+    Integer(i64),
+    /// A floating point value.
+    Float(f64),
+    /// A string value; every environment variable is loaded as a string.
+    String(String),
+    /// A list of values.
+    List(Vec<RawValue>),
+}
+
+impl RawValue {
+    /// Returns the name of the value type, used in conversion errors.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool(_) => "bool",
+            Self::Integer(_) => "integer",
+            Self::Float(_) => "float",
+            Self::String(_) => "string",
+            Self::List(_) => "list",
+        }
+    }
+}
+
+impl fmt::Display for RawValue {
+    /// Renders the value the way it would appear in an environment variable;
+    /// lists are joined with commas.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => Ok(()),
+            Self::Bool(v) => write!(f, "{}", v),
+            Self::Integer(v) => write!(f, "{}", v),
+            Self::Float(v) => write!(f, "{}", v),
+            Self::String(v) => write!(f, "{}", v),
+            Self::List(items) => {
+                let items: Vec<String> = items.iter().map(|i| i.to_string()).collect();
+                write!(f, "{}", items.join(","))
+            }
+        }
+    }
+}
+
+impl From<&str> for RawValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for RawValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<bool> for RawValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for RawValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+/// Converts a [`RawValue`] into a concrete type.
+///
+/// String values are parsed, so `"true"` from an environment variable and
+/// `true` from a JSON file both convert to `bool`.
+pub trait FromValue: Sized {
+    /// Converts `value`, loaded for `key`, into `Self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SourceError::Conversion` if the value has an incompatible type.
+    fn from_value(key: &str, value: &RawValue) -> SourceResult<Self>;
+}
+
+fn conversion_error(key: &str, expected: &'static str, value: &RawValue) -> SourceError {
+    SourceError::Conversion {
+        key: key.to_string(),
+        expected,
+        value: value.to_string(),
+    }
+}
+
+impl FromValue for RawValue {
+    fn from_value(_key: &str, value: &RawValue) -> SourceResult<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl FromValue for String {
+    fn from_value(_key: &str, value: &RawValue) -> SourceResult<Self> {
+        Ok(value.to_string())
+    }
+}
+
+impl FromValue for PathBuf {
+    fn from_value(_key: &str, value: &RawValue) -> SourceResult<Self> {
+        Ok(PathBuf::from(value.to_string()))
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(key: &str, value: &RawValue) -> SourceResult<Self> {
+        match value {
+            RawValue::Bool(v) => Ok(*v),
+            RawValue::String(s) => match s.to_lowercase().as_str() {
+                "true" | "1" | "yes" => Ok(true),
+                "false" | "0" | "no" | "" => Ok(false),
+                _ => Err(conversion_error(key, "bool", value)),
+            },
+            _ => Err(conversion_error(key, "bool", value)),
+        }
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(key: &str, value: &RawValue) -> SourceResult<Self> {
+        match value {
+            RawValue::Integer(v) => Ok(*v),
+            RawValue::String(s) => s
+                .trim()
+                .parse()
+                .map_err(|_| conversion_error(key, "integer", value)),
+            _ => Err(conversion_error(key, "integer", value)),
+        }
+    }
+}
+
+impl FromValue for u64 {
+    fn from_value(key: &str, value: &RawValue) -> SourceResult<Self> {
+        let v = i64::from_value(key, value)?;
+        u64::try_from(v).map_err(|_| conversion_error(key, "unsigned integer", value))
+    }
+}
+
+impl FromValue for f64 {
+    fn from_value(key: &str, value: &RawValue) -> SourceResult<Self> {
+        match value {
+            RawValue::Float(v) => Ok(*v),
+            RawValue::Integer(v) => Ok(*v as f64),
+            RawValue::String(s) => s
+                .trim()
+                .parse()
+                .map_err(|_| conversion_error(key, "float", value)),
+            _ => Err(conversion_error(key, "float", value)),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    /// Converts lists item by item; strings are split on commas.
+    fn from_value(key: &str, value: &RawValue) -> SourceResult<Self> {
+        match value {
+            RawValue::List(items) => items.iter().map(|i| T::from_value(key, i)).collect(),
+            RawValue::String(s) if s.is_empty() => Ok(Vec::new()),
+            RawValue::String(s) => s
+                .split(',')
+                .map(|i| T::from_value(key, &RawValue::String(i.trim().to_string())))
+                .collect(),
+            RawValue::Null => Ok(Vec::new()),
+            _ => Ok(vec![T::from_value(key, value)?]),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    /// Converts null and empty strings to `None`.
+    fn from_value(key: &str, value: &RawValue) -> SourceResult<Self> {
+        match value {
+            RawValue::Null => Ok(None),
+            RawValue::String(s) if s.is_empty() => Ok(None),
+            _ => T::from_value(key, value).map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(RawValue::Null.to_string(), "");
+        assert_eq!(RawValue::Integer(3).to_string(), "3");
+        let list = RawValue::List(vec![RawValue::from("a"), RawValue::Bool(true)]);
+        assert_eq!(list.to_string(), "a,true");
+    }
+
+    #[test]
+    fn test_bool_from_value() {
+        assert!(bool::from_value("K", &RawValue::from("TRUE")).unwrap());
+        assert!(!bool::from_value("K", &RawValue::Bool(false)).unwrap());
+        assert!(matches!(
+            bool::from_value("K", &RawValue::from("maybe")),
+            Err(SourceError::Conversion {
+                expected: "bool",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_integer_from_value() {
+        assert_eq!(i64::from_value("K", &RawValue::from(" 42 ")).unwrap(), 42);
+        assert_eq!(u64::from_value("K", &RawValue::Integer(7)).unwrap(), 7);
+        assert!(u64::from_value("K", &RawValue::Integer(-1)).is_err());
+        assert_eq!(f64::from_value("K", &RawValue::Integer(2)).unwrap(), 2.0);
+    }
+
+    #[test]
+    fn test_list_from_value() {
+        let from_string: Vec<String> = Vec::from_value("K", &RawValue::from("a, b")).unwrap();
+        assert_eq!(from_string, vec!["a", "b"]);
+
+        let from_list: Vec<i64> = Vec::from_value(
+            "K",
+            &RawValue::List(vec![RawValue::Integer(1), RawValue::from("2")]),
+        )
+        .unwrap();
+        assert_eq!(from_list, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_option_from_value() {
+        let empty: Option<i64> = Option::from_value("K", &RawValue::from("")).unwrap();
+        assert!(empty.is_none());
+        let set: Option<i64> = Option::from_value("K", &RawValue::from("5")).unwrap();
+        assert_eq!(set, Some(5));
+    }
+}
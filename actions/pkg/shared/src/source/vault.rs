@@ -0,0 +1,354 @@
+use super::{FileFormat, RawValue, Source, SourceError, SourceResult};
+use crate::http::{Request, Response, DEFAULT_TIMEOUT};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How a [`VaultSource`] authenticates.
+#[derive(Debug, Clone)]
+pub enum VaultAuth {
+    /// An existing token, e.g. from `VAULT_TOKEN`.
+    Token(String),
+    /// A login with the AppRole auth method mounted at `approle`.
+    AppRole { role_id: String, secret_id: String },
+}
+
+/// Reads configuration values from a secret of a Vault KV v2 engine.
+///
+/// Nested keys of the secret are flattened into dotted keys like those of a
+/// [`super::FileSource`]. Every value is treated as a secret.
+///
+/// Tokens with a TTL are renewed once two thirds of it have passed. When a
+/// token cannot be renewed or is rejected, AppRole logs in again; a given
+/// token that cannot be renewed is used until Vault rejects it.
+#[derive(Debug)]
+pub struct VaultSource {
+    addr: String,
+    auth: VaultAuth,
+    mount: String,
+    path: String,
+    timeout: Duration,
+    session: Mutex<Option<Session>>,
+}
+
+/// A token in use and when it has to be renewed.
+#[derive(Debug, Clone)]
+struct Session {
+    token: String,
+    renewable: bool,
+    /// Renewal time; `None` for tokens without a TTL.
+    renew_at: Option<Instant>,
+}
+
+impl Session {
+    /// Creates a session from the `auth` or `data` object of a response.
+    fn new(token: String, ttl: u64, renewable: bool, issued: Instant) -> Self {
+        Self {
+            token,
+            renewable,
+            renew_at: (ttl > 0).then(|| issued + Duration::from_secs(ttl) * 2 / 3),
+        }
+    }
+
+    fn needs_renewal(&self, now: Instant) -> bool {
+        self.renew_at.is_some_and(|at| now >= at)
+    }
+}
+
+impl VaultSource {
+    /// Creates a source over the secret at `path` of the KV v2 engine at `mount`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use shared::source::{VaultAuth, VaultSource};
+    ///
+    /// let source = VaultSource::new(
+    ///     "https://vault.example.com",
+    ///     VaultAuth::Token("hvs.example".to_string()),
+    ///     "secret",
+    ///     "deploy/prod",
+    /// );
+    /// assert_eq!(source.url(), "https://vault.example.com/v1/secret/data/deploy/prod");
+    /// ```
+    pub fn new(
+        addr: impl Into<String>,
+        auth: VaultAuth,
+        mount: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Self {
+        Self {
+            addr: addr.into().trim_end_matches('/').to_string(),
+            auth,
+            mount: mount.into().trim_matches('/').to_string(),
+            path: path.into().trim_matches('/').to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            session: Mutex::new(None),
+        }
+    }
+
+    /// Limits the duration of one request, 10 seconds by default.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the API URL of the secret.
+    pub fn url(&self) -> String {
+        format!("{}/v1/{}/data/{}", self.addr, self.mount, self.path)
+    }
+
+    fn error(&self, message: impl Into<String>) -> SourceError {
+        SourceError::Remote {
+            source_name: format!("vault:{}/{}", self.mount, self.path),
+            message: message.into(),
+        }
+    }
+
+    fn send(&self, api: &str, token: Option<&str>, body: Option<String>) -> SourceResult<Response> {
+        let mut request =
+            Request::get(format!("{}/v1/{}", self.addr, api)).with_timeout(self.timeout);
+        if let Some(token) = token {
+            request = request.with_header("X-Vault-Token", token);
+        }
+        if let Some(body) = body {
+            request = request.with_body(body);
+        }
+        request.send().map_err(|e| self.error(e.to_string()))
+    }
+
+    /// Sends a request and returns its JSON body, failing on error statuses.
+    fn call(&self, api: &str, token: Option<&str>, body: Option<String>) -> SourceResult<Value> {
+        let response = self.send(api, token, body)?;
+        if response.status != 200 {
+            return Err(self.error(format!("{} returned status {}", api, response.status)));
+        }
+        serde_json::from_str(&response.body).map_err(|e| self.error(e.to_string()))
+    }
+
+    /// Obtains a session for the configured authentication.
+    fn login(&self) -> SourceResult<Session> {
+        let issued = Instant::now();
+        match &self.auth {
+            VaultAuth::Token(token) => {
+                let lookup = self.call("auth/token/lookup-self", Some(token), None)?;
+                let data = &lookup["data"];
+                Ok(Session::new(
+                    token.clone(),
+                    data["ttl"].as_u64().unwrap_or(0),
+                    data["renewable"].as_bool().unwrap_or(false),
+                    issued,
+                ))
+            }
+            VaultAuth::AppRole { role_id, secret_id } => {
+                let body = serde_json::json!({"role_id": role_id, "secret_id": secret_id});
+                let login = self.call("auth/approle/login", None, Some(body.to_string()))?;
+                self.session_from_auth(&login, issued)
+            }
+        }
+    }
+
+    /// Renews the token of a session, logging in again if that fails with AppRole.
+    ///
+    /// A given token that cannot be renewed is kept without further renewals.
+    fn renew(&self, session: &Session) -> SourceResult<Session> {
+        let issued = Instant::now();
+        let renewed = if session.renewable {
+            self.call(
+                "auth/token/renew-self",
+                Some(&session.token),
+                Some("{}".to_string()),
+            )
+            .and_then(|renewal| self.session_from_auth(&renewal, issued))
+        } else {
+            Err(self.error("token is not renewable"))
+        };
+        match (renewed, &self.auth) {
+            (Ok(session), _) => Ok(session),
+            (Err(_), VaultAuth::AppRole { .. }) => self.login(),
+            (Err(_), VaultAuth::Token(_)) => Ok(Session {
+                renew_at: None,
+                ..session.clone()
+            }),
+        }
+    }
+
+    fn session_from_auth(&self, response: &Value, issued: Instant) -> SourceResult<Session> {
+        let auth = &response["auth"];
+        let token = auth["client_token"]
+            .as_str()
+            .ok_or_else(|| self.error("response without client_token"))?;
+        Ok(Session::new(
+            token.to_string(),
+            auth["lease_duration"].as_u64().unwrap_or(0),
+            auth["renewable"].as_bool().unwrap_or(false),
+            issued,
+        ))
+    }
+
+    /// Returns a valid token, logging in or renewing as needed.
+    fn token(&self, session: &mut Option<Session>) -> SourceResult<String> {
+        let current = match session.take() {
+            None => self.login()?,
+            Some(current) if current.needs_renewal(Instant::now()) => self.renew(&current)?,
+            Some(current) => current,
+        };
+        let token = current.token.clone();
+        *session = Some(current);
+        Ok(token)
+    }
+
+    fn read(&self) -> SourceResult<Value> {
+        let api = format!("{}/data/{}", self.mount, self.path);
+        let mut session = self.session.lock().unwrap();
+        let token = self.token(&mut session)?;
+        let mut response = self.send(&api, Some(&token), None)?;
+        if response.status == 403 && matches!(self.auth, VaultAuth::AppRole { .. }) {
+            // The token was revoked or expired early; a new login may still succeed.
+            *session = None;
+            let token = self.token(&mut session)?;
+            response = self.send(&api, Some(&token), None)?;
+        }
+        match response.status {
+            200 => serde_json::from_str(&response.body).map_err(|e| self.error(e.to_string())),
+            404 => Err(self.error("secret does not exist")),
+            status => Err(self.error(format!("reading the secret returned status {}", status))),
+        }
+    }
+}
+
+impl Source for VaultSource {
+    fn name(&self) -> &str {
+        "vault"
+    }
+
+    fn load(&self) -> SourceResult<HashMap<String, RawValue>> {
+        let secret = self.read()?;
+        let data = &secret["data"]["data"];
+        if !data.is_object() {
+            return Err(self.error("response without data"));
+        }
+        FileFormat::Json
+            .parse(&data.to_string())
+            .map_err(|message| self.error(message))
+    }
+
+    /// Every value of the secret is treated as a secret.
+    fn sensitive_keys(&self) -> &[String] {
+        &[]
+    }
+
+    fn is_sensitive(&self, _key: &str) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::server::{response, serve};
+
+    const SECRET: &str = r#"{"data": {"data": {"db": {"password": "hunter2"}, "region": "eu-west-1"}, "metadata": {"version": 3}}}"#;
+
+    #[test]
+    fn test_load_with_token() {
+        let (addr, requests) = serve(vec![
+            response("200 OK", &[], r#"{"data": {"ttl": 0, "renewable": false}}"#),
+            response("200 OK", &[], SECRET),
+        ]);
+        let source = VaultSource::new(
+            addr,
+            VaultAuth::Token("hvs.t0ken".to_string()),
+            "secret",
+            "/deploy/prod/",
+        );
+
+        let values = source.load().unwrap();
+        assert_eq!(values.get("db.password"), Some(&RawValue::from("hunter2")));
+        assert_eq!(values.get("region"), Some(&RawValue::from("eu-west-1")));
+        assert!(source.is_sensitive("region"));
+
+        let lookup = requests.recv().unwrap();
+        assert!(lookup[0].starts_with("GET /v1/auth/token/lookup-self "));
+        let read = requests.recv().unwrap();
+        assert!(read[0].starts_with("GET /v1/secret/data/deploy/prod "));
+        assert!(read.contains(&"x-vault-token: hvs.t0ken".to_string()));
+    }
+
+    #[test]
+    fn test_approle_logs_in_again_when_token_is_rejected() {
+        let login = |token: &str| {
+            response(
+                "200 OK",
+                &[],
+                &format!(
+                    r#"{{"auth": {{"client_token": "{}", "lease_duration": 3600, "renewable": true}}}}"#,
+                    token
+                ),
+            )
+        };
+        let (addr, requests) = serve(vec![
+            login("hvs.first"),
+            response("403 Forbidden", &[], r#"{"errors": ["permission denied"]}"#),
+            login("hvs.second"),
+            response("200 OK", &[], SECRET),
+        ]);
+        let source = VaultSource::new(
+            addr,
+            VaultAuth::AppRole {
+                role_id: "deploy".to_string(),
+                secret_id: "s3cr3t".to_string(),
+            },
+            "secret",
+            "deploy/prod",
+        );
+
+        assert_eq!(
+            source.get("db.password").unwrap(),
+            Some(RawValue::from("hunter2"))
+        );
+        let first = requests.recv().unwrap();
+        assert!(first[0].starts_with("POST /v1/auth/approle/login "));
+        assert_eq!(
+            first.last().unwrap(),
+            r#"{"role_id":"deploy","secret_id":"s3cr3t"}"#
+        );
+        requests.recv().unwrap();
+        requests.recv().unwrap();
+        let retry = requests.recv().unwrap();
+        assert!(retry.contains(&"x-vault-token: hvs.second".to_string()));
+    }
+
+    #[test]
+    fn test_token_is_kept_when_it_cannot_be_renewed() {
+        let (addr, requests) = serve(vec![response("200 OK", &[], SECRET)]);
+        let source = VaultSource::new(
+            addr,
+            VaultAuth::Token("hvs.t0ken".to_string()),
+            "secret",
+            "deploy/prod",
+        );
+        let issued = Instant::now().checked_sub(Duration::from_secs(60)).unwrap();
+        *source.session.lock().unwrap() =
+            Some(Session::new("hvs.t0ken".to_string(), 90, false, issued));
+
+        assert_eq!(
+            source.get("region").unwrap(),
+            Some(RawValue::from("eu-west-1"))
+        );
+        let read = requests.recv().unwrap();
+        assert!(read[0].starts_with("GET /v1/secret/data/deploy/prod "));
+        assert!(read.contains(&"x-vault-token: hvs.t0ken".to_string()));
+    }
+
+    #[test]
+    fn test_session_renewal_time() {
+        let issued = Instant::now();
+        let session = Session::new("hvs.t".to_string(), 90, true, issued);
+        assert!(!session.needs_renewal(issued + Duration::from_secs(59)));
+        assert!(session.needs_renewal(issued + Duration::from_secs(60)));
+        let forever = Session::new("hvs.t".to_string(), 0, false, issued);
+        assert!(!forever.needs_renewal(issued + Duration::from_secs(86400)));
+    }
+}
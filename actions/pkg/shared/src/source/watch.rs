@@ -0,0 +1,83 @@
+use super::{RawValue, SourceResult};
+use std::collections::{BTreeSet, HashMap};
+
+/// How a value changed between two polls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+/// A changed key of a watched source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceEvent {
+    pub key: String,
+    pub kind: ChangeKind,
+}
+
+/// Detects changes of a source by polling; see [`super::Source::watch`].
+pub trait Watch: Send {
+    /// Returns the keys that changed since the previous poll, or since the
+    /// watch was started, sorted by key.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SourceError` if the source cannot be read; the next poll
+    /// compares against the last values read successfully.
+    fn poll(&mut self) -> SourceResult<Vec<SourceEvent>>;
+}
+
+/// Compares two loads of a source.
+pub(super) fn diff(
+    old: &HashMap<String, RawValue>,
+    new: &HashMap<String, RawValue>,
+) -> Vec<SourceEvent> {
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let kind = match (old.get(key), new.get(key)) {
+                (None, Some(_)) => ChangeKind::Added,
+                (Some(_), None) => ChangeKind::Removed,
+                (Some(a), Some(b)) if a != b => ChangeKind::Changed,
+                _ => return None,
+            };
+            Some(SourceEvent {
+                key: key.clone(),
+                kind,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let old = HashMap::from([
+            ("a".to_string(), RawValue::from("1")),
+            ("b".to_string(), RawValue::from("2")),
+            ("c".to_string(), RawValue::from("3")),
+        ]);
+        let new = HashMap::from([
+            ("b".to_string(), RawValue::from("2")),
+            ("c".to_string(), RawValue::from("4")),
+            ("d".to_string(), RawValue::from("5")),
+        ]);
+        let event = |key: &str, kind| SourceEvent {
+            key: key.to_string(),
+            kind,
+        };
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                event("a", ChangeKind::Removed),
+                event("c", ChangeKind::Changed),
+                event("d", ChangeKind::Added),
+            ]
+        );
+        assert!(diff(&new, &new).is_empty());
+    }
+}
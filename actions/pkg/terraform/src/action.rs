@@ -0,0 +1,80 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Represents the actions supported by the Terraform binary.
+///
+/// Parsed (case-insensitively) from the `ACTION_TERRAFORM_CMD` environment variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerraformAction {
+    /// Run the init -> workspace -> plan chain.
+    Plan,
+
+    /// Run the init -> workspace -> plan -> apply chain.
+    Apply,
+
+    /// Run init, then `terraform validate` and report its diagnostics.
+    Validate,
+
+    /// Check the formatting with `terraform fmt -check`; no init is needed.
+    FmtCheck,
+
+    /// Run the init -> workspace -> destroy chain.
+    ///
+    /// Requires `ACTION_TERRAFORM_ALLOW_DESTROY=true`.
+    Destroy,
+}
+
+impl TerraformAction {
+    /// All supported actions, in the form accepted by `FromStr`.
+    pub const VARIANTS: &'static [&'static str] =
+        &["plan", "apply", "validate", "fmt_check", "destroy"];
+
+    /// Returns the string representation of the action.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Plan => "plan",
+            Self::Apply => "apply",
+            Self::Validate => "validate",
+            Self::FmtCheck => "fmt_check",
+            Self::Destroy => "destroy",
+        }
+    }
+}
+
+impl FromStr for TerraformAction {
+    type Err = String;
+
+    /// Parses an action name, ignoring case.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terraform::TerraformAction;
+    ///
+    /// let action: TerraformAction = "PLAN".parse().unwrap();
+    /// assert_eq!(action, TerraformAction::Plan);
+    ///
+    /// let err = "refresh".parse::<TerraformAction>().unwrap_err();
+    /// assert!(err.contains("plan, apply, validate, fmt_check, destroy"));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plan" => Ok(Self::Plan),
+            "apply" => Ok(Self::Apply),
+            "validate" => Ok(Self::Validate),
+            "fmt_check" => Ok(Self::FmtCheck),
+            "destroy" => Ok(Self::Destroy),
+            _ => Err(format!(
+                "unknown command '{}', expected one of: {}",
+                s,
+                Self::VARIANTS.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TerraformAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -1,15 +1,24 @@
 use crate::command::{TerraformCommand, WorkspaceOperation};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct CommandChain {
     dir: PathBuf,
     vars: HashMap<String, String>,
+    var_files: Vec<PathBuf>,
     backend_config: Option<HashMap<String, String>>,
+    backend_config_file: Option<PathBuf>,
+    upgrade: bool,
+    no_color: bool,
     workspace: Option<String>,
     out: Option<PathBuf>,
     auto_approve: bool,
+    targets: Vec<String>,
+    replace: Vec<String>,
+    detailed_exitcode: bool,
+    parallelism: Option<u32>,
+    lock_timeout: Option<String>,
 }
 
 impl CommandChain {
@@ -17,10 +26,19 @@ impl CommandChain {
         Self {
             dir,
             vars: HashMap::new(),
+            var_files: Vec::new(),
             backend_config: None,
+            backend_config_file: None,
+            upgrade: false,
+            no_color: false,
             workspace: None,
             out: None,
             auto_approve: false,
+            targets: Vec::new(),
+            replace: Vec::new(),
+            detailed_exitcode: false,
+            parallelism: None,
+            lock_timeout: None,
         }
     }
 
@@ -29,11 +47,23 @@ impl CommandChain {
         self
     }
 
+    /// Loads variable files for plan, apply and destroy, in the given order.
+    pub fn with_var_files(mut self, var_files: Vec<PathBuf>) -> Self {
+        self.var_files = var_files;
+        self
+    }
+
     pub fn with_backend_config(mut self, config: HashMap<String, String>) -> Self {
         self.backend_config = Some(config);
         self
     }
 
+    /// Loads backend settings from a file, e.g. `backend.hcl`, during init.
+    pub fn with_backend_config_file(mut self, file: Option<PathBuf>) -> Self {
+        self.backend_config_file = file;
+        self
+    }
+
     pub fn with_workspace(mut self, workspace: Option<String>) -> Self {
         self.workspace = workspace;
         self
@@ -49,33 +79,90 @@ impl CommandChain {
         self
     }
 
+    /// Limits plan, apply and destroy to the given resource addresses.
+    pub fn with_targets(mut self, targets: Vec<String>) -> Self {
+        self.targets = targets;
+        self
+    }
+
+    /// Makes the plan exit with status 2 when it has changes, see `PlanOutcome`.
+    pub fn with_detailed_exitcode(mut self, detailed_exitcode: bool) -> Self {
+        self.detailed_exitcode = detailed_exitcode;
+        self
+    }
+
+    /// Forces plan and apply to recreate the given resource addresses.
+    pub fn with_replace(mut self, replace: Vec<String>) -> Self {
+        self.replace = replace;
+        self
+    }
+
+    /// Limits the concurrent operations of plan and apply.
+    pub fn with_parallelism(mut self, parallelism: Option<u32>) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Runs the chain in another directory, e.g. one module of a monorepo.
+    pub fn with_dir(mut self, dir: PathBuf) -> Self {
+        self.dir = dir;
+        self
+    }
+
+    /// Returns the working directory of the chain.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Makes init upgrade modules and providers to the newest allowed versions.
+    pub fn with_upgrade(mut self, upgrade: bool) -> Self {
+        self.upgrade = upgrade;
+        self
+    }
+
+    /// Disables colors in the init output.
+    pub fn with_no_color(mut self, no_color: bool) -> Self {
+        self.no_color = no_color;
+        self
+    }
+
+    /// Makes plan, apply and destroy wait for the state lock, e.g. `Some("5m")`.
+    pub fn with_lock_timeout(mut self, lock_timeout: Option<String>) -> Self {
+        self.lock_timeout = lock_timeout;
+        self
+    }
+
     fn build_init(&self) -> TerraformCommand {
         TerraformCommand::Init {
             dir: self.dir.clone(),
             backend_config: self.backend_config.clone(),
+            backend_config_file: self.backend_config_file.clone(),
+            upgrade: self.upgrade,
+            no_color: self.no_color,
         }
     }
 
     fn build_workspace(&self) -> Option<Vec<TerraformCommand>> {
         self.workspace.as_ref().map(|ws| {
-            vec![
-                TerraformCommand::Workspace {
-                    dir: self.dir.clone(),
-                    operation: WorkspaceOperation::New(ws.clone()),
-                },
-                TerraformCommand::Workspace {
-                    dir: self.dir.clone(),
-                    operation: WorkspaceOperation::Select(ws.clone()),
-                },
-            ]
+            vec![TerraformCommand::Workspace {
+                dir: self.dir.clone(),
+                operation: WorkspaceOperation::SelectOrCreate(ws.clone()),
+            }]
         })
     }
 
-    fn build_plan(&self) -> TerraformCommand {
+    fn build_plan(&self, destroy: bool) -> TerraformCommand {
         TerraformCommand::Plan {
             dir: self.dir.clone(),
             vars: self.vars.clone(),
+            var_files: self.var_files.clone(),
+            targets: self.targets.clone(),
+            replace: self.replace.clone(),
+            parallelism: self.parallelism,
+            lock_timeout: self.lock_timeout.clone(),
             out: self.out.clone(),
+            detailed_exitcode: self.detailed_exitcode,
+            destroy,
         }
     }
 
@@ -83,28 +170,104 @@ impl CommandChain {
         TerraformCommand::Apply {
             dir: self.dir.clone(),
             plan_file: self.out.clone(),
+            var_files: self.var_files.clone(),
+            targets: self.targets.clone(),
+            replace: self.replace.clone(),
+            parallelism: self.parallelism,
+            lock_timeout: self.lock_timeout.clone(),
             auto_approve: self.auto_approve,
         }
     }
 
-    pub fn plan_chain(&self) -> Vec<TerraformCommand> {
-        let mut commands = vec![self.build_init()];
+    fn build_destroy(&self) -> TerraformCommand {
+        TerraformCommand::Destroy {
+            dir: self.dir.clone(),
+            vars: self.vars.clone(),
+            var_files: self.var_files.clone(),
+            auto_approve: self.auto_approve,
+            targets: self.targets.clone(),
+            lock_timeout: self.lock_timeout.clone(),
+        }
+    }
 
+    /// Builds the init -> workspace chain other chains start with.
+    pub fn init_chain(&self) -> Vec<TerraformCommand> {
+        let mut commands = vec![self.build_init()];
         if let Some(workspace_cmds) = self.build_workspace() {
             commands.extend(workspace_cmds);
         }
+        commands
+    }
 
-        commands.push(self.build_plan());
+    pub fn plan_chain(&self) -> Vec<TerraformCommand> {
+        let mut commands = self.init_chain();
+        commands.push(self.build_plan(false));
         commands
     }
 
     pub fn apply_chain(&self) -> Vec<TerraformCommand> {
-        let mut commands = vec![self.build_init()];
-        if let Some(workspace_cmds) = self.build_workspace() {
-            commands.extend(workspace_cmds);
-        }
-        commands.push(self.build_plan());
+        let mut commands = self.init_chain();
+        commands.push(self.build_plan(false));
         commands.push(self.build_apply());
         commands
     }
+
+    /// Builds the init -> workspace -> apply chain for a plan file made earlier.
+    ///
+    /// Callers are responsible for verifying the plan file.
+    pub fn saved_plan_apply_chain(&self) -> Vec<TerraformCommand> {
+        let mut commands = self.init_chain();
+        commands.push(self.build_apply());
+        commands
+    }
+
+    /// Builds the init -> workspace -> destroy chain.
+    ///
+    /// Callers are responsible for checking that destroying is allowed.
+    pub fn destroy_chain(&self) -> Vec<TerraformCommand> {
+        let mut commands = self.init_chain();
+        commands.push(self.build_destroy());
+        commands
+    }
+
+    /// Builds the init -> workspace -> plan chain previewing a destroy.
+    pub fn destroy_plan_chain(&self) -> Vec<TerraformCommand> {
+        let mut commands = self.init_chain();
+        commands.push(self.build_plan(true));
+        commands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_destroy_chain() {
+        let chain = CommandChain::new(PathBuf::from("/infra"))
+            .with_workspace(Some("staging".to_string()))
+            .with_auto_approve(true)
+            .with_targets(vec!["module.cdn".to_string()]);
+
+        let args: Vec<_> = chain.destroy_chain().iter().map(|c| c.to_args()).collect();
+        assert_eq!(
+            args,
+            vec![
+                vec!["init", "-input=false", "-reconfigure"],
+                vec!["workspace", "select", "staging"],
+                vec![
+                    "destroy",
+                    "-input=false",
+                    "-auto-approve",
+                    "-target=module.cdn"
+                ],
+            ]
+        );
+
+        let plan = chain.destroy_plan_chain().pop().unwrap();
+        assert_eq!(
+            plan.to_args(),
+            vec!["plan", "-input=false", "-destroy", "-target=module.cdn"]
+        );
+    }
 }
@@ -0,0 +1,263 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::path::Path;
+use std::time::Duration;
+
+/// Hostname of Terraform Cloud, used when the configuration does not set one.
+pub const DEFAULT_CLOUD_HOSTNAME: &str = "app.terraform.io";
+
+/// Matches API tokens of Terraform Cloud and Terraform Enterprise.
+const TOKEN_PATTERN: &str = r"[A-Za-z0-9]{14}\.atlasv1\.[A-Za-z0-9_-]{60,}";
+/// Matches the browser links of remote runs, which name the organization and workspace.
+const RUN_URL_PATTERN: &str =
+    r"https://[A-Za-z0-9.-]+/app/[^\s/]+/(workspaces/)?[^\s/]+/runs/run-[A-Za-z0-9]+";
+
+/// Terraform Cloud or Enterprise backend of a working directory.
+///
+/// Plans and applies on these backends run remotely and only stream their
+/// output, so there is no local plan file to inspect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerraformCloud {
+    /// Hostname of the Terraform Cloud or Enterprise instance.
+    pub hostname: String,
+    /// API token for the hostname, if one is set in the environment.
+    pub token: Option<String>,
+    /// How the run status is polled once the CLI has finished; not polled when `None`.
+    pub polling: Option<Polling>,
+}
+
+/// How often and how long the status of a remote run is polled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Polling {
+    pub interval: Duration,
+    pub timeout: Duration,
+}
+
+impl TerraformCloud {
+    /// Detects a `cloud {}` block or `remote` backend in the `*.tf` files of a directory.
+    ///
+    /// The files are scanned line by line rather than parsed, which covers the
+    /// way these blocks are written in practice.
+    pub fn detect(dir: &Path) -> Option<Self> {
+        let mut files: Vec<_> = std::fs::read_dir(dir)
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "tf"))
+            .collect();
+        files.sort();
+
+        files.iter().find_map(|path| {
+            let source = std::fs::read_to_string(path).ok()?;
+            let hostname = parse_hostname(&source)?;
+            let env: HashMap<String, String> = env::vars().collect();
+            let hostname = env
+                .get("TF_CLOUD_HOSTNAME")
+                .filter(|h| !h.is_empty())
+                .cloned()
+                .unwrap_or(hostname);
+            Some(Self {
+                token: token_for(&hostname, &env),
+                hostname,
+                polling: None,
+            })
+        })
+    }
+
+    pub fn with_polling(mut self, polling: Option<Polling>) -> Self {
+        self.polling = polling;
+        self
+    }
+
+    /// Returns the values to mask in the output.
+    pub fn values(&self) -> Vec<&str> {
+        self.token.iter().map(|s| s.as_str()).collect()
+    }
+
+    /// Returns the patterns of tokens and run links to mask in the output.
+    pub fn patterns() -> Vec<String> {
+        vec![TOKEN_PATTERN.to_string(), RUN_URL_PATTERN.to_string()]
+    }
+
+    /// Returns the API endpoint of a run.
+    pub fn run_endpoint(&self, run_id: &str) -> String {
+        format!("https://{}/api/v2/runs/{}", self.hostname, run_id)
+    }
+}
+
+/// Returns the hostname of the first cloud block, or `None` without one.
+fn parse_hostname(source: &str) -> Option<String> {
+    let mut lines = source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && !line.starts_with("//"));
+    lines.find(|line| {
+        line.strip_prefix("cloud")
+            .is_some_and(|rest| rest.trim_start().starts_with('{'))
+            || line.starts_with(r#"backend "remote""#)
+    })?;
+
+    let hostname = lines
+        .take_while(|line| !line.starts_with('}'))
+        .find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "hostname").then(|| value.trim().trim_matches('"').to_string())
+        });
+    Some(hostname.unwrap_or_else(|| DEFAULT_CLOUD_HOSTNAME.to_string()))
+}
+
+/// Looks up the API token the Terraform CLI would use for a hostname.
+fn token_for(hostname: &str, env: &HashMap<String, String>) -> Option<String> {
+    let host_var = format!("TF_TOKEN_{}", hostname.replace('.', "_").replace('-', "__"));
+    [host_var.as_str(), "TFE_TOKEN"]
+        .iter()
+        .find_map(|name| env.get(*name).filter(|v| !v.is_empty()).cloned())
+}
+
+/// Finds the link of a remote run in a line of streamed output.
+///
+/// # Example
+///
+/// ```rust
+/// use terraform::cloud::run_url;
+///
+/// assert_eq!(
+///     run_url("https://app.terraform.io/app/acme/infra/runs/run-CZcmD7eagjhyX0vN"),
+///     Some("https://app.terraform.io/app/acme/infra/runs/run-CZcmD7eagjhyX0vN".to_string())
+/// );
+/// assert_eq!(run_url("Waiting for the plan to start..."), None);
+/// ```
+pub fn run_url(line: &str) -> Option<String> {
+    line.split_whitespace()
+        .find(|word| word.starts_with("https://") && run_id(word).is_some())
+        .map(str::to_string)
+}
+
+/// Returns the run ID at the end of a run link.
+pub fn run_id(url: &str) -> Option<&str> {
+    let (_, id) = url.rsplit_once("/runs/")?;
+    (id.starts_with("run-")
+        && id.len() > 4
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+    .then_some(id)
+}
+
+/// Status of a remote run as reported by the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunStatus(pub String);
+
+impl RunStatus {
+    /// Parses the response of the run endpoint.
+    pub fn parse(json: &str) -> serde_json::Result<Self> {
+        let run: RunJson = serde_json::from_str(json)?;
+        Ok(Self(run.data.attributes.status))
+    }
+
+    /// Returns whether the run will not change status anymore.
+    pub fn is_finished(&self) -> bool {
+        self.is_success()
+            || matches!(
+                self.0.as_str(),
+                "errored" | "discarded" | "canceled" | "force_canceled" | "policy_soft_failed"
+            )
+    }
+
+    /// Returns whether the run finished without errors.
+    pub fn is_success(&self) -> bool {
+        matches!(
+            self.0.as_str(),
+            "applied" | "planned_and_finished" | "planned_and_saved"
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct RunJson {
+    data: RunData,
+}
+
+#[derive(Deserialize)]
+struct RunData {
+    attributes: RunAttributes,
+}
+
+#[derive(Deserialize)]
+struct RunAttributes {
+    status: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hostname() {
+        let cloud = r#"
+terraform {
+  # backend "s3" {}
+  cloud {
+    organization = "acme"
+    hostname     = "tfe.acme.dev"
+
+    workspaces {
+      name = "infra"
+    }
+  }
+}
+"#;
+        assert_eq!(parse_hostname(cloud), Some("tfe.acme.dev".to_string()));
+
+        let remote = "terraform {\n  backend \"remote\" {\n    organization = \"acme\"\n  }\n}\n";
+        assert_eq!(
+            parse_hostname(remote),
+            Some(DEFAULT_CLOUD_HOSTNAME.to_string())
+        );
+
+        let s3 = "terraform {\n  backend \"s3\" {\n    bucket = \"state\"\n  }\n}\n";
+        assert_eq!(parse_hostname(s3), None);
+    }
+
+    #[test]
+    fn test_token_for_hostname() {
+        let env = HashMap::from([
+            (
+                "TF_TOKEN_tfe_acme__corp_dev".to_string(),
+                "host".to_string(),
+            ),
+            ("TFE_TOKEN".to_string(), "fallback".to_string()),
+        ]);
+        assert_eq!(
+            token_for("tfe.acme-corp.dev", &env),
+            Some("host".to_string())
+        );
+        assert_eq!(
+            token_for("app.terraform.io", &env),
+            Some("fallback".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_id() {
+        assert_eq!(
+            run_id("https://app.terraform.io/app/acme/infra/runs/run-CZcmD7eagjhyX0vN"),
+            Some("run-CZcmD7eagjhyX0vN")
+        );
+        assert_eq!(
+            run_id("https://app.terraform.io/app/acme/infra/runs/"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_run_status() {
+        let status =
+            RunStatus::parse(r#"{"data": {"id": "run-1", "attributes": {"status": "planning"}}}"#)
+                .unwrap();
+        assert!(!status.is_finished());
+
+        let status = RunStatus("policy_soft_failed".to_string());
+        assert!(status.is_finished());
+        assert!(!status.is_success());
+        assert!(RunStatus("applied".to_string()).is_success());
+    }
+}
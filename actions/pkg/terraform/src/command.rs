@@ -1,3 +1,6 @@
+/// Keeps commands that could prompt for input from waiting on a terminal CI never provides.
+const INPUT_DISABLED: &str = "-input=false";
+
 /// Defines the operations that can be performed on Terraform workspaces.
 #[derive(Debug, Clone)]
 pub enum WorkspaceOperation {
@@ -12,6 +15,12 @@ pub enum WorkspaceOperation {
 
     /// Delete a workspace by name.
     Delete(String),
+
+    /// Select a workspace by name, creating it if it does not exist.
+    ///
+    /// Rendered as `workspace select`; `TerraformExecutor` lists the workspaces
+    /// first and runs `workspace new` instead when the workspace is missing.
+    SelectOrCreate(String),
 }
 
 /// Represents the various Terraform commands that can be executed.
@@ -23,9 +32,16 @@ pub enum TerraformCommand {
     ///
     /// - `dir`: The directory where Terraform is initialized.
     /// - `backend_config`: Optional backend configuration parameters.
+    /// - `backend_config_file`: Optional backend configuration file, e.g. `backend.hcl`;
+    ///   `backend_config` parameters take precedence over its settings.
+    /// - `upgrade`: Upgrade modules and providers to the newest allowed versions.
+    /// - `no_color`: Disable colors in the output.
     Init {
         dir: std::path::PathBuf,
         backend_config: Option<std::collections::HashMap<String, String>>,
+        backend_config_file: Option<std::path::PathBuf>,
+        upgrade: bool,
+        no_color: bool,
     },
 
     /// Create an execution plan.
@@ -34,11 +50,25 @@ pub enum TerraformCommand {
     ///
     /// - `dir`: The directory where the plan is created.
     /// - `vars`: Variables to pass to the Terraform configuration.
+    /// - `var_files`: Variable files to load, in order; later files take precedence.
+    /// - `targets`: Resource addresses to limit the plan to; all resources when empty.
+    /// - `replace`: Resource addresses to plan to recreate even without changes.
+    /// - `parallelism`: Limit on concurrent operations; Terraform's default when `None`.
+    /// - `lock_timeout`: How long to wait for the state lock, e.g. `5m`.
     /// - `out`: Optional path to save the generated plan.
+    /// - `detailed_exitcode`: Exit with status 2 when the plan has changes.
+    /// - `destroy`: Plan to destroy all managed resources, or `targets` only.
     Plan {
         dir: std::path::PathBuf,
         vars: std::collections::HashMap<String, String>,
+        var_files: Vec<std::path::PathBuf>,
+        targets: Vec<String>,
+        replace: Vec<String>,
+        parallelism: Option<u32>,
+        lock_timeout: Option<String>,
         out: Option<std::path::PathBuf>,
+        detailed_exitcode: bool,
+        destroy: bool,
     },
 
     /// Apply the changes required to reach the desired state of the configuration.
@@ -47,13 +77,117 @@ pub enum TerraformCommand {
     ///
     /// - `dir`: The directory where the apply is executed.
     /// - `plan_file`: Optional path to a plan file.
+    /// - `var_files`: Variable files to load, in order.
+    /// - `targets`: Resource addresses to limit the apply to.
+    /// - `replace`: Resource addresses to recreate even without changes.
+    /// - `parallelism`: Limit on concurrent operations; Terraform's default when `None`.
+    /// - `lock_timeout`: How long to wait for the state lock, e.g. `5m`.
     /// - `auto_approve`: Automatically approve the plan without prompting.
+    ///
+    /// `var_files`, `targets` and `replace` are ignored with a plan file, which
+    /// was already created with them.
     Apply {
         dir: std::path::PathBuf,
         plan_file: Option<std::path::PathBuf>,
+        var_files: Vec<std::path::PathBuf>,
+        targets: Vec<String>,
+        replace: Vec<String>,
+        parallelism: Option<u32>,
+        lock_timeout: Option<String>,
+        auto_approve: bool,
+    },
+
+    /// Destroy the infrastructure managed by the configuration.
+    ///
+    /// # Fields
+    ///
+    /// - `dir`: The directory where the destroy is executed.
+    /// - `vars`: Variables to pass to the Terraform configuration.
+    /// - `var_files`: Variable files to load, in order; later files take precedence.
+    /// - `auto_approve`: Automatically approve the destroy without prompting.
+    /// - `targets`: Resource addresses to limit the destroy to; all resources when empty.
+    /// - `lock_timeout`: How long to wait for the state lock, e.g. `5m`.
+    Destroy {
+        dir: std::path::PathBuf,
+        vars: std::collections::HashMap<String, String>,
+        var_files: Vec<std::path::PathBuf>,
         auto_approve: bool,
+        targets: Vec<String>,
+        lock_timeout: Option<String>,
+    },
+
+    /// Check whether the configuration is valid.
+    ///
+    /// # Fields
+    ///
+    /// - `dir`: The directory whose configuration is validated.
+    /// - `json`: Report the diagnostics as JSON.
+    Validate { dir: std::path::PathBuf, json: bool },
+
+    /// Rewrite or check configuration files in the canonical format.
+    ///
+    /// # Fields
+    ///
+    /// - `dir`: The directory whose files are formatted.
+    /// - `check`: Only check the formatting; files are left unchanged.
+    /// - `recursive`: Also process files in subdirectories.
+    /// - `diff`: Print the differences of the formatting changes.
+    Fmt {
+        dir: std::path::PathBuf,
+        check: bool,
+        recursive: bool,
+        diff: bool,
     },
 
+    /// Show a saved plan.
+    ///
+    /// # Fields
+    ///
+    /// - `dir`: The directory where the plan was created.
+    /// - `plan_file`: Path to the saved plan.
+    /// - `json`: Print the plan as JSON.
+    Show {
+        dir: std::path::PathBuf,
+        plan_file: std::path::PathBuf,
+        json: bool,
+    },
+
+    /// Mark a resource as degraded so the next apply recreates it.
+    ///
+    /// # Fields
+    ///
+    /// - `dir`: The directory whose state is changed.
+    /// - `address`: The address of the resource.
+    Taint {
+        dir: std::path::PathBuf,
+        address: String,
+    },
+
+    /// Remove the degraded mark from a resource.
+    ///
+    /// # Fields
+    ///
+    /// - `dir`: The directory whose state is changed.
+    /// - `address`: The address of the resource.
+    Untaint {
+        dir: std::path::PathBuf,
+        address: String,
+    },
+
+    /// Read the root module outputs as JSON.
+    ///
+    /// # Fields
+    ///
+    /// - `dir`: The directory whose state the outputs are read from.
+    Output { dir: std::path::PathBuf },
+
+    /// Print the current state as JSON.
+    ///
+    /// # Fields
+    ///
+    /// - `dir`: The directory whose state is read.
+    StatePull { dir: std::path::PathBuf },
+
     /// Manage Terraform workspaces.
     ///
     /// # Fields
@@ -67,6 +201,35 @@ pub enum TerraformCommand {
 }
 
 impl TerraformCommand {
+    /// Returns whether the command only initializes the directory or reads or
+    /// writes the state, so it is safe to retry after a transient backend error.
+    pub fn is_state_operation(&self) -> bool {
+        matches!(
+            self,
+            Self::Init { .. }
+                | Self::Output { .. }
+                | Self::StatePull { .. }
+                | Self::Taint { .. }
+                | Self::Untaint { .. }
+                | Self::Workspace { .. }
+        )
+    }
+
+    /// Returns the operation of the command, e.g. `plan` or `workspace select`.
+    pub fn operation(&self) -> String {
+        let args = self.to_args();
+        let words = if matches!(self, Self::Workspace { .. } | Self::StatePull { .. }) {
+            2
+        } else {
+            1
+        };
+        args.iter()
+            .take(words)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     /// Converts the `TerraformCommand` into a list of command-line arguments.
     ///
     /// # Examples
@@ -82,6 +245,9 @@ impl TerraformCommand {
     ///         ("key1".to_string(), "value1".to_string()),
     ///         ("key2".to_string(), "value2".to_string()),
     ///     ])),
+    ///     backend_config_file: Some(PathBuf::from("backend.hcl")),
+    ///     upgrade: true,
+    ///     no_color: false,
     /// };
     ///
     /// let args = init_command.to_args();
@@ -89,7 +255,10 @@ impl TerraformCommand {
     ///     args,
     ///     vec![
     ///         "init".to_string(),
+    ///         "-input=false".to_string(),
     ///         "-reconfigure".to_string(),
+    ///         "-upgrade".to_string(),
+    ///         "-backend-config=backend.hcl".to_string(),
     ///         "-backend-config=key1=value1".to_string(),
     ///         "-backend-config=key2=value2".to_string()
     ///     ]
@@ -100,8 +269,24 @@ impl TerraformCommand {
             Self::Init {
                 dir: _,
                 backend_config,
+                backend_config_file,
+                upgrade,
+                no_color,
             } => {
-                let mut args = vec!["init".to_string(), "-reconfigure".to_string()];
+                let mut args = vec![
+                    "init".to_string(),
+                    INPUT_DISABLED.to_string(),
+                    "-reconfigure".to_string(),
+                ];
+                if *upgrade {
+                    args.push("-upgrade".to_string());
+                }
+                if *no_color {
+                    args.push("-no-color".to_string());
+                }
+                if let Some(file) = backend_config_file {
+                    args.push(format!("-backend-config={}", file.to_string_lossy()));
+                }
                 if let Some(config) = backend_config {
                     let mut keys: Vec<_> = config.keys().collect();
                     keys.sort();
@@ -114,17 +299,32 @@ impl TerraformCommand {
                 }
                 args
             }
-            Self::Plan { dir: _, vars, out } => {
-                let mut args = vec!["plan".to_string()];
-
-                let mut var_keys: Vec<_> = vars.keys().collect();
-                var_keys.sort();
-
-                for key in var_keys {
-                    if let Some(value) = vars.get(key) {
-                        args.push(format!("-var={}={}", key, value));
-                    }
+            Self::Plan {
+                dir: _,
+                vars,
+                var_files,
+                targets,
+                replace,
+                parallelism,
+                lock_timeout,
+                out,
+                detailed_exitcode,
+                destroy,
+            } => {
+                let mut args = vec!["plan".to_string(), INPUT_DISABLED.to_string()];
+                if *destroy {
+                    args.push("-destroy".to_string());
+                }
+                if *detailed_exitcode {
+                    args.push("-detailed-exitcode".to_string());
                 }
+                if let Some(n) = parallelism {
+                    args.push(format!("-parallelism={}", n));
+                }
+                args.extend(lock_timeout_arg(lock_timeout));
+                args.extend(var_args(vars, var_files));
+                args.extend(address_args("-target", targets));
+                args.extend(address_args("-replace", replace));
                 if let Some(out_file) = out {
                     args.push("-out".to_string());
                     args.push(out_file.to_string_lossy().to_string());
@@ -134,17 +334,89 @@ impl TerraformCommand {
             Self::Apply {
                 dir: _,
                 plan_file,
+                var_files,
+                targets,
+                replace,
+                parallelism,
+                lock_timeout,
                 auto_approve,
             } => {
-                let mut args = vec!["apply".to_string()];
+                let mut args = vec!["apply".to_string(), INPUT_DISABLED.to_string()];
                 if *auto_approve {
                     args.push("-auto-approve".to_string());
                 }
-                if let Some(file) = plan_file {
-                    args.push(file.to_string_lossy().to_string());
+                if let Some(n) = parallelism {
+                    args.push(format!("-parallelism={}", n));
+                }
+                args.extend(lock_timeout_arg(lock_timeout));
+                match plan_file {
+                    Some(file) => args.push(file.to_string_lossy().to_string()),
+                    None => {
+                        args.extend(var_args(&Default::default(), var_files));
+                        args.extend(address_args("-target", targets));
+                        args.extend(address_args("-replace", replace));
+                    }
+                }
+                args
+            }
+            Self::Destroy {
+                dir: _,
+                vars,
+                var_files,
+                auto_approve,
+                targets,
+                lock_timeout,
+            } => {
+                let mut args = vec!["destroy".to_string(), INPUT_DISABLED.to_string()];
+                if *auto_approve {
+                    args.push("-auto-approve".to_string());
+                }
+                args.extend(lock_timeout_arg(lock_timeout));
+                args.extend(var_args(vars, var_files));
+                args.extend(address_args("-target", targets));
+                args
+            }
+            Self::Validate { dir: _, json } => {
+                let mut args = vec!["validate".to_string()];
+                if *json {
+                    args.push("-json".to_string());
+                }
+                args
+            }
+            Self::Fmt {
+                dir: _,
+                check,
+                recursive,
+                diff,
+            } => {
+                let mut args = vec!["fmt".to_string()];
+                if *check {
+                    args.push("-check".to_string());
+                }
+                if *recursive {
+                    args.push("-recursive".to_string());
                 }
+                if *diff {
+                    args.push("-diff".to_string());
+                }
+                args
+            }
+            Self::Show {
+                dir: _,
+                plan_file,
+                json,
+            } => {
+                let mut args = vec!["show".to_string()];
+                if *json {
+                    args.push("-json".to_string());
+                }
+                args.push(plan_file.to_string_lossy().to_string());
                 args
             }
+            Self::Taint { dir: _, address } => vec!["taint".to_string(), address.clone()],
+            Self::Untaint { dir: _, address } => vec!["untaint".to_string(), address.clone()],
+            Self::Output { dir: _ } => vec!["output".to_string(), "-json".to_string()],
+            Self::StatePull { dir: _ } => vec!["state".to_string(), "pull".to_string()],
             Self::Workspace { dir: _, operation } => {
                 let mut args = vec!["workspace".to_string()];
                 match operation {
@@ -153,7 +425,7 @@ impl TerraformCommand {
                         args.push("new".to_string());
                         args.push(name.clone());
                     }
-                    WorkspaceOperation::Select(name) => {
+                    WorkspaceOperation::Select(name) | WorkspaceOperation::SelectOrCreate(name) => {
                         args.push("select".to_string());
                         args.push(name.clone());
                     }
@@ -167,3 +439,134 @@ impl TerraformCommand {
         }
     }
 }
+
+/// Builds the `-var-file` arguments in order, followed by the `-var` arguments sorted by name.
+///
+/// Variables passed with `-var` take precedence over those from files.
+fn var_args(
+    vars: &std::collections::HashMap<String, String>,
+    var_files: &[std::path::PathBuf],
+) -> Vec<String> {
+    let mut args: Vec<String> = var_files
+        .iter()
+        .map(|file| format!("-var-file={}", file.to_string_lossy()))
+        .collect();
+
+    let mut var_keys: Vec<_> = vars.keys().collect();
+    var_keys.sort();
+
+    for key in var_keys {
+        if let Some(value) = vars.get(key) {
+            args.push(format!("-var={}={}", key, value));
+        }
+    }
+    args
+}
+
+/// Builds the `-lock-timeout` argument, if a timeout is set.
+fn lock_timeout_arg(lock_timeout: &Option<String>) -> Option<String> {
+    lock_timeout
+        .as_ref()
+        .map(|timeout| format!("-lock-timeout={}", timeout))
+}
+
+/// Builds one `flag=address` argument per resource address, e.g. `-target=module.cdn`.
+fn address_args(flag: &str, addresses: &[String]) -> Vec<String> {
+    addresses
+        .iter()
+        .map(|address| format!("{}={}", flag, address))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_fmt_args() {
+        let command = TerraformCommand::Fmt {
+            dir: PathBuf::from("/infra"),
+            check: true,
+            recursive: true,
+            diff: false,
+        };
+        assert_eq!(command.to_args(), vec!["fmt", "-check", "-recursive"]);
+    }
+
+    #[test]
+    fn test_plan_var_files_in_order() {
+        let command = TerraformCommand::Plan {
+            dir: PathBuf::from("/infra"),
+            vars: HashMap::from([("env".to_string(), "prod".to_string())]),
+            var_files: vec![PathBuf::from("common.tfvars"), PathBuf::from("prod.tfvars")],
+            targets: vec!["module.api".to_string()],
+            replace: vec!["aws_instance.web".to_string()],
+            parallelism: None,
+            lock_timeout: None,
+            out: None,
+            detailed_exitcode: false,
+            destroy: false,
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "plan",
+                "-input=false",
+                "-var-file=common.tfvars",
+                "-var-file=prod.tfvars",
+                "-var=env=prod",
+                "-target=module.api",
+                "-replace=aws_instance.web",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_plan_file_skips_planning_options() {
+        let command = TerraformCommand::Apply {
+            dir: PathBuf::from("/infra"),
+            plan_file: Some(PathBuf::from("plan.out")),
+            var_files: vec![PathBuf::from("prod.tfvars")],
+            targets: vec!["module.api".to_string()],
+            replace: vec!["aws_instance.web".to_string()],
+            parallelism: Some(4),
+            lock_timeout: Some("5m".to_string()),
+            auto_approve: false,
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "apply",
+                "-input=false",
+                "-parallelism=4",
+                "-lock-timeout=5m",
+                "plan.out"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_destroy_args() {
+        let command = TerraformCommand::Destroy {
+            dir: PathBuf::from("/infra"),
+            vars: HashMap::from([("region".to_string(), "eu-west-1".to_string())]),
+            var_files: vec![],
+            auto_approve: true,
+            targets: vec!["aws_s3_bucket.site".to_string(), "module.cdn".to_string()],
+            lock_timeout: None,
+        };
+        assert_eq!(
+            command.to_args(),
+            vec![
+                "destroy",
+                "-input=false",
+                "-auto-approve",
+                "-var=region=eu-west-1",
+                "-target=aws_s3_bucket.site",
+                "-target=module.cdn",
+            ]
+        );
+    }
+}
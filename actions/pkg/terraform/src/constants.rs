@@ -1,4 +1,4 @@
-use config::{ConfigValue, FileExists, Required};
+use config::{ConfigError, ConfigValue, FileExists, Number, OneOf, Required};
 
 use lazy_static::lazy_static;
 use std::path::PathBuf;
@@ -8,22 +8,257 @@ pub const ENV_TERRAFORM_CMD: &str = "ACTION_TERRAFORM_CMD";
 pub const ENV_TERRAFORM_BIN: &str = "ACTION_TERRAFORM_BIN";
 pub const ENV_TERRAFORM_OUTPUT: &str = "ACTION_TERRAFORM_OUTPUT";
 pub const ENV_TERRAFORM_WORKSPACE: &str = "ACTION_TERRAFORM_WORKSPACE";
+pub const ENV_TERRAFORM_ALLOW_DESTROY: &str = "ACTION_TERRAFORM_ALLOW_DESTROY";
+pub const ENV_TERRAFORM_TARGETS: &str = "ACTION_TERRAFORM_TARGETS";
+pub const ENV_TERRAFORM_TERRAGRUNT: &str = "ACTION_TERRAFORM_TERRAGRUNT";
+pub const ENV_TERRAFORM_TERRAGRUNT_BIN: &str = "ACTION_TERRAFORM_TERRAGRUNT_BIN";
+pub const ENV_TERRAFORM_TERRAGRUNT_RUN_ALL: &str = "ACTION_TERRAFORM_TERRAGRUNT_RUN_ALL";
+pub const ENV_TERRAFORM_TERRAGRUNT_INCLUDE_DIRS: &str = "ACTION_TERRAFORM_TERRAGRUNT_INCLUDE_DIRS";
+pub const ENV_TERRAFORM_LOCK_TIMEOUT: &str = "ACTION_TERRAFORM_LOCK_TIMEOUT";
+pub const ENV_TERRAFORM_LOCK_RETRY_ATTEMPTS: &str = "ACTION_TERRAFORM_LOCK_RETRY_ATTEMPTS";
+pub const ENV_TERRAFORM_LOCK_RETRY_DELAY_MS: &str = "ACTION_TERRAFORM_LOCK_RETRY_DELAY_MS";
+pub const ENV_TERRAFORM_BACKEND_RETRY_ATTEMPTS: &str = "ACTION_TERRAFORM_BACKEND_RETRY_ATTEMPTS";
+pub const ENV_TERRAFORM_BACKEND_RETRY_DELAY_MS: &str = "ACTION_TERRAFORM_BACKEND_RETRY_DELAY_MS";
+pub const ENV_TERRAFORM_REPLACE: &str = "ACTION_TERRAFORM_REPLACE";
+pub const ENV_TERRAFORM_VAR_FILES: &str = "ACTION_TERRAFORM_VAR_FILES";
+pub const ENV_TERRAFORM_PARALLELISM: &str = "ACTION_TERRAFORM_PARALLELISM";
+pub const ENV_TERRAFORM_BACKEND_CONFIG_FILE: &str = "ACTION_TERRAFORM_BACKEND_CONFIG_FILE";
+pub const ENV_TERRAFORM_FMT_RECURSIVE: &str = "ACTION_TERRAFORM_FMT_RECURSIVE";
+pub const ENV_TERRAFORM_DETAILED_EXITCODE: &str = "ACTION_TERRAFORM_DETAILED_EXITCODE";
+pub const ENV_TERRAFORM_PLAN_SUMMARY_FILE: &str = "ACTION_TERRAFORM_PLAN_SUMMARY_FILE";
+pub const ENV_TERRAFORM_INIT_UPGRADE: &str = "ACTION_TERRAFORM_INIT_UPGRADE";
+pub const ENV_TERRAFORM_NO_COLOR: &str = "ACTION_TERRAFORM_NO_COLOR";
+pub const ENV_TERRAFORM_SAVED_PLAN: &str = "ACTION_TERRAFORM_SAVED_PLAN";
+pub const ENV_TERRAFORM_COMMIT_SHA: &str = "ACTION_TERRAFORM_COMMIT_SHA";
+pub const ENV_TERRAFORM_ROLLBACK_REF: &str = "ACTION_TERRAFORM_ROLLBACK_REF";
+pub const ENV_TERRAFORM_CLOUD_POLL: &str = "ACTION_TERRAFORM_CLOUD_POLL";
+pub const ENV_TERRAFORM_CLOUD_POLL_INTERVAL_MS: &str = "ACTION_TERRAFORM_CLOUD_POLL_INTERVAL_MS";
+pub const ENV_TERRAFORM_CLOUD_POLL_TIMEOUT_MS: &str = "ACTION_TERRAFORM_CLOUD_POLL_TIMEOUT_MS";
+pub const ENV_TERRAFORM_LOG: &str = "ACTION_TERRAFORM_LOG";
+pub const ENV_TERRAFORM_LOG_PATH: &str = "ACTION_TERRAFORM_LOG_PATH";
+pub const ENV_TERRAFORM_PLUGIN_CACHE_DIR: &str = "ACTION_TERRAFORM_PLUGIN_CACHE_DIR";
+pub const ENV_TERRAFORM_MODULES: &str = "ACTION_TERRAFORM_MODULES";
+pub const ENV_TERRAFORM_MODULES_PARALLEL: &str = "ACTION_TERRAFORM_MODULES_PARALLEL";
+/// Commit of the workflow run, set by the GitHub Actions runner.
+pub const ENV_GITHUB_SHA: &str = "GITHUB_SHA";
 
 // Default values
 pub const DEFAULT_TERRAFORM_OUTPUT: &str = "./tf_output_file";
 pub const DEFAULT_TERRAFORM_BIN: &str = "/usr/local/bin/terraform";
+pub const DEFAULT_TERRAGRUNT_BIN: &str = "/usr/local/bin/terragrunt";
 pub const DEFAULT_EMPTY: &str = "";
+pub const DEFAULT_TERRAFORM_LOG: &str = "off";
+pub const DEFAULT_TERRAFORM_LOG_PATH: &str = "./terraform-debug.log";
+pub const TERRAFORM_LOG_LEVELS: &[&str] =
+    &["off", "trace", "debug", "info", "warn", "error", "json"];
+pub const DEFAULT_LOCK_RETRY_ATTEMPTS: u32 = 0;
+pub const DEFAULT_LOCK_RETRY_DELAY_MS: u64 = 10000;
+pub const DEFAULT_BACKEND_RETRY_ATTEMPTS: u32 = 2;
+pub const DEFAULT_BACKEND_RETRY_DELAY_MS: u64 = 2000;
+pub const DEFAULT_CLOUD_POLL_INTERVAL_MS: u64 = 5000;
+pub const DEFAULT_CLOUD_POLL_TIMEOUT_MS: u64 = 3600000;
 
 lazy_static! {
-    pub static ref CMD: ConfigValue<Required> =
-        ConfigValue::<Required>::required(ENV_TERRAFORM_CMD);
+    pub static ref CMD: ConfigValue<Required> = ConfigValue::<Required>::required(
+        ENV_TERRAFORM_CMD
+    )
+    .with_description("Terraform command to run: plan, apply, validate, fmt_check or destroy.");
     pub static ref TERRAFORM_WORKSPACE: ConfigValue<String> =
-        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_TERRAFORM_WORKSPACE);
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_TERRAFORM_WORKSPACE)
+            .with_description("Terraform workspace to select.");
     pub static ref TERRAFORM_OUTPUT: ConfigValue<PathBuf> = ConfigValue::new(
         PathBuf::from(DEFAULT_TERRAFORM_OUTPUT),
         ENV_TERRAFORM_OUTPUT
-    );
+    )
+    .with_description("File the Terraform output is written to.");
     pub static ref TERRAFORM_BIN: ConfigValue<PathBuf> =
         ConfigValue::new(PathBuf::from(DEFAULT_TERRAFORM_BIN), ENV_TERRAFORM_BIN)
-            .with_validator(FileExists);
+            .expand()
+            .with_validator(FileExists)
+            .with_description("Path to the Terraform executable.");
+    pub static ref TERRAFORM_ALLOW_DESTROY: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_TERRAFORM_ALLOW_DESTROY)
+            .with_description("Allow the destroy command to run.");
+    pub static ref TERRAFORM_TARGETS: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_TERRAFORM_TARGETS).with_description(
+            "Comma-separated resource addresses to limit plan, apply and destroy to."
+        );
+    pub static ref TERRAFORM_LOCK_TIMEOUT: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_TERRAFORM_LOCK_TIMEOUT)
+            .with_validator(validate_duration)
+            .with_description("How long plan, apply and destroy wait for the state lock, e.g. 5m.");
+    pub static ref TERRAFORM_LOCK_RETRY_ATTEMPTS: ConfigValue<String> = ConfigValue::new(
+        DEFAULT_LOCK_RETRY_ATTEMPTS.to_string(),
+        ENV_TERRAFORM_LOCK_RETRY_ATTEMPTS
+    )
+    .with_validator(Number::<u32>::new("a number of retries"))
+    .with_description(
+        "Number of retries of commands that failed to acquire the state lock; 0 disables retrying."
+    );
+    pub static ref TERRAFORM_LOCK_RETRY_DELAY_MS: ConfigValue<String> = ConfigValue::new(
+        DEFAULT_LOCK_RETRY_DELAY_MS.to_string(),
+        ENV_TERRAFORM_LOCK_RETRY_DELAY_MS
+    )
+    .with_validator(Number::<u64>::new("a delay in milliseconds"))
+    .with_description(
+        "Delay before the first lock retry in milliseconds, doubled on every further retry."
+    );
+    pub static ref TERRAFORM_BACKEND_RETRY_ATTEMPTS: ConfigValue<String> = ConfigValue::new(
+        DEFAULT_BACKEND_RETRY_ATTEMPTS.to_string(),
+        ENV_TERRAFORM_BACKEND_RETRY_ATTEMPTS
+    )
+    .with_validator(Number::<u32>::new("a number of retries"))
+    .with_description(
+        "Number of retries of init and state operations that failed on a transient backend error; 0 disables retrying."
+    );
+    pub static ref TERRAFORM_BACKEND_RETRY_DELAY_MS: ConfigValue<String> = ConfigValue::new(
+        DEFAULT_BACKEND_RETRY_DELAY_MS.to_string(),
+        ENV_TERRAFORM_BACKEND_RETRY_DELAY_MS
+    )
+    .with_validator(Number::<u64>::new("a delay in milliseconds"))
+    .with_description(
+        "Delay before the first backend retry in milliseconds, doubled on every further retry."
+    );
+    pub static ref TERRAFORM_REPLACE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_TERRAFORM_REPLACE)
+            .with_description("Comma-separated resource addresses plan and apply recreate.");
+    pub static ref TERRAFORM_VAR_FILES: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_TERRAFORM_VAR_FILES)
+            .with_description("Comma-separated variable files, relative to the working directory.");
+    pub static ref TERRAFORM_BACKEND_CONFIG_FILE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_TERRAFORM_BACKEND_CONFIG_FILE)
+            .with_description("Backend configuration file, relative to the working directory.");
+    pub static ref TERRAFORM_PARALLELISM: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_TERRAFORM_PARALLELISM)
+            .with_validator(
+                Number::<u32>::new("a positive number of operations")
+                    .at_least(1)
+                    .or_empty(),
+            )
+            .with_description("Limit on concurrent operations of plan and apply.");
+    pub static ref TERRAFORM_FMT_RECURSIVE: ConfigValue<bool> =
+        ConfigValue::new(true, ENV_TERRAFORM_FMT_RECURSIVE)
+            .with_description("Check the formatting of subdirectories too.");
+    pub static ref TERRAFORM_DETAILED_EXITCODE: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_TERRAFORM_DETAILED_EXITCODE)
+            .with_description("Report whether the plan has changes as the has_changes output.");
+    pub static ref TERRAFORM_PLAN_SUMMARY_FILE: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_TERRAFORM_PLAN_SUMMARY_FILE)
+            .with_description(
+                "File the markdown plan summary is written to, e.g. for a PR comment."
+            );
+    pub static ref TERRAFORM_LOG: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_TERRAFORM_LOG.to_string(), ENV_TERRAFORM_LOG)
+            .with_validator(OneOf::new(TERRAFORM_LOG_LEVELS).ignore_case())
+            .with_description("Terraform debug log level (TF_LOG); the log is written to the log file, masked, never to the console.");
+    pub static ref TERRAFORM_LOG_PATH: ConfigValue<PathBuf> = ConfigValue::new(
+        PathBuf::from(DEFAULT_TERRAFORM_LOG_PATH),
+        ENV_TERRAFORM_LOG_PATH
+    )
+    .relative_to_working_dir()
+    .with_description("File the masked Terraform debug log is appended to.");
+    pub static ref TERRAFORM_PLUGIN_CACHE_DIR: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_TERRAFORM_PLUGIN_CACHE_DIR)
+            .with_description(
+            "Directory providers are cached in between runs, relative to the working directory."
+        );
+    pub static ref TERRAFORM_INIT_UPGRADE: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_TERRAFORM_INIT_UPGRADE).with_description(
+            "Upgrade modules and providers to the newest allowed versions on init."
+        );
+    pub static ref TERRAFORM_NO_COLOR: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_TERRAFORM_NO_COLOR)
+            .with_description("Disable colors in the init output.");
+    pub static ref TERRAFORM_SAVED_PLAN: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_TERRAFORM_SAVED_PLAN).with_description(
+            "Apply the plan file of an earlier plan run after verifying it against its manifest."
+        );
+    pub static ref TERRAFORM_COMMIT_SHA: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_TERRAFORM_COMMIT_SHA).with_description(
+            "Commit recorded in and checked against the plan manifest; defaults to GITHUB_SHA."
+        );
+    pub static ref TERRAFORM_ROLLBACK_REF: ConfigValue<String> =
+        ConfigValue::new(DEFAULT_EMPTY.to_string(), ENV_TERRAFORM_ROLLBACK_REF).with_description(
+            "Git ref of the previous configuration to plan a rollback against when apply fails."
+        );
+}
+
+// Terragrunt mode.
+lazy_static! {
+    pub static ref TERRAFORM_TERRAGRUNT: ConfigValue<bool> =
+        ConfigValue::new(false, ENV_TERRAFORM_TERRAGRUNT)
+            .with_description("Run the commands through Terragrunt.");
+    pub static ref TERRAFORM_TERRAGRUNT_BIN: ConfigValue<PathBuf> = ConfigValue::new(
+        PathBuf::from(DEFAULT_TERRAGRUNT_BIN),
+        ENV_TERRAFORM_TERRAGRUNT_BIN
+    )
+    .expand()
+    .with_description("Path to the Terragrunt executable.");
+    pub static ref TERRAFORM_TERRAGRUNT_RUN_ALL: ConfigValue<bool> = ConfigValue::new(
+        false,
+        ENV_TERRAFORM_TERRAGRUNT_RUN_ALL
+    )
+    .with_description(
+        "Run init, plan, apply and destroy in every Terragrunt module below the working directory."
+    );
+    pub static ref TERRAFORM_TERRAGRUNT_INCLUDE_DIRS: ConfigValue<String> = ConfigValue::new(
+        DEFAULT_EMPTY.to_string(),
+        ENV_TERRAFORM_TERRAGRUNT_INCLUDE_DIRS
+    )
+    .with_description("Comma-separated module directories run-all is limited to.");
+}
+
+// Monorepo modules.
+lazy_static! {
+    pub static ref TERRAFORM_MODULES: ConfigValue<String> = ConfigValue::new(
+        DEFAULT_EMPTY.to_string(),
+        ENV_TERRAFORM_MODULES
+    )
+    .with_description(
+        "Comma-separated module directories or globs, relative to the working directory, to run the chain in."
+    );
+    pub static ref TERRAFORM_MODULES_PARALLEL: ConfigValue<bool> = ConfigValue::new(
+        false,
+        ENV_TERRAFORM_MODULES_PARALLEL
+    )
+    .with_description(
+        "Run the modules in parallel; in order, the modules after the first failure are skipped."
+    );
+}
+
+// Terraform Cloud remote runs.
+lazy_static! {
+    pub static ref TERRAFORM_CLOUD_POLL: ConfigValue<bool> = ConfigValue::new(
+        false,
+        ENV_TERRAFORM_CLOUD_POLL
+    )
+    .with_description(
+        "Take the result of remote runs from the Terraform Cloud API instead of the streamed output."
+    );
+    pub static ref TERRAFORM_CLOUD_POLL_INTERVAL_MS: ConfigValue<String> = ConfigValue::new(
+        DEFAULT_CLOUD_POLL_INTERVAL_MS.to_string(),
+        ENV_TERRAFORM_CLOUD_POLL_INTERVAL_MS
+    )
+    .with_validator(Number::<u64>::new("an interval in milliseconds"))
+    .with_description("Interval between two reads of the remote run status in milliseconds.");
+    pub static ref TERRAFORM_CLOUD_POLL_TIMEOUT_MS: ConfigValue<String> = ConfigValue::new(
+        DEFAULT_CLOUD_POLL_TIMEOUT_MS.to_string(),
+        ENV_TERRAFORM_CLOUD_POLL_TIMEOUT_MS
+    )
+    .with_validator(Number::<u64>::new("a timeout in milliseconds"))
+    .with_description("How long to wait for a remote run to finish in milliseconds.");
+}
+
+/// Accepts an empty value or a Terraform duration such as `30s`, `5m` or `1h`.
+#[allow(clippy::ptr_arg)] // must match `Validator<String>`
+fn validate_duration(v: &String) -> Result<(), ConfigError> {
+    let digits = v.trim_end_matches(['s', 'm', 'h']);
+    let unit_len = v.len() - digits.len();
+    if v.is_empty()
+        || (unit_len == 1 && !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()))
+    {
+        Ok(())
+    } else {
+        Err(ConfigError::invalid(v, "a duration such as 30s, 5m or 1h"))
+    }
 }
@@ -0,0 +1,142 @@
+use executer::Output;
+
+/// Environment variable that enables Terraform's debug log.
+pub const ENV_TF_LOG: &str = "TF_LOG";
+/// Environment variable that makes Terraform write its debug log to a file, unmasked.
+pub const ENV_TF_LOG_PATH: &str = "TF_LOG_PATH";
+/// `TF_LOG` level that disables the debug log.
+pub const TF_LOG_OFF: &str = "OFF";
+
+/// Sends Terraform's debug log to a masked file instead of the console.
+///
+/// Terraform writes its debug log to standard error, mixed with the
+/// diagnostics of the command. Log lines start with a timestamp, or with
+/// `{"@level"` for `TF_LOG=JSON`; the lines following a log line belong to it
+/// until a diagnostic or the next log line starts.
+#[derive(Clone)]
+pub struct DebugLog {
+    level: String,
+    output: Output,
+}
+
+impl DebugLog {
+    /// Creates a debug log of `level`, e.g. `DEBUG`, written through `output`.
+    ///
+    /// `output` should mask with the same processors as the console and
+    /// write to a file target.
+    pub fn new(level: impl Into<String>, output: Output) -> Self {
+        Self {
+            level: level.into(),
+            output,
+        }
+    }
+
+    pub fn level(&self) -> &str {
+        &self.level
+    }
+
+    /// Returns a router for the standard error of one command.
+    pub(crate) fn router(&self) -> LogRouter {
+        LogRouter {
+            output: self.output.clone(),
+            in_log: false,
+        }
+    }
+}
+
+/// Tracks whether the lines of a stream belong to the debug log.
+pub(crate) struct LogRouter {
+    output: Output,
+    in_log: bool,
+}
+
+impl LogRouter {
+    /// Writes a line to the debug log if it belongs to it and returns whether it did.
+    pub(crate) fn route(&mut self, line: &str) -> bool {
+        if is_log_line(line) {
+            self.in_log = true;
+        } else if is_diagnostic(line) {
+            self.in_log = false;
+        }
+        if self.in_log {
+            self.output.write(line);
+        }
+        self.in_log
+    }
+}
+
+/// Returns whether a line starts a debug log entry.
+///
+/// # Example
+///
+/// ```rust
+/// use terraform::debug_log::is_log_line;
+///
+/// assert!(is_log_line("2024-05-06T10:11:12.345Z [DEBUG] provider: starting plugin"));
+/// assert!(is_log_line(r#"{"@level":"debug","@message":"starting plugin"}"#));
+/// assert!(!is_log_line("Error: Invalid reference"));
+/// ```
+pub fn is_log_line(line: &str) -> bool {
+    if line.starts_with(r#"{"@level""#) {
+        return true;
+    }
+    let bytes = line.as_bytes();
+    bytes.len() > 20
+        && bytes[..4].iter().all(u8::is_ascii_digit)
+        && bytes[4] == b'-'
+        && bytes[10] == b'T'
+        && line.contains(" [")
+}
+
+/// Returns whether a line starts a diagnostic of the command itself.
+fn is_diagnostic(line: &str) -> bool {
+    let line = line.trim_start();
+    ["╷", "│", "╵", "Error:", "Warning:"]
+        .iter()
+        .any(|prefix| line.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use executer::Target;
+    use processor::{MaskerEqual, ProcessorCollection, ProcessorItem};
+
+    #[test]
+    fn test_router_masks_log_and_keeps_diagnostics() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("terraform.log");
+        let processors = ProcessorCollection::new(vec![ProcessorItem::Equal(MaskerEqual::new(
+            vec!["s3cr3t"],
+            "****",
+        ))]);
+        let log = DebugLog::new(
+            "DEBUG",
+            Output::new(
+                processors,
+                Target::File(path.clone()),
+                Target::File(path.clone()),
+            ),
+        );
+
+        let mut router = log.router();
+        let routed: Vec<_> = [
+            "2024-05-06T10:11:12.345Z [DEBUG] HTTP request:",
+            "Authorization: Bearer s3cr3t",
+            "╷",
+            "│ Error: Invalid reference",
+            "2024-05-06T10:11:13.000Z [INFO] exiting",
+        ]
+        .iter()
+        .map(|line| router.route(line))
+        .collect();
+        assert_eq!(routed, vec![true, true, false, false, true]);
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "2024-05-06T10:11:12.345Z [DEBUG] HTTP request:\n\
+             Authorization: Bearer ****\n\
+             2024-05-06T10:11:13.000Z [INFO] exiting\n"
+        );
+    }
+}
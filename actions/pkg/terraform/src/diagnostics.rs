@@ -0,0 +1,185 @@
+use executer::Annotation;
+use std::path::PathBuf;
+
+/// Turns the diagnostics Terraform prints to standard error into annotations.
+///
+/// A diagnostic starts with `Error: SUMMARY` or `Warning: SUMMARY`, framed
+/// by `╷`, `│` and `╵` unless `-no-color` is set, and names its location
+/// as `on FILE line N`. The source snippet below the location is skipped;
+/// the remaining lines are the detail.
+///
+/// # Example
+///
+/// ```rust
+/// use terraform::diagnostics::DiagnosticMatcher;
+///
+/// let mut matcher = DiagnosticMatcher::new(Some("infra".into()));
+/// for line in [
+///     "╷",
+///     "│ Error: Unsupported argument",
+///     "│ ",
+///     "│   on main.tf line 4, in resource \"aws_s3_bucket\" \"site\":",
+///     "│    4:   acl = \"private\"",
+///     "│ ",
+///     "│ An argument named \"acl\" is not expected here.",
+///     "╵",
+/// ] {
+///     matcher.line(line);
+/// }
+/// assert_eq!(
+///     matcher.finish()[0].render(),
+///     "::error file=infra/main.tf,line=4,title=Unsupported argument::\
+///      An argument named \"acl\" is not expected here."
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct DiagnosticMatcher {
+    dir: Option<PathBuf>,
+    current: Option<Pending>,
+    found: Vec<Annotation>,
+}
+
+#[derive(Debug)]
+struct Pending {
+    warning: bool,
+    summary: String,
+    location: Option<(String, u32)>,
+    detail: Vec<String>,
+}
+
+impl DiagnosticMatcher {
+    /// Creates a matcher for a command run in `dir`, which file names are relative to.
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            ..Self::default()
+        }
+    }
+
+    /// Reads one line of standard error.
+    pub fn line(&mut self, line: &str) {
+        let line = line.trim_end();
+        // The frame is in the first column; indented box characters belong to the snippet.
+        if line.starts_with('╷') || line.starts_with('╵') {
+            self.close();
+            return;
+        }
+        let body = line.strip_prefix('│').unwrap_or(line).trim();
+
+        if let Some(summary) = body.strip_prefix("Error: ") {
+            self.open(false, summary);
+        } else if let Some(summary) = body.strip_prefix("Warning: ") {
+            self.open(true, summary);
+        } else if let Some(current) = &mut self.current {
+            if current.location.is_none() && current.detail.is_empty() {
+                if let Some(location) = location(body) {
+                    current.location = Some(location);
+                    return;
+                }
+            }
+            if is_snippet(body) {
+                return;
+            }
+            if !body.is_empty() || current.detail.last().is_some_and(|l| !l.is_empty()) {
+                current.detail.push(body.to_string());
+            }
+        }
+    }
+
+    /// Returns the annotations of all diagnostics read.
+    pub fn finish(mut self) -> Vec<Annotation> {
+        self.close();
+        self.found
+    }
+
+    fn open(&mut self, warning: bool, summary: &str) {
+        self.close();
+        self.current = Some(Pending {
+            warning,
+            summary: summary.to_string(),
+            location: None,
+            detail: Vec::new(),
+        });
+    }
+
+    fn close(&mut self) {
+        let Some(pending) = self.current.take() else {
+            return;
+        };
+        let detail = pending.detail.join("\n").trim().to_string();
+        let message = if detail.is_empty() {
+            pending.summary.clone()
+        } else {
+            detail
+        };
+        let mut annotation = if pending.warning {
+            Annotation::warning(message)
+        } else {
+            Annotation::error(message)
+        };
+        if let Some((file, line)) = pending.location {
+            let file = match &self.dir {
+                Some(dir) => dir.join(&file),
+                None => PathBuf::from(file),
+            };
+            annotation = annotation.with_file(file).with_line(line);
+        }
+        self.found.push(annotation.with_title(pending.summary));
+    }
+}
+
+/// Parses `on FILE line N, in ...:`.
+fn location(body: &str) -> Option<(String, u32)> {
+    let rest = body.strip_prefix("on ")?;
+    let (file, rest) = rest.split_once(" line ")?;
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    Some((file.to_string(), digits.parse().ok()?))
+}
+
+/// Returns whether a line is part of the source snippet of a diagnostic,
+/// e.g. `4:   acl = "private"` or the values of the expressions below it.
+fn is_snippet(body: &str) -> bool {
+    let digits = body.chars().take_while(char::is_ascii_digit).count();
+    (digits > 0 && body[digits..].starts_with(':'))
+        || body.starts_with('├')
+        || body.starts_with('│')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_diagnostics_and_expression_values() {
+        let mut matcher = DiagnosticMatcher::new(None);
+        for line in [
+            "",
+            "Warning: Deprecated attribute",
+            "",
+            "The attribute \"acl\" is deprecated.",
+            "",
+            "Error: Invalid function argument",
+            "",
+            "  on outputs.tf line 12, in output \"bucket\":",
+            "  12:   value = lower(var.bucket)",
+            "    ├────────────────",
+            "    │ var.bucket is null",
+            "",
+            "Invalid value for \"str\" parameter: argument must not be null.",
+        ] {
+            matcher.line(line);
+        }
+
+        let rendered: Vec<_> = matcher.finish().iter().map(Annotation::render).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "::warning title=Deprecated attribute::The attribute \"acl\" is deprecated."
+                    .to_string(),
+                "::error file=outputs.tf,line=12,title=Invalid function argument::\
+                 Invalid value for \"str\" parameter: argument must not be null."
+                    .to_string(),
+            ]
+        );
+    }
+}
@@ -23,6 +23,38 @@ pub enum TerraformError {
     #[error("Terraform apply error: {0}")]
     ApplyError(String),
 
+    /// Error when the validation report cannot be read.
+    #[error("Terraform validate error: {0}")]
+    ValidateError(String),
+
+    /// Error when the formatting cannot be checked.
+    #[error("Terraform fmt error: {0}")]
+    FmtError(String),
+
+    /// Error when reading or parsing Terraform outputs.
+    #[error("Terraform output error: {0}")]
+    OutputError(String),
+
+    /// Error when a plan file does not match its manifest.
+    #[error("Terraform plan integrity error: {0}")]
+    PlanIntegrityError(String),
+
+    /// Error when the status of a remote run cannot be read or the run failed.
+    #[error("Terraform Cloud error: {0}")]
+    CloudError(String),
+
+    /// Error when the module directories cannot be resolved.
+    #[error("Terraform module error: {0}")]
+    ModuleError(String),
+
+    /// Error when the state cannot be snapshotted or the rollback cannot be planned.
+    #[error("Terraform rollback error: {0}")]
+    RollbackError(String),
+
+    /// Error when a destroy is requested without being explicitly allowed.
+    #[error("Terraform destroy is not allowed: set {0}=true to enable it")]
+    DestroyNotAllowed(String),
+
     /// Error from the underlying executor.
     #[error(transparent)]
     ExecuterError(#[from] executer::ExecuterError),
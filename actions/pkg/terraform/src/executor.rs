@@ -1,18 +1,91 @@
 use crate::chain::CommandChain;
+use crate::cloud::{run_id, run_url, Polling, RunStatus, TerraformCloud};
 use crate::command::{TerraformCommand, WorkspaceOperation};
+use crate::debug_log::{DebugLog, ENV_TF_LOG, TF_LOG_OFF};
+use crate::diagnostics::DiagnosticMatcher;
 use crate::error::{TerraformError, TerraformResult};
+use crate::modules::{ModuleReport, ModuleResult, ModuleStatus};
+use crate::response::{plan_secrets, FmtCheck, PlanSummary, TerraformOutput, Validation};
+use crate::retry::{is_lock_error, is_transient_error};
+use crate::rollback::StateSnapshot;
+use crate::step::{chain_status, StepOutcome, StepResult};
+use crate::terragrunt::Terragrunt;
 
-use executer::{Context, Output, Subprocess, Target, Validator};
-use processor::ProcessorCollection;
+use executer::{
+    Annotations, Captured, Context, LineHook, Metrics, Output, RetryPolicy, Subprocess, Target,
+    Validator,
+};
+use futures::future::join_all;
+use processor::{MaskerRuntime, ProcessorCollection};
+use shared::http::Request;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The result of a plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanOutcome {
+    /// The infrastructure matches the configuration.
+    NoChanges,
+    /// Applying the plan would change the infrastructure.
+    ChangesPresent,
+    /// The plan succeeded without `-detailed-exitcode`, so whether it has
+    /// changes is not known.
+    Unknown,
+    /// The plan failed with the given exit status.
+    Failed(i32),
+}
+
+impl PlanOutcome {
+    /// Interprets the exit status of `terraform plan`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terraform::executor::PlanOutcome;
+    ///
+    /// assert_eq!(PlanOutcome::from_exit_code(0, true), PlanOutcome::NoChanges);
+    /// assert_eq!(PlanOutcome::from_exit_code(2, true), PlanOutcome::ChangesPresent);
+    /// assert_eq!(PlanOutcome::from_exit_code(0, false), PlanOutcome::Unknown);
+    /// assert_eq!(PlanOutcome::from_exit_code(2, false), PlanOutcome::Failed(2));
+    /// ```
+    pub fn from_exit_code(code: i32, detailed_exitcode: bool) -> Self {
+        match (code, detailed_exitcode) {
+            (0, true) => Self::NoChanges,
+            (2, true) => Self::ChangesPresent,
+            (0, false) => Self::Unknown,
+            (code, _) => Self::Failed(code),
+        }
+    }
+
+    /// Returns `true` unless the plan failed.
+    pub fn is_success(&self) -> bool {
+        !matches!(self, Self::Failed(_))
+    }
+}
 
 /// Executor responsible for running Terraform commands.
 pub struct TerraformExecutor {
     subprocess: Subprocess,
     terraform_path: PathBuf,
+    secrets: Option<MaskerRuntime>,
+    lock_retry: RetryPolicy,
+    backend_retry: RetryPolicy,
+    terragrunt: Option<Terragrunt>,
+    cloud: Option<TerraformCloud>,
+    env: HashMap<String, String>,
+    debug_log: Option<DebugLog>,
+    metrics: Option<Metrics>,
+    annotations: Option<Annotations>,
 }
 
+/// Counter of commands retried after failing to acquire the state lock.
+pub const LOCK_RETRIES: &str = "state lock retries";
+/// Counter of commands retried after a transient backend error.
+pub const BACKEND_RETRIES: &str = "backend retries";
+
 impl TerraformExecutor {
     /// Creates a new instance of `TerraformExecutor`.
     ///
@@ -49,9 +122,99 @@ impl TerraformExecutor {
         Self {
             subprocess,
             terraform_path,
+            secrets: None,
+            lock_retry: RetryPolicy::none(),
+            backend_retry: RetryPolicy::none(),
+            terragrunt: None,
+            cloud: None,
+            env: HashMap::new(),
+            debug_log: None,
+            metrics: None,
+            annotations: None,
+        }
+    }
+
+    /// Records command timings, retries and masked output lines on `metrics`.
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.subprocess = self.subprocess.with_metrics(metrics.clone());
+        self.metrics = Some(metrics);
+        self
+    }
+
+    fn count_retry(&self, name: &str) {
+        if let Some(metrics) = &self.metrics {
+            metrics.increment(name);
         }
     }
 
+    /// Registers sensitive values discovered while running on `secrets`.
+    ///
+    /// `secrets` should be a clone of a `MaskerRuntime` that is part of the
+    /// processor collection, so the values are masked in all later output.
+    pub fn with_secrets(mut self, secrets: MaskerRuntime) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
+    /// Sets the policy for retrying commands that failed to acquire the state lock.
+    ///
+    /// Commands fail immediately on a held lock by default; see also
+    /// [`CommandChain::with_lock_timeout`] to wait for the lock within a run.
+    pub fn with_lock_retry(mut self, lock_retry: RetryPolicy) -> Self {
+        self.lock_retry = lock_retry;
+        self
+    }
+
+    /// Sets the policy for retrying init and state operations that failed on a
+    /// transient backend error, such as a throttled or unreachable S3 or DynamoDB.
+    ///
+    /// Other failures, including configuration errors, are never retried.
+    pub fn with_backend_retry(mut self, backend_retry: RetryPolicy) -> Self {
+        self.backend_retry = backend_retry;
+        self
+    }
+
+    /// Runs all commands through Terragrunt, which uses `terraform_path` as its Terraform.
+    pub fn with_terragrunt(mut self, terragrunt: Terragrunt) -> Self {
+        self.terragrunt = Some(terragrunt);
+        self
+    }
+
+    /// Sets an environment variable for all Terraform commands, e.g. `TF_PLUGIN_CACHE_DIR`.
+    pub fn with_env(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(name.into(), value.into());
+        self
+    }
+
+    /// Enables Terraform's debug log and writes it to `debug_log` instead of the console.
+    ///
+    /// Without a debug log, an inherited `TF_LOG` is turned off so the log never
+    /// reaches the console. Callers must remove an inherited `TF_LOG_PATH` from
+    /// the environment, which would make Terraform write the log unmasked.
+    pub fn with_debug_log(mut self, debug_log: Option<DebugLog>) -> Self {
+        let level = debug_log.as_ref().map_or(TF_LOG_OFF, |log| log.level());
+        self.env.insert(ENV_TF_LOG.to_string(), level.to_string());
+        self.debug_log = debug_log;
+        self
+    }
+
+    /// Collects the diagnostics commands print to standard error on `annotations`.
+    ///
+    /// Diagnostics of attempts that are retried are dropped.
+    pub fn with_annotations(mut self, annotations: Annotations) -> Self {
+        self.annotations = Some(annotations);
+        self
+    }
+
+    /// Treats runs as remote runs on Terraform Cloud or Enterprise.
+    ///
+    /// With polling enabled, the exit status of a command that started a remote
+    /// run is taken from the run status reported by the API.
+    pub fn with_cloud(mut self, cloud: TerraformCloud) -> Self {
+        self.cloud = Some(cloud);
+        self
+    }
+
     /// Executes a given Terraform command asynchronously.
     ///
     /// # Arguments
@@ -92,27 +255,202 @@ impl TerraformExecutor {
     /// }
     /// ```
     pub async fn execute(&self, command: TerraformCommand) -> TerraformResult<i32> {
-        let args = command.to_args();
-        let working_dir = match &command {
+        if let TerraformCommand::Workspace {
+            dir,
+            operation: WorkspaceOperation::SelectOrCreate(name),
+        } = &command
+        {
+            return self
+                .select_or_create_workspace(dir.clone(), name)
+                .await
+                .map(|_| 0);
+        }
+        self.run(&command).await
+    }
+
+    /// Runs a command, retrying it while the state lock is held according to the lock retry policy,
+    /// and after transient backend errors according to the backend retry policy.
+    async fn run(&self, command: &TerraformCommand) -> TerraformResult<i32> {
+        let context = self.context(command);
+        let mut lock_attempt = 0;
+        let mut backend_attempt = 0;
+        loop {
+            let remote_run = Arc::new(Mutex::new(None));
+            let stdout = self.cloud.as_ref().map(|_| {
+                let remote_run = Arc::clone(&remote_run);
+                let hook: LineHook = Box::new(move |line| {
+                    if let Some(url) = run_url(line) {
+                        *remote_run.lock().unwrap() = Some(url);
+                    }
+                    true
+                });
+                hook
+            });
+            let locked = Arc::new(AtomicBool::new(false));
+            let transient = Arc::new(AtomicBool::new(false));
+            let (locked_flag, transient_flag) = (Arc::clone(&locked), Arc::clone(&transient));
+            let mut router = self.debug_log.as_ref().map(DebugLog::router);
+            let matcher = self
+                .annotations
+                .as_ref()
+                .map(|_| Arc::new(Mutex::new(DiagnosticMatcher::new(context.cwd.clone()))));
+            let line_matcher = matcher.clone();
+            let stderr: LineHook = Box::new(move |line| {
+                if is_lock_error(line) {
+                    locked_flag.store(true, Ordering::Relaxed);
+                } else if is_transient_error(line) {
+                    transient_flag.store(true, Ordering::Relaxed);
+                }
+                let routed = router.as_mut().is_some_and(|router| router.route(line));
+                if let Some(matcher) = line_matcher.as_ref().filter(|_| !routed) {
+                    matcher.lock().unwrap().line(line);
+                }
+                !routed
+            });
+
+            let code = self
+                .subprocess
+                .execute_hooked(context.clone(), stdout, Some(stderr))
+                .await?;
+            let retry = if code == 0 {
+                None
+            } else if locked.load(Ordering::Relaxed) {
+                Some((&self.lock_retry, &mut lock_attempt, LOCK_RETRIES))
+            } else if transient.load(Ordering::Relaxed) && command.is_state_operation() {
+                Some((&self.backend_retry, &mut backend_attempt, BACKEND_RETRIES))
+            } else {
+                None
+            };
+            if let Some((policy, attempt, counter)) =
+                retry.filter(|(policy, attempt, _)| policy.should_retry(**attempt))
+            {
+                tokio::time::sleep(policy.delay(*attempt)).await;
+                *attempt += 1;
+                self.count_retry(counter);
+                continue;
+            }
+
+            if let (Some(annotations), Some(matcher)) = (&self.annotations, matcher) {
+                annotations.extend(std::mem::take(&mut *matcher.lock().unwrap()).finish());
+            }
+            let remote_run = remote_run.lock().unwrap().take();
+            return match (&self.cloud, remote_run) {
+                (
+                    Some(
+                        cloud @ TerraformCloud {
+                            polling: Some(polling),
+                            ..
+                        },
+                    ),
+                    Some(url),
+                ) => self.wait_for_run(cloud, *polling, &url, code).await,
+                _ => Ok(code),
+            };
+        }
+    }
+
+    /// Runs a state operation with its output captured, retrying it after
+    /// transient backend errors according to the backend retry policy.
+    async fn capture(&self, command: &TerraformCommand) -> TerraformResult<Captured> {
+        let mut attempt = 0;
+        loop {
+            let captured = self
+                .subprocess
+                .execute_captured(self.context(command))
+                .await?;
+            if captured.code == 0
+                || !captured.stderr.lines().any(is_transient_error)
+                || !self.backend_retry.should_retry(attempt)
+            {
+                return Ok(captured);
+            }
+            tokio::time::sleep(self.backend_retry.delay(attempt)).await;
+            attempt += 1;
+            self.count_retry(BACKEND_RETRIES);
+        }
+    }
+
+    /// Polls the API until a remote run has finished and returns its exit status.
+    ///
+    /// A successful run keeps the exit status 2 of a plan with `-detailed-exitcode`.
+    async fn wait_for_run(
+        &self,
+        cloud: &TerraformCloud,
+        polling: Polling,
+        url: &str,
+        code: i32,
+    ) -> TerraformResult<i32> {
+        let id = run_id(url)
+            .ok_or_else(|| TerraformError::CloudError(format!("no run ID in {}", url)))?;
+        let token = cloud.token.as_ref().ok_or_else(|| {
+            TerraformError::CloudError(format!("no API token for {}", cloud.hostname))
+        })?;
+
+        let started = Instant::now();
+        loop {
+            let request = Request::get(cloud.run_endpoint(id))
+                .with_header("Authorization", format!("Bearer {}", token));
+            let response = tokio::task::spawn_blocking(move || request.send())
+                .await
+                .map_err(|e| TerraformError::CloudError(e.to_string()))?
+                .map_err(|e| {
+                    TerraformError::CloudError(format!("cannot read the status of {}: {}", id, e))
+                })?;
+            if response.status != 200 {
+                return Err(TerraformError::CloudError(format!(
+                    "cannot read the status of {}: status {}",
+                    id, response.status
+                )));
+            }
+            let status = RunStatus::parse(&response.body)
+                .map_err(|e| TerraformError::CloudError(e.to_string()))?;
+            if status.is_success() {
+                return Ok(if code == 2 { code } else { 0 });
+            }
+            if status.is_finished() {
+                return Err(TerraformError::CloudError(format!(
+                    "{} finished with status {}",
+                    id, status.0
+                )));
+            }
+            if started.elapsed() >= polling.timeout {
+                return Err(TerraformError::CloudError(format!(
+                    "{} is still {} after {}s",
+                    id,
+                    status.0,
+                    polling.timeout.as_secs()
+                )));
+            }
+            tokio::time::sleep(polling.interval).await;
+        }
+    }
+
+    fn context(&self, command: &TerraformCommand) -> Context {
+        let working_dir = match command {
             TerraformCommand::Init { dir, .. } => dir,
             TerraformCommand::Plan { dir, .. } => dir,
             TerraformCommand::Apply { dir, .. } => dir,
+            TerraformCommand::Destroy { dir, .. } => dir,
+            TerraformCommand::Validate { dir, .. } => dir,
+            TerraformCommand::Fmt { dir, .. } => dir,
+            TerraformCommand::Show { dir, .. } => dir,
+            TerraformCommand::Taint { dir, .. } => dir,
+            TerraformCommand::Untaint { dir, .. } => dir,
+            TerraformCommand::Output { dir } => dir,
+            TerraformCommand::StatePull { dir } => dir,
             TerraformCommand::Workspace { dir, .. } => dir,
         };
 
-        let mut cmd = vec![self.terraform_path.to_string_lossy().to_string()];
-        cmd.extend(args);
-
-        let context = Context::new(
-            cmd,
-            std::collections::HashMap::new(),
-            Some(working_dir.clone()),
-        );
+        let cmd = match &self.terragrunt {
+            Some(terragrunt) => terragrunt.command_line(&self.terraform_path, command),
+            None => {
+                let mut cmd = vec![self.terraform_path.to_string_lossy().to_string()];
+                cmd.extend(command.to_args());
+                cmd
+            }
+        };
 
-        self.subprocess
-            .execute(context)
-            .await
-            .map_err(TerraformError::from)
+        Context::new(cmd, self.env.clone(), Some(working_dir.clone()))
     }
 
     /// Initializes a Terraform working directory.
@@ -158,6 +496,9 @@ impl TerraformExecutor {
         self.execute(TerraformCommand::Init {
             dir,
             backend_config,
+            backend_config_file: None,
+            upgrade: false,
+            no_color: false,
         })
         .await
     }
@@ -205,10 +546,103 @@ impl TerraformExecutor {
         vars: std::collections::HashMap<String, String>,
         out: Option<PathBuf>,
     ) -> TerraformResult<i32> {
-        self.execute(TerraformCommand::Plan { dir, vars, out })
+        self.execute(TerraformCommand::Plan {
+            dir,
+            vars,
+            var_files: Vec::new(),
+            targets: Vec::new(),
+            replace: Vec::new(),
+            parallelism: None,
+            lock_timeout: None,
+            out,
+            detailed_exitcode: false,
+            destroy: false,
+        })
+        .await
+    }
+
+    /// Marks a resource as degraded so the next apply recreates it.
+    ///
+    /// Prefer [`CommandChain::with_replace`], which recreates the resource
+    /// without changing the state before the plan is reviewed.
+    pub async fn taint(&self, dir: PathBuf, address: String) -> TerraformResult<i32> {
+        self.execute(TerraformCommand::Taint { dir, address }).await
+    }
+
+    /// Removes the degraded mark from a resource.
+    pub async fn untaint(&self, dir: PathBuf, address: String) -> TerraformResult<i32> {
+        self.execute(TerraformCommand::Untaint { dir, address })
             .await
     }
 
+    /// Selects a workspace, creating it first if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TerraformError::WorkspaceError` if the workspaces cannot be
+    /// listed, or if selecting or creating the workspace fails.
+    /// Selects a workspace, creating it first if needed, and returns whether it was created.
+    async fn select_or_create_workspace(&self, dir: PathBuf, name: &str) -> TerraformResult<bool> {
+        let list = TerraformCommand::Workspace {
+            dir: dir.clone(),
+            operation: WorkspaceOperation::List,
+        };
+        let captured = self.capture(&list).await?;
+        if captured.code != 0 {
+            return Err(TerraformError::WorkspaceError(format!(
+                "listing workspaces failed with status {}",
+                captured.code
+            )));
+        }
+
+        // The current workspace is marked with a leading `*`.
+        let exists = captured
+            .stdout
+            .lines()
+            .any(|line| line.trim_start_matches(['*', ' ']).trim_end() == name);
+        let operation = if exists {
+            WorkspaceOperation::Select(name.to_string())
+        } else {
+            WorkspaceOperation::New(name.to_string())
+        };
+        let code = self
+            .run(&TerraformCommand::Workspace { dir, operation })
+            .await?;
+        if code != 0 {
+            return Err(TerraformError::WorkspaceError(format!(
+                "{} workspace {} failed with status {}",
+                if exists { "selecting" } else { "creating" },
+                name,
+                code
+            )));
+        }
+        Ok(!exists)
+    }
+
+    /// Executes a plan command and reports whether it has changes.
+    ///
+    /// With `detailed_exitcode` set, status 2 means the plan succeeded with
+    /// changes; without it, a successful plan is reported as `Unknown`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TerraformError::PlanError` if `command` is not a plan command.
+    pub async fn execute_plan(&self, command: TerraformCommand) -> TerraformResult<PlanOutcome> {
+        let detailed = match &command {
+            TerraformCommand::Plan {
+                detailed_exitcode, ..
+            } => *detailed_exitcode,
+            other => {
+                return Err(TerraformError::PlanError(format!(
+                    "expected a plan command, got {:?}",
+                    other
+                )))
+            }
+        };
+        let code = self.execute(command).await?;
+        Ok(PlanOutcome::from_exit_code(code, detailed))
+    }
+
     /// Applies the changes required to reach the desired state.
     ///
     /// # Arguments
@@ -257,6 +691,11 @@ impl TerraformExecutor {
         self.execute(TerraformCommand::Apply {
             dir,
             plan_file,
+            var_files: Vec::new(),
+            targets: Vec::new(),
+            replace: Vec::new(),
+            parallelism: None,
+            lock_timeout: None,
             auto_approve,
         })
         .await
@@ -309,30 +748,343 @@ impl TerraformExecutor {
             .await
     }
 
-    pub async fn execute_chain(&self, commands: Vec<TerraformCommand>) -> TerraformResult<i32> {
-        let mut last_result = 0;
-        for cmd in &commands {
-            let result = self.execute(cmd.clone()).await;
-            match result {
-                Ok(code) => {
-                    if let TerraformCommand::Workspace {
-                        operation: WorkspaceOperation::New(_),
-                        ..
-                    } = cmd
-                    {
-                        if code != 0 {
-                            continue;
-                        }
-                    }
-                    last_result = code;
-                    if code != 0 {
-                        return Ok(code);
-                    }
+    /// Validates the configuration with `terraform validate -json`.
+    ///
+    /// The JSON is captured instead of printed. An invalid configuration is not
+    /// an error; check [`Validation::valid`] and its diagnostics instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TerraformError::ValidateError` if the command prints something
+    /// other than the validation JSON.
+    pub async fn validate(&self, dir: PathBuf) -> TerraformResult<Validation> {
+        let command = TerraformCommand::Validate { dir, json: true };
+        let captured = self
+            .subprocess
+            .execute_captured(self.context(&command))
+            .await?;
+
+        // `validate` exits non-zero for an invalid configuration but still prints the report.
+        Validation::parse(&captured.stdout).map_err(|e| {
+            TerraformError::ValidateError(format!(
+                "unexpected output of terraform validate (status {}): {}",
+                captured.code, e
+            ))
+        })
+    }
+
+    /// Summarizes a saved plan with `terraform show -json`.
+    ///
+    /// The plan JSON is captured instead of printed; only the resource changes
+    /// are kept, so planned values never reach the output. Values the plan
+    /// marks as sensitive are registered on the runtime masker (see
+    /// [`TerraformExecutor::with_secrets`]), so they stay masked when the plan
+    /// is applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TerraformError::PlanError` if the command fails or prints
+    /// something other than the plan JSON.
+    pub async fn show_plan(
+        &self,
+        dir: PathBuf,
+        plan_file: PathBuf,
+    ) -> TerraformResult<PlanSummary> {
+        let command = TerraformCommand::Show {
+            dir,
+            plan_file,
+            json: true,
+        };
+        let captured = self
+            .subprocess
+            .execute_captured(self.context(&command))
+            .await?;
+        if captured.code != 0 {
+            return Err(TerraformError::PlanError(format!(
+                "terraform show failed with status {}",
+                captured.code
+            )));
+        }
+        if let Some(secrets) = &self.secrets {
+            let values = plan_secrets(&captured.stdout)
+                .map_err(|e| TerraformError::PlanError(e.to_string()))?;
+            for value in values {
+                secrets.add(&value);
+            }
+        }
+        PlanSummary::parse(&captured.stdout).map_err(|e| TerraformError::PlanError(e.to_string()))
+    }
+
+    /// Checks the formatting with `terraform fmt -check`.
+    ///
+    /// The list of files printed by Terraform is captured instead of printed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TerraformError::FmtError` if the check fails without reporting
+    /// any file, e.g. because a file cannot be parsed.
+    pub async fn fmt_check(&self, dir: PathBuf, recursive: bool) -> TerraformResult<FmtCheck> {
+        let command = TerraformCommand::Fmt {
+            dir,
+            check: true,
+            recursive,
+            diff: false,
+        };
+        let captured = self
+            .subprocess
+            .execute_captured(self.context(&command))
+            .await?;
+
+        let check = FmtCheck::parse(&captured.stdout);
+        if captured.code != 0 && check.is_formatted() {
+            return Err(TerraformError::FmtError(format!(
+                "terraform fmt failed with status {}",
+                captured.code
+            )));
+        }
+        Ok(check)
+    }
+
+    /// Reads the root module outputs with `terraform output -json`.
+    ///
+    /// The JSON is captured instead of printed. Values of sensitive outputs are
+    /// registered on the runtime masker (see [`TerraformExecutor::with_secrets`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `TerraformError::OutputError` if the command fails or prints
+    /// something other than the outputs JSON.
+    pub async fn outputs(&self, dir: PathBuf) -> TerraformResult<HashMap<String, TerraformOutput>> {
+        let command = TerraformCommand::Output { dir };
+        let captured = self.capture(&command).await?;
+        if captured.code != 0 {
+            return Err(TerraformError::OutputError(format!(
+                "terraform output failed with status {}",
+                captured.code
+            )));
+        }
+
+        let outputs = TerraformOutput::parse_all(&captured.stdout)
+            .map_err(|e| TerraformError::OutputError(e.to_string()))?;
+        if let Some(secrets) = &self.secrets {
+            for output in outputs.values().filter(|o| o.sensitive) {
+                for value in output.secret_values() {
+                    secrets.add(&value);
+                }
+            }
+        }
+        Ok(outputs)
+    }
+
+    /// Snapshots the state serial and the outputs of a working directory.
+    ///
+    /// The pulled state is captured, never printed.
+    pub async fn snapshot_state(&self, dir: PathBuf) -> TerraformResult<StateSnapshot> {
+        let command = TerraformCommand::StatePull { dir: dir.clone() };
+        let captured = self.capture(&command).await?;
+        if captured.code != 0 {
+            return Err(TerraformError::RollbackError(format!(
+                "terraform state pull failed with status {}",
+                captured.code
+            )));
+        }
+        let outputs = self.outputs(dir).await?;
+        Ok(StateSnapshot::new(&captured.stdout, &outputs))
+    }
+
+    /// Plans the configuration of an earlier git ref against the current state.
+    ///
+    /// The ref is checked out into a temporary worktree, where the chain is
+    /// initialized and planned to a plan file in the same module directory; the
+    /// worktree is removed afterwards. The ref must have been fetched.
+    pub async fn rollback_plan(
+        &self,
+        chain: &CommandChain,
+        git_ref: &str,
+    ) -> TerraformResult<PlanSummary> {
+        let dir = chain
+            .dir()
+            .canonicalize()
+            .map_err(|e| TerraformError::RollbackError(e.to_string()))?;
+        let root = PathBuf::from(
+            self.git(&dir, &["rev-parse", "--show-toplevel"])
+                .await?
+                .trim(),
+        );
+        let module = dir
+            .strip_prefix(&root)
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        let worktree =
+            std::env::temp_dir().join(format!("terraform-rollback-{}", std::process::id()));
+        let worktree_arg = worktree.to_string_lossy().to_string();
+        self.git(
+            &root,
+            &["worktree", "add", "--detach", &worktree_arg, git_ref],
+        )
+        .await?;
+
+        let module_dir = worktree.join(module);
+        let plan_file = module_dir.join("rollback.tfplan");
+        let commands = chain
+            .clone()
+            .with_dir(module_dir.clone())
+            .with_out(Some(plan_file.clone()))
+            .with_detailed_exitcode(false)
+            .plan_chain();
+        let summary = match chain_status(self.execute_chain(commands).await) {
+            Ok(0) => self.show_plan(module_dir, plan_file).await,
+            Ok(code) => Err(TerraformError::RollbackError(format!(
+                "planning {} failed with status {}",
+                git_ref, code
+            ))),
+            Err(e) => Err(e),
+        };
+        let removed = self
+            .git(&root, &["worktree", "remove", "--force", &worktree_arg])
+            .await;
+        let summary = summary?;
+        removed?;
+        Ok(summary)
+    }
+
+    /// Runs a git command in `dir` and returns its standard output.
+    async fn git(&self, dir: &Path, args: &[&str]) -> TerraformResult<String> {
+        let mut cmd = vec!["git".to_string()];
+        cmd.extend(args.iter().map(|arg| arg.to_string()));
+        let context = Context::new(cmd, HashMap::new(), Some(dir.to_path_buf()));
+        let captured = self.subprocess.execute_captured(context).await?;
+        if captured.code != 0 {
+            return Err(TerraformError::RollbackError(format!(
+                "git {} failed: {}",
+                args.join(" "),
+                captured.stderr.trim()
+            )));
+        }
+        Ok(captured.stdout)
+    }
+
+    /// Runs commands in order and returns one result per command.
+    ///
+    /// The commands after the first failure are skipped. Status 2 of a plan with
+    /// `-detailed-exitcode` counts as success; see [`chain_status`] to reduce the
+    /// results to a single status.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "chain", skip_all, fields(steps = commands.len()))
+    )]
+    pub async fn execute_chain(&self, commands: Vec<TerraformCommand>) -> Vec<StepResult> {
+        let mut stopped = false;
+        let mut results = Vec::with_capacity(commands.len());
+        for command in commands {
+            if stopped {
+                results.push(StepResult {
+                    command,
+                    duration: Duration::ZERO,
+                    exit_code: None,
+                    outcome: StepOutcome::Skipped,
+                });
+                continue;
+            }
+            let started = Instant::now();
+            let (exit_code, outcome) = self.execute_step(&command).await;
+            stopped = matches!(outcome, StepOutcome::Failed(_) | StepOutcome::Error(_));
+            results.push(StepResult {
+                command,
+                duration: started.elapsed(),
+                exit_code,
+                outcome,
+            });
+        }
+        results
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "step", skip_all, fields(command = %command.operation()))
+    )]
+    async fn execute_step(&self, command: &TerraformCommand) -> (Option<i32>, StepOutcome) {
+        let code = match command {
+            TerraformCommand::Workspace {
+                dir,
+                operation: WorkspaceOperation::SelectOrCreate(name),
+            } => {
+                return match self.select_or_create_workspace(dir.clone(), name).await {
+                    Ok(true) => (Some(0), StepOutcome::WorkspaceCreated(name.clone())),
+                    Ok(false) => (Some(0), StepOutcome::Succeeded),
+                    Err(e) => (None, StepOutcome::Error(e)),
                 }
-                Err(e) => return Err(e),
             }
+            command => match self.run(command).await {
+                Ok(code) => code,
+                Err(e) => return (None, StepOutcome::Error(e)),
+            },
+        };
+        let detailed = matches!(
+            command,
+            TerraformCommand::Plan {
+                detailed_exitcode: true,
+                ..
+            }
+        );
+        let outcome = match PlanOutcome::from_exit_code(code, detailed) {
+            PlanOutcome::Failed(code) => StepOutcome::Failed(code),
+            PlanOutcome::ChangesPresent => StepOutcome::ChangesPresent,
+            _ => StepOutcome::Succeeded,
+        };
+        (Some(code), outcome)
+    }
+
+    /// Runs the command chain of every module.
+    ///
+    /// In order, the modules after the first failure are skipped, since later
+    /// stacks usually depend on earlier ones. In parallel, all modules run to
+    /// completion.
+    pub async fn execute_modules(
+        &self,
+        chains: Vec<(PathBuf, Vec<TerraformCommand>)>,
+        parallel: bool,
+    ) -> ModuleReport {
+        let results = if parallel {
+            join_all(
+                chains
+                    .into_iter()
+                    .map(|(dir, commands)| self.execute_module(dir, commands)),
+            )
+            .await
+        } else {
+            let mut results = Vec::new();
+            let mut failed = false;
+            for (dir, commands) in chains {
+                let result = if failed {
+                    ModuleResult {
+                        dir,
+                        status: ModuleStatus::Skipped,
+                        duration: Duration::ZERO,
+                    }
+                } else {
+                    self.execute_module(dir, commands).await
+                };
+                failed |= result.status != ModuleStatus::Succeeded;
+                results.push(result);
+            }
+            results
+        };
+        ModuleReport { results }
+    }
+
+    async fn execute_module(&self, dir: PathBuf, commands: Vec<TerraformCommand>) -> ModuleResult {
+        let started = Instant::now();
+        let status = match chain_status(self.execute_chain(commands).await) {
+            Ok(0) => ModuleStatus::Succeeded,
+            Ok(code) => ModuleStatus::Failed(code),
+            Err(e) => ModuleStatus::Error(e.to_string()),
+        };
+        ModuleResult {
+            dir,
+            status,
+            duration: started.elapsed(),
         }
-        Ok(last_result)
     }
 
     pub async fn execute_plan_chain(
@@ -347,7 +1099,7 @@ impl TerraformExecutor {
             .with_out(out)
             .with_workspace(workspace);
 
-        self.execute_chain(chain.plan_chain()).await
+        chain_status(self.execute_chain(chain.plan_chain()).await)
     }
 
     pub async fn execute_apply_chain(
@@ -362,6 +1114,513 @@ impl TerraformExecutor {
             .with_workspace(workspace)
             .with_auto_approve(auto_approve);
 
-        self.execute_chain(chain.apply_chain()).await
+        chain_status(self.execute_chain(chain.apply_chain()).await)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use processor::{MaskerEqual, Processor, ProcessorItem};
+    use std::fs;
+    use std::time::Duration;
+    use tempfile::tempdir;
+    use testing::script;
+
+    #[tokio::test]
+    async fn test_outputs_masks_sensitive_values() {
+        let dir = tempdir().unwrap();
+        let terraform = script(
+            &dir.path().join("terraform"),
+            r#"echo '{"url": {"sensitive": false, "type": "string", "value": "https://site"},'
+echo ' "token": {"sensitive": true, "type": "string", "value": "s3cr3t"}}'"#,
+        );
+
+        let secrets = MaskerRuntime::new("****");
+        let processor = ProcessorCollection::new(vec![ProcessorItem::Runtime(secrets.clone())]);
+        let executor = TerraformExecutor::new(processor, terraform).with_secrets(secrets.clone());
+
+        let outputs = executor.outputs(dir.path().to_path_buf()).await.unwrap();
+        assert_eq!(outputs["url"].render(), "https://site");
+        assert_eq!(outputs["token"].render(), "s3cr3t");
+        assert_eq!(
+            secrets.process("url=https://site token=s3cr3t"),
+            "url=https://site token=****"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_outputs_failure() {
+        let dir = tempdir().unwrap();
+        let terraform = script(&dir.path().join("terraform"), "exit 1");
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform);
+
+        let err = executor
+            .outputs(dir.path().to_path_buf())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TerraformError::OutputError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_validate_reports_diagnostics() {
+        let dir = tempdir().unwrap();
+        let terraform = script(
+            &dir.path().join("terraform"),
+            r#"echo '{"valid": false, "error_count": 1, "warning_count": 0, "diagnostics": [
+  {"severity": "error", "summary": "Missing required argument",
+   "range": {"filename": "main.tf", "start": {"line": 3, "column": 1}}}]}'
+exit 1"#,
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform);
+
+        let validation = executor.validate(dir.path().to_path_buf()).await.unwrap();
+        assert!(!validation.valid);
+        assert_eq!(validation.error_count, 1);
+        assert_eq!(
+            validation.diagnostics[0].summary,
+            "Missing required argument"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fmt_check_lists_files() {
+        let dir = tempdir().unwrap();
+        let terraform = script(
+            &dir.path().join("terraform"),
+            "echo main.tf\necho modules/cdn/variables.tf\nexit 3",
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform);
+
+        let check = executor
+            .fmt_check(dir.path().to_path_buf(), true)
+            .await
+            .unwrap();
+        assert_eq!(check.files, vec!["main.tf", "modules/cdn/variables.tf"]);
+    }
+
+    #[tokio::test]
+    async fn test_fmt_check_error_without_files() {
+        let dir = tempdir().unwrap();
+        let terraform = script(
+            &dir.path().join("terraform"),
+            "echo 'Error: Invalid block' >&2\nexit 2",
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform);
+
+        let err = executor
+            .fmt_check(dir.path().to_path_buf(), true)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TerraformError::FmtError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_chain_continues_after_plan_with_changes() {
+        let dir = tempdir().unwrap();
+        let terraform = script(
+            &dir.path().join("terraform"),
+            &format!(
+                "echo \"$1\" >> {}\n[ \"$1\" = plan ] && exit 2\nexit 0",
+                dir.path().join("calls").display()
+            ),
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform);
+        let chain = CommandChain::new(dir.path().to_path_buf()).with_detailed_exitcode(true);
+
+        assert_eq!(
+            chain_status(executor.execute_chain(chain.apply_chain()).await).unwrap(),
+            0
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("calls")).unwrap(),
+            "init\nplan\napply\n"
+        );
+
+        let plan = chain.plan_chain().pop().unwrap();
+        assert_eq!(
+            executor.execute_plan(plan).await.unwrap(),
+            PlanOutcome::ChangesPresent
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plan_without_detailed_exitcode_is_unknown() {
+        let dir = tempdir().unwrap();
+        let terraform = script(&dir.path().join("terraform"), "exit 0");
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform);
+
+        let plan = CommandChain::new(dir.path().to_path_buf())
+            .plan_chain()
+            .pop()
+            .unwrap();
+        assert_eq!(
+            executor.execute_plan(plan).await.unwrap(),
+            PlanOutcome::Unknown
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_plan_summary() {
+        let dir = tempdir().unwrap();
+        let terraform = script(
+            &dir.path().join("terraform"),
+            r#"[ "$1 $2 $3" = "show -json plan.out" ] || exit 1
+echo '{"resource_changes": [{"address": "aws_s3_bucket.site", "change": {"actions": ["create"]}}]}'"#,
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform);
+
+        let summary = executor
+            .show_plan(dir.path().to_path_buf(), PathBuf::from("plan.out"))
+            .await
+            .unwrap();
+        assert_eq!(summary.add, 1);
+        assert_eq!(summary.resources[0].address, "aws_s3_bucket.site");
+    }
+
+    #[tokio::test]
+    async fn test_execute_retries_held_state_lock() {
+        let dir = tempdir().unwrap();
+        let attempts = dir.path().join("attempts");
+        let terraform = script(
+            &dir.path().join("terraform"),
+            &format!(
+                "echo x >> {0}\n[ $(wc -l < {0}) -ge 3 ] && exit 0\n\
+                 echo 'Error: Error acquiring the state lock' >&2\nexit 1",
+                attempts.display()
+            ),
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform)
+            .with_lock_retry(RetryPolicy::new(2, Duration::ZERO));
+
+        let code = executor.taint(dir.path().to_path_buf(), "aws_instance.web".to_string());
+        assert_eq!(code.await.unwrap(), 0);
+        assert_eq!(fs::read_to_string(&attempts).unwrap().lines().count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_execute_annotates_diagnostics_of_last_attempt() {
+        let dir = tempdir().unwrap();
+        let attempts = dir.path().join("attempts");
+        let terraform = script(
+            &dir.path().join("terraform"),
+            &format!(
+                r#"echo x >> {0}
+if [ $(wc -l < {0}) -ge 2 ]; then
+  cat >&2 <<'EOF'
+╷
+│ Error: Unsupported argument
+│
+│   on main.tf line 4, in resource "aws_s3_bucket" "site":
+│    4:   acl = "private"
+│
+│ An argument named "acl" is not expected here.
+╵
+EOF
+  exit 1
+fi
+echo 'Error: Error acquiring the state lock' >&2
+exit 1"#,
+                attempts.display()
+            ),
+        );
+        let annotations = Annotations::new();
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform)
+            .with_lock_retry(RetryPolicy::new(2, Duration::ZERO))
+            .with_annotations(annotations.clone());
+
+        let code = executor.taint(dir.path().to_path_buf(), "aws_instance.web".to_string());
+        assert_eq!(code.await.unwrap(), 1);
+        let annotations = annotations.take();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(
+            annotations[0].title.as_deref(),
+            Some("Unsupported argument")
+        );
+        assert_eq!(annotations[0].line, Some(4));
+        assert!(annotations[0].file.as_ref().unwrap().ends_with("main.tf"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_does_not_retry_other_errors() {
+        let dir = tempdir().unwrap();
+        let attempts = dir.path().join("attempts");
+        let terraform = script(
+            &dir.path().join("terraform"),
+            &format!(
+                "echo x >> {}\necho 'Error: Invalid reference' >&2\nexit 1",
+                attempts.display()
+            ),
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform)
+            .with_lock_retry(RetryPolicy::new(2, Duration::ZERO));
+
+        let code = executor.untaint(dir.path().to_path_buf(), "aws_instance.web".to_string());
+        assert_eq!(code.await.unwrap(), 1);
+        assert_eq!(fs::read_to_string(&attempts).unwrap().lines().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_backend_retry_only_retries_state_operations() {
+        let dir = tempdir().unwrap();
+        let attempts = dir.path().join("attempts");
+        let terraform = script(
+            &dir.path().join("terraform"),
+            &format!(
+                "echo \"$1\" >> {0}\n[ \"$1\" != destroy ] && [ $(grep -c \"^$1$\" {0}) -ge 2 ] && echo '{{}}' && exit 0\n\
+                 echo 'Error: RequestError: send request failed: dial tcp: i/o timeout' >&2\nexit 1",
+                attempts.display()
+            ),
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform)
+            .with_backend_retry(RetryPolicy::new(2, Duration::ZERO));
+        let cwd = dir.path().to_path_buf();
+
+        assert!(executor.outputs(cwd.clone()).await.unwrap().is_empty());
+        let code = executor.taint(cwd.clone(), "aws_instance.web".to_string());
+        assert_eq!(code.await.unwrap(), 0);
+        let code = executor.execute(TerraformCommand::Destroy {
+            dir: cwd.clone(),
+            auto_approve: true,
+            vars: HashMap::new(),
+            var_files: Vec::new(),
+            targets: Vec::new(),
+            lock_timeout: None,
+        });
+        assert_eq!(code.await.unwrap(), 1);
+        assert_eq!(
+            fs::read_to_string(&attempts).unwrap(),
+            "output\noutput\ntaint\ntaint\ndestroy\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_select_or_create_workspace() {
+        let dir = tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let terraform = script(
+            &dir.path().join("terraform"),
+            &format!(
+                "echo \"$*\" >> {}\n[ \"$2\" = list ] && printf '* default\\n  staging\\n'\nexit 0",
+                calls.display()
+            ),
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform);
+        let cwd = dir.path().to_path_buf();
+
+        for name in ["staging", "prod"] {
+            let operation = WorkspaceOperation::SelectOrCreate(name.to_string());
+            assert_eq!(executor.workspace(cwd.clone(), operation).await.unwrap(), 0);
+        }
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap(),
+            "workspace list\nworkspace select staging\nworkspace list\nworkspace new prod\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chain_reports_each_step() {
+        let dir = tempdir().unwrap();
+        let terraform = script(
+            &dir.path().join("terraform"),
+            "[ \"$2\" = list ] && echo '* default' && exit 0\n[ \"$1\" = plan ] && exit 1\nexit 0",
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform);
+        let chain =
+            CommandChain::new(dir.path().to_path_buf()).with_workspace(Some("prod".to_string()));
+
+        let steps = executor.execute_chain(chain.apply_chain()).await;
+        let outcomes: Vec<_> = steps
+            .iter()
+            .map(|step| (step.command.operation(), format!("{:?}", step.outcome)))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("init".to_string(), "Succeeded".to_string()),
+                (
+                    "workspace select".to_string(),
+                    r#"WorkspaceCreated("prod")"#.to_string()
+                ),
+                ("plan".to_string(), "Failed(1)".to_string()),
+                ("apply".to_string(), "Skipped".to_string()),
+            ]
+        );
+        assert_eq!(steps[2].exit_code, Some(1));
+        assert_eq!(chain_status(steps).unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_plan_runs_in_worktree() {
+        let dir = tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        fs::create_dir_all(repo.join("infra")).unwrap();
+        fs::write(repo.join("infra/main.tf"), "").unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(["-c", "user.name=ci", "-c", "user.email=ci@example.com"])
+                .args(args)
+                .current_dir(&repo)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        git(&["init", "-q"]);
+        git(&["add", "."]);
+        git(&["commit", "-qm", "infra"]);
+
+        let calls = dir.path().join("calls");
+        let terraform = script(
+            &dir.path().join("terraform"),
+            &format!(
+                r#"echo "$1 $PWD" >> {}
+[ "$1" = show ] && echo '{{"resource_changes": [{{"address": "aws_s3_bucket.logs", "change": {{"actions": ["delete"]}}}}]}}'
+exit 0"#,
+                calls.display()
+            ),
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform);
+        let chain = CommandChain::new(repo.join("infra"));
+
+        let summary = executor.rollback_plan(&chain, "HEAD").await.unwrap();
+        assert_eq!(summary.destroy, 1);
+
+        let calls = fs::read_to_string(&calls).unwrap();
+        let calls: Vec<_> = calls.lines().collect();
+        assert_eq!(calls.len(), 3);
+        for (call, operation) in calls.iter().zip(["init", "plan", "show"]) {
+            let (name, cwd) = call.split_once(' ').unwrap();
+            assert_eq!(name, operation);
+            assert!(cwd.contains("terraform-rollback-") && cwd.ends_with("/infra"));
+        }
+        let worktree = Path::new(calls[0].split_once(' ').unwrap().1)
+            .parent()
+            .unwrap();
+        assert!(!worktree.exists());
+    }
+
+    #[tokio::test]
+    async fn test_select_or_create_workspace_fails_loudly() {
+        let dir = tempdir().unwrap();
+        let terraform = script(
+            &dir.path().join("terraform"),
+            "[ \"$2\" = list ] && echo '* default' && exit 0\nexit 1",
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform);
+
+        let operation = WorkspaceOperation::SelectOrCreate("prod".to_string());
+        let err = executor
+            .workspace(dir.path().to_path_buf(), operation)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TerraformError::WorkspaceError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_show_plan_masks_sensitive_values() {
+        let dir = tempdir().unwrap();
+        let terraform = script(
+            &dir.path().join("terraform"),
+            r#"echo '{"resource_changes": [{"address": "random_password.db", "change": {
+  "actions": ["create"], "after": {"result": "pa55word"}, "after_sensitive": {"result": true}}}]}'"#,
+        );
+        let secrets = MaskerRuntime::new("****");
+        let processor = ProcessorCollection::new(vec![ProcessorItem::Runtime(secrets.clone())]);
+        let executor = TerraformExecutor::new(processor, terraform).with_secrets(secrets.clone());
+
+        executor
+            .show_plan(dir.path().to_path_buf(), PathBuf::from("plan.out"))
+            .await
+            .unwrap();
+        assert_eq!(secrets.process("password: pa55word"), "password: ****");
+    }
+
+    #[tokio::test]
+    async fn test_execute_through_terragrunt() {
+        let dir = tempdir().unwrap();
+        let calls = dir.path().join("calls");
+        let terragrunt = script(
+            &dir.path().join("terragrunt"),
+            &format!("echo \"$*\" > {}", calls.display()),
+        );
+        let executor = TerraformExecutor::new(
+            ProcessorCollection::new(vec![]),
+            PathBuf::from("/usr/local/bin/terraform"),
+        )
+        .with_terragrunt(Terragrunt::new(terragrunt).with_run_all(true));
+
+        let code = executor.init(dir.path().to_path_buf(), None).await.unwrap();
+        assert_eq!(code, 0);
+        assert_eq!(
+            fs::read_to_string(&calls).unwrap().trim(),
+            "run-all init -input=false -reconfigure --terragrunt-tfpath=/usr/local/bin/terraform \
+             --terragrunt-non-interactive"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_modules_in_order_skips_after_failure() {
+        let dir = tempdir().unwrap();
+        let terraform = script(
+            &dir.path().join("terraform"),
+            r#"case "$PWD" in */b) exit 3 ;; esac"#,
+        );
+        let executor = TerraformExecutor::new(ProcessorCollection::new(vec![]), terraform);
+        let chains = ["a", "b", "c"]
+            .iter()
+            .map(|name| {
+                let module = dir.path().join(name);
+                fs::create_dir(&module).unwrap();
+                let commands = CommandChain::new(module.clone()).init_chain();
+                (module, commands)
+            })
+            .collect::<Vec<_>>();
+
+        let report = executor.execute_modules(chains.clone(), false).await;
+        let statuses: Vec<_> = report.results.iter().map(|r| r.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ModuleStatus::Succeeded,
+                ModuleStatus::Failed(3),
+                ModuleStatus::Skipped
+            ]
+        );
+        assert_eq!(report.exit_code(), 3);
+
+        let report = executor.execute_modules(chains, true).await;
+        assert_eq!(report.results[2].status, ModuleStatus::Succeeded);
+        assert_eq!(report.failed(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_debug_log_is_written_to_file() {
+        let dir = tempdir().unwrap();
+        let log = dir.path().join("debug.log");
+        let terraform = script(
+            &dir.path().join("terraform"),
+            r#"echo "2024-05-06T10:11:12.345Z [$TF_LOG] token=s3cr3t" >&2"#,
+        );
+        let processor = ProcessorCollection::new(vec![ProcessorItem::Equal(MaskerEqual::new(
+            vec!["s3cr3t"],
+            "****",
+        ))]);
+        let output = Output::new(
+            processor.clone(),
+            Target::File(log.clone()),
+            Target::File(log.clone()),
+        );
+        let executor = TerraformExecutor::new(processor, terraform)
+            .with_debug_log(Some(DebugLog::new("DEBUG", output)));
+
+        let code = executor
+            .untaint(dir.path().to_path_buf(), "aws_instance.web".to_string())
+            .await
+            .unwrap();
+        assert_eq!(code, 0);
+        assert_eq!(
+            fs::read_to_string(&log).unwrap(),
+            "2024-05-06T10:11:12.345Z [DEBUG] token=****\n"
+        );
     }
 }
@@ -0,0 +1,147 @@
+use crate::error::{TerraformError, TerraformResult};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Records the plan file an apply may consume.
+///
+/// The manifest is written next to the plan file and has to travel with it
+/// when the plan is passed between jobs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanManifest {
+    /// Hex-encoded SHA-256 of the plan file.
+    pub sha256: String,
+    /// Commit the plan was made from, if known.
+    #[serde(default)]
+    pub commit: Option<String>,
+}
+
+impl PlanManifest {
+    /// Computes the manifest of a plan file.
+    pub fn for_plan(plan_file: &Path, commit: Option<String>) -> TerraformResult<Self> {
+        Ok(Self {
+            sha256: sha256_file(plan_file)?,
+            commit,
+        })
+    }
+
+    /// Returns where the manifest of a plan file is stored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::{Path, PathBuf};
+    /// use terraform::integrity::PlanManifest;
+    ///
+    /// assert_eq!(
+    ///     PlanManifest::path_for(Path::new("/infra/tf_output_file")),
+    ///     PathBuf::from("/infra/tf_output_file.manifest.json")
+    /// );
+    /// ```
+    pub fn path_for(plan_file: &Path) -> PathBuf {
+        let mut path = plan_file.as_os_str().to_owned();
+        path.push(".manifest.json");
+        PathBuf::from(path)
+    }
+
+    /// Writes the manifest next to the plan file.
+    pub fn write(&self, plan_file: &Path) -> TerraformResult<()> {
+        let path = Self::path_for(plan_file);
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| TerraformError::PlanIntegrityError(e.to_string()))?;
+        std::fs::write(&path, json).map_err(|e| {
+            TerraformError::PlanIntegrityError(format!("cannot write {}: {}", path.display(), e))
+        })
+    }
+
+    /// Reads the manifest stored next to the plan file.
+    pub fn read(plan_file: &Path) -> TerraformResult<Self> {
+        let path = Self::path_for(plan_file);
+        let json = std::fs::read_to_string(&path).map_err(|e| {
+            TerraformError::PlanIntegrityError(format!("cannot read {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&json).map_err(|e| {
+            TerraformError::PlanIntegrityError(format!("invalid {}: {}", path.display(), e))
+        })
+    }
+
+    /// Checks that the plan file is the one the manifest was made for.
+    ///
+    /// The commits are only compared when both are known.
+    pub fn verify(&self, plan_file: &Path, commit: Option<&str>) -> TerraformResult<()> {
+        let sha256 = sha256_file(plan_file)?;
+        if sha256 != self.sha256 {
+            return Err(TerraformError::PlanIntegrityError(format!(
+                "{} was modified: expected sha256 {}, found {}",
+                plan_file.display(),
+                self.sha256,
+                sha256
+            )));
+        }
+        if let (Some(planned), Some(current)) = (self.commit.as_deref(), commit) {
+            if planned != current {
+                return Err(TerraformError::PlanIntegrityError(format!(
+                    "{} is stale: planned at commit {}, applying at {}",
+                    plan_file.display(),
+                    planned,
+                    current
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn sha256_file(path: &Path) -> TerraformResult<String> {
+    let data = std::fs::read(path).map_err(|e| {
+        TerraformError::PlanIntegrityError(format!("cannot read {}: {}", path.display(), e))
+    })?;
+    Ok(Sha256::digest(&data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_plan_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = dir.path().join("tfplan");
+        std::fs::write(&plan, "plan").unwrap();
+
+        let manifest = PlanManifest::for_plan(&plan, Some("abc123".to_string())).unwrap();
+        assert_eq!(
+            manifest.sha256,
+            "64879f7d6b960a01909762d911a32d4582c20010c5641ee90278b644a9e3b525"
+        );
+        manifest.write(&plan).unwrap();
+
+        let manifest = PlanManifest::read(&plan).unwrap();
+        assert!(manifest.verify(&plan, Some("abc123")).is_ok());
+        assert!(manifest.verify(&plan, None).is_ok());
+        assert!(matches!(
+            manifest.verify(&plan, Some("def456")),
+            Err(TerraformError::PlanIntegrityError(msg)) if msg.contains("stale")
+        ));
+
+        std::fs::write(&plan, "tampered").unwrap();
+        assert!(matches!(
+            manifest.verify(&plan, Some("abc123")),
+            Err(TerraformError::PlanIntegrityError(msg)) if msg.contains("modified")
+        ));
+    }
+
+    #[test]
+    fn test_read_missing_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = dir.path().join("tfplan");
+        std::fs::write(&plan, "plan").unwrap();
+        assert!(matches!(
+            PlanManifest::read(&plan),
+            Err(TerraformError::PlanIntegrityError(_))
+        ));
+    }
+}
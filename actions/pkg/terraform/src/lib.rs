@@ -1,45 +1,136 @@
+pub mod action;
 pub mod chain;
+pub mod cloud;
 pub mod command;
 pub mod constants;
+pub mod debug_log;
+pub mod diagnostics;
 pub mod error;
+pub mod integrity;
+pub mod modules;
+pub mod plugin_cache;
+pub mod response;
+pub mod retry;
+pub mod rollback;
+pub mod step;
+pub mod terragrunt;
 
 pub mod backend;
 pub mod environments;
 pub mod executor;
+pub use action::TerraformAction;
 pub use backend::TerraformBackend;
 pub use constants::*;
 pub use environments::TerraformEnv;
 
 pub use chain::CommandChain;
-use config::ConfigResult;
+use config::{config_struct, non_empty, split_list, split_paths, Optional};
 use std::path::PathBuf;
+use std::time::Duration;
 
-pub struct TerraformConfig {}
-
-impl TerraformConfig {
-    pub fn new() -> Self {
-        Self {}
-    }
-
-    pub fn get_output_file(&self) -> ConfigResult<PathBuf> {
-        TERRAFORM_OUTPUT.get()
+config_struct! {
+    /// Represents the configuration for Terraform operations.
+    pub struct TerraformConfig {
+        /// Gets the Terraform command to execute.
+        get_cmd: TerraformAction = CMD,
+        /// Gets the Terraform executable path.
+        get_bin: PathBuf = TERRAFORM_BIN,
+        /// Gets the file the Terraform result output is written to.
+        get_output_file: PathBuf = TERRAFORM_OUTPUT,
+        /// Gets the Terraform workspace name.
+        get_workspace: String = TERRAFORM_WORKSPACE,
+        /// Gets whether the destroy command may run.
+        get_allow_destroy: bool = TERRAFORM_ALLOW_DESTROY,
+        /// Gets whether the plan reports changes through its exit status.
+        get_detailed_exitcode: bool = TERRAFORM_DETAILED_EXITCODE,
+        /// Gets whether fmt_check also checks subdirectories.
+        get_fmt_recursive: bool = TERRAFORM_FMT_RECURSIVE,
+        /// Gets the resource addresses to limit plan, apply and destroy to.
+        get_targets: Vec<String> = TERRAFORM_TARGETS => split_list,
+        /// Gets whether the commands run through Terragrunt.
+        get_terragrunt: bool = TERRAFORM_TERRAGRUNT,
+        /// Gets the Terragrunt executable path.
+        get_terragrunt_bin: PathBuf = TERRAFORM_TERRAGRUNT_BIN,
+        /// Gets whether Terragrunt runs the commands in every module.
+        get_terragrunt_run_all: bool = TERRAFORM_TERRAGRUNT_RUN_ALL,
+        /// Gets the Terraform debug log level, or `None` when it is off.
+        get_log_level: Option<String> = TERRAFORM_LOG => |s| (!s.eq_ignore_ascii_case("off")).then(|| s.to_uppercase()),
+        /// Gets the file the masked debug log is written to.
+        get_log_path: PathBuf = TERRAFORM_LOG_PATH,
+        /// Gets the provider plugin cache directory, if set.
+        get_plugin_cache_dir: Option<PathBuf> = TERRAFORM_PLUGIN_CACHE_DIR => non_empty_path,
+        /// Gets the module directories run-all is limited to.
+        get_terragrunt_include_dirs: Vec<PathBuf> = TERRAFORM_TERRAGRUNT_INCLUDE_DIRS => split_paths,
+        /// Gets how long to wait for the state lock, if set.
+        get_lock_timeout: Option<String> = TERRAFORM_LOCK_TIMEOUT => non_empty,
+        /// Gets the number of retries of commands that failed to acquire the state lock.
+        get_lock_retry_attempts: u32 = TERRAFORM_LOCK_RETRY_ATTEMPTS as u32,
+        /// Gets the delay before the first lock retry.
+        get_lock_retry_delay: Duration = TERRAFORM_LOCK_RETRY_DELAY_MS as u64 => Duration::from_millis,
+        /// Gets the number of retries of init and state operations after transient backend errors.
+        get_backend_retry_attempts: u32 = TERRAFORM_BACKEND_RETRY_ATTEMPTS as u32,
+        /// Gets the delay before the first backend retry.
+        get_backend_retry_delay: Duration = TERRAFORM_BACKEND_RETRY_DELAY_MS as u64 => Duration::from_millis,
+        /// Gets the resource addresses plan and apply recreate.
+        get_replace: Vec<String> = TERRAFORM_REPLACE => split_list,
+        /// Gets the backend configuration file, if set.
+        get_backend_config_file: Option<PathBuf> = TERRAFORM_BACKEND_CONFIG_FILE => non_empty_path,
+        /// Gets the limit on concurrent operations, if set.
+        get_parallelism: Option<u32> = TERRAFORM_PARALLELISM as Optional<u32> => Optional::into_inner,
+        /// Gets the variable files to load, in order.
+        get_var_files: Vec<PathBuf> = TERRAFORM_VAR_FILES => split_paths,
+        /// Gets the file the markdown plan summary is written to, if set.
+        get_plan_summary_file: Option<PathBuf> = TERRAFORM_PLAN_SUMMARY_FILE => non_empty_path,
+        /// Gets whether init upgrades modules and providers.
+        get_init_upgrade: bool = TERRAFORM_INIT_UPGRADE,
+        /// Gets whether colors are disabled in the init output.
+        get_no_color: bool = TERRAFORM_NO_COLOR,
+        /// Gets the module directories or globs to run the chain in.
+        get_modules: Vec<String> = TERRAFORM_MODULES => split_list,
+        /// Gets whether the modules run in parallel.
+        get_modules_parallel: bool = TERRAFORM_MODULES_PARALLEL,
+        /// Gets whether the result of remote runs is read from the Terraform Cloud API.
+        get_cloud_poll: bool = TERRAFORM_CLOUD_POLL,
+        /// Gets the interval between two reads of the remote run status.
+        get_cloud_poll_interval: Duration = TERRAFORM_CLOUD_POLL_INTERVAL_MS as u64 => Duration::from_millis,
+        /// Gets how long to wait for a remote run to finish.
+        get_cloud_poll_timeout: Duration = TERRAFORM_CLOUD_POLL_TIMEOUT_MS as u64 => Duration::from_millis,
+        /// Gets whether apply consumes the plan file of an earlier plan run.
+        get_saved_plan: bool = TERRAFORM_SAVED_PLAN,
+        /// Gets the commit the plan is made from, falling back to `GITHUB_SHA`.
+        get_commit_sha: Option<String> = TERRAFORM_COMMIT_SHA => |s| non_empty(s).or_else(|| std::env::var(ENV_GITHUB_SHA).ok().filter(|s| !s.is_empty())),
+        /// Gets the git ref a rollback is planned against when apply fails, if set.
+        get_rollback_ref: Option<String> = TERRAFORM_ROLLBACK_REF => non_empty,
     }
+}
 
-    pub fn get_bin(&self) -> ConfigResult<PathBuf> {
-        TERRAFORM_BIN.get()
+/// Returns `None` for an empty path.
+fn non_empty_path(s: String) -> Option<PathBuf> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(PathBuf::from(s))
     }
+}
 
-    pub fn get_workspace(&self) -> ConfigResult<String> {
-        TERRAFORM_WORKSPACE.get()
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
 
-    pub fn get_cmd(&self) -> ConfigResult<String> {
-        CMD.get()
-    }
-}
+    #[test]
+    fn test_log_path_does_not_set_log_level() {
+        env::remove_var(ENV_TERRAFORM_LOG);
+        env::set_var(ENV_TERRAFORM_LOG_PATH, "/non/existent/debug.log");
+        TERRAFORM_LOG.refresh();
+        TERRAFORM_LOG_PATH.refresh();
 
-impl Default for TerraformConfig {
-    fn default() -> Self {
-        Self::new()
+        let config = TerraformConfig::new();
+        assert_eq!(config.get_log_level().unwrap(), None);
+        assert_eq!(TERRAFORM_LOG.get().unwrap(), DEFAULT_TERRAFORM_LOG);
+        assert_eq!(
+            config.get_log_path().unwrap(),
+            PathBuf::from("/non/existent/debug.log")
+        );
     }
 }
@@ -0,0 +1,222 @@
+use crate::error::{TerraformError, TerraformResult};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Resolves module directories relative to `root`.
+///
+/// Patterns may be plain directories or globs such as `stacks/*`; glob matches
+/// are sorted and only directories are kept. Every pattern must match at least
+/// one directory, and a directory matched twice is only run once.
+pub fn resolve_modules(root: &Path, patterns: &[String]) -> TerraformResult<Vec<PathBuf>> {
+    let mut modules = Vec::new();
+    for pattern in patterns {
+        let full = root.join(pattern);
+        let matched: Vec<PathBuf> = if pattern.contains(['*', '?', '[']) {
+            let paths = glob::glob(&full.to_string_lossy())
+                .map_err(|e| TerraformError::ModuleError(format!("{}: {}", pattern, e)))?;
+            let mut dirs: Vec<_> = paths
+                .filter_map(Result::ok)
+                .filter(|path| path.is_dir())
+                .collect();
+            dirs.sort();
+            dirs
+        } else if full.is_dir() {
+            vec![full]
+        } else {
+            Vec::new()
+        };
+        if matched.is_empty() {
+            return Err(TerraformError::ModuleError(format!(
+                "{} does not match a directory",
+                pattern
+            )));
+        }
+        for dir in matched {
+            if !modules.contains(&dir) {
+                modules.push(dir);
+            }
+        }
+    }
+    Ok(modules)
+}
+
+/// The outcome of the chain of one module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleStatus {
+    Succeeded,
+    /// A command exited with the given status.
+    Failed(i32),
+    /// A command could not be run.
+    Error(String),
+    /// Not run because an earlier module failed.
+    Skipped,
+}
+
+/// The result of the chain of one module.
+#[derive(Debug, Clone)]
+pub struct ModuleResult {
+    pub dir: PathBuf,
+    pub status: ModuleStatus,
+    pub duration: Duration,
+}
+
+impl ModuleResult {
+    fn status_str(&self) -> String {
+        match &self.status {
+            ModuleStatus::Succeeded => "succeeded".to_string(),
+            ModuleStatus::Failed(code) => format!("failed with status {}", code),
+            ModuleStatus::Error(e) => format!("error: {}", e),
+            ModuleStatus::Skipped => "skipped".to_string(),
+        }
+    }
+}
+
+/// Results of the module chains, in module order.
+#[derive(Debug, Clone, Default)]
+pub struct ModuleReport {
+    pub results: Vec<ModuleResult>,
+}
+
+impl ModuleReport {
+    /// Returns the number of modules that did not succeed, including skipped ones.
+    pub fn failed(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| r.status != ModuleStatus::Succeeded)
+            .count()
+    }
+
+    /// Returns the status of the first module that failed, or `0`.
+    pub fn exit_code(&self) -> i32 {
+        self.results
+            .iter()
+            .find_map(|r| match r.status {
+                ModuleStatus::Succeeded | ModuleStatus::Skipped => None,
+                ModuleStatus::Failed(code) => Some(code),
+                ModuleStatus::Error(_) => Some(1),
+            })
+            .unwrap_or(0)
+    }
+
+    /// Renders the report as markdown, with module paths relative to `root`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::{Path, PathBuf};
+    /// use std::time::Duration;
+    /// use terraform::modules::{ModuleReport, ModuleResult, ModuleStatus};
+    ///
+    /// let report = ModuleReport {
+    ///     results: vec![
+    ///         ModuleResult {
+    ///             dir: PathBuf::from("/repo/stacks/vpc"),
+    ///             status: ModuleStatus::Succeeded,
+    ///             duration: Duration::from_secs(42),
+    ///         },
+    ///         ModuleResult {
+    ///             dir: PathBuf::from("/repo/stacks/eks"),
+    ///             status: ModuleStatus::Failed(1),
+    ///             duration: Duration::from_secs(7),
+    ///         },
+    ///     ],
+    /// };
+    /// assert_eq!(
+    ///     report.markdown(Path::new("/repo")),
+    ///     "### Terraform modules\n\n\
+    ///      | Module | Result | Duration |\n\
+    ///      | --- | --- | --- |\n\
+    ///      | `stacks/vpc` | succeeded | 42s |\n\
+    ///      | `stacks/eks` | failed with status 1 | 7s |\n"
+    /// );
+    /// ```
+    pub fn markdown(&self, root: &Path) -> String {
+        let mut out =
+            "### Terraform modules\n\n| Module | Result | Duration |\n| --- | --- | --- |\n"
+                .to_string();
+        for result in &self.results {
+            out.push_str(&format!(
+                "| `{}` | {} | {}s |\n",
+                relative(&result.dir, root).display(),
+                result.status_str().replace('|', "\\|"),
+                result.duration.as_secs()
+            ));
+        }
+        out
+    }
+
+    /// Returns one log line per module, with module paths relative to `root`.
+    pub fn lines(&self, root: &Path) -> Vec<String> {
+        self.results
+            .iter()
+            .map(|r| {
+                format!(
+                    "{}: {} in {}s",
+                    relative(&r.dir, root).display(),
+                    r.status_str(),
+                    r.duration.as_secs()
+                )
+            })
+            .collect()
+    }
+}
+
+fn relative<'a>(dir: &'a Path, root: &Path) -> &'a Path {
+    dir.strip_prefix(root).unwrap_or(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_modules() {
+        let root = tempfile::tempdir().unwrap();
+        for dir in ["stacks/vpc", "stacks/eks", "global"] {
+            std::fs::create_dir_all(root.path().join(dir)).unwrap();
+        }
+        std::fs::write(root.path().join("stacks/README.md"), "").unwrap();
+
+        let modules = resolve_modules(
+            root.path(),
+            &[
+                "global".to_string(),
+                "stacks/*".to_string(),
+                "stacks/vpc".to_string(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            modules,
+            vec![
+                root.path().join("global"),
+                root.path().join("stacks/eks"),
+                root.path().join("stacks/vpc"),
+            ]
+        );
+
+        assert!(matches!(
+            resolve_modules(root.path(), &["apps/*".to_string()]),
+            Err(TerraformError::ModuleError(_))
+        ));
+    }
+
+    #[test]
+    fn test_report_exit_code() {
+        let result = |status| ModuleResult {
+            dir: PathBuf::from("m"),
+            status,
+            duration: Duration::ZERO,
+        };
+        let report = ModuleReport {
+            results: vec![
+                result(ModuleStatus::Succeeded),
+                result(ModuleStatus::Error("no such file".to_string())),
+                result(ModuleStatus::Skipped),
+            ],
+        };
+        assert_eq!(report.exit_code(), 1);
+        assert_eq!(report.failed(), 2);
+        assert_eq!(ModuleReport::default().exit_code(), 0);
+    }
+}
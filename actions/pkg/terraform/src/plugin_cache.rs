@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable Terraform reads the provider plugin cache directory from.
+pub const ENV_TF_PLUGIN_CACHE_DIR: &str = "TF_PLUGIN_CACHE_DIR";
+
+/// Name of the dependency lock file init writes to the working directory.
+const LOCK_FILE: &str = ".terraform.lock.hcl";
+
+/// Shared directory Terraform keeps downloaded providers in between runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginCache {
+    pub dir: PathBuf,
+}
+
+/// A provider version pinned in the dependency lock file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedProvider {
+    /// Source address, e.g. `registry.terraform.io/hashicorp/aws`.
+    pub address: String,
+    pub version: String,
+}
+
+/// Providers of a working directory found in or missing from the cache.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheUsage {
+    /// Providers that were already cached before init.
+    pub hits: Vec<LockedProvider>,
+    /// Providers init had to download.
+    pub misses: Vec<LockedProvider>,
+}
+
+impl PluginCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Creates the cache directory, which Terraform does not create itself.
+    pub fn prepare(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)
+    }
+
+    /// Lists the cached provider versions as `address/version`.
+    ///
+    /// The cache is laid out as `HOSTNAME/NAMESPACE/TYPE/VERSION/TARGET`.
+    pub fn snapshot(&self) -> HashSet<String> {
+        let mut entries = vec![(self.dir.clone(), String::new())];
+        for _ in 0..4 {
+            entries = entries
+                .into_iter()
+                .flat_map(|(dir, prefix)| subdirs(&dir, &prefix))
+                .collect();
+        }
+        entries.into_iter().map(|(_, key)| key).collect()
+    }
+
+    /// Compares the providers locked in `dir` with a snapshot taken before init.
+    pub fn usage(&self, before: &HashSet<String>, dir: &Path) -> io::Result<CacheUsage> {
+        let lock = fs::read_to_string(dir.join(LOCK_FILE))?;
+        let (hits, misses) = locked_providers(&lock)
+            .into_iter()
+            .partition(|p| before.contains(&format!("{}/{}", p.address, p.version)));
+        Ok(CacheUsage { hits, misses })
+    }
+}
+
+impl CacheUsage {
+    /// Renders the usage as markdown for the job summary.
+    pub fn markdown(&self) -> String {
+        let mut out = format!(
+            "### Terraform provider cache\n\n{} cached, {} downloaded.\n",
+            self.hits.len(),
+            self.misses.len()
+        );
+        if self.hits.is_empty() && self.misses.is_empty() {
+            return out;
+        }
+        out.push_str("\n| Provider | Version | Cache |\n| --- | --- | --- |\n");
+        for (provider, cache) in self
+            .hits
+            .iter()
+            .map(|p| (p, "hit"))
+            .chain(self.misses.iter().map(|p| (p, "miss")))
+        {
+            out.push_str(&format!(
+                "| `{}` | {} | {} |\n",
+                provider.address, provider.version, cache
+            ));
+        }
+        out
+    }
+}
+
+fn subdirs(dir: &Path, prefix: &str) -> Vec<(PathBuf, String)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let key = if prefix.is_empty() {
+                name
+            } else {
+                format!("{}/{}", prefix, name)
+            };
+            (entry.path(), key)
+        })
+        .collect()
+}
+
+/// Reads the provider versions from a dependency lock file.
+///
+/// # Example
+///
+/// ```rust
+/// use terraform::plugin_cache::{locked_providers, LockedProvider};
+///
+/// let lock = r#"
+/// provider "registry.terraform.io/hashicorp/aws" {
+///   version     = "5.31.0"
+///   constraints = "~> 5.0"
+/// }
+/// "#;
+/// assert_eq!(
+///     locked_providers(lock),
+///     vec![LockedProvider {
+///         address: "registry.terraform.io/hashicorp/aws".to_string(),
+///         version: "5.31.0".to_string(),
+///     }]
+/// );
+/// ```
+pub fn locked_providers(lock: &str) -> Vec<LockedProvider> {
+    let mut providers = Vec::new();
+    let mut address = None;
+    for line in lock.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("provider ") {
+            address = Some(
+                rest.trim_end_matches('{')
+                    .trim()
+                    .trim_matches('"')
+                    .to_string(),
+            );
+        } else if let Some((key, value)) = line.split_once('=') {
+            if key.trim() == "version" {
+                if let Some(address) = address.take() {
+                    providers.push(LockedProvider {
+                        address,
+                        version: value.trim().trim_matches('"').to_string(),
+                    });
+                }
+            }
+        }
+    }
+    providers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_after_init() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = PluginCache::new(root.path().join("cache"));
+        cache.prepare().unwrap();
+        fs::create_dir_all(
+            cache
+                .dir
+                .join("registry.terraform.io/hashicorp/aws/5.31.0/linux_amd64"),
+        )
+        .unwrap();
+        let before = cache.snapshot();
+        assert_eq!(
+            before,
+            HashSet::from(["registry.terraform.io/hashicorp/aws/5.31.0".to_string()])
+        );
+
+        fs::write(
+            root.path().join(LOCK_FILE),
+            r#"
+provider "registry.terraform.io/hashicorp/aws" {
+  version = "5.31.0"
+}
+
+provider "registry.terraform.io/hashicorp/random" {
+  version = "3.6.0"
+}
+"#,
+        )
+        .unwrap();
+        let usage = cache.usage(&before, root.path()).unwrap();
+        assert_eq!(usage.hits.len(), 1);
+        assert_eq!(
+            usage.misses[0].address,
+            "registry.terraform.io/hashicorp/random"
+        );
+        assert!(usage
+            .markdown()
+            .contains("| `registry.terraform.io/hashicorp/random` | 3.6.0 | miss |\n"));
+    }
+}
@@ -0,0 +1,643 @@
+use executer::Annotation;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// A root module output as reported by `terraform output -json`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TerraformOutput {
+    /// Whether the output is marked `sensitive` in the configuration.
+    #[serde(default)]
+    pub sensitive: bool,
+    /// The Terraform type of the value, e.g. `"string"` or `["list", "string"]`.
+    #[serde(rename = "type", default)]
+    pub value_type: Value,
+    pub value: Value,
+}
+
+impl TerraformOutput {
+    /// Parses the JSON output of `terraform output -json`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terraform::response::TerraformOutput;
+    ///
+    /// let outputs = TerraformOutput::parse_all(r#"{
+    ///     "bucket": {"sensitive": false, "type": "string", "value": "site"},
+    ///     "token": {"sensitive": true, "type": "string", "value": "s3cr3t"}
+    /// }"#).unwrap();
+    /// assert_eq!(outputs["bucket"].render(), "site");
+    /// assert!(outputs["token"].sensitive);
+    /// ```
+    pub fn parse_all(output: &str) -> serde_json::Result<HashMap<String, Self>> {
+        // Without any outputs, older Terraform versions print nothing at all.
+        if output.trim().is_empty() {
+            return Ok(HashMap::new());
+        }
+        serde_json::from_str(output)
+    }
+
+    /// Renders the value for a step output: strings as-is, anything else as compact JSON.
+    pub fn render(&self) -> String {
+        match &self.value {
+            Value::String(s) => s.clone(),
+            value => value.to_string(),
+        }
+    }
+
+    /// Returns the strings that must be masked for a sensitive output.
+    ///
+    /// Besides the rendered value, every scalar nested in a list or object is
+    /// returned, so secrets stay masked when parts of the value are printed.
+    pub fn secret_values(&self) -> Vec<String> {
+        let mut values = vec![self.render()];
+        collect_scalars(&self.value, &mut values);
+        values.dedup();
+        values
+    }
+}
+
+/// The report printed by `terraform validate -json`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Validation {
+    pub valid: bool,
+    #[serde(default)]
+    pub error_count: u32,
+    #[serde(default)]
+    pub warning_count: u32,
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Validation {
+    /// Parses the JSON output of `terraform validate -json`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terraform::response::{Severity, Validation};
+    ///
+    /// let validation = Validation::parse(r#"{
+    ///     "valid": false,
+    ///     "error_count": 1,
+    ///     "warning_count": 0,
+    ///     "diagnostics": [{
+    ///         "severity": "error",
+    ///         "summary": "Unsupported argument",
+    ///         "detail": "An argument named \"acl\" is not expected here.",
+    ///         "range": {"filename": "main.tf", "start": {"line": 4, "column": 3}}
+    ///     }]
+    /// }"#).unwrap();
+    /// assert!(!validation.valid);
+    /// assert_eq!(validation.diagnostics[0].severity, Severity::Error);
+    /// ```
+    pub fn parse(output: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(output)
+    }
+}
+
+/// The severity of a Terraform diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single error or warning reported by Terraform.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub summary: String,
+    #[serde(default)]
+    pub detail: String,
+    /// The source location, absent for diagnostics not tied to a file.
+    #[serde(default)]
+    pub range: Option<SourceRange>,
+}
+
+impl Diagnostic {
+    /// Renders the diagnostic as a GitHub Actions workflow annotation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terraform::response::{Diagnostic, Severity};
+    ///
+    /// let diagnostic = Diagnostic {
+    ///     severity: Severity::Warning,
+    ///     summary: "Deprecated attribute".to_string(),
+    ///     detail: String::new(),
+    ///     range: None,
+    /// };
+    /// assert_eq!(diagnostic.annotation(), "::warning title=Deprecated attribute::Deprecated attribute");
+    /// ```
+    pub fn annotation(&self) -> String {
+        let message = if self.detail.is_empty() {
+            &self.summary
+        } else {
+            &self.detail
+        };
+        let mut annotation = match self.severity {
+            Severity::Error => Annotation::error(message),
+            Severity::Warning => Annotation::warning(message),
+        };
+        if let Some(range) = &self.range {
+            annotation = annotation
+                .with_file(&range.filename)
+                .with_line(range.start.line)
+                .with_col(range.start.column);
+        }
+        annotation.with_title(&self.summary).render()
+    }
+}
+
+/// The action planned for a resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeAction {
+    Create,
+    Update,
+    Delete,
+    /// Delete and create, in either order.
+    Replace,
+    Read,
+    NoOp,
+}
+
+impl ChangeAction {
+    /// Maps the `actions` list of a resource change; `None` for unknown lists.
+    fn from_actions(actions: &[String]) -> Option<Self> {
+        let actions: Vec<&str> = actions.iter().map(String::as_str).collect();
+        match actions.as_slice() {
+            ["create"] => Some(Self::Create),
+            ["update"] => Some(Self::Update),
+            ["delete"] => Some(Self::Delete),
+            ["delete", "create"] | ["create", "delete"] => Some(Self::Replace),
+            ["read"] => Some(Self::Read),
+            ["no-op"] => Some(Self::NoOp),
+            _ => None,
+        }
+    }
+
+    /// Returns the name Terraform uses in its plan output.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Delete => "destroy",
+            Self::Replace => "replace",
+            Self::Read => "read",
+            Self::NoOp => "no-op",
+        }
+    }
+}
+
+/// A resource the plan changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceChange {
+    pub address: String,
+    pub action: ChangeAction,
+    /// Why Terraform chose the action, e.g. `replace_because_tainted`.
+    pub reason: Option<String>,
+}
+
+impl ResourceChange {
+    /// Describes the reason in words, e.g. `tainted` for `replace_because_tainted`.
+    pub fn describe_reason(&self) -> Option<String> {
+        self.reason.as_ref().map(|reason| {
+            let reason = reason
+                .split_once("_because_")
+                .map_or(reason.as_str(), |(_, why)| why);
+            reason.replace('_', " ")
+        })
+    }
+}
+
+/// The resource changes of a saved plan, as counted by `terraform plan`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlanSummary {
+    pub add: usize,
+    pub change: usize,
+    pub destroy: usize,
+    /// Resources with an action other than `no-op`, in plan order.
+    pub resources: Vec<ResourceChange>,
+}
+
+#[derive(Deserialize)]
+struct PlanJson {
+    #[serde(default)]
+    resource_changes: Vec<ResourceChangeJson>,
+}
+
+#[derive(Deserialize)]
+struct ResourceChangeJson {
+    address: String,
+    change: ChangeJson,
+    #[serde(default)]
+    action_reason: Option<String>,
+}
+
+// Only the actions are read; `before` and `after` may hold sensitive values,
+// see `plan_secrets`.
+#[derive(Deserialize)]
+struct ChangeJson {
+    actions: Vec<String>,
+}
+
+impl PlanSummary {
+    /// Parses the JSON output of `terraform show -json <planfile>`.
+    ///
+    /// A replaced resource counts as one to add and one to destroy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terraform::response::PlanSummary;
+    ///
+    /// let summary = PlanSummary::parse(r#"{"resource_changes": [
+    ///     {"address": "aws_s3_bucket.site", "change": {"actions": ["create"]}},
+    ///     {"address": "aws_instance.web", "change": {"actions": ["delete", "create"]}},
+    ///     {"address": "aws_iam_role.ci", "change": {"actions": ["no-op"]}}
+    /// ]}"#).unwrap();
+    /// assert_eq!(summary.headline(), "Plan: 2 to add, 0 to change, 1 to destroy.");
+    /// assert_eq!(summary.resources.len(), 2);
+    /// ```
+    pub fn parse(output: &str) -> serde_json::Result<Self> {
+        let plan: PlanJson = serde_json::from_str(output)?;
+        let mut summary = Self::default();
+        for resource in plan.resource_changes {
+            let action = match ChangeAction::from_actions(&resource.change.actions) {
+                Some(ChangeAction::NoOp) | None => continue,
+                Some(action) => action,
+            };
+            match action {
+                ChangeAction::Create => summary.add += 1,
+                ChangeAction::Update => summary.change += 1,
+                ChangeAction::Delete => summary.destroy += 1,
+                ChangeAction::Replace => {
+                    summary.add += 1;
+                    summary.destroy += 1;
+                }
+                ChangeAction::Read | ChangeAction::NoOp => {}
+            }
+            summary.resources.push(ResourceChange {
+                address: resource.address,
+                action,
+                reason: resource.action_reason,
+            });
+        }
+        Ok(summary)
+    }
+
+    /// Returns `true` if the plan adds, changes or destroys anything.
+    pub fn has_changes(&self) -> bool {
+        self.add + self.change + self.destroy > 0
+    }
+
+    /// Renders the summary line `terraform plan` prints.
+    pub fn headline(&self) -> String {
+        if !self.has_changes() {
+            return "No changes.".to_string();
+        }
+        format!(
+            "Plan: {} to add, {} to change, {} to destroy.",
+            self.add, self.change, self.destroy
+        )
+    }
+
+    /// Renders the summary as markdown for job summaries and PR comments.
+    ///
+    /// The output is not masked; pass it through the processors before writing it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terraform::response::PlanSummary;
+    ///
+    /// let summary = PlanSummary::parse(r#"{"resource_changes": [{
+    ///     "address": "aws_instance.web",
+    ///     "action_reason": "replace_because_tainted",
+    ///     "change": {"actions": ["delete", "create"]}
+    /// }]}"#).unwrap();
+    /// assert_eq!(
+    ///     summary.markdown(),
+    ///     "### Terraform plan\n\n\
+    ///      Plan: 1 to add, 0 to change, 1 to destroy.\n\n\
+    ///      | Resource | Action | Reason |\n\
+    ///      | --- | --- | --- |\n\
+    ///      | `aws_instance.web` | replace | tainted |\n"
+    /// );
+    /// ```
+    pub fn markdown(&self) -> String {
+        format!(
+            "### Terraform plan\n\n{}\n{}",
+            self.headline(),
+            self.resource_table()
+        )
+    }
+
+    /// Renders the changed resources as a markdown table preceded by a blank line,
+    /// or nothing without changes.
+    pub(crate) fn resource_table(&self) -> String {
+        if self.resources.is_empty() {
+            return String::new();
+        }
+        let mut out = "\n| Resource | Action | Reason |\n| --- | --- | --- |\n".to_string();
+        for resource in &self.resources {
+            out.push_str(&format!(
+                "| `{}` | {} | {} |\n",
+                escape_cell(&resource.address),
+                resource.action.as_str(),
+                escape_cell(&resource.describe_reason().unwrap_or_default())
+            ));
+        }
+        out
+    }
+}
+
+/// Escapes the column separator in a markdown table cell.
+fn escape_cell(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+/// Returns the values a plan marks as sensitive, for masking.
+///
+/// Reads the JSON output of `terraform show -json <planfile>`: resource
+/// attributes flagged in `before_sensitive`/`after_sensitive`, sensitive
+/// root module variables and sensitive outputs. Nested values are returned
+/// scalar by scalar, like [`TerraformOutput::secret_values`].
+///
+/// # Example
+///
+/// ```rust
+/// use terraform::response::plan_secrets;
+///
+/// let secrets = plan_secrets(r#"{
+///     "variables": {"db_password": {"value": "hunter2"}, "region": {"value": "eu-west-1"}},
+///     "configuration": {"root_module": {"variables": {"db_password": {"sensitive": true}}}},
+///     "resource_changes": [{
+///         "address": "aws_db_instance.main",
+///         "change": {
+///             "actions": ["create"],
+///             "after": {"username": "admin", "password": "hunter2"},
+///             "after_sensitive": {"password": true}
+///         }
+///     }]
+/// }"#).unwrap();
+/// assert_eq!(secrets, vec!["hunter2"]);
+/// ```
+pub fn plan_secrets(output: &str) -> serde_json::Result<Vec<String>> {
+    let plan: Value = serde_json::from_str(output)?;
+    let mut values = Vec::new();
+
+    let sensitive_variables = plan
+        .pointer("/configuration/root_module/variables")
+        .and_then(Value::as_object);
+    if let (Some(variables), Some(config)) = (
+        plan.get("variables").and_then(Value::as_object),
+        sensitive_variables,
+    ) {
+        for (name, variable) in variables {
+            if config
+                .get(name)
+                .is_some_and(|c| c["sensitive"] == Value::Bool(true))
+            {
+                collect_scalars(&variable["value"], &mut values);
+            }
+        }
+    }
+
+    if let Some(outputs) = plan
+        .pointer("/planned_values/outputs")
+        .and_then(Value::as_object)
+    {
+        for output in outputs.values() {
+            if output["sensitive"] == Value::Bool(true) {
+                collect_scalars(&output["value"], &mut values);
+            }
+        }
+    }
+
+    if let Some(changes) = plan.get("resource_changes").and_then(Value::as_array) {
+        for change in changes.iter().map(|c| &c["change"]) {
+            collect_sensitive(&change["before"], &change["before_sensitive"], &mut values);
+            collect_sensitive(&change["after"], &change["after_sensitive"], &mut values);
+        }
+    }
+
+    values.sort();
+    values.dedup();
+    Ok(values)
+}
+
+/// Collects the scalars of `value` flagged by `sensitive`, a mask of the same shape.
+fn collect_sensitive(value: &Value, sensitive: &Value, values: &mut Vec<String>) {
+    match (value, sensitive) {
+        (value, Value::Bool(true)) => collect_scalars(value, values),
+        (Value::Object(map), Value::Object(mask)) => {
+            for (key, flag) in mask {
+                if let Some(value) = map.get(key) {
+                    collect_sensitive(value, flag, values);
+                }
+            }
+        }
+        (Value::Array(items), Value::Array(mask)) => {
+            for (value, flag) in items.iter().zip(mask) {
+                collect_sensitive(value, flag, values);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The files reported by `terraform fmt -check`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FmtCheck {
+    /// Files that are not in the canonical format, relative to the checked directory.
+    pub files: Vec<String>,
+}
+
+impl FmtCheck {
+    /// Parses the file list printed by `terraform fmt -check`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terraform::response::FmtCheck;
+    ///
+    /// let check = FmtCheck::parse("main.tf\nmodules/cdn/outputs.tf\n");
+    /// assert_eq!(check.files, vec!["main.tf", "modules/cdn/outputs.tf"]);
+    /// assert!(!check.is_formatted());
+    /// ```
+    pub fn parse(output: &str) -> Self {
+        Self {
+            files: output
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if no file needs formatting.
+    pub fn is_formatted(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Renders one GitHub Actions error annotation per unformatted file.
+    pub fn annotations(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .map(|file| {
+                Annotation::error("File is not formatted, run terraform fmt")
+                    .with_title("Terraform fmt")
+                    .with_file(file)
+                    .render()
+            })
+            .collect()
+    }
+}
+
+/// A location in a configuration file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SourceRange {
+    pub filename: String,
+    pub start: SourcePos,
+}
+
+/// A line and column in a configuration file, both 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct SourcePos {
+    pub line: u32,
+    pub column: u32,
+}
+
+fn collect_scalars(value: &Value, values: &mut Vec<String>) {
+    match value {
+        // Booleans are too common in logs to be masked.
+        Value::Null | Value::Bool(_) => {}
+        Value::String(s) => values.push(s.clone()),
+        Value::Array(items) => items.iter().for_each(|v| collect_scalars(v, values)),
+        Value::Object(map) => map.values().for_each(|v| collect_scalars(v, values)),
+        scalar => values.push(scalar.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_all_typed_values() {
+        let outputs = TerraformOutput::parse_all(
+            r#"{
+                "ids": {"sensitive": false, "type": ["list", "string"], "value": ["a", "b"]},
+                "port": {"sensitive": false, "type": "number", "value": 8080}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(outputs["ids"].render(), r#"["a","b"]"#);
+        assert_eq!(outputs["port"].render(), "8080");
+        assert_eq!(outputs["port"].value_type, Value::from("number"));
+    }
+
+    #[test]
+    fn test_parse_all_empty() {
+        assert!(TerraformOutput::parse_all("").unwrap().is_empty());
+        assert!(TerraformOutput::parse_all("{}\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_secret_values_nested() {
+        let output: TerraformOutput = serde_json::from_str(
+            r#"{"sensitive": true, "value": {"user": "admin", "password": "s3cr3t"}}"#,
+        )
+        .unwrap();
+        let values = output.secret_values();
+        assert!(values.contains(&"admin".to_string()));
+        assert!(values.contains(&"s3cr3t".to_string()));
+        assert!(values.contains(&output.render()));
+    }
+
+    #[test]
+    fn test_plan_summary_counts() {
+        let summary = PlanSummary::parse(
+            r#"{"format_version": "1.2", "resource_changes": [
+                {"address": "aws_s3_bucket.logs", "change": {"actions": ["update"], "after": {"acl": "private"}}},
+                {"address": "aws_instance.old", "change": {"actions": ["delete"]}},
+                {"address": "data.aws_caller_identity.current", "change": {"actions": ["read"]}}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!((summary.add, summary.change, summary.destroy), (0, 1, 1));
+        assert_eq!(summary.resources[2].action, ChangeAction::Read);
+        assert!(summary.has_changes());
+    }
+
+    #[test]
+    fn test_markdown_escapes_cells() {
+        let summary = PlanSummary::parse(
+            r#"{"resource_changes": [
+                {"address": "aws_route53_record.www[\"a|b\"]", "change": {"actions": ["create"]}},
+                {"address": "aws_iam_role.old", "action_reason": "delete_because_no_resource_config",
+                 "change": {"actions": ["delete"]}}
+            ]}"#,
+        )
+        .unwrap();
+        let markdown = summary.markdown();
+        assert!(markdown.contains("| `aws_route53_record.www[\"a\\|b\"]` | create |  |\n"));
+        assert!(markdown.contains("| `aws_iam_role.old` | destroy | no resource config |\n"));
+    }
+
+    #[test]
+    fn test_plan_summary_without_changes() {
+        let summary = PlanSummary::parse(r#"{"format_version": "1.2"}"#).unwrap();
+        assert!(summary.resources.is_empty());
+        assert_eq!(summary.headline(), "No changes.");
+        assert_eq!(summary.markdown(), "### Terraform plan\n\nNo changes.\n");
+    }
+
+    #[test]
+    fn test_plan_secrets_nested_and_outputs() {
+        let secrets = plan_secrets(
+            r#"{
+                "planned_values": {"outputs": {
+                    "endpoint": {"sensitive": false, "value": "db.internal"},
+                    "token": {"sensitive": true, "value": "tok-123"}
+                }},
+                "resource_changes": [{
+                    "address": "aws_ssm_parameter.keys",
+                    "change": {
+                        "actions": ["update"],
+                        "before": {"tags": {"team": "ci"}, "values": ["old-key", "public"]},
+                        "before_sensitive": {"values": [true, false]},
+                        "after": {"tags": {"team": "ci"}, "values": ["new-key", "public"]},
+                        "after_sensitive": {"values": [true, false]}
+                    }
+                }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(secrets, vec!["new-key", "old-key", "tok-123"]);
+    }
+
+    #[test]
+    fn test_annotation_with_location() {
+        let validation = Validation::parse(
+            r#"{"valid": false, "diagnostics": [{
+                "severity": "error",
+                "summary": "Invalid reference",
+                "detail": "A reference must be followed by a name.\nSee docs: a, b",
+                "range": {"filename": "modules/cdn/main.tf", "start": {"line": 12, "column": 5}}
+            }]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            validation.diagnostics[0].annotation(),
+            "::error file=modules/cdn/main.tf,line=12,col=5,title=Invalid reference::\
+             A reference must be followed by a name.%0ASee docs: a, b"
+        );
+    }
+}
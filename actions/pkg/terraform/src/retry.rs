@@ -0,0 +1,57 @@
+/// Error message of Terraform when another run holds the state lock.
+const LOCK_ERROR: &str = "Error acquiring the state lock";
+
+/// Returns `true` if a line of Terraform error output reports a held state lock.
+///
+/// # Example
+///
+/// ```rust
+/// use terraform::retry::is_lock_error;
+///
+/// assert!(is_lock_error("│ Error: Error acquiring the state lock"));
+/// assert!(!is_lock_error("│ Error: Invalid reference"));
+/// ```
+pub fn is_lock_error(line: &str) -> bool {
+    line.contains(LOCK_ERROR)
+}
+
+/// Errors of the state backends and their APIs that go away when the command is retried.
+///
+/// Errors of the configuration or credentials, such as `AccessDenied` or
+/// `NoSuchBucket`, are deliberately absent.
+const TRANSIENT_ERRORS: &[&str] = &[
+    "RequestError: send request failed",
+    "connection reset by peer",
+    "i/o timeout",
+    "TLS handshake timeout",
+    "context deadline exceeded",
+    "Temporary failure in name resolution",
+    "RequestTimeout",
+    "SlowDown",
+    "ThrottlingException",
+    "Throttling: Rate exceeded",
+    "ProvisionedThroughputExceededException",
+    "ServiceUnavailable",
+    "InternalError",
+    "502 Bad Gateway",
+    "503 Service Unavailable",
+];
+
+/// Returns `true` if a line of Terraform error output reports a transient backend error.
+///
+/// # Example
+///
+/// ```rust
+/// use terraform::retry::is_transient_error;
+///
+/// assert!(is_transient_error(
+///     "│ Error: RequestError: send request failed: dial tcp: i/o timeout"
+/// ));
+/// assert!(is_transient_error("│ ThrottlingException: Rate of requests exceeds the allowed throughput."));
+/// assert!(!is_transient_error("│ Error: NoSuchBucket: The specified bucket does not exist"));
+/// ```
+pub fn is_transient_error(line: &str) -> bool {
+    TRANSIENT_ERRORS
+        .iter()
+        .any(|pattern| line.contains(pattern))
+}
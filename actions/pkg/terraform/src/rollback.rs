@@ -0,0 +1,178 @@
+use crate::response::{PlanSummary, TerraformOutput};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// State of a working directory taken before an apply.
+///
+/// Compared with a snapshot taken after a failed apply, it tells whether the
+/// apply got far enough to write the state and which outputs it changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StateSnapshot {
+    /// Serial of the state, which Terraform increments on every write; `None` without a state.
+    pub serial: Option<u64>,
+    /// Values of the root module outputs by name, sensitive ones included.
+    pub outputs: BTreeMap<String, Value>,
+}
+
+/// What a failed apply changed compared to the snapshot taken before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateChanges {
+    pub serial_before: Option<u64>,
+    pub serial_after: Option<u64>,
+    /// Names of the outputs that were added, removed or changed.
+    pub changed_outputs: Vec<String>,
+}
+
+/// A plan of the previous configuration against the state a failed apply left.
+#[derive(Debug, Clone)]
+pub struct RollbackReport {
+    /// Git ref of the previous configuration.
+    pub git_ref: String,
+    pub changes: StateChanges,
+    pub plan: PlanSummary,
+}
+
+impl StateSnapshot {
+    /// Creates a snapshot from the output of `terraform state pull` and the outputs.
+    ///
+    /// An empty state, as pulled before the first apply, has no serial.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use terraform::rollback::StateSnapshot;
+    ///
+    /// let snapshot = StateSnapshot::new(r#"{"version": 4, "serial": 12}"#, &HashMap::new());
+    /// assert_eq!(snapshot.serial, Some(12));
+    /// assert_eq!(StateSnapshot::new("", &HashMap::new()).serial, None);
+    /// ```
+    pub fn new(state: &str, outputs: &HashMap<String, TerraformOutput>) -> Self {
+        let serial = serde_json::from_str::<Value>(state)
+            .ok()
+            .and_then(|state| state.get("serial").and_then(Value::as_u64));
+        Self {
+            serial,
+            outputs: outputs
+                .iter()
+                .map(|(name, output)| (name.clone(), output.value.clone()))
+                .collect(),
+        }
+    }
+
+    /// Compares the snapshot with one taken after the apply.
+    pub fn changes(&self, after: &StateSnapshot) -> StateChanges {
+        let names: BTreeSet<&String> = self.outputs.keys().chain(after.outputs.keys()).collect();
+        let changed_outputs = names
+            .into_iter()
+            .filter(|name| self.outputs.get(*name) != after.outputs.get(*name))
+            .cloned()
+            .collect();
+        StateChanges {
+            serial_before: self.serial,
+            serial_after: after.serial,
+            changed_outputs,
+        }
+    }
+}
+
+impl StateChanges {
+    /// Returns whether the apply wrote the state before it failed.
+    pub fn state_written(&self) -> bool {
+        self.serial_before != self.serial_after
+    }
+}
+
+impl RollbackReport {
+    /// Renders the report as markdown for the job summary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use terraform::response::PlanSummary;
+    /// use terraform::rollback::{RollbackReport, StateChanges};
+    ///
+    /// let report = RollbackReport {
+    ///     git_ref: "v1.4.0".to_string(),
+    ///     changes: StateChanges {
+    ///         serial_before: Some(12),
+    ///         serial_after: Some(13),
+    ///         changed_outputs: vec!["endpoint".to_string()],
+    ///     },
+    ///     plan: PlanSummary::default(),
+    /// };
+    /// assert_eq!(
+    ///     report.markdown(),
+    ///     "### Terraform rollback plan\n\n\
+    ///      The apply failed after writing the state (serial 12 to 13). \
+    ///      Changed outputs: `endpoint`.\n\n\
+    ///      Plan of `v1.4.0` against the current state: No changes.\n"
+    /// );
+    /// ```
+    pub fn markdown(&self) -> String {
+        let mut out = "### Terraform rollback plan\n\n".to_string();
+        if self.changes.state_written() {
+            out.push_str(&format!(
+                "The apply failed after writing the state (serial {} to {}).",
+                serial(self.changes.serial_before),
+                serial(self.changes.serial_after)
+            ));
+        } else {
+            out.push_str("The apply failed without writing the state.");
+        }
+        if !self.changes.changed_outputs.is_empty() {
+            let names: Vec<_> = self
+                .changes
+                .changed_outputs
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect();
+            out.push_str(&format!(" Changed outputs: {}.", names.join(", ")));
+        }
+        out.push_str(&format!(
+            "\n\nPlan of `{}` against the current state: {}\n{}",
+            self.git_ref,
+            self.plan.headline(),
+            self.plan.resource_table()
+        ));
+        out
+    }
+}
+
+fn serial(serial: Option<u64>) -> String {
+    serial.map_or_else(|| "none".to_string(), |s| s.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_changes() {
+        let snapshot = |serial, outputs: Vec<(&str, Value)>| StateSnapshot {
+            serial,
+            outputs: outputs
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        };
+        let before = snapshot(
+            Some(3),
+            vec![
+                ("bucket", json!("site")),
+                ("endpoint", json!("a.example.com")),
+            ],
+        );
+        let after = snapshot(
+            Some(4),
+            vec![("endpoint", json!("b.example.com")), ("zone", json!("Z1"))],
+        );
+
+        let changes = before.changes(&after);
+        assert!(changes.state_written());
+        assert_eq!(changes.changed_outputs, vec!["bucket", "endpoint", "zone"]);
+        assert!(!before.changes(&before).state_written());
+        assert!(before.changes(&before).changed_outputs.is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::command::TerraformCommand;
+use crate::error::{TerraformError, TerraformResult};
+use std::time::Duration;
+
+/// The outcome of a single step of a chain.
+#[derive(Debug)]
+pub enum StepOutcome {
+    /// The command exited with status `0`.
+    Succeeded,
+
+    /// A plan with `-detailed-exitcode` succeeded and has changes (status `2`).
+    ChangesPresent,
+
+    /// The workspace did not exist and was created.
+    WorkspaceCreated(String),
+
+    /// The command exited with a non-zero status.
+    Failed(i32),
+
+    /// The command could not be run.
+    Error(TerraformError),
+
+    /// The command was skipped because an earlier step failed.
+    Skipped,
+}
+
+/// The result of a step, in chain order.
+#[derive(Debug)]
+pub struct StepResult {
+    pub command: TerraformCommand,
+    /// Time the command ran, zero for skipped steps.
+    pub duration: Duration,
+    /// Exit status of the command; `None` if it errored or was skipped.
+    pub exit_code: Option<i32>,
+    pub outcome: StepOutcome,
+}
+
+impl StepResult {
+    /// Formats the finish record of the step, e.g. `[step 3/4] plan: has changes in 12.30s`.
+    pub fn record(&self, index: usize, total: usize) -> String {
+        let outcome = match &self.outcome {
+            StepOutcome::Succeeded => "succeeded".to_string(),
+            StepOutcome::ChangesPresent => "has changes".to_string(),
+            StepOutcome::WorkspaceCreated(name) => format!("created workspace {}", name),
+            StepOutcome::Failed(code) => format!("failed with status {}", code),
+            StepOutcome::Error(e) => format!("errored ({})", e),
+            StepOutcome::Skipped => {
+                return format!(
+                    "[step {}/{}] {}: skipped",
+                    index + 1,
+                    total,
+                    self.command.operation()
+                )
+            }
+        };
+        format!(
+            "[step {}/{}] {}: {} in {:.2}s",
+            index + 1,
+            total,
+            self.command.operation(),
+            outcome,
+            self.duration.as_secs_f64()
+        )
+    }
+
+    /// Returns `true` if the step failed or errored.
+    pub fn is_failure(&self) -> bool {
+        matches!(self.outcome, StepOutcome::Failed(_) | StepOutcome::Error(_))
+    }
+}
+
+/// Reduces the results of a chain to its status.
+///
+/// Returns the error of the first step that errored, or else the status of
+/// the first step that failed, or `0`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::path::PathBuf;
+/// use std::time::Duration;
+/// use terraform::command::TerraformCommand;
+/// use terraform::step::{chain_status, StepOutcome, StepResult};
+///
+/// let output = TerraformCommand::Output { dir: PathBuf::from("/infra") };
+/// let results = vec![
+///     StepResult {
+///         command: output.clone(),
+///         duration: Duration::from_secs(1),
+///         exit_code: Some(3),
+///         outcome: StepOutcome::Failed(3),
+///     },
+///     StepResult {
+///         command: output,
+///         duration: Duration::ZERO,
+///         exit_code: None,
+///         outcome: StepOutcome::Skipped,
+///     },
+/// ];
+/// assert_eq!(chain_status(results).unwrap(), 3);
+/// ```
+pub fn chain_status(results: Vec<StepResult>) -> TerraformResult<i32> {
+    for result in results {
+        match result.outcome {
+            StepOutcome::Error(e) => return Err(e),
+            StepOutcome::Failed(code) => return Ok(code),
+            _ => {}
+        }
+    }
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_record() {
+        let step = |outcome| StepResult {
+            command: TerraformCommand::Output {
+                dir: PathBuf::from("/infra"),
+            },
+            duration: Duration::from_millis(1500),
+            exit_code: None,
+            outcome,
+        };
+        assert_eq!(
+            step(StepOutcome::WorkspaceCreated("prod".to_string())).record(1, 4),
+            "[step 2/4] output: created workspace prod in 1.50s"
+        );
+        assert_eq!(
+            step(StepOutcome::Skipped).record(3, 4),
+            "[step 4/4] output: skipped"
+        );
+        assert!(step(StepOutcome::Failed(1)).is_failure());
+        assert!(!step(StepOutcome::ChangesPresent).is_failure());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::command::TerraformCommand;
+use std::path::{Path, PathBuf};
+
+/// Runs Terraform commands through Terragrunt.
+///
+/// Commands keep their `TerraformCommand` arguments; Terragrunt passes them on
+/// to the Terraform executable of the executor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Terragrunt {
+    /// Path to the Terragrunt executable.
+    pub bin: PathBuf,
+    /// Run init, plan, apply and destroy in every module below the working directory.
+    pub run_all: bool,
+    /// With `run_all`, only run the modules in these directories.
+    pub include_dirs: Vec<PathBuf>,
+}
+
+impl Terragrunt {
+    /// Creates a Terragrunt runner for a single module.
+    pub fn new(bin: PathBuf) -> Self {
+        Self {
+            bin,
+            run_all: false,
+            include_dirs: Vec::new(),
+        }
+    }
+
+    pub fn with_run_all(mut self, run_all: bool) -> Self {
+        self.run_all = run_all;
+        self
+    }
+
+    pub fn with_include_dirs(mut self, include_dirs: Vec<PathBuf>) -> Self {
+        self.include_dirs = include_dirs;
+        self
+    }
+
+    /// Builds the Terragrunt command line for `command`.
+    ///
+    /// Only init, plan, apply and destroy run in all modules; commands whose
+    /// output is parsed, like `show -json`, always run in the working directory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::{Path, PathBuf};
+    /// use terraform::command::TerraformCommand;
+    /// use terraform::terragrunt::Terragrunt;
+    ///
+    /// let terragrunt = Terragrunt::new(PathBuf::from("terragrunt")).with_run_all(true);
+    /// let init = TerraformCommand::Init {
+    ///     dir: PathBuf::from("live/prod"),
+    ///     backend_config: None,
+    ///     backend_config_file: None,
+    ///     upgrade: false,
+    ///     no_color: false,
+    /// };
+    /// assert_eq!(
+    ///     terragrunt.command_line(Path::new("/usr/local/bin/terraform"), &init),
+    ///     vec![
+    ///         "terragrunt",
+    ///         "run-all",
+    ///         "init",
+    ///         "-input=false",
+    ///         "-reconfigure",
+    ///         "--terragrunt-tfpath=/usr/local/bin/terraform",
+    ///         "--terragrunt-non-interactive",
+    ///     ]
+    /// );
+    /// ```
+    pub fn command_line(&self, terraform_path: &Path, command: &TerraformCommand) -> Vec<String> {
+        let mut cmd = vec![self.bin.to_string_lossy().to_string()];
+        let run_all = self.run_all && runs_in_all_modules(command);
+        if run_all {
+            cmd.push("run-all".to_string());
+        }
+        cmd.extend(command.to_args());
+        cmd.push(format!(
+            "--terragrunt-tfpath={}",
+            terraform_path.to_string_lossy()
+        ));
+        cmd.push("--terragrunt-non-interactive".to_string());
+        if run_all {
+            for dir in &self.include_dirs {
+                cmd.push(format!(
+                    "--terragrunt-include-dir={}",
+                    dir.to_string_lossy()
+                ));
+            }
+        }
+        cmd
+    }
+}
+
+fn runs_in_all_modules(command: &TerraformCommand) -> bool {
+    matches!(
+        command,
+        TerraformCommand::Init { .. }
+            | TerraformCommand::Plan { .. }
+            | TerraformCommand::Apply { .. }
+            | TerraformCommand::Destroy { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_include_dirs_only_with_run_all() {
+        let terragrunt = Terragrunt::new(PathBuf::from("terragrunt"))
+            .with_run_all(true)
+            .with_include_dirs(vec![PathBuf::from("vpc"), PathBuf::from("eks")]);
+        let output = TerraformCommand::Output {
+            dir: PathBuf::from("live"),
+        };
+
+        assert_eq!(
+            terragrunt.command_line(Path::new("terraform"), &output),
+            vec![
+                "terragrunt",
+                "output",
+                "-json",
+                "--terragrunt-tfpath=terraform",
+                "--terragrunt-non-interactive",
+            ]
+        );
+
+        let destroy = TerraformCommand::Destroy {
+            dir: PathBuf::from("live"),
+            vars: Default::default(),
+            var_files: vec![],
+            auto_approve: true,
+            targets: vec![],
+            lock_timeout: None,
+        };
+        let cmd = terragrunt.command_line(Path::new("terraform"), &destroy);
+        assert_eq!(
+            &cmd[..5],
+            [
+                "terragrunt",
+                "run-all",
+                "destroy",
+                "-input=false",
+                "-auto-approve"
+            ]
+        );
+        assert_eq!(
+            &cmd[cmd.len() - 2..],
+            [
+                "--terragrunt-include-dir=vpc",
+                "--terragrunt-include-dir=eks"
+            ]
+        );
+    }
+}
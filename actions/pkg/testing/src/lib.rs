@@ -0,0 +1,25 @@
+//! Helpers shared by the tests of the tool crates.
+//!
+//! Only added as a dev-dependency.
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Writes an executable shell script standing in for a tool and returns its path.
+///
+/// # Example
+///
+/// ```rust
+/// let dir = std::env::temp_dir().join("testing-script-example");
+/// std::fs::create_dir_all(&dir).unwrap();
+/// let aws = testing::script(&dir.join("aws"), "echo \"$@\"");
+///
+/// let output = std::process::Command::new(&aws).arg("s3").output().unwrap();
+/// assert_eq!(output.stdout, b"s3\n");
+/// ```
+pub fn script(path: &Path, body: &str) -> PathBuf {
+    fs::write(path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+    path.to_path_buf()
+}
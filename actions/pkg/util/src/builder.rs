@@ -0,0 +1,242 @@
+use crate::{GithubDrain, JsonDrain, LevelSwitch, LogFile, LogFormat, RotatingFile, SwitchFilter};
+use slog::{o, Drain, Level, Logger};
+use std::io;
+
+type BoxDrain = Box<dyn Drain<Ok = (), Err = slog::Never> + Send>;
+
+/// Stream the console log is written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogTarget {
+    Stdout,
+    Stderr,
+}
+
+/// Builds the root logger of an action.
+///
+/// By default the logger writes info records to standard error, in the
+/// format detected by [`LogFormat::detect`], with colors and timestamps.
+/// The `github` format writes to standard output, where the runner reads
+/// workflow commands from.
+///
+/// # Example
+///
+/// ```rust
+/// use util::LoggerBuilder;
+///
+/// let (logger, level) = LoggerBuilder::new()
+///     .with_level("debug")
+///     .with_format("json")
+///     .quiet()
+///     .build();
+/// slog::debug!(logger, "Not written anywhere");
+/// level.set(slog::Level::Info);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoggerBuilder {
+    level: Level,
+    format: LogFormat,
+    color: bool,
+    timestamps: bool,
+    target: Option<LogTarget>,
+    file: Option<LogFile>,
+    quiet: bool,
+}
+
+impl Default for LoggerBuilder {
+    fn default() -> Self {
+        Self {
+            level: Level::Info,
+            format: LogFormat::detect(),
+            color: true,
+            timestamps: true,
+            target: None,
+            file: None,
+            quiet: false,
+        }
+    }
+}
+
+impl LoggerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the console level: trace, debug, info, warn, error or critical.
+    ///
+    /// An invalid level is reported on standard error and info is used.
+    pub fn with_level(mut self, level: &str) -> Self {
+        self.level = parse_level(level);
+        self
+    }
+
+    /// Sets the console format: auto, plain, compact, json or github.
+    ///
+    /// An invalid format is reported on standard error and the format is detected.
+    pub fn with_format(mut self, format: &str) -> Self {
+        self.format = LogFormat::parse(format).unwrap_or_else(|| {
+            eprintln!("Invalid log format '{}', detecting it", format);
+            LogFormat::detect()
+        });
+        self
+    }
+
+    /// Colors the plain and compact formats; enabled by default.
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Writes timestamps in the plain, compact and json formats; enabled by default.
+    pub fn with_timestamps(mut self, timestamps: bool) -> Self {
+        self.timestamps = timestamps;
+        self
+    }
+
+    pub fn with_target(mut self, target: LogTarget) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Also writes records to `file`, with its own level.
+    pub fn with_file(mut self, file: Option<LogFile>) -> Self {
+        self.file = file;
+        self
+    }
+
+    /// Discards the console log, e.g. in tests; the file is still written.
+    pub fn quiet(mut self) -> Self {
+        self.quiet = true;
+        self
+    }
+
+    /// Builds the logger and the switch of its console level.
+    ///
+    /// The file gets plain, uncolored lines; when it cannot be opened, only
+    /// the console is logged to.
+    pub fn build(self) -> (Logger, LevelSwitch) {
+        let switch = LevelSwitch::new(self.level);
+        let console = SwitchFilter::new(self.console(), switch.clone()).fuse();
+
+        let drain: BoxDrain = match self.file.as_ref().and_then(file_drain) {
+            Some(file) => Box::new(slog::Duplicate::new(console, file).fuse()),
+            None => Box::new(console),
+        };
+        let drain = slog_async::Async::new(drain).build().fuse();
+        (Logger::root(drain, o!()), switch)
+    }
+
+    fn console(&self) -> BoxDrain {
+        if self.quiet {
+            return Box::new(slog::Discard);
+        }
+        let target = self.target.unwrap_or(match self.format {
+            LogFormat::Github => LogTarget::Stdout,
+            _ => LogTarget::Stderr,
+        });
+        let writer = || -> Box<dyn io::Write + Send> {
+            match target {
+                LogTarget::Stdout => Box::new(io::stdout()),
+                LogTarget::Stderr => Box::new(io::stderr()),
+            }
+        };
+        let mut decorator = slog_term::TermDecorator::new();
+        decorator = match target {
+            LogTarget::Stdout => decorator.stdout(),
+            LogTarget::Stderr => decorator.stderr(),
+        };
+        decorator = if self.color {
+            decorator.force_color()
+        } else {
+            decorator.force_plain()
+        };
+
+        match self.format {
+            LogFormat::Plain => {
+                let format = slog_term::FullFormat::new(decorator.build());
+                let format = if self.timestamps {
+                    format
+                } else {
+                    format.use_custom_timestamp(no_timestamp)
+                };
+                Box::new(format.build().fuse())
+            }
+            LogFormat::Compact => {
+                let format = slog_term::CompactFormat::new(decorator.build());
+                let format = if self.timestamps {
+                    format
+                } else {
+                    format.use_custom_timestamp(no_timestamp)
+                };
+                Box::new(format.build().fuse())
+            }
+            LogFormat::Json => {
+                let drain = JsonDrain::new(writer());
+                let drain = if self.timestamps {
+                    drain
+                } else {
+                    drain.without_timestamps()
+                };
+                Box::new(drain.fuse())
+            }
+            LogFormat::Github => Box::new(GithubDrain::new(writer()).fuse()),
+        }
+    }
+}
+
+fn no_timestamp(_: &mut dyn io::Write) -> io::Result<()> {
+    Ok(())
+}
+
+fn file_drain(file: &LogFile) -> Option<BoxDrain> {
+    match RotatingFile::open(&file.path, file.max_bytes, file.keep) {
+        Ok(out) => {
+            let decorator = slog_term::PlainSyncDecorator::new(out);
+            let drain = slog_term::FullFormat::new(decorator).build().fuse();
+            let drain = slog::LevelFilter::new(drain, parse_level(&file.level)).ignore_res();
+            Some(Box::new(drain))
+        }
+        Err(e) => {
+            eprintln!("Cannot open log file {:?}: {}", file.path, e);
+            None
+        }
+    }
+}
+
+fn parse_level(level: &str) -> Level {
+    match level.to_lowercase().as_str() {
+        "trace" => Level::Trace,
+        "debug" => Level::Debug,
+        "info" => Level::Info,
+        "warn" => Level::Warning,
+        "error" => Level::Error,
+        "critical" => Level::Critical,
+        _ => {
+            eprintln!("Invalid log level '{}', defaulting to 'Info'", level);
+            Level::Info
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_logger_writes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("action.log");
+        let (logger, switch) = LoggerBuilder::new()
+            .with_level("warn")
+            .with_file(Some(LogFile::new(&path)))
+            .quiet()
+            .build();
+        assert_eq!(switch.level(), Level::Warning);
+        slog::debug!(logger, "Planning"; "dir" => "infra");
+        slog::trace!(logger, "Not in the file");
+        drop(logger);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("Planning"));
+        assert!(!content.contains("Not in the file"));
+    }
+}
@@ -0,0 +1,90 @@
+use slog::{o, Logger, OwnedKV, Record, Serializer, KV};
+
+/// Key of the step of a chain, e.g. `2/4`.
+pub const STEP_KEY: &str = "step";
+/// Key of the command a record belongs to.
+pub const COMMAND_KEY: &str = "command";
+
+/// Keys and the runner environment variables they are read from.
+const RUN_FIELDS: &[(&str, &str)] = &[
+    ("run_id", "GITHUB_RUN_ID"),
+    ("run_attempt", "GITHUB_RUN_ATTEMPT"),
+    ("workflow", "GITHUB_WORKFLOW"),
+    ("job", "GITHUB_JOB"),
+];
+
+/// Key-values attached to every record of a logger, so records can be
+/// filtered by run, action, step or command downstream.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogContext {
+    fields: Vec<(&'static str, String)>,
+}
+
+impl LogContext {
+    /// Creates a context of the action named `action`.
+    pub fn new(action: &str) -> Self {
+        Self::default().with_field("action", action)
+    }
+
+    /// Creates a context of the action, with the workflow run from the runner environment.
+    pub fn from_env(action: &str) -> Self {
+        RUN_FIELDS.iter().fold(
+            Self::new(action),
+            |context, (key, var)| match std::env::var(var).ok().filter(|v| !v.is_empty()) {
+                Some(value) => context.with_field(key, value),
+                None => context,
+            },
+        )
+    }
+
+    pub fn with_field(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.fields.push((key, value.into()));
+        self
+    }
+
+    /// Returns a child of `logger` with the fields of the context.
+    pub fn logger(&self, logger: &Logger) -> Logger {
+        logger.new(OwnedKV(self.clone()))
+    }
+}
+
+impl KV for LogContext {
+    fn serialize(&self, _record: &Record, serializer: &mut dyn Serializer) -> slog::Result {
+        for (key, value) in &self.fields {
+            serializer.emit_str(key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns a child of `logger` for step `index` of `total` running `command`.
+pub fn step_logger(logger: &Logger, index: usize, total: usize, command: &str) -> Logger {
+    logger.new(o!(
+        STEP_KEY => format!("{}/{}", index + 1, total),
+        COMMAND_KEY => command.to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Buffer;
+    use crate::JsonDrain;
+    use slog::Drain;
+
+    #[test]
+    fn test_context_fields() {
+        std::env::set_var("GITHUB_RUN_ID", "4242");
+        let buffer = Buffer::default();
+        let root = Logger::root(JsonDrain::new(buffer.clone()).fuse(), o!());
+        let logger = LogContext::from_env("terraform").logger(&root);
+        slog::info!(step_logger(&logger, 1, 4, "plan"), "Step finished");
+
+        let line = buffer.contents();
+        let record: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(record["action"], "terraform");
+        assert_eq!(record["run_id"], "4242");
+        assert_eq!(record["step"], "2/4");
+        assert_eq!(record["command"], "plan");
+    }
+}
@@ -0,0 +1,135 @@
+use config::ConfigError;
+use executer::ExecuterError;
+use provider::ProviderError;
+use std::error::Error;
+use std::process::ExitCode;
+
+/// Result of an action run: the class of failure it finished with, if any.
+pub type ActionResult = Result<Option<Failure>, Box<dyn Error>>;
+
+/// Class of failure an action exits with, so workflows can branch on the
+/// exit code instead of the log.
+///
+/// | Class | Exit code |
+/// | --- | --- |
+/// | tool | 1 |
+/// | config | 2 |
+/// | validation | 3 |
+/// | provider | 4 |
+/// | timeout | 124 |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// A command of the tool failed.
+    Tool,
+    /// An input is missing, invalid or does not allow the run.
+    Config,
+    /// A check failed before anything was changed, e.g. `terraform validate`
+    /// or a saved plan that does not match its manifest.
+    Validation,
+    /// The credentials of the cloud provider are missing or were rejected.
+    Provider,
+    /// A command was killed after running longer than its timeout.
+    Timeout,
+}
+
+impl Failure {
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::Tool => 1,
+            Self::Config => 2,
+            Self::Validation => 3,
+            Self::Provider => 4,
+            // The exit code of timeout(1).
+            Self::Timeout => 124,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tool => "tool",
+            Self::Config => "config",
+            Self::Validation => "validation",
+            Self::Provider => "provider",
+            Self::Timeout => "timeout",
+        }
+    }
+
+    pub fn of_executer(error: &ExecuterError) -> Self {
+        match error {
+            ExecuterError::Timeout(_) => Self::Timeout,
+            ExecuterError::ValidationError(_) => Self::Validation,
+            ExecuterError::EnvironmentError(_) => Self::Config,
+            ExecuterError::ExecutionError(_)
+            | ExecuterError::StreamError(_)
+            | ExecuterError::IoError(_) => Self::Tool,
+        }
+    }
+
+    /// Classifies the errors of the crates all actions share; `None` for other errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use config::ConfigError;
+    /// use util::Failure;
+    ///
+    /// let error = ConfigError::RequiredValueMissing("ACTION_AWS_S3_BUCKET".to_string());
+    /// assert_eq!(Failure::of_shared(&error), Some(Failure::Config));
+    /// ```
+    pub fn of_shared(error: &(dyn Error + 'static)) -> Option<Self> {
+        if error.is::<ConfigError>() {
+            Some(Self::Config)
+        } else if error.is::<ProviderError>() {
+            Some(Self::Provider)
+        } else {
+            error.downcast_ref::<ExecuterError>().map(Self::of_executer)
+        }
+    }
+}
+
+impl From<Failure> for ExitCode {
+    fn from(failure: Failure) -> Self {
+        ExitCode::from(failure.code())
+    }
+}
+
+/// Returns the exit code of an action run, classifying the error it stopped
+/// with by `classify`.
+///
+/// The error is printed to standard error, as returning it from `main` would.
+pub fn exit_code(
+    result: ActionResult,
+    classify: impl Fn(&(dyn Error + 'static)) -> Failure,
+) -> ExitCode {
+    match result {
+        Ok(None) => ExitCode::SUCCESS,
+        Ok(Some(failure)) => failure.into(),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            classify(e.as_ref()).into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_errors_are_classified() {
+        let timeout: Box<dyn Error> = Box::new(ExecuterError::Timeout(30));
+        let provider: Box<dyn Error> = Box::new(ProviderError::ProviderNotFound);
+        let io: Box<dyn Error> = Box::new(std::io::Error::other("disk full"));
+
+        assert_eq!(Failure::of_shared(timeout.as_ref()), Some(Failure::Timeout));
+        assert_eq!(
+            Failure::of_shared(provider.as_ref()),
+            Some(Failure::Provider)
+        );
+        assert_eq!(Failure::of_shared(io.as_ref()), None);
+        assert_eq!(
+            exit_code(Ok(Some(Failure::Validation)), |_| Failure::Tool),
+            ExitCode::from(3)
+        );
+    }
+}
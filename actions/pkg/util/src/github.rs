@@ -0,0 +1,149 @@
+use executer::escape_data;
+use slog::{Drain, Key, Level, Logger, OwnedKVList, Record, Serializer, KV};
+use std::fmt::{self, Write as _};
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+/// Environment variable the GitHub Actions runner sets to `true`.
+pub const ENV_GITHUB_ACTIONS: &str = "GITHUB_ACTIONS";
+/// Key of records rendered as `::notice::` annotations.
+pub const NOTICE_KEY: &str = "notice";
+/// Key of the records that open a log group; see [`group`].
+const GROUP_KEY: &str = "log_group";
+/// Key of the records that close a log group.
+const END_GROUP_KEY: &str = "log_endgroup";
+
+/// Returns whether the process runs in GitHub Actions.
+pub fn is_github_actions() -> bool {
+    std::env::var(ENV_GITHUB_ACTIONS).is_ok_and(|v| v == "true")
+}
+
+/// Renders records as workflow commands, so the runner turns them into
+/// annotations and collapsible groups.
+///
+/// Errors become `::error::`, warnings `::warning::` and debug and trace
+/// records `::debug::`, which the runner only shows with debug logging
+/// enabled. Info records are plain lines, unless they have the
+/// [`NOTICE_KEY`] key. Key-values are appended as `key=value`.
+pub struct GithubDrain<W: Write> {
+    out: Mutex<W>,
+}
+
+impl<W: Write> GithubDrain<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out: Mutex::new(out),
+        }
+    }
+}
+
+impl<W: Write> Drain for GithubDrain<W> {
+    type Ok = ();
+    type Err = io::Error;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> io::Result<()> {
+        let mut fields = Fields::default();
+        record.kv().serialize(record, &mut fields)?;
+        values.serialize(record, &mut fields)?;
+
+        let message = format!("{}", record.msg());
+        let line = if let Some(title) = fields.group.take() {
+            format!("::group::{}", escape_data(&title))
+        } else if fields.end_group {
+            "::endgroup::".to_string()
+        } else {
+            let text = format!("{}{}", message, fields.text);
+            match record.level() {
+                Level::Critical | Level::Error => format!("::error::{}", escape_data(&text)),
+                Level::Warning => format!("::warning::{}", escape_data(&text)),
+                Level::Info if fields.notice => format!("::notice::{}", escape_data(&text)),
+                Level::Info => text,
+                Level::Debug | Level::Trace => format!("::debug::{}", escape_data(&text)),
+            }
+        };
+
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        writeln!(out, "{}", line)?;
+        out.flush()
+    }
+}
+
+#[derive(Default)]
+struct Fields {
+    text: String,
+    notice: bool,
+    group: Option<String>,
+    end_group: bool,
+}
+
+impl Serializer for Fields {
+    fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments) -> slog::Result {
+        match key {
+            NOTICE_KEY => self.notice = true,
+            GROUP_KEY => self.group = Some(val.to_string()),
+            END_GROUP_KEY => self.end_group = true,
+            _ => {
+                let _ = write!(self.text, " {}={}", key, val);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An open log group, closed when dropped.
+#[must_use = "the group is closed when dropped"]
+pub struct LogGroup<'a> {
+    logger: &'a Logger,
+    title: String,
+}
+
+/// Opens a collapsible group titled `title` in the workflow log.
+///
+/// Records logged until the returned guard is dropped are shown in the
+/// group. With other drains the title is logged as an info record.
+pub fn group<'a>(logger: &'a Logger, title: &str) -> LogGroup<'a> {
+    slog::info!(logger, "{}", title; GROUP_KEY => title);
+    LogGroup {
+        logger,
+        title: title.to_string(),
+    }
+}
+
+impl Drop for LogGroup<'_> {
+    fn drop(&mut self) {
+        // Logged at info level, so that the level filter cannot leave the group open.
+        slog::info!(self.logger, "End of {}", self.title; END_GROUP_KEY => true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Buffer;
+    use slog::o;
+
+    #[test]
+    fn test_workflow_commands() {
+        let buffer = Buffer::default();
+        let logger = Logger::root(GithubDrain::new(buffer.clone()).fuse(), o!());
+        {
+            let _group = group(&logger, "terraform init");
+            slog::info!(logger, "Initializing");
+            slog::info!(logger, "Plan has changes"; NOTICE_KEY => true);
+            slog::warn!(logger, "Lock held"; "attempt" => 2);
+            slog::debug!(logger, "Command");
+        }
+        slog::error!(logger, "Failed\n100% broken");
+
+        assert_eq!(
+            buffer.contents(),
+            "::group::terraform init\n\
+             Initializing\n\
+             ::notice::Plan has changes\n\
+             ::warning::Lock held attempt=2\n\
+             ::debug::Command\n\
+             ::endgroup::\n\
+             ::error::Failed%0A100%25 broken\n"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use serde_json::{Map, Number, Value};
+use slog::{Drain, Key, Level, OwnedKVList, Record, Serializer, KV};
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+/// Writes every record as one JSON object per line.
+///
+/// Records have `ts` (RFC 3339, UTC), `level`, `msg` and `module` fields,
+/// followed by the key-values of the record and the logger. Keys of the
+/// record take precedence over logger keys of the same name.
+pub struct JsonDrain<W: Write> {
+    out: Mutex<W>,
+    timestamps: bool,
+}
+
+impl<W: Write> JsonDrain<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out: Mutex::new(out),
+            timestamps: true,
+        }
+    }
+
+    /// Leaves out the `ts` field, e.g. when the collector adds its own.
+    pub fn without_timestamps(mut self) -> Self {
+        self.timestamps = false;
+        self
+    }
+}
+
+impl<W: Write> Drain for JsonDrain<W> {
+    type Ok = ();
+    type Err = io::Error;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> io::Result<()> {
+        let mut fields = Fields(Map::new());
+        if self.timestamps {
+            fields.insert("ts", chrono::Utc::now().to_rfc3339().into());
+        }
+        fields.insert("level", level_name(record.level()).into());
+        fields.insert("msg", record.msg().to_string().into());
+        fields.insert("module", record.module().into());
+        record.kv().serialize(record, &mut fields)?;
+        values.serialize(record, &mut fields)?;
+
+        let mut line = serde_json::to_vec(&Value::Object(fields.0))?;
+        line.push(b'\n');
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        out.write_all(&line)?;
+        out.flush()
+    }
+}
+
+/// Returns the name of a level as [`LoggerBuilder::with_level`](crate::LoggerBuilder::with_level) accepts it.
+fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::Critical => "critical",
+        Level::Error => "error",
+        Level::Warning => "warn",
+        Level::Info => "info",
+        Level::Debug => "debug",
+        Level::Trace => "trace",
+    }
+}
+
+/// Collects key-values, keeping the first value of a key.
+struct Fields(Map<String, Value>);
+
+impl Fields {
+    fn insert(&mut self, key: &str, value: Value) {
+        self.0.entry(key).or_insert(value);
+    }
+}
+
+/// Implements the integer methods of `Serializer`, which otherwise render integers as strings.
+macro_rules! emit_numbers {
+    ($($name:ident: $ty:ty),*) => {
+        $(
+            fn $name(&mut self, key: Key, val: $ty) -> slog::Result {
+                self.insert(key, val.into());
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl Serializer for Fields {
+    fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments) -> slog::Result {
+        self.insert(key, val.to_string().into());
+        Ok(())
+    }
+
+    fn emit_str(&mut self, key: Key, val: &str) -> slog::Result {
+        self.insert(key, val.into());
+        Ok(())
+    }
+
+    fn emit_bool(&mut self, key: Key, val: bool) -> slog::Result {
+        self.insert(key, val.into());
+        Ok(())
+    }
+
+    emit_numbers!(
+        emit_i8: i8,
+        emit_i16: i16,
+        emit_i32: i32,
+        emit_i64: i64,
+        emit_isize: isize,
+        emit_u8: u8,
+        emit_u16: u16,
+        emit_u32: u32,
+        emit_u64: u64,
+        emit_usize: usize
+    );
+
+    fn emit_f32(&mut self, key: Key, val: f32) -> slog::Result {
+        self.emit_f64(key, val.into())
+    }
+
+    fn emit_f64(&mut self, key: Key, val: f64) -> slog::Result {
+        let value = Number::from_f64(val).map_or(Value::Null, Value::Number);
+        self.insert(key, value);
+        Ok(())
+    }
+
+    fn emit_unit(&mut self, key: Key) -> slog::Result {
+        self.insert(key, Value::Null);
+        Ok(())
+    }
+
+    fn emit_none(&mut self, key: Key) -> slog::Result {
+        self.insert(key, Value::Null);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Buffer;
+    use slog::{o, Logger};
+
+    #[test]
+    fn test_json_record() {
+        let buffer = Buffer::default();
+        let logger = Logger::root(
+            JsonDrain::new(buffer.clone()).fuse(),
+            o!("action" => "terraform", "step" => 1),
+        );
+        slog::warn!(logger, "Retrying {}", "init"; "attempt" => 2, "step" => 3, "locked" => true);
+
+        let line = buffer.contents();
+        assert!(line.ends_with('\n'));
+        let record: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(record["level"], "warn");
+        assert_eq!(record["msg"], "Retrying init");
+        assert_eq!(record["attempt"], 2);
+        assert_eq!(record["step"], 3);
+        assert_eq!(record["locked"], true);
+        assert_eq!(record["action"], "terraform");
+        assert!(record["ts"].as_str().unwrap().contains('T'));
+    }
+}
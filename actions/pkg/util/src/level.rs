@@ -0,0 +1,89 @@
+use slog::{Drain, Level, OwnedKVList, Record};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A log level that can be changed while the logger runs; clones share the level.
+#[derive(Debug, Clone)]
+pub struct LevelSwitch {
+    level: Arc<AtomicUsize>,
+}
+
+impl LevelSwitch {
+    pub fn new(level: Level) -> Self {
+        Self {
+            level: Arc::new(AtomicUsize::new(level.as_usize())),
+        }
+    }
+
+    pub fn level(&self) -> Level {
+        Level::from_usize(self.level.load(Ordering::Relaxed)).unwrap_or(Level::Info)
+    }
+
+    pub fn set(&self, level: Level) {
+        self.level.store(level.as_usize(), Ordering::Relaxed);
+    }
+
+    /// Sets `level` if it is more verbose than the current level, e.g. to
+    /// switch to debug after a failure without silencing a trace log.
+    pub fn raise(&self, level: Level) {
+        self.level.fetch_max(level.as_usize(), Ordering::Relaxed);
+    }
+}
+
+/// Filters records by the current level of a [`LevelSwitch`].
+pub struct SwitchFilter<D> {
+    drain: D,
+    switch: LevelSwitch,
+}
+
+impl<D> SwitchFilter<D> {
+    pub fn new(drain: D, switch: LevelSwitch) -> Self {
+        Self { drain, switch }
+    }
+}
+
+impl<D: Drain> Drain for SwitchFilter<D> {
+    type Ok = Option<D::Ok>;
+    type Err = D::Err;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        if self.is_enabled(record.level()) {
+            self.drain.log(record, values).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn is_enabled(&self, level: Level) -> bool {
+        level.is_at_least(self.switch.level()) && self.drain.is_enabled(level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Buffer;
+    use crate::JsonDrain;
+    use slog::{o, Logger};
+
+    #[test]
+    fn test_switch_level() {
+        let buffer = Buffer::default();
+        let switch = LevelSwitch::new(Level::Info);
+        let logger = Logger::root(
+            SwitchFilter::new(JsonDrain::new(buffer.clone()), switch.clone()).fuse(),
+            o!(),
+        );
+        slog::debug!(logger, "hidden");
+        switch.raise(Level::Debug);
+        slog::debug!(logger, "shown");
+        switch.raise(Level::Warning);
+        assert_eq!(switch.level(), Level::Debug);
+        switch.set(Level::Error);
+        slog::warn!(logger, "hidden");
+
+        let contents = buffer.contents();
+        assert_eq!(contents.lines().count(), 1);
+        assert!(contents.contains("shown"));
+    }
+}
@@ -1,22 +1,124 @@
-use slog::{o, Drain, Level, Logger};
-
-pub fn init_logger(level: &str) -> Logger {
-    let log_level = match level.to_lowercase().as_str() {
-        "trace" => Level::Trace,
-        "debug" => Level::Debug,
-        "info" => Level::Info,
-        "warn" => Level::Warning,
-        "error" => Level::Error,
-        "critical" => Level::Critical,
-        _ => {
-            eprintln!("Invalid log level '{}', defaulting to 'Info'", level);
-            Level::Info
+mod builder;
+mod context;
+mod exit;
+mod file;
+mod github;
+mod json;
+mod level;
+mod mask;
+mod outputs;
+mod summary;
+#[cfg(feature = "tracing")]
+mod trace;
+
+pub use builder::{LogTarget, LoggerBuilder};
+pub use context::{step_logger, LogContext, COMMAND_KEY, STEP_KEY};
+pub use exit::{exit_code, ActionResult, Failure};
+pub use file::RotatingFile;
+
+pub use github::{group, is_github_actions, GithubDrain, LogGroup, ENV_GITHUB_ACTIONS, NOTICE_KEY};
+pub use json::JsonDrain;
+pub use level::{LevelSwitch, SwitchFilter};
+pub use mask::{masked, MaskingDrain};
+pub use outputs::{write_step_summary, Outputs, ENV_GITHUB_OUTPUT, ENV_GITHUB_STEP_SUMMARY};
+pub use summary::{workflow_run_url, RunSummary};
+#[cfg(feature = "tracing")]
+pub use trace::{masking_layer, MaskingFormat, MaskingLayer};
+
+use std::path::PathBuf;
+
+/// A file all records at or above its level are written to, besides the console.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFile {
+    pub path: PathBuf,
+    pub level: String,
+    /// Size at which the file is rotated.
+    pub max_bytes: u64,
+    /// Number of rotated files kept.
+    pub keep: usize,
+}
+
+impl LogFile {
+    /// Creates a debug log file rotated at 10 MiB, keeping 3 rotated files.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            level: "debug".to_string(),
+            max_bytes: 10 * 1024 * 1024,
+            keep: 3,
         }
-    };
-    let decorator = slog_term::TermDecorator::new().force_color().build();
-    let drain = slog_term::CompactFormat::new(decorator).build().fuse();
+    }
 
-    let drain = slog::LevelFilter::new(drain, log_level).fuse();
-    let drain = slog_async::Async::new(drain).build().fuse();
-    Logger::root(drain, o!())
+    pub fn with_level(mut self, level: &str) -> Self {
+        self.level = level.to_string();
+        self
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+}
+
+/// How log records are rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One line per record with all key-values.
+    Plain,
+    /// Lines grouped by logger key-values, for reading in a terminal.
+    #[default]
+    Compact,
+    /// One JSON object per line, for log aggregation.
+    Json,
+    /// Workflow commands, so the GitHub Actions UI shows annotations and groups.
+    Github,
+}
+
+impl LogFormat {
+    /// Parses a format name, ignoring case; `auto` detects the format.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "auto" => Some(Self::detect()),
+            "plain" => Some(Self::Plain),
+            "compact" => Some(Self::Compact),
+            "json" => Some(Self::Json),
+            "github" => Some(Self::Github),
+            _ => None,
+        }
+    }
+
+    /// Returns `Github` when running in GitHub Actions and `Compact` otherwise.
+    pub fn detect() -> Self {
+        if is_github_actions() {
+            Self::Github
+        } else {
+            Self::Compact
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    /// A writer the test can read back.
+    #[derive(Clone, Default)]
+    pub(crate) struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Buffer {
+        pub(crate) fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,191 @@
+use processor::{Processor, ProcessorCollection};
+use slog::{
+    o, BorrowedKV, Drain, Key, Logger, OwnedKV, OwnedKVList, Record, RecordStatic, Serializer, KV,
+};
+use std::fmt;
+
+/// Masks the message and string key-values of every record before passing
+/// it to the wrapped drain.
+///
+/// Numbers and booleans are passed through unchanged.
+pub struct MaskingDrain<D> {
+    drain: D,
+    processor: ProcessorCollection,
+}
+
+impl<D: Drain> MaskingDrain<D> {
+    pub fn new(drain: D, processor: ProcessorCollection) -> Self {
+        Self { drain, processor }
+    }
+}
+
+/// Returns a logger that masks every record with `processor` before logging it to `logger`.
+///
+/// # Example
+///
+/// ```rust
+/// use processor::{MaskerEqual, ProcessorCollection, ProcessorItem};
+/// use slog::{o, Discard, Logger};
+///
+/// let logger = Logger::root(Discard, o!());
+/// let processor = ProcessorCollection::new(vec![ProcessorItem::Equal(MaskerEqual::new(
+///     vec!["s3cr3t"],
+///     "****",
+/// ))]);
+/// let logger = util::masked(&logger, processor);
+/// slog::info!(logger, "Token s3cr3t"; "token" => "s3cr3t");
+/// ```
+pub fn masked(logger: &Logger, processor: ProcessorCollection) -> Logger {
+    Logger::root(MaskingDrain::new(logger.clone(), processor).fuse(), o!())
+}
+
+impl<D: Drain> Drain for MaskingDrain<D> {
+    type Ok = D::Ok;
+    type Err = D::Err;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<D::Ok, D::Err> {
+        let msg = self.processor.process(&record.msg().to_string());
+        // Collecting into memory cannot fail.
+        let mut record_kv = Masked::new(&self.processor);
+        let _ = record.kv().serialize(record, &mut record_kv);
+        let mut logger_kv = Masked::new(&self.processor);
+        let _ = values.serialize(record, &mut logger_kv);
+
+        let rstatic = RecordStatic {
+            location: record.location(),
+            tag: record.tag(),
+            level: record.level(),
+        };
+        let values = OwnedKVList::from(OwnedKV(logger_kv.values));
+        self.drain.log(
+            &Record::new(
+                &rstatic,
+                &format_args!("{}", msg),
+                BorrowedKV(&record_kv.values),
+            ),
+            &values,
+        )
+    }
+}
+
+#[derive(Clone)]
+enum Value {
+    Str(String),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    Bool(bool),
+    Unit,
+    None,
+}
+
+/// Key-values in the order they were serialized.
+#[derive(Clone)]
+struct Values(Vec<(Key, Value)>);
+
+impl KV for Values {
+    fn serialize(&self, _record: &Record, serializer: &mut dyn Serializer) -> slog::Result {
+        for (key, value) in &self.0 {
+            match value {
+                Value::Str(v) => serializer.emit_str(key, v)?,
+                Value::I64(v) => serializer.emit_i64(key, *v)?,
+                Value::U64(v) => serializer.emit_u64(key, *v)?,
+                Value::F64(v) => serializer.emit_f64(key, *v)?,
+                Value::Bool(v) => serializer.emit_bool(key, *v)?,
+                Value::Unit => serializer.emit_unit(key)?,
+                Value::None => serializer.emit_none(key)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Masked<'a> {
+    processor: &'a ProcessorCollection,
+    values: Values,
+}
+
+impl<'a> Masked<'a> {
+    fn new(processor: &'a ProcessorCollection) -> Self {
+        Self {
+            processor,
+            values: Values(Vec::new()),
+        }
+    }
+
+    fn push(&mut self, key: Key, value: Value) -> slog::Result {
+        self.values.0.push((key, value));
+        Ok(())
+    }
+}
+
+/// Implements the integer methods of `Serializer`, keeping integers typed.
+macro_rules! emit_integers {
+    ($variant:ident: $($name:ident: $ty:ty),*) => {
+        $(
+            fn $name(&mut self, key: Key, val: $ty) -> slog::Result {
+                self.push(key, Value::$variant(val.into()))
+            }
+        )*
+    };
+}
+
+impl Serializer for Masked<'_> {
+    fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments) -> slog::Result {
+        let masked = self.processor.process(&val.to_string());
+        self.push(key, Value::Str(masked))
+    }
+
+    fn emit_str(&mut self, key: Key, val: &str) -> slog::Result {
+        let masked = self.processor.process(val);
+        self.push(key, Value::Str(masked))
+    }
+
+    emit_integers!(I64: emit_i8: i8, emit_i16: i16, emit_i32: i32, emit_i64: i64);
+    emit_integers!(U64: emit_u8: u8, emit_u16: u16, emit_u32: u32, emit_u64: u64);
+
+    fn emit_f64(&mut self, key: Key, val: f64) -> slog::Result {
+        self.push(key, Value::F64(val))
+    }
+
+    fn emit_bool(&mut self, key: Key, val: bool) -> slog::Result {
+        self.push(key, Value::Bool(val))
+    }
+
+    fn emit_unit(&mut self, key: Key) -> slog::Result {
+        self.push(key, Value::Unit)
+    }
+
+    fn emit_none(&mut self, key: Key) -> slog::Result {
+        self.push(key, Value::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Buffer;
+    use crate::JsonDrain;
+    use processor::{MaskerEqual, ProcessorItem};
+
+    #[test]
+    fn test_masked_logger() {
+        let buffer = Buffer::default();
+        let logger = Logger::root(JsonDrain::new(buffer.clone()).fuse(), o!("action" => "aws"));
+        let processor = ProcessorCollection::new(vec![ProcessorItem::Equal(MaskerEqual::new(
+            vec!["s3cr3t"],
+            "****",
+        ))]);
+        let logger = masked(&logger, processor).new(o!("user" => "admin:s3cr3t"));
+        slog::info!(logger, "Login with {}", "s3cr3t"; "token" => "s3cr3t", "attempt" => 2);
+
+        let line = buffer.contents();
+        assert!(!line.contains("s3cr3t"));
+        let record: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(record["msg"], "Login with ****");
+        assert_eq!(record["token"], "****");
+        assert_eq!(record["user"], "admin:****");
+        assert_eq!(record["attempt"], 2);
+        assert_eq!(record["action"], "aws");
+    }
+}
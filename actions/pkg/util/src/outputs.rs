@@ -0,0 +1,172 @@
+use processor::{Processor, ProcessorCollection};
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// File the step outputs are appended to, set by the GitHub Actions runner.
+pub const ENV_GITHUB_OUTPUT: &str = "GITHUB_OUTPUT";
+/// File the job summary markdown is appended to, set by the GitHub Actions runner.
+pub const ENV_GITHUB_STEP_SUMMARY: &str = "GITHUB_STEP_SUMMARY";
+/// Delimiter of multiline values, extended until no line of the value equals it.
+const DELIMITER: &str = "ACTION_OUTPUT_EOF";
+
+/// Step outputs collected while the action runs.
+///
+/// With a processor, values are masked when they are written, so secrets
+/// known to the maskers never reach the `GITHUB_OUTPUT` file.
+#[derive(Default)]
+pub struct Outputs {
+    values: BTreeMap<String, String>,
+    processor: Option<ProcessorCollection>,
+}
+
+impl Outputs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Masks every value with `processor` before it is written.
+    pub fn with_processor(mut self, processor: ProcessorCollection) -> Self {
+        self.processor = Some(processor);
+        self
+    }
+
+    /// Sets an output, replacing an earlier value of the same name.
+    pub fn set(&mut self, name: &str, value: impl ToString) {
+        self.values.insert(name.to_string(), value.to_string());
+    }
+
+    /// Returns the unmasked value of an output.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn masked(&self, value: &str) -> String {
+        match &self.processor {
+            Some(processor) => value
+                .split('\n')
+                .map(|line| processor.process(line))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            None => value.to_string(),
+        }
+    }
+
+    /// Renders the outputs in the `GITHUB_OUTPUT` file format.
+    ///
+    /// Multiline values use the `name<<DELIMITER` syntax.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use processor::{MaskerEqual, ProcessorCollection, ProcessorItem};
+    /// use util::Outputs;
+    ///
+    /// let processor = ProcessorCollection::new(vec![ProcessorItem::Equal(MaskerEqual::new(
+    ///     vec!["s3cr3t"],
+    ///     "****",
+    /// ))]);
+    /// let mut outputs = Outputs::new().with_processor(processor);
+    /// outputs.set("url", "https://example.com/?token=s3cr3t");
+    /// outputs.set("notes", "first\nsecond");
+    /// assert_eq!(
+    ///     outputs.render(),
+    ///     "notes<<ACTION_OUTPUT_EOF\nfirst\nsecond\nACTION_OUTPUT_EOF\n\
+    ///      url=https://example.com/?token=****\n"
+    /// );
+    /// ```
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for (name, value) in &self.values {
+            let value = self.masked(value);
+            if value.contains('\n') || value.contains('\r') {
+                let mut delimiter = DELIMITER.to_string();
+                while value.lines().any(|line| line == delimiter) {
+                    delimiter.push('_');
+                }
+                out.push_str(&format!(
+                    "{}<<{}\n{}\n{}\n",
+                    name, delimiter, value, delimiter
+                ));
+            } else {
+                out.push_str(&format!("{}={}\n", name, value));
+            }
+        }
+        out
+    }
+
+    /// Appends the outputs to a file.
+    pub fn write_to(&self, path: &Path) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(self.render().as_bytes())
+    }
+
+    /// Appends the outputs to the `GITHUB_OUTPUT` file.
+    ///
+    /// Returns `Ok(false)` without writing when not running in GitHub Actions.
+    pub fn write(&self) -> io::Result<bool> {
+        match std::env::var_os(ENV_GITHUB_OUTPUT) {
+            Some(path) if !path.is_empty() && !self.is_empty() => {
+                self.write_to(Path::new(&path))?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+/// Appends markdown to the `GITHUB_STEP_SUMMARY` file.
+///
+/// Returns `Ok(false)` without writing when not running in GitHub Actions.
+pub fn write_step_summary(markdown: &str) -> io::Result<bool> {
+    match std::env::var_os(ENV_GITHUB_STEP_SUMMARY) {
+        Some(path) if !path.is_empty() => {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            file.write_all(markdown.as_bytes())?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_multiline() {
+        let mut outputs = Outputs::new();
+        outputs.set("port", 8080);
+        outputs.set("notes", "a\nACTION_OUTPUT_EOF\nb");
+        assert_eq!(
+            outputs.render(),
+            "notes<<ACTION_OUTPUT_EOF_\na\nACTION_OUTPUT_EOF\nb\nACTION_OUTPUT_EOF_\nport=8080\n"
+        );
+        assert_eq!(outputs.get("port"), Some("8080"));
+    }
+
+    #[test]
+    fn test_write_to_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("output");
+        std::fs::write(&path, "earlier=1\n").unwrap();
+
+        let mut outputs = Outputs::new();
+        outputs.set("bucket", "site");
+        outputs.write_to(&path).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "earlier=1\nbucket=site\n"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use crate::outputs::write_step_summary;
+use executer::{Metrics, MASKED_LINES};
+use processor::{Processor, ProcessorCollection};
+
+/// Environment variables the workflow run link is built from.
+const ENV_GITHUB_SERVER_URL: &str = "GITHUB_SERVER_URL";
+const ENV_GITHUB_REPOSITORY: &str = "GITHUB_REPOSITORY";
+const ENV_GITHUB_RUN_ID: &str = "GITHUB_RUN_ID";
+const ENV_GITHUB_RUN_ATTEMPT: &str = "GITHUB_RUN_ATTEMPT";
+
+/// Report of an action run appended to the job summary when the run ends.
+///
+/// Lists the commands in the order they ran with their exit codes and
+/// durations, the counters of the run, how many output lines had secrets
+/// masked, and links.
+pub struct RunSummary {
+    title: String,
+    metrics: Metrics,
+    code: Option<i32>,
+    error: Option<String>,
+    dry_run: bool,
+    links: Vec<(String, String)>,
+    processor: Option<ProcessorCollection>,
+}
+
+impl RunSummary {
+    /// Creates the report of the run titled `title`, e.g. `helm upgrade`.
+    pub fn new(title: impl Into<String>, metrics: Metrics) -> Self {
+        Self {
+            title: title.into(),
+            metrics,
+            code: None,
+            error: None,
+            dry_run: false,
+            links: Vec::new(),
+            processor: None,
+        }
+    }
+
+    /// Sets the status the action finished with.
+    pub fn with_code(mut self, code: i32) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Sets the error the action stopped with.
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.error = Some(error.into());
+        self
+    }
+
+    /// Sets the status the action finished with, or the error it stopped with.
+    pub fn with_outcome<E: std::fmt::Display>(self, outcome: &Result<i32, E>) -> Self {
+        match outcome {
+            Ok(code) => self.with_code(*code),
+            Err(e) => self.with_error(e.to_string()),
+        }
+    }
+
+    /// Marks the run as a dry run, which changed nothing.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn with_link(mut self, title: impl Into<String>, url: impl Into<String>) -> Self {
+        self.links.push((title.into(), url.into()));
+        self
+    }
+
+    /// Links the workflow run, when the runner environment describes one.
+    pub fn with_workflow_run(self) -> Self {
+        match workflow_run_url() {
+            Some(url) => self.with_link("Workflow run", url),
+            None => self,
+        }
+    }
+
+    /// Masks the report with `processor` before it is written.
+    pub fn with_processor(mut self, processor: ProcessorCollection) -> Self {
+        self.processor = Some(processor);
+        self
+    }
+
+    /// Renders the report as markdown.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use executer::{Metrics, MASKED_LINES};
+    /// use std::time::Duration;
+    /// use util::RunSummary;
+    ///
+    /// let metrics = Metrics::new();
+    /// metrics.record_run("helm upgrade", Duration::from_millis(2500), 0);
+    /// metrics.add(MASKED_LINES, 2);
+    /// let summary = RunSummary::new("helm upgrade", metrics)
+    ///     .with_code(0)
+    ///     .with_link("Chart", "https://example.com/chart");
+    /// assert_eq!(
+    ///     summary.markdown(),
+    ///     "### helm upgrade: succeeded\n\n\
+    ///      | Command | Exit code | Duration |\n\
+    ///      | --- | --- | --- |\n\
+    ///      | `helm upgrade` | 0 | 2.50s |\n\n\
+    ///      Secrets were masked in 2 output lines.\n\n\
+    ///      [Chart](https://example.com/chart)\n"
+    /// );
+    /// ```
+    pub fn markdown(&self) -> String {
+        let status = match (&self.error, self.code) {
+            (Some(_), _) => "stopped with an error".to_string(),
+            (None, Some(0)) => "succeeded".to_string(),
+            (None, Some(code)) => format!("failed with status {}", code),
+            (None, None) => "finished".to_string(),
+        };
+        let mut out = if self.dry_run {
+            format!(
+                "### {} (dry run): {}\n\nThis was a dry run: nothing was changed.\n",
+                self.title, status
+            )
+        } else {
+            format!("### {}: {}\n", self.title, status)
+        };
+
+        if let Some(error) = &self.error {
+            out.push_str(&format!("\n> {}\n", error.replace('\n', "\n> ")));
+        }
+
+        let runs = self.metrics.runs();
+        if !runs.is_empty() {
+            out.push_str("\n| Command | Exit code | Duration |\n| --- | --- | --- |\n");
+            for run in &runs {
+                out.push_str(&format!(
+                    "| `{}` | {} | {:.2}s |\n",
+                    run.command,
+                    run.code,
+                    run.duration.as_secs_f64()
+                ));
+            }
+        }
+
+        let mut counters = self.metrics.counters();
+        let masked = counters.remove(MASKED_LINES).unwrap_or(0);
+        if !counters.is_empty() {
+            out.push_str("\n| Counter | Value |\n| --- | --- |\n");
+            for (name, value) in &counters {
+                out.push_str(&format!("| {} | {} |\n", name, value));
+            }
+        }
+        out.push_str(&match masked {
+            0 => "\nNo secrets were masked in the output.\n".to_string(),
+            1 => "\nSecrets were masked in 1 output line.\n".to_string(),
+            n => format!("\nSecrets were masked in {} output lines.\n", n),
+        });
+
+        if !self.links.is_empty() {
+            let links: Vec<_> = self
+                .links
+                .iter()
+                .map(|(title, url)| format!("[{}]({})", title, url))
+                .collect();
+            out.push_str(&format!("\n{}\n", links.join(" · ")));
+        }
+
+        match &self.processor {
+            Some(processor) => processor.process(&out),
+            None => out,
+        }
+    }
+
+    /// Logs the metrics of the run and appends the report to the job summary.
+    pub fn report(&self, logger: &slog::Logger) {
+        for line in self.metrics.lines() {
+            slog::info!(logger, "Metrics: {}", line);
+        }
+        match write_step_summary(&self.markdown()) {
+            Ok(true) => slog::debug!(logger, "Run summary was added to the job summary"),
+            Ok(false) => {}
+            Err(e) => slog::warn!(logger, "Failed to write job summary"; "error" => e.to_string()),
+        }
+    }
+}
+
+/// Returns the URL of the workflow run from the runner environment.
+pub fn workflow_run_url() -> Option<String> {
+    let var = |name| std::env::var(name).ok().filter(|v| !v.is_empty());
+    let mut url = format!(
+        "{}/{}/actions/runs/{}",
+        var(ENV_GITHUB_SERVER_URL)?.trim_end_matches('/'),
+        var(ENV_GITHUB_REPOSITORY)?,
+        var(ENV_GITHUB_RUN_ID)?
+    );
+    if let Some(attempt) = var(ENV_GITHUB_RUN_ATTEMPT) {
+        url.push_str(&format!("/attempts/{}", attempt));
+    }
+    Some(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use processor::{MaskerEqual, ProcessorItem};
+    use std::time::Duration;
+
+    #[test]
+    fn test_failed_run_is_masked() {
+        let metrics = Metrics::new();
+        metrics.record_run("aws s3", Duration::from_secs(1), 0);
+        metrics.record_run("aws cloudfront", Duration::from_secs(3), 255);
+        metrics.increment("invalidation retries");
+        let processor = ProcessorCollection::new(vec![ProcessorItem::Equal(MaskerEqual::new(
+            vec!["s3cr3t"],
+            "****",
+        ))]);
+
+        let markdown = RunSummary::new("aws s3-sync", metrics)
+            .with_code(255)
+            .with_dry_run(true)
+            .with_link("Object", "https://example.com/?signature=s3cr3t")
+            .with_processor(processor)
+            .markdown();
+        assert_eq!(
+            markdown,
+            "### aws s3-sync (dry run): failed with status 255\n\n\
+             This was a dry run: nothing was changed.\n\n\
+             | Command | Exit code | Duration |\n\
+             | --- | --- | --- |\n\
+             | `aws s3` | 0 | 1.00s |\n\
+             | `aws cloudfront` | 255 | 3.00s |\n\n\
+             | Counter | Value |\n\
+             | --- | --- |\n\
+             | invalidation retries | 1 |\n\n\
+             No secrets were masked in the output.\n\n\
+             [Object](https://example.com/?signature=****)\n"
+        );
+    }
+
+    #[test]
+    fn test_error_is_reported() {
+        let metrics = Metrics::new();
+        metrics.record_run("terraform plan", Duration::from_secs(30), 124);
+        let outcome: Result<i32, _> = Err("Command timed out after 30 seconds");
+
+        let markdown = RunSummary::new("terraform plan", metrics)
+            .with_outcome(&outcome)
+            .markdown();
+        assert_eq!(
+            markdown,
+            "### terraform plan: stopped with an error\n\n\
+             > Command timed out after 30 seconds\n\n\
+             | Command | Exit code | Duration |\n\
+             | --- | --- | --- |\n\
+             | `terraform plan` | 124 | 30.00s |\n\n\
+             No secrets were masked in the output.\n"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use processor::{Processor, ProcessorCollection};
+use std::fmt;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::{DefaultFields, Format, Writer};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+/// A `fmt` layer that masks every event before writing it.
+pub type MaskingLayer<S> = tracing_subscriber::fmt::Layer<S, DefaultFields, MaskingFormat<Format>>;
+
+/// Masks the formatted event, including the fields of its spans, before
+/// passing it to the writer.
+///
+/// Events are formatted without colors, so escape codes never split a secret.
+pub struct MaskingFormat<F> {
+    format: F,
+    processor: ProcessorCollection,
+}
+
+impl<F> MaskingFormat<F> {
+    pub fn new(format: F, processor: ProcessorCollection) -> Self {
+        Self { format, processor }
+    }
+}
+
+/// Returns a layer that formats events like the default `fmt` layer and masks
+/// them with `processor`.
+///
+/// # Example
+///
+/// ```rust
+/// use processor::{MaskerEqual, ProcessorCollection, ProcessorItem};
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let processor = ProcessorCollection::new(vec![ProcessorItem::Equal(MaskerEqual::new(
+///     vec!["s3cr3t"],
+///     "****",
+/// ))]);
+/// let subscriber = tracing_subscriber::registry().with(util::masking_layer(processor));
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::info!(token = "s3cr3t", "Token s3cr3t");
+/// });
+/// ```
+pub fn masking_layer<S>(processor: ProcessorCollection) -> MaskingLayer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .event_format(MaskingFormat::new(Format::default(), processor))
+}
+
+impl<S, N, F> FormatEvent<S, N> for MaskingFormat<F>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+    F: FormatEvent<S, N>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut line = String::new();
+        self.format
+            .format_event(ctx, Writer::new(&mut line), event)?;
+        writer.write_str(&self.processor.process(&line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Buffer;
+    use processor::{MaskerEqual, ProcessorItem};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_masking_layer() {
+        let buffer = Buffer::default();
+        let processor = ProcessorCollection::new(vec![ProcessorItem::Equal(MaskerEqual::new(
+            vec!["s3cr3t"],
+            "****",
+        ))]);
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry()
+            .with(masking_layer(processor).with_writer(move || writer.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            let _step = tracing::info_span!("step", user = "admin:s3cr3t").entered();
+            tracing::info!(token = "s3cr3t", attempt = 2, "Login with {}", "s3cr3t");
+        });
+
+        let line = buffer.contents();
+        assert!(!line.contains("s3cr3t"));
+        assert!(line.contains("step{user=\"admin:****\"}"));
+        assert!(line.contains("Login with **** token=\"****\" attempt=2"));
+    }
+}